@@ -2,7 +2,7 @@ use crate::{line::tab_separator, LinePart};
 use ansi_term::{ANSIString, ANSIStrings};
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
-use zellij_tile_utils::style;
+use zellij_tile_utils::{contrasting_foreground, style};
 
 fn cursors(focused_clients: &[ClientId], palette: Palette) -> (Vec<ANSIString>, usize) {
     // cursor section, text length
@@ -42,10 +42,12 @@ pub fn render_tab(
         ThemeHue::Dark => palette.black,
         ThemeHue::Light => palette.white,
     };
+    // the tab's text is drawn on its background, the separators blend into the bar's background
+    let text_color = contrasting_foreground(background_color, &palette);
     let left_separator = style!(foreground_color, background_color).paint(separator);
     let mut tab_text_len = text.width() + (separator_width * 2) + 2; // + 2 for padding
 
-    let tab_styled_text = style!(foreground_color, background_color)
+    let tab_styled_text = style!(text_color, background_color)
         .bold()
         .paint(format!(" {} ", text));
 
@@ -54,12 +56,12 @@ pub fn render_tab(
         let (cursor_section, extra_length) = cursors(focused_clients, palette);
         tab_text_len += extra_length;
         let mut s = String::new();
-        let cursor_beginning = style!(foreground_color, background_color)
+        let cursor_beginning = style!(text_color, background_color)
             .bold()
             .paint("[")
             .to_string();
         let cursor_section = ANSIStrings(&cursor_section).to_string();
-        let cursor_end = style!(foreground_color, background_color)
+        let cursor_end = style!(text_color, background_color)
             .bold()
             .paint("]")
             .to_string();
@@ -48,6 +48,7 @@ impl ZellijPlugin for State {
             PermissionType::RunCommands,
             PermissionType::OpenTerminalsOrPlugins,
             PermissionType::WriteToStdin,
+            PermissionType::MessageOtherPlugins,
         ]);
         self.configuration = configuration;
         subscribe(&[
@@ -240,6 +241,14 @@ impl ZellijPlugin for State {
                 Key::Ctrl('1') => {
                     request_permission(&[PermissionType::ReadApplicationState]);
                 },
+                Key::Ctrl('2') => {
+                    if let Some(pipe_to) = self.configuration.get("pipe_to") {
+                        pipe_message_to_plugin(
+                            MessageToPlugin::new(pipe_to, "ping")
+                                .with_payload("gimme_back_my_payload"),
+                        );
+                    }
+                },
                 _ => {},
             },
             Event::CustomMessage(message, payload) => {
@@ -272,4 +281,19 @@ impl ZellijPlugin for State {
             );
         }
     }
+    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        if pipe_message.name == "ping" {
+            go_to_tab_name(&format!(
+                "pipe message: {:?}",
+                pipe_message.payload.unwrap_or_default()
+            ));
+        } else if let PipeSource::Cli = pipe_message.source {
+            match pipe_message.payload.as_deref() {
+                Some("block") => block_cli_pipe_input(&pipe_message.name),
+                Some("unblock") => unblock_cli_pipe_input(&pipe_message.name),
+                _ => {},
+            }
+        }
+        false
+    }
 }
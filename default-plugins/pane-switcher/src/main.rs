@@ -0,0 +1,131 @@
+mod pane_list;
+use zellij_tile::prelude::*;
+
+use std::collections::BTreeMap;
+
+use pane_list::PaneList;
+
+#[derive(Default)]
+struct State {
+    own_plugin_id: Option<u32>,
+    tabs: Vec<TabInfo>,
+    pane_manifest: PaneManifest,
+    pane_list: PaneList,
+    search_term: String,
+}
+
+register_plugin!(State);
+
+impl ZellijPlugin for State {
+    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+        self.own_plugin_id = Some(get_plugin_ids().plugin_id);
+        subscribe(&[EventType::TabUpdate, EventType::PaneUpdate, EventType::Key]);
+    }
+
+    fn update(&mut self, event: Event) -> bool {
+        let mut should_render = false;
+        match event {
+            Event::TabUpdate(tabs) => {
+                self.tabs = tabs;
+                self.update_pane_list();
+                should_render = true;
+            },
+            Event::PaneUpdate(pane_manifest) => {
+                self.pane_manifest = pane_manifest;
+                self.update_pane_list();
+                should_render = true;
+            },
+            Event::Key(key) => {
+                should_render = self.handle_key(key);
+            },
+            _ => (),
+        };
+        should_render
+    }
+
+    fn render(&mut self, rows: usize, cols: usize) {
+        let search_line = format!("Search: {}_", self.search_term);
+        print_nested_list_with_coordinates(
+            vec![NestedListItem::new(search_line).color_range(2, 0..7)],
+            Coordinates::new(0, 0, Some(cols), Some(1)),
+        );
+        let room_for_list = rows.saturating_sub(3); // search line, its margin and controls
+        let list = self.pane_list.render(room_for_list);
+        if list.is_empty() && self.pane_list.is_searching() {
+            print_nested_list_with_coordinates(
+                vec![NestedListItem::new("No matching panes")],
+                Coordinates::new(0, 2, Some(cols), Some(1)),
+            );
+        } else {
+            print_nested_list_with_coordinates(
+                list,
+                Coordinates::new(0, 2, Some(cols), Some(room_for_list)),
+            );
+        }
+        let controls = "<↓↑> Navigate, <ENTER> Focus, <Ctrl c> Clear search, <ESC> Hide";
+        let mut controls_item = NestedListItem::new(controls);
+        for key in ["<↓↑>", "<ENTER>", "<Ctrl c>", "<ESC>"] {
+            if let Some(byte_index) = controls.find(key) {
+                let start = controls[..byte_index].chars().count();
+                controls_item = controls_item.color_range(3, start..start + key.chars().count());
+            }
+        }
+        print_nested_list_with_coordinates(
+            vec![controls_item],
+            Coordinates::new(0, rows.saturating_sub(1), Some(cols), Some(1)),
+        );
+    }
+}
+
+impl State {
+    fn update_pane_list(&mut self) {
+        self.pane_list.set_panes(
+            &self.tabs,
+            &self.pane_manifest,
+            self.own_plugin_id.unwrap_or_default(),
+        );
+    }
+    fn handle_key(&mut self, key: Key) -> bool {
+        let mut should_render = false;
+        if let Key::Down = key {
+            self.pane_list.move_selection_down();
+            should_render = true;
+        } else if let Key::Up = key {
+            self.pane_list.move_selection_up();
+            should_render = true;
+        } else if let Key::Char(character) = key {
+            if character == '\n' {
+                self.handle_selection();
+            } else {
+                self.search_term.push(character);
+                self.pane_list.update_search_term(&self.search_term);
+            }
+            should_render = true;
+        } else if let Key::Backspace = key {
+            self.search_term.pop();
+            self.pane_list.update_search_term(&self.search_term);
+            should_render = true;
+        } else if let Key::Ctrl('c') = key {
+            if !self.search_term.is_empty() {
+                self.search_term.clear();
+                self.pane_list.update_search_term(&self.search_term);
+            } else {
+                self.pane_list.reset_selected_index();
+                hide_self();
+            }
+            should_render = true;
+        } else if let Key::Esc = key {
+            hide_self();
+        }
+        should_render
+    }
+    fn handle_selection(&mut self) {
+        if let Some(selected) = self.pane_list.get_selected() {
+            focus_pane_with_id(selected.pane_id, selected.is_plugin, true);
+        }
+        self.search_term.clear();
+        self.pane_list.update_search_term(&self.search_term);
+        self.pane_list.reset_selected_index();
+        hide_self();
+    }
+}
@@ -0,0 +1,183 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+
+use zellij_tile::prelude::*;
+
+/// A pane that can be switched to, along with the tab it is in
+#[derive(Debug, Clone)]
+pub struct PaneEntry {
+    pub pane_id: u32,
+    pub is_plugin: bool,
+    pub tab_position: usize,
+    pub tab_name: String,
+    pub label: String, // the pane title, followed by its command if it has one
+}
+
+impl PaneEntry {
+    pub fn new(pane_info: &PaneInfo, tab_info: &TabInfo) -> Self {
+        let command = pane_info
+            .terminal_command
+            .as_ref()
+            .or(pane_info.foreground_process.as_ref())
+            .filter(|command| **command != pane_info.title);
+        let label = match command {
+            Some(command) => format!("{} ({})", pane_info.title, command),
+            None => pane_info.title.clone(),
+        };
+        PaneEntry {
+            pane_id: pane_info.id,
+            is_plugin: pane_info.is_plugin,
+            tab_position: tab_info.position,
+            tab_name: tab_info.name.clone(),
+            label,
+        }
+    }
+    /// What the search term is matched against, and how the entry is shown while searching (when
+    /// entries are no longer grouped by their tabs)
+    fn search_line(&self) -> String {
+        format!("{} [{}]", self.label, self.tab_name)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct SearchResult {
+    entry_index: usize,
+    score: i64,
+    indices: Vec<usize>,
+}
+
+#[derive(Debug, Default)]
+pub struct PaneList {
+    entries: Vec<PaneEntry>,
+    search_results: Vec<SearchResult>,
+    search_term: String,
+    selected_index: usize,
+}
+
+impl PaneList {
+    /// Lists the selectable panes of all tabs (by tab position, in the order they appear in the
+    /// manifest), except for the pane with `own_plugin_id`
+    pub fn set_panes(
+        &mut self,
+        tabs: &[TabInfo],
+        pane_manifest: &PaneManifest,
+        own_plugin_id: u32,
+    ) {
+        let mut tabs = tabs.to_vec();
+        tabs.sort_by_key(|tab| tab.position);
+        self.entries = tabs
+            .iter()
+            .flat_map(|tab| {
+                pane_manifest
+                    .panes
+                    .get(&tab.position)
+                    .into_iter()
+                    .flatten()
+                    .filter(|pane| pane.is_selectable)
+                    .filter(move |pane| !(pane.is_plugin && pane.id == own_plugin_id))
+                    .map(move |pane| PaneEntry::new(pane, tab))
+            })
+            .collect();
+        let search_term = std::mem::take(&mut self.search_term);
+        self.update_search_term(&search_term);
+    }
+    pub fn update_search_term(&mut self, search_term: &str) {
+        let matcher = SkimMatcherV2::default().use_cache(true);
+        let mut search_results: Vec<SearchResult> = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter_map(|(entry_index, entry)| {
+                matcher
+                    .fuzzy_indices(&entry.search_line(), search_term)
+                    .map(|(score, indices)| SearchResult {
+                        entry_index,
+                        score,
+                        indices,
+                    })
+            })
+            .collect();
+        search_results.sort_by(|a, b| b.score.cmp(&a.score));
+        if search_term != self.search_term {
+            self.selected_index = 0;
+        }
+        self.search_results = search_results;
+        self.search_term = search_term.to_owned();
+        self.selected_index = self
+            .selected_index
+            .min(self.visible_len().saturating_sub(1));
+    }
+    pub fn is_searching(&self) -> bool {
+        !self.search_term.is_empty()
+    }
+    fn visible_len(&self) -> usize {
+        if self.is_searching() {
+            self.search_results.len()
+        } else {
+            self.entries.len()
+        }
+    }
+    pub fn move_selection_down(&mut self) {
+        let visible_len = self.visible_len();
+        if visible_len > 0 {
+            self.selected_index = (self.selected_index + 1) % visible_len;
+        }
+    }
+    pub fn move_selection_up(&mut self) {
+        let visible_len = self.visible_len();
+        if visible_len > 0 {
+            self.selected_index = (self.selected_index + visible_len - 1) % visible_len;
+        }
+    }
+    pub fn reset_selected_index(&mut self) {
+        self.selected_index = 0;
+    }
+    pub fn get_selected(&self) -> Option<&PaneEntry> {
+        if self.is_searching() {
+            self.search_results
+                .get(self.selected_index)
+                .and_then(|result| self.entries.get(result.entry_index))
+        } else {
+            self.entries.get(self.selected_index)
+        }
+    }
+    /// The list items to print in `max_rows` rows, scrolled so that the selected pane is visible.
+    /// Panes are grouped under their tab names, or listed by their match score while searching.
+    pub fn render(&self, max_rows: usize) -> Vec<NestedListItem> {
+        let mut items = vec![];
+        let mut selected_line = 0;
+        if self.is_searching() {
+            for (index, result) in self.search_results.iter().enumerate() {
+                let entry = &self.entries[result.entry_index];
+                let mut item = NestedListItem::new(entry.search_line());
+                for character_index in &result.indices {
+                    item = item.color_range(0, *character_index..*character_index + 1);
+                }
+                if index == self.selected_index {
+                    selected_line = items.len();
+                    item = item.selected();
+                }
+                items.push(item);
+            }
+        } else {
+            let mut current_tab_position = None;
+            for (index, entry) in self.entries.iter().enumerate() {
+                if current_tab_position != Some(entry.tab_position) {
+                    current_tab_position = Some(entry.tab_position);
+                    let tab_name_len = entry.tab_name.chars().count();
+                    items.push(
+                        NestedListItem::new(entry.tab_name.clone()).color_range(2, 0..tab_name_len),
+                    );
+                }
+                let mut item = NestedListItem::new(entry.label.clone()).indent(1);
+                if index == self.selected_index {
+                    selected_line = items.len();
+                    item = item.selected();
+                }
+                items.push(item);
+            }
+        }
+        let first_line = (selected_line + 1).saturating_sub(max_rows);
+        items.into_iter().skip(first_line).take(max_rows).collect()
+    }
+}
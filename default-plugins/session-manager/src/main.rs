@@ -1,22 +1,35 @@
+mod resurrectable_sessions;
 mod session_list;
 mod ui;
 use zellij_tile::prelude::*;
 
 use std::collections::BTreeMap;
+use std::time::Duration;
 
 use ui::{
-    components::{render_controls_line, render_new_session_line, render_prompt, Colors},
+    components::{
+        render_controls_line, render_new_session_line, render_prompt, render_rename_session_line,
+        render_session_actions_line, Colors,
+    },
     SessionUiInfo,
 };
 
+use resurrectable_sessions::ResurrectableSessions;
 use session_list::SessionList;
 
+// the first one is the layout this session was started with
+const NEW_SESSION_LAYOUTS: &[&str] = &["default", "compact", "strider", "disable-status-bar"];
+
 #[derive(Default)]
 struct State {
     session_name: Option<String>,
     sessions: SessionList,
+    resurrectable_sessions: ResurrectableSessions,
+    browsing_resurrectable_sessions: bool,
     search_term: String,
     new_session_name: Option<String>,
+    new_session_layout_index: usize,
+    renaming_session_name: Option<String>,
     colors: Colors,
 }
 
@@ -44,8 +57,9 @@ impl ZellijPlugin for State {
             Event::PermissionRequestResult(_result) => {
                 should_render = true;
             },
-            Event::SessionUpdate(session_infos) => {
+            Event::SessionUpdate(session_infos, resurrectable_sessions) => {
                 self.update_session_infos(session_infos);
+                self.update_resurrectable_sessions(resurrectable_sessions);
                 should_render = true;
             },
             _ => (),
@@ -55,62 +69,117 @@ impl ZellijPlugin for State {
 
     fn render(&mut self, rows: usize, cols: usize) {
         render_prompt(
-            self.new_session_name.is_some(),
+            self.new_session_name.is_some() || self.renaming_session_name.is_some(),
             &self.search_term,
             self.colors,
         );
         let room_for_list = rows.saturating_sub(5); // search line and controls
-        self.sessions.update_rows(room_for_list);
-        let list = self
-            .sessions
-            .render(room_for_list, cols.saturating_sub(7), self.colors); // 7 for various ui
+        let list = if self.browsing_resurrectable_sessions {
+            self.resurrectable_sessions
+                .render(room_for_list, cols.saturating_sub(7), self.colors) // 7 for various ui
+        } else {
+            self.sessions.update_rows(room_for_list);
+            self.sessions
+                .render(room_for_list, cols.saturating_sub(7), self.colors) // 7 for various ui
+        };
         for line in list {
             println!("{}", line.render());
         }
-        render_new_session_line(
-            &self.new_session_name,
-            self.sessions.is_searching,
+        if self.renaming_session_name.is_some() {
+            render_rename_session_line(&self.renaming_session_name, self.colors);
+        } else {
+            render_new_session_line(
+                &self.new_session_name,
+                NEW_SESSION_LAYOUTS[self.new_session_layout_index],
+                self.is_searching(),
+                self.colors,
+            );
+        }
+        render_session_actions_line(
+            self.browsing_resurrectable_sessions,
+            rows.saturating_sub(1),
+            cols,
+            self.colors,
+        );
+        render_controls_line(
+            self.is_searching(),
+            self.browsing_resurrectable_sessions,
+            rows,
+            cols,
             self.colors,
         );
-        render_controls_line(self.sessions.is_searching, rows, cols, self.colors);
     }
 }
 
 impl State {
     fn reset_selected_index(&mut self) {
         self.sessions.reset_selected_index();
+        self.resurrectable_sessions.reset_selected_index();
+    }
+    fn is_typing_session_name(&self) -> bool {
+        self.new_session_name.is_some() || self.renaming_session_name.is_some()
+    }
+    fn is_searching(&self) -> bool {
+        if self.browsing_resurrectable_sessions {
+            self.resurrectable_sessions.is_searching
+        } else {
+            self.sessions.is_searching
+        }
+    }
+    fn update_search_term(&mut self) {
+        self.sessions
+            .update_search_term(&self.search_term, &self.colors);
+        self.resurrectable_sessions
+            .update_search_term(&self.search_term);
     }
     fn handle_key(&mut self, key: Key) -> bool {
         let mut should_render = false;
         if let Key::Right = key {
-            if self.new_session_name.is_none() {
+            if !self.is_typing_session_name() && !self.browsing_resurrectable_sessions {
                 self.sessions.result_expand();
             }
             should_render = true;
         } else if let Key::Left = key {
-            if self.new_session_name.is_none() {
+            if !self.is_typing_session_name() && !self.browsing_resurrectable_sessions {
                 self.sessions.result_shrink();
             }
             should_render = true;
         } else if let Key::Down = key {
-            if self.new_session_name.is_none() {
+            if self.is_typing_session_name() {
+                // no-op
+            } else if self.browsing_resurrectable_sessions {
+                self.resurrectable_sessions.move_selection_down();
+            } else {
                 self.sessions.move_selection_down();
             }
             should_render = true;
         } else if let Key::Up = key {
-            if self.new_session_name.is_none() {
+            if self.is_typing_session_name() {
+                // no-op
+            } else if self.browsing_resurrectable_sessions {
+                self.resurrectable_sessions.move_selection_up();
+            } else {
                 self.sessions.move_selection_up();
             }
             should_render = true;
+        } else if let Key::Char('\t') = key {
+            if self.new_session_name.is_some() {
+                self.new_session_layout_index =
+                    (self.new_session_layout_index + 1) % NEW_SESSION_LAYOUTS.len();
+            } else if self.renaming_session_name.is_none() {
+                self.browsing_resurrectable_sessions = !self.browsing_resurrectable_sessions;
+            }
+            should_render = true;
         } else if let Key::Char(character) = key {
             if character == '\n' {
                 self.handle_selection();
             } else if let Some(new_session_name) = self.new_session_name.as_mut() {
                 new_session_name.push(character);
+            } else if let Some(renaming_session_name) = self.renaming_session_name.as_mut() {
+                renaming_session_name.push(character);
             } else {
                 self.search_term.push(character);
-                self.sessions
-                    .update_search_term(&self.search_term, &self.colors);
+                self.update_search_term();
             }
             should_render = true;
         } else if let Key::Backspace = key {
@@ -120,19 +189,56 @@ impl State {
                 } else {
                     new_session_name.pop();
                 }
+            } else if let Some(renaming_session_name) = self.renaming_session_name.as_mut() {
+                if renaming_session_name.is_empty() {
+                    self.renaming_session_name = None;
+                } else {
+                    renaming_session_name.pop();
+                }
             } else {
                 self.search_term.pop();
-                self.sessions
-                    .update_search_term(&self.search_term, &self.colors);
+                self.update_search_term();
             }
             should_render = true;
         } else if let Key::Ctrl('w') = key {
-            if self.sessions.is_searching {
+            if self.is_searching() || self.renaming_session_name.is_some() {
                 // no-op
             } else if self.new_session_name.is_some() {
                 self.new_session_name = None;
             } else {
                 self.new_session_name = Some(String::new());
+                self.new_session_layout_index = 0;
+            }
+            should_render = true;
+        } else if let Key::Ctrl('r') = key {
+            if self.is_searching() || self.new_session_name.is_some() {
+                // no-op
+            } else if self.renaming_session_name.is_some() {
+                self.renaming_session_name = None;
+            } else {
+                self.renaming_session_name = Some(String::new());
+            }
+            should_render = true;
+        } else if let Key::Ctrl('x') = key {
+            if !self.is_typing_session_name()
+                && !self.browsing_resurrectable_sessions
+                && !self.sessions.selected_is_current_session()
+            {
+                if let Some(selected_session_name) = self.sessions.get_selected_session_name() {
+                    kill_sessions(&[selected_session_name]);
+                    self.reset_selected_index();
+                }
+            }
+            should_render = true;
+        } else if let Key::Ctrl('d') = key {
+            if !self.is_typing_session_name() && self.browsing_resurrectable_sessions {
+                if let Some(selected_session_name) =
+                    self.resurrectable_sessions.get_selected_session_name()
+                {
+                    delete_dead_session(&selected_session_name);
+                    self.resurrectable_sessions
+                        .remove_session(&selected_session_name);
+                }
             }
             should_render = true;
         } else if let Key::Ctrl('c') = key {
@@ -142,10 +248,15 @@ impl State {
                 } else {
                     new_session_name.clear()
                 }
+            } else if let Some(renaming_session_name) = self.renaming_session_name.as_mut() {
+                if renaming_session_name.is_empty() {
+                    self.renaming_session_name = None;
+                } else {
+                    renaming_session_name.clear()
+                }
             } else if !self.search_term.is_empty() {
                 self.search_term.clear();
-                self.sessions
-                    .update_search_term(&self.search_term, &self.colors);
+                self.update_search_term();
                 self.reset_selected_index();
             } else {
                 self.reset_selected_index();
@@ -153,19 +264,35 @@ impl State {
             }
             should_render = true;
         } else if let Key::Esc = key {
+            self.renaming_session_name = None;
             hide_self();
         }
         should_render
     }
     fn handle_selection(&mut self) {
         if let Some(new_session_name) = &self.new_session_name {
-            if new_session_name.is_empty() {
-                switch_session(None);
-            } else if self.session_name.as_ref() == Some(new_session_name) {
+            let new_session_name = Some(new_session_name.as_str()).filter(|n| !n.is_empty());
+            if new_session_name.is_some() && self.session_name.as_deref() == new_session_name {
                 // noop - we're already here!
                 self.new_session_name = None;
+            } else if self.new_session_layout_index == 0 {
+                switch_session(new_session_name);
             } else {
-                switch_session(Some(new_session_name));
+                switch_session_with_layout(
+                    new_session_name,
+                    NEW_SESSION_LAYOUTS[self.new_session_layout_index],
+                );
+            }
+        } else if let Some(renaming_session_name) = self.renaming_session_name.take() {
+            if !renaming_session_name.is_empty() {
+                rename_session(&renaming_session_name);
+            }
+        } else if self.browsing_resurrectable_sessions {
+            if let Some(selected_session_name) =
+                self.resurrectable_sessions.get_selected_session_name()
+            {
+                // sessions with a serialized layout are resurrected when switched to
+                switch_session(Some(&selected_session_name));
             }
         } else if let Some(selected_session_name) = self.sessions.get_selected_session_name() {
             let selected_tab = self.sessions.get_selected_tab_position();
@@ -187,11 +314,14 @@ impl State {
         }
         self.reset_selected_index();
         self.new_session_name = None;
+        self.browsing_resurrectable_sessions = false;
         self.search_term.clear();
-        self.sessions
-            .update_search_term(&self.search_term, &self.colors);
+        self.update_search_term();
         hide_self();
     }
+    fn update_resurrectable_sessions(&mut self, resurrectable_sessions: Vec<(String, Duration)>) {
+        self.resurrectable_sessions.update(resurrectable_sessions);
+    }
     fn update_session_infos(&mut self, session_infos: Vec<SessionInfo>) {
         let session_infos: Vec<SessionUiInfo> = session_infos
             .iter()
@@ -0,0 +1,215 @@
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use std::time::Duration;
+
+use crate::ui::components::{Colors, LineToRender, SpanStyle, TruncatableUiSpan};
+
+#[derive(Debug, Default)]
+pub struct ResurrectableSessions {
+    pub all_resurrectable_sessions: Vec<(String, Duration)>, // session name, time since it was last serialized
+    pub selected_index: Option<usize>,
+    pub selected_search_index: Option<usize>,
+    pub search_results: Vec<SearchResult>,
+    pub is_searching: bool,
+    search_term: String,
+}
+
+impl ResurrectableSessions {
+    pub fn update(&mut self, resurrectable_sessions: Vec<(String, Duration)>) {
+        self.all_resurrectable_sessions = resurrectable_sessions;
+        if let Some(selected_index) = self.selected_index {
+            if selected_index >= self.all_resurrectable_sessions.len() {
+                self.selected_index = self.all_resurrectable_sessions.len().checked_sub(1);
+            }
+        }
+        if self.is_searching {
+            // keep the search results in sync, this list changes as sessions die or are deleted
+            let search_term = self.search_term.clone();
+            let selected_search_index = self.selected_search_index;
+            self.update_search_term(&search_term);
+            self.selected_search_index =
+                selected_search_index.filter(|i| *i < self.search_results.len());
+        }
+    }
+    pub fn update_search_term(&mut self, search_term: &str) {
+        let matcher = SkimMatcherV2::default().use_cache(true);
+        let mut matches = vec![];
+        for (session_name, age) in &self.all_resurrectable_sessions {
+            if let Some((score, indices)) = matcher.fuzzy_indices(session_name, search_term) {
+                matches.push(SearchResult {
+                    score,
+                    indices,
+                    session_name: session_name.clone(),
+                    age: *age,
+                });
+            }
+        }
+        matches.sort_by(|a, b| b.score.cmp(&a.score));
+        self.search_results = matches;
+        self.search_term = search_term.to_owned();
+        self.is_searching = !search_term.is_empty();
+        self.selected_search_index = Some(0);
+    }
+    pub fn render(&self, max_rows: usize, max_cols: usize, colors: Colors) -> Vec<LineToRender> {
+        if self.all_resurrectable_sessions.is_empty() {
+            let mut line_to_render = LineToRender::new(colors);
+            line_to_render.append(&format!(" {}", colors.bold("No resurrectable sessions")));
+            return vec![line_to_render];
+        }
+        let (sessions, selected_index): (Vec<(&String, &Duration, Option<&Vec<usize>>)>, _) =
+            if self.is_searching {
+                (
+                    self.search_results
+                        .iter()
+                        .map(|s| (&s.session_name, &s.age, Some(&s.indices)))
+                        .collect(),
+                    self.selected_search_index,
+                )
+            } else {
+                (
+                    self.all_resurrectable_sessions
+                        .iter()
+                        .map(|(name, age)| (name, age, None))
+                        .collect(),
+                    self.selected_index,
+                )
+            };
+        // scroll the list so that the selected session is always visible
+        let first_visible_index = selected_index
+            .map(|i| (i + 1).saturating_sub(max_rows))
+            .unwrap_or(0);
+        sessions
+            .iter()
+            .enumerate()
+            .skip(first_visible_index)
+            .take(max_rows)
+            .map(|(i, (session_name, age, indices))| {
+                let mut line_to_render =
+                    render_resurrectable_session(session_name, age, *indices, max_cols, colors);
+                if Some(i) == selected_index {
+                    line_to_render.make_selected();
+                }
+                line_to_render
+            })
+            .collect()
+    }
+    pub fn move_selection_down(&mut self) {
+        if self.is_searching {
+            if let Some(selected_search_index) = self.selected_search_index.as_mut() {
+                if *selected_search_index + 1 < self.search_results.len() {
+                    *selected_search_index += 1;
+                }
+            } else if !self.search_results.is_empty() {
+                self.selected_search_index = Some(0);
+            }
+        } else {
+            match self.selected_index {
+                Some(selected_index)
+                    if selected_index + 1 < self.all_resurrectable_sessions.len() =>
+                {
+                    self.selected_index = Some(selected_index + 1);
+                },
+                Some(_) => self.selected_index = None,
+                None if !self.all_resurrectable_sessions.is_empty() => {
+                    self.selected_index = Some(0);
+                },
+                None => {},
+            }
+        }
+    }
+    pub fn move_selection_up(&mut self) {
+        if self.is_searching {
+            if let Some(selected_search_index) = self.selected_search_index.as_mut() {
+                *selected_search_index = selected_search_index.saturating_sub(1);
+            } else if !self.search_results.is_empty() {
+                self.selected_search_index = Some(0);
+            }
+        } else {
+            match self.selected_index {
+                Some(0) => self.selected_index = None,
+                Some(selected_index) => self.selected_index = Some(selected_index - 1),
+                None => {
+                    self.selected_index = self.all_resurrectable_sessions.len().checked_sub(1);
+                },
+            }
+        }
+    }
+    pub fn get_selected_session_name(&self) -> Option<String> {
+        if self.is_searching {
+            self.selected_search_index
+                .and_then(|i| self.search_results.get(i))
+                .map(|s| s.session_name.clone())
+        } else {
+            self.selected_index
+                .and_then(|i| self.all_resurrectable_sessions.get(i))
+                .map(|(session_name, _age)| session_name.clone())
+        }
+    }
+    pub fn remove_session(&mut self, session_name: &str) {
+        // the host will stop reporting this session soon, we remove it right away so that the UI
+        // does not lag behind
+        let remaining_sessions = self
+            .all_resurrectable_sessions
+            .drain(..)
+            .filter(|(name, _age)| name != session_name)
+            .collect();
+        self.update(remaining_sessions);
+    }
+    pub fn reset_selected_index(&mut self) {
+        self.selected_index = None;
+        self.selected_search_index = None;
+    }
+}
+
+#[derive(Debug)]
+pub struct SearchResult {
+    score: i64,
+    indices: Vec<usize>,
+    session_name: String,
+    age: Duration,
+}
+
+fn render_resurrectable_session(
+    session_name: &str,
+    age: &Duration,
+    indices: Option<&Vec<usize>>,
+    max_cols: usize,
+    colors: Colors,
+) -> LineToRender {
+    let mut line_to_render = LineToRender::new(colors);
+    let mut remaining_cols = max_cols;
+    line_to_render.append(" > ");
+    remaining_cols = remaining_cols.saturating_sub(3);
+    TruncatableUiSpan::new(
+        session_name.to_owned(),
+        SpanStyle::ForegroundBold(colors.palette.orange),
+    )
+    .render(
+        indices.map(|i| (SpanStyle::ForegroundBold(colors.palette.magenta), i.clone())),
+        &mut line_to_render,
+        &mut remaining_cols,
+    );
+    let age = format!(" (last seen {} ago)", format_duration(age));
+    if age.chars().count() <= remaining_cols {
+        line_to_render.append(&age);
+    }
+    line_to_render
+}
+
+fn format_duration(duration: &Duration) -> String {
+    let seconds = duration.as_secs();
+    let (count, unit) = if seconds < 60 {
+        (seconds, "second")
+    } else if seconds < 60 * 60 {
+        (seconds / 60, "minute")
+    } else if seconds < 60 * 60 * 24 {
+        (seconds / (60 * 60), "hour")
+    } else {
+        (seconds / (60 * 60 * 24), "day")
+    };
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
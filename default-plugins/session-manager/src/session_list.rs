@@ -23,7 +23,15 @@ impl SessionList {
             } else if b.is_current_session {
                 std::cmp::Ordering::Greater
             } else {
-                a.name.cmp(&b.name)
+                // keep sessions of the same group together, ungrouped sessions last
+                match (&a.group, &b.group) {
+                    (Some(a_group), Some(b_group)) => {
+                        a_group.cmp(b_group).then_with(|| a.name.cmp(&b.name))
+                    },
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => a.name.cmp(&b.name),
+                }
             }
         });
         self.session_ui_infos = session_ui_infos;
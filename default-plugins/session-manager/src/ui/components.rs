@@ -376,6 +376,17 @@ pub fn build_session_ui_line(session_ui_info: &SessionUiInfo, colors: Colors) ->
     ]));
     ui_spans.push(session_bullet_span);
     ui_spans.push(session_name_span);
+    if let Some(group) = &session_ui_info.group {
+        let group_styled = colors.magenta(group);
+        let group_span = UiSpan::UiSpanTelescope(UiSpanTelescope::new(vec![
+            StringAndLength::new(
+                format!(" <group: {group_styled}>"),
+                2 + 7 + group.width() + 1,
+            ),
+            StringAndLength::new(format!(" <{group_styled}>"), 2 + group.width() + 1),
+        ]));
+        ui_spans.push(group_span);
+    }
     ui_spans.push(tab_and_pane_count);
     ui_spans.push(connected_users_count);
     if session_ui_info.is_current_session {
@@ -484,7 +495,12 @@ pub fn render_prompt(typing_session_name: bool, search_term: &str, colors: Color
     }
 }
 
-pub fn render_new_session_line(session_name: &Option<String>, is_searching: bool, colors: Colors) {
+pub fn render_new_session_line(
+    session_name: &Option<String>,
+    layout_name: &str,
+    is_searching: bool,
+    colors: Colors,
+) {
     if is_searching {
         return;
     }
@@ -492,13 +508,17 @@ pub fn render_new_session_line(session_name: &Option<String>, is_searching: bool
     let new_session_shortcut = colors.magenta(new_session_shortcut_text);
     let new_session = colors.bold("New session");
     let enter = colors.magenta("<ENTER>");
+    let tab = colors.magenta("<TAB>");
     match session_name {
         Some(session_name) => {
             println!(
-                "\u{1b}[m > {}_ ({}, {} when done)",
+                "\u{1b}[m > {}_ ({}, {} when done) [{}: {}, {} to change]",
                 colors.orange(session_name),
                 colors.bold("Type optional name"),
-                enter
+                enter,
+                colors.bold("Layout"),
+                colors.cyan(layout_name),
+                tab
             );
         },
         None => {
@@ -507,14 +527,75 @@ pub fn render_new_session_line(session_name: &Option<String>, is_searching: bool
     }
 }
 
-pub fn render_controls_line(is_searching: bool, row: usize, max_cols: usize, colors: Colors) {
-    let (arrows, navigate) = if is_searching {
+pub fn render_rename_session_line(session_name: &Option<String>, colors: Colors) {
+    if let Some(session_name) = session_name {
+        let enter = colors.magenta("<ENTER>");
+        println!(
+            "\u{1b}[m > {}_ ({}, {} when done)",
+            colors.orange(session_name),
+            colors.bold("Type new name for the current session"),
+            enter
+        );
+    }
+}
+
+pub fn render_session_actions_line(
+    browsing_resurrectable_sessions: bool,
+    row: usize,
+    max_cols: usize,
+    colors: Colors,
+) {
+    let tab = colors.magenta("<TAB>");
+    if browsing_resurrectable_sessions {
+        let ctrl_d = colors.magenta("<Ctrl d>");
+        let delete = colors.bold("Delete selected");
+        let running_sessions = colors.bold("Running sessions");
+        if max_cols >= 53 {
+            print!("\u{1b}[m\u{1b}[{row}H{ctrl_d} - {delete}, {tab} - {running_sessions}");
+        } else if max_cols >= 20 {
+            print!("\u{1b}[m\u{1b}[{row}H{ctrl_d}/{tab}");
+        }
+    } else {
+        let ctrl_x = colors.magenta("<Ctrl x>");
+        let kill = colors.bold("Kill selected");
+        let ctrl_r = colors.magenta("<Ctrl r>");
+        let rename = colors.bold("Rename current");
+        let resurrectable_sessions = colors.bold("Resurrectable sessions");
+        if max_cols >= 90 {
+            print!(
+                "\u{1b}[m\u{1b}[{row}H{ctrl_x} - {kill}, {ctrl_r} - {rename}, {tab} - {resurrectable_sessions}"
+            );
+        } else if max_cols >= 57 {
+            let kill = colors.bold("Kill");
+            let rename = colors.bold("Rename");
+            let resurrectable_sessions = colors.bold("Resurrect");
+            print!(
+                "\u{1b}[m\u{1b}[{row}H{ctrl_x} - {kill}, {ctrl_r} - {rename}, {tab} - {resurrectable_sessions}"
+            );
+        } else if max_cols >= 30 {
+            print!("\u{1b}[m\u{1b}[{row}H{ctrl_x}/{ctrl_r}/{tab}");
+        }
+    }
+}
+
+pub fn render_controls_line(
+    is_searching: bool,
+    browsing_resurrectable_sessions: bool,
+    row: usize,
+    max_cols: usize,
+    colors: Colors,
+) {
+    let (arrows, navigate) = if is_searching || browsing_resurrectable_sessions {
         (colors.magenta("<↓↑>"), colors.bold("Navigate"))
     } else {
         (colors.magenta("<←↓↑→>"), colors.bold("Navigate and Expand"))
     };
     let enter = colors.magenta("<ENTER>");
-    let select = colors.bold("Switch to selected");
+    let select = if browsing_resurrectable_sessions {
+        colors.bold("Resurrect selected")
+    } else {
+        colors.bold("Switch to selected")
+    };
     let esc = colors.magenta("<ESC>");
     let to_hide = colors.bold("Hide");
 
@@ -524,7 +605,11 @@ pub fn render_controls_line(is_searching: bool, row: usize, max_cols: usize, col
         );
     } else if max_cols >= 57 {
         let navigate = colors.bold("Navigate");
-        let select = colors.bold("Switch");
+        let select = if browsing_resurrectable_sessions {
+            colors.bold("Resurrect")
+        } else {
+            colors.bold("Switch")
+        };
         print!(
             "\u{1b}[m\u{1b}[{row}HHelp: {arrows} - {navigate}, {enter} - {select}, {esc} - {to_hide}"
         );
@@ -224,6 +224,7 @@ pub struct SessionUiInfo {
     pub tabs: Vec<TabUiInfo>,
     pub connected_users: usize,
     pub is_current_session: bool,
+    pub group: Option<String>,
 }
 
 impl SessionUiInfo {
@@ -237,6 +238,7 @@ impl SessionUiInfo {
                 .collect(),
             connected_users: session_info.connected_clients,
             is_current_session: session_info.is_current_session,
+            group: session_info.group.clone(),
         }
     }
     pub fn line_count(&self, selected_index: &SelectedIndex) -> usize {
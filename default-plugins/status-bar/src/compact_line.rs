@@ -0,0 +1,205 @@
+//! The single line status-bar, enabled with `compact "true"` and laid out with the
+//! `compact_template` configuration, eg. `compact_template "{mode} {keybinds} {session}"`
+//!
+//! Placeholders: `{mode}` (the current input mode), `{keybinds}` (the keybinding hints of the
+//! current mode, shortened to fit the rest of the line), `{session}` (the session name) and
+//! `{datetime}` (the current date and time in UTC, plugins don't know the local time zone)
+use ansi_term::Style;
+use std::time::{SystemTime, UNIX_EPOCH};
+use zellij_tile::prelude::*;
+
+use crate::{color_elements, LinePart};
+
+pub const DEFAULT_COMPACT_TEMPLATE: &str = "{mode} {keybinds}";
+
+#[derive(Debug, PartialEq)]
+enum Segment<'a> {
+    Literal(&'a str),
+    Mode,
+    Keybinds,
+    Session,
+    Datetime,
+}
+
+/// Anything in braces that is not a known placeholder is left as is
+fn parse_template(template: &str) -> Vec<Segment> {
+    let mut segments = vec![];
+    let mut remaining = template;
+    while let Some(start) = remaining.find('{') {
+        let placeholder = remaining[start..]
+            .find('}')
+            .map(|end| &remaining[start..=start + end]);
+        let segment = match placeholder {
+            Some("{mode}") => Some(Segment::Mode),
+            Some("{keybinds}") => Some(Segment::Keybinds),
+            Some("{session}") => Some(Segment::Session),
+            Some("{datetime}") => Some(Segment::Datetime),
+            _ => None,
+        };
+        match (segment, placeholder) {
+            (Some(segment), Some(placeholder)) => {
+                if start > 0 {
+                    segments.push(Segment::Literal(&remaining[..start]));
+                }
+                segments.push(segment);
+                remaining = &remaining[start + placeholder.len()..];
+            },
+            _ => {
+                segments.push(Segment::Literal(&remaining[..=start]));
+                remaining = &remaining[start + 1..];
+            },
+        }
+    }
+    if !remaining.is_empty() {
+        segments.push(Segment::Literal(remaining));
+    }
+    segments
+}
+
+/// `keybinds` renders the keybinding hints (or whatever the second line of the full status-bar
+/// would show) in the given width, which is what is left of `cols` after the other segments.
+/// Segments that do not fit are left out
+pub fn compact_line(
+    mode_info: &ModeInfo,
+    template: &str,
+    unix_time: u64,
+    cols: usize,
+    keybinds: impl Fn(usize) -> LinePart,
+) -> LinePart {
+    let segments = parse_template(template);
+    let keybinds_count = segments
+        .iter()
+        .filter(|segment| **segment == Segment::Keybinds)
+        .count();
+    let fixed_parts: Vec<Option<LinePart>> = segments
+        .iter()
+        .map(|segment| match segment {
+            Segment::Literal(text) => Some(plain_part(text, Style::new())),
+            Segment::Mode => Some(mode_part(mode_info)),
+            Segment::Session => Some(plain_part(
+                mode_info.session_name.as_deref().unwrap_or_default(),
+                Style::new().bold(),
+            )),
+            Segment::Datetime => Some(plain_part(&format_datetime(unix_time), Style::new())),
+            Segment::Keybinds => None,
+        })
+        .collect();
+    let fixed_len: usize = fixed_parts.iter().flatten().map(|part| part.len).sum();
+    let keybinds_width = cols.saturating_sub(fixed_len) / keybinds_count.max(1);
+    let mut line = LinePart::default();
+    for part in fixed_parts {
+        let part = part.unwrap_or_else(|| keybinds(keybinds_width));
+        if line.len + part.len <= cols {
+            line.append(&part);
+        }
+    }
+    line
+}
+
+fn plain_part(text: &str, style: Style) -> LinePart {
+    LinePart {
+        part: style.paint(text).to_string(),
+        len: text.chars().count(),
+    }
+}
+
+fn mode_part(mode_info: &ModeInfo) -> LinePart {
+    let colored_elements = color_elements(mode_info.style.colors, false);
+    let mode = match (mode_info.mode, &mode_info.custom_mode) {
+        (InputMode::Custom, Some(custom_mode)) => custom_mode.to_uppercase(),
+        (mode, _) => format!("{:?}", mode).to_uppercase(),
+    };
+    let text = format!(" {} ", mode);
+    plain_part(&text, colored_elements.selected.styled_text)
+}
+
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0)
+}
+
+/// `YYYY-MM-DD HH:MM UTC`
+fn format_datetime(unix_time: u64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = unix_time / 86400 + 719468;
+    let era = days / 146097;
+    let day_of_era = days % 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153; // starting from March
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = era * 400 + year_of_era + if month <= 2 { 1 } else { 0 };
+    let seconds_of_day = unix_time % 86400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds_of_day / 3600,
+        seconds_of_day % 3600 / 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hints(width: usize) -> LinePart {
+        let hints = "<p> PANE <t> TAB";
+        let hints = if hints.len() <= width { hints } else { "..." };
+        plain_part(hints, Style::new())
+    }
+
+    fn compact_line_text(template: &str, cols: usize) -> String {
+        let mode_info = ModeInfo {
+            session_name: Some("my-session".to_owned()),
+            ..Default::default()
+        };
+        let line = compact_line(&mode_info, template, 1700000000, cols, hints);
+        let re = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+        re.replace_all(&line.part, "").to_string()
+    }
+
+    #[test]
+    fn template_with_unknown_placeholders_and_stray_braces() {
+        assert_eq!(
+            parse_template("{mode} {foo} {keybinds"),
+            vec![
+                Segment::Mode,
+                Segment::Literal(" {"),
+                Segment::Literal("foo} {"),
+                Segment::Literal("keybinds"),
+            ]
+        );
+    }
+
+    #[test]
+    fn all_segments_on_one_line() {
+        assert_eq!(
+            compact_line_text("{mode} {keybinds} | {session} {datetime}", 80),
+            " NORMAL  <p> PANE <t> TAB | my-session 2023-11-14 22:13 UTC"
+        );
+    }
+
+    #[test]
+    fn keybinds_get_what_is_left_of_the_line() {
+        assert_eq!(
+            compact_line_text("{mode} {keybinds} {session}", 30),
+            " NORMAL  ... my-session"
+        );
+    }
+
+    #[test]
+    fn datetime_at_the_start_of_a_leap_day() {
+        assert_eq!(format_datetime(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_datetime(1709164800), "2024-02-29 00:00 UTC");
+    }
+}
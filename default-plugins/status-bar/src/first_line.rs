@@ -25,6 +25,7 @@ enum KeyAction {
     Session,
     Move,
     Tmux,
+    Custom(String),
 }
 
 enum KeyMode {
@@ -40,7 +41,7 @@ impl KeyShortcut {
     }
 
     pub fn full_text(&self) -> String {
-        match self.action {
+        match &self.action {
             KeyAction::Lock => String::from("LOCK"),
             KeyAction::Pane => String::from("PANE"),
             KeyAction::Tab => String::from("TAB"),
@@ -50,6 +51,7 @@ impl KeyShortcut {
             KeyAction::Session => String::from("SESSION"),
             KeyAction::Move => String::from("MOVE"),
             KeyAction::Tmux => String::from("TMUX"),
+            KeyAction::Custom(custom_mode) => custom_mode.to_uppercase(),
         }
     }
     pub fn letter_shortcut(&self, with_prefix: bool) -> String {
@@ -432,7 +434,7 @@ fn get_key_shortcut_for_mode<'a>(
     mode: &InputMode,
 ) -> Option<&'a mut KeyShortcut> {
     let key_action = match mode {
-        InputMode::Normal | InputMode::Prompt | InputMode::Tmux => return None,
+        InputMode::Normal | InputMode::Prompt | InputMode::Tmux | InputMode::Custom => return None,
         InputMode::Locked => KeyAction::Lock,
         InputMode::Pane | InputMode::RenamePane => KeyAction::Pane,
         InputMode::Tab | InputMode::RenameTab => KeyAction::Tab,
@@ -535,6 +537,15 @@ pub fn first_line(
         ));
     }
 
+    if let (InputMode::Custom, Some(custom_mode)) = (help.mode, &help.custom_mode) {
+        // custom modes have no tile of their own, so the active one is shown like the tmux mode
+        default_keys.push(KeyShortcut::new(
+            KeyMode::Selected,
+            KeyAction::Custom(custom_mode.clone()),
+            to_char(action_key(binds, &[TO_NORMAL])),
+        ));
+    }
+
     let mut key_indicators =
         key_indicators(max_len, &default_keys, colored_elements, separator, help);
     if key_indicators.len < max_len {
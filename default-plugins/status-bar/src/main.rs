@@ -1,6 +1,8 @@
+mod compact_line;
 mod first_line;
 mod second_line;
 mod tip;
+mod widgets;
 
 use ansi_term::{
     ANSIString,
@@ -14,13 +16,15 @@ use zellij_tile::prelude::actions::Action;
 use zellij_tile::prelude::*;
 use zellij_tile_utils::{palette_match, style};
 
+use compact_line::{compact_line, unix_time, DEFAULT_COMPACT_TEMPLATE};
 use first_line::first_line;
 use second_line::{
     floating_panes_are_visible, fullscreen_panes_to_hide, keybinds,
-    locked_floating_panes_are_visible, locked_fullscreen_panes_to_hide, system_clipboard_error,
-    text_copied_hint,
+    locked_floating_panes_are_visible, locked_fullscreen_panes_to_hide, pending_keys_hint,
+    system_clipboard_error, text_copied_hint,
 };
 use tip::utils::get_cached_tip_name;
+use widgets::{append_right_aligned, Widgets, WIDGETS_UPDATE_INTERVAL};
 
 // for more of these, copy paste from: https://en.wikipedia.org/wiki/Box-drawing_character
 static ARROW_SEPARATOR: &str = "";
@@ -35,6 +39,10 @@ struct State {
     mode_info: ModeInfo,
     text_copy_destination: Option<CopyDestination>,
     display_system_clipboard_failure: bool,
+    compact_template: Option<String>, // everything is rendered on one line if set
+    widgets: Option<Widgets>,
+    system_state: Option<SystemState>,
+    pending_keys: Vec<Key>, // the keys typed so far of a key sequence binding
 }
 
 register_plugin!(State);
@@ -177,7 +185,7 @@ fn color_elements(palette: Palette, different_color_alternates: bool) -> Colored
 }
 
 impl ZellijPlugin for State {
-    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
         // TODO: Should be able to choose whether to use the cache through config.
         self.tip_name = get_cached_tip_name();
         set_selectable(false);
@@ -187,7 +195,27 @@ impl ZellijPlugin for State {
             EventType::CopyToClipboard,
             EventType::InputReceived,
             EventType::SystemClipboardFailure,
+            EventType::PendingKeys,
         ]);
+        if configuration.get("compact").map(|s| s.as_str()) == Some("true") {
+            let compact_template = configuration
+                .get("compact_template")
+                .cloned()
+                .unwrap_or_else(|| DEFAULT_COMPACT_TEMPLATE.to_owned());
+            if compact_template.contains("{datetime}") {
+                subscribe(&[EventType::Timer]);
+                set_timeout(seconds_until_next_minute());
+            }
+            self.compact_template = Some(compact_template);
+        }
+        self.widgets = Widgets::from_configuration(&configuration);
+        if self.widgets.is_some() {
+            subscribe(&[
+                EventType::PermissionRequestResult,
+                EventType::SystemStateUpdate,
+            ]);
+            request_permission(&[PermissionType::ReadSystemState]);
+        }
     }
 
     fn update(&mut self, event: Event) -> bool {
@@ -222,6 +250,27 @@ impl ZellijPlugin for State {
                 should_render = true;
                 self.display_system_clipboard_failure = true;
             },
+            Event::PermissionRequestResult(PermissionStatus::Granted) => {
+                subscribe_to_system_state(WIDGETS_UPDATE_INTERVAL);
+            },
+            Event::SystemStateUpdate(system_state) => {
+                // the time changes with every update, the rendered widgets do not necessarily
+                should_render = self.system_state.is_none()
+                    || self.widgets_line(self.system_state.as_ref()).part
+                        != self.widgets_line(Some(&system_state)).part;
+                self.system_state = Some(system_state);
+            },
+            Event::PendingKeys(pending_keys) => {
+                if self.pending_keys != pending_keys {
+                    should_render = true;
+                }
+                self.pending_keys = pending_keys;
+            },
+            Event::Timer(_) => {
+                // only the datetime segment of the compact status-bar sets timers
+                should_render = true;
+                set_timeout(seconds_until_next_minute());
+            },
             Event::InputReceived => {
                 if self.text_copy_destination.is_some()
                     || self.display_system_clipboard_failure == true
@@ -237,6 +286,25 @@ impl ZellijPlugin for State {
     }
 
     fn render(&mut self, rows: usize, cols: usize) {
+        let widgets_line = self.widgets_line(self.system_state.as_ref());
+        // widgets take precedence over whatever is left of them, unless they don't fit at all
+        let cols_left_of_widgets = if widgets_line.len <= cols {
+            cols - widgets_line.len
+        } else {
+            cols
+        };
+        if let Some(compact_template) = &self.compact_template {
+            let mut line = compact_line(
+                &self.mode_info,
+                compact_template,
+                unix_time(),
+                cols_left_of_widgets,
+                |width| self.second_line(width),
+            );
+            append_right_aligned(&mut line, &widgets_line, cols, Style::new());
+            print!("\u{1b}[m{}\u{1b}[0K", line);
+            return;
+        }
         let supports_arrow_fonts = !self.mode_info.capabilities.arrow_fonts;
         let separator = if supports_arrow_fonts {
             ARROW_SEPARATOR
@@ -245,13 +313,20 @@ impl ZellijPlugin for State {
         };
 
         let active_tab = self.tabs.iter().find(|t| t.active);
-        let first_line = first_line(&self.mode_info, active_tab, cols, separator);
+        let mut first_line =
+            first_line(&self.mode_info, active_tab, cols_left_of_widgets, separator);
         let second_line = self.second_line(cols);
 
         let background = match self.mode_info.style.colors.theme_hue {
             ThemeHue::Dark => self.mode_info.style.colors.black,
             ThemeHue::Light => self.mode_info.style.colors.white,
         };
+        append_right_aligned(
+            &mut first_line,
+            &widgets_line,
+            cols,
+            style!(self.mode_info.style.colors.fg, background),
+        );
 
         // [48;5;238m is white background, [0K is so that it fills the rest of the line
         // [m is background reset, [0K is so that it clears the rest of the line
@@ -260,7 +335,7 @@ impl ZellijPlugin for State {
                 if rows > 1 {
                     println!("{}\u{1b}[48;2;{};{};{}m\u{1b}[0K", first_line, r, g, b);
                 } else {
-                    if self.mode_info.mode == InputMode::Normal {
+                    if self.mode_info.mode == InputMode::Normal && self.pending_keys.is_empty() {
                         print!("{}\u{1b}[48;2;{};{};{}m\u{1b}[0K", first_line, r, g, b);
                     } else {
                         print!("\u{1b}[m{}\u{1b}[0K", second_line);
@@ -271,7 +346,7 @@ impl ZellijPlugin for State {
                 if rows > 1 {
                     println!("{}\u{1b}[48;5;{}m\u{1b}[0K", first_line, color);
                 } else {
-                    if self.mode_info.mode == InputMode::Normal {
+                    if self.mode_info.mode == InputMode::Normal && self.pending_keys.is_empty() {
                         print!("{}\u{1b}[48;5;{}m\u{1b}[0K", first_line, color);
                     } else {
                         print!("\u{1b}[m{}\u{1b}[0K", second_line);
@@ -286,11 +361,30 @@ impl ZellijPlugin for State {
     }
 }
 
+fn seconds_until_next_minute() -> f64 {
+    (60 - unix_time() % 60) as f64
+}
+
 impl State {
+    fn widgets_line(&self, system_state: Option<&SystemState>) -> LinePart {
+        let palette = self.mode_info.style.colors;
+        let background = match palette.theme_hue {
+            ThemeHue::Dark => palette.black,
+            ThemeHue::Light => palette.white,
+        };
+        match (&self.widgets, system_state) {
+            (Some(widgets), Some(system_state)) => {
+                widgets.render(system_state, style!(palette.fg, background).bold())
+            },
+            _ => LinePart::default(),
+        }
+    }
     fn second_line(&self, cols: usize) -> LinePart {
         let active_tab = self.tabs.iter().find(|t| t.active);
 
-        if let Some(copy_destination) = self.text_copy_destination {
+        if !self.pending_keys.is_empty() {
+            pending_keys_hint(&self.mode_info.style.colors, &self.pending_keys)
+        } else if let Some(copy_destination) = self.text_copy_destination {
             text_copied_hint(&self.mode_info.style.colors, copy_destination)
         } else if self.display_system_clipboard_failure {
             system_clipboard_error(&self.mode_info.style.colors)
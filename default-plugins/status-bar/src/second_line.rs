@@ -82,6 +82,113 @@ fn full_shortcut_list_nonstandard_mode(help: &ModeInfo) -> LinePart {
     line_part
 }
 
+/// Like [`action_key`], but also finds the keys bound to the same actions without switching back
+/// to normal mode afterwards (or the other way around), which is how keys are commonly remapped
+fn bound_keys(keymap: &[(Key, Vec<Action>)], actions: &[Action]) -> Vec<Key> {
+    let keys = action_key(keymap, actions);
+    if !keys.is_empty() {
+        return keys;
+    }
+    match actions.split_last() {
+        Some((last_action, other_actions)) if *last_action == TO_NORMAL => {
+            if other_actions.is_empty() {
+                vec![]
+            } else {
+                action_key(keymap, other_actions)
+            }
+        },
+        _ => {
+            let mut actions_and_to_normal = actions.to_vec();
+            actions_and_to_normal.push(TO_NORMAL);
+            action_key(keymap, &actions_and_to_normal)
+        },
+    }
+}
+
+/// The keys that launch (or focus) the built-in plugin `plugin_name`, which [`action_key`] cannot
+/// tell apart from the keys launching other plugins
+fn plugin_keys(keymap: &[(Key, Vec<Action>)], plugin_name: &str) -> Vec<Key> {
+    keymap
+        .iter()
+        .filter_map(|(key, actions)| match actions.first() {
+            Some(Action::LaunchOrFocusPlugin(run_plugin, ..))
+                if run_plugin.location.to_string() == plugin_name =>
+            {
+                Some(*key)
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+/// The keys and hints to display for the current mode: the pre-defined ones if any of their keys
+/// are bound, otherwise (eg. in modes we do not know of or whose keys were all bound to other
+/// actions) ones generated from the keybindings themselves.
+fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
+    let keys_and_hints = predefined_keys_and_hints(mi);
+    if keys_and_hints.iter().any(|(_, _, keys)| !keys.is_empty()) {
+        keys_and_hints
+    } else {
+        keys_and_hints_from_keybinds(mi)
+    }
+}
+
+/// Creates a hint for each action bound in the current mode, named after the action (eg.
+/// `Action::ToggleFloatingPanes` becomes "Toggle Floating Panes"). Keys bound to actions with the
+/// same name (eg. `MoveFocus(Left)` and `MoveFocus(Right)`) are grouped together.
+fn keys_and_hints_from_keybinds(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
+    let mut keymap = mi.get_mode_keybinds();
+    keymap.sort_unstable_by(|(keya, _), (keyb, _)| keya.partial_cmp(keyb).unwrap());
+    let mut known_actions: Vec<Vec<Action>> = vec![];
+    let mut keys_and_hints: Vec<(String, String, Vec<Key>)> = vec![];
+    for (key, actions) in keymap {
+        if known_actions.contains(&actions) {
+            continue;
+        }
+        known_actions.push(actions.clone());
+        let hint = match action_hint(&actions) {
+            Some(hint) => hint,
+            None => continue,
+        };
+        match keys_and_hints.iter_mut().find(|(long, _, _)| long == &hint) {
+            Some((_, _, keys)) => keys.push(key),
+            None => {
+                let short = hint.split(' ').next().unwrap_or_default().to_owned();
+                keys_and_hints.push((hint, short, vec![key]));
+            },
+        }
+    }
+    keys_and_hints
+}
+
+fn action_hint(actions: &[Action]) -> Option<String> {
+    let action = actions
+        .iter()
+        .find(|action| !matches!(action, Action::SwitchToMode(_)))
+        .or_else(|| actions.first())?;
+    let action_name = match action {
+        // switching back to normal mode is what selecting the pane does and has no hint of its own
+        Action::SwitchToMode(InputMode::Normal) => return None,
+        Action::SwitchToMode(input_mode) => format!("{:?}", input_mode),
+        action => {
+            let action_debug = format!("{:?}", action);
+            action_debug
+                .split(|c: char| !c.is_alphanumeric())
+                .next()
+                .unwrap_or_default()
+                .to_owned()
+        },
+    };
+    let mut hint = String::new();
+    for c in action_name.chars() {
+        if c.is_uppercase() && !hint.is_empty() {
+            hint.push(' ');
+        }
+        hint.push(c);
+    }
+    Some(hint)
+}
+
 /// Collect all relevant keybindings and hints to display.
 ///
 /// Creates a vector with tuples containing the following entries:
@@ -106,7 +213,7 @@ fn full_shortcut_list_nonstandard_mode(help: &ModeInfo) -> LinePart {
 // three times the length and all the keybinding vectors we generate become virtually unreadable
 // for humans.
 #[rustfmt::skip]
-fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
+fn predefined_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
     use Action as A;
     use InputMode as IM;
     use Direction as Dir;
@@ -144,17 +251,17 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
     }
 
     if mi.mode == IM::Pane { vec![
-        (s("New"), s("New"), action_key(&km, &[A::NewPane(None, None), TO_NORMAL])),
+        (s("New"), s("New"), bound_keys(&km, &[A::NewPane(None, None), TO_NORMAL])),
         (s("Change Focus"), s("Move"),
             action_key_group(&km, &[&[A::MoveFocus(Dir::Left)], &[A::MoveFocus(Dir::Down)],
                 &[A::MoveFocus(Dir::Up)], &[A::MoveFocus(Dir::Right)]])),
-        (s("Close"), s("Close"), action_key(&km, &[A::CloseFocus, TO_NORMAL])),
+        (s("Close"), s("Close"), bound_keys(&km, &[A::CloseFocus, TO_NORMAL])),
         (s("Rename"), s("Rename"),
-            action_key(&km, &[A::SwitchToMode(IM::RenamePane), A::PaneNameInput(vec![0])])),
-        (s("Toggle Fullscreen"), s("Fullscreen"), action_key(&km, &[A::ToggleFocusFullscreen, TO_NORMAL])),
+            bound_keys(&km, &[A::SwitchToMode(IM::RenamePane), A::PaneNameInput(vec![0])])),
+        (s("Toggle Fullscreen"), s("Fullscreen"), bound_keys(&km, &[A::ToggleFocusFullscreen, TO_NORMAL])),
         (s("Toggle Floating"), s("Floating"),
-            action_key(&km, &[A::ToggleFloatingPanes, TO_NORMAL])),
-        (s("Toggle Embed"), s("Embed"), action_key(&km, &[A::TogglePaneEmbedOrFloating, TO_NORMAL])),
+            bound_keys(&km, &[A::ToggleFloatingPanes, TO_NORMAL])),
+        (s("Toggle Embed"), s("Embed"), bound_keys(&km, &[A::TogglePaneEmbedOrFloating, TO_NORMAL])),
         (s("Select pane"), s("Select"), to_normal_key),
     ]} else if mi.mode == IM::Tab {
         // With the default bindings, "Move focus" for tabs is tricky: It binds all the arrow keys
@@ -174,13 +281,13 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
         };
 
         vec![
-        (s("New"), s("New"), action_key(&km, &[A::NewTab(None, vec![], None, None, None), TO_NORMAL])),
+        (s("New"), s("New"), bound_keys(&km, &[A::NewTab(None, vec![], None, None, None), TO_NORMAL])),
         (s("Change focus"), s("Move"), focus_keys),
-        (s("Close"), s("Close"), action_key(&km, &[A::CloseTab, TO_NORMAL])),
+        (s("Close"), s("Close"), bound_keys(&km, &[A::CloseTab, TO_NORMAL])),
         (s("Rename"), s("Rename"),
-            action_key(&km, &[A::SwitchToMode(IM::RenameTab), A::TabNameInput(vec![0])])),
-        (s("Sync"), s("Sync"), action_key(&km, &[A::ToggleActiveSyncTab, TO_NORMAL])),
-        (s("Toggle"), s("Toggle"), action_key(&km, &[A::ToggleTab])),
+            bound_keys(&km, &[A::SwitchToMode(IM::RenameTab), A::TabNameInput(vec![0])])),
+        (s("Sync"), s("Sync"), bound_keys(&km, &[A::ToggleActiveSyncTab, TO_NORMAL])),
+        (s("Toggle"), s("Toggle"), bound_keys(&km, &[A::ToggleTab])),
         (s("Select pane"), s("Select"), to_normal_key),
     ]} else if mi.mode == IM::Resize { vec![
         (s("Increase/Decrease size"), s("Increase/Decrease"),
@@ -207,7 +314,7 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
             &[Action::MovePane(Some(Dir::Up))], &[Action::MovePane(Some(Dir::Right))]])),
     ]} else if mi.mode == IM::Scroll { vec![
         (s("Enter search term"), s("Search"),
-            action_key(&km, &[A::SwitchToMode(IM::EnterSearch), A::SearchInput(vec![0])])),
+            bound_keys(&km, &[A::SwitchToMode(IM::EnterSearch), A::SearchInput(vec![0])])),
         (s("Scroll"), s("Scroll"),
             action_key_group(&km, &[&[Action::ScrollDown], &[Action::ScrollUp]])),
         (s("Scroll page"), s("Scroll"),
@@ -215,45 +322,48 @@ fn get_keys_and_hints(mi: &ModeInfo) -> Vec<(String, String, Vec<Key>)> {
         (s("Scroll half page"), s("Scroll"),
             action_key_group(&km, &[&[Action::HalfPageScrollDown], &[Action::HalfPageScrollUp]])),
         (s("Edit scrollback in default editor"), s("Edit"),
-            action_key(&km, &[Action::EditScrollback, TO_NORMAL])),
+            bound_keys(&km, &[Action::EditScrollback, TO_NORMAL])),
         (s("Select pane"), s("Select"), to_normal_key),
     ]} else if mi.mode == IM::EnterSearch { vec![
-        (s("When done"), s("Done"), action_key(&km, &[A::SwitchToMode(IM::Search)])),
+        (s("When done"), s("Done"), bound_keys(&km, &[A::SwitchToMode(IM::Search)])),
         (s("Cancel"), s("Cancel"),
-            action_key(&km, &[A::SearchInput(vec![27]), A::SwitchToMode(IM::Scroll)])),
+            bound_keys(&km, &[A::SearchInput(vec![27]), A::SwitchToMode(IM::Scroll)])),
     ]} else if mi.mode == IM::Search { vec![
         (s("Enter Search term"), s("Search"),
-            action_key(&km, &[A::SwitchToMode(IM::EnterSearch), A::SearchInput(vec![0])])),
+            bound_keys(&km, &[A::SwitchToMode(IM::EnterSearch), A::SearchInput(vec![0])])),
         (s("Scroll"), s("Scroll"),
             action_key_group(&km, &[&[Action::ScrollDown], &[Action::ScrollUp]])),
         (s("Scroll page"), s("Scroll"),
             action_key_group(&km, &[&[Action::PageScrollDown], &[Action::PageScrollUp]])),
         (s("Scroll half page"), s("Scroll"),
             action_key_group(&km, &[&[Action::HalfPageScrollDown], &[Action::HalfPageScrollUp]])),
-        (s("Search down"), s("Down"), action_key(&km, &[A::Search(SDir::Down)])),
-        (s("Search up"), s("Up"), action_key(&km, &[A::Search(SDir::Up)])),
+        (s("Search down"), s("Down"), bound_keys(&km, &[A::Search(SDir::Down)])),
+        (s("Search up"), s("Up"), bound_keys(&km, &[A::Search(SDir::Up)])),
         (s("Case sensitive"), s("Case"),
-            action_key(&km, &[A::SearchToggleOption(SOpt::CaseSensitivity)])),
+            bound_keys(&km, &[A::SearchToggleOption(SOpt::CaseSensitivity)])),
         (s("Wrap"), s("Wrap"),
-            action_key(&km, &[A::SearchToggleOption(SOpt::Wrap)])),
+            bound_keys(&km, &[A::SearchToggleOption(SOpt::Wrap)])),
         (s("Whole words"), s("Whole"),
-            action_key(&km, &[A::SearchToggleOption(SOpt::WholeWord)])),
+            bound_keys(&km, &[A::SearchToggleOption(SOpt::WholeWord)])),
+        (s("Regex"), s("Regex"),
+            bound_keys(&km, &[A::SearchToggleOption(SOpt::Regex)])),
     ]} else if mi.mode == IM::Session { vec![
-        (s("Detach"), s("Detach"), action_key(&km, &[Action::Detach])),
-        (s("Session Manager"), s("Manager"), action_key(&km, &[A::LaunchOrFocusPlugin(Default::default(), true, true), TO_NORMAL])), // not entirely accurate
+        (s("Detach"), s("Detach"), bound_keys(&km, &[Action::Detach])),
+        (s("Session Manager"), s("Manager"), plugin_keys(&km, "session-manager")),
+        (s("Pane Switcher"), s("Panes"), plugin_keys(&km, "pane-switcher")),
         (s("Select pane"), s("Select"), to_normal_key),
     ]} else if mi.mode == IM::Tmux { vec![
         (s("Move focus"), s("Move"), action_key_group(&km, &[
             &[A::MoveFocus(Dir::Left)], &[A::MoveFocus(Dir::Down)],
             &[A::MoveFocus(Dir::Up)], &[A::MoveFocus(Dir::Right)]])),
-        (s("Split down"), s("Down"), action_key(&km, &[A::NewPane(Some(Dir::Down), None), TO_NORMAL])),
-        (s("Split right"), s("Right"), action_key(&km, &[A::NewPane(Some(Dir::Right), None), TO_NORMAL])),
-        (s("Fullscreen"), s("Fullscreen"), action_key(&km, &[A::ToggleFocusFullscreen, TO_NORMAL])),
-        (s("New tab"), s("New"), action_key(&km, &[A::NewTab(None, vec![], None, None, None), TO_NORMAL])),
+        (s("Split down"), s("Down"), bound_keys(&km, &[A::NewPane(Some(Dir::Down), None), TO_NORMAL])),
+        (s("Split right"), s("Right"), bound_keys(&km, &[A::NewPane(Some(Dir::Right), None), TO_NORMAL])),
+        (s("Fullscreen"), s("Fullscreen"), bound_keys(&km, &[A::ToggleFocusFullscreen, TO_NORMAL])),
+        (s("New tab"), s("New"), bound_keys(&km, &[A::NewTab(None, vec![], None, None, None), TO_NORMAL])),
         (s("Rename tab"), s("Rename"),
-            action_key(&km, &[A::SwitchToMode(IM::RenameTab), A::TabNameInput(vec![0])])),
-        (s("Previous Tab"), s("Previous"), action_key(&km, &[A::GoToPreviousTab, TO_NORMAL])),
-        (s("Next Tab"), s("Next"), action_key(&km, &[A::GoToNextTab, TO_NORMAL])),
+            bound_keys(&km, &[A::SwitchToMode(IM::RenameTab), A::TabNameInput(vec![0])])),
+        (s("Previous Tab"), s("Previous"), bound_keys(&km, &[A::GoToPreviousTab, TO_NORMAL])),
+        (s("Next Tab"), s("Next"), bound_keys(&km, &[A::GoToNextTab, TO_NORMAL])),
         (s("Select pane"), s("Select"), to_normal_key),
     ]} else if matches!(mi.mode, IM::RenamePane | IM::RenameTab) { vec![
         (s("When done"), s("Done"), to_normal_key),
@@ -360,6 +470,20 @@ pub fn text_copied_hint(palette: &Palette, copy_destination: CopyDestination) ->
     }
 }
 
+pub fn pending_keys_hint(palette: &Palette, pending_keys: &[Key]) -> LinePart {
+    let orange_color = palette_match!(palette.orange);
+    let keys = pending_keys
+        .iter()
+        .map(|key| key.to_string())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let hint = format!(" {} ...", keys);
+    LinePart {
+        len: hint.chars().count(),
+        part: Style::new().fg(orange_color).bold().paint(hint).to_string(),
+    }
+}
+
 pub fn system_clipboard_error(palette: &Palette) -> LinePart {
     let hint = " Error using the system clipboard.";
     let red_color = palette_match!(palette.red);
@@ -750,4 +874,50 @@ mod tests {
 
         assert_eq!(ret, " <BACKSPACE> New / Ctrl + <a|ENTER|1|SPACE> Change Focus / <ESC> Close / <END> Toggle Fullscreen");
     }
+
+    #[test]
+    fn keybinds_remapped_without_switching_to_normal_mode() {
+        let mode_info = ModeInfo {
+            mode: InputMode::Pane,
+            keybinds: vec![(
+                InputMode::Pane,
+                vec![
+                    (Key::Char('n'), vec![Action::NewPane(None, None)]),
+                    (Key::Char('x'), vec![Action::CloseFocus]),
+                ],
+            )],
+            ..ModeInfo::default()
+        };
+
+        let ret = keybinds(&mode_info, "quicknav", 500);
+        let ret = unstyle(ret);
+
+        assert_eq!(ret, " <n> New / <x> Close");
+    }
+
+    #[test]
+    fn keybinds_generated_for_modes_without_predefined_hints() {
+        let mode_info = ModeInfo {
+            mode: InputMode::Prompt,
+            keybinds: vec![(
+                InputMode::Prompt,
+                vec![
+                    (Key::Char('w'), vec![Action::ToggleFloatingPanes, TO_NORMAL]),
+                    (Key::Left, vec![Action::MoveFocus(Direction::Left)]),
+                    (Key::Right, vec![Action::MoveFocus(Direction::Right)]),
+                    (Key::Char('t'), vec![Action::SwitchToMode(InputMode::Tab)]),
+                    (Key::Esc, vec![TO_NORMAL]),
+                ],
+            )],
+            ..ModeInfo::default()
+        };
+
+        let ret = keybinds(&mode_info, "quicknav", 500);
+        let ret = unstyle(ret);
+
+        assert_eq!(
+            ret,
+            " <←→> Move Focus / <t> Tab / <w> Toggle Floating Panes"
+        );
+    }
 }
@@ -0,0 +1,157 @@
+//! Optional widgets shown at the right of the status-bar, enabled with `clock_format` (a strftime
+//! format, eg. `clock_format "%H:%M"`), `load_average "true"` and `battery "true"`. They are
+//! drawn from the system state Zellij reports, which needs the `ReadSystemState` permission
+use ansi_term::Style;
+use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use zellij_tile::prelude::*;
+
+use crate::LinePart;
+
+/// How often (in seconds) the system state is asked for, so that the clock is at most this late
+pub const WIDGETS_UPDATE_INTERVAL: f64 = 1.0;
+
+const WIDGET_SEPARATOR: &str = " | ";
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Widgets {
+    clock_format: Option<String>,
+    load_average: bool,
+    battery: bool,
+}
+
+impl Widgets {
+    /// `None` if no widget is enabled
+    pub fn from_configuration(configuration: &BTreeMap<String, String>) -> Option<Self> {
+        let enabled = |key: &str| configuration.get(key).map(|s| s.as_str()) == Some("true");
+        let widgets = Widgets {
+            clock_format: configuration.get("clock_format").cloned(),
+            load_average: enabled("load_average"),
+            battery: enabled("battery"),
+        };
+        if widgets == Widgets::default() {
+            None
+        } else {
+            Some(widgets)
+        }
+    }
+    /// The widgets that have something to show, a clock with an invalid format shows an error
+    pub fn render(&self, system_state: &SystemState, style: Style) -> LinePart {
+        let mut texts = vec![];
+        if let Some([one_minute, ..]) = system_state.load_average.filter(|_| self.load_average) {
+            texts.push(format!("LOAD {:.2}", one_minute));
+        }
+        if let Some(battery) = system_state.battery.filter(|_| self.battery) {
+            let charging = if battery.charging { "+" } else { "" };
+            texts.push(format!("BAT {}%{}", battery.percentage, charging));
+        }
+        if let Some(clock_format) = &self.clock_format {
+            texts.push(format_clock(clock_format, system_state));
+        }
+        if texts.is_empty() {
+            return LinePart::default();
+        }
+        let text = format!(" {} ", texts.join(WIDGET_SEPARATOR));
+        LinePart {
+            len: text.chars().count(),
+            part: style.paint(text).to_string(),
+        }
+    }
+}
+
+fn format_clock(clock_format: &str, system_state: &SystemState) -> String {
+    let local_time = system_state.unix_time as i64 + system_state.utc_offset as i64;
+    let mut clock = String::new();
+    match NaiveDateTime::from_timestamp_opt(local_time, 0) {
+        Some(date_time) if write!(clock, "{}", date_time.format(clock_format)).is_ok() => clock,
+        _ => "invalid clock_format".to_owned(),
+    }
+}
+
+/// `widgets` at the right edge of `line` (which is `cols` wide), if there is room for them
+pub fn append_right_aligned(line: &mut LinePart, widgets: &LinePart, cols: usize, style: Style) {
+    if widgets.len == 0 || line.len + widgets.len > cols {
+        return;
+    }
+    let padding = " ".repeat(cols - line.len - widgets.len);
+    line.append(&LinePart {
+        len: padding.len(),
+        part: style.paint(padding).to_string(),
+    });
+    line.append(widgets);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn widgets(clock_format: &str, load_average: &str, battery: &str) -> Option<Widgets> {
+        let mut configuration = BTreeMap::new();
+        configuration.insert("clock_format".to_owned(), clock_format.to_owned());
+        configuration.insert("load_average".to_owned(), load_average.to_owned());
+        configuration.insert("battery".to_owned(), battery.to_owned());
+        Widgets::from_configuration(&configuration)
+    }
+
+    fn system_state() -> SystemState {
+        SystemState {
+            unix_time: 1700000000, // 2023-11-14 22:13:20 UTC
+            utc_offset: 3600,
+            load_average: Some([0.5, 0.25, 0.125]),
+            battery: Some(BatteryState {
+                percentage: 87,
+                charging: true,
+            }),
+        }
+    }
+
+    #[test]
+    fn no_widgets_without_configuration() {
+        assert_eq!(Widgets::from_configuration(&BTreeMap::new()), None);
+    }
+
+    #[test]
+    fn all_widgets_with_the_clock_in_local_time() {
+        let widgets = widgets("%Y-%m-%d %H:%M", "true", "true").unwrap();
+        let rendered = widgets.render(&system_state(), Style::new());
+        assert_eq!(rendered.part, " LOAD 0.50 | BAT 87%+ | 2023-11-14 23:13 ");
+        assert_eq!(rendered.len, 41);
+    }
+
+    #[test]
+    fn widgets_without_system_state_are_left_out() {
+        let widgets = widgets("%H:%M", "true", "true").unwrap();
+        let system_state = SystemState {
+            load_average: None,
+            battery: None,
+            ..system_state()
+        };
+        assert_eq!(widgets.render(&system_state, Style::new()).part, " 23:13 ");
+    }
+
+    #[test]
+    fn invalid_clock_format() {
+        let widgets = widgets("%Q", "false", "false").unwrap();
+        assert_eq!(
+            widgets.render(&system_state(), Style::new()).part,
+            " invalid clock_format "
+        );
+    }
+
+    #[test]
+    fn widgets_are_right_aligned_if_they_fit() {
+        let mut line = LinePart {
+            part: "left".to_owned(),
+            len: 4,
+        };
+        let widgets = LinePart {
+            part: "right".to_owned(),
+            len: 5,
+        };
+        append_right_aligned(&mut line, &widgets, 12, Style::new());
+        assert_eq!(line.part, "left   right");
+        append_right_aligned(&mut line, &widgets, 12, Style::new());
+        assert_eq!(line.part, "left   right");
+    }
+}
@@ -21,6 +21,8 @@ use serde::{Deserialize, Serialize};
 
 use std::io::{self, BufRead};
 
+const SCAN_BATCH_SIZE: usize = 1000; // entries scanned between streaming partial search results
+
 #[derive(Default, Serialize, Deserialize)]
 pub struct Search {
     search_type: SearchType,
@@ -65,64 +67,118 @@ impl Search {
         }
     }
     pub fn scan_hd(&mut self) {
-        for result in Walk::new(ROOT) {
-            if let Ok(entry) = result {
-                self.add_file_entry(entry.path(), entry.metadata().ok());
+        let mut scanned_files = vec![];
+        for entry in Walk::new(ROOT).flatten() {
+            scanned_files.push(self.add_file_entry(entry.path(), entry.metadata().ok()));
+            if scanned_files.len() == SCAN_BATCH_SIZE {
+                self.stream_partial_results(&scanned_files);
+                scanned_files.clear();
             }
         }
+        self.stream_partial_results(&scanned_files);
     }
-    pub fn search(&mut self, search_term: String) {
-        let search_results_limit = 100; // artificial limit to prevent probably unwanted chaos
-                                        // let mut matcher = SkimMatcherV2::default().use_cache(true).element_limit(search_results_limit);
-        let mut file_names_search_results = None;
-        let mut file_contents_search_results = None;
-        if let SearchType::Names | SearchType::NamesAndContents = self.search_type {
-            let file_names_matches = match self.cached_file_name_results.get(&search_term) {
-                Some(cached_results) => cached_results.clone(),
-                None => {
-                    let mut matcher = SkimMatcherV2::default().use_cache(true);
-                    let results = self.search_file_names(&search_term, &mut matcher);
-                    self.cached_file_name_results
-                        .insert(search_term.clone(), results.clone());
-                    results
-                },
-            };
-            file_names_search_results = Some(
-                ResultsOfSearch::new(search_term.clone(), file_names_matches)
-                    .limit_search_results(search_results_limit),
-            );
-        };
-        if let SearchType::Contents | SearchType::NamesAndContents = self.search_type {
-            let file_contents_matches = match self.cached_file_contents_results.get(&search_term) {
-                Some(cached_results) => cached_results.clone(),
-                None => {
-                    let mut matcher = SkimMatcherV2::default().use_cache(true);
-                    let results = self.search_file_contents(&search_term, &mut matcher);
-                    self.cached_file_contents_results
-                        .insert(search_term.clone(), results.clone());
-                    results
-                },
-            };
-            file_contents_search_results = Some(
-                ResultsOfSearch::new(search_term.clone(), file_contents_matches)
-                    .limit_search_results(search_results_limit),
-            );
+    fn stream_partial_results(&mut self, scanned_files: &[String]) {
+        // so that matches show up while a large folder is still being scanned rather than only
+        // once it is done - only the files scanned since the last batch are matched and merged
+        // into the cached results, so that the scan does not get slower the more it has scanned
+        let current_search_term = match self.read_search_term_from_hd_cache() {
+            Some(current_search_term) if !current_search_term.is_empty() => current_search_term,
+            _ => return,
         };
-
+        // results cached for other search terms are missing the files scanned since
+        self.cached_file_name_results
+            .retain(|search_term, _| search_term == &current_search_term);
+        self.cached_file_contents_results
+            .retain(|search_term, _| search_term == &current_search_term);
+        self.merge_matches_of_scanned_files(&current_search_term, scanned_files);
+        self.search(current_search_term);
+    }
+    pub fn search(&mut self, search_term: String) {
+        self.cache_search_results(&search_term);
         // if the search term changed before we finished, let's search again!
         if let Some(current_search_term) = self.read_search_term_from_hd_cache() {
             if current_search_term != search_term {
-                return self.search(current_search_term.into());
+                return self.search(current_search_term);
+            }
+        }
+        self.post_search_results(&search_term);
+    }
+    fn cache_search_results(&mut self, search_term: &str) {
+        if let SearchType::Names | SearchType::NamesAndContents = self.search_type {
+            if !self.cached_file_name_results.contains_key(search_term) {
+                let mut matcher = SkimMatcherV2::default().use_cache(true);
+                let results = self.search_file_names(search_term, &mut matcher);
+                self.cached_file_name_results
+                    .insert(search_term.to_owned(), results);
+            }
+        }
+        if let SearchType::Contents | SearchType::NamesAndContents = self.search_type {
+            if !self.cached_file_contents_results.contains_key(search_term) {
+                let mut matcher = SkimMatcherV2::default().use_cache(true);
+                let results = self.search_file_contents(search_term, &mut matcher);
+                self.cached_file_contents_results
+                    .insert(search_term.to_owned(), results);
+            }
+        }
+    }
+    fn merge_matches_of_scanned_files(&mut self, search_term: &str, scanned_files: &[String]) {
+        // without cached results there is nothing to merge into, they will be created from all
+        // the files scanned so far (which include these) once we search
+        let matcher = SkimMatcherV2::default().use_cache(true);
+        if self.cached_file_name_results.contains_key(search_term) {
+            let mut matches = vec![];
+            for file_name in scanned_files {
+                if let Some((score, indices)) = matcher.fuzzy_indices(file_name, search_term) {
+                    matches.push(SearchResult::new_file_name(
+                        score,
+                        indices,
+                        file_name.to_owned(),
+                    ));
+                }
+            }
+            if let Some(cached_results) = self.cached_file_name_results.get_mut(search_term) {
+                cached_results.append(&mut matches);
+            }
+        }
+        if self.cached_file_contents_results.contains_key(search_term) {
+            let mut matches = vec![];
+            for file_name in scanned_files {
+                let lines_of_file = self
+                    .file_contents
+                    .range((file_name.to_owned(), 0)..=(file_name.to_owned(), usize::MAX));
+                for ((file_name, line_number), line_entry) in lines_of_file {
+                    if let Some((score, indices)) = matcher.fuzzy_indices(line_entry, search_term) {
+                        matches.push(SearchResult::new_file_line(
+                            score,
+                            indices,
+                            file_name.clone(),
+                            line_entry.clone(),
+                            *line_number,
+                        ));
+                    }
+                }
+            }
+            if let Some(cached_results) = self.cached_file_contents_results.get_mut(search_term) {
+                cached_results.append(&mut matches);
             }
         }
-        if let Some(file_names_search_results) = file_names_search_results {
+    }
+    fn post_search_results(&self, search_term: &str) {
+        let search_results_limit = 100; // artificial limit to prevent probably unwanted chaos
+        if let Some(file_name_matches) = self.cached_file_name_results.get(search_term) {
+            let file_names_search_results =
+                ResultsOfSearch::new(search_term.to_owned(), file_name_matches.clone())
+                    .limit_search_results(search_results_limit);
             post_message_to_plugin(PluginMessage {
                 name: serde_json::to_string(&MessageToPlugin::UpdateFileNameSearchResults).unwrap(),
                 payload: serde_json::to_string(&file_names_search_results).unwrap(),
                 ..Default::default()
             });
         }
-        if let Some(file_contents_search_results) = file_contents_search_results {
+        if let Some(file_contents_matches) = self.cached_file_contents_results.get(search_term) {
+            let file_contents_search_results =
+                ResultsOfSearch::new(search_term.to_owned(), file_contents_matches.clone())
+                    .limit_search_results(search_results_limit);
             post_message_to_plugin(PluginMessage {
                 name: serde_json::to_string(&MessageToPlugin::UpdateFileContentsSearchResults)
                     .unwrap(),
@@ -153,7 +209,12 @@ impl Search {
             Err(e) => eprintln!("Failed to deserialize paths: {:?}", e),
         }
     }
-    fn add_file_entry(&mut self, file_name: &Path, file_metadata: Option<std::fs::Metadata>) {
+    /// Returns the entry's path relative to the scanned folder, which is what it is searched by
+    fn add_file_entry(
+        &mut self,
+        file_name: &Path,
+        file_metadata: Option<std::fs::Metadata>,
+    ) -> String {
         let file_path = file_name.display().to_string();
         let file_path_stripped_prefix = self.strip_file_prefix(&file_name);
 
@@ -181,6 +242,7 @@ impl Search {
                 }
             }
         }
+        file_path_stripped_prefix
     }
     fn search_file_names(
         &self,
@@ -315,3 +377,78 @@ impl ResultsOfSearch {
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_scanned_file(search: &mut Search, file_name: &str, lines: &[&str]) -> String {
+        search.file_names.insert(file_name.to_owned());
+        for (index, line) in lines.iter().enumerate() {
+            search
+                .file_contents
+                .insert((file_name.to_owned(), index + 1), line.to_string());
+        }
+        file_name.to_owned()
+    }
+
+    fn sorted_matches(search_results: &[SearchResult]) -> Vec<(String, Option<usize>, i64)> {
+        let mut matches: Vec<(String, Option<usize>, i64)> = search_results
+            .iter()
+            .map(|search_result| match search_result {
+                SearchResult::File { path, score, .. } => (path.clone(), None, *score),
+                SearchResult::LineInFile {
+                    path,
+                    line_number,
+                    score,
+                    ..
+                } => (path.clone(), Some(*line_number), *score),
+            })
+            .collect();
+        matches.sort();
+        matches
+    }
+
+    #[test]
+    fn matches_of_scanned_files_are_merged_into_the_cached_results() {
+        let mut search = Search::new(SearchType::NamesAndContents);
+        add_scanned_file(&mut search, "src/main.rs", &["fn main() {}", "// nothing"]);
+        search.cache_search_results("main");
+        let scanned_files = vec![
+            add_scanned_file(&mut search, "src/domain.rs", &["// the main entry"]),
+            add_scanned_file(&mut search, "README", &["unrelated"]),
+        ];
+        search.merge_matches_of_scanned_files("main", &scanned_files);
+
+        let mut searched_at_once = Search::new(SearchType::NamesAndContents);
+        searched_at_once.file_names = search.file_names.clone();
+        searched_at_once.file_contents = search.file_contents.clone();
+        searched_at_once.cache_search_results("main");
+
+        assert_eq!(
+            sorted_matches(&search.cached_file_name_results["main"]),
+            sorted_matches(&searched_at_once.cached_file_name_results["main"]),
+        );
+        assert_eq!(
+            sorted_matches(&search.cached_file_contents_results["main"]),
+            sorted_matches(&searched_at_once.cached_file_contents_results["main"]),
+        );
+        assert_eq!(search.cached_file_name_results["main"].len(), 2);
+        assert_eq!(search.cached_file_contents_results["main"].len(), 2);
+    }
+
+    #[test]
+    fn scanned_files_are_not_merged_into_search_terms_that_were_not_searched() {
+        let mut search = Search::new(SearchType::NamesAndContents);
+        let scanned_files = vec![add_scanned_file(&mut search, "src/main.rs", &["fn main"])];
+        search.merge_matches_of_scanned_files("main", &scanned_files);
+        assert!(search.cached_file_name_results.is_empty());
+        assert!(search.cached_file_contents_results.is_empty());
+        search.cache_search_results("main");
+        assert_eq!(
+            search.cached_file_name_results["main"].len(),
+            1,
+            "scanned file is matched once the search term is searched"
+        );
+    }
+}
@@ -3,7 +3,9 @@ use crate::search::ui::{
     GREEN, ORANGE,
 };
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use unicode_width::UnicodeWidthStr;
+use zellij_tile::prelude::FileToOpen;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum SearchResult {
@@ -50,6 +52,22 @@ impl SearchResult {
             SearchResult::LineInFile { score, .. } => *score,
         }
     }
+    /// The file to open in an editor pane, at the matching line if this is a line in a file
+    pub fn file_to_open(&self) -> FileToOpen {
+        match self {
+            SearchResult::File { path, .. } => FileToOpen {
+                path: PathBuf::from(path),
+                ..Default::default()
+            },
+            SearchResult::LineInFile {
+                path, line_number, ..
+            } => FileToOpen {
+                path: PathBuf::from(path),
+                line_number: Some(*line_number),
+                ..Default::default()
+            },
+        }
+    }
     pub fn rendered_height(&self) -> usize {
         match self {
             SearchResult::File { .. } => 1,
@@ -306,3 +324,25 @@ impl SearchResult {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_in_file_is_opened_at_its_line() {
+        let search_result =
+            SearchResult::new_file_line(1, vec![], "src/main.rs".to_owned(), "fn main".into(), 12);
+        let file_to_open = search_result.file_to_open();
+        assert_eq!(file_to_open.path, PathBuf::from("src/main.rs"));
+        assert_eq!(file_to_open.line_number, Some(12));
+    }
+
+    #[test]
+    fn file_is_opened_at_its_start() {
+        let search_result = SearchResult::new_file_name(1, vec![], "src/main.rs".to_owned());
+        let file_to_open = search_result.file_to_open();
+        assert_eq!(file_to_open.path, PathBuf::from("src/main.rs"));
+        assert_eq!(file_to_open.line_number, None);
+    }
+}
@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use zellij_tile::prelude::{
     hide_self, open_file, open_file_floating, open_terminal, open_terminal_floating,
-    post_message_to, FileToOpen, Key, PluginMessage,
+    post_message_to, Key, PluginMessage,
 };
 
 pub const CURRENT_SEARCH_TERM: &str = "/data/current_search_term";
@@ -86,34 +86,11 @@ impl SearchState {
     }
     fn open_search_result_in_editor(&mut self) {
         match self.selected_search_result_entry() {
-            Some(SearchResult::File { path, .. }) => {
+            Some(search_result) => {
                 if self.should_open_floating {
-                    open_file_floating(FileToOpen {
-                        path: PathBuf::from(path),
-                        ..Default::default()
-                    });
-                } else {
-                    open_file(FileToOpen {
-                        path: PathBuf::from(path),
-                        ..Default::default()
-                    });
-                }
-            },
-            Some(SearchResult::LineInFile {
-                path, line_number, ..
-            }) => {
-                if self.should_open_floating {
-                    open_file_floating(FileToOpen {
-                        path: PathBuf::from(path),
-                        line_number: Some(line_number),
-                        ..Default::default()
-                    });
+                    open_file_floating(search_result.file_to_open());
                 } else {
-                    open_file(FileToOpen {
-                        path: PathBuf::from(path),
-                        line_number: Some(line_number),
-                        ..Default::default()
-                    });
+                    open_file(search_result.file_to_open());
                 }
             },
             None => eprintln!("Search results not found"),
@@ -0,0 +1,68 @@
+use zellij_tile::prelude::*;
+use zellij_tile_utils::MiniMenu;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TabAction {
+    Rename,
+    Close,
+    MoveLeft,
+    MoveRight,
+    NewTab,
+    Dismiss,
+}
+
+static TAB_ACTIONS: [(&str, TabAction); 6] = [
+    ("Rename", TabAction::Rename),
+    ("Close", TabAction::Close),
+    ("Move left", TabAction::MoveLeft),
+    ("Move right", TabAction::MoveRight),
+    ("New tab", TabAction::NewTab),
+    ("x", TabAction::Dismiss),
+];
+
+/// Opened by right clicking a tab, drawn instead of the tab line until an item (or anything
+/// else) is clicked
+#[derive(Debug, Clone)]
+pub struct TabContextMenu {
+    tab_position: usize,
+    menu: MiniMenu,
+}
+
+impl TabContextMenu {
+    pub fn new(tab: &TabInfo) -> Self {
+        let actions: Vec<&str> = TAB_ACTIONS.iter().map(|(name, _)| *name).collect();
+        TabContextMenu {
+            tab_position: tab.position,
+            menu: MiniMenu::new(actions).title(format!("Tab #{}", tab.position + 1)),
+        }
+    }
+    /// The tab the menu was opened on was closed or moved out of range
+    pub fn is_stale(&self, tabs: &[TabInfo]) -> bool {
+        self.tab_position >= tabs.len()
+    }
+    pub fn render(&self, cols: usize, styling: &Styling) -> String {
+        self.menu.render(cols, styling)
+    }
+    /// Runs the action of the item at `col` (if any), the menu should be closed afterwards either
+    /// way
+    pub fn click(&self, col: usize, tab_count: usize) {
+        let tab_position = self.tab_position as u32;
+        let action = self.menu.item_at(col).map(|index| TAB_ACTIONS[index].1);
+        match action {
+            Some(TabAction::Rename) => {
+                // renaming is done by the tab itself, in RenameTab mode
+                switch_tab_to(tab_position + 1);
+                switch_to_input_mode(&InputMode::RenameTab);
+            },
+            Some(TabAction::Close) => close_tab_with_index(tab_position),
+            Some(TabAction::MoveLeft) if tab_position > 0 => {
+                move_tab_to_index(tab_position, tab_position - 1);
+            },
+            Some(TabAction::MoveRight) if (tab_position as usize) + 1 < tab_count => {
+                move_tab_to_index(tab_position, tab_position + 1);
+            },
+            Some(TabAction::NewTab) => new_tab(),
+            _ => {},
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use ansi_term::ANSIStrings;
 use unicode_width::UnicodeWidthStr;
 
-use crate::{LinePart, ARROW_SEPARATOR};
+use crate::{LinePart, OtherSessions, OtherSessionsOnClick, SessionClick, ARROW_SEPARATOR};
 use zellij_tile::prelude::*;
 use zellij_tile_utils::style;
 
@@ -10,7 +10,8 @@ fn get_current_title_len(current_title: &[LinePart]) -> usize {
 }
 
 // move elements from before_active and after_active into tabs_to_render while they fit in cols
-// adds collapsed_tabs to the left and right if there's left over tabs that don't fit
+// adds collapsed_tabs to the left and right if there's left over tabs that don't fit, clicking
+// these scrolls the tab line to the first hidden tab on their side
 fn populate_tabs_in_tab_line(
     tabs_before_active: &mut Vec<LinePart>,
     tabs_after_active: &mut Vec<LinePart>,
@@ -48,7 +49,16 @@ fn populate_tabs_in_tab_line(
         let total_size = collapsed_left.len + middle_size + collapsed_right.len;
 
         if total_size > cols {
-            // break and dont add collapsed tabs to tabs_to_render, they will not fit
+            // both collapsed tabs do not fit, add the ones that do rather than silently cutting
+            // the hidden tabs off
+            let mut remaining_cols = cols.saturating_sub(middle_size);
+            if collapsed_left.len <= remaining_cols {
+                remaining_cols -= collapsed_left.len;
+                tabs_to_render.insert(0, collapsed_left);
+            }
+            if collapsed_right.len <= remaining_cols {
+                tabs_to_render.push(collapsed_right);
+            }
             break;
         }
 
@@ -135,7 +145,9 @@ fn left_more_message(
     LinePart {
         part: more_styled_text,
         len: more_text_len,
-        tab_index: Some(tab_index),
+        tab_index: None,
+        session_click: None,
+        scroll_to_tab: Some(tab_index),
     }
 }
 
@@ -167,7 +179,9 @@ fn right_more_message(
     LinePart {
         part: more_styled_text,
         len: more_text_len,
-        tab_index: Some(tab_index),
+        tab_index: None,
+        session_click: None,
+        scroll_to_tab: Some(tab_index),
     }
 }
 
@@ -188,6 +202,8 @@ fn tab_line_prefix(session_name: Option<&str>, palette: Palette, cols: usize) ->
         part: prefix_styled_text.to_string(),
         len: prefix_text_len,
         tab_index: None,
+        session_click: None,
+        scroll_to_tab: None,
     }];
     if let Some(name) = session_name {
         let name_part = format!("({}) ", name);
@@ -202,8 +218,72 @@ fn tab_line_prefix(session_name: Option<&str>, palette: Palette, cols: usize) ->
                 part: name_part_styled_text.to_string(),
                 len: name_part_len,
                 tab_index: None,
+                session_click: None,
+                scroll_to_tab: None,
+            })
+        }
+    }
+    parts
+}
+
+fn other_sessions_segment(
+    other_sessions: &[String],
+    display: OtherSessions,
+    on_click: OtherSessionsOnClick,
+    palette: Palette,
+    cols: usize,
+) -> Vec<LinePart> {
+    if other_sessions.is_empty() {
+        return vec![];
+    }
+    let bg_color = match palette.theme_hue {
+        ThemeHue::Dark => palette.black,
+        ThemeHue::Light => palette.white,
+    };
+    let texts_and_clicks: Vec<(String, SessionClick)> = match display {
+        OtherSessions::Count => {
+            let count = other_sessions.len();
+            let text = if count == 1 {
+                "[+1 session] ".to_owned()
+            } else {
+                format!("[+{} sessions] ", count)
+            };
+            let session_click = match on_click {
+                // with only a count to click on, we switch to the first of the other sessions
+                OtherSessionsOnClick::Switch => SessionClick::SwitchTo(other_sessions[0].clone()),
+                OtherSessionsOnClick::SessionManager => SessionClick::OpenSessionManager,
+            };
+            vec![(text, session_click)]
+        },
+        OtherSessions::Names => other_sessions
+            .iter()
+            .map(|name| {
+                let session_click = match on_click {
+                    OtherSessionsOnClick::Switch => SessionClick::SwitchTo(name.clone()),
+                    OtherSessionsOnClick::SessionManager => SessionClick::OpenSessionManager,
+                };
+                (format!("[{}] ", name), session_click)
             })
+            .collect(),
+    };
+    let mut parts = vec![];
+    let mut total_len = 0;
+    for (text, session_click) in texts_and_clicks {
+        let len = text.width();
+        if total_len + len > cols {
+            break;
         }
+        total_len += len;
+        parts.push(LinePart {
+            part: style!(palette.magenta, bg_color)
+                .bold()
+                .paint(text)
+                .to_string(),
+            len,
+            tab_index: None,
+            session_click: Some(session_click),
+            scroll_to_tab: None,
+        });
     }
     parts
 }
@@ -216,16 +296,21 @@ pub fn tab_separator(capabilities: PluginCapabilities) -> &'static str {
     }
 }
 
+// the tab line is laid out around centered_tab_index, which is the active tab unless the user
+// scrolled the tab line by clicking on the collapsed tabs
 pub fn tab_line(
     session_name: Option<&str>,
     mut all_tabs: Vec<LinePart>,
-    active_tab_index: usize,
+    centered_tab_index: usize,
     cols: usize,
     palette: Palette,
     capabilities: PluginCapabilities,
     hide_session_name: bool,
+    other_sessions: &[String],
+    other_sessions_display: Option<OtherSessions>,
+    other_sessions_on_click: OtherSessionsOnClick,
 ) -> Vec<LinePart> {
-    let mut tabs_after_active = all_tabs.split_off(active_tab_index);
+    let mut tabs_after_active = all_tabs.split_off(centered_tab_index);
     let mut tabs_before_active = all_tabs;
     let active_tab = if !tabs_after_active.is_empty() {
         tabs_after_active.remove(0)
@@ -236,6 +321,16 @@ pub fn tab_line(
         true => tab_line_prefix(None, palette, cols),
         false => tab_line_prefix(session_name, palette, cols),
     };
+    if let Some(other_sessions_display) = other_sessions_display {
+        let prefix_len = get_current_title_len(&prefix);
+        prefix.append(&mut other_sessions_segment(
+            other_sessions,
+            other_sessions_display,
+            other_sessions_on_click,
+            palette,
+            cols.saturating_sub(prefix_len),
+        ));
+    }
     let prefix_len = get_current_title_len(&prefix);
 
     // if active tab alone won't fit in cols, don't draw any tabs
@@ -1,13 +1,15 @@
+mod context_menu;
 mod line;
 mod tab;
 
 use std::cmp::{max, min};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::convert::TryInto;
 
-use tab::get_tab_to_focus;
+use tab::{get_clicked_line_part, get_tab_to_focus};
 use zellij_tile::prelude::*;
 
+use crate::context_menu::TabContextMenu;
 use crate::line::tab_line;
 use crate::tab::tab_style;
 
@@ -16,6 +18,31 @@ pub struct LinePart {
     part: String,
     len: usize,
     tab_index: Option<usize>,
+    session_click: Option<SessionClick>,
+    scroll_to_tab: Option<usize>, // the collapsed tabs scroll the tab line to this tab position
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SessionClick {
+    OpenSessionManager,
+    SwitchTo(String),
+}
+
+/// How to display the other running sessions, configured with `other_sessions "count"` or
+/// `other_sessions "names"`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OtherSessions {
+    Count,
+    Names,
+}
+
+/// What clicking on the other sessions segment does, configured with
+/// `other_sessions_on_click "session-manager"` (the default) or `other_sessions_on_click "switch"`
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum OtherSessionsOnClick {
+    #[default]
+    SessionManager,
+    Switch,
 }
 
 #[derive(Default)]
@@ -24,20 +51,113 @@ struct State {
     active_tab_idx: usize,
     mode_info: ModeInfo,
     tab_line: Vec<LinePart>,
+    other_sessions: Vec<String>,
+    other_sessions_display: Option<OtherSessions>,
+    other_sessions_on_click: OtherSessionsOnClick,
+    scrolled_to_tab: Option<usize>, // the tab line is centered around the active tab if None
+    tab_template: Option<String>,
+    show_tab_index: bool, // prefix tab names with their index (eg. `1:editor`) for GoToTab bindings
+    dragged_tab: Option<usize>, // the position of the tab being dragged to reorder it with the mouse
+    tabs_with_bell: HashSet<usize>, // positions of tabs with a pane that rang the bell
+    tabs_with_silence: HashSet<usize>, // positions of tabs with a pane that went silent
+    context_menu: Option<TabContextMenu>,
+    hovered_tab: Option<usize>, // the position of the tab under the mouse
 }
 
 static ARROW_SEPARATOR: &str = "";
+static BELL_MARKER: &str = "!";
+static SILENCE_MARKER: &str = "~";
 
 register_plugin!(State);
 
+/// Renders the tab name from the `tab_template` configuration, eg. `tab_template "{name} ({panes})"`
+///
+/// Placeholders: `{name}`, `{index}` (the position of the tab starting from 1, as used by the
+/// `GoToTab` action), `{panes}` (the number of panes), `{command}` (the title of the focused
+/// pane, usually the command running in it), `{activity}` (a marker shown if a pane in the tab
+/// had output since it was last focused), `{bell}` (a marker shown if a pane in the tab rang the
+/// bell) and `{silence}` (a marker shown if a pane in the tab went silent, see the
+/// `monitor_silence` option)
+fn render_tab_template(
+    tab_template: &str,
+    tab: &TabInfo,
+    has_bell: bool,
+    is_silent: bool,
+) -> String {
+    // the name is replaced last so that placeholders in it are left alone
+    tab_template
+        .replace("{index}", &(tab.position + 1).to_string())
+        .replace("{panes}", &tab.pane_count.to_string())
+        .replace(
+            "{command}",
+            tab.focused_pane_title.as_deref().unwrap_or_default(),
+        )
+        .replace("{activity}", if tab.has_unseen_output { "●" } else { "" })
+        .replace("{bell}", if has_bell { BELL_MARKER } else { "" })
+        .replace("{silence}", if is_silent { SILENCE_MARKER } else { "" })
+        .replace("{name}", &tab.name)
+}
+
+/// The positions of the tabs that have a pane matching `pane_is_marked`
+fn marked_tabs(
+    pane_manifest: &PaneManifest,
+    pane_is_marked: fn(&PaneInfo) -> bool,
+) -> HashSet<usize> {
+    pane_manifest
+        .panes
+        .iter()
+        .filter(|(_tab_position, panes)| panes.iter().any(pane_is_marked))
+        .map(|(tab_position, _panes)| *tab_position)
+        .collect()
+}
+
+impl State {
+    /// The name shown in the tab's segment, rendered from the `tab_template` if one is configured
+    fn tab_name(&self, tab: &TabInfo) -> String {
+        let has_bell = self.tabs_with_bell.contains(&tab.position);
+        let is_silent = self.tabs_with_silence.contains(&tab.position);
+        if let Some(tab_template) = &self.tab_template {
+            return render_tab_template(tab_template, tab, has_bell, is_silent);
+        }
+        let mut tabname = tab.name.clone();
+        if self.show_tab_index {
+            tabname = format!("{}:{}", tab.position + 1, tabname);
+        }
+        if has_bell {
+            tabname = format!("{} {}", tabname, BELL_MARKER);
+        }
+        tabname
+    }
+}
+
 impl ZellijPlugin for State {
-    fn load(&mut self, _configuration: BTreeMap<String, String>) {
+    fn load(&mut self, configuration: BTreeMap<String, String>) {
         set_selectable(false);
         subscribe(&[
             EventType::TabUpdate,
+            EventType::PaneUpdate,
             EventType::ModeUpdate,
             EventType::Mouse,
         ]);
+        self.other_sessions_display = match configuration.get("other_sessions").map(|s| s.as_str())
+        {
+            Some("count") => Some(OtherSessions::Count),
+            Some("names") => Some(OtherSessions::Names),
+            _ => None,
+        };
+        if configuration
+            .get("other_sessions_on_click")
+            .map(|s| s.as_str())
+            == Some("switch")
+        {
+            self.other_sessions_on_click = OtherSessionsOnClick::Switch;
+        }
+        if self.other_sessions_display.is_some() {
+            subscribe(&[EventType::SessionUpdate]);
+        }
+        self.tab_template = configuration.get("tab_template").cloned();
+        self.show_tab_index =
+            configuration.get("show_tab_index").map(|s| s.as_str()) == Some("true");
     }
 
     fn update(&mut self, event: Event) -> bool {
@@ -57,17 +177,122 @@ impl ZellijPlugin for State {
                     if self.active_tab_idx != active_tab_idx || self.tabs != tabs {
                         should_render = true;
                     }
+                    if self.active_tab_idx != active_tab_idx || self.tabs.len() != tabs.len() {
+                        // bring the active tab back into view
+                        self.scrolled_to_tab = None;
+                    }
                     self.active_tab_idx = active_tab_idx;
                     self.tabs = tabs;
+                    if self
+                        .context_menu
+                        .as_ref()
+                        .map(|menu| menu.is_stale(&self.tabs))
+                        .unwrap_or(false)
+                    {
+                        self.context_menu = None;
+                        should_render = true;
+                    }
                 } else {
                     eprintln!("Could not find active tab.");
                 }
             },
+            Event::PaneUpdate(pane_manifest) => {
+                let tabs_with_bell = marked_tabs(&pane_manifest, |pane| pane.has_bell);
+                let tabs_with_silence = marked_tabs(&pane_manifest, |pane| pane.is_silent);
+                if self.tabs_with_bell != tabs_with_bell
+                    || self.tabs_with_silence != tabs_with_silence
+                {
+                    should_render = true;
+                }
+                self.tabs_with_bell = tabs_with_bell;
+                self.tabs_with_silence = tabs_with_silence;
+            },
+            Event::SessionUpdate(session_infos, _resurrectable_sessions) => {
+                let mut other_sessions: Vec<String> = session_infos
+                    .iter()
+                    .filter(|s| !s.is_current_session)
+                    .map(|s| s.name.clone())
+                    .collect();
+                other_sessions.sort();
+                if self.other_sessions != other_sessions {
+                    should_render = true;
+                }
+                self.other_sessions = other_sessions;
+            },
             Event::Mouse(me) => match me {
+                Mouse::LeftClick(_, col) if self.context_menu.is_some() => {
+                    if let Some(context_menu) = self.context_menu.take() {
+                        context_menu.click(col, self.tabs.len());
+                    }
+                    should_render = true;
+                },
                 Mouse::LeftClick(_, col) => {
-                    let tab_to_focus = get_tab_to_focus(&self.tab_line, self.active_tab_idx, col);
-                    if let Some(idx) = tab_to_focus {
-                        switch_tab_to(idx.try_into().unwrap());
+                    let clicked_line_part = get_clicked_line_part(&self.tab_line, col);
+                    if let Some(scroll_to_tab) =
+                        clicked_line_part.and_then(|part| part.scroll_to_tab)
+                    {
+                        self.scrolled_to_tab = Some(scroll_to_tab);
+                        return true;
+                    }
+                    match clicked_line_part.and_then(|part| part.session_click.as_ref()) {
+                        Some(SessionClick::SwitchTo(session_name)) => {
+                            switch_session(Some(session_name));
+                        },
+                        Some(SessionClick::OpenSessionManager) => {
+                            start_or_reload_plugin("zellij:session-manager");
+                        },
+                        None => {
+                            self.dragged_tab = clicked_line_part.and_then(|part| part.tab_index);
+                            let tab_to_focus =
+                                get_tab_to_focus(&self.tab_line, self.active_tab_idx, col);
+                            if let Some(idx) = tab_to_focus {
+                                switch_tab_to(idx.try_into().unwrap());
+                            }
+                        },
+                    }
+                },
+                Mouse::Hold(_, col) => {
+                    let tab_under_mouse =
+                        get_clicked_line_part(&self.tab_line, col).and_then(|part| part.tab_index);
+                    if let (Some(dragged_tab), Some(tab_under_mouse)) =
+                        (self.dragged_tab, tab_under_mouse)
+                    {
+                        if dragged_tab != tab_under_mouse {
+                            move_tab_to_index(dragged_tab as u32, tab_under_mouse as u32);
+                            self.dragged_tab = Some(tab_under_mouse);
+                        }
+                    }
+                },
+                Mouse::Release(..) => {
+                    self.dragged_tab = None;
+                },
+                Mouse::RightClick(_, col) => {
+                    let clicked_tab = get_clicked_line_part(&self.tab_line, col)
+                        .and_then(|part| part.tab_index)
+                        .and_then(|tab_index| self.tabs.iter().find(|t| t.position == tab_index));
+                    if let Some(tab) = clicked_tab {
+                        self.context_menu = Some(TabContextMenu::new(tab));
+                        should_render = true;
+                    }
+                },
+                Mouse::Hover(_, col) => {
+                    let hovered_tab =
+                        get_clicked_line_part(&self.tab_line, col).and_then(|part| part.tab_index);
+                    if self.hovered_tab != hovered_tab {
+                        self.hovered_tab = hovered_tab;
+                        should_render = true;
+                    }
+                },
+                Mouse::HoverEnd => {
+                    if self.hovered_tab.take().is_some() {
+                        should_render = true;
+                    }
+                },
+                Mouse::MiddleClick(_, col) => {
+                    let clicked_tab =
+                        get_clicked_line_part(&self.tab_line, col).and_then(|part| part.tab_index);
+                    if let Some(tab_index) = clicked_tab {
+                        close_tab_with_index(tab_index as u32);
                     }
                 },
                 Mouse::ScrollUp(_) => {
@@ -76,7 +301,6 @@ impl ZellijPlugin for State {
                 Mouse::ScrollDown(_) => {
                     switch_tab_to(max(self.active_tab_idx.saturating_sub(1), 1) as u32);
                 },
-                _ => {},
             },
             _ => {
                 eprintln!("Got unrecognized event: {:?}", event);
@@ -92,40 +316,54 @@ impl ZellijPlugin for State {
         let mut all_tabs: Vec<LinePart> = vec![];
         let mut active_tab_index = 0;
         let mut is_alternate_tab = false;
-        for t in &mut self.tabs {
+        for t in &self.tabs {
             let mut tabname = t.name.clone();
             if t.active && self.mode_info.mode == InputMode::RenameTab {
                 if tabname.is_empty() {
                     tabname = String::from("Enter name...");
                 }
                 active_tab_index = t.position;
-            } else if t.active {
-                active_tab_index = t.position;
+            } else {
+                tabname = self.tab_name(t);
+                if t.active {
+                    active_tab_index = t.position;
+                }
             }
             let tab = tab_style(
                 tabname,
                 t,
                 is_alternate_tab,
+                self.hovered_tab == Some(t.position),
                 self.mode_info.style.colors,
                 self.mode_info.capabilities,
             );
             is_alternate_tab = !is_alternate_tab;
             all_tabs.push(tab);
         }
+        let centered_tab_index = self
+            .scrolled_to_tab
+            .filter(|tab_index| *tab_index < all_tabs.len())
+            .unwrap_or(active_tab_index);
         self.tab_line = tab_line(
             self.mode_info.session_name.as_deref(),
             all_tabs,
-            active_tab_index,
+            centered_tab_index,
             cols.saturating_sub(1),
             self.mode_info.style.colors,
             self.mode_info.capabilities,
             self.mode_info.style.hide_session_name,
+            &self.other_sessions,
+            self.other_sessions_display,
+            self.other_sessions_on_click,
         );
 
-        let output = self
-            .tab_line
-            .iter()
-            .fold(String::new(), |output, part| output + &part.part);
+        let output = match &self.context_menu {
+            Some(context_menu) => context_menu.render(cols, &self.mode_info.style.styling),
+            None => self
+                .tab_line
+                .iter()
+                .fold(String::new(), |output, part| output + &part.part),
+        };
 
         let background = match self.mode_info.style.colors.theme_hue {
             ThemeHue::Dark => self.mode_info.style.colors.black,
@@ -141,3 +379,41 @@ impl ZellijPlugin for State {
         }
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    fn tab_named_editor_in_second_position() -> TabInfo {
+        TabInfo {
+            position: 1,
+            name: "editor".to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tab_index_is_shown_only_when_enabled() {
+        let tab = tab_named_editor_in_second_position();
+        let mut state = State::default();
+        assert_eq!(state.tab_name(&tab), "editor");
+        state.show_tab_index = true;
+        assert_eq!(state.tab_name(&tab), "2:editor");
+    }
+
+    #[test]
+    fn index_placeholder_is_filled_in_regardless_of_show_tab_index() {
+        let tab = tab_named_editor_in_second_position();
+        let mut state = State {
+            tab_template: Some("{index}-{name}".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(state.tab_name(&tab), "2-editor");
+        state.show_tab_index = true;
+        assert_eq!(
+            state.tab_name(&tab),
+            "2-editor",
+            "the template decides where the index goes"
+        );
+    }
+}
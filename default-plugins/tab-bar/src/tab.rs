@@ -2,7 +2,7 @@ use crate::{line::tab_separator, LinePart};
 use ansi_term::{ANSIString, ANSIStrings};
 use unicode_width::UnicodeWidthStr;
 use zellij_tile::prelude::*;
-use zellij_tile_utils::style;
+use zellij_tile_utils::{contrasting_foreground, style};
 
 fn cursors(focused_clients: &[ClientId], palette: Palette) -> (Vec<ANSIString>, usize) {
     // cursor section, text length
@@ -21,6 +21,7 @@ pub fn render_tab(
     text: String,
     tab: &TabInfo,
     is_alternate_tab: bool,
+    is_hovered: bool,
     palette: Palette,
     separator: &str,
 ) -> LinePart {
@@ -33,6 +34,8 @@ pub fn render_tab(
     };
     let background_color = if tab.active {
         palette.green
+    } else if is_hovered {
+        palette.cyan
     } else if is_alternate_tab {
         alternate_tab_color
     } else {
@@ -42,9 +45,11 @@ pub fn render_tab(
         ThemeHue::Dark => palette.black,
         ThemeHue::Light => palette.white,
     };
+    // the tab's text is drawn on its background, the separators blend into the bar's background
+    let text_color = contrasting_foreground(background_color, &palette);
     let left_separator = style!(foreground_color, background_color).paint(separator);
     let mut tab_text_len = text.width() + (separator_width * 2) + 2; // +2 for padding
-    let tab_styled_text = style!(foreground_color, background_color)
+    let tab_styled_text = style!(text_color, background_color)
         .bold()
         .paint(format!(" {} ", text));
 
@@ -53,12 +58,12 @@ pub fn render_tab(
         let (cursor_section, extra_length) = cursors(focused_clients, palette);
         tab_text_len += extra_length;
         let mut s = String::new();
-        let cursor_beginning = style!(foreground_color, background_color)
+        let cursor_beginning = style!(text_color, background_color)
             .bold()
             .paint("[")
             .to_string();
         let cursor_section = ANSIStrings(&cursor_section).to_string();
-        let cursor_end = style!(foreground_color, background_color)
+        let cursor_end = style!(text_color, background_color)
             .bold()
             .paint("]")
             .to_string();
@@ -77,6 +82,8 @@ pub fn render_tab(
         part: tab_styled_text,
         len: tab_text_len,
         tab_index: Some(tab.position),
+        session_click: None,
+        scroll_to_tab: None,
     }
 }
 
@@ -84,6 +91,7 @@ pub fn tab_style(
     mut tabname: String,
     tab: &TabInfo,
     mut is_alternate_tab: bool,
+    is_hovered: bool,
     palette: Palette,
     capabilities: PluginCapabilities,
 ) -> LinePart {
@@ -97,7 +105,14 @@ pub fn tab_style(
         is_alternate_tab = false;
     }
 
-    render_tab(tabname, tab, is_alternate_tab, palette, separator)
+    render_tab(
+        tabname,
+        tab,
+        is_alternate_tab,
+        is_hovered,
+        palette,
+        separator,
+    )
 }
 
 pub(crate) fn get_tab_to_focus(
@@ -1,19 +1,24 @@
 use dialoguer::Confirm;
-use std::{fs::File, io::prelude::*, path::PathBuf, process};
+use std::{collections::BTreeMap, fs::File, io::prelude::*, path::PathBuf, process, thread};
 
 use crate::sessions::{
     assert_session, assert_session_ne, get_active_session, get_name_generator, get_sessions,
     get_sessions_sorted_by_mtime, kill_session as kill_session_impl, match_session_name,
-    print_sessions, print_sessions_with_index, session_exists, ActiveSession, SessionNameMatch,
+    print_sessions, print_sessions_with_index, session_exists, session_name_from_template,
+    ActiveSession, SessionNameMatch,
 };
 use zellij_client::{
+    create_detached_session,
     old_config_converter::{
         config_yaml_to_config_kdl, convert_old_yaml_files, layout_yaml_to_layout_kdl,
     },
-    os_input_output::get_client_os_input,
-    start_client as start_client_impl, ClientInfo,
+    os_input_output::{get_cli_client_os_input, get_client_os_input, ClientOsInputOutput},
+    prepare_detached_session, start_client as start_client_impl, start_server_detached, ClientInfo,
+};
+use zellij_server::{
+    os_input_output::get_server_os_input, run_server, socket_activation_listener,
+    start_server as start_server_impl,
 };
-use zellij_server::{os_input_output::get_server_os_input, start_server as start_server_impl};
 use zellij_utils::{
     cli::{CliArgs, Command, SessionCommand, Sessions},
     data::ConnectToSession,
@@ -21,14 +26,16 @@ use zellij_utils::{
     input::{
         actions::Action,
         config::{Config, ConfigError},
+        layout::Layout,
         options::Options,
     },
     miette::{Report, Result},
     nix,
-    setup::Setup,
+    session_serialization::{session_layout_cache_dir, SESSION_LAYOUT_FILE_NAME},
+    setup::{find_default_config_dir, get_layout_dir, Setup},
 };
 
-pub(crate) use crate::sessions::list_sessions;
+pub(crate) use crate::sessions::{list_sessions, list_sessions_for_completion};
 
 pub(crate) fn kill_all_sessions(yes: bool) {
     match get_sessions() {
@@ -93,8 +100,72 @@ pub(crate) fn start_server(path: PathBuf, debug: bool) {
     start_server_impl(Box::new(os_input), path);
 }
 
-fn create_new_client() -> ClientInfo {
-    ClientInfo::New(generate_unique_session_name())
+/// Runs a session's server in this process (rather than forking it to the background) and
+/// creates the session in it without attaching to it
+pub(crate) fn start_daemon(opts: CliArgs, session_name: Option<String>, resurrect: bool) {
+    let (config, layout, config_options) = match Setup::from_cli_args(&opts) {
+        Ok(results) => results,
+        Err(e) => {
+            if let ConfigError::KdlError(error) = e {
+                let report: Report = error.into();
+                eprintln!("{:?}", report);
+            } else {
+                eprintln!("{}", e);
+            }
+            process::exit(1);
+        },
+    };
+    // this has to be taken before looking for existing sessions, since connecting to the
+    // socket systemd is holding for us would block until we accept the connection
+    let listener = socket_activation_listener();
+    let session_name = match (session_name, &listener) {
+        (Some(session_name), Some(_)) => session_name,
+        (None, Some(_)) => {
+            eprintln!("Please specify the name of the session the socket was created for.");
+            process::exit(1);
+        },
+        (Some(session_name), None) => {
+            assert_session_ne(&session_name);
+            session_name
+        },
+        (None, None) => generate_unique_session_name(&config_options),
+    };
+    let layout = if resurrect
+        && session_layout_cache_dir(&session_name)
+            .join(SESSION_LAYOUT_FILE_NAME)
+            .exists()
+    {
+        get_resurrection_layout(&session_name, config.clone())
+    } else {
+        layout
+    };
+    log::info!("Starting Zellij session {} as a daemon", session_name);
+
+    zellij_utils::consts::DEBUG_MODE.set(opts.debug).unwrap();
+    let socket_path = prepare_detached_session(&config, &session_name);
+    let _ = thread::Builder::new()
+        .name("daemon_session".to_string())
+        .spawn({
+            let socket_path = socket_path.clone();
+            move || {
+                let os_input = get_os_input(get_cli_client_os_input);
+                create_detached_session(
+                    Box::new(os_input),
+                    &socket_path,
+                    opts,
+                    config,
+                    config_options,
+                    session_name,
+                    layout,
+                );
+            }
+        });
+    let os_input = get_os_input(get_server_os_input);
+    run_server(Box::new(os_input), socket_path, listener);
+}
+
+fn create_new_client(config_options: &Options) -> ClientInfo {
+    ClientInfo::New(generate_unique_session_name(config_options))
 }
 
 fn find_indexed_session(
@@ -105,7 +176,7 @@ fn find_indexed_session(
 ) -> ClientInfo {
     match sessions.get(index) {
         Some(session) => ClientInfo::Attach(session.clone(), config_options),
-        None if create => create_new_client(),
+        None if create => create_new_client(&config_options),
         None => {
             println!(
                 "No session indexed by {} found. The following sessions are active:",
@@ -122,6 +193,42 @@ pub(crate) fn send_action_to_session(
     requested_session_name: Option<String>,
     config: Option<Config>,
 ) {
+    let session_name = session_to_send_actions_to(requested_session_name);
+    attach_with_cli_client(cli_action, &session_name, config);
+}
+pub(crate) fn start_control_mode_client(
+    requested_session_name: Option<String>,
+    config: Option<Config>,
+) {
+    let session_name = session_to_send_actions_to(requested_session_name);
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    zellij_client::control_mode::start_control_mode_client(
+        Box::new(os_input),
+        &session_name,
+        config,
+    );
+}
+pub(crate) fn send_pipe_to_session(
+    requested_session_name: Option<String>,
+    name: Option<String>,
+    payload: Option<String>,
+    args: Option<BTreeMap<String, String>>,
+    plugin: Option<String>,
+    pane_id: Option<u32>,
+) {
+    let session_name = session_to_send_actions_to(requested_session_name);
+    let os_input = get_os_input(zellij_client::os_input_output::get_cli_client_os_input);
+    zellij_client::cli_client::start_cli_pipe_client(
+        Box::new(os_input),
+        &session_name,
+        name,
+        payload,
+        args,
+        plugin,
+        pane_id,
+    );
+}
+fn session_to_send_actions_to(requested_session_name: Option<String>) -> String {
     match get_active_session() {
         ActiveSession::None => {
             eprintln!("There is no active session!");
@@ -138,13 +245,13 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             }
-            attach_with_cli_client(cli_action, &session_name, config);
+            session_name
         },
         ActiveSession::Many => {
             let existing_sessions = get_sessions().unwrap();
             if let Some(session_name) = requested_session_name {
                 if existing_sessions.contains(&session_name) {
-                    attach_with_cli_client(cli_action, &session_name, config);
+                    session_name
                 } else {
                     eprintln!(
                         "Session '{}' not found. The following sessions are active:",
@@ -154,14 +261,14 @@ pub(crate) fn send_action_to_session(
                     std::process::exit(1);
                 }
             } else if let Ok(session_name) = envs::get_session_name() {
-                attach_with_cli_client(cli_action, &session_name, config);
+                session_name
             } else {
                 eprintln!("Please specify the session name to send actions to. The following sessions are active:");
                 print_sessions(existing_sessions);
                 std::process::exit(1);
             }
         },
-    };
+    }
 }
 pub(crate) fn convert_old_config_file(old_config_file: PathBuf) {
     match File::open(&old_config_file) {
@@ -257,7 +364,7 @@ fn attach_with_session_index(config_options: Options, index: usize, create: bool
     match get_sessions_sorted_by_mtime() {
         Ok(sessions) if sessions.is_empty() => {
             if create {
-                create_new_client()
+                create_new_client(&config_options)
             } else {
                 eprintln!("No active zellij sessions found.");
                 process::exit(1);
@@ -302,7 +409,7 @@ fn attach_with_session_name(
             },
         },
         None => match get_active_session() {
-            ActiveSession::None if create => create_new_client(),
+            ActiveSession::None if create => create_new_client(&config_options),
             ActiveSession::None => {
                 eprintln!("No active zellij sessions found.");
                 process::exit(1);
@@ -333,11 +440,18 @@ pub(crate) fn start_client(opts: CliArgs) {
         },
     };
     let mut reconnect_to_session: Option<ConnectToSession> = None;
-    let os_input = get_os_input(get_client_os_input);
+    // sessions started in the background might be started from scripts without a terminal
+    let os_input = match opts.command {
+        Some(Command::Sessions(Sessions::Attach {
+            create_background: true,
+            ..
+        })) => get_os_input(get_cli_client_os_input),
+        _ => get_os_input(get_client_os_input),
+    };
     loop {
         let os_input = os_input.clone();
         let config = config.clone();
-        let layout = layout.clone();
+        let mut layout = layout.clone();
         let mut config_options = config_options.clone();
         let mut opts = opts.clone();
 
@@ -346,11 +460,23 @@ pub(crate) fn start_client(opts: CliArgs) {
             // untested and pretty involved function
             //
             // ideally, we should write tests for this whole function and refctor it
-            if reconnect_to_session.name.is_some() {
+            if let Some(reconnect_layout) = &reconnect_to_session.layout {
+                layout = get_reconnect_layout(reconnect_layout, &opts, &config_options, &config)
+                    .unwrap_or(layout);
+            }
+            if let Some(session_name) = &reconnect_to_session.name {
+                // sessions that are not running but were serialized are resurrected rather than
+                // created anew
+                let resurrect = !session_exists(session_name).unwrap_or(false)
+                    && session_layout_cache_dir(session_name)
+                        .join(SESSION_LAYOUT_FILE_NAME)
+                        .exists();
                 opts.command = Some(Command::Sessions(Sessions::Attach {
-                    session_name: reconnect_to_session.name.clone(),
+                    session_name: Some(session_name.clone()),
                     create: true,
+                    create_background: false,
                     index: None,
+                    resurrect,
                     options: None,
                 }));
             } else {
@@ -367,10 +493,13 @@ pub(crate) fn start_client(opts: CliArgs) {
         if let Some(Command::Sessions(Sessions::Attach {
             session_name,
             create,
+            create_background,
             index,
+            resurrect,
             options,
         })) = opts.command.clone()
         {
+            let create = create || create_background;
             let config_options = match options.as_deref() {
                 Some(SessionCommand::Options(o)) => {
                     config_options.merge_from_cli(o.to_owned().into())
@@ -378,7 +507,27 @@ pub(crate) fn start_client(opts: CliArgs) {
                 None => config_options,
             };
 
-            let client = if let Some(idx) = index {
+            let resurrected_session = if resurrect {
+                match session_name.as_ref() {
+                    Some(session_name) => {
+                        Some(session_name.clone()).filter(|s| !session_exists(s).unwrap_or(false))
+                    },
+                    None => {
+                        eprintln!("Please specify the name of the session to resurrect.");
+                        process::exit(1);
+                    },
+                }
+            } else {
+                None
+            };
+            let resurrection_layout = resurrected_session
+                .as_ref()
+                .map(|session_name| get_resurrection_layout(session_name, config.clone()));
+
+            let client = if let Some(session_name) = resurrected_session {
+                start_client_plan(session_name.clone());
+                ClientInfo::New(session_name)
+            } else if let Some(idx) = index {
                 attach_with_session_index(config_options.clone(), idx, create)
             } else {
                 let session_exists = session_name
@@ -391,6 +540,18 @@ pub(crate) fn start_client(opts: CliArgs) {
                 attach_with_session_name(session_name, config_options.clone(), create)
             };
 
+            if create_background {
+                start_background_session(
+                    os_input,
+                    opts,
+                    config,
+                    config_options,
+                    client,
+                    resurrection_layout.unwrap_or(layout),
+                );
+                break;
+            }
+
             if let Ok(val) = std::env::var(envs::SESSION_NAME_ENV_KEY) {
                 if val == *client.get_session_name() {
                     panic!("You are trying to attach to the current session (\"{}\"). This is not supported.", val);
@@ -399,7 +560,7 @@ pub(crate) fn start_client(opts: CliArgs) {
 
             let attach_layout = match client {
                 ClientInfo::Attach(_, _) => None,
-                ClientInfo::New(_) => Some(layout),
+                ClientInfo::New(_) => Some(resurrection_layout.unwrap_or(layout)),
             };
 
             let tab_position_to_focus = reconnect_to_session
@@ -490,7 +651,7 @@ pub(crate) fn start_client(opts: CliArgs) {
                     process::exit(0);
                 }
 
-                let session_name = generate_unique_session_name();
+                let session_name = generate_unique_session_name(&config_options);
                 start_client_plan(session_name.clone());
                 reconnect_to_session = start_client_impl(
                     Box::new(os_input),
@@ -510,13 +671,93 @@ pub(crate) fn start_client(opts: CliArgs) {
     }
 }
 
-fn generate_unique_session_name() -> String {
+fn start_background_session(
+    os_input: ClientOsInputOutput,
+    opts: CliArgs,
+    config: Config,
+    config_options: Options,
+    client: ClientInfo,
+    layout: Layout,
+) {
+    match client {
+        ClientInfo::Attach(session_name, _) => {
+            println!("Session \"{}\" is already running.", session_name);
+        },
+        ClientInfo::New(session_name) => {
+            println!("Starting session \"{}\" in the background.", session_name);
+            start_server_detached(
+                Box::new(os_input),
+                opts,
+                config,
+                config_options,
+                session_name,
+                layout,
+            );
+        },
+    }
+}
+
+fn get_reconnect_layout(
+    layout: &str,
+    opts: &CliArgs,
+    config_options: &Options,
+    config: &Config,
+) -> Option<Layout> {
+    let layout_dir = config_options
+        .layout_dir
+        .clone()
+        .or_else(|| get_layout_dir(opts.config_dir.clone().or_else(find_default_config_dir)));
+    match Layout::from_path_or_default(Some(&PathBuf::from(layout)), layout_dir, config.clone()) {
+        Ok((layout, _config)) => Some(layout),
+        Err(e) => {
+            log::error!("Failed to load layout {}: {}", layout, e);
+            None
+        },
+    }
+}
+
+fn get_resurrection_layout(session_name: &str, config: Config) -> Layout {
+    let layout_path = session_layout_cache_dir(session_name).join(SESSION_LAYOUT_FILE_NAME);
+    if !layout_path.exists() {
+        eprintln!(
+            "No serialized layout found for session \"{}\", cannot resurrect it.",
+            session_name
+        );
+        process::exit(1);
+    }
+    match Layout::from_path_or_default(Some(&layout_path), None, config) {
+        Ok((layout, _config)) => layout,
+        Err(e) => {
+            eprintln!(
+                "Failed to load the serialized layout of session \"{}\": {}",
+                session_name, e
+            );
+            process::exit(1);
+        },
+    }
+}
+
+fn generate_unique_session_name(config_options: &Options) -> String {
     let sessions = get_sessions();
     let Ok(sessions) = sessions else {
         eprintln!("Failed to list existing sessions: {:?}", sessions);
         process::exit(1);
     };
 
+    if let Some(name) = config_options
+        .session_name_template
+        .as_deref()
+        .and_then(session_name_from_template)
+    {
+        // a numbered suffix tells apart sessions started with the same template in the same place
+        let name = std::iter::once(name.clone())
+            .chain((2..).map(|suffix| format!("{}-{}", name, suffix)))
+            .find(|name| !sessions.contains(name));
+        if let Some(name) = name {
+            return name;
+        }
+    }
+
     let name = get_name_generator()
         .take(1000)
         .find(|name| !sessions.contains(name));
@@ -6,13 +6,17 @@ mod tests;
 use zellij_utils::{
     clap::Parser,
     cli::{CliAction, CliArgs, Command, Sessions},
-    input::config::Config,
+    input::{config::Config, layout::PluginUserConfiguration},
     logging::*,
 };
 
 fn main() {
-    configure_logger();
     let opts = CliArgs::parse();
+    if let Some(Command::Sessions(Sessions::Daemon { .. })) = opts.command {
+        configure_daemon_logger();
+    } else {
+        configure_logger();
+    }
 
     {
         let config = Config::try_from(&opts).ok();
@@ -20,6 +24,22 @@ fn main() {
             commands::send_action_to_session(cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::ControlMode)) = opts.command {
+            commands::start_control_mode_client(opts.session, config);
+            std::process::exit(0);
+        }
+        if let Some(Command::Sessions(Sessions::Pipe {
+            name,
+            payload,
+            args,
+            plugin,
+            pane_id,
+        })) = opts.command
+        {
+            let args = args.map(|args| args.inner().clone());
+            commands::send_pipe_to_session(opts.session, name, payload, args, plugin, pane_id);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::Run {
             command,
             direction,
@@ -28,6 +48,8 @@ fn main() {
             name,
             close_on_exit,
             start_suspended,
+            in_place,
+            wait,
         })) = opts.command
         {
             let command_cli_action = CliAction::NewPane {
@@ -40,10 +62,23 @@ fn main() {
                 close_on_exit,
                 start_suspended,
                 configuration: None,
+                in_place,
+                wait,
             };
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Sessions(Sessions::Plugin {
+            url,
+            floating,
+            configuration,
+            in_place,
+        })) = opts.command
+        {
+            let command_cli_action = plugin_pane_action(url, floating, configuration, in_place);
+            commands::send_action_to_session(command_cli_action, opts.session, config);
+            std::process::exit(0);
+        }
         if let Some(Command::Sessions(Sessions::Edit {
             file,
             direction,
@@ -69,6 +104,14 @@ fn main() {
             commands::send_action_to_session(command_cli_action, opts.session, config);
             std::process::exit(0);
         }
+        if let Some(Command::Setup(ref setup)) = opts.command {
+            // this is called by the shell completions on every tab press, so we don't want to
+            // load the configuration and layout like the rest of the setup commands do
+            if setup.list_sessions_for_completion {
+                commands::list_sessions_for_completion();
+                std::process::exit(0);
+            }
+        }
         if let Some(Command::Sessions(Sessions::ConvertConfig { old_config_file })) = opts.command {
             commands::convert_old_config_file(old_config_file);
             std::process::exit(0);
@@ -83,17 +126,45 @@ fn main() {
         }
     }
 
-    if let Some(Command::Sessions(Sessions::ListSessions)) = opts.command {
-        commands::list_sessions();
+    if let Some(Command::Sessions(Sessions::ListSessions { group })) = opts.command {
+        commands::list_sessions(group);
     } else if let Some(Command::Sessions(Sessions::KillAllSessions { yes })) = opts.command {
         commands::kill_all_sessions(yes);
     } else if let Some(Command::Sessions(Sessions::KillSession { ref target_session })) =
         opts.command
     {
         commands::kill_session(target_session);
+    } else if let Some(Command::Sessions(Sessions::Daemon {
+        session_name,
+        resurrect,
+    })) = opts.command.clone()
+    {
+        commands::start_daemon(opts, session_name, resurrect);
     } else if let Some(path) = opts.server {
         commands::start_server(path, opts.debug);
     } else {
         commands::start_client(opts);
     }
 }
+
+/// The action `zellij plugin` sends to the session, opening the plugin in a new pane
+fn plugin_pane_action(
+    url: String,
+    floating: bool,
+    configuration: Option<PluginUserConfiguration>,
+    in_place: bool,
+) -> CliAction {
+    CliAction::NewPane {
+        command: vec![],
+        plugin: Some(url),
+        direction: None,
+        cwd: None,
+        floating,
+        name: None,
+        close_on_exit: false,
+        start_suspended: false,
+        configuration,
+        in_place,
+        wait: false,
+    }
+}
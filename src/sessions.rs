@@ -1,13 +1,16 @@
 use std::os::unix::fs::FileTypeExt;
+use std::path::Path;
 use std::time::SystemTime;
 use std::{fs, io, process};
 use suggest::Suggest;
 use zellij_utils::{
     anyhow,
-    consts::ZELLIJ_SOCK_DIR,
+    consts::{ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR},
+    data::SessionInfo,
     envs,
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientToServerMsg, IpcReceiverWithContext, IpcSenderWithContext, ServerToClientMsg},
+    shared::fill_template,
 };
 
 pub(crate) fn get_sessions() -> Result<Vec<String>, io::ErrorKind> {
@@ -82,6 +85,15 @@ pub(crate) fn print_sessions(sessions: Vec<String>) {
     })
 }
 
+/// Prints the names of the running sessions for the shell completions, without any decoration
+pub(crate) fn list_sessions_for_completion() {
+    if let Ok(sessions) = get_sessions() {
+        for session in sessions {
+            println!("{}", session);
+        }
+    }
+}
+
 pub(crate) fn print_sessions_with_index(sessions: Vec<String>) {
     let curr_session = envs::get_session_name().unwrap_or_else(|_| "".into());
     for (i, session) in sessions.iter().enumerate() {
@@ -125,8 +137,22 @@ pub(crate) fn kill_session(name: &str) {
     };
 }
 
-pub(crate) fn list_sessions() {
-    let exit_code = match get_sessions() {
+fn get_session_group(session_name: &str) -> Option<String> {
+    let session_cache_file_name =
+        ZELLIJ_SESSION_INFO_CACHE_DIR.join(format!("{}.kdl", session_name));
+    let raw_session_info = fs::read_to_string(session_cache_file_name).ok()?;
+    SessionInfo::from_string(&raw_session_info, "").ok()?.group
+}
+
+pub(crate) fn list_sessions(group: Option<String>) {
+    let sessions = get_sessions().map(|sessions| match group {
+        Some(group) => sessions
+            .into_iter()
+            .filter(|s| get_session_group(s).as_ref() == Some(&group))
+            .collect(),
+        None => sessions,
+    });
+    let exit_code = match sessions {
         Ok(sessions) if !sessions.is_empty() => {
             print_sessions(sessions);
             0
@@ -220,6 +246,44 @@ pub(crate) fn assert_session_ne(name: &str) {
     process::exit(1);
 }
 
+/// Fill in the `session_name_template` option for a session started in the current directory
+///
+/// Placeholders: `{cwd_base}` (the name of the current directory), `{git_repo}` (the name of the
+/// git repository it is in) and `{git_branch}` (the branch checked out in that repository). Returns
+/// `None` if nothing is left of the name once the placeholders are filled in.
+pub(crate) fn session_name_from_template(template: &str) -> Option<String> {
+    let cwd = std::env::current_dir().ok()?;
+    let repository = cwd.ancestors().find(|dir| dir.join(".git").exists());
+    let file_name = |path: &Path| {
+        path.file_name()
+            .map(|name| name.to_string_lossy().to_string())
+    };
+    let name = fill_template(template, |placeholder| match placeholder {
+        "cwd_base" => Some(file_name(&cwd).unwrap_or_default()),
+        "git_repo" => Some(repository.and_then(file_name).unwrap_or_default()),
+        "git_branch" => Some(
+            repository
+                .and_then(|repository| fs::read_to_string(repository.join(".git/HEAD")).ok())
+                .and_then(|head| {
+                    head.trim()
+                        .strip_prefix("ref: refs/heads/")
+                        .map(String::from)
+                })
+                .unwrap_or_default(),
+        ),
+        _ => None,
+    });
+    // session names are socket file names, and the separators around empty placeholders are
+    // trimmed away
+    let name = name.replace('/', "-");
+    let name = name.trim_matches(|c: char| c.is_whitespace() || c == '-' || c == '_' || c == '.');
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_owned())
+    }
+}
+
 /// Create a new random name generator
 ///
 /// Used to provide a memorable handle for a session when users don't specify a session name when the session is
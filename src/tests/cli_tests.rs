@@ -0,0 +1,75 @@
+use crate::plugin_pane_action;
+use zellij_utils::{
+    clap::Parser,
+    cli::{CliAction, CliArgs, Command, Sessions},
+};
+
+fn new_pane_action_from_args(args: &[&str]) -> CliAction {
+    match CliArgs::try_parse_from(args)
+        .expect("Could not parse the cli arguments")
+        .command
+    {
+        Some(Command::Sessions(Sessions::Plugin {
+            url,
+            floating,
+            configuration,
+            in_place,
+        })) => plugin_pane_action(url, floating, configuration, in_place),
+        command => panic!("Parsed {:?} rather than the plugin subcommand", command),
+    }
+}
+
+#[test]
+fn plugin_subcommand_opens_the_plugin_in_a_new_pane() {
+    match new_pane_action_from_args(&["zellij", "plugin", "--", "zellij:strider"]) {
+        CliAction::NewPane {
+            command,
+            plugin,
+            floating,
+            configuration,
+            in_place,
+            ..
+        } => {
+            assert!(command.is_empty());
+            assert_eq!(plugin.as_deref(), Some("zellij:strider"));
+            assert!(!floating);
+            assert!(configuration.is_none());
+            assert!(!in_place);
+        },
+        action => panic!("Got {:?} rather than a new pane", action),
+    }
+}
+
+#[test]
+fn plugin_subcommand_passes_floating_and_configuration_to_the_new_pane() {
+    let args = [
+        "zellij",
+        "plugin",
+        "--floating",
+        "--configuration",
+        "cwd_hint=/tmp,theme=dark",
+        "--",
+        "file:/path/to/plugin.wasm",
+    ];
+    match new_pane_action_from_args(&args) {
+        CliAction::NewPane {
+            plugin,
+            floating,
+            configuration,
+            ..
+        } => {
+            assert_eq!(plugin.as_deref(), Some("file:/path/to/plugin.wasm"));
+            assert!(floating);
+            let configuration = configuration.expect("Configuration is passed on");
+            assert_eq!(
+                configuration.inner().get("cwd_hint").map(|s| s.as_str()),
+                Some("/tmp")
+            );
+            assert_eq!(
+                configuration.inner().get("theme").map(|s| s.as_str()),
+                Some("dark")
+            );
+        },
+        action => panic!("Got {:?} rather than a new pane", action),
+    }
+}
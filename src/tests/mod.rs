@@ -1 +1,2 @@
+mod cli_tests;
 pub mod e2e;
@@ -36,6 +36,7 @@ lazy_static::lazy_static! {
         WorkspaceMember{crate_name: "default-plugins/tab-bar", build: true},
         WorkspaceMember{crate_name: "default-plugins/fixture-plugin-for-tests", build: true},
         WorkspaceMember{crate_name: "default-plugins/session-manager", build: true},
+        WorkspaceMember{crate_name: "default-plugins/pane-switcher", build: true},
         WorkspaceMember{crate_name: "zellij-utils", build: false},
         WorkspaceMember{crate_name: "zellij-tile-utils", build: false},
         WorkspaceMember{crate_name: "zellij-tile", build: false},
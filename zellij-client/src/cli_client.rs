@@ -1,5 +1,7 @@
 //! The `[cli_client]` is used to attach to a running server session
 //! and dispatch actions, that are specified through the command line.
+use std::collections::BTreeMap;
+use std::io::{self, BufRead};
 use std::process;
 use std::{fs, path::PathBuf};
 
@@ -9,7 +11,7 @@ use zellij_utils::{
     ipc::{ClientToServerMsg, ServerToClientMsg},
 };
 
-pub fn start_cli_client(os_input: Box<dyn ClientOsApi>, session_name: &str, actions: Vec<Action>) {
+pub(crate) fn connect_to_session(os_input: &dyn ClientOsApi, session_name: &str) {
     let zellij_ipc_pipe: PathBuf = {
         let mut sock_dir = zellij_utils::consts::ZELLIJ_SOCK_DIR.clone();
         fs::create_dir_all(&sock_dir).unwrap();
@@ -18,16 +20,35 @@ pub fn start_cli_client(os_input: Box<dyn ClientOsApi>, session_name: &str, acti
         sock_dir
     };
     os_input.connect_to_server(&*zellij_ipc_pipe);
+}
+
+pub fn start_cli_client(os_input: Box<dyn ClientOsApi>, session_name: &str, actions: Vec<Action>) {
+    connect_to_session(&*os_input, session_name);
+    let wait_for_command_exit = actions.iter().any(|a| a.waits_for_command_exit());
     for action in actions {
         let msg = ClientToServerMsg::Action(action, None);
         os_input.send_to_server(msg);
     }
     loop {
         match os_input.recv_from_server() {
-            Some((ServerToClientMsg::UnblockInputThread, _)) => {
+            Some((ServerToClientMsg::UnblockInputThread, _)) if !wait_for_command_exit => {
                 os_input.send_to_server(ClientToServerMsg::ClientExited);
                 process::exit(0);
             },
+            Some((ServerToClientMsg::CommandExited(exit_status), _)) => {
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                match exit_status {
+                    Some(exit_status) => process::exit(exit_status),
+                    None => {
+                        eprintln!("Command could not be run");
+                        process::exit(1);
+                    },
+                }
+            },
+            None if wait_for_command_exit => {
+                eprintln!("Lost connection to the session before the command exited");
+                process::exit(1);
+            },
             Some((ServerToClientMsg::Log(log_lines), _)) => {
                 log_lines.iter().for_each(|line| println!("{line}"));
                 process::exit(0);
@@ -36,3 +57,59 @@ pub fn start_cli_client(os_input: Box<dyn ClientOsApi>, session_name: &str, acti
         }
     }
 }
+
+/// Streams STDIN (or a single payload) line by line into plugins or a terminal pane, each line is
+/// only sent once the session is done with the previous one
+pub fn start_cli_pipe_client(
+    os_input: Box<dyn ClientOsApi>,
+    session_name: &str,
+    name: Option<String>,
+    payload: Option<String>,
+    args: Option<BTreeMap<String, String>>,
+    plugin: Option<String>,
+    pane_id: Option<u32>,
+) {
+    connect_to_session(&*os_input, session_name);
+    let pipe_message = |payload: String| Action::CliPipe {
+        name: name.clone(),
+        payload: Some(payload),
+        args: args.clone(),
+        plugin: plugin.clone(),
+        pane_id,
+    };
+    match payload {
+        Some(payload) => send_pipe_message_and_wait(&*os_input, pipe_message(payload)),
+        None => {
+            for line in io::stdin().lock().lines() {
+                match line {
+                    Ok(line) => send_pipe_message_and_wait(&*os_input, pipe_message(line)),
+                    Err(e) => {
+                        eprintln!("Failed to read from STDIN: {}", e);
+                        break;
+                    },
+                }
+            }
+        },
+    }
+    os_input.send_to_server(ClientToServerMsg::ClientExited);
+    process::exit(0);
+}
+
+fn send_pipe_message_and_wait(os_input: &dyn ClientOsApi, pipe_message: Action) {
+    os_input.send_to_server(ClientToServerMsg::Action(pipe_message, None));
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::UnblockCliPipeInput, _)) => return,
+            Some((ServerToClientMsg::Log(log_lines), _)) => {
+                log_lines.iter().for_each(|line| eprintln!("{line}"));
+                os_input.send_to_server(ClientToServerMsg::ClientExited);
+                process::exit(1);
+            },
+            Some((ServerToClientMsg::Exit(_), _)) | None => {
+                eprintln!("Lost connection to the session");
+                process::exit(1);
+            },
+            _ => {},
+        }
+    }
+}
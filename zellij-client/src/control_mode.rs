@@ -0,0 +1,292 @@
+//! A subset of tmux's control mode (`tmux -CC`), so that programs that know how to drive tmux
+//! this way can drive a zellij session.
+//!
+//! tmux commands are read from STDIN one per line, run as zellij actions and answered on STDOUT
+//! in a `%begin`/`%end` (or `%begin`/`%error`) block, the way tmux answers them. Notifications
+//! (eg. `%output` or `%layout-change`) are not sent.
+use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::cli_client::connect_to_session;
+use crate::os_input_output::ClientOsApi;
+use zellij_utils::{
+    cli::CliAction,
+    data::Direction,
+    input::{actions::Action, config::Config},
+    ipc::{ClientToServerMsg, ServerToClientMsg},
+    tmux_converter::{split_tmux_words, TmuxArgs},
+};
+
+/// A tmux command, translated to what it means in zellij
+#[derive(Debug, Clone)]
+pub enum ControlModeCommand {
+    /// Run these actions, the command is done once the session handled them
+    Run(Vec<CliAction>),
+    /// List the tabs of the session as tmux windows
+    ListWindows,
+    /// Exit, leaving the session running
+    Detach,
+    /// Kill the session and exit
+    KillSession,
+}
+
+enum CommandResult {
+    Done(Vec<String>),
+    Failed(String),
+    SessionEnded,
+}
+
+pub fn start_control_mode_client(
+    os_input: Box<dyn ClientOsApi>,
+    session_name: &str,
+    config: Option<Config>,
+) {
+    connect_to_session(&*os_input, session_name);
+    let mut stdout = io::stdout();
+    // tmux -CC wraps the whole session in a DCS so that the terminal knows to take over
+    let _ = write!(stdout, "\u{1b}P1000p");
+    let _ = stdout.flush();
+    let mut command_number = 0;
+    for line in io::stdin().lock().lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        command_number += 1;
+        let result = match parse_command(&line) {
+            Ok(ControlModeCommand::Detach) => break,
+            Ok(ControlModeCommand::KillSession) => {
+                os_input.send_to_server(ClientToServerMsg::KillSession);
+                write_command_result(&mut stdout, command_number, &CommandResult::Done(vec![]));
+                break;
+            },
+            Ok(ControlModeCommand::ListWindows) => match query_tab_names(&*os_input) {
+                CommandResult::Done(tab_names) => CommandResult::Done(list_windows(&tab_names)),
+                result => result,
+            },
+            Ok(ControlModeCommand::Run(cli_actions)) => {
+                run_cli_actions(&*os_input, cli_actions, config.clone())
+            },
+            Err(e) => CommandResult::Failed(e),
+        };
+        if let CommandResult::SessionEnded = result {
+            let _ = write!(stdout, "%exit session ended\n\u{1b}\\");
+            let _ = stdout.flush();
+            return;
+        }
+        write_command_result(&mut stdout, command_number, &result);
+    }
+    let _ = write!(stdout, "%exit\n\u{1b}\\");
+    let _ = stdout.flush();
+    os_input.send_to_server(ClientToServerMsg::ClientExited);
+}
+
+fn run_cli_actions(
+    os_input: &dyn ClientOsApi,
+    cli_actions: Vec<CliAction>,
+    config: Option<Config>,
+) -> CommandResult {
+    let get_current_dir = || std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let mut actions = vec![];
+    for cli_action in cli_actions {
+        match Action::actions_from_cli(cli_action, Box::new(get_current_dir), config.clone()) {
+            Ok(mut cli_actions) => actions.append(&mut cli_actions),
+            Err(e) => return CommandResult::Failed(e),
+        }
+    }
+    for action in actions {
+        os_input.send_to_server(ClientToServerMsg::Action(action, None));
+    }
+    // the session handles the actions of a client in order, so once it answered a query sent
+    // after them it is done with them
+    match query_tab_names(os_input) {
+        CommandResult::Done(_tab_names) => CommandResult::Done(vec![]),
+        result => result,
+    }
+}
+
+/// Unlike `UnblockInputThread`, which the session broadcasts to all of its clients whenever it
+/// handled an action of any of them, the answer to this query is only sent to us
+fn query_tab_names(os_input: &dyn ClientOsApi) -> CommandResult {
+    os_input.send_to_server(ClientToServerMsg::Action(Action::QueryTabNames, None));
+    loop {
+        match os_input.recv_from_server() {
+            Some((ServerToClientMsg::Log(tab_names), _)) => return CommandResult::Done(tab_names),
+            Some((ServerToClientMsg::Exit(_), _)) | None => return CommandResult::SessionEnded,
+            _ => {},
+        }
+    }
+}
+
+/// Tabs as tmux windows, which are targeted by the same index (see `parse_window_index`)
+fn list_windows(tab_names: &[String]) -> Vec<String> {
+    tab_names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| format!("{}: {} @{}", index, name, index))
+        .collect()
+}
+
+fn write_command_result(stdout: &mut impl Write, command_number: usize, result: &CommandResult) {
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let _ = writeln!(stdout, "%begin {} {} 1", time, command_number);
+    let end = match result {
+        CommandResult::Done(lines) => {
+            for line in lines {
+                let _ = writeln!(stdout, "{}", line);
+            }
+            "%end"
+        },
+        CommandResult::Failed(e) => {
+            let _ = writeln!(stdout, "{}", e);
+            "%error"
+        },
+        CommandResult::SessionEnded => "%error",
+    };
+    let _ = writeln!(stdout, "{} {} {} 1", end, time, command_number);
+    let _ = stdout.flush();
+}
+
+/// Translates a tmux command line into zellij terms, only a subset of tmux's commands (and of
+/// their flags) is supported
+pub fn parse_command(line: &str) -> Result<ControlModeCommand, String> {
+    let words = split_tmux_words(line)?;
+    let (command, args) = match words.split_first() {
+        Some((command, args)) => (command.as_str(), args),
+        // like tmux, an empty line ends the control mode client
+        None => return Ok(ControlModeCommand::Detach),
+    };
+    let parse_args = |flags_with_values: &str| TmuxArgs::parse(args, flags_with_values);
+    let command = match command {
+        "new-window" | "neww" => {
+            let args = parse_args("nct")?;
+            ControlModeCommand::Run(vec![CliAction::NewTab {
+                layout: None,
+                layout_dir: None,
+                name: args.value('n').map(|name| name.to_owned()),
+                cwd: args.value('c').map(PathBuf::from),
+            }])
+        },
+        "split-window" | "splitw" => {
+            let args = parse_args("ct")?;
+            let direction = if args.has('h') {
+                Direction::Right
+            } else {
+                Direction::Down
+            };
+            ControlModeCommand::Run(vec![CliAction::NewPane {
+                direction: Some(direction),
+                command: args.positional.clone(),
+                plugin: None,
+                cwd: args.value('c').map(PathBuf::from),
+                floating: false,
+                name: None,
+                close_on_exit: false,
+                start_suspended: false,
+                configuration: None,
+                in_place: false,
+                wait: false,
+            }])
+        },
+        "select-window" | "selectw" => {
+            let args = parse_args("t")?;
+            if args.has('n') {
+                ControlModeCommand::Run(vec![CliAction::GoToNextTab])
+            } else if args.has('p') {
+                ControlModeCommand::Run(vec![CliAction::GoToPreviousTab])
+            } else {
+                let index = args
+                    .value('t')
+                    .ok_or_else(|| "select-window needs a target window".to_owned())
+                    .and_then(parse_window_index)?;
+                ControlModeCommand::Run(vec![CliAction::GoToTab { index: index + 1 }])
+            }
+        },
+        "next-window" | "next" => ControlModeCommand::Run(vec![CliAction::GoToNextTab]),
+        "previous-window" | "prev" => ControlModeCommand::Run(vec![CliAction::GoToPreviousTab]),
+        "select-pane" | "selectp" => {
+            let args = parse_args("t")?;
+            let direction = if args.has('L') {
+                Direction::Left
+            } else if args.has('R') {
+                Direction::Right
+            } else if args.has('U') {
+                Direction::Up
+            } else if args.has('D') {
+                Direction::Down
+            } else {
+                return Err("select-pane only supports -L, -R, -U and -D".to_owned());
+            };
+            ControlModeCommand::Run(vec![CliAction::MoveFocus { direction }])
+        },
+        "kill-pane" | "killp" => ControlModeCommand::Run(vec![CliAction::ClosePane]),
+        "kill-window" | "killw" => ControlModeCommand::Run(vec![CliAction::CloseTab]),
+        "rename-window" | "renamew" => {
+            let args = parse_args("t")?;
+            let name = args
+                .positional
+                .first()
+                .cloned()
+                .ok_or_else(|| "rename-window needs a name".to_owned())?;
+            ControlModeCommand::Run(vec![CliAction::RenameTab { name }])
+        },
+        "send-keys" | "send" => {
+            let args = parse_args("t")?;
+            let chars = if args.has('l') {
+                args.positional.join(" ")
+            } else {
+                args.positional
+                    .iter()
+                    .map(|key| key_to_chars(key))
+                    .collect()
+            };
+            ControlModeCommand::Run(vec![CliAction::WriteChars {
+                chars,
+                pane_id: None,
+            }])
+        },
+        "list-windows" | "lsw" => ControlModeCommand::ListWindows,
+        "detach-client" | "detach" => ControlModeCommand::Detach,
+        "kill-session" | "kill-server" => ControlModeCommand::KillSession,
+        _ => return Err(format!("unknown command: {}", command)),
+    };
+    Ok(command)
+}
+
+/// Windows are targeted by their index (`1`, `:1`, `@1`), the same index list-windows reports
+fn parse_window_index(target: &str) -> Result<u32, String> {
+    target
+        .trim_start_matches(&[':', '@'][..])
+        .parse()
+        .map_err(|_| format!("can't find window: {}", target))
+}
+
+/// tmux key names (eg. `Enter` or `C-c`) to the characters they send, anything else is sent as is
+fn key_to_chars(key: &str) -> String {
+    match key {
+        "Enter" => "\r".to_owned(),
+        "Tab" => "\t".to_owned(),
+        "Escape" => "\u{1b}".to_owned(),
+        "Space" => " ".to_owned(),
+        "BSpace" => "\u{7f}".to_owned(),
+        _ => match key.strip_prefix("C-") {
+            Some(control_key) if control_key.len() == 1 => {
+                let character = control_key.chars().next().unwrap().to_ascii_lowercase();
+                if character.is_ascii_lowercase() {
+                    ((character as u8 - b'a' + 1) as char).to_string()
+                } else {
+                    key.to_owned()
+                }
+            },
+            _ => key.to_owned(),
+        },
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/control_mode_tests.rs"]
+mod control_mode_tests;
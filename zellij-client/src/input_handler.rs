@@ -3,8 +3,9 @@ use crate::{
     os_input_output::ClientOsApi, stdin_ansi_parser::AnsiStdinInstruction, ClientId,
     ClientInstruction, CommandIsExecuting, InputInstruction,
 };
+use std::time::{Duration, Instant};
 use zellij_utils::{
-    channels::{Receiver, SenderWithContext, OPENCALLS},
+    channels::{Receiver, RecvError, RecvTimeoutError, SenderWithContext, OPENCALLS},
     data::{InputMode, Key},
     errors::{ContextType, ErrorContext, FatalError},
     input::{
@@ -18,6 +19,12 @@ use zellij_utils::{
     termwiz::input::InputEvent,
 };
 
+// pastes are sent to the server in messages of at most this many bytes
+const PASTE_CHUNK_SIZE: usize = 64 * 1024;
+
+// how long to wait for the next key of a key sequence if the key_sequence_timeout option isn't set
+const DEFAULT_KEY_SEQUENCE_TIMEOUT_MS: u64 = 1000;
+
 #[derive(Debug, Clone, Copy)]
 enum HeldMouseButton {
     Left,
@@ -31,11 +38,22 @@ impl Default for HeldMouseButton {
     }
 }
 
+/// A key sequence binding that was started and is waiting for its next key
+struct PendingKeySequence {
+    /// The keys typed so far, starting with the one bound to the sequences
+    keys: Vec<Key>,
+    /// The rest of the keys of each sequence starting with the first key, and their actions
+    sequences: Vec<(Vec<Key>, Vec<Action>)>,
+    started_at: Instant,
+}
+
 /// Handles the dispatching of [`Action`]s according to the current
 /// [`InputMode`], and keep tracks of the current [`InputMode`].
 struct InputHandler {
     /// The current input mode
     mode: InputMode,
+    /// The name of the current custom mode, when `mode` is `InputMode::Custom`
+    custom_mode: Option<String>,
     os_input: Box<dyn ClientOsApi>,
     config: Config,
     options: Options,
@@ -45,6 +63,7 @@ struct InputHandler {
     receive_input_instructions: Receiver<(InputInstruction, ErrorContext)>,
     holding_mouse: Option<HeldMouseButton>,
     mouse_mode_active: bool,
+    pending_key_sequence: Option<PendingKeySequence>,
 }
 
 impl InputHandler {
@@ -60,6 +79,7 @@ impl InputHandler {
     ) -> Self {
         InputHandler {
             mode,
+            custom_mode: None,
             os_input,
             config,
             options,
@@ -69,6 +89,7 @@ impl InputHandler {
             receive_input_instructions,
             holding_mouse: None,
             mouse_mode_active: false,
+            pending_key_sequence: None,
         }
     }
 
@@ -87,7 +108,21 @@ impl InputHandler {
             if self.should_exit {
                 break;
             }
-            match self.receive_input_instructions.recv() {
+            let input_instruction = match &self.pending_key_sequence {
+                Some(pending_key_sequence) => {
+                    let deadline = pending_key_sequence.started_at + self.key_sequence_timeout();
+                    match self.receive_input_instructions.recv_deadline(deadline) {
+                        Ok(input_instruction) => Ok(input_instruction),
+                        Err(RecvTimeoutError::Timeout) => {
+                            self.cancel_key_sequence();
+                            continue;
+                        },
+                        Err(RecvTimeoutError::Disconnected) => Err(RecvError),
+                    }
+                },
+                None => self.receive_input_instructions.recv(),
+            };
+            match input_instruction {
                 Ok((InputInstruction::KeyEvent(input_event, raw_bytes), _error_context)) => {
                     match input_event {
                         InputEvent::Key(key_event) => {
@@ -105,10 +140,12 @@ impl InputHandler {
                                     Action::Write(bracketed_paste_start.clone()),
                                     None,
                                 );
-                                self.dispatch_action(
-                                    Action::Write(pasted_text.as_bytes().to_vec()),
-                                    None,
-                                );
+                                // large pastes are sent in chunks so that they don't hold up
+                                // the server, which forwards them to the pane at the pace its
+                                // pty can take them
+                                for chunk in pasted_text.as_bytes().chunks(PASTE_CHUNK_SIZE) {
+                                    self.dispatch_action(Action::Write(chunk.to_vec()), None);
+                                }
                                 self.dispatch_action(
                                     Action::Write(bracketed_paste_end.clone()),
                                     None,
@@ -136,9 +173,33 @@ impl InputHandler {
                         _ => {},
                     }
                 },
+                Ok((InputInstruction::KittyKeyEvent(kitty_key, raw_bytes), _error_context)) => {
+                    if kitty_key.is_release() {
+                        continue;
+                    }
+                    if let Some(key) = kitty_key.to_key() {
+                        // panes translate these keys for applications that did not enable the
+                        // protocol, everything else expects the legacy bytes
+                        let raw_bytes =
+                            if self.mode == InputMode::Normal || self.mode == InputMode::Locked {
+                                raw_bytes
+                            } else {
+                                kitty_key.to_legacy_bytes()
+                            };
+                        self.handle_key(&key, raw_bytes);
+                    }
+                },
                 Ok((InputInstruction::SwitchToMode(input_mode), _error_context)) => {
+                    // the server only tells us we're in a custom mode, which one we already know
+                    // since we're the ones who switched to it
+                    if input_mode != InputMode::Custom {
+                        self.custom_mode = None;
+                    }
                     self.mode = input_mode;
                 },
+                Ok((InputInstruction::RebindKeys(keybinds), _error_context)) => {
+                    self.config.keybinds = keybinds;
+                },
                 Ok((
                     InputInstruction::AnsiStdinInstructions(ansi_stdin_instructions),
                     _error_context,
@@ -165,10 +226,81 @@ impl InputHandler {
         }
     }
     fn handle_key(&mut self, key: &Key, raw_bytes: Vec<u8>) {
+        if let Some(pending_key_sequence) = self.pending_key_sequence.take() {
+            self.continue_key_sequence(pending_key_sequence, key, raw_bytes);
+            return;
+        }
         let keybinds = &self.config.keybinds;
-        for action in
-            keybinds.get_actions_for_key_in_mode_or_default_action(&self.mode, key, raw_bytes)
+        let actions = match (&self.mode, &self.custom_mode) {
+            (InputMode::Custom, Some(custom_mode)) => {
+                keybinds.get_actions_for_key_in_custom_mode_or_default_action(custom_mode, key)
+            },
+            _ => keybinds.get_actions_for_key_in_mode_or_default_action(
+                &self.mode,
+                key,
+                raw_bytes.clone(),
+            ),
+        };
+        if let [Action::KeySequence(sequences)] = actions.as_slice() {
+            let keys = vec![key.clone()];
+            self.dispatch_action(Action::PendingKeys(keys.clone()), None);
+            self.pending_key_sequence = Some(PendingKeySequence {
+                keys,
+                sequences: sequences.clone(),
+                started_at: Instant::now(),
+            });
+            return;
+        }
+        self.dispatch_key_actions(actions, raw_bytes);
+    }
+    fn continue_key_sequence(
+        &mut self,
+        mut pending_key_sequence: PendingKeySequence,
+        key: &Key,
+        raw_bytes: Vec<u8>,
+    ) {
+        pending_key_sequence.keys.push(key.clone());
+        let typed_keys = &pending_key_sequence.keys[1..];
+        let completed_sequence = pending_key_sequence
+            .sequences
+            .iter()
+            .find(|(keys, _actions)| keys == typed_keys);
+        if let Some((_keys, actions)) = completed_sequence {
+            let actions = actions.clone();
+            self.dispatch_action(Action::PendingKeys(vec![]), None);
+            self.dispatch_key_actions(actions, raw_bytes);
+        } else if pending_key_sequence
+            .sequences
+            .iter()
+            .any(|(keys, _actions)| keys.starts_with(typed_keys))
         {
+            self.dispatch_action(Action::PendingKeys(pending_key_sequence.keys.clone()), None);
+            self.pending_key_sequence = Some(pending_key_sequence);
+        } else {
+            // the keys don't make up any of the bound sequences, so they are dropped
+            self.cancel_key_sequence();
+        }
+    }
+    fn cancel_key_sequence(&mut self) {
+        self.pending_key_sequence = None;
+        self.dispatch_action(Action::PendingKeys(vec![]), None);
+    }
+    fn key_sequence_timeout(&self) -> Duration {
+        Duration::from_millis(
+            self.options
+                .key_sequence_timeout
+                .unwrap_or(DEFAULT_KEY_SEQUENCE_TIMEOUT_MS),
+        )
+    }
+    fn dispatch_key_actions(&mut self, actions: Vec<Action>, raw_bytes: Vec<u8>) {
+        for action in actions {
+            // the focused pane might claim the key, in which case it gets it as it was typed
+            let action = match action {
+                Action::MoveFocusOrDelegate(direction, _) => {
+                    Action::MoveFocusOrDelegate(direction, raw_bytes.clone())
+                },
+                action => action,
+            };
             let should_exit = self.dispatch_action(action, None);
             if should_exit {
                 self.should_exit = true;
@@ -233,8 +365,56 @@ impl InputHandler {
                     self.holding_mouse = Some(HeldMouseButton::Middle);
                 },
             },
+            MouseEvent::CtrlPress(MouseButton::Left, point) => {
+                if self.holding_mouse.is_some() {
+                    self.dispatch_action(Action::MouseHoldLeft(point), None);
+                } else {
+                    self.dispatch_action(Action::CtrlLeftClick(point), None);
+                }
+                self.holding_mouse = Some(HeldMouseButton::Left);
+            },
+            // ctrl scrolls zellij's scrollback even if the application asked for mouse events
+            MouseEvent::CtrlPress(MouseButton::WheelUp, point) => {
+                self.dispatch_action(Action::ForceScrollUpAt(point), None);
+            },
+            MouseEvent::CtrlPress(MouseButton::WheelDown, point) => {
+                self.dispatch_action(Action::ForceScrollDownAt(point), None);
+            },
+            MouseEvent::CtrlPress(button, point) => {
+                self.handle_mouse_event(&MouseEvent::Press(button, point));
+            },
+            MouseEvent::ShiftPress(MouseButton::Left, point) => {
+                if self.holding_mouse.is_some() {
+                    self.dispatch_action(Action::MouseHoldLeft(point), None);
+                } else {
+                    self.dispatch_action(Action::ShiftLeftClick(point), None);
+                }
+                self.holding_mouse = Some(HeldMouseButton::Left);
+            },
+            MouseEvent::ShiftPress(button, point) => {
+                self.handle_mouse_event(&MouseEvent::Press(button, point));
+            },
+            MouseEvent::AltPress(MouseButton::Left, point) => {
+                if self.holding_mouse.is_some() {
+                    self.dispatch_action(Action::MouseHoldLeft(point), None);
+                } else {
+                    self.dispatch_action(Action::AltLeftClick(point), None);
+                }
+                self.holding_mouse = Some(HeldMouseButton::Left);
+            },
+            MouseEvent::AltPress(button, point) => {
+                self.handle_mouse_event(&MouseEvent::Press(button, point));
+            },
             MouseEvent::Release(point) => {
-                let button_released = self.holding_mouse.unwrap_or_default();
+                let button_released = match self.holding_mouse {
+                    Some(button_released) => button_released,
+                    None => {
+                        // with any-event tracking, motion without a held button is reported as a
+                        // release
+                        self.dispatch_action(Action::MouseHover(point), None);
+                        return;
+                    },
+                };
                 match button_released {
                     HeldMouseButton::Left => {
                         self.dispatch_action(Action::LeftMouseRelease(point), None)
@@ -297,6 +477,13 @@ impl InputHandler {
                 // this is an optimistic update, we should get a SwitchMode instruction from the
                 // server later that atomically changes the mode as well
                 self.mode = mode;
+                self.custom_mode = None;
+                self.os_input
+                    .send_to_server(ClientToServerMsg::Action(action, None));
+            },
+            Action::SwitchToCustomMode(ref custom_mode) => {
+                self.mode = InputMode::Custom;
+                self.custom_mode = Some(custom_mode.clone());
                 self.os_input
                     .send_to_server(ClientToServerMsg::Action(action, None));
             },
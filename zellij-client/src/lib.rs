@@ -2,6 +2,7 @@ pub mod os_input_output;
 
 pub mod cli_client;
 mod command_is_executing;
+pub mod control_mode;
 mod input_handler;
 pub mod old_config_converter;
 mod stdin_ansi_parser;
@@ -10,8 +11,8 @@ mod stdin_handler;
 use log::info;
 use std::env::current_exe;
 use std::io::{self, Write};
-use std::path::Path;
-use std::process::Command;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use zellij_utils::errors::FatalError;
@@ -27,8 +28,14 @@ use zellij_utils::{
     data::{ClientId, ConnectToSession, InputMode, Style},
     envs,
     errors::{ClientContext, ContextType, ErrorInstruction},
-    input::{config::Config, options::Options},
+    input::{
+        config::Config, keybinds::Keybinds, kitty_keyboard::KittyKey, options::Options,
+        theme::Theme,
+    },
     ipc::{ClientAttributes, ClientToServerMsg, ExitReason, ServerToClientMsg},
+    nix::sys::signal::Signal,
+    pane_size::Size,
+    signal_hook::consts::signal::{SIGHUP, SIGTERM},
     termwiz::input::InputEvent,
 };
 use zellij_utils::{cli::CliArgs, input::layout::Layout};
@@ -46,7 +53,10 @@ pub(crate) enum ClientInstruction {
     StartedParsingStdinQuery,
     DoneParsingStdinQuery,
     Log(Vec<String>),
+    CommandExited(Option<i32>),
     SwitchSession(ConnectToSession),
+    RebindKeys(Keybinds),
+    UnblockCliPipeInput,
 }
 
 impl From<ServerToClientMsg> for ClientInstruction {
@@ -61,9 +71,14 @@ impl From<ServerToClientMsg> for ClientInstruction {
             ServerToClientMsg::Connected => ClientInstruction::Connected,
             ServerToClientMsg::ActiveClients(clients) => ClientInstruction::ActiveClients(clients),
             ServerToClientMsg::Log(log_lines) => ClientInstruction::Log(log_lines),
+            ServerToClientMsg::CommandExited(exit_status) => {
+                ClientInstruction::CommandExited(exit_status)
+            },
             ServerToClientMsg::SwitchSession(connect_to_session) => {
                 ClientInstruction::SwitchSession(connect_to_session)
             },
+            ServerToClientMsg::RebindKeys(keybinds) => ClientInstruction::RebindKeys(keybinds),
+            ServerToClientMsg::UnblockCliPipeInput => ClientInstruction::UnblockCliPipeInput,
         }
     }
 }
@@ -79,9 +94,12 @@ impl From<&ClientInstruction> for ClientContext {
             ClientInstruction::Connected => ClientContext::Connected,
             ClientInstruction::ActiveClients(_) => ClientContext::ActiveClients,
             ClientInstruction::Log(_) => ClientContext::Log,
+            ClientInstruction::CommandExited(_) => ClientContext::CommandExited,
             ClientInstruction::StartedParsingStdinQuery => ClientContext::StartedParsingStdinQuery,
             ClientInstruction::DoneParsingStdinQuery => ClientContext::DoneParsingStdinQuery,
             ClientInstruction::SwitchSession(..) => ClientContext::SwitchSession,
+            ClientInstruction::RebindKeys(..) => ClientContext::RebindKeys,
+            ClientInstruction::UnblockCliPipeInput => ClientContext::UnblockCliPipeInput,
         }
     }
 }
@@ -92,6 +110,9 @@ impl ErrorInstruction for ClientInstruction {
     }
 }
 
+const DEFAULT_HEADLESS_COLUMNS: usize = 80;
+const DEFAULT_HEADLESS_ROWS: usize = 24;
+
 fn spawn_server(socket_path: &Path, debug: bool) -> io::Result<()> {
     let mut cmd = Command::new(current_exe()?);
     cmd.arg("--server");
@@ -131,7 +152,9 @@ impl ClientInfo {
 #[derive(Debug, Clone)]
 pub(crate) enum InputInstruction {
     KeyEvent(InputEvent, Vec<u8>),
+    KittyKeyEvent(KittyKey, Vec<u8>),
     SwitchToMode(InputMode),
+    RebindKeys(Keybinds),
     AnsiStdinInstructions(Vec<AnsiStdinInstruction>),
     StartedParsing,
     DoneParsing,
@@ -154,6 +177,12 @@ pub fn start_client(
     let clear_client_terminal_attributes = "\u{1b}[?1l\u{1b}=\u{1b}[r\u{1b}[?1000l\u{1b}[?1002l\u{1b}[?1003l\u{1b}[?1005l\u{1b}[?1006l\u{1b}[?12l";
     let take_snapshot = "\u{1b}[?1049h";
     let bracketed_paste = "\u{1b}[?2004h";
+    // ask the terminal emulator to disambiguate escape codes, see the kitty_keyboard module
+    let enable_kitty_keyboard = "\u{1b}[>1u";
+    let disable_kitty_keyboard = "\u{1b}[<1u";
+    let support_kitty_keyboard_protocol = config_options
+        .support_kitty_keyboard_protocol
+        .unwrap_or(true);
     os_input.unset_raw_mode(0).unwrap();
 
     let _ = os_input
@@ -167,19 +196,25 @@ pub fn start_client(
     envs::set_zellij("0".to_string());
     config.env.set_vars();
 
-    let palette = config
+    let mut theme = config
         .theme_config(&config_options)
-        .unwrap_or_else(|| os_input.load_palette());
+        .unwrap_or_else(|| Theme::from(os_input.load_palette()));
+    if !envs::terminal_supports_truecolor() {
+        theme.palette = theme.palette.to_eight_bit();
+        theme.styling = theme.styling.to_eight_bit();
+    }
 
     let full_screen_ws = os_input.get_terminal_size_using_fd(0);
     let client_attributes = ClientAttributes {
         size: full_screen_ws,
         style: Style {
-            colors: palette,
+            colors: theme.palette,
             rounded_corners: config.ui.pane_frames.rounded_corners,
             hide_session_name: config.ui.pane_frames.hide_session_name,
+            styling: theme.styling,
         },
         keybinds: config.keybinds.clone(),
+        pane_frame_title: config.ui.pane_frames.title.clone(),
     };
 
     let create_ipc_pipe = || -> std::path::PathBuf {
@@ -236,6 +271,12 @@ pub fn start_client(
         .get_stdout_writer()
         .write(bracketed_paste.as_bytes())
         .unwrap();
+    if support_kitty_keyboard_protocol {
+        let _ = os_input
+            .get_stdout_writer()
+            .write(enable_kitty_keyboard.as_bytes())
+            .unwrap();
+    }
 
     let (send_client_instructions, receive_client_instructions): ChannelWithContext<
         ClientInstruction,
@@ -259,6 +300,9 @@ pub fn start_client(
     });
 
     let on_force_close = config_options.on_force_close.unwrap_or_default();
+    let on_sighup = config_options.on_sighup.unwrap_or(on_force_close);
+    let on_sigterm = config_options.on_sigterm.unwrap_or(on_force_close);
+    let on_force_close_command = config_options.on_force_close_command.clone();
     let stdin_ansi_parser = Arc::new(Mutex::new(StdinAnsiParser::new()));
 
     let _stdin_thread = thread::Builder::new()
@@ -306,7 +350,15 @@ pub fn start_client(
                     }),
                     Box::new({
                         let os_api = os_input.clone();
-                        move || {
+                        move |signal| {
+                            if let Some(command) = &on_force_close_command {
+                                run_force_close_command(command, signal);
+                            }
+                            let on_force_close = match signal {
+                                SIGHUP => on_sighup,
+                                SIGTERM => on_sigterm,
+                                _ => on_force_close,
+                            };
                             os_api.send_to_server(ClientToServerMsg::Action(
                                 on_force_close.into(),
                                 None,
@@ -443,6 +495,11 @@ pub fn start_client(
                     .send(InputInstruction::SwitchToMode(input_mode))
                     .unwrap();
             },
+            ClientInstruction::RebindKeys(keybinds) => {
+                send_input_instructions
+                    .send(InputInstruction::RebindKeys(keybinds))
+                    .unwrap();
+            },
             ClientInstruction::Log(lines_to_log) => {
                 for line in lines_to_log {
                     log::info!("{line}");
@@ -459,6 +516,13 @@ pub fn start_client(
 
     router_thread.join().unwrap();
 
+    if support_kitty_keyboard_protocol {
+        let _ = os_input
+            .get_stdout_writer()
+            .write(disable_kitty_keyboard.as_bytes())
+            .unwrap();
+    }
+
     if reconnect_to_session.is_none() {
         let reset_style = "\u{1b}[m";
         let show_cursor = "\u{1b}[?25h";
@@ -487,6 +551,115 @@ pub fn start_client(
     reconnect_to_session
 }
 
+/// Starts a new session without attaching to it, laying out its panes in the configured headless
+/// size until a client attaches
+/// Runs the `on_force_close_command` and waits for it, so that it can still interact with the
+/// session before the client detaches or quits
+fn run_force_close_command(command: &str, signal: i32) {
+    let signal_name = Signal::try_from(signal)
+        .map(|signal| signal.as_str().to_owned())
+        .unwrap_or_else(|_| signal.to_string());
+    let result = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .env("ZELLIJ_FORCE_CLOSE_SIGNAL", signal_name)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    if let Err(e) = result {
+        log::error!("Failed to run on_force_close_command {}: {}", command, e);
+    }
+}
+
+pub fn start_server_detached(
+    os_input: Box<dyn ClientOsApi>,
+    opts: CliArgs,
+    config: Config,
+    config_options: Options,
+    session_name: String,
+    layout: Layout,
+) {
+    info!("Starting Zellij session {} in the background", session_name);
+
+    let ipc_pipe = prepare_detached_session(&config, &session_name);
+    spawn_server(&*ipc_pipe, opts.debug).unwrap();
+    create_detached_session(
+        os_input,
+        &ipc_pipe,
+        opts,
+        config,
+        config_options,
+        session_name,
+        layout,
+    );
+}
+
+/// Sets up the environment (inherited by the server) of a session that is started without a
+/// client attached to it, returning the path of the session's socket
+pub fn prepare_detached_session(config: &Config, session_name: &str) -> PathBuf {
+    envs::set_zellij("0".to_string());
+    config.env.set_vars();
+    envs::set_session_name(session_name.to_owned());
+
+    let mut ipc_pipe = ZELLIJ_SOCK_DIR.clone();
+    std::fs::create_dir_all(&ipc_pipe).unwrap();
+    set_permissions(&ipc_pipe, 0o700).unwrap();
+    ipc_pipe.push(session_name);
+    ipc_pipe
+}
+
+/// Creates the session in the server listening at `ipc_pipe` on behalf of a client that leaves
+/// right away, so that the session keeps running with no clients attached
+pub fn create_detached_session(
+    mut os_input: Box<dyn ClientOsApi>,
+    ipc_pipe: &Path,
+    opts: CliArgs,
+    config: Config,
+    config_options: Options,
+    session_name: String,
+    layout: Layout,
+) {
+    let mut theme = config
+        .theme_config(&config_options)
+        .unwrap_or_else(|| Theme::from(os_input.load_palette()));
+    if !envs::terminal_supports_truecolor() {
+        theme.palette = theme.palette.to_eight_bit();
+        theme.styling = theme.styling.to_eight_bit();
+    }
+
+    let client_attributes = ClientAttributes {
+        size: Size {
+            rows: config_options
+                .headless_rows
+                .unwrap_or(DEFAULT_HEADLESS_ROWS),
+            cols: config_options
+                .headless_columns
+                .unwrap_or(DEFAULT_HEADLESS_COLUMNS),
+        },
+        style: Style {
+            colors: theme.palette,
+            rounded_corners: config.ui.pane_frames.rounded_corners,
+            hide_session_name: config.ui.pane_frames.hide_session_name,
+            styling: theme.styling,
+        },
+        keybinds: config.keybinds.clone(),
+        pane_frame_title: config.ui.pane_frames.title.clone(),
+    };
+
+    os_input.update_session_name(session_name);
+    os_input.connect_to_server(ipc_pipe);
+    os_input.send_to_server(ClientToServerMsg::NewClient(
+        client_attributes,
+        Box::new(opts),
+        Box::new(config_options),
+        Box::new(layout),
+        Some(config.plugins.clone()),
+    ));
+    // the session was created on our behalf, leaving it running with no clients attached
+    os_input.send_to_server(ClientToServerMsg::ClientExited);
+}
+
 #[cfg(test)]
 #[path = "./unit/stdin_tests.rs"]
 mod stdin_tests;
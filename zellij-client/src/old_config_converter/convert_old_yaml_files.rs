@@ -93,6 +93,8 @@ pub fn convert_old_yaml_files(opts: &CliArgs) {
 fn print_conversion_title_message() {
     println!("");
     println!("\u{1b}[1mZellij has moved to a new configuration format (KDL - https://kdl.dev) and has now been run with an old YAML configuration/layout/theme file.\u{1b}[m");
+    println!("Only the configuration file, the layout passed with --layout and the layouts and themes in the layout and theme directories that have no KDL file next to them are converted below.");
+    println!("Any other YAML file (eg. a layout or theme outside of those directories) is not converted and will no longer be loaded, convert it by hand with \u{1b}[1;33mzellij convert-config\u{1b}[m, \u{1b}[1;33mzellij convert-layout\u{1b}[m or \u{1b}[1;33mzellij convert-theme\u{1b}[m.");
 }
 
 fn print_converting_config_message(old_file_name: String, new_file_name: String) {
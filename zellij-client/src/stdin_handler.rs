@@ -3,6 +3,7 @@ use crate::stdin_ansi_parser::StdinAnsiParser;
 use crate::InputInstruction;
 use std::sync::{Arc, Mutex};
 use zellij_utils::channels::SenderWithContext;
+use zellij_utils::input::kitty_keyboard::split_kitty_keys;
 use zellij_utils::termwiz::input::{InputEvent, InputParser, MouseButtons};
 
 fn send_done_parsing_after_query_timeout(
@@ -84,6 +85,39 @@ pub(crate) fn stdin_loop(
                         .unwrap()
                         .write_cache(ansi_stdin_events.drain(..).collect());
                 }
+                let segments = split_kitty_keys(&buf);
+                if segments.iter().any(|(kitty_key, _)| kitty_key.is_some()) {
+                    // keys encoded with the kitty keyboard protocol are not understood by the
+                    // termwiz parser, so we send them separately from the input around them
+                    for (kitty_key, mut bytes) in segments {
+                        match kitty_key {
+                            Some(kitty_key) => {
+                                send_input_instructions
+                                    .send(InputInstruction::KittyKeyEvent(kitty_key, bytes))
+                                    .unwrap();
+                            },
+                            None => {
+                                let mut events = vec![];
+                                input_parser.parse(
+                                    &bytes,
+                                    |input_event: InputEvent| {
+                                        events.push(input_event);
+                                    },
+                                    false,
+                                );
+                                for input_event in events {
+                                    send_input_instructions
+                                        .send(InputInstruction::KeyEvent(
+                                            input_event,
+                                            bytes.drain(..).collect(),
+                                        ))
+                                        .unwrap();
+                                }
+                            },
+                        }
+                    }
+                    continue;
+                }
                 current_buffer.append(&mut buf.to_vec());
                 let maybe_more = false; // read_from_stdin should (hopefully) always empty the STDIN buffer completely
                 let mut events = vec![];
@@ -0,0 +1,225 @@
+use super::{list_windows, parse_command, run_cli_actions, CommandResult, ControlModeCommand};
+use crate::os_input_output::{ClientOsApi, StdinPoller};
+use std::collections::VecDeque;
+use std::io;
+use std::os::unix::io::RawFd;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use zellij_utils::anyhow::Result;
+use zellij_utils::cli::CliAction;
+use zellij_utils::data::{Direction, Palette};
+use zellij_utils::errors::ErrorContext;
+use zellij_utils::input::actions::Action;
+use zellij_utils::ipc::{ClientToServerMsg, ExitReason, ServerToClientMsg};
+use zellij_utils::nix;
+use zellij_utils::pane_size::Size;
+
+/// Answers with the given messages in order, as if the session sent them
+#[derive(Clone, Default)]
+struct FakeClientOsApi {
+    messages_sent_to_server: Arc<Mutex<Vec<ClientToServerMsg>>>,
+    messages_from_server: Arc<Mutex<VecDeque<ServerToClientMsg>>>,
+}
+
+impl FakeClientOsApi {
+    pub fn with_messages_from_server(messages_from_server: Vec<ServerToClientMsg>) -> Self {
+        FakeClientOsApi {
+            messages_from_server: Arc::new(Mutex::new(messages_from_server.into())),
+            ..Default::default()
+        }
+    }
+    pub fn actions_sent_to_server(&self) -> Vec<Action> {
+        self.messages_sent_to_server
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(|message| match message {
+                ClientToServerMsg::Action(action, _) => Some(action.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+    pub fn unread_message_count(&self) -> usize {
+        self.messages_from_server.lock().unwrap().len()
+    }
+}
+
+impl ClientOsApi for FakeClientOsApi {
+    fn get_terminal_size_using_fd(&self, _fd: RawFd) -> Size {
+        unimplemented!()
+    }
+    fn set_raw_mode(&mut self, _fd: RawFd) {
+        unimplemented!()
+    }
+    fn unset_raw_mode(&self, _fd: RawFd) -> Result<(), nix::Error> {
+        unimplemented!()
+    }
+    fn get_stdout_writer(&self) -> Box<dyn io::Write> {
+        unimplemented!()
+    }
+    fn get_stdin_reader(&self) -> Box<dyn io::Read> {
+        unimplemented!()
+    }
+    fn update_session_name(&mut self, _new_session_name: String) {}
+    fn read_from_stdin(&mut self) -> Result<Vec<u8>, &'static str> {
+        unimplemented!()
+    }
+    fn box_clone(&self) -> Box<dyn ClientOsApi> {
+        Box::new(self.clone())
+    }
+    fn send_to_server(&self, msg: ClientToServerMsg) {
+        self.messages_sent_to_server.lock().unwrap().push(msg);
+    }
+    fn recv_from_server(&self) -> Option<(ServerToClientMsg, ErrorContext)> {
+        self.messages_from_server
+            .lock()
+            .unwrap()
+            .pop_front()
+            .map(|message| (message, ErrorContext::new()))
+    }
+    fn handle_signals(&self, _sigwinch_cb: Box<dyn Fn()>, _quit_cb: Box<dyn Fn(i32)>) {
+        unimplemented!()
+    }
+    fn connect_to_server(&self, _path: &Path) {
+        unimplemented!()
+    }
+    fn load_palette(&self) -> Palette {
+        unimplemented!()
+    }
+    fn enable_mouse(&self) -> Result<()> {
+        Ok(())
+    }
+    fn disable_mouse(&self) -> Result<()> {
+        Ok(())
+    }
+    fn stdin_poller(&self) -> StdinPoller {
+        unimplemented!()
+    }
+}
+
+#[test]
+fn new_window_with_a_name_opens_a_named_tab() {
+    let command = parse_command("new-window -n 'my tab'").unwrap();
+    assert!(matches!(
+        command,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::NewTab { name: Some(name), .. }] if name == "my tab"
+        )
+    ));
+}
+
+#[test]
+fn split_window_opens_a_pane_in_the_right_direction() {
+    let horizontal = parse_command("split-window -h").unwrap();
+    let vertical = parse_command("splitw").unwrap();
+    assert!(matches!(
+        horizontal,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::NewPane { direction: Some(Direction::Right), .. }]
+        )
+    ));
+    assert!(matches!(
+        vertical,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::NewPane { direction: Some(Direction::Down), .. }]
+        )
+    ));
+}
+
+#[test]
+fn select_window_targets_tabs_by_their_index() {
+    let command = parse_command("select-window -t @2").unwrap();
+    assert!(matches!(
+        command,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::GoToTab { index: 3 }]
+        )
+    ));
+    assert!(parse_command("select-window -t nope").is_err());
+}
+
+#[test]
+fn send_keys_translates_key_names() {
+    let command = parse_command("send-keys ls Space -la Enter C-c").unwrap();
+    assert!(matches!(
+        command,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::WriteChars { chars, .. }] if chars == "ls -la\r\u{3}"
+        )
+    ));
+    let literal = parse_command("send-keys -l \"Enter\"").unwrap();
+    assert!(matches!(
+        literal,
+        ControlModeCommand::Run(actions) if matches!(
+            &actions[..],
+            [CliAction::WriteChars { chars, .. }] if chars == "Enter"
+        )
+    ));
+}
+
+#[test]
+fn empty_line_detaches() {
+    assert!(matches!(parse_command(""), Ok(ControlModeCommand::Detach)));
+    assert!(matches!(
+        parse_command("detach-client"),
+        Ok(ControlModeCommand::Detach)
+    ));
+}
+
+#[test]
+fn unsupported_commands_are_errors() {
+    assert!(parse_command("choose-tree").is_err());
+    assert!(parse_command("select-pane -t %1").is_err());
+    assert!(parse_command("rename-window 'unterminated").is_err());
+}
+
+#[test]
+fn command_is_done_once_the_session_answered_the_query_sent_after_it() {
+    // input is unblocked for every client whenever any of them had an action handled, so these
+    // might be about another client's actions
+    let os_input = FakeClientOsApi::with_messages_from_server(vec![
+        ServerToClientMsg::UnblockInputThread,
+        ServerToClientMsg::UnblockInputThread,
+        ServerToClientMsg::UnblockInputThread,
+        ServerToClientMsg::Log(vec!["Tab #1".to_owned()]),
+        ServerToClientMsg::UnblockInputThread,
+    ]);
+    let result = run_cli_actions(
+        &os_input,
+        vec![CliAction::GoToNextTab, CliAction::CloseTab],
+        None,
+    );
+    assert!(matches!(result, CommandResult::Done(lines) if lines.is_empty()));
+    assert!(matches!(
+        &os_input.actions_sent_to_server()[..],
+        [Action::GoToNextTab, Action::CloseTab, Action::QueryTabNames]
+    ));
+    assert_eq!(
+        os_input.unread_message_count(),
+        1,
+        "stopped reading at the answer to the query"
+    );
+}
+
+#[test]
+fn command_fails_if_the_session_ends_before_answering() {
+    let os_input = FakeClientOsApi::with_messages_from_server(vec![
+        ServerToClientMsg::UnblockInputThread,
+        ServerToClientMsg::Exit(ExitReason::Normal),
+    ]);
+    let result = run_cli_actions(&os_input, vec![CliAction::CloseTab], None);
+    assert!(matches!(result, CommandResult::SessionEnded));
+}
+
+#[test]
+fn tabs_are_listed_as_windows_by_index() {
+    assert_eq!(
+        list_windows(&["Tab #1".to_owned(), "editor".to_owned()]),
+        vec!["0: Tab #1 @0".to_owned(), "1: editor @1".to_owned()]
+    );
+}
@@ -174,7 +174,7 @@ impl ClientOsApi for FakeClientOsApi {
     fn recv_from_server(&self) -> Option<(ServerToClientMsg, ErrorContext)> {
         unimplemented!()
     }
-    fn handle_signals(&self, _sigwinch_cb: Box<dyn Fn()>, _quit_cb: Box<dyn Fn()>) {
+    fn handle_signals(&self, _sigwinch_cb: Box<dyn Fn()>, _quit_cb: Box<dyn Fn(i32)>) {
         unimplemented!()
     }
     fn connect_to_server(&self, _path: &Path) {
@@ -318,3 +318,70 @@ pub fn move_focus_left_in_normal_mode() {
         "All actions sent to server properly"
     );
 }
+
+#[test]
+pub fn multi_megabyte_paste_is_sent_in_chunks() {
+    let pasted_text = "pasted line\n".repeat(200 * 1024);
+    let stdin_events = vec![
+        (vec![], InputEvent::Paste(pasted_text.clone())),
+        (
+            commands::QUIT.to_vec(),
+            InputEvent::Key(KeyEvent {
+                key: KeyCode::Char('q'),
+                modifiers: Modifiers::CTRL,
+            }),
+        ),
+    ];
+
+    let events_sent_to_server = Arc::new(Mutex::new(vec![]));
+    let command_is_executing = CommandIsExecuting::new();
+    let client_os_api = Box::new(FakeClientOsApi::new(
+        events_sent_to_server.clone(),
+        command_is_executing.clone(),
+    ));
+    let config = Config::from_default_assets().unwrap();
+    let options = Options::default();
+
+    let (send_client_instructions, _receive_client_instructions): ChannelWithContext<
+        ClientInstruction,
+    > = channels::bounded(50);
+    let send_client_instructions = SenderWithContext::new(send_client_instructions);
+
+    let (send_input_instructions, receive_input_instructions): ChannelWithContext<
+        InputInstruction,
+    > = channels::bounded(50);
+    let send_input_instructions = SenderWithContext::new(send_input_instructions);
+    for event in stdin_events {
+        send_input_instructions
+            .send(InputInstruction::KeyEvent(event.1, event.0))
+            .unwrap();
+    }
+
+    let default_mode = InputMode::Normal;
+    input_loop(
+        client_os_api,
+        config,
+        options,
+        command_is_executing,
+        send_client_instructions,
+        default_mode,
+        receive_input_instructions,
+    );
+    let received_actions = extract_actions_sent_to_server(events_sent_to_server);
+    let written_bytes: Vec<Vec<u8>> = received_actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::Write(bytes) => Some(bytes.clone()),
+            _ => None,
+        })
+        .collect();
+    // the bracketed paste start and end sequences surround the chunks of the paste
+    assert_eq!(written_bytes.len(), 40, "paste sent in chunks");
+    assert_eq!(written_bytes.first(), Some(&b"\x1b[200~".to_vec()));
+    assert_eq!(written_bytes.last(), Some(&b"\x1b[201~".to_vec()));
+    assert!(
+        written_bytes[1..written_bytes.len() - 1].concat() == pasted_text.as_bytes(),
+        "chunks contain the whole paste in order"
+    );
+    assert_eq!(received_actions.last(), Some(&Action::Quit));
+}
@@ -1,22 +1,34 @@
 use zellij_utils::async_std::task;
 use zellij_utils::consts::{ZELLIJ_SESSION_INFO_CACHE_DIR, ZELLIJ_SOCK_DIR};
-use zellij_utils::data::SessionInfo;
+use zellij_utils::data::{BatteryState, Event, HttpVerb, SessionInfo, SystemState};
 use zellij_utils::errors::{prelude::*, BackgroundJobContext, ContextType};
+use zellij_utils::input::config::Config;
+use zellij_utils::session_serialization::{
+    resurrectable_sessions, session_layout_cache_dir, session_layout_staging_dir,
+    write_session_layout,
+};
+use zellij_utils::surf::http::headers::{HeaderName, HeaderValue};
+use zellij_utils::{libc, surf};
 
 use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::Write;
 use std::os::unix::fs::FileTypeExt;
 use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
 use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex,
 };
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::panes::PaneId;
+use crate::plugins::PluginInstruction;
+use crate::pty::PtyInstruction;
 use crate::screen::ScreenInstruction;
 use crate::thread_bus::Bus;
+use crate::{ClientId, ServerInstruction};
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum BackgroundJob {
@@ -25,6 +37,34 @@ pub enum BackgroundJob {
     StopPluginLoadingAnimation(u32),        // u32 - plugin_id
     ReadAllSessionInfosOnMachine,           // u32 - plugin_id
     ReportSessionInfo(String, SessionInfo), // String - session name
+    PollTerminalProcesses,
+    StopPollingTerminalProcesses,
+    MonitorPaneSilence,
+    SerializeSessionLayout,
+    WriteSessionLayout(String, String, BTreeMap<String, String>), // session name, layout, pane contents
+    WriteKeybindsToConfigFile(PathBuf, String), // config file path, kdl keybinds block
+    WatchConfigFile(PathBuf),
+    WatchPluginFile(PathBuf),
+    RunCommand(
+        u32,      // plugin_id
+        ClientId, // client_id
+        String,   // command
+        Vec<String>,
+        BTreeMap<String, String>, // env variables
+        Option<PathBuf>,          // cwd
+    ),
+    WebRequest(
+        u32,      // plugin_id
+        ClientId, // client_id
+        String,   // url
+        HttpVerb,
+        BTreeMap<String, String>, // headers
+        Vec<u8>,                  // body
+    ),
+    ReportSystemState(u32, ClientId, Duration), // plugin_id, client_id, interval
+    StopReportingSystemState(u32),              // plugin_id
+    SetPluginInterval(u32, ClientId, Duration), // plugin_id, client_id, interval
+    ClearPluginInterval(u32),                   // plugin_id
     Exit,
 }
 
@@ -40,6 +80,26 @@ impl From<&BackgroundJob> for BackgroundJobContext {
                 BackgroundJobContext::ReadAllSessionInfosOnMachine
             },
             BackgroundJob::ReportSessionInfo(..) => BackgroundJobContext::ReportSessionInfo,
+            BackgroundJob::PollTerminalProcesses => BackgroundJobContext::PollTerminalProcesses,
+            BackgroundJob::StopPollingTerminalProcesses => {
+                BackgroundJobContext::StopPollingTerminalProcesses
+            },
+            BackgroundJob::MonitorPaneSilence => BackgroundJobContext::MonitorPaneSilence,
+            BackgroundJob::SerializeSessionLayout => BackgroundJobContext::SerializeSessionLayout,
+            BackgroundJob::WriteSessionLayout(..) => BackgroundJobContext::WriteSessionLayout,
+            BackgroundJob::WriteKeybindsToConfigFile(..) => {
+                BackgroundJobContext::WriteKeybindsToConfigFile
+            },
+            BackgroundJob::WatchConfigFile(..) => BackgroundJobContext::WatchConfigFile,
+            BackgroundJob::WatchPluginFile(..) => BackgroundJobContext::WatchPluginFile,
+            BackgroundJob::RunCommand(..) => BackgroundJobContext::RunCommand,
+            BackgroundJob::WebRequest(..) => BackgroundJobContext::WebRequest,
+            BackgroundJob::ReportSystemState(..) => BackgroundJobContext::ReportSystemState,
+            BackgroundJob::StopReportingSystemState(..) => {
+                BackgroundJobContext::StopReportingSystemState
+            },
+            BackgroundJob::SetPluginInterval(..) => BackgroundJobContext::SetPluginInterval,
+            BackgroundJob::ClearPluginInterval(..) => BackgroundJobContext::ClearPluginInterval,
             BackgroundJob::Exit => BackgroundJobContext::Exit,
         }
     }
@@ -48,11 +108,19 @@ impl From<&BackgroundJob> for BackgroundJobContext {
 static FLASH_DURATION_MS: u64 = 1000;
 static PLUGIN_ANIMATION_OFFSET_DURATION_MD: u64 = 500;
 static SESSION_READ_DURATION: u64 = 1000;
+static TERMINAL_PROCESSES_POLL_DURATION: u64 = 1000;
+static PANE_SILENCE_POLL_DURATION: u64 = 1000;
+static SESSION_SERIALIZATION_DURATION: u64 = 60000;
+static CONFIG_FILE_POLL_DURATION: u64 = 1000;
+static PLUGIN_FILE_POLL_DURATION: u64 = 500;
 
 pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
     let err_context = || "failed to write to pty".to_string();
     let mut running_jobs: HashMap<BackgroundJob, Instant> = HashMap::new();
     let mut loading_plugins: HashMap<u32, Arc<AtomicBool>> = HashMap::new(); // u32 - plugin_id
+    let mut system_state_subscribers: HashMap<u32, Arc<AtomicBool>> = HashMap::new(); // u32 - plugin_id
+    let mut plugin_intervals: HashMap<u32, Arc<AtomicBool>> = HashMap::new(); // u32 - plugin_id
+    let mut terminal_processes_poll: Option<Arc<AtomicBool>> = None;
     let current_session_name = Arc::new(Mutex::new(String::default()));
     let current_session_info = Arc::new(Mutex::new(SessionInfo::default()));
 
@@ -109,7 +177,13 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
                 }
             },
             BackgroundJob::ReportSessionInfo(session_name, session_info) => {
-                *current_session_name.lock().unwrap() = session_name;
+                let mut current_session_name = current_session_name.lock().unwrap();
+                if !current_session_name.is_empty() && *current_session_name != session_name {
+                    // the session was renamed, its info should not linger under the old name
+                    let _ =
+                        std::fs::remove_file(session_info_cache_file_name(&current_session_name));
+                }
+                *current_session_name = session_name;
                 *current_session_info.lock().unwrap() = session_info;
             },
             BackgroundJob::ReadAllSessionInfosOnMachine => {
@@ -160,6 +234,8 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
                                 });
                             }
 
+                            let resurrectable_sessions =
+                                resurrectable_sessions(&other_session_names);
                             for session_name in other_session_names {
                                 let session_cache_file_name = ZELLIJ_SESSION_INFO_CACHE_DIR
                                     .join(format!("{}.kdl", session_name));
@@ -176,6 +252,7 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
                             }
                             let _ = senders.send_to_screen(ScreenInstruction::UpdateSessionInfos(
                                 session_infos_on_machine,
+                                resurrectable_sessions,
                             ));
                             task::sleep(std::time::Duration::from_millis(SESSION_READ_DURATION))
                                 .await;
@@ -183,10 +260,256 @@ pub(crate) fn background_jobs_main(bus: Bus<BackgroundJob>) -> Result<()> {
                     }
                 });
             },
+            BackgroundJob::PollTerminalProcesses => {
+                // this job runs until it is stopped, it periodically asks the pty thread to
+                // report the processes running in each terminal pane so that they can be
+                // forwarded to plugins and queried from the cli
+                if terminal_processes_poll.is_some() {
+                    continue;
+                }
+                let poll = Arc::new(AtomicBool::new(true));
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    let poll = poll.clone();
+                    async move {
+                        while poll.load(Ordering::SeqCst) {
+                            let _ = senders.send_to_pty(PtyInstruction::ReportTerminalProcesses);
+                            task::sleep(std::time::Duration::from_millis(
+                                TERMINAL_PROCESSES_POLL_DURATION,
+                            ))
+                            .await;
+                        }
+                    }
+                });
+                terminal_processes_poll = Some(poll);
+            },
+            BackgroundJob::StopPollingTerminalProcesses => {
+                if let Some(poll) = terminal_processes_poll.take() {
+                    poll.store(false, Ordering::SeqCst);
+                }
+            },
+            BackgroundJob::MonitorPaneSilence => {
+                // this job should only be run once, it periodically asks the screen thread to
+                // mark panes that had no output for a while as silent
+                if running_jobs.get(&job).is_some() {
+                    continue;
+                }
+                running_jobs.insert(job, Instant::now());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        loop {
+                            task::sleep(std::time::Duration::from_millis(
+                                PANE_SILENCE_POLL_DURATION,
+                            ))
+                            .await;
+                            let _ = senders.send_to_screen(ScreenInstruction::MarkSilentPanes);
+                        }
+                    }
+                });
+            },
+            BackgroundJob::SerializeSessionLayout => {
+                // this job should only be run once, it periodically asks the screen thread to dump
+                // the current session layout to the cache folder so that the session can later be
+                // resurrected
+                if running_jobs.get(&job).is_some() {
+                    continue;
+                }
+                running_jobs.insert(job, Instant::now());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        loop {
+                            task::sleep(std::time::Duration::from_millis(
+                                SESSION_SERIALIZATION_DURATION,
+                            ))
+                            .await;
+                            let _ = senders.send_to_screen(ScreenInstruction::DumpLayoutToHd);
+                        }
+                    }
+                });
+            },
+            BackgroundJob::WriteSessionLayout(session_name, session_layout, pane_contents) => {
+                if let Err(e) = write_session_layout(
+                    &session_layout_cache_dir(&session_name),
+                    &session_layout_staging_dir(&session_name),
+                    &session_layout,
+                    &pane_contents,
+                ) {
+                    log::error!("Failed to serialize session layout: {:?}", e);
+                }
+            },
+            BackgroundJob::WriteKeybindsToConfigFile(config_file_path, kdl_keybinds) => {
+                match Config::write_keybinds_to_file(&kdl_keybinds, &config_file_path) {
+                    Ok(()) => {
+                        let _ = bus.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                            None,
+                            None,
+                            Event::ConfigWasWrittenToDisk,
+                        )]));
+                    },
+                    Err(e) => {
+                        log::error!("Failed to write keybindings to config file: {:?}", e);
+                    },
+                }
+            },
+            BackgroundJob::WatchConfigFile(config_file_path) => {
+                // this job should only be run once, it periodically checks whether the config file
+                // was modified and if so asks the server to reload it into the session
+                if running_jobs.get(&job).is_some() {
+                    continue;
+                }
+                running_jobs.insert(job, Instant::now());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        watch_file_for_changes(config_file_path, CONFIG_FILE_POLL_DURATION, || {
+                            let _ = senders.send_to_server(ServerInstruction::ReloadConfig);
+                        })
+                        .await
+                    }
+                });
+            },
+            BackgroundJob::WatchPluginFile(plugin_file_path) => {
+                // one watcher per plugin file, no matter how many instances of it are loaded
+                if running_jobs.get(&job).is_some() {
+                    continue;
+                }
+                running_jobs.insert(job, Instant::now());
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        watch_file_for_changes(
+                            plugin_file_path.clone(),
+                            PLUGIN_FILE_POLL_DURATION,
+                            || {
+                                let _ = senders.send_to_plugin(
+                                    PluginInstruction::PluginFileChanged(plugin_file_path.clone()),
+                                );
+                            },
+                        )
+                        .await
+                    }
+                });
+            },
+            BackgroundJob::RunCommand(plugin_id, client_id, command, args, env_variables, cwd) => {
+                // commands can take a while (eg. a test run), so they are not awaited on this thread
+                task::spawn_blocking({
+                    let senders = bus.senders.clone();
+                    move || {
+                        let mut command_to_run = Command::new(&command);
+                        command_to_run.args(&args).envs(&env_variables);
+                        if let Some(cwd) = cwd {
+                            command_to_run.current_dir(cwd);
+                        }
+                        let (exit_code, stdout, stderr) = match command_to_run.output() {
+                            Ok(output) => (output.status.code(), output.stdout, output.stderr),
+                            Err(e) => {
+                                log::error!("Failed to run command {}: {:?}", command, e);
+                                (None, vec![], e.to_string().into_bytes())
+                            },
+                        };
+                        let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::RunCommandResult(exit_code, stdout, stderr),
+                        )]));
+                    }
+                });
+            },
+            BackgroundJob::WebRequest(plugin_id, client_id, url, verb, headers, body) => {
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    async move {
+                        let (status, headers, body) =
+                            match make_web_request(&url, verb, headers, body).await {
+                                Ok(response) => response,
+                                Err(e) => {
+                                    log::error!("Failed to make web request to {}: {}", url, e);
+                                    (None, BTreeMap::new(), e.to_string().into_bytes())
+                                },
+                            };
+                        let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::WebRequestResult(status, headers, body),
+                        )]));
+                    }
+                });
+            },
+            BackgroundJob::ReportSystemState(plugin_id, client_id, interval) => {
+                // subscribing again replaces the previous subscription (eg. to change its interval)
+                if let Some(subscription) = system_state_subscribers.remove(&plugin_id) {
+                    subscription.store(false, Ordering::SeqCst);
+                }
+                let subscription = Arc::new(AtomicBool::new(true));
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    let subscription = subscription.clone();
+                    async move {
+                        while subscription.load(Ordering::SeqCst) {
+                            let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::SystemStateUpdate(read_system_state()),
+                            )]));
+                            task::sleep(interval).await;
+                        }
+                    }
+                });
+                system_state_subscribers.insert(plugin_id, subscription);
+            },
+            BackgroundJob::StopReportingSystemState(plugin_id) => {
+                if let Some(subscription) = system_state_subscribers.remove(&plugin_id) {
+                    subscription.store(false, Ordering::SeqCst);
+                }
+            },
+            BackgroundJob::SetPluginInterval(plugin_id, client_id, interval) => {
+                // a plugin has at most one interval, setting another one replaces it
+                if let Some(running_interval) = plugin_intervals.remove(&plugin_id) {
+                    running_interval.store(false, Ordering::SeqCst);
+                }
+                let running_interval = Arc::new(AtomicBool::new(true));
+                task::spawn({
+                    let senders = bus.senders.clone();
+                    let running_interval = running_interval.clone();
+                    async move {
+                        let mut last_tick = Instant::now();
+                        loop {
+                            task::sleep(interval).await;
+                            if !running_interval.load(Ordering::SeqCst) {
+                                break;
+                            }
+                            let elapsed_time = last_tick.elapsed().as_secs_f64();
+                            last_tick = Instant::now();
+                            let _ = senders.send_to_plugin(PluginInstruction::Update(vec![(
+                                Some(plugin_id),
+                                Some(client_id),
+                                Event::Timer(elapsed_time),
+                            )]));
+                        }
+                    }
+                });
+                plugin_intervals.insert(plugin_id, running_interval);
+            },
+            BackgroundJob::ClearPluginInterval(plugin_id) => {
+                if let Some(running_interval) = plugin_intervals.remove(&plugin_id) {
+                    running_interval.store(false, Ordering::SeqCst);
+                }
+            },
             BackgroundJob::Exit => {
                 for loading_plugin in loading_plugins.values() {
                     loading_plugin.store(false, Ordering::SeqCst);
                 }
+                for subscription in system_state_subscribers.values() {
+                    subscription.store(false, Ordering::SeqCst);
+                }
+                for running_interval in plugin_intervals.values() {
+                    running_interval.store(false, Ordering::SeqCst);
+                }
+                if let Some(poll) = terminal_processes_poll.take() {
+                    poll.store(false, Ordering::SeqCst);
+                }
 
                 let cache_file_name =
                     session_info_cache_file_name(&current_session_name.lock().unwrap().to_owned());
@@ -217,6 +540,102 @@ fn job_already_running(
     }
 }
 
+fn read_system_state() -> SystemState {
+    let now = chrono::Local::now();
+    SystemState {
+        unix_time: now.timestamp().max(0) as u64,
+        utc_offset: now.offset().local_minus_utc(),
+        load_average: load_average(),
+        battery: battery_state(),
+    }
+}
+
+fn load_average() -> Option<[f64; 3]> {
+    let mut load_average = [0.0; 3];
+    // the buffer has room for the 3 samples asked for
+    let samples = unsafe { libc::getloadavg(load_average.as_mut_ptr(), 3) };
+    if samples == 3 {
+        Some(load_average)
+    } else {
+        None
+    }
+}
+
+/// The first battery the kernel reports, this is only supported on linux
+fn battery_state() -> Option<BatteryState> {
+    fs::read_dir("/sys/class/power_supply")
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("BAT"))
+        .find_map(|entry| {
+            let battery = entry.path();
+            let percentage = fs::read_to_string(battery.join("capacity"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            let status = fs::read_to_string(battery.join("status")).unwrap_or_default();
+            Some(BatteryState {
+                percentage,
+                charging: status.trim() == "Charging",
+            })
+        })
+}
+
+fn file_modified_time(path: &PathBuf) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+async fn watch_file_for_changes(path: PathBuf, poll_duration_ms: u64, on_change: impl Fn()) {
+    // we only report a change once the modified time has stayed the same for a whole poll
+    // interval, so that we do not pick up files that are still in the middle of being written
+    let mut last_reported = file_modified_time(&path);
+    let mut last_polled = last_reported;
+    loop {
+        task::sleep(Duration::from_millis(poll_duration_ms)).await;
+        let modified = file_modified_time(&path);
+        if modified.is_some() && modified == last_polled && modified != last_reported {
+            on_change();
+            last_reported = modified;
+        }
+        last_polled = modified;
+    }
+}
+
+async fn make_web_request(
+    url: &str,
+    verb: HttpVerb,
+    headers: BTreeMap<String, String>,
+    body: Vec<u8>,
+) -> std::result::Result<(Option<u16>, BTreeMap<String, String>, Vec<u8>), surf::Error> {
+    let method = match verb {
+        HttpVerb::Get => surf::http::Method::Get,
+        HttpVerb::Post => surf::http::Method::Post,
+        HttpVerb::Put => surf::http::Method::Put,
+        HttpVerb::Delete => surf::http::Method::Delete,
+    };
+    let url = surf::Url::parse(url)?;
+    let mut request = surf::RequestBuilder::new(method, url);
+    for (name, value) in headers {
+        // these come from plugins, so they are parsed rather than trusted to be valid ascii
+        let name = HeaderName::from_str(&name)?;
+        let value = HeaderValue::from_str(&value)?;
+        request = request.header(name, value);
+    }
+    if !body.is_empty() {
+        request = request.body(body);
+    }
+    let client = surf::client().with(surf::middleware::Redirect::default());
+    let mut response = client.send(request.build()).await?;
+    let status = u16::from(response.status());
+    let headers = response
+        .iter()
+        .map(|(name, values)| (name.to_string(), values.to_string()))
+        .collect();
+    let body = response.body_bytes().await?;
+    Ok((Some(status), headers, body))
+}
+
 fn session_info_cache_file_name(session_name: &str) -> PathBuf {
     ZELLIJ_SESSION_INFO_CACHE_DIR.join(format!("{}.kdl", &session_name))
 }
@@ -10,6 +10,7 @@ mod pty;
 mod pty_writer;
 mod route;
 mod screen;
+mod session_hooks;
 mod terminal_bytes;
 mod thread_bus;
 mod ui;
@@ -17,13 +18,19 @@ mod ui;
 use background_jobs::{background_jobs_main, BackgroundJob};
 use log::info;
 use pty_writer::{pty_writer_main, PtyWriteInstruction};
+use session_hooks::{run_session_hook, SessionHook};
 use std::collections::{HashMap, HashSet};
 use std::{
+    io,
+    os::unix::io::{FromRawFd, IntoRawFd, RawFd},
+    os::unix::net::UnixListener,
     path::PathBuf,
     sync::{Arc, RwLock},
     thread,
 };
 use zellij_utils::envs;
+use zellij_utils::interprocess::local_socket::{LocalSocketListener, LocalSocketStream};
+use zellij_utils::nix::fcntl::{fcntl, FcntlArg, FdFlag};
 use zellij_utils::nix::sys::stat::{umask, Mode};
 use zellij_utils::pane_size::Size;
 
@@ -40,18 +47,27 @@ use route::route_thread_main;
 use zellij_utils::{
     channels::{self, ChannelWithContext, SenderWithContext},
     cli::CliArgs,
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
-    data::{ConnectToSession, Event, PluginCapabilities},
+    consts::{
+        BELL_COMMAND, DEFAULT_LINK_OPENER, DEFAULT_SCROLLBACK_SPILLOVER_LIMIT,
+        DEFAULT_SCROLL_BUFFER_SIZE, LINK_OPENER, MONITOR_SILENCE, ON_CLIENT_ATTACH_COMMAND,
+        ON_CLIENT_DETACH_COMMAND, ON_SESSION_CREATE_COMMAND, PANE_CLOSE_CONFIRMATION,
+        PANE_FRAME_TITLE, SCROLLBACK_SPILLOVER, SCROLLBACK_SPILLOVER_LIMIT, SCROLL_BUFFER_SIZE,
+        SET_TERMINAL_TITLE, TERMINAL_TITLE_TEMPLATE, WORD_CHARACTERS,
+    },
+    data::{ConnectToSession, Event, PluginCapabilities, Style},
     errors::{prelude::*, ContextType, ErrorInstruction, FatalError, ServerContext},
     input::{
         command::{RunCommand, TerminalAction},
+        config::Config,
         get_mode_info,
+        keybinds::Keybinds,
         layout::Layout,
         options::Options,
         plugins::PluginsConfig,
+        theme::Theme,
     },
     ipc::{ClientAttributes, ExitReason, ServerToClientMsg},
-    setup::get_default_data_dir,
+    setup::{find_default_config_dir, get_default_data_dir, get_theme_dir, load_themes},
 };
 
 pub type ClientId = u16;
@@ -84,7 +100,12 @@ pub enum ServerInstruction {
     ConnStatus(ClientId),
     ActiveClients(ClientId),
     Log(Vec<String>, ClientId),
+    CommandExited(Option<i32>, ClientId), // exit status of a command this cli client waits for
     SwitchSession(ConnectToSession, ClientId),
+    RebindKeys(String, bool), // kdl keybinds block, write to config file
+    ReloadConfig,
+    UnblockCliPipeInput(ClientId), // the `zellij pipe` client can send its next message
+    SwitchTheme(String),           // theme name
 }
 
 impl From<&ServerInstruction> for ServerContext {
@@ -102,7 +123,12 @@ impl From<&ServerInstruction> for ServerContext {
             ServerInstruction::ConnStatus(..) => ServerContext::ConnStatus,
             ServerInstruction::ActiveClients(_) => ServerContext::ActiveClients,
             ServerInstruction::Log(..) => ServerContext::Log,
+            ServerInstruction::CommandExited(..) => ServerContext::CommandExited,
             ServerInstruction::SwitchSession(..) => ServerContext::SwitchSession,
+            ServerInstruction::RebindKeys(..) => ServerContext::RebindKeys,
+            ServerInstruction::ReloadConfig => ServerContext::ReloadConfig,
+            ServerInstruction::UnblockCliPipeInput(..) => ServerContext::UnblockCliPipeInput,
+            ServerInstruction::SwitchTheme(..) => ServerContext::SwitchTheme,
         }
     }
 }
@@ -119,6 +145,8 @@ pub(crate) struct SessionMetaData {
     pub client_attributes: ClientAttributes,
     pub default_shell: Option<TerminalAction>,
     pub layout: Box<Layout>,
+    pub config_options: Box<Options>,
+    pub config_file_path: Option<PathBuf>,
     screen_thread: Option<thread::JoinHandle<()>>,
     pty_thread: Option<thread::JoinHandle<()>>,
     plugin_thread: Option<thread::JoinHandle<()>>,
@@ -128,17 +156,19 @@ pub(crate) struct SessionMetaData {
 
 impl Drop for SessionMetaData {
     fn drop(&mut self) {
-        let _ = self.senders.send_to_pty(PtyInstruction::Exit);
+        // the screen serializes the session as it exits, which goes through the pty and the
+        // background jobs threads, so those are only asked to exit once it is done
         let _ = self.senders.send_to_screen(ScreenInstruction::Exit);
-        let _ = self.senders.send_to_plugin(PluginInstruction::Exit);
-        let _ = self.senders.send_to_pty_writer(PtyWriteInstruction::Exit);
-        let _ = self.senders.send_to_background_jobs(BackgroundJob::Exit);
         if let Some(screen_thread) = self.screen_thread.take() {
             let _ = screen_thread.join();
         }
+        let _ = self.senders.send_to_pty(PtyInstruction::Exit);
+        let _ = self.senders.send_to_plugin(PluginInstruction::Exit);
+        let _ = self.senders.send_to_pty_writer(PtyWriteInstruction::Exit);
         if let Some(pty_thread) = self.pty_thread.take() {
             let _ = pty_thread.join();
         }
+        let _ = self.senders.send_to_background_jobs(BackgroundJob::Exit);
         if let Some(plugin_thread) = self.plugin_thread.take() {
             let _ = plugin_thread.join();
         }
@@ -241,9 +271,50 @@ impl SessionState {
     }
 }
 
-pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
-    info!("Starting Zellij server!");
+const SD_LISTEN_FDS_START: RawFd = 3; // the first file descriptor passed by systemd
+
+/// Returns the listening socket passed to this process by systemd's socket activation (see
+/// `sd_listen_fds(3)`), if any
+pub fn socket_activation_listener() -> Option<UnixListener> {
+    let listen_fds = systemd_listen_fds(
+        std::env::var("LISTEN_PID").ok().as_deref(),
+        std::env::var("LISTEN_FDS").ok().as_deref(),
+        std::process::id(),
+    )?;
+    if listen_fds > 1 {
+        log::warn!(
+            "Got {} sockets from systemd, using the first one",
+            listen_fds
+        );
+    }
+    // these should not be inherited by the processes running in the panes
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+    if let Err(e) = fcntl(SD_LISTEN_FDS_START, FcntlArg::F_SETFD(FdFlag::FD_CLOEXEC)) {
+        log::error!("Failed to set close-on-exec on the systemd socket: {}", e);
+    }
+    Some(unsafe { UnixListener::from_raw_fd(SD_LISTEN_FDS_START) })
+}
 
+/// Returns how many sockets systemd passed to the process with the given pid, given the values of
+/// the `LISTEN_PID` and `LISTEN_FDS` environment variables (which are meant for another process,
+/// eg. our parent, if the pids don't match)
+fn systemd_listen_fds(
+    listen_pid: Option<&str>,
+    listen_fds: Option<&str>,
+    pid: u32,
+) -> Option<usize> {
+    let listen_pid: u32 = listen_pid?.parse().ok()?;
+    let listen_fds: usize = listen_fds?.parse().ok()?;
+    if listen_pid != pid || listen_fds == 0 {
+        return None;
+    }
+    Some(listen_fds)
+}
+
+/// Starts the server in the background, detached from the terminal of the client that spawned it
+pub fn start_server(os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
     // preserve the current umask: read current value by setting to another mode, and then restoring it
     let current_umask = umask(Mode::all());
     umask(current_umask);
@@ -253,6 +324,19 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
         .start()
         .expect("could not daemonize the server process");
 
+    run_server(os_input, socket_path, None);
+}
+
+/// Runs the server in the current process until its session ends, accepting clients on
+/// `listener` if one is given (eg. by systemd's socket activation) or on a new socket bound at
+/// `socket_path` otherwise
+pub fn run_server(
+    mut os_input: Box<dyn ServerOsApi>,
+    socket_path: PathBuf,
+    listener: Option<UnixListener>,
+) {
+    info!("Starting Zellij server!");
+
     envs::set_zellij("0".to_string());
 
     let (to_server, server_receiver): ChannelWithContext<ServerInstruction> = channels::bounded(50);
@@ -271,9 +355,7 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
     let _ = thread::Builder::new()
         .name("server_listener".to_string())
         .spawn({
-            use zellij_utils::{
-                interprocess::local_socket::LocalSocketListener, shared::set_permissions,
-            };
+            use zellij_utils::shared::set_permissions;
 
             let os_input = os_input.clone();
             let session_data = session_data.clone();
@@ -281,14 +363,27 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
             let to_server = to_server.clone();
             let socket_path = socket_path.clone();
             move || {
-                drop(std::fs::remove_file(&socket_path));
-                let listener = LocalSocketListener::bind(&*socket_path).unwrap();
+                let bound_listener;
+                let incoming: Box<dyn Iterator<Item = io::Result<LocalSocketStream>>> =
+                    match &listener {
+                        Some(listener) => Box::new(listener.incoming().map(|stream| {
+                            // the accepted stream is owned by the LocalSocketStream from now on
+                            stream.map(|stream| unsafe {
+                                LocalSocketStream::from_raw_fd(stream.into_raw_fd())
+                            })
+                        })),
+                        None => {
+                            drop(std::fs::remove_file(&socket_path));
+                            bound_listener = LocalSocketListener::bind(&*socket_path).unwrap();
+                            Box::new(bound_listener.incoming())
+                        },
+                    };
                 // set the sticky bit to avoid the socket file being potentially cleaned up
                 // https://specifications.freedesktop.org/basedir-spec/basedir-spec-latest.html states that for XDG_RUNTIME_DIR:
                 // "To ensure that your files are not removed, they should have their access time timestamp modified at least once every 6 hours of monotonic time or the 'sticky' bit should be set on the file. "
                 // It is not guaranteed that all platforms allow setting the sticky bit on sockets!
                 drop(set_permissions(&socket_path, 0o1700));
-                for stream in listener.incoming() {
+                for stream in incoming {
                     match stream {
                         Ok(stream) => {
                             let mut os_input = os_input.clone();
@@ -348,6 +443,9 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     .write()
                     .unwrap()
                     .set_client_size(client_id, client_attributes.size);
+                if let Err(e) = run_session_hook(SessionHook::SessionCreate) {
+                    Err::<(), _>(e).non_fatal();
+                }
 
                 let default_shell = config_options.default_shell.map(|shell| {
                     TerminalAction::RunCommand(RunCommand {
@@ -478,6 +576,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     ServerToClientMsg::SwitchToMode(mode),
                     session_state
                 );
+                let connected_clients = session_state.read().unwrap().clients.len();
+                if let Err(e) =
+                    run_session_hook(SessionHook::ClientAttach(client_id, connected_clients))
+                {
+                    Err::<(), _>(e).non_fatal();
+                }
             },
             ServerInstruction::UnblockInputThread => {
                 for client_id in session_state.read().unwrap().clients.keys() {
@@ -526,6 +630,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
             },
             ServerInstruction::RemoveClient(client_id) => {
                 remove_client!(client_id, os_input, session_state);
+                let connected_clients = session_state.read().unwrap().clients.len();
+                if let Err(e) =
+                    run_session_hook(SessionHook::ClientDetach(client_id, connected_clients))
+                {
+                    Err::<(), _>(e).non_fatal();
+                }
                 if let Some(min_size) = session_state.read().unwrap().min_client_terminal_size() {
                     session_data
                         .write()
@@ -567,6 +677,12 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     let _ = os_input
                         .send_to_client(client_id, ServerToClientMsg::Exit(ExitReason::Normal));
                     remove_client!(client_id, os_input, session_state);
+                    let connected_clients = session_state.read().unwrap().clients.len();
+                    if let Err(e) =
+                        run_session_hook(SessionHook::ClientDetach(client_id, connected_clients))
+                    {
+                        Err::<(), _>(e).non_fatal();
+                    }
                     if let Some(min_size) = session_state.read().unwrap().min_client_terminal_size()
                     {
                         session_data
@@ -653,6 +769,36 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                     session_state
                 );
             },
+            ServerInstruction::UnblockCliPipeInput(client_id) => {
+                if session_state
+                    .read()
+                    .unwrap()
+                    .clients
+                    .contains_key(&client_id)
+                {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::UnblockCliPipeInput,
+                        session_state
+                    );
+                }
+            },
+            ServerInstruction::CommandExited(exit_status, client_id) => {
+                if session_state
+                    .read()
+                    .unwrap()
+                    .clients
+                    .contains_key(&client_id)
+                {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::CommandExited(exit_status),
+                        session_state
+                    );
+                }
+            },
             ServerInstruction::SwitchSession(connect_to_session, client_id) => {
                 if let Some(min_size) = session_state.read().unwrap().min_client_terminal_size() {
                     session_data
@@ -688,13 +834,175 @@ pub fn start_server(mut os_input: Box<dyn ServerOsApi>, socket_path: PathBuf) {
                 );
                 remove_client!(client_id, os_input, session_state);
             },
+            ServerInstruction::RebindKeys(kdl_keybinds, write_config_to_disk) => {
+                let mut session_data = session_data.write().unwrap();
+                let session_data = session_data.as_mut().unwrap();
+                let keybinds = match Keybinds::from_kdl_string(
+                    &kdl_keybinds,
+                    session_data.client_attributes.keybinds.clone(),
+                    &session_data.config_options,
+                ) {
+                    Ok(keybinds) => keybinds,
+                    Err(e) => {
+                        log::error!("Failed to parse keybindings: {:?}", e);
+                        continue;
+                    },
+                };
+                session_data.client_attributes.keybinds = keybinds.clone();
+                session_data
+                    .senders
+                    .send_to_screen(ScreenInstruction::RebindKeys(keybinds.clone()))
+                    .unwrap();
+                for client_id in session_state.read().unwrap().client_ids() {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::RebindKeys(keybinds.clone()),
+                        session_state
+                    );
+                }
+                if write_config_to_disk {
+                    match session_data.config_file_path.clone() {
+                        Some(config_file_path) => session_data
+                            .senders
+                            .send_to_background_jobs(BackgroundJob::WriteKeybindsToConfigFile(
+                                config_file_path,
+                                kdl_keybinds,
+                            ))
+                            .unwrap(),
+                        None => log::error!("No config file to write keybindings to"),
+                    }
+                }
+            },
+            ServerInstruction::ReloadConfig => {
+                let mut session_data = session_data.write().unwrap();
+                let session_data = match session_data.as_mut() {
+                    Some(session_data) => session_data,
+                    None => continue,
+                };
+                let config = match session_data.config_file_path.as_ref().map(|path| {
+                    Config::from_default_assets()
+                        .and_then(|default_config| Config::from_path(path, Some(default_config)))
+                }) {
+                    Some(Ok(config)) => config,
+                    Some(Err(e)) => {
+                        log::error!("Failed to reload config: {:?}", e);
+                        continue;
+                    },
+                    None => continue,
+                };
+                let config_options = session_data.config_options.merge(config.options.clone());
+                // only touch the pane frames if they were changed in the config, so as not to
+                // undo them being toggled in the session
+                let draw_pane_frames = (config_options.pane_frames
+                    != session_data.config_options.pane_frames)
+                    .then(|| config_options.pane_frames.unwrap_or(true));
+                let mut style = session_data.client_attributes.style;
+                let theme_dir = config_options
+                    .theme_dir
+                    .clone()
+                    .or_else(|| get_theme_dir(find_default_config_dir()).filter(|d| d.exists()));
+                let themes = load_themes(config.themes.clone(), theme_dir).unwrap_or_else(|e| {
+                    log::error!("Failed to load themes: {:?}", e);
+                    config.themes.clone()
+                });
+                let theme_name = config_options.theme.as_deref().unwrap_or("default");
+                if let Some(theme) = themes.get_theme(theme_name) {
+                    apply_theme(&mut style, theme.clone());
+                }
+                style.rounded_corners = config.ui.pane_frames.rounded_corners;
+                style.hide_session_name = config.ui.pane_frames.hide_session_name;
+                session_data.capabilities.arrow_fonts =
+                    config_options.simplified_ui.unwrap_or_default();
+                session_data.client_attributes.style = style;
+                session_data.client_attributes.keybinds = config.keybinds.clone();
+                session_data.config_options = Box::new(config_options);
+                session_data
+                    .senders
+                    .send_to_screen(ScreenInstruction::Reconfigure(
+                        config.keybinds.clone(),
+                        style,
+                        session_data.capabilities,
+                        draw_pane_frames,
+                    ))
+                    .unwrap();
+                for client_id in session_state.read().unwrap().client_ids() {
+                    send_to_client!(
+                        client_id,
+                        os_input,
+                        ServerToClientMsg::RebindKeys(config.keybinds.clone()),
+                        session_state
+                    );
+                }
+            },
+            ServerInstruction::SwitchTheme(theme_name) => {
+                let mut session_data = session_data.write().unwrap();
+                let session_data = match session_data.as_mut() {
+                    Some(session_data) => session_data,
+                    None => continue,
+                };
+                // the themes of the configuration file the session was started with, or of the
+                // default configuration if it was started without one
+                let config = Config::from_default_assets().and_then(|default_config| {
+                    match session_data.config_file_path.as_ref() {
+                        Some(path) => Config::from_path(path, Some(default_config)),
+                        None => Ok(default_config),
+                    }
+                });
+                let theme_dir =
+                    session_data.config_options.theme_dir.clone().or_else(|| {
+                        get_theme_dir(find_default_config_dir()).filter(|d| d.exists())
+                    });
+                let theme = match config
+                    .and_then(|config| load_themes(config.themes, theme_dir))
+                    .map(|themes| themes.get_theme(&theme_name).cloned())
+                {
+                    Ok(Some(theme)) => theme,
+                    Ok(None) => {
+                        log::error!("Failed to switch theme: no theme named {}", theme_name);
+                        continue;
+                    },
+                    Err(e) => {
+                        log::error!("Failed to load themes: {:?}", e);
+                        continue;
+                    },
+                };
+                let mut style = session_data.client_attributes.style;
+                apply_theme(&mut style, theme);
+                session_data.client_attributes.style = style;
+                // kept when the configuration is reloaded, unless it sets another theme
+                session_data.config_options.theme = Some(theme_name);
+                session_data
+                    .senders
+                    .send_to_screen(ScreenInstruction::Reconfigure(
+                        session_data.client_attributes.keybinds.clone(),
+                        style,
+                        session_data.capabilities,
+                        None,
+                    ))
+                    .unwrap();
+            },
         }
     }
 
     // Drop cached session data before exit.
     *session_data.write().unwrap() = None;
 
-    drop(std::fs::remove_file(&socket_path));
+    // the session might have been renamed since its socket was created
+    let socket_path = match envs::get_session_name() {
+        Ok(session_name) => socket_path.with_file_name(session_name),
+        Err(_) => socket_path,
+    };
+    drop(std::fs::remove_file(socket_path));
+}
+
+fn apply_theme(style: &mut Style, mut theme: Theme) {
+    if !envs::terminal_supports_truecolor() {
+        theme.palette = theme.palette.to_eight_bit();
+        theme.styling = theme.styling.to_eight_bit();
+    }
+    style.colors = theme.palette;
+    style.styling = theme.styling;
 }
 
 pub struct SessionOptions {
@@ -722,6 +1030,42 @@ fn init_session(
             .scroll_buffer_size
             .unwrap_or(DEFAULT_SCROLL_BUFFER_SIZE),
     );
+    let _ = SCROLLBACK_SPILLOVER.set(config_options.scrollback_spillover.unwrap_or(false));
+    let _ = SCROLLBACK_SPILLOVER_LIMIT.set(
+        config_options
+            .scrollback_spillover_limit
+            .unwrap_or(DEFAULT_SCROLLBACK_SPILLOVER_LIMIT),
+    );
+    let _ = WORD_CHARACTERS.set(config_options.word_characters.clone().unwrap_or_default());
+    let _ = LINK_OPENER.set(
+        config_options
+            .link_opener
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LINK_OPENER.to_owned()),
+    );
+    if let Some(bell_command) = config_options.bell_command.clone() {
+        let _ = BELL_COMMAND.set(bell_command);
+    }
+    if let Some(monitor_silence) = config_options.monitor_silence {
+        let _ = MONITOR_SILENCE.set(monitor_silence);
+    }
+    let _ = PANE_CLOSE_CONFIRMATION.set(config_options.pane_close_confirmation.unwrap_or(true));
+    if let Some(command) = config_options.on_session_create_command.clone() {
+        let _ = ON_SESSION_CREATE_COMMAND.set(command);
+    }
+    if let Some(command) = config_options.on_client_attach_command.clone() {
+        let _ = ON_CLIENT_ATTACH_COMMAND.set(command);
+    }
+    if let Some(command) = config_options.on_client_detach_command.clone() {
+        let _ = ON_CLIENT_DETACH_COMMAND.set(command);
+    }
+    let _ = SET_TERMINAL_TITLE.set(config_options.set_terminal_title.unwrap_or(true));
+    if let Some(terminal_title_template) = config_options.terminal_title_template.clone() {
+        let _ = TERMINAL_TITLE_TEMPLATE.set(terminal_title_template);
+    }
+    if let Some(pane_frame_title) = client_attributes.pane_frame_title.clone() {
+        let _ = PANE_FRAME_TITLE.set(pane_frame_title);
+    }
 
     let (to_screen, screen_receiver): ChannelWithContext<ScreenInstruction> = channels::unbounded();
     let to_screen = SenderWithContext::new(to_screen);
@@ -743,6 +1087,9 @@ fn init_session(
         channels::unbounded();
     let to_background_jobs = SenderWithContext::new(to_background_jobs);
 
+    let config_file_path = Config::file_path(&opts);
+    let session_config_options = config_options.clone();
+
     // Determine and initialize the data directory
     let data_dir = opts.data_dir.unwrap_or_else(get_default_data_dir);
 
@@ -815,6 +1162,7 @@ fn init_session(
         .unwrap();
 
     let zellij_cwd = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    let watch_plugins = opts.watch_plugins;
     let plugin_thread = thread::Builder::new()
         .name("wasm".to_string())
         .spawn({
@@ -846,6 +1194,7 @@ fn init_session(
                     capabilities,
                     client_attributes,
                     default_shell,
+                    watch_plugins,
                 )
                 .fatal()
             }
@@ -886,6 +1235,12 @@ fn init_session(
         })
         .unwrap();
 
+    if let Some(config_file_path) = config_file_path.clone() {
+        to_background_jobs
+            .send(BackgroundJob::WatchConfigFile(config_file_path))
+            .unwrap();
+    }
+
     SessionMetaData {
         senders: ThreadSenders {
             to_screen: Some(to_screen),
@@ -900,6 +1255,8 @@ fn init_session(
         default_shell,
         client_attributes,
         layout,
+        config_options: session_config_options,
+        config_file_path,
         screen_thread: Some(screen_thread),
         pty_thread: Some(pty_thread),
         plugin_thread: Some(plugin_thread),
@@ -919,3 +1276,28 @@ fn get_store() -> Store {
     log::info!("Compiling plugins using Singlepass");
     Store::new(&wasmer::Universal::new(wasmer::Singlepass::default()).engine())
 }
+
+#[cfg(test)]
+mod socket_activation_test {
+
+    use super::*;
+
+    #[test]
+    fn sockets_passed_to_this_process_are_used() {
+        assert_eq!(systemd_listen_fds(Some("42"), Some("1"), 42), Some(1));
+        assert_eq!(systemd_listen_fds(Some("42"), Some("2"), 42), Some(2));
+    }
+
+    #[test]
+    fn sockets_passed_to_another_process_are_ignored() {
+        assert_eq!(systemd_listen_fds(Some("41"), Some("1"), 42), None);
+    }
+
+    #[test]
+    fn missing_or_malformed_variables_are_ignored() {
+        assert_eq!(systemd_listen_fds(None, Some("1"), 42), None);
+        assert_eq!(systemd_listen_fds(Some("42"), None, 42), None);
+        assert_eq!(systemd_listen_fds(Some("not-a-pid"), Some("1"), 42), None);
+        assert_eq!(systemd_listen_fds(Some("42"), Some("0"), 42), None);
+    }
+}
@@ -17,6 +17,7 @@ use zellij_utils::{
     async_std, channels,
     channels::TrySendError,
     data::Palette,
+    envs::PANE_ID_ENV_KEY,
     errors::prelude::*,
     input::command::{RunCommand, TerminalAction},
     interprocess,
@@ -183,6 +184,8 @@ fn handle_openpty(
             }
             command
                 .args(&cmd.args)
+                .envs(&cmd.env)
+                .env(PANE_ID_ENV_KEY, terminal_id.to_string())
                 .pre_exec(move || -> std::io::Result<()> {
                     if libc::login_tty(pid_secondary) != 0 {
                         panic!("failed to set controlling terminal");
@@ -334,8 +337,10 @@ fn spawn_terminal(
                 command,
                 args,
                 cwd,
+                env: BTreeMap::new(),
                 hold_on_close: false,
                 hold_on_start: false,
+                waiting_client: None,
             }
         },
         TerminalAction::RunCommand(command) => command,
@@ -503,6 +508,14 @@ pub trait ServerOsApi: Send + Sync {
     fn load_palette(&self) -> Palette;
     /// Returns the current working directory for a given pid
     fn get_cwd(&self, pid: Pid) -> Option<PathBuf>;
+    /// Returns the processes running inside each of the given terminals, keyed by terminal id,
+    /// given the pid of the process each terminal was spawned with (usually a shell)
+    fn get_terminal_processes(
+        &self,
+        _terminal_id_to_pid: &HashMap<u32, Pid>,
+    ) -> HashMap<u32, TerminalProcesses> {
+        HashMap::new()
+    }
     /// Writes the given buffer to a string
     fn write_to_file(&mut self, buf: String, file: Option<String>) -> Result<()>;
 
@@ -755,6 +768,83 @@ impl ServerOsApi for ServerOsInputOutput {
         None
     }
 
+    fn get_terminal_processes(
+        &self,
+        terminal_id_to_pid: &HashMap<u32, Pid>,
+    ) -> HashMap<u32, TerminalProcesses> {
+        let mut system_info = System::new();
+        system_info.refresh_processes_specifics(ProcessRefreshKind::default());
+        let mut children_of_process: HashMap<sysinfo::Pid, Vec<sysinfo::Pid>> = HashMap::new();
+        for (pid, process) in system_info.processes() {
+            if let Some(parent_pid) = process.parent() {
+                children_of_process
+                    .entry(parent_pid)
+                    .or_default()
+                    .push(*pid);
+            }
+        }
+        let terminal_id_to_fd = self.terminal_id_to_raw_fd.lock().unwrap();
+        let mut terminal_processes = HashMap::new();
+        for (terminal_id, pid) in terminal_id_to_pid {
+            let pid = pid.as_raw();
+            let mut child_pids = vec![];
+            let mut pids_to_visit = vec![pid];
+            while let Some(pid) = pids_to_visit.pop() {
+                if system_info.process(pid).is_some() {
+                    child_pids.push(pid as u32);
+                    if let Some(children) = children_of_process.get(&pid) {
+                        pids_to_visit.extend(children);
+                    }
+                }
+            }
+            if child_pids.is_empty() {
+                continue;
+            }
+            child_pids.sort_unstable();
+            // the foreground process group of the terminal is the one currently interacting with
+            // the user (eg. `vim` rather than the shell that started it), if we can't get it we
+            // fall back to the process the terminal was started with
+            let foreground_pid = terminal_id_to_fd
+                .get(terminal_id)
+                .copied()
+                .flatten()
+                .and_then(|fd| unistd::tcgetpgrp(fd).ok())
+                .map(|pgrp| pgrp.as_raw())
+                .filter(|pgrp| system_info.process(*pgrp).is_some())
+                .unwrap_or(pid);
+            let foreground_process = system_info.process(foreground_pid);
+            // something other than the process the terminal was started with (usually a shell) is
+            // in the foreground, so all of the shell's descendants are considered to be running
+            let mut running_processes: Vec<String> = vec![];
+            if foreground_pid != pid {
+                for child_pid in &child_pids {
+                    let name = system_info
+                        .process(*child_pid as i32)
+                        .filter(|_| *child_pid as i32 != pid)
+                        .map(|p| p.name().to_owned());
+                    if let Some(name) = name {
+                        if !running_processes.contains(&name) {
+                            running_processes.push(name);
+                        }
+                    }
+                }
+            }
+            terminal_processes.insert(
+                *terminal_id,
+                TerminalProcesses {
+                    child_pids,
+                    foreground_process: foreground_process.map(|p| p.name().to_owned()),
+                    cwd: foreground_process
+                        .map(|p| p.cwd())
+                        .filter(|cwd| cwd.iter().next().is_some())
+                        .map(|cwd| cwd.to_path_buf()),
+                    running_processes,
+                },
+            );
+        }
+        terminal_processes
+    }
+
     fn write_to_file(&mut self, buf: String, name: Option<String>) -> Result<()> {
         let err_context = || "failed to write to file".to_string();
 
@@ -869,6 +959,20 @@ impl Drop for ResizeCache {
     }
 }
 
+/// The processes running inside a terminal pane
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TerminalProcesses {
+    /// The process the terminal was spawned with and all of its descendants
+    pub child_pids: Vec<u32>,
+    /// The name of the process in the foreground process group of the terminal
+    pub foreground_process: Option<String>,
+    /// The current working directory of the foreground process
+    pub cwd: Option<PathBuf>,
+    /// The names of the processes started from the shell of the terminal while something other
+    /// than the shell is in the foreground (eg. a build), empty if the shell is idle
+    pub running_processes: Vec<String>,
+}
+
 /// Process id's for forked terminals
 #[derive(Debug)]
 pub struct ChildId {
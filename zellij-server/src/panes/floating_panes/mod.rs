@@ -6,7 +6,7 @@ use zellij_utils::{
 
 use crate::resize_pty;
 use crate::tab::{pane_info_for_pane, Pane};
-use floating_pane_grid::FloatingPaneGrid;
+use floating_pane_grid::{half_size_middle_geom, FloatingPaneGrid};
 
 use crate::{
     os_input_output::ServerOsApi,
@@ -14,7 +14,10 @@ use crate::{
     panes::{ActivePanes, PaneId},
     plugins::PluginInstruction,
     thread_bus::ThreadSenders,
-    ui::pane_contents_and_ui::PaneContentsAndUi,
+    ui::{
+        pane_boundaries_frame::position_is_on_pin_indication,
+        pane_contents_and_ui::PaneContentsAndUi,
+    },
     ClientId,
 };
 use std::cell::RefCell;
@@ -47,6 +50,7 @@ pub struct FloatingPanes {
     z_indices: Vec<PaneId>,
     active_panes: ActivePanes,
     show_panes: bool,
+    pinned_panes: HashSet<PaneId>, // pinned panes stay visible when floating panes are hidden
     pane_being_moved_with_mouse: Option<(PaneId, Position)>,
     senders: ThreadSenders,
 }
@@ -81,16 +85,18 @@ impl FloatingPanes {
             desired_pane_positions: HashMap::new(),
             z_indices: vec![],
             show_panes: false,
+            pinned_panes: HashSet::new(),
             active_panes: ActivePanes::new(&os_input),
             pane_being_moved_with_mouse: None,
             senders,
         }
     }
     pub fn stack(&self) -> Option<FloatingPanesStack> {
-        if self.panes_are_visible() {
+        if self.panes_are_visible() || self.has_pinned_panes() {
             let layers = self
                 .z_indices
                 .iter()
+                .filter(|pane_id| self.pane_is_visible(pane_id))
                 .map(|pane_id| self.panes.get(pane_id).unwrap().position_and_size())
                 .collect();
             Some(FloatingPanesStack { layers })
@@ -154,6 +160,9 @@ impl FloatingPanes {
             self.desired_pane_positions
                 .insert(with_pane_id, desired_pane_position);
         }
+        if self.pinned_panes.remove(&pane_id) {
+            self.pinned_panes.insert(with_pane_id);
+        }
 
         // move clients from the previously active pane to the new pane we just inserted
         self.move_clients_between_panes(pane_id, with_pane_id);
@@ -162,6 +171,7 @@ impl FloatingPanes {
     pub fn remove_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
         self.z_indices.retain(|p_id| *p_id != pane_id);
         self.desired_pane_positions.remove(&pane_id);
+        self.pinned_panes.remove(&pane_id);
         self.panes.remove(&pane_id)
     }
     pub fn hold_pane(
@@ -219,6 +229,54 @@ impl FloatingPanes {
             self.active_panes.unfocus_all_panes(&mut self.panes);
         }
     }
+    pub fn has_pinned_panes(&self) -> bool {
+        !self.pinned_panes.is_empty()
+    }
+    pub fn pane_is_pinned(&self, pane_id: &PaneId) -> bool {
+        self.pinned_panes.contains(pane_id)
+    }
+    fn pane_is_visible(&self, pane_id: &PaneId) -> bool {
+        self.show_panes || self.pane_is_pinned(pane_id)
+    }
+    pub fn toggle_pane_pinned(&mut self, pane_id: PaneId) {
+        if self.panes.contains_key(&pane_id) && !self.pinned_panes.remove(&pane_id) {
+            self.pinned_panes.insert(pane_id);
+        }
+        self.set_force_render();
+    }
+    pub fn toggle_active_pane_pinned(&mut self, client_id: ClientId) {
+        if let Some(active_pane_id) = self.active_panes.get(&client_id).copied() {
+            self.toggle_pane_pinned(active_pane_id);
+        }
+    }
+    /// The topmost pinned pane at `point`, to be used when floating panes are hidden and only
+    /// pinned panes are displayed
+    pub fn get_pinned_pane_id_at(&self, point: &Position) -> Option<PaneId> {
+        self.z_indices
+            .iter()
+            .rev()
+            .filter(|pane_id| self.pane_is_pinned(pane_id))
+            .find(|pane_id| {
+                self.panes
+                    .get(*pane_id)
+                    .map(|pane| pane.contains(point))
+                    .unwrap_or(false)
+            })
+            .copied()
+    }
+    /// The visible pane whose pin indication (in its frame) is at `point`, if any
+    pub fn get_pane_id_with_pin_indication_at(&self, point: &Position) -> Option<PaneId> {
+        let pane_id = if self.show_panes {
+            self.get_pane_id_at(point, false).ok().flatten()
+        } else {
+            self.get_pinned_pane_id_at(point)
+        }?;
+        self.panes
+            .get(&pane_id)
+            .filter(|pane| !pane.borderless())
+            .filter(|pane| position_is_on_pin_indication(&pane.current_geom(), point))
+            .map(|_| pane_id)
+    }
     pub fn active_panes_contain(&self, client_id: &ClientId) -> bool {
         self.active_panes.contains_key(client_id)
     }
@@ -236,6 +294,17 @@ impl FloatingPanes {
         );
         floating_pane_grid.find_room_for_new_pane()
     }
+    /// Half the size of the viewport and in its middle, whether or not other panes are there
+    pub fn centered_pane_geom(&self) -> PaneGeom {
+        half_size_middle_geom(&self.viewport.borrow(), 0)
+    }
+    pub fn set_pane_geom(&mut self, pane_id: PaneId, geom: PaneGeom) {
+        if let Some(pane) = self.panes.get_mut(&pane_id) {
+            pane.set_geom(geom);
+            self.desired_pane_positions.insert(pane_id, geom);
+            self.set_force_render();
+        }
+    }
     pub fn position_floating_pane_layout(
         &mut self,
         floating_pane_layout: &FloatingPaneLayout,
@@ -295,6 +364,13 @@ impl FloatingPanes {
             pane.render_full_viewport();
         }
     }
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        for pane in self.panes.values_mut() {
+            pane.update_style(style);
+        }
+        self.set_force_render();
+    }
     pub fn set_pane_frames(&mut self, _os_api: &mut Box<dyn ServerOsApi>) -> Result<()> {
         let err_context =
             |pane_id: &PaneId| format!("failed to activate frame on pane {pane_id:?}");
@@ -312,11 +388,22 @@ impl FloatingPanes {
         }
         Ok(())
     }
-    pub fn render(&mut self, output: &mut Output) -> Result<()> {
+    pub fn render(
+        &mut self,
+        output: &mut Output,
+        tab_name: &str,
+        terminal_title: &mut Option<String>,
+    ) -> Result<()> {
         let err_context = || "failed to render output";
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
-        let mut floating_panes: Vec<_> = self.panes.iter_mut().collect();
+        let show_panes = self.show_panes;
+        let pinned_panes = &self.pinned_panes;
+        let mut floating_panes: Vec<_> = self
+            .panes
+            .iter_mut()
+            .filter(|(pane_id, _)| show_panes || pinned_panes.contains(*pane_id))
+            .collect();
         floating_panes.sort_by(|(a_id, _a_pane), (b_id, _b_pane)| {
             self.z_indices
                 .iter()
@@ -348,6 +435,8 @@ impl FloatingPanes {
                 false,
                 false,
                 true,
+                pinned_panes.contains(*kind),
+                0,
             );
             for client_id in &connected_clients {
                 let client_mode = self
@@ -359,6 +448,16 @@ impl FloatingPanes {
                 pane_contents_and_ui
                     .render_pane_frame(*client_id, client_mode, self.session_is_mirrored)
                     .with_context(err_context)?;
+                if show_panes {
+                    // pinned panes are also rendered when the floating panes are hidden, but they
+                    // are not focused then
+                    pane_contents_and_ui.render_terminal_title_if_needed(
+                        *client_id,
+                        client_mode,
+                        tab_name,
+                        terminal_title,
+                    );
+                }
                 if let PaneId::Plugin(..) = kind {
                     pane_contents_and_ui
                         .render_pane_contents_for_client(*client_id)
@@ -3,6 +3,7 @@ use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::Rc;
+use std::time::Instant;
 use unicode_width::UnicodeWidthChar;
 use zellij_utils::data::Style;
 use zellij_utils::errors::prelude::*;
@@ -16,15 +17,23 @@ use std::{
 };
 
 use zellij_utils::{
-    consts::{DEFAULT_SCROLL_BUFFER_SIZE, SCROLL_BUFFER_SIZE},
-    data::{Palette, PaletteColor},
+    consts::{
+        DEFAULT_SCROLLBACK_SPILLOVER_LIMIT, DEFAULT_SCROLL_BUFFER_SIZE, SCROLLBACK_SPILLOVER,
+        SCROLLBACK_SPILLOVER_LIMIT, SCROLL_BUFFER_SIZE, WORD_CHARACTERS,
+    },
+    data::{Direction, Palette, PaletteColor},
+    input::kitty_keyboard,
     pane_size::SizeInPixels,
-    position::Position,
-    vte,
+    position::{Column, Line, Position},
+    serde, vte,
 };
 
+use serde::{Deserialize, Serialize};
+
 const TABSTOP_WIDTH: usize = 8; // TODO: is this always right?
 pub const MAX_TITLE_STACK_SIZE: usize = 1000;
+const MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE: usize = 16;
+const MULTI_CLICK_INTERVAL_MS: u128 = 400;
 
 use vte::{Params, Perform};
 use zellij_utils::{consts::VERSION, shared::version_number};
@@ -32,11 +41,12 @@ use zellij_utils::{consts::VERSION, shared::version_number};
 use crate::output::{CharacterChunk, OutputBuffer, SixelImageChunk};
 use crate::panes::alacritty_functions::{parse_number, xparse_color};
 use crate::panes::link_handler::LinkHandler;
+use crate::panes::scrollback_spill::ScrollbackSpill;
 use crate::panes::search::SearchResult;
 use crate::panes::selection::Selection;
 use crate::panes::terminal_character::{
-    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, StandardCharset,
-    TerminalCharacter, EMPTY_TERMINAL_CHARACTER,
+    AnsiCode, CharacterStyles, CharsetIndex, Cursor, CursorShape, LinkAnchor, StandardCharset,
+    TerminalCharacter, EMPTY_TERMINAL_CHARACTER, RESET_STYLES,
 };
 
 fn get_top_non_canonical_rows(rows: &mut Vec<Row>) -> Vec<Row> {
@@ -119,6 +129,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     lines_above: &mut VecDeque<Row>,
     viewport: &mut Vec<Row>,
     sixel_grid: &mut SixelGrid,
+    scrollback_spill: Option<&RefCell<ScrollbackSpill>>,
     count: usize,
     max_viewport_width: usize,
 ) -> usize {
@@ -150,7 +161,7 @@ fn transfer_rows_from_lines_above_to_viewport(
     }
     if !next_lines.is_empty() {
         let excess_row = Row::from_rows(next_lines);
-        bounded_push(lines_above, sixel_grid, excess_row);
+        bounded_push(lines_above, sixel_grid, scrollback_spill, excess_row);
     }
     match usize::try_from(lines_added_to_viewport) {
         Ok(n) => n,
@@ -162,6 +173,7 @@ fn transfer_rows_from_viewport_to_lines_above(
     viewport: &mut Vec<Row>,
     lines_above: &mut VecDeque<Row>,
     sixel_grid: &mut SixelGrid,
+    scrollback_spill: Option<&RefCell<ScrollbackSpill>>,
     count: usize,
     max_viewport_width: usize,
 ) -> isize {
@@ -184,7 +196,12 @@ fn transfer_rows_from_viewport_to_lines_above(
                 break; // no more rows
             }
         }
-        let dropped_line_width = bounded_push(lines_above, sixel_grid, next_lines.remove(0));
+        let dropped_line_width = bounded_push(
+            lines_above,
+            sixel_grid,
+            scrollback_spill,
+            next_lines.remove(0),
+        );
         if let Some(width) = dropped_line_width {
             transferred_rows_count -=
                 calculate_row_display_height(width, max_viewport_width) as isize;
@@ -239,13 +256,21 @@ fn transfer_rows_from_lines_below_to_viewport(
     }
 }
 
-fn bounded_push(vec: &mut VecDeque<Row>, sixel_grid: &mut SixelGrid, value: Row) -> Option<usize> {
+fn bounded_push(
+    vec: &mut VecDeque<Row>,
+    sixel_grid: &mut SixelGrid,
+    scrollback_spill: Option<&RefCell<ScrollbackSpill>>,
+    value: Row,
+) -> Option<usize> {
     let mut dropped_line_width = None;
     if vec.len() >= *SCROLL_BUFFER_SIZE.get().unwrap() {
         let line = vec.pop_front();
         if let Some(line) = line {
             sixel_grid.offset_grid_top();
             dropped_line_width = Some(line.width());
+            if let Some(scrollback_spill) = scrollback_spill {
+                let _ = scrollback_spill.borrow_mut().push(line).to_log();
+            }
         }
     }
     vec.push_back(value);
@@ -300,6 +325,26 @@ macro_rules! dump_screen {
     }};
 }
 
+fn styles_for_dump(styles: &CharacterStyles) -> CharacterStyles {
+    // styles that are not set are explicitly reset, so that a character does not inherit the
+    // styles of the one before it
+    let reset = Some(AnsiCode::Reset);
+    CharacterStyles {
+        foreground: styles.foreground.or(reset),
+        background: styles.background.or(reset),
+        strike: styles.strike.or(reset),
+        hidden: styles.hidden.or(reset),
+        reverse: styles.reverse.or(reset),
+        slow_blink: styles.slow_blink.or(reset),
+        fast_blink: styles.fast_blink.or(reset),
+        underline: styles.underline.or(reset),
+        bold: styles.bold.or(reset),
+        dim: styles.dim.or(reset),
+        italic: styles.italic.or(reset),
+        link_anchor: RESET_STYLES.link_anchor,
+    }
+}
+
 fn utf8_mouse_coordinates(column: usize, line: isize) -> Vec<u8> {
     let mut coordinates = vec![];
     let mouse_pos_encode = |pos: usize| -> Vec<u8> {
@@ -359,19 +404,44 @@ pub struct Grid {
     pub height: usize,
     pub pending_messages_to_pty: Vec<Vec<u8>>,
     pub selection: Selection,
+    selection_granularity: SelectionGranularity,
+    selection_anchor: Option<(Position, Position)>, // the word or line a multi-click selection started from
+    last_click: Option<(Position, Instant, usize)>, // usize is the number of consecutive clicks
+    copy_mode: Option<CopyMode>,
+    pub word_characters: String,
     pub title: Option<String>,
     pub is_scrolled: bool,
     pub link_handler: Rc<RefCell<LinkHandler>>,
+    hovered_link: Option<u16>,
     pub ring_bell: bool,
+    pub pending_bell_notification: bool, // ring_bell is reset once rendered, this once reported
     scrollback_buffer_lines: usize,
+    scrollback_spill: Option<Rc<RefCell<ScrollbackSpill>>>,
     pub mouse_mode: MouseMode,
     pub mouse_tracking: MouseTracking,
     pub focus_event_tracking: bool,
+    kitty_keyboard_flags: Vec<u8>, // the stack of progressive enhancement flags, latest last
     pub search_results: SearchResult,
     pub pending_clipboard_update: Option<String>,
+    pub navigation_is_delegated: bool, // the application claimed the directional navigation keys
+    pub pending_navigation_bounce: Option<Direction>, // a move the application handed back to us
     debug: bool,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SelectionGranularity {
+    Character,
+    Word,
+    Line,
+    Block, // a rectangle of columns
+}
+
+#[derive(Clone, Copy, Debug)]
+struct CopyMode {
+    cursor: Position, // relative to the viewport, like mouse positions
+    selection: Option<(SelectionGranularity, Position)>, // the position the selection started from
+}
+
 #[derive(Clone, Debug)]
 pub enum MouseMode {
     NoEncoding,
@@ -465,6 +535,19 @@ impl Grid {
         // something changed since this comment was written), we get an Error which we ignore
         // I don't know why this needs to be a OneCell, but whatevs
         let _ = SCROLL_BUFFER_SIZE.set(DEFAULT_SCROLL_BUFFER_SIZE);
+        let scrollback_spill = if SCROLLBACK_SPILLOVER.get().copied().unwrap_or(false) {
+            ScrollbackSpill::new(
+                SCROLLBACK_SPILLOVER_LIMIT
+                    .get()
+                    .copied()
+                    .unwrap_or(DEFAULT_SCROLLBACK_SPILLOVER_LIMIT),
+            )
+            .to_log()
+            .ok()
+            .map(|scrollback_spill| Rc::new(RefCell::new(scrollback_spill)))
+        } else {
+            None
+        };
         Grid {
             lines_above: VecDeque::new(),
             viewport: vec![Row::new().canonical()],
@@ -493,20 +576,31 @@ impl Grid {
             terminal_emulator_color_codes,
             output_buffer: Default::default(),
             selection: Default::default(),
+            selection_granularity: SelectionGranularity::Character,
+            selection_anchor: None,
+            last_click: None,
+            copy_mode: None,
+            word_characters: WORD_CHARACTERS.get().cloned().unwrap_or_default(),
             title_stack: vec![],
             title: None,
             changed_colors: None,
             is_scrolled: false,
             link_handler,
+            hovered_link: None,
             ring_bell: false,
+            pending_bell_notification: false,
             scrollback_buffer_lines: 0,
+            scrollback_spill,
             mouse_mode: MouseMode::default(),
             mouse_tracking: MouseTracking::default(),
             focus_event_tracking: false,
+            kitty_keyboard_flags: vec![],
             character_cell_size,
             search_results: Default::default(),
             sixel_grid,
             pending_clipboard_update: None,
+            navigation_is_delegated: false,
+            pending_navigation_bounce: None,
             debug,
         }
     }
@@ -558,9 +652,38 @@ impl Grid {
         // (position, length)
         (
             self.lines_below.len(),
-            (self.scrollback_buffer_lines + self.lines_below.len()),
+            (self.scrollback_buffer_lines + self.lines_below.len() + self.spilled_line_count()),
         )
     }
+    pub(crate) fn spilled_line_count(&self) -> usize {
+        self.scrollback_spill
+            .as_ref()
+            .map(|scrollback_spill| scrollback_spill.borrow().len())
+            .unwrap_or(0)
+    }
+    fn page_in_spilled_lines(&mut self) {
+        // we page in a viewport's worth of lines at a time so as not to hit the disk for every
+        // line scrolled
+        if let Some(scrollback_spill) = &self.scrollback_spill {
+            let mut scrollback_spill = scrollback_spill.borrow_mut();
+            for _ in 0..self.height.max(1) {
+                match scrollback_spill.pop().to_log() {
+                    Ok(Some(row)) => {
+                        self.scrollback_buffer_lines +=
+                            calculate_row_display_height(row.width(), self.width);
+                        self.sixel_grid.offset_grid_bottom();
+                        self.lines_above.push_front(row);
+                    },
+                    _ => break,
+                }
+            }
+        }
+    }
+    fn clear_scrollback_spill(&mut self) {
+        if let Some(scrollback_spill) = &self.scrollback_spill {
+            let _ = scrollback_spill.borrow_mut().clear().to_log();
+        }
+    }
 
     fn recalculate_scrollback_buffer_count(&self) -> usize {
         let mut scrollback_buffer_count = 0;
@@ -667,6 +790,9 @@ impl Grid {
 
     pub fn scroll_up_one_line(&mut self) -> bool {
         let mut found_something = false;
+        if self.lines_above.is_empty() {
+            self.page_in_spilled_lines();
+        }
         if !self.lines_above.is_empty() && self.viewport.len() == self.height {
             self.is_scrolled = true;
             let line_to_push_down = self.viewport.pop().unwrap();
@@ -676,6 +802,7 @@ impl Grid {
                 &mut self.lines_above,
                 &mut self.viewport,
                 &mut self.sixel_grid,
+                self.scrollback_spill.as_deref(),
                 1,
                 self.width,
             );
@@ -708,8 +835,12 @@ impl Grid {
                 last_line_above
             };
 
-            let dropped_line_width =
-                bounded_push(&mut self.lines_above, &mut self.sixel_grid, line_to_push_up);
+            let dropped_line_width = bounded_push(
+                &mut self.lines_above,
+                &mut self.sixel_grid,
+                self.scrollback_spill.as_deref(),
+                line_to_push_up,
+            );
             if let Some(width) = dropped_line_width {
                 let dropped_line_height = calculate_row_display_height(width, self.width);
 
@@ -823,7 +954,9 @@ impl Grid {
             for mut canonical_line in viewport_canonical_lines {
                 let mut canonical_line_parts: Vec<Row> = vec![];
                 if canonical_line.columns.is_empty() {
-                    canonical_line_parts.push(Row::new().canonical());
+                    let mut row = Row::new().canonical();
+                    row.prompt_mark = canonical_line.prompt_mark;
+                    canonical_line_parts.push(row);
                 }
                 while !canonical_line.columns.is_empty() {
                     let next_wrap = canonical_line.drain_until(new_columns);
@@ -837,7 +970,9 @@ impl Grid {
                     // was canonical (it might not have been for example if it's the first row in
                     // the viewport, and the actual canonical row is above it in the scrollback)
                     let row = if canonical_line_parts.is_empty() && canonical_line.is_canonical {
-                        row.canonical()
+                        let mut row = row.canonical();
+                        row.prompt_mark = canonical_line.prompt_mark;
+                        row
                     } else {
                         row
                     };
@@ -877,6 +1012,7 @@ impl Grid {
                         &mut self.lines_above,
                         &mut self.viewport,
                         &mut self.sixel_grid,
+                        self.scrollback_spill.as_deref(),
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -904,6 +1040,7 @@ impl Grid {
                         &mut self.viewport,
                         &mut self.lines_above,
                         &mut self.sixel_grid,
+                        self.scrollback_spill.as_deref(),
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -934,6 +1071,7 @@ impl Grid {
                         &mut self.lines_above,
                         &mut self.viewport,
                         &mut self.sixel_grid,
+                        self.scrollback_spill.as_deref(),
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -962,6 +1100,7 @@ impl Grid {
                         &mut self.viewport,
                         &mut self.lines_above,
                         &mut self.sixel_grid,
+                        self.scrollback_spill.as_deref(),
                         row_count_to_transfer,
                         new_columns,
                     );
@@ -1045,6 +1184,17 @@ impl Grid {
         let (mut character_chunks, sixel_image_chunks) = self.read_changes(content_x, content_y);
         for character_chunk in character_chunks.iter_mut() {
             character_chunk.add_changed_colors(self.changed_colors);
+            if let Some(hovered_link) = self.hovered_link {
+                for terminal_character in character_chunk.terminal_characters.iter_mut() {
+                    if terminal_character.styles.link_anchor
+                        == Some(LinkAnchor::Start(hovered_link))
+                    {
+                        terminal_character.styles.underline = Some(AnsiCode::On);
+                    } else if terminal_character.styles.underline.is_none() {
+                        terminal_character.styles.underline = Some(AnsiCode::Reset);
+                    }
+                }
+            }
             if self
                 .selection
                 .contains_row(character_chunk.y.saturating_sub(content_y))
@@ -1100,7 +1250,9 @@ impl Grid {
         )));
     }
     pub fn cursor_coordinates(&self) -> Option<(usize, usize)> {
-        if self.cursor_is_hidden {
+        if let Some(copy_mode) = self.copy_mode {
+            Some((copy_mode.cursor.column(), copy_mode.cursor.line() as usize))
+        } else if self.cursor_is_hidden {
             None
         } else {
             Some((self.cursor.x, self.cursor.y))
@@ -1117,17 +1269,86 @@ impl Grid {
     }
     /// Dumps all lines above terminal vieport and the viewport itself to a string
     pub fn dump_screen(&mut self, full: bool) -> String {
-        let viewport: String = dump_screen!(self.viewport);
         if !full {
-            return viewport;
+            return dump_screen!(self.viewport);
         }
-        let mut scrollback: String = dump_screen!(self.lines_above);
+        let spilled_lines = self.read_spilled_lines();
+        let scrollback_lines: Vec<&Row> = spilled_lines
+            .iter()
+            .chain(self.lines_above.iter())
+            .collect();
+        // if the pane is scrolled up, the lines below the viewport are part of the scrollback too
+        // (and the top one might be the rest of a line wrapped from the viewport)
+        let viewport_and_lines_below: Vec<&Row> = self
+            .viewport
+            .iter()
+            .chain(self.lines_below.iter())
+            .collect();
+        let mut scrollback: String = dump_screen!(scrollback_lines);
         if !scrollback.is_empty() {
             scrollback.push('\n');
         }
-        scrollback.push_str(&viewport);
+        scrollback.push_str(&dump_screen!(viewport_and_lines_below));
         scrollback
     }
+    pub fn dump_screen_with_ansi_styling(&mut self, full: bool) -> String {
+        let spilled_lines = if full {
+            self.read_spilled_lines()
+        } else {
+            vec![]
+        };
+        let rows: Vec<&Row> = if full {
+            spilled_lines
+                .iter()
+                .chain(self.lines_above.iter())
+                .chain(self.viewport.iter())
+                .chain(self.lines_below.iter())
+                .collect()
+        } else {
+            self.viewport.iter().collect()
+        };
+        let mut canonical_lines: Vec<Vec<TerminalCharacter>> = vec![];
+        for row in rows {
+            match canonical_lines.last_mut() {
+                Some(canonical_line) if !row.is_canonical => {
+                    canonical_line.extend(row.columns.iter().copied())
+                },
+                _ => canonical_lines.push(row.columns.iter().copied().collect()),
+            }
+        }
+        let mut dump = String::new();
+        let mut current_styles = RESET_STYLES;
+        for (i, canonical_line) in canonical_lines.iter().enumerate() {
+            if i > 0 {
+                dump.push('\n');
+            }
+            // trailing unstyled spaces are padding rather than content
+            let content_length = canonical_line
+                .iter()
+                .rposition(|c| c.character != ' ' || styles_for_dump(&c.styles) != RESET_STYLES)
+                .map(|last_index| last_index + 1)
+                .unwrap_or(0);
+            for character in &canonical_line[..content_length] {
+                if let Some(styles_diff) =
+                    current_styles.update_and_return_diff(&styles_for_dump(&character.styles), None)
+                {
+                    dump.push_str(&styles_diff.to_string());
+                }
+                dump.push(character.character);
+            }
+            if current_styles != RESET_STYLES {
+                dump.push_str(&RESET_STYLES.to_string());
+                current_styles = RESET_STYLES;
+            }
+        }
+        dump
+    }
+    fn read_spilled_lines(&self) -> Vec<Row> {
+        self.scrollback_spill
+            .as_ref()
+            .and_then(|scrollback_spill| scrollback_spill.borrow_mut().read_all().to_log().ok())
+            .unwrap_or_default()
+    }
     pub fn move_viewport_up(&mut self, count: usize) {
         for _ in 0..count {
             self.scroll_up_one_line();
@@ -1140,8 +1361,102 @@ impl Grid {
         }
         self.output_buffer.update_all_lines();
     }
+    pub fn scroll_to_previous_prompt(&mut self) {
+        let has_prompt_above = self
+            .lines_above
+            .iter()
+            .any(|row| row.prompt_mark == Some(PromptMark::Prompt));
+        if !has_prompt_above && self.spilled_line_count() == 0 {
+            return;
+        }
+        loop {
+            if self.lines_above.is_empty() {
+                self.page_in_spilled_lines();
+            }
+            if self.lines_above.is_empty() || self.viewport.len() != self.height {
+                break;
+            }
+            self.scroll_up_one_line();
+            if self.prompt_is_at_top_of_viewport() {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+    }
+    pub fn scroll_to_next_prompt(&mut self) {
+        // when there is no prompt below the viewport, this scrolls to the bottom (where the
+        // current prompt is)
+        while !self.lines_below.is_empty() && self.viewport.len() == self.height {
+            self.scroll_down_one_line();
+            if self.prompt_is_at_top_of_viewport() {
+                break;
+            }
+        }
+        self.output_buffer.update_all_lines();
+    }
+    fn prompt_is_at_top_of_viewport(&self) -> bool {
+        self.viewport
+            .first()
+            .map(|row| row.is_canonical && row.prompt_mark == Some(PromptMark::Prompt))
+            .unwrap_or(false)
+    }
+    fn mark_cursor_line(&mut self, prompt_mark: PromptMark) {
+        self.pad_lines_until(self.cursor.y, EMPTY_TERMINAL_CHARACTER);
+        let canonical_row_index = self.viewport[..=self.cursor.y]
+            .iter()
+            .rposition(|row| row.is_canonical);
+        let canonical_row = match canonical_row_index {
+            Some(index) => self.viewport.get_mut(index),
+            None => self.lines_above.back_mut(),
+        };
+        if let Some(canonical_row) = canonical_row {
+            // a command might start outputting on the same line as its prompt, in which case we
+            // keep the prompt so that it can still be scrolled to
+            if prompt_mark == PromptMark::Prompt || canonical_row.prompt_mark.is_none() {
+                canonical_row.prompt_mark = Some(prompt_mark);
+            }
+        }
+    }
+    /// The output of the last command run in a shell with shell integration (OSC 133), from the
+    /// line it started on until the next prompt or the end of the scrollback
+    pub fn last_command_output(&self) -> Option<String> {
+        let mut canonical_lines: Vec<(Option<PromptMark>, String)> = vec![];
+        let rows = self
+            .lines_above
+            .iter()
+            .chain(self.viewport.iter())
+            .chain(self.lines_below.iter());
+        for row in rows {
+            let text: String = row.columns.iter().map(|c| c.character).collect();
+            if !row.is_canonical {
+                if let Some((_, line)) = canonical_lines.last_mut() {
+                    line.push_str(&text);
+                    continue;
+                }
+            }
+            canonical_lines.push((row.prompt_mark, text));
+        }
+        let output_start = canonical_lines
+            .iter()
+            .rposition(|(prompt_mark, _)| *prompt_mark == Some(PromptMark::CommandOutput))?;
+        let mut output: Vec<&str> = canonical_lines[output_start..]
+            .iter()
+            .enumerate()
+            .take_while(|(i, (prompt_mark, _))| *i == 0 || *prompt_mark != Some(PromptMark::Prompt))
+            .map(|(_, (_, line))| line.trim_end())
+            .collect();
+        while output.last().map(|line| line.is_empty()).unwrap_or(false) {
+            output.pop();
+        }
+        if output.is_empty() {
+            None
+        } else {
+            Some(output.join("\n"))
+        }
+    }
     pub fn reset_viewport(&mut self) {
-        let max_lines_to_scroll = *SCROLL_BUFFER_SIZE.get().unwrap() * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
+        let max_lines_to_scroll =
+            (*SCROLL_BUFFER_SIZE.get().unwrap() + self.spilled_line_count()) * 2; // while not very elegant, this can prevent minor bugs from becoming showstoppers by sticking the whole app display in an endless loop
         let mut lines_scrolled = 0;
         let should_clear_output_buffer = self.is_scrolled;
         while self.is_scrolled && lines_scrolled < max_lines_to_scroll {
@@ -1421,6 +1736,7 @@ impl Grid {
     }
     fn clear_lines_above(&mut self) {
         self.lines_above.clear();
+        self.clear_scrollback_spill();
         self.scrollback_buffer_lines = self.recalculate_scrollback_buffer_count();
     }
 
@@ -1654,6 +1970,7 @@ impl Grid {
         self.lines_below = vec![];
         self.viewport = vec![Row::new().canonical()];
         self.alternate_screen_state = None;
+        self.clear_scrollback_spill();
         self.cursor_key_mode = false;
         self.scroll_region = None;
         self.clear_viewport_before_rendering = true;
@@ -1672,7 +1989,9 @@ impl Grid {
         self.mouse_mode = MouseMode::NoEncoding;
         self.mouse_tracking = MouseTracking::Off;
         self.focus_event_tracking = false;
+        self.kitty_keyboard_flags.clear();
         self.cursor_is_hidden = false;
+        self.navigation_is_delegated = false;
         if let Some(images_to_reap) = self.sixel_grid.clear() {
             self.sixel_grid.reap_images(images_to_reap);
         }
@@ -1682,30 +2001,594 @@ impl Grid {
     }
     pub fn start_selection(&mut self, start: &Position) {
         let old_selection = self.selection;
+        self.selection_granularity = match self.register_click(start) {
+            2 => SelectionGranularity::Word,
+            3 => SelectionGranularity::Line,
+            _ => SelectionGranularity::Character,
+        };
+        if self.selection_granularity == SelectionGranularity::Character {
+            self.selection_anchor = None;
+            self.selection.start(*start);
+        } else {
+            let (anchor_start, anchor_end) = self.selection_bounds_at(start);
+            self.selection_anchor = Some((anchor_start, anchor_end));
+            self.selection.start(anchor_start);
+            self.selection.to(anchor_end);
+        }
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+    }
+    pub fn start_block_selection(&mut self, start: &Position) {
+        let old_selection = self.selection;
+        self.last_click = None;
+        self.selection_granularity = SelectionGranularity::Block;
+        self.selection_anchor = Some((*start, *start));
         self.selection.start(*start);
+        self.selection.set_block(true);
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+        self.mark_for_rerender();
+    }
+    pub fn extend_selection(&mut self, to: &Position) {
+        // grows the existing selection towards `to` (by words or lines if that's how it was
+        // made), without a selection this is a normal click
+        if self.selection.is_empty() {
+            return self.start_selection(to);
+        }
+        let old_selection = self.selection;
+        if self.selection_granularity == SelectionGranularity::Block {
+            self.selection_granularity = SelectionGranularity::Character;
+        }
+        let sorted = self.selection.sorted();
+        let anchor = (sorted.start, sorted.end);
+        let (start, end) = self.selection_from_anchor(anchor, to);
+        self.selection_anchor = Some(anchor);
+        self.selection.start(start);
+        self.selection.to(end);
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
     pub fn update_selection(&mut self, to: &Position) {
         let old_selection = self.selection;
-        self.selection.to(*to);
+        match self.selection_anchor {
+            Some(anchor) => {
+                let (start, end) = self.selection_from_anchor(anchor, to);
+                self.selection.start = start;
+                self.selection.to(end);
+            },
+            None => self.selection.to(*to),
+        }
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
 
     pub fn end_selection(&mut self, end: &Position) {
         let old_selection = self.selection;
-        self.selection.end(*end);
+        match self.selection_anchor.take() {
+            Some(anchor) => {
+                let (start, end) = self.selection_from_anchor(anchor, end);
+                self.selection.start = start;
+                self.selection.end(end);
+            },
+            None => self.selection.end(*end),
+        }
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
 
     pub fn reset_selection(&mut self) {
         let old_selection = self.selection;
+        self.selection_anchor = None;
         self.selection.reset();
         self.update_selected_lines(&old_selection, &self.selection.clone());
         self.mark_for_rerender();
     }
+    fn register_click(&mut self, position: &Position) -> usize {
+        // returns the number of consecutive clicks on this position, cycling through
+        // single (character), double (word) and triple (line) clicks
+        let now = Instant::now();
+        let click_count = match self.last_click {
+            Some((last_position, last_click_at, click_count))
+                if last_position == *position
+                    && now.duration_since(last_click_at).as_millis() <= MULTI_CLICK_INTERVAL_MS =>
+            {
+                click_count % 3 + 1
+            },
+            _ => 1,
+        };
+        self.last_click = Some((*position, now, click_count));
+        click_count
+    }
+    fn selection_from_anchor(
+        &self,
+        (anchor_start, anchor_end): (Position, Position),
+        to: &Position,
+    ) -> (Position, Position) {
+        if self.selection_granularity == SelectionGranularity::Block {
+            // the rectangle between the anchor and `to`
+            return (
+                Position {
+                    line: Line(anchor_start.line().min(to.line())),
+                    column: Column(anchor_start.column().min(to.column())),
+                },
+                Position {
+                    line: Line(anchor_start.line().max(to.line())),
+                    column: Column(anchor_start.column().max(to.column())),
+                },
+            );
+        }
+        // extend the selection word-by-word or line-by-line, always keeping the anchor selected
+        let (to_start, to_end) = self.selection_bounds_at(to);
+        if to_start < anchor_start {
+            (anchor_end, to_start)
+        } else if to_end > anchor_end {
+            (anchor_start, to_end)
+        } else {
+            (anchor_start, anchor_end)
+        }
+    }
+    fn selection_bounds_at(&self, position: &Position) -> (Position, Position) {
+        match self.selection_granularity {
+            SelectionGranularity::Line => self.line_bounds_at(position),
+            SelectionGranularity::Word => self.word_bounds_at(position),
+            SelectionGranularity::Character | SelectionGranularity::Block => (*position, *position),
+        }
+    }
+    fn line_bounds_at(&self, position: &Position) -> (Position, Position) {
+        // wrapped lines are selected as a whole
+        let mut first_line = position.line();
+        while first_line > -(self.lines_above.len() as isize)
+            && self
+                .row_at(first_line)
+                .map(|r| !r.is_canonical)
+                .unwrap_or(false)
+        {
+            first_line -= 1;
+        }
+        let mut last_line = position.line();
+        while self
+            .row_at(last_line + 1)
+            .map(|r| !r.is_canonical)
+            .unwrap_or(false)
+        {
+            last_line += 1;
+        }
+        (
+            Position {
+                line: Line(first_line),
+                column: Column(0),
+            },
+            Position {
+                line: Line(last_line),
+                column: Column(self.width),
+            },
+        )
+    }
+    fn word_bounds_at(&self, position: &Position) -> (Position, Position) {
+        let line = position.line;
+        let single_character = (
+            *position,
+            Position {
+                line,
+                column: Column(position.column() + 1),
+            },
+        );
+        let row = match self.row_at(line.0) {
+            Some(row) => row,
+            None => return single_character,
+        };
+        let mut characters = vec![]; // (terminal column, width, is part of a word)
+        let mut terminal_col = 0;
+        for terminal_character in &row.columns {
+            characters.push((
+                terminal_col,
+                terminal_character.width,
+                self.is_word_character(terminal_character.character),
+            ));
+            terminal_col += terminal_character.width;
+        }
+        let clicked = match characters
+            .iter()
+            .position(|(col, width, _)| (*col..col + width.max(&1)).contains(&position.column()))
+        {
+            Some(clicked) => clicked,
+            None => return single_character,
+        };
+        let (mut first, mut last) = (clicked, clicked);
+        if characters[clicked].2 {
+            while first > 0 && characters[first - 1].2 {
+                first -= 1;
+            }
+            while last + 1 < characters.len() && characters[last + 1].2 {
+                last += 1;
+            }
+        }
+        (
+            Position {
+                line,
+                column: Column(characters[first].0),
+            },
+            Position {
+                line,
+                column: Column(characters[last].0 + characters[last].1),
+            },
+        )
+    }
+    /// The uri of the OSC 8 hyperlink under the given position, if any
+    pub fn link_at(&self, position: &Position) -> Option<String> {
+        self.link_index_at(position)
+            .and_then(|link_index| self.link_handler.borrow().uri(link_index))
+    }
+    /// Underlines the hyperlink under the given position (if any) when rendering, returning
+    /// whether the hovered link changed
+    pub fn set_hovered_link(&mut self, position: Option<&Position>) -> bool {
+        let hovered_link = position.and_then(|position| self.link_index_at(position));
+        if hovered_link == self.hovered_link {
+            return false;
+        }
+        self.hovered_link = hovered_link;
+        self.render_full_viewport();
+        true
+    }
+    /// The OSC 8 hyperlinks in the viewport along with anything that looks like a url or a path,
+    /// each with the position it starts at
+    pub fn links_in_viewport(&self) -> Vec<(Position, String)> {
+        let link_pattern =
+            Regex::new(r#"(?:https?|ftp|file)://[^\s<>"'`]+|[\w.~-]*/[\w.~/-]+"#).unwrap();
+        let mut links = vec![];
+        for (line, row) in self.viewport.iter().enumerate() {
+            // OSC 8 links are blanked out of the row's text so they're not detected twice
+            let mut text = String::new();
+            let mut text_columns = vec![]; // the terminal column of each byte in text
+            let mut previous_link_index = None;
+            let mut terminal_col = 0;
+            for terminal_character in &row.columns {
+                let link_index = match terminal_character.styles.link_anchor {
+                    Some(LinkAnchor::Start(link_index)) => Some(link_index),
+                    _ => None,
+                };
+                if link_index.is_some() && link_index != previous_link_index {
+                    if let Some(uri) = link_index.and_then(|i| self.link_handler.borrow().uri(i)) {
+                        links.push((Position::new(line as i32, terminal_col as u16), uri));
+                    }
+                }
+                previous_link_index = link_index;
+                let character = if link_index.is_some() {
+                    ' '
+                } else {
+                    terminal_character.character
+                };
+                text.push(character);
+                text_columns.extend(std::iter::repeat(terminal_col).take(character.len_utf8()));
+                terminal_col += terminal_character.width;
+            }
+            for detected in link_pattern.find_iter(&text) {
+                let link = detected
+                    .as_str()
+                    .trim_end_matches(&['.', ',', ':', ';', '!', '?', ')', ']', '}', '\''][..]);
+                if link.chars().any(char::is_alphanumeric) {
+                    let column = text_columns[detected.start()];
+                    links.push((Position::new(line as i32, column as u16), link.to_owned()));
+                }
+            }
+        }
+        links.sort_by_key(|(position, _)| (position.line(), position.column()));
+        links
+    }
+    fn link_index_at(&self, position: &Position) -> Option<u16> {
+        let row = self.row_at(position.line.0)?;
+        let mut terminal_col = 0;
+        for terminal_character in &row.columns {
+            let width = terminal_character.width.max(1);
+            if (terminal_col..terminal_col + width).contains(&position.column()) {
+                return match terminal_character.styles.link_anchor {
+                    Some(LinkAnchor::Start(link_index)) => Some(link_index),
+                    _ => None,
+                };
+            }
+            terminal_col += terminal_character.width;
+        }
+        None
+    }
+    fn is_word_character(&self, character: char) -> bool {
+        character.is_alphanumeric() || self.word_characters.contains(character)
+    }
+    fn row_at(&self, line: isize) -> Option<&Row> {
+        if line < 0 {
+            self.lines_above
+                .len()
+                .checked_sub(line.unsigned_abs())
+                .and_then(|index| self.lines_above.get(index))
+        } else {
+            self.viewport.get(line as usize)
+        }
+    }
+    pub fn enter_copy_mode(&mut self) {
+        let cursor = Position::new(
+            self.cursor.y.min(self.height.saturating_sub(1)) as i32,
+            self.cursor.x.min(self.width.saturating_sub(1)) as u16,
+        );
+        self.copy_mode = Some(CopyMode {
+            cursor,
+            selection: None,
+        });
+        self.reset_selection();
+    }
+    pub fn is_in_copy_mode(&self) -> bool {
+        self.copy_mode.is_some()
+    }
+    pub fn exit_copy_mode(&mut self) {
+        self.copy_mode = None;
+        self.reset_selection();
+        self.reset_viewport();
+    }
+    /// Moves the copy mode cursor or changes its selection with vi or emacs style keys, returning
+    /// the selected text if it should be copied
+    pub fn handle_copy_mode_input(&mut self, input_bytes: &[u8]) -> Option<String> {
+        let page = self.height.saturating_sub(1).max(1);
+        let half_page = (self.height / 2).max(1);
+        match input_bytes {
+            b"h" | [2] | b"\x1b[D" | b"\x1bOD" => self.move_copy_mode_cursor_left(),
+            b"l" | [6] | b"\x1b[C" | b"\x1bOC" => self.move_copy_mode_cursor_right(),
+            b"k" | [16] | b"\x1b[A" | b"\x1bOA" => self.move_copy_mode_cursor_up(1),
+            b"j" | [14] | b"\x1b[B" | b"\x1bOB" => self.move_copy_mode_cursor_down(1),
+            b"\x1b[5~" | b"\x1bv" => self.move_copy_mode_cursor_up(page),
+            b"\x1b[6~" => self.move_copy_mode_cursor_down(page),
+            [21] => self.move_copy_mode_cursor_up(half_page),
+            [4] => self.move_copy_mode_cursor_down(half_page),
+            b"w" | b"\x1bf" => self.move_copy_mode_cursor_to_next_word(),
+            b"b" | b"\x1bb" => self.move_copy_mode_cursor_to_previous_word(),
+            b"0" | [1] | b"\x1b[H" | b"\x1bOH" => self.move_copy_mode_cursor_to_line_start(),
+            b"$" | [5] | b"\x1b[F" | b"\x1bOF" => self.move_copy_mode_cursor_to_line_end(),
+            b"g" | b"\x1b<" => self.move_copy_mode_cursor_to_top(),
+            b"G" | b"\x1b>" => self.move_copy_mode_cursor_to_bottom(),
+            b"v" | b" " | [0] => {
+                // like multiple clicks, selecting again widens the selection to words and lines
+                let granularity = match self.copy_mode.and_then(|c| c.selection) {
+                    None => Some(SelectionGranularity::Character),
+                    Some((SelectionGranularity::Character, _)) => Some(SelectionGranularity::Word),
+                    Some((SelectionGranularity::Word, _)) => Some(SelectionGranularity::Line),
+                    Some(_) => None,
+                };
+                self.select_in_copy_mode(granularity);
+            },
+            b"V" => self.toggle_copy_mode_selection(SelectionGranularity::Line),
+            [22] | b"R" => self.toggle_copy_mode_selection(SelectionGranularity::Block),
+            b"y" | b"\r" | b"\x1bw" => {
+                let selected_text = self.get_selected_text();
+                self.exit_copy_mode();
+                return selected_text;
+            },
+            b"q" | b"\x1b" | [3] | [7] => {
+                self.exit_copy_mode();
+                return None;
+            },
+            _ => {},
+        }
+        self.update_copy_mode_selection();
+        self.mark_for_rerender();
+        None
+    }
+    fn move_copy_mode_cursor_left(&mut self) {
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor.column.0 = copy_mode.cursor.column().saturating_sub(1);
+        }
+    }
+    fn move_copy_mode_cursor_right(&mut self) {
+        let last_column = self.width.saturating_sub(1);
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor.column.0 = (copy_mode.cursor.column() + 1).min(last_column);
+        }
+    }
+    fn move_copy_mode_cursor_up(&mut self, count: usize) {
+        // once the cursor reaches the top of the viewport, the viewport scrolls up instead
+        for _ in 0..count {
+            match self.copy_mode.map(|c| c.cursor.line()) {
+                Some(line) if line > 0 => self.set_copy_mode_cursor_line(line - 1),
+                Some(_) if self.scroll_up_in_copy_mode() => {},
+                _ => break,
+            }
+        }
+    }
+    fn move_copy_mode_cursor_down(&mut self, count: usize) {
+        // once the cursor reaches the bottom of the viewport, the viewport scrolls down instead
+        let last_line = self.height.saturating_sub(1) as isize;
+        for _ in 0..count {
+            match self.copy_mode.map(|c| c.cursor.line()) {
+                Some(line) if line < last_line => self.set_copy_mode_cursor_line(line + 1),
+                Some(_) if self.scroll_down_in_copy_mode() => {},
+                _ => break,
+            }
+        }
+    }
+    fn move_copy_mode_cursor_to_next_word(&mut self) {
+        let cursor = match self.copy_mode {
+            Some(copy_mode) => copy_mode.cursor,
+            None => return,
+        };
+        let next_word_start = self
+            .word_starts_at(cursor.line())
+            .into_iter()
+            .find(|word_start| *word_start > cursor.column());
+        match next_word_start {
+            Some(next_word_start) => self.set_copy_mode_cursor_column(next_word_start),
+            None => {
+                self.move_copy_mode_cursor_down(1);
+                self.move_copy_mode_cursor_to_line_start();
+                let cursor_line = self.copy_mode.map(|c| c.cursor.line()).unwrap_or(0);
+                if let Some(first_word_start) = self.word_starts_at(cursor_line).first() {
+                    self.set_copy_mode_cursor_column(*first_word_start);
+                }
+            },
+        }
+    }
+    fn move_copy_mode_cursor_to_previous_word(&mut self) {
+        let cursor = match self.copy_mode {
+            Some(copy_mode) => copy_mode.cursor,
+            None => return,
+        };
+        let previous_word_start = self
+            .word_starts_at(cursor.line())
+            .into_iter()
+            .rev()
+            .find(|word_start| *word_start < cursor.column());
+        match previous_word_start {
+            Some(previous_word_start) => self.set_copy_mode_cursor_column(previous_word_start),
+            None if cursor.line() > 0 || !self.lines_above.is_empty() => {
+                self.move_copy_mode_cursor_up(1);
+                let cursor_line = self.copy_mode.map(|c| c.cursor.line()).unwrap_or(0);
+                let last_word_start = self.word_starts_at(cursor_line).last().copied();
+                self.set_copy_mode_cursor_column(last_word_start.unwrap_or(0));
+            },
+            None => self.move_copy_mode_cursor_to_line_start(),
+        }
+    }
+    fn move_copy_mode_cursor_to_line_start(&mut self) {
+        self.set_copy_mode_cursor_column(0);
+    }
+    fn move_copy_mode_cursor_to_line_end(&mut self) {
+        let line_end = self
+            .copy_mode
+            .and_then(|c| self.row_at(c.cursor.line()))
+            .map(|r| r.width().saturating_sub(1))
+            .unwrap_or(0);
+        self.set_copy_mode_cursor_column(line_end);
+    }
+    fn move_copy_mode_cursor_to_top(&mut self) {
+        let max_lines_to_scroll = self.lines_above.len() + self.spilled_line_count();
+        for _ in 0..max_lines_to_scroll {
+            if !self.scroll_up_in_copy_mode() {
+                break;
+            }
+        }
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor = Position::new(0, 0);
+        }
+    }
+    fn move_copy_mode_cursor_to_bottom(&mut self) {
+        let max_lines_to_scroll = self.lines_below.len();
+        for _ in 0..max_lines_to_scroll {
+            if !self.scroll_down_in_copy_mode() {
+                break;
+            }
+        }
+        let last_line = self.viewport.len().saturating_sub(1);
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor = Position::new(last_line as i32, 0);
+        }
+    }
+    fn set_copy_mode_cursor_line(&mut self, line: isize) {
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor.line.0 = line;
+        }
+    }
+    fn set_copy_mode_cursor_column(&mut self, column: usize) {
+        let last_column = self.width.saturating_sub(1);
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            copy_mode.cursor.column.0 = column.min(last_column);
+        }
+    }
+    fn scroll_up_in_copy_mode(&mut self) -> bool {
+        // returns true if the viewport was scrolled, moving the start of the selection along with
+        // the lines it is on
+        let lines_below = self.lines_below.len();
+        self.scroll_up_one_line();
+        let scrolled = self.lines_below.len() > lines_below;
+        if let (true, Some((_, start))) = (scrolled, self.copy_mode_selection_mut()) {
+            start.line.0 += 1;
+        }
+        scrolled
+    }
+    fn scroll_down_in_copy_mode(&mut self) -> bool {
+        let lines_below = self.lines_below.len();
+        self.scroll_down_one_line();
+        let scrolled = self.lines_below.len() < lines_below;
+        if let (true, Some((_, start))) = (scrolled, self.copy_mode_selection_mut()) {
+            start.line.0 -= 1;
+        }
+        scrolled
+    }
+    fn copy_mode_selection_mut(&mut self) -> Option<&mut (SelectionGranularity, Position)> {
+        self.copy_mode.as_mut().and_then(|c| c.selection.as_mut())
+    }
+    fn select_in_copy_mode(&mut self, granularity: Option<SelectionGranularity>) {
+        // changing the granularity of an existing selection keeps the position it started from
+        if let Some(copy_mode) = self.copy_mode.as_mut() {
+            let start = copy_mode
+                .selection
+                .map(|(_, start)| start)
+                .unwrap_or(copy_mode.cursor);
+            copy_mode.selection = granularity.map(|granularity| (granularity, start));
+        }
+    }
+    fn toggle_copy_mode_selection(&mut self, granularity: SelectionGranularity) {
+        match self.copy_mode.and_then(|c| c.selection) {
+            Some((current_granularity, _)) if current_granularity == granularity => {
+                self.select_in_copy_mode(None)
+            },
+            _ => self.select_in_copy_mode(Some(granularity)),
+        }
+    }
+    fn update_copy_mode_selection(&mut self) {
+        let old_selection = self.selection;
+        let (cursor, granularity, start) = match self.copy_mode {
+            Some(CopyMode {
+                cursor,
+                selection: Some((granularity, start)),
+            }) => (cursor, granularity, start),
+            _ => {
+                self.selection.reset();
+                self.update_selected_lines(&old_selection, &self.selection.clone());
+                return;
+            },
+        };
+        let one_column_right = |position: Position| Position {
+            line: position.line,
+            column: Column(position.column() + 1),
+        };
+        self.selection_granularity = granularity;
+        let (selection_start, selection_end) = match granularity {
+            // both the character the selection started on and the one under the cursor are
+            // selected
+            SelectionGranularity::Character if cursor < start => (one_column_right(start), cursor),
+            SelectionGranularity::Character => (start, one_column_right(cursor)),
+            SelectionGranularity::Block => (
+                Position {
+                    line: Line(start.line().min(cursor.line())),
+                    column: Column(start.column().min(cursor.column())),
+                },
+                Position {
+                    line: Line(start.line().max(cursor.line())),
+                    column: Column(start.column().max(cursor.column()) + 1),
+                },
+            ),
+            SelectionGranularity::Word | SelectionGranularity::Line => {
+                let start_bounds = self.selection_bounds_at(&start);
+                self.selection_from_anchor(start_bounds, &cursor)
+            },
+        };
+        self.selection.start(selection_start);
+        self.selection.to(selection_end);
+        self.selection
+            .set_block(granularity == SelectionGranularity::Block);
+        self.update_selected_lines(&old_selection, &self.selection.clone());
+    }
+    fn word_starts_at(&self, line: isize) -> Vec<usize> {
+        // the terminal columns at which the words on this line start
+        let mut word_starts = vec![];
+        let mut terminal_col = 0;
+        let mut previous_is_word_character = false;
+        if let Some(row) = self.row_at(line) {
+            for terminal_character in &row.columns {
+                let is_word_character = self.is_word_character(terminal_character.character);
+                if is_word_character && !previous_is_word_character {
+                    word_starts.push(terminal_col);
+                }
+                previous_is_word_character = is_word_character;
+                terminal_col += terminal_character.width;
+            }
+        }
+        word_starts
+    }
     pub fn get_selected_text(&self) -> Option<String> {
         if self.selection.is_empty() {
             return None;
@@ -1720,10 +2603,14 @@ impl Grid {
 
             // on the first line of the selection, use the selection start column
             // otherwise, start at the beginning of the line
-            let start_column = if l == start.line.0 { start.column.0 } else { 0 };
+            let start_column = if l == start.line.0 || self.selection.is_block() {
+                start.column.0
+            } else {
+                0
+            };
 
             // same thing on the last line, but with the selection end column
-            let end_column = if l == end.line.0 {
+            let end_column = if l == end.line.0 || self.selection.is_block() {
                 end.column.0
             } else {
                 self.width
@@ -1765,7 +2652,7 @@ impl Grid {
                 terminal_col += terminal_character.width;
             }
 
-            if row.is_canonical {
+            if row.is_canonical || self.selection.is_block() {
                 selection.push(line_selection);
             } else {
                 // rejoin wrapped lines if possible
@@ -1788,7 +2675,28 @@ impl Grid {
         }
     }
     pub fn absolute_position_in_scrollback(&self) -> usize {
-        self.lines_above.len() + self.cursor.y
+        // lines in the scrollback are unwrapped, so wrapped lines in the viewport count only once
+        // - if the pane is scrolled up the cursor is still on the bottom of the rows, some of
+        // which are now below the viewport
+        let rows = self.viewport.len() + self.lines_below.len();
+        let cursor_row = rows.saturating_sub(self.height) + self.cursor.y;
+        let mut cursor_canonical_line_index = 0;
+        let mut canonical_lines_traversed = 0;
+        for (i, line) in self
+            .viewport
+            .iter()
+            .chain(self.lines_below.iter())
+            .enumerate()
+        {
+            if line.is_canonical {
+                cursor_canonical_line_index = canonical_lines_traversed;
+                canonical_lines_traversed += 1;
+            }
+            if i == cursor_row {
+                break;
+            }
+        }
+        self.spilled_line_count() + self.lines_above.len() + cursor_canonical_line_index
     }
 
     fn update_selected_lines(&mut self, old_selection: &Selection, new_selection: &Selection) {
@@ -1817,6 +2725,7 @@ impl Grid {
             &mut self.viewport,
             &mut self.lines_above,
             &mut self.sixel_grid,
+            self.scrollback_spill.as_deref(),
             count,
             self.width,
         );
@@ -2081,6 +2990,13 @@ impl Grid {
     pub fn is_alternate_mode_active(&self) -> bool {
         self.alternate_screen_state.is_some()
     }
+    fn kitty_keyboard_flags(&self) -> u8 {
+        self.kitty_keyboard_flags.last().copied().unwrap_or(0)
+    }
+    /// Whether the application asked for key events encoded with the kitty keyboard protocol
+    pub fn supports_kitty_keyboard_protocol(&self) -> bool {
+        self.kitty_keyboard_flags() != 0
+    }
     pub fn focus_event(&self) -> Option<String> {
         if self.focus_event_tracking {
             Some("\u{1b}[I".into())
@@ -2097,6 +3013,7 @@ impl Grid {
     }
     pub fn delete_viewport_and_scroll(&mut self) {
         self.lines_above.clear();
+        self.clear_scrollback_spill();
         self.viewport.clear();
         self.lines_below.clear();
     }
@@ -2124,6 +3041,7 @@ impl Perform for Grid {
         match byte {
             7 => {
                 self.ring_bell = true;
+                self.pending_bell_notification = true;
             },
             8 => {
                 // backspace
@@ -2313,6 +3231,33 @@ impl Perform for Grid {
                 }
             },
 
+            // Semantic prompt marks (shell integration).
+            b"133" => match params.get(1).and_then(|mark| mark.first()) {
+                Some(b'A') => self.mark_cursor_line(PromptMark::Prompt),
+                Some(b'C') => self.mark_cursor_line(PromptMark::CommandOutput),
+                // the end of the prompt (B) and of the command (D) are implied by the
+                // surrounding marks
+                _ => {},
+            },
+
+            // Seamless navigation (zellij specific): the application claims (`claim`) or gives
+            // back (`release`) the keys bound to MoveFocusOrDelegate, and hands back the moves it
+            // can't make itself (`move;left`), eg. at the edge of its own splits.
+            b"7711" => match params.get(1).copied() {
+                Some(b"claim") => self.navigation_is_delegated = true,
+                Some(b"release") => self.navigation_is_delegated = false,
+                Some(b"move") => {
+                    self.pending_navigation_bounce = match params.get(2).copied() {
+                        Some(b"left") => Some(Direction::Left),
+                        Some(b"right") => Some(Direction::Right),
+                        Some(b"up") => Some(Direction::Up),
+                        Some(b"down") => Some(Direction::Down),
+                        _ => None,
+                    };
+                },
+                _ => {},
+            },
+
             // Set clipboard.
             b"52" => {
                 if params.len() < 3 {
@@ -2485,6 +3430,7 @@ impl Perform for Grid {
                                     &mut self.viewport,
                                     &mut self.cursor,
                                     &mut self.sixel_grid,
+                                    &mut self.scrollback_spill,
                                 );
                             }
                             self.alternate_screen_state = None;
@@ -2578,11 +3524,14 @@ impl Perform for Grid {
                                 &mut self.sixel_grid,
                                 SixelGrid::new(self.character_cell_size.clone(), sixel_image_store),
                             );
+                            // the alternate screen has no scrollback worth spilling to disk
+                            let current_scrollback_spill = self.scrollback_spill.take();
                             self.alternate_screen_state = Some(AlternateScreenState::new(
                                 current_lines_above,
                                 current_viewport,
                                 current_cursor,
                                 alternate_sixelgrid,
+                                current_scrollback_spill,
                             ));
                             self.clear_viewport_before_rendering = true;
                             self.scrollback_buffer_lines =
@@ -2745,7 +3694,40 @@ impl Perform for Grid {
         } else if c == 's' {
             self.save_cursor_position();
         } else if c == 'u' {
-            self.restore_cursor_position();
+            match intermediates.get(0) {
+                // kitty keyboard protocol: query, push, pop and set the enhancement flags
+                Some(b'?') => {
+                    let flags_report = format!("\u{1b}[?{}u", self.kitty_keyboard_flags());
+                    self.pending_messages_to_pty
+                        .push(flags_report.as_bytes().to_vec());
+                },
+                Some(b'>') => {
+                    if self.kitty_keyboard_flags.len() >= MAX_KITTY_KEYBOARD_FLAGS_STACK_SIZE {
+                        self.kitty_keyboard_flags.remove(0);
+                    }
+                    let flags = next_param_or(0) as u8 & kitty_keyboard::SUPPORTED_FLAGS;
+                    self.kitty_keyboard_flags.push(flags);
+                },
+                Some(b'<') => {
+                    let count = next_param_or(1).min(self.kitty_keyboard_flags.len());
+                    self.kitty_keyboard_flags
+                        .truncate(self.kitty_keyboard_flags.len() - count);
+                },
+                Some(b'=') => {
+                    let flags = next_param_or(0) as u8 & kitty_keyboard::SUPPORTED_FLAGS;
+                    let current_flags = self.kitty_keyboard_flags();
+                    let new_flags = match next_param_or(1) {
+                        2 => current_flags | flags,
+                        3 => current_flags & !flags,
+                        _ => flags,
+                    };
+                    match self.kitty_keyboard_flags.last_mut() {
+                        Some(current_flags) => *current_flags = new_flags,
+                        None => self.kitty_keyboard_flags.push(new_flags),
+                    }
+                },
+                _ => self.restore_cursor_position(),
+            }
         } else if c == '@' {
             let count = next_param_or(1);
             for _ in 0..count {
@@ -3003,6 +3985,7 @@ pub struct AlternateScreenState {
     viewport: Vec<Row>,
     cursor: Cursor,
     sixel_grid: SixelGrid,
+    scrollback_spill: Option<Rc<RefCell<ScrollbackSpill>>>,
 }
 impl AlternateScreenState {
     pub fn new(
@@ -3010,12 +3993,14 @@ impl AlternateScreenState {
         viewport: Vec<Row>,
         cursor: Cursor,
         sixel_grid: SixelGrid,
+        scrollback_spill: Option<Rc<RefCell<ScrollbackSpill>>>,
     ) -> Self {
         AlternateScreenState {
             lines_above,
             viewport,
             cursor,
             sixel_grid,
+            scrollback_spill,
         }
     }
     pub fn apply_contents_to(
@@ -3024,18 +4009,29 @@ impl AlternateScreenState {
         viewport: &mut Vec<Row>,
         cursor: &mut Cursor,
         sixel_grid: &mut SixelGrid,
+        scrollback_spill: &mut Option<Rc<RefCell<ScrollbackSpill>>>,
     ) {
         std::mem::swap(&mut self.lines_above, lines_above);
         std::mem::swap(&mut self.viewport, viewport);
         std::mem::swap(&mut self.cursor, cursor);
         std::mem::swap(&mut self.sixel_grid, sixel_grid);
+        std::mem::swap(&mut self.scrollback_spill, scrollback_spill);
     }
 }
 
+/// A semantic prompt mark (OSC 133) the shell placed at the start of a canonical line
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+pub enum PromptMark {
+    Prompt,        // OSC 133;A - a prompt starts on this line
+    CommandOutput, // OSC 133;C - the output of the command entered at the last prompt starts here
+}
+
 #[derive(Clone)]
 pub struct Row {
     pub columns: VecDeque<TerminalCharacter>,
     pub is_canonical: bool,
+    pub prompt_mark: Option<PromptMark>,
     width: Option<usize>,
 }
 
@@ -3053,6 +4049,7 @@ impl Row {
         Row {
             columns: VecDeque::new(),
             is_canonical: false,
+            prompt_mark: None,
             width: None,
         }
     }
@@ -3060,6 +4057,7 @@ impl Row {
         Row {
             columns,
             is_canonical: false,
+            prompt_mark: None,
             width: None,
         }
     }
@@ -3357,6 +4355,7 @@ impl Row {
         };
         if !parts.is_empty() && self.is_canonical {
             parts.get_mut(0).unwrap().is_canonical = true;
+            parts.get_mut(0).unwrap().prompt_mark = self.prompt_mark;
         }
         if parts.is_empty() {
             parts.push(self.clone());
@@ -1,6 +1,7 @@
 pub mod alacritty_functions;
 pub mod grid;
 pub mod link_handler;
+pub mod pane_output_decoder;
 pub mod selection;
 pub mod sixel;
 pub mod terminal_character;
@@ -8,6 +9,7 @@ pub mod terminal_character;
 mod active_panes;
 mod floating_panes;
 mod plugin_pane;
+mod scrollback_spill;
 mod search;
 mod terminal_pane;
 mod tiled_panes;
@@ -0,0 +1,66 @@
+use zellij_utils::vte::{Params, Parser, Perform};
+
+/// Decodes the raw pty output of a terminal pane into plain text for plugins subscribed to it,
+/// dropping escape sequences and carriage returns. The parser is kept between chunks so that
+/// sequences split across reads are still recognized.
+#[derive(Default)]
+pub struct PaneOutputDecoder {
+    parser: Parser,
+    text: DecodedText,
+}
+
+#[derive(Default)]
+struct DecodedText(String);
+
+impl Perform for DecodedText {
+    fn print(&mut self, c: char) {
+        self.0.push(c);
+    }
+    fn execute(&mut self, byte: u8) {
+        match byte {
+            b'\n' => self.0.push('\n'),
+            b'\t' => self.0.push('\t'),
+            _ => {},
+        }
+    }
+    fn hook(&mut self, _params: &Params, _intermediates: &[u8], _ignore: bool, _c: char) {}
+    fn put(&mut self, _byte: u8) {}
+    fn unhook(&mut self) {}
+    fn osc_dispatch(&mut self, _params: &[&[u8]], _bell_terminated: bool) {}
+    fn csi_dispatch(&mut self, _params: &Params, _intermediates: &[u8], _ignore: bool, _c: char) {}
+    fn esc_dispatch(&mut self, _intermediates: &[u8], _ignore: bool, _byte: u8) {}
+}
+
+impl PaneOutputDecoder {
+    /// Returns the text decoded from this chunk, if any
+    pub fn decode(&mut self, bytes: &[u8]) -> Option<String> {
+        for byte in bytes {
+            self.parser.advance(&mut self.text, *byte);
+        }
+        if self.text.0.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut self.text.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_escape_sequences_and_carriage_returns() {
+        let mut decoder = PaneOutputDecoder::default();
+        let decoded = decoder.decode(b"\x1b[1;31mtest result: FAILED\x1b[0m\r\n\x1b]0;title\x07ok");
+        assert_eq!(decoded, Some("test result: FAILED\nok".to_owned()));
+    }
+
+    #[test]
+    fn escape_sequence_split_across_chunks() {
+        let mut decoder = PaneOutputDecoder::default();
+        assert_eq!(decoder.decode(b"foo\x1b[3"), Some("foo".to_owned()));
+        assert_eq!(decoder.decode(b"2mbar"), Some("bar".to_owned()));
+        assert_eq!(decoder.decode(b"\x1b[0m"), None);
+    }
+}
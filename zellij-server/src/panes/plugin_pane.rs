@@ -7,6 +7,7 @@ use crate::plugins::PluginInstruction;
 use crate::pty::VteBytes;
 use crate::tab::{AdjustedInput, Pane};
 use crate::ui::{
+    components::render_ui_components,
     loading_indication::LoadingIndication,
     pane_boundaries_frame::{FrameParams, PaneFrame},
 };
@@ -85,6 +86,7 @@ pub(crate) struct PluginPane {
     loading_indication: LoadingIndication,
     requesting_permissions: Option<PluginPermission>,
     debug: bool,
+    hovered_positions: HashMap<ClientId, Position>, // only changes are sent to the plugin
 }
 
 impl PluginPane {
@@ -134,6 +136,7 @@ impl PluginPane {
             loading_indication,
             requesting_permissions: None,
             debug,
+            hovered_positions: HashMap::new(),
         };
         for client_id in currently_connected_clients {
             plugin.handle_plugin_bytes(client_id, initial_loading_message.as_bytes().to_vec());
@@ -194,7 +197,7 @@ impl Pane for PluginPane {
     fn handle_plugin_bytes(&mut self, client_id: ClientId, bytes: VteBytes) {
         self.set_client_should_render(client_id, true);
 
-        let mut vte_bytes = bytes;
+        let mut vte_bytes = render_ui_components(bytes, &self.style);
         if let Some(plugin_permission) = &self.requesting_permissions {
             vte_bytes = self
                 .display_request_permission_message(plugin_permission)
@@ -391,7 +394,7 @@ impl Pane for PluginPane {
     ) -> Option<String> {
         None
     }
-    fn render_terminal_title(&mut self, input_mode: InputMode) -> String {
+    fn render_terminal_title(&mut self, input_mode: InputMode, tab_name: &str) -> String {
         let pane_title = if self.pane_name.is_empty() && input_mode == InputMode::RenamePane {
             "Enter name..."
         } else if self.pane_name.is_empty() {
@@ -399,7 +402,7 @@ impl Pane for PluginPane {
         } else {
             &self.pane_name
         };
-        make_terminal_title(pane_title)
+        make_terminal_title(pane_title, tab_name, None)
     }
     fn update_name(&mut self, name: &str) {
         match name {
@@ -568,6 +571,34 @@ impl Pane for PluginPane {
             )]))
             .unwrap();
     }
+    fn handle_middle_click(&mut self, to: &Position, client_id: ClientId) {
+        self.send_plugin_instructions
+            .send(PluginInstruction::Update(vec![(
+                Some(self.pid),
+                Some(client_id),
+                Event::Mouse(Mouse::MiddleClick(to.line.0, to.column.0)),
+            )]))
+            .unwrap();
+    }
+    fn handle_mouse_hover(&mut self, position: Option<&Position>, client_id: ClientId) {
+        // the mouse reports every movement, the plugin only hears about it when it moves to
+        // another cell or out of the pane
+        let mouse_event = match position {
+            Some(position) if self.hovered_positions.get(&client_id) != Some(position) => {
+                self.hovered_positions.insert(client_id, *position);
+                Mouse::Hover(position.line.0, position.column.0)
+            },
+            None if self.hovered_positions.remove(&client_id).is_some() => Mouse::HoverEnd,
+            _ => return,
+        };
+        self.send_plugin_instructions
+            .send(PluginInstruction::Update(vec![(
+                Some(self.pid),
+                Some(client_id),
+                Event::Mouse(mouse_event),
+            )]))
+            .unwrap();
+    }
     fn add_red_pane_frame_color_override(&mut self, error_text: Option<String>) {
         self.pane_frame_color_override = Some((self.style.colors.red, error_text));
     }
@@ -585,6 +616,9 @@ impl Pane for PluginPane {
     fn set_title(&mut self, title: String) {
         self.pane_title = title;
     }
+    fn update_style(&mut self, style: Style) {
+        self.style = style;
+    }
     fn update_loading_indication(&mut self, loading_indication: LoadingIndication) {
         if self.loading_indication.ended && !loading_indication.is_error() {
             return;
@@ -616,6 +650,13 @@ impl Pane for PluginPane {
             self.pane_name.to_owned()
         }
     }
+    fn custom_title(&self) -> Option<String> {
+        if self.pane_name.is_empty() {
+            None
+        } else {
+            Some(self.pane_name.clone())
+        }
+    }
     fn rename(&mut self, buf: Vec<u8>) {
         self.pane_name = String::from_utf8_lossy(&buf).to_string();
         self.set_should_render(true);
@@ -0,0 +1,193 @@
+//! Scrollback lines beyond the in-memory limit, kept on disk rather than discarded.
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+use zellij_utils::errors::prelude::*;
+use zellij_utils::{serde, tempfile::tempfile};
+
+use serde::{Deserialize, Serialize};
+use unicode_width::UnicodeWidthChar;
+
+use crate::panes::grid::{PromptMark, Row};
+use crate::panes::terminal_character::{CharacterStyles, TerminalCharacter};
+
+/// A row as it is kept on disk: its text, and the styles of its characters as runs of
+/// consecutive characters sharing the same styles (most rows have only a handful of them)
+#[derive(Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
+struct SpilledRow {
+    text: String,
+    styles: Vec<(usize, CharacterStyles)>, // (character count, styles)
+    #[serde(default)]
+    widths: Vec<(usize, usize)>, // (character index, width) of characters with a non-default width
+    is_canonical: bool,
+    #[serde(default)]
+    prompt_mark: Option<PromptMark>,
+}
+
+impl SpilledRow {
+    fn from_row(row: Row) -> Self {
+        let mut text = String::with_capacity(row.columns.len());
+        let mut styles: Vec<(usize, CharacterStyles)> = vec![];
+        let mut widths = vec![];
+        for (index, character) in row.columns.iter().enumerate() {
+            text.push(character.character);
+            match styles.last_mut() {
+                Some((count, styles)) if *styles == character.styles => *count += 1,
+                _ => styles.push((1, character.styles)),
+            }
+            if character.width != character.character.width().unwrap_or(0) {
+                widths.push((index, character.width));
+            }
+        }
+        SpilledRow {
+            text,
+            styles,
+            widths,
+            is_canonical: row.is_canonical,
+            prompt_mark: row.prompt_mark,
+        }
+    }
+    fn into_row(self) -> Row {
+        let mut styles = self
+            .styles
+            .into_iter()
+            .flat_map(|(count, styles)| std::iter::repeat(styles).take(count));
+        let mut widths = self.widths.into_iter().peekable();
+        let columns: VecDeque<TerminalCharacter> = self
+            .text
+            .chars()
+            .enumerate()
+            .map(|(index, character)| {
+                let mut terminal_character = TerminalCharacter::new(character);
+                terminal_character.styles = styles.next().unwrap_or_default();
+                if let Some((_, width)) = widths.next_if(|(i, _)| *i == index) {
+                    terminal_character.width = width;
+                }
+                terminal_character
+            })
+            .collect();
+        let mut row = Row::from_columns(columns);
+        row.is_canonical = self.is_canonical;
+        row.prompt_mark = self.prompt_mark;
+        row
+    }
+}
+
+/// A stack of rows that were pushed out of the top of a grid's `lines_above`.
+///
+/// Rows are appended to an anonymous temporary file as they are spilled and read back (and
+/// truncated from the file) in reverse order as they are paged back in, so that the most recently
+/// spilled row is always the one directly above the oldest row still held in memory.
+///
+/// Once `limit` rows were spilled, the oldest ones are discarded to make room for new ones.
+pub struct ScrollbackSpill {
+    file: File,
+    row_offsets: VecDeque<(u64, usize)>, // (offset, length) of each spilled row, oldest first
+    limit: usize,
+}
+
+impl ScrollbackSpill {
+    pub fn new(limit: usize) -> Result<Self> {
+        let file = tempfile().context("failed to create scrollback spillover file")?;
+        Ok(ScrollbackSpill {
+            file,
+            row_offsets: VecDeque::new(),
+            limit,
+        })
+    }
+    pub fn len(&self) -> usize {
+        self.row_offsets.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.row_offsets.is_empty()
+    }
+    pub fn push(&mut self, row: Row) -> Result<()> {
+        let err_context = || "failed to spill scrollback row to disk";
+
+        if self.limit == 0 {
+            return Ok(());
+        }
+        if self.row_offsets.len() >= self.limit {
+            self.discard_oldest_row().with_context(err_context)?;
+        }
+        let bytes = serde_json::to_vec(&SpilledRow::from_row(row)).with_context(err_context)?;
+        let offset = self.end_offset();
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .with_context(err_context)?;
+        self.file.write_all(&bytes).with_context(err_context)?;
+        self.row_offsets.push_back((offset, bytes.len()));
+        Ok(())
+    }
+    pub fn pop(&mut self) -> Result<Option<Row>> {
+        let err_context = || "failed to page spilled scrollback row back in";
+
+        let (offset, length) = match self.row_offsets.pop_back() {
+            Some(row_offset) => row_offset,
+            None => return Ok(None),
+        };
+        let mut bytes = vec![0; length];
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .with_context(err_context)?;
+        self.file.read_exact(&mut bytes).with_context(err_context)?;
+        self.file.set_len(offset).with_context(err_context)?;
+        let spilled_row: SpilledRow = serde_json::from_slice(&bytes).with_context(err_context)?;
+        Ok(Some(spilled_row.into_row()))
+    }
+    pub fn read_all(&mut self) -> Result<Vec<Row>> {
+        let err_context = || "failed to read spilled scrollback rows";
+
+        let mut bytes = vec![];
+        self.file
+            .seek(SeekFrom::Start(0))
+            .with_context(err_context)?;
+        self.file
+            .read_to_end(&mut bytes)
+            .with_context(err_context)?;
+        let mut rows = Vec::with_capacity(self.row_offsets.len());
+        for (offset, length) in &self.row_offsets {
+            let start = *offset as usize;
+            let spilled_row: SpilledRow =
+                serde_json::from_slice(&bytes[start..start + length]).with_context(err_context)?;
+            rows.push(spilled_row.into_row());
+        }
+        Ok(rows)
+    }
+    pub fn clear(&mut self) -> Result<()> {
+        self.row_offsets.clear();
+        self.file
+            .set_len(0)
+            .context("failed to clear scrollback spillover file")
+    }
+    fn end_offset(&self) -> u64 {
+        self.row_offsets
+            .back()
+            .map(|(offset, length)| offset + *length as u64)
+            .unwrap_or(0)
+    }
+    fn discard_oldest_row(&mut self) -> Result<()> {
+        self.row_offsets.pop_front();
+        let discarded_bytes = match self.row_offsets.front() {
+            Some((offset, _length)) => *offset,
+            None => return self.clear(),
+        };
+        // the discarded rows are left at the start of the file until they take up more space
+        // than the rows that are kept, so that the file is not rewritten for every spilled row
+        let kept_bytes = self.end_offset() - discarded_bytes;
+        if discarded_bytes > kept_bytes {
+            let mut bytes = vec![0; kept_bytes as usize];
+            self.file.seek(SeekFrom::Start(discarded_bytes))?;
+            self.file.read_exact(&mut bytes)?;
+            self.file.seek(SeekFrom::Start(0))?;
+            self.file.write_all(&bytes)?;
+            self.file.set_len(kept_bytes)?;
+            for (offset, _length) in self.row_offsets.iter_mut() {
+                *offset -= discarded_bytes;
+            }
+        }
+        Ok(())
+    }
+}
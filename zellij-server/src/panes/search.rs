@@ -5,6 +5,7 @@ use std::borrow::Cow;
 use std::fmt::Debug;
 use zellij_utils::input::actions::SearchDirection;
 use zellij_utils::position::Position;
+use zellij_utils::regex::{Regex, RegexBuilder};
 
 // If char is neither alphanumeric nor an underscore do we consider it a word-boundary
 fn is_word_boundary(x: &Option<char>) -> bool {
@@ -98,6 +99,10 @@ pub struct SearchResult {
     pub whole_word_only: bool, // TODO
     // Jump from the bottom to the top (or vice versa), if we run out of lines to search
     pub wrap_search: bool,
+    // Interpret the needle as a regular expression
+    pub regex_search: bool,
+    // The compiled needle, if searching with a regular expression (None if it is invalid)
+    regex: Option<Regex>,
 }
 
 impl SearchResult {
@@ -141,7 +146,25 @@ impl SearchResult {
     }
 
     pub fn has_modifiers_set(&self) -> bool {
-        self.wrap_search || self.whole_word_only || self.case_insensitive
+        self.wrap_search || self.whole_word_only || self.case_insensitive || self.regex_search
+    }
+
+    /// Needs to be called whenever the needle or one of the options affecting the regular
+    /// expression changes
+    pub(crate) fn update_regex(&mut self) {
+        self.regex = if self.regex_search && !self.needle.is_empty() {
+            let pattern = if self.whole_word_only {
+                format!(r"\b(?:{})\b", self.needle)
+            } else {
+                self.needle.clone()
+            };
+            RegexBuilder::new(&pattern)
+                .case_insensitive(self.case_insensitive)
+                .build()
+                .ok()
+        } else {
+            None
+        };
     }
 
     fn check_if_haystack_char_matches_needle(
@@ -181,6 +204,9 @@ impl SearchResult {
         if self.needle.is_empty() || row.columns.is_empty() {
             return res;
         }
+        if self.regex_search {
+            return self.search_row_with_regex(ridx, row, tail);
+        }
 
         let mut tailit = tail.iter();
         let mut source = SearchSource::Main(row); // Where we currently get the haystack-characters from
@@ -279,6 +305,47 @@ impl SearchResult {
         res
     }
 
+    /// Search a row and its tail with the compiled regular expression, only matches starting in
+    /// `row` are returned (the ones starting in the tail will be found when searching it)
+    fn search_row_with_regex(&self, ridx: usize, row: &Row, tail: &[&Row]) -> Vec<Selection> {
+        let mut res = Vec::new();
+        let regex = match &self.regex {
+            Some(regex) => regex,
+            None => return res,
+        };
+        // the byte offset of each haystack char, along with its position in the grid
+        let mut char_positions: Vec<(usize, Position)> = Vec::new();
+        let mut haystack = String::new();
+        for (line_offset, haystack_row) in
+            std::iter::once(row).chain(tail.iter().copied()).enumerate()
+        {
+            for (hidx, terminal_character) in haystack_row.columns.iter().enumerate() {
+                char_positions.push((
+                    haystack.len(),
+                    Position::new((ridx + line_offset) as i32, hidx as u16),
+                ));
+                haystack.push(terminal_character.character);
+            }
+        }
+        let main_row_len = row.columns.len();
+        for regex_match in regex.find_iter(&haystack) {
+            if regex_match.start() == regex_match.end() {
+                continue;
+            }
+            let start_idx = char_positions.partition_point(|(b, _)| *b < regex_match.start());
+            if start_idx >= main_row_len {
+                break;
+            }
+            let end_idx = char_positions.partition_point(|(b, _)| *b < regex_match.end()) - 1;
+            let end = char_positions[end_idx].1;
+            let mut selection = Selection::default();
+            selection.start(char_positions[start_idx].1);
+            selection.end(Position::new(end.line() as i32, (end.column() + 1) as u16));
+            res.push(selection);
+        }
+        res
+    }
+
     pub(crate) fn move_active_selection_to_next(&mut self) {
         if let Some(active_idx) = self.active {
             self.active = self
@@ -412,6 +479,7 @@ impl Grid {
 
     pub fn set_search_string(&mut self, needle: &str) {
         self.search_results.needle = needle.to_string();
+        self.search_results.update_regex();
         self.search_viewport();
         // If the current viewport does not contain any hits,
         // we jump around until we find something. Starting
@@ -454,6 +522,7 @@ impl Grid {
 
     pub fn toggle_search_case_sensitivity(&mut self) {
         self.search_results.case_insensitive = !self.search_results.case_insensitive;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -469,6 +538,20 @@ impl Grid {
 
     pub fn toggle_search_whole_words(&mut self) {
         self.search_results.whole_word_only = !self.search_results.whole_word_only;
+        self.search_results.update_regex();
+        for line in self.search_results.selections.drain(..) {
+            self.output_buffer
+                .update_lines(line.start.line() as usize, line.end.line() as usize);
+        }
+        self.search_results.active = None;
+        self.search_viewport();
+        // Maybe the selection we had is now gone
+        self.search_results.unset_active_selection_if_nonexistent();
+    }
+
+    pub fn toggle_search_regex(&mut self) {
+        self.search_results.regex_search = !self.search_results.regex_search;
+        self.search_results.update_regex();
         for line in self.search_results.selections.drain(..) {
             self.output_buffer
                 .update_lines(line.start.line() as usize, line.end.line() as usize);
@@ -539,7 +622,7 @@ impl Grid {
 
     fn search_reached_opposite_end(&mut self, dir: SearchDirection) -> bool {
         match dir {
-            SearchDirection::Up => self.lines_above.is_empty(),
+            SearchDirection::Up => self.lines_above.is_empty() && self.spilled_line_count() == 0,
             SearchDirection::Down => self.lines_below.is_empty(),
         }
     }
@@ -652,7 +735,7 @@ impl Grid {
             },
             SearchDirection::Down => {
                 // Go to the top
-                while !self.lines_above.is_empty() {
+                while !self.lines_above.is_empty() || self.spilled_line_count() > 0 {
                     rows += 1;
                     self.scroll_up_one_line();
                 }
@@ -9,6 +9,7 @@ pub struct Selection {
     pub start: Position,
     pub end: Position,
     active: bool, // used to handle moving the selection up and down
+    block: bool,  // only the columns between start and end are selected, on every line
 }
 
 impl Default for Selection {
@@ -17,6 +18,7 @@ impl Default for Selection {
             start: Position::new(0, 0),
             end: Position::new(0, 0),
             active: false,
+            block: false,
         }
     }
 }
@@ -24,6 +26,7 @@ impl Default for Selection {
 impl Selection {
     pub fn start(&mut self, start: Position) {
         self.active = true;
+        self.block = false;
         self.start = start;
         self.end = start;
     }
@@ -37,6 +40,14 @@ impl Selection {
         self.end = end;
     }
 
+    pub fn set_block(&mut self, block: bool) {
+        self.block = block;
+    }
+
+    pub fn is_block(&self) -> bool {
+        self.block
+    }
+
     pub fn contains(&self, row: usize, col: usize) -> bool {
         let row = row as isize;
         let (start, end) = if self.start <= self.end {
@@ -45,6 +56,12 @@ impl Selection {
             (self.end, self.start)
         };
 
+        if self.block {
+            return start.line.0 <= row
+                && row <= end.line.0
+                && start.column.0 <= col
+                && col < end.column.0;
+        }
         if (start.line.0) < row && row < end.line.0 {
             return true;
         }
@@ -74,6 +91,7 @@ impl Selection {
     pub fn reset(&mut self) {
         self.start = Position::new(0, 0);
         self.end = self.start;
+        self.block = false;
     }
 
     pub fn sorted(&self) -> Self {
@@ -86,6 +104,7 @@ impl Selection {
             start,
             end,
             active: self.active,
+            block: self.block,
         }
     }
 
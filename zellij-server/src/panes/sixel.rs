@@ -224,6 +224,15 @@ impl SixelGrid {
             }
         }
     }
+    pub fn offset_grid_bottom(&mut self) {
+        // a line was added to the top of the scrollback (eg. paged back in from disk)
+        if let Some(character_cell_size) = *self.character_cell_size.borrow() {
+            let height_to_add = character_cell_size.height as isize;
+            for (_sixel_image_id, pixel_rect) in self.sixel_image_locations.iter_mut() {
+                pixel_rect.y += height_to_add;
+            }
+        }
+    }
     pub fn drain_image_ids_to_reap(&mut self) -> Option<Vec<usize>> {
         let images_to_reap = self.image_ids_to_reap.drain(..);
         if images_to_reap.len() > 0 {
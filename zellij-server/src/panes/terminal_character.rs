@@ -7,9 +7,12 @@ use unicode_width::UnicodeWidthStr;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::{
     data::{PaletteColor, Style},
+    serde,
     vte::ParamsIter,
 };
 
+use serde::{Deserialize, Serialize};
+
 use crate::panes::alacritty_functions::parse_sgr_color;
 
 pub const EMPTY_TERMINAL_CHARACTER: TerminalCharacter = TerminalCharacter {
@@ -33,7 +36,8 @@ pub const RESET_STYLES: CharacterStyles = CharacterStyles {
     link_anchor: Some(LinkAnchor::End),
 };
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum AnsiCode {
     On,
     Reset,
@@ -51,7 +55,8 @@ impl From<PaletteColor> for AnsiCode {
     }
 }
 
-#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+#[derive(Clone, Copy, Eq, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum NamedColor {
     Black,
     Red,
@@ -118,7 +123,8 @@ impl NamedColor {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
+#[derive(Clone, Copy, Debug, PartialEq, Default, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub struct CharacterStyles {
     pub foreground: Option<AnsiCode>,
     pub background: Option<AnsiCode>,
@@ -568,7 +574,8 @@ impl Display for CharacterStyles {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub enum LinkAnchor {
     Start(u16),
     End,
@@ -719,7 +726,8 @@ impl Cursor {
     }
 }
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(crate = "self::serde")]
 pub struct TerminalCharacter {
     pub character: char,
     pub styles: CharacterStyles,
@@ -1,3 +1,4 @@
+use crate::os_input_output::TerminalProcesses;
 use crate::output::{CharacterChunk, SixelImageChunk};
 use crate::panes::sixel::SixelImageStore;
 use crate::panes::LinkHandler;
@@ -16,9 +17,10 @@ use std::time::{self, Instant};
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::pane_size::Offset;
 use zellij_utils::{
-    data::{InputMode, Palette, PaletteColor, Style},
+    consts::PANE_FRAME_TITLE,
+    data::{Direction, InputMode, Palette, PaletteColor, Style},
     errors::prelude::*,
-    input::layout::Run,
+    input::{kitty_keyboard, layout::Run},
     pane_size::PaneGeom,
     pane_size::SizeInPixels,
     position::Position,
@@ -37,8 +39,8 @@ const UP_ARROW: &[u8] = &[27, 91, 65];
 const DOWN_ARROW: &[u8] = &[27, 91, 66];
 const HOME_KEY: &[u8] = &[27, 91, 72];
 const END_KEY: &[u8] = &[27, 91, 70];
-const BRACKETED_PASTE_BEGIN: &[u8] = &[27, 91, 50, 48, 48, 126];
-const BRACKETED_PASTE_END: &[u8] = &[27, 91, 50, 48, 49, 126];
+pub const BRACKETED_PASTE_BEGIN: &[u8] = &[27, 91, 50, 48, 48, 126];
+pub const BRACKETED_PASTE_END: &[u8] = &[27, 91, 50, 48, 49, 126];
 const ENTER_NEWLINE: &[u8] = &[10];
 const ENTER_CARRIAGE_RETURN: &[u8] = &[13];
 const SPACE: &[u8] = &[32];
@@ -106,6 +108,8 @@ pub struct TerminalPane {
     frame: HashMap<ClientId, PaneFrame>,
     borderless: bool,
     exclude_from_sync: bool,
+    is_sync_group_member: bool,
+    is_pane_group_member: bool,
     fake_cursor_locations: HashSet<(usize, usize)>, // (x, y) - these hold a record of previous fake cursors which we need to clear on render
     search_term: String,
     is_held: Option<(Option<i32>, IsFirstRun, RunCommand)>, // a "held" pane means that its command has either exited and the pane is waiting for a
@@ -114,6 +118,7 @@ pub struct TerminalPane {
     // held on startup and can possibly be used to display some errors
     pane_frame_color_override: Option<(PaletteColor, Option<String>)>,
     invoked_with: Option<Run>,
+    terminal_processes: Option<TerminalProcesses>, // shown in the frame title if it has a template
 }
 
 impl Pane for TerminalPane {
@@ -182,20 +187,22 @@ impl Pane for TerminalPane {
         // needs to be adjusted.
         // here we match against those cases - if need be, we adjust the input and if not
         // we send back the original input
-        if let Some((_exit_status, _is_first_run, run_command)) = &self.is_held {
-            match input_bytes.as_slice() {
+        if self.is_held.is_some() {
+            match kitty_keyboard::to_legacy_input(&input_bytes).as_slice() {
                 ENTER_CARRIAGE_RETURN | ENTER_NEWLINE | SPACE => {
-                    let run_command = run_command.clone();
-                    self.is_held = None;
-                    self.grid.reset_terminal_state();
-                    self.set_should_render(true);
-                    self.remove_banner();
-                    Some(AdjustedInput::ReRunCommandInThisPane(run_command))
+                    self.respawn().map(AdjustedInput::ReRunCommandInThisPane)
                 },
                 CTRL_C => Some(AdjustedInput::CloseThisPane),
                 _ => None,
             }
         } else {
+            // keys encoded with the kitty keyboard protocol are only understood by applications
+            // that enabled it
+            let input_bytes = if self.grid.supports_kitty_keyboard_protocol() {
+                input_bytes
+            } else {
+                kitty_keyboard::to_legacy_input(&input_bytes)
+            };
             if self.grid.new_line_mode {
                 if let &[13] = input_bytes.as_slice() {
                     // LNM - carriage return is followed by linefeed
@@ -314,6 +321,9 @@ impl Pane for TerminalPane {
     ) -> Result<Option<(Vec<CharacterChunk>, Option<String>)>> {
         let err_context = || format!("failed to render frame for client {client_id}");
         // TODO: remove the cursor stuff from here
+        // the configured title template only replaces the title of the pane, not the prompts and
+        // messages displayed in its place
+        let mut is_pane_title = false;
         let pane_title = if let Some(text_color_override) = self
             .pane_frame_color_override
             .as_ref()
@@ -346,18 +356,33 @@ impl Pane for TerminalPane {
                 if self.grid.search_results.wrap_search {
                     modifiers.push("w")
                 }
+                if self.grid.search_results.regex_search {
+                    modifiers.push("r")
+                }
                 modifier_text.push_str(&modifiers.join(", "));
                 modifier_text.push(']');
             }
             format!("SEARCHING: {}{}", self.search_term, modifier_text)
         } else if self.pane_name.is_empty() {
+            is_pane_title = true;
             self.grid
                 .title
                 .clone()
                 .unwrap_or_else(|| self.pane_title.clone())
         } else {
+            is_pane_title = true;
             self.pane_name.clone()
         };
+        let pane_title = if self.is_sync_group_member {
+            format!("{} (Sync)", pane_title)
+        } else {
+            pane_title
+        };
+        let pane_title = if self.is_pane_group_member {
+            format!("{} (Grouped)", pane_title)
+        } else {
+            pane_title
+        };
 
         let frame_geom = self.current_geom();
         let mut frame = PaneFrame::new(
@@ -376,6 +401,9 @@ impl Pane for TerminalPane {
         if let Some((frame_color_override, _text)) = self.pane_frame_color_override.as_ref() {
             frame.override_color(*frame_color_override);
         }
+        if let Some(title_template) = PANE_FRAME_TITLE.get().filter(|_| is_pane_title) {
+            frame.apply_title_template(title_template, self.terminal_processes.as_ref());
+        }
 
         let res = match self.frame.get(&client_id) {
             // TODO: use and_then or something?
@@ -430,7 +458,7 @@ impl Pane for TerminalPane {
         }
         vte_output
     }
-    fn render_terminal_title(&mut self, input_mode: InputMode) -> String {
+    fn render_terminal_title(&mut self, input_mode: InputMode, tab_name: &str) -> String {
         let pane_title = if self.pane_name.is_empty() && input_mode == InputMode::RenamePane {
             "Enter name..."
         } else if self.pane_name.is_empty() {
@@ -438,7 +466,11 @@ impl Pane for TerminalPane {
         } else {
             &self.pane_name
         };
-        make_terminal_title(pane_title)
+        let command = self
+            .terminal_processes
+            .as_ref()
+            .and_then(|terminal_processes| terminal_processes.foreground_process.as_deref());
+        make_terminal_title(pane_title, tab_name, command)
     }
     fn update_name(&mut self, name: &str) {
         match name {
@@ -500,6 +532,9 @@ impl Pane for TerminalPane {
     fn dump_screen(&mut self, _client_id: ClientId, full: bool) -> String {
         self.grid.dump_screen(full)
     }
+    fn dump_screen_with_ansi_styling(&mut self, _client_id: ClientId, full: bool) -> String {
+        self.grid.dump_screen_with_ansi_styling(full)
+    }
     fn clear_screen(&mut self) {
         self.grid.clear_screen()
     }
@@ -537,11 +572,33 @@ impl Pane for TerminalPane {
         self.grid.pending_clipboard_update.take()
     }
 
+    fn drain_bell_notification(&mut self) -> bool {
+        std::mem::take(&mut self.grid.pending_bell_notification)
+    }
+
+    fn navigation_is_delegated(&self) -> bool {
+        self.grid.navigation_is_delegated
+    }
+
+    fn drain_navigation_bounce(&mut self) -> Option<Direction> {
+        self.grid.pending_navigation_bounce.take()
+    }
+
     fn start_selection(&mut self, start: &Position, _client_id: ClientId) {
         self.grid.start_selection(start);
         self.set_should_render(true);
     }
 
+    fn start_block_selection(&mut self, start: &Position, _client_id: ClientId) {
+        self.grid.start_block_selection(start);
+        self.set_should_render(true);
+    }
+
+    fn extend_selection(&mut self, to: &Position, _client_id: ClientId) {
+        self.grid.extend_selection(to);
+        self.set_should_render(true);
+    }
+
     fn update_selection(&mut self, to: &Position, _client_id: ClientId) {
         let should_scroll = self.selection_scrolled_at.elapsed()
             >= time::Duration::from_millis(SELECTION_SCROLL_INTERVAL_MS);
@@ -576,6 +633,74 @@ impl Pane for TerminalPane {
         self.grid.get_selected_text()
     }
 
+    fn scroll_to_previous_prompt(&mut self) {
+        self.grid.scroll_to_previous_prompt();
+        self.set_should_render(true);
+    }
+
+    fn scroll_to_next_prompt(&mut self) {
+        self.grid.scroll_to_next_prompt();
+        self.set_should_render(true);
+    }
+
+    fn last_command_output(&self) -> Option<String> {
+        self.grid.last_command_output()
+    }
+
+    fn link_at(&self, position: &Position) -> Option<String> {
+        self.grid.link_at(position)
+    }
+
+    fn set_hovered_link(&mut self, position: Option<&Position>) {
+        if self.grid.set_hovered_link(position) {
+            self.set_should_render(true);
+        }
+    }
+
+    fn links_in_viewport(&self) -> Vec<(Position, String)> {
+        let cwd = self
+            .terminal_processes
+            .as_ref()
+            .and_then(|terminal_processes| terminal_processes.cwd.as_ref());
+        self.grid
+            .links_in_viewport()
+            .into_iter()
+            .map(|(position, link)| {
+                let position = Position::new(
+                    (position.line() + self.get_content_y() as isize) as i32,
+                    (position.column() + self.get_content_x()) as u16,
+                );
+                let link = if link.contains("://") || link.starts_with('/') {
+                    link
+                } else if let (Some(path), Ok(home)) =
+                    (link.strip_prefix("~/"), std::env::var("HOME"))
+                {
+                    format!("{}/{}", home, path)
+                } else if let Some(cwd) = cwd {
+                    cwd.join(&link).display().to_string()
+                } else {
+                    link
+                };
+                (position, link)
+            })
+            .collect()
+    }
+
+    fn enter_copy_mode(&mut self) {
+        self.grid.enter_copy_mode();
+        self.set_should_render(true);
+    }
+
+    fn is_in_copy_mode(&self) -> bool {
+        self.grid.is_in_copy_mode()
+    }
+
+    fn handle_copy_mode_input(&mut self, input_bytes: &[u8]) -> Option<String> {
+        let selected_text = self.grid.handle_copy_mode_input(input_bytes);
+        self.set_should_render(true);
+        selected_text
+    }
+
     fn set_frame(&mut self, _frame: bool) {
         self.frame.clear();
     }
@@ -611,6 +736,29 @@ impl Pane for TerminalPane {
         self.exclude_from_sync
     }
 
+    fn set_sync_group_member(&mut self, is_sync_group_member: bool) {
+        self.is_sync_group_member = is_sync_group_member;
+    }
+
+    fn is_sync_group_member(&self) -> bool {
+        self.is_sync_group_member
+    }
+
+    fn set_pane_group_member(&mut self, is_pane_group_member: bool) {
+        self.is_pane_group_member = is_pane_group_member;
+    }
+
+    fn is_pane_group_member(&self) -> bool {
+        self.is_pane_group_member
+    }
+
+    fn update_terminal_processes(&mut self, terminal_processes: Option<TerminalProcesses>) {
+        if self.terminal_processes != terminal_processes {
+            self.terminal_processes = terminal_processes;
+            self.set_should_render(true);
+        }
+    }
+
     fn mouse_left_click(&self, position: &Position, is_held: bool) -> Option<String> {
         self.grid.mouse_left_click_signal(position, is_held)
     }
@@ -689,6 +837,10 @@ impl Pane for TerminalPane {
     fn toggle_search_wrap(&mut self) {
         self.grid.toggle_search_wrap();
     }
+    fn toggle_search_regex(&mut self) {
+        self.grid.toggle_search_regex();
+        self.set_should_render(true);
+    }
     fn clear_search(&mut self) {
         self.grid.clear_search();
         self.search_term.clear();
@@ -721,6 +873,9 @@ impl Pane for TerminalPane {
     fn set_title(&mut self, title: String) {
         self.pane_title = title;
     }
+    fn update_style(&mut self, style: Style) {
+        self.style = style;
+    }
     fn current_title(&self) -> String {
         if self.pane_name.is_empty() {
             self.grid
@@ -732,6 +887,13 @@ impl Pane for TerminalPane {
             self.pane_name.to_owned()
         }
     }
+    fn custom_title(&self) -> Option<String> {
+        if self.pane_name.is_empty() {
+            None
+        } else {
+            Some(self.pane_name.clone())
+        }
+    }
     fn exit_status(&self) -> Option<i32> {
         self.is_held
             .as_ref()
@@ -750,6 +912,13 @@ impl Pane for TerminalPane {
         self.pane_name = String::from_utf8_lossy(&buf).to_string();
         self.set_should_render(true);
     }
+    fn respawn(&mut self) -> Option<RunCommand> {
+        let (_exit_status, _is_first_run, run_command) = self.is_held.take()?;
+        self.grid.reset_terminal_state();
+        self.set_should_render(true);
+        self.remove_banner();
+        Some(run_command)
+    }
 }
 
 impl TerminalPane {
@@ -798,6 +967,9 @@ impl TerminalPane {
             prev_pane_name: pane_name,
             borderless: false,
             exclude_from_sync: false,
+            is_sync_group_member: false,
+            is_pane_group_member: false,
+            terminal_processes: None,
             fake_cursor_locations: HashSet::new(),
             search_term: String::new(),
             is_held: None,
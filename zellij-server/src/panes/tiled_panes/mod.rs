@@ -18,7 +18,7 @@ use crate::{
 };
 use stacked_panes::StackedPanes;
 use zellij_utils::{
-    data::{Direction, ModeInfo, PaneInfo, ResizeStrategy, Style},
+    data::{Direction, ModeInfo, PaneInfo, Resize, ResizeStrategy, Style},
     errors::prelude::*,
     input::{
         command::RunCommand,
@@ -67,9 +67,9 @@ pub struct TiledPanes {
     active_panes: ActivePanes,
     draw_pane_frames: bool,
     panes_to_hide: HashSet<PaneId>,
+    panes_with_activity: HashSet<PaneId>, // hidden panes that had output while fullscreen is active
     fullscreen_is_active: bool,
     senders: ThreadSenders,
-    window_title: Option<String>,
     client_id_to_boundaries: HashMap<ClientId, Boundaries>,
 }
 
@@ -103,9 +103,9 @@ impl TiledPanes {
             active_panes: ActivePanes::new(&os_api),
             draw_pane_frames,
             panes_to_hide: HashSet::new(),
+            panes_with_activity: HashSet::new(),
             fullscreen_is_active: false,
             senders,
-            window_title: None,
             client_id_to_boundaries: HashMap::new(),
         }
     }
@@ -522,6 +522,81 @@ impl TiledPanes {
             },
         }
     }
+    pub fn toggle_active_pane_stacked(&mut self, client_id: ClientId) {
+        if self.fullscreen_is_active {
+            return;
+        }
+        if let Some(active_pane_id) = self.get_active_pane_id(client_id) {
+            let active_pane_is_stacked = self
+                .panes
+                .get(&active_pane_id)
+                .map(|p| p.current_geom().is_stacked)
+                .unwrap_or(false);
+            let mut stacked_panes =
+                StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide);
+            let toggled = if active_pane_is_stacked {
+                stacked_panes.unstack(&active_pane_id)
+            } else {
+                stacked_panes.stack_column(&active_pane_id)
+            };
+            match toggled {
+                Ok(true) => {
+                    if !active_pane_is_stacked {
+                        // clients focused on the panes that are now collapsed move to the
+                        // expanded one
+                        self.expand_pane_in_stack(active_pane_id);
+                    }
+                    self.set_force_render();
+                    self.reapply_pane_frames();
+                },
+                Ok(false) => {},
+                Err(e) => log::error!("Failed to toggle stacked panes: {:?}", e),
+            }
+        }
+    }
+    pub fn focus_next_pane_in_stack(&mut self, client_id: ClientId) -> bool {
+        let next_pane_id = self
+            .get_active_pane_id(client_id)
+            .and_then(|active_pane_id| {
+                StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide)
+                    .next_pane_in_stack(&active_pane_id)
+            });
+        self.focus_pane_in_stack(next_pane_id, client_id)
+    }
+    pub fn focus_previous_pane_in_stack(&mut self, client_id: ClientId) -> bool {
+        let previous_pane_id = self
+            .get_active_pane_id(client_id)
+            .and_then(|active_pane_id| {
+                StackedPanes::new_from_btreemap(&mut self.panes, &self.panes_to_hide)
+                    .previous_pane_in_stack(&active_pane_id)
+            });
+        self.focus_pane_in_stack(previous_pane_id, client_id)
+    }
+    fn focus_pane_in_stack(&mut self, pane_id: Option<PaneId>, client_id: ClientId) -> bool {
+        match pane_id {
+            Some(p) => {
+                // render previously active pane so that its frame does not remain actively
+                // colored
+                if let Some(previously_active_pane) = self
+                    .active_panes
+                    .get(&client_id)
+                    .copied()
+                    .and_then(|active_pane_id| self.panes.get_mut(&active_pane_id))
+                {
+                    previously_active_pane.set_should_render(true);
+                    previously_active_pane.render_full_viewport();
+                }
+                self.focus_pane(p, client_id);
+                self.set_pane_active_at(p);
+                // focusing a pane in a stack expands it, which changes the geometry of the
+                // panes in the stack
+                self.focus_pane_for_all_clients(p);
+                self.reapply_pane_frames();
+                true
+            },
+            None => false,
+        }
+    }
     pub fn focus_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         if self.panes_to_hide.contains(&pane_id) {
             // this means there is a fullscreen pane that is not the current pane, let's unset it
@@ -627,19 +702,33 @@ impl TiledPanes {
         }
         self.reset_boundaries();
     }
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        for pane in self.panes.values_mut() {
+            pane.update_style(style);
+        }
+        self.set_force_render();
+    }
     pub fn has_active_panes(&self) -> bool {
         !self.active_panes.is_empty()
     }
     pub fn has_panes(&self) -> bool {
         !self.panes.is_empty()
     }
-    pub fn render(&mut self, output: &mut Output, floating_panes_are_visible: bool) -> Result<()> {
+    pub fn render(
+        &mut self,
+        output: &mut Output,
+        floating_panes_are_visible: bool,
+        tab_name: &str,
+        terminal_title: &mut Option<String>,
+    ) -> Result<()> {
         let err_context = || "failed to render tiled panes";
 
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
         let multiple_users_exist_in_session = { self.connected_clients_in_app.borrow().len() > 1 };
         let mut client_id_to_boundaries: HashMap<ClientId, Boundaries> = HashMap::new();
+        let hidden_panes_with_activity = self.panes_with_activity.len();
         let active_panes = if floating_panes_are_visible {
             HashMap::new()
         } else {
@@ -663,6 +752,9 @@ impl TiledPanes {
                     stacked_pane_ids_over_flexible_pane.contains(&pane.pid());
                 let should_draw_pane_frames = self.draw_pane_frames;
                 let pane_is_stacked = pane.current_geom().is_stacked;
+                // the only pane in the viewport while fullscreen is active is the fullscreen one
+                let pane_is_fullscreen =
+                    self.fullscreen_is_active && is_inside_viewport(&*self.viewport.borrow(), pane);
                 let mut pane_contents_and_ui = PaneContentsAndUi::new(
                     pane,
                     output,
@@ -673,6 +765,12 @@ impl TiledPanes {
                     pane_is_stacked_under,
                     pane_is_stacked_over,
                     should_draw_pane_frames,
+                    false,
+                    if pane_is_fullscreen {
+                        hidden_panes_with_activity
+                    } else {
+                        0
+                    },
                 );
                 for client_id in &connected_clients {
                     let client_mode = self
@@ -722,7 +820,8 @@ impl TiledPanes {
                     pane_contents_and_ui.render_terminal_title_if_needed(
                         *client_id,
                         client_mode,
-                        &mut self.window_title,
+                        tab_name,
+                        terminal_title,
                     );
                     // this is done for panes that don't have their own cursor (eg. panes of
                     // another user)
@@ -884,6 +983,85 @@ impl TiledPanes {
         Ok(())
     }
 
+    pub fn pane_has_neighbors_in_direction(
+        &mut self,
+        pane_id: PaneId,
+        direction: Direction,
+    ) -> bool {
+        let pane_grid = TiledPaneGrid::new(
+            &mut self.panes,
+            &self.panes_to_hide,
+            *self.display_area.borrow(),
+            *self.viewport.borrow(),
+        );
+        pane_grid
+            .pane_ids_directly_next_to(&pane_id, &direction)
+            .map(|pane_ids| !pane_ids.is_empty())
+            .unwrap_or(false)
+    }
+    /// Moves the border of the pane on its `direction` side by `change_by` cells, positive values
+    /// move it outwards (growing the pane) and negative values inwards (shrinking it)
+    ///
+    /// Returns true if the pane was resized
+    pub fn resize_pane_with_mouse(
+        &mut self,
+        pane_id: PaneId,
+        direction: Direction,
+        change_by: isize,
+    ) -> Result<bool> {
+        let err_context = || {
+            format!("failed to resize pane {pane_id:?} {direction} by {change_by} with the mouse")
+        };
+
+        let total_cells = if direction.is_horizontal() {
+            self.display_area.borrow().cols
+        } else {
+            self.display_area.borrow().rows
+        };
+        if change_by == 0 || total_cells == 0 {
+            return Ok(false);
+        }
+        let geom_before_resize = self.panes.get(&pane_id).map(|p| p.position_and_size());
+        let resize = if change_by > 0 {
+            Resize::Increase
+        } else {
+            Resize::Decrease
+        };
+        let strategy = ResizeStrategy {
+            resize,
+            direction: Some(direction),
+            invert_on_boundaries: false,
+        };
+        // pane sizes are kept as percentages of the display area
+        let change_by_percent = change_by.unsigned_abs() as f64 / total_cells as f64 * 100.0;
+        {
+            let mut pane_grid = TiledPaneGrid::new(
+                &mut self.panes,
+                &self.panes_to_hide,
+                *self.display_area.borrow(),
+                *self.viewport.borrow(),
+            );
+            match pane_grid.change_pane_size(
+                &pane_id,
+                &strategy,
+                (change_by_percent, change_by_percent),
+            ) {
+                Ok(_) => {},
+                Err(err) => match err.downcast_ref::<ZellijError>() {
+                    Some(ZellijError::PaneSizeUnchanged)
+                    | Some(ZellijError::CantResizeFixedPanes { .. }) => return Ok(false),
+                    _ => return Err(err).with_context(err_context),
+                },
+            }
+        }
+        for pane in self.panes.values_mut() {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        self.reset_boundaries();
+        Ok(self.panes.get(&pane_id).map(|p| p.position_and_size()) != geom_before_resize)
+    }
+
     pub fn focus_next_pane(&mut self, client_id: ClientId) {
         let connected_clients: Vec<ClientId> =
             { self.connected_clients.borrow().iter().copied().collect() };
@@ -1479,6 +1657,56 @@ impl TiledPanes {
             }
         }
     }
+    pub fn swap_pane_positions(&mut self, pane_id: PaneId, other_pane_id: PaneId) -> Result<()> {
+        let err_context =
+            || format!("failed to swap the positions of panes {pane_id:?} and {other_pane_id:?}");
+
+        if pane_id == other_pane_id {
+            return Ok(());
+        }
+        let pane = self
+            .panes
+            .get(&pane_id)
+            .with_context(|| format!("no pane with id {pane_id:?}"))
+            .with_context(err_context)?;
+        let prev_geom = pane.position_and_size();
+        let prev_geom_override = pane.geom_override();
+
+        let other_pane = self
+            .panes
+            .get_mut(&other_pane_id)
+            .with_context(|| format!("no pane with id {other_pane_id:?}"))
+            .with_context(err_context)?;
+        let next_geom = other_pane.position_and_size();
+        let next_geom_override = other_pane.geom_override();
+        other_pane.set_geom(prev_geom);
+        if let Some(geom) = prev_geom_override {
+            other_pane.set_geom_override(geom);
+        }
+        resize_pty!(
+            other_pane,
+            self.os_api,
+            self.senders,
+            self.character_cell_size
+        )
+        .with_context(err_context)?;
+        other_pane.set_should_render(true);
+
+        let pane = self
+            .panes
+            .get_mut(&pane_id)
+            .with_context(|| format!("no pane with id {pane_id:?}"))
+            .with_context(err_context)?;
+        pane.set_geom(next_geom);
+        if let Some(geom) = next_geom_override {
+            pane.set_geom_override(geom);
+        }
+        resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+            .with_context(err_context)?;
+        pane.set_should_render(true);
+        self.set_pane_frames(self.draw_pane_frames);
+        Ok(())
+    }
     pub fn move_clients_out_of_pane(&mut self, pane_id: PaneId) {
         let active_panes: Vec<(ClientId, PaneId)> = self
             .active_panes
@@ -1525,9 +1753,11 @@ impl TiledPanes {
     }
     pub fn extract_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
         self.reset_boundaries();
+        self.panes_with_activity.remove(&pane_id);
         self.panes.remove(&pane_id)
     }
     pub fn remove_pane(&mut self, pane_id: PaneId) -> Option<Box<dyn Pane>> {
+        self.panes_with_activity.remove(&pane_id);
         let mut pane_grid = TiledPaneGrid::new(
             &mut self.panes,
             &self.panes_to_hide,
@@ -1592,6 +1822,7 @@ impl TiledPanes {
                 viewport_pane.reset_size_and_position_override();
             }
             self.panes_to_hide.clear();
+            self.panes_with_activity.clear();
             let active_terminal = self.get_pane_mut(active_pane_id).unwrap();
             active_terminal.reset_size_and_position_override();
             self.set_force_render();
@@ -1707,6 +1938,14 @@ impl TiledPanes {
     }
     pub fn remove_from_hidden_panels(&mut self, pid: PaneId) {
         self.panes_to_hide.remove(&pid);
+        self.panes_with_activity.remove(&pid);
+    }
+    /// Note that a pane produced output, so that the fullscreen pane can indicate it if this pane
+    /// is hidden behind it
+    pub fn mark_pane_activity(&mut self, pane_id: PaneId) {
+        if self.fullscreen_is_active && self.panes_to_hide.contains(&pane_id) {
+            self.panes_with_activity.insert(pane_id);
+        }
     }
     pub fn unfocus_all_panes(&mut self) {
         self.active_panes.unfocus_all_panes(&mut self.panes);
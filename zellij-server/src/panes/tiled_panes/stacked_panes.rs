@@ -1,4 +1,7 @@
-use crate::{panes::PaneId, tab::Pane};
+use crate::{
+    panes::PaneId,
+    tab::{Pane, MIN_TERMINAL_HEIGHT},
+};
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
@@ -162,6 +165,134 @@ impl<'a> StackedPanes<'a> {
             .map(|(pane_id, _pane_position)| *pane_id)
             .collect())
     }
+    pub fn next_pane_in_stack(&self, pane_id: &PaneId) -> Option<PaneId> {
+        // the pane under this one, or the topmost pane if this one is the lowest
+        let all_stacked_pane_positions = self.positions_in_stack(pane_id).ok()?;
+        let position_of_pane = all_stacked_pane_positions
+            .iter()
+            .position(|(pid, _p)| pid == pane_id)?;
+        all_stacked_pane_positions
+            .iter()
+            .cycle()
+            .nth(position_of_pane + 1)
+            .map(|(pid, _p)| *pid)
+            .filter(|pid| pid != pane_id)
+    }
+    pub fn previous_pane_in_stack(&self, pane_id: &PaneId) -> Option<PaneId> {
+        // the pane over this one, or the lowest pane if this one is the topmost
+        let all_stacked_pane_positions = self.positions_in_stack(pane_id).ok()?;
+        let position_of_pane = all_stacked_pane_positions
+            .iter()
+            .position(|(pid, _p)| pid == pane_id)?;
+        all_stacked_pane_positions
+            .iter()
+            .rev()
+            .cycle()
+            .nth(all_stacked_pane_positions.len() - position_of_pane)
+            .map(|(pid, _p)| *pid)
+            .filter(|pid| pid != pane_id)
+    }
+    pub fn unstack(&mut self, pane_id: &PaneId) -> Result<bool> {
+        // spreads the panes of the stack evenly over the space of the stack, returns false if
+        // there is not enough room for all of them
+        let err_context = || format!("Failed to unstack panes");
+        let all_stacked_pane_positions =
+            self.positions_in_stack(pane_id).with_context(err_context)?;
+        let stack_geom = self
+            .position_and_size_of_stack(pane_id)
+            .with_context(err_context)?;
+        let stack_percent = stack_geom.rows.as_percent().with_context(err_context)?;
+        let stack_height = stack_geom.rows.as_usize();
+        let pane_count = all_stacked_pane_positions.len();
+        if stack_height / pane_count < MIN_TERMINAL_HEIGHT {
+            return Ok(false);
+        }
+        let mut panes = self.panes.borrow_mut();
+        let mut y = stack_geom.y;
+        for (i, (pid, mut pane_geom)) in all_stacked_pane_positions.into_iter().enumerate() {
+            // the first panes get the rows that do not divide evenly
+            let rows = stack_height / pane_count + usize::from(i < stack_height % pane_count);
+            pane_geom.y = y;
+            pane_geom.rows = Dimension::percent(stack_percent * rows as f64 / stack_height as f64);
+            pane_geom.rows.set_inner(rows);
+            pane_geom.is_stacked = false;
+            panes
+                .get_mut(&pid)
+                .with_context(err_context)?
+                .set_geom(pane_geom);
+            y += rows;
+        }
+        Ok(true)
+    }
+    pub fn stack_column(&mut self, pane_id: &PaneId) -> Result<bool> {
+        // stacks the pane along with the panes of the same width directly above and below it,
+        // the pane itself is the expanded one, returns false if there is nothing to stack
+        let err_context = || format!("Failed to stack panes");
+        let mut panes = self.panes.borrow_mut();
+        let pane_geom = panes
+            .get(pane_id)
+            .with_context(err_context)?
+            .position_and_size();
+        let mut column: Vec<(PaneId, PaneGeom)> = panes
+            .iter()
+            .map(|(pid, p)| (*pid, p.position_and_size()))
+            .filter(|(_pid, p)| p.x == pane_geom.x && p.cols == pane_geom.cols)
+            .collect();
+        if column.iter().any(|(_pid, p)| p.is_stacked) {
+            // stacks are told apart by their position and width, so this would join them
+            return Ok(false);
+        }
+        column.sort_by(|(_a_pid, a), (_b_pid, b)| a.y.cmp(&b.y));
+        let position_of_pane = column
+            .iter()
+            .position(|(pid, _p)| pid == pane_id)
+            .with_context(err_context)?;
+        let mut first = position_of_pane;
+        while first > 0
+            && column[first - 1].1.y + column[first - 1].1.rows.as_usize() == column[first].1.y
+        {
+            first -= 1;
+        }
+        let mut last = position_of_pane;
+        while last + 1 < column.len()
+            && column[last].1.y + column[last].1.rows.as_usize() == column[last + 1].1.y
+        {
+            last += 1;
+        }
+        let panes_to_stack = &column[first..=last];
+        if panes_to_stack.len() < 2 || panes_to_stack.iter().any(|(_pid, p)| p.rows.is_fixed()) {
+            return Ok(false);
+        }
+        let stack_height: usize = panes_to_stack
+            .iter()
+            .map(|(_pid, p)| p.rows.as_usize())
+            .sum();
+        let stack_percent: f64 = panes_to_stack
+            .iter()
+            .filter_map(|(_pid, p)| p.rows.as_percent())
+            .sum();
+        let expanded_pane_height = stack_height - (panes_to_stack.len() - 1);
+        if expanded_pane_height < MIN_TERMINAL_HEIGHT {
+            return Ok(false);
+        }
+        let mut y = panes_to_stack[0].1.y;
+        for (pid, mut geom) in panes_to_stack.iter().copied() {
+            geom.y = y;
+            geom.is_stacked = true;
+            if pid == *pane_id {
+                geom.rows = Dimension::percent(stack_percent);
+                geom.rows.set_inner(expanded_pane_height);
+            } else {
+                geom.rows = Dimension::fixed(1);
+            }
+            y += geom.rows.as_usize();
+            panes
+                .get_mut(&pid)
+                .with_context(err_context)?
+                .set_geom(geom);
+        }
+        Ok(true)
+    }
     pub fn flexible_pane_id_in_stack(&self, pane_id_in_stack: &PaneId) -> Option<PaneId> {
         let all_stacked_pane_positions = self.positions_in_stack(pane_id_in_stack).ok()?;
         all_stacked_pane_positions
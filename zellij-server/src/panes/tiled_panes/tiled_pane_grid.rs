@@ -629,7 +629,11 @@ impl<'a> TiledPaneGrid<'a> {
     ///
     /// The vector is empty for example if the given pane (`id`) is at the boundary of the viewport
     /// already.
-    fn pane_ids_directly_next_to(&self, id: &PaneId, direction: &Direction) -> Result<Vec<PaneId>> {
+    pub fn pane_ids_directly_next_to(
+        &self,
+        id: &PaneId,
+        direction: &Direction,
+    ) -> Result<Vec<PaneId>> {
         let err_context = || format!("failed to find panes {direction} from pane {id:?}");
 
         let mut ids = vec![];
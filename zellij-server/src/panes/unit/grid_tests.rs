@@ -1,11 +1,19 @@
 use super::super::Grid;
-use crate::panes::grid::SixelImageStore;
+use crate::panes::grid::{Row, SixelImageStore};
 use crate::panes::link_handler::LinkHandler;
+use crate::panes::scrollback_spill::ScrollbackSpill;
+use crate::panes::terminal_character::{AnsiCode, CharacterStyles, TerminalCharacter};
 use ::insta::assert_snapshot;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::rc::Rc;
-use zellij_utils::{data::Palette, pane_size::SizeInPixels, position::Position, vte};
+use zellij_utils::{
+    data::{Direction, Palette},
+    pane_size::SizeInPixels,
+    position::Position,
+    vte,
+};
 
 use std::fmt::Write;
 
@@ -1303,6 +1311,341 @@ fn copy_selected_text_from_lines_below() {
     );
 }
 
+#[test]
+fn double_click_selects_word() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::new();
+    for byte in "ls /tmp/foo-bar.txt --all".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    assert_eq!(grid.get_selected_text().unwrap(), "foo");
+}
+
+#[test]
+fn double_click_selects_word_with_configured_word_characters() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::from("-./");
+    for byte in "ls /tmp/foo-bar.txt --all".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    assert_eq!(grid.get_selected_text().unwrap(), "/tmp/foo-bar.txt");
+}
+
+#[test]
+fn shift_click_extends_selection() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::new();
+    for byte in "ls /tmp/foo-bar.txt --all".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.start_selection(&Position::new(0, 3));
+    grid.end_selection(&Position::new(0, 7));
+    grid.extend_selection(&Position::new(0, 19));
+    grid.end_selection(&Position::new(0, 19));
+    assert_eq!(grid.get_selected_text().unwrap(), "/tmp/foo-bar.txt");
+}
+
+#[test]
+fn shift_click_extends_word_selection_by_words() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::new();
+    for byte in "ls /tmp/foo-bar.txt --all".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    grid.start_selection(&Position::new(0, 10));
+    grid.end_selection(&Position::new(0, 10));
+    grid.extend_selection(&Position::new(0, 23));
+    grid.end_selection(&Position::new(0, 23));
+    assert_eq!(grid.get_selected_text().unwrap(), "foo-bar.txt --all");
+}
+
+#[test]
+fn alt_drag_selects_block_of_columns() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    for byte in "one   1   a\r\ntwo   22  b\r\nthree 333 c".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    // dragging from the top right to the bottom left corner of the rectangle
+    grid.start_block_selection(&Position::new(0, 9));
+    grid.update_selection(&Position::new(1, 7));
+    grid.update_selection(&Position::new(2, 6));
+    grid.end_selection(&Position::new(2, 6));
+    assert_eq!(grid.get_selected_text().unwrap(), "1\n22\n333");
+}
+
+#[test]
+fn osc8_link_at_position() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let content = "see \u{1b}]8;;https://zellij.dev\u{1b}\\the docs\u{1b}]8;;\u{1b}\\ for more";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    assert_eq!(grid.link_at(&Position::new(0, 2)), None);
+    assert_eq!(
+        grid.link_at(&Position::new(0, 4)),
+        Some(String::from("https://zellij.dev"))
+    );
+    assert_eq!(
+        grid.link_at(&Position::new(0, 11)),
+        Some(String::from("https://zellij.dev"))
+    );
+    assert_eq!(grid.link_at(&Position::new(0, 12)), None);
+    assert!(grid.set_hovered_link(Some(&Position::new(0, 6))));
+    assert!(!grid.set_hovered_link(Some(&Position::new(0, 8))));
+    assert!(grid.set_hovered_link(None));
+}
+
+#[test]
+fn links_in_viewport() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        80,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let content = "see https://zellij.dev/documentation. or \u{1b}]8;;https://github.com/zellij-org\u{1b}\\the repo\u{1b}]8;;\u{1b}\\\n\rsrc/main.rs:12: error";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    assert_eq!(
+        grid.links_in_viewport(),
+        vec![
+            (
+                Position::new(0, 4),
+                String::from("https://zellij.dev/documentation")
+            ),
+            (
+                Position::new(0, 41),
+                String::from("https://github.com/zellij-org")
+            ),
+            (Position::new(1, 0), String::from("src/main.rs")),
+        ]
+    );
+}
+
+#[test]
+fn double_click_and_drag_extends_selection_by_words() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::new();
+    for byte in "one two three four".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.start_selection(&Position::new(0, 9));
+    grid.end_selection(&Position::new(0, 9));
+    grid.start_selection(&Position::new(0, 9));
+    grid.update_selection(&Position::new(0, 5));
+    grid.end_selection(&Position::new(0, 5));
+    assert_eq!(grid.get_selected_text().unwrap(), "two three");
+}
+
+#[test]
+fn triple_click_selects_wrapped_line() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        10,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    for byte in "first\n\rsecond line wraps\n\rthird".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    for _ in 0..3 {
+        grid.start_selection(&Position::new(2, 3));
+        grid.end_selection(&Position::new(2, 3));
+    }
+    assert_eq!(grid.get_selected_text().unwrap(), "second line wraps");
+}
+
+#[test]
+fn copy_mode_selects_and_copies_with_the_keyboard() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.word_characters = String::new();
+    for byte in "one two three\n\rfour five six\n\r".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.enter_copy_mode();
+    assert!(grid.is_in_copy_mode());
+    for keys in ["k", "k", "w", "v", "j", "w"] {
+        assert_eq!(grid.handle_copy_mode_input(keys.as_bytes()), None);
+    }
+    assert_eq!(
+        grid.handle_copy_mode_input(b"y"),
+        Some(String::from("two three\nfour f"))
+    );
+    assert!(!grid.is_in_copy_mode());
+    assert!(grid.selection.is_empty());
+}
+
+#[test]
+fn copy_mode_block_selection() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    for byte in "abcdef\n\rghijkl\n\rmnopqr".as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+
+    grid.enter_copy_mode();
+    for keys in ["g", "l", "\u{16}", "j", "j", "l", "l"] {
+        grid.handle_copy_mode_input(keys.as_bytes());
+    }
+    assert_eq!(
+        grid.handle_copy_mode_input(b"\r"),
+        Some(String::from("bcd\nhij\nnop"))
+    );
+}
+
 /*
  * These tests below are general compatibility tests for non-trivial scenarios running in the terminal.
  * They use fake TTY input replicated from these scenarios.
@@ -2768,3 +3111,305 @@ pub fn cursor_hide_persists_through_alternate_screen() {
         "Cursor still shown away from alternate screen"
     );
 }
+
+#[test]
+pub fn copy_last_command_output_with_prompt_marks() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let content = "\u{1b}]133;A\u{7}$ \u{1b}]133;B\u{7}echo one\r\n\u{1b}]133;C\u{7}one\r\n\u{1b}]133;D;0\u{7}\
+                   \u{1b}]133;A\u{7}$ \u{1b}]133;B\u{7}ls\r\n\u{1b}]133;C\u{7}a\r\nb\r\n\u{1b}]133;D;0\u{7}\
+                   \u{1b}]133;A\u{7}$ ";
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    assert_eq!(grid.last_command_output(), Some("a\nb".to_owned()));
+}
+
+#[test]
+pub fn scroll_to_previous_and_next_prompt() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut content = String::new();
+    for command in 1..=3 {
+        write!(
+            content,
+            "\u{1b}]133;A\u{7}$ cmd{}\r\n\u{1b}]133;C\u{7}",
+            command
+        )
+        .unwrap();
+        for line in 1..=4 {
+            write!(content, "out{}-{}\r\n", command, line).unwrap();
+        }
+    }
+    content.push_str("\u{1b}]133;A\u{7}$ ");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let top_line = |grid: &Grid| -> String {
+        grid.viewport[0]
+            .columns
+            .iter()
+            .map(|c| c.character)
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    };
+
+    grid.scroll_to_previous_prompt();
+    assert_eq!(top_line(&grid), "$ cmd3");
+    grid.scroll_to_previous_prompt();
+    assert_eq!(top_line(&grid), "$ cmd2");
+    grid.scroll_to_next_prompt();
+    assert_eq!(top_line(&grid), "$ cmd3");
+    grid.scroll_to_next_prompt();
+    assert!(
+        !grid.is_scrolled,
+        "scrolled back to the bottom past the last prompt"
+    );
+}
+
+#[test]
+pub fn application_claims_and_bounces_back_navigation() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        10,
+        50,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut feed = |grid: &mut Grid, content: &str| {
+        for byte in content.as_bytes() {
+            vte_parser.advance(grid, *byte);
+        }
+    };
+    feed(&mut grid, "\u{1b}]7711;claim\u{1b}\\");
+    assert!(grid.navigation_is_delegated, "navigation claimed");
+    feed(&mut grid, "\u{1b}]7711;move;left\u{7}");
+    assert_eq!(grid.pending_navigation_bounce, Some(Direction::Left));
+    feed(&mut grid, "\u{1b}]7711;release\u{7}");
+    assert!(!grid.navigation_is_delegated, "navigation released");
+    feed(&mut grid, "\u{1b}]7711;claim\u{7}\u{1b}c");
+    assert!(
+        !grid.navigation_is_delegated,
+        "navigation released on terminal reset"
+    );
+}
+
+#[test]
+fn absolute_position_in_scrollback_of_wrapped_cursor_line_when_scrolled_up() {
+    let mut vte_parser = vte::Parser::new();
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        5,
+        20,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    let mut content = String::new();
+    for i in 1..=20 {
+        write!(content, "line {}\r\n", i).unwrap();
+    }
+    content.push_str("the cursor line wraps around the pane");
+    for byte in content.as_bytes() {
+        vte_parser.advance(&mut grid, *byte);
+    }
+    let cursor_line = "the cursor line wraps around the pane";
+    assert_eq!(grid.absolute_position_in_scrollback(), 20);
+    assert_eq!(grid.dump_screen(true).lines().nth(20), Some(cursor_line));
+
+    // first only the wrapped part of the cursor line is below the viewport, then the whole cursor
+    // line and the one above it
+    for scrolled_lines in [1, 3] {
+        while grid.lines_below.len() < scrolled_lines {
+            grid.scroll_up_one_line();
+        }
+        assert_eq!(
+            grid.absolute_position_in_scrollback(),
+            20,
+            "scrolling {} lines up does not move the cursor",
+            scrolled_lines
+        );
+        let dump = grid.dump_screen(true);
+        assert_eq!(
+            dump.lines().count(),
+            21,
+            "wrapped lines are dumped unwrapped"
+        );
+        assert_eq!(dump.lines().nth(20), Some(cursor_line));
+    }
+}
+
+fn grid_spilling_scrollback_to_disk(rows: usize, columns: usize, spill_limit: usize) -> Grid {
+    let sixel_image_store = Rc::new(RefCell::new(SixelImageStore::default()));
+    let terminal_emulator_color_codes = Rc::new(RefCell::new(HashMap::new()));
+    let debug = false;
+    let mut grid = Grid::new(
+        rows,
+        columns,
+        Rc::new(RefCell::new(Palette::default())),
+        terminal_emulator_color_codes,
+        Rc::new(RefCell::new(LinkHandler::new())),
+        Rc::new(RefCell::new(None)),
+        sixel_image_store,
+        debug,
+    );
+    grid.scrollback_spill = Some(Rc::new(RefCell::new(
+        ScrollbackSpill::new(spill_limit).unwrap(),
+    )));
+    grid
+}
+
+fn write_lines(grid: &mut Grid, first_line: &str, line_count: usize) {
+    let mut vte_parser = vte::Parser::new();
+    let mut content = format!("{}\r\n", first_line);
+    for i in 2..=line_count {
+        write!(content, "line {}\r\n", i).unwrap();
+    }
+    for byte in content.as_bytes() {
+        vte_parser.advance(grid, *byte);
+    }
+}
+
+fn row_text(row: &Row) -> String {
+    row.columns.iter().map(|c| c.character).collect()
+}
+
+fn scroll_to_top(grid: &mut Grid) {
+    while !grid.lines_above.is_empty() || grid.spilled_line_count() > 0 {
+        grid.scroll_up_one_line();
+    }
+}
+
+#[test]
+fn lines_beyond_scroll_buffer_size_are_spilled_and_paged_back_in_when_scrolling_up() {
+    let mut grid = grid_spilling_scrollback_to_disk(10, 50, 1000);
+    // the scroll buffer size is the default 10000 lines in tests
+    write_lines(&mut grid, "line 1", 10_100);
+    let spilled_line_count = grid.spilled_line_count();
+    assert!(spilled_line_count > 0, "lines were spilled to disk");
+    assert_eq!(grid.lines_above.len(), 10_000);
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (0, 10_000 + spilled_line_count),
+        "spilled lines are part of the scrollback"
+    );
+    let spilled_lines = grid.read_spilled_lines();
+    assert_eq!(spilled_lines.len(), spilled_line_count);
+    assert_eq!(row_text(&spilled_lines[0]), "line 1");
+    assert_eq!(
+        row_text(&spilled_lines[spilled_line_count - 1]),
+        format!("line {}", spilled_line_count),
+        "lines are spilled in order"
+    );
+
+    scroll_to_top(&mut grid);
+    assert_eq!(
+        grid.spilled_line_count(),
+        0,
+        "all spilled lines were paged in"
+    );
+    assert_eq!(grid.dump_screen(false).lines().next(), Some("line 1"));
+    assert_eq!(
+        grid.scrollback_position_and_length(),
+        (10_000 + spilled_line_count, 10_000 + spilled_line_count)
+    );
+}
+
+#[test]
+fn search_finds_spilled_lines() {
+    let mut grid = grid_spilling_scrollback_to_disk(10, 50, 1000);
+    write_lines(&mut grid, "the needle in the haystack", 10_100);
+    assert!(grid.spilled_line_count() > 0);
+    grid.set_search_string("needle");
+    assert!(
+        !grid.search_results.selections.is_empty(),
+        "search scrolled up to the spilled line"
+    );
+    assert_eq!(
+        grid.dump_screen(false).lines().next(),
+        Some("the needle in the haystack")
+    );
+}
+
+#[test]
+fn oldest_spilled_lines_are_discarded_beyond_the_spill_limit() {
+    let mut grid = grid_spilling_scrollback_to_disk(10, 50, 5);
+    write_lines(&mut grid, "line 1", 10_100);
+    assert_eq!(grid.spilled_line_count(), 5);
+    // 10000 lines above the viewport, 9 lines and the empty cursor line in it and the 5 lines
+    // spilled right before the ones above the viewport
+    let first_kept_line = 10_100 - 9 - 10_000 - 5 + 1;
+    let spilled_lines = grid.read_spilled_lines();
+    assert_eq!(
+        row_text(&spilled_lines[0]),
+        format!("line {}", first_kept_line)
+    );
+    assert_eq!(
+        row_text(&grid.lines_above[0]),
+        format!("line {}", first_kept_line + 5)
+    );
+    scroll_to_top(&mut grid);
+    assert_eq!(
+        grid.dump_screen(false).lines().next().map(|l| l.to_owned()),
+        Some(format!("line {}", first_kept_line))
+    );
+}
+
+#[test]
+fn spilled_rows_keep_their_styles_and_widths() {
+    let mut scrollback_spill = ScrollbackSpill::new(10).unwrap();
+    let red = CharacterStyles::new().foreground(Some(AnsiCode::ColorIndex(1)));
+    let mut columns: VecDeque<TerminalCharacter> =
+        "plain red 漢".chars().map(TerminalCharacter::new).collect();
+    for character in columns.iter_mut().skip(6).take(3) {
+        character.styles = red;
+    }
+    let mut row = Row::from_columns(columns.clone());
+    row.is_canonical = true;
+    scrollback_spill
+        .push(Row::from_columns(VecDeque::new()))
+        .unwrap();
+    scrollback_spill.push(row).unwrap();
+    assert_eq!(scrollback_spill.read_all().unwrap()[1].columns, columns);
+    let paged_in_row = scrollback_spill.pop().unwrap().unwrap();
+    assert_eq!(paged_in_row.columns, columns);
+    assert!(paged_in_row.is_canonical);
+    assert_eq!(scrollback_spill.len(), 1);
+}
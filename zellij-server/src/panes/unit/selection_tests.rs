@@ -43,6 +43,7 @@ fn contains() {
         start: Position::new(10, 5),
         end: Position::new(40, 20),
         active: false,
+        block: false,
     };
 
     let test_cases = vec![
@@ -87,12 +88,30 @@ fn contains() {
     }
 }
 
+#[test]
+fn contains_block() {
+    let selection = Selection {
+        start: Position::new(10, 5),
+        end: Position::new(40, 20),
+        active: false,
+        block: true,
+    };
+
+    assert!(selection.contains(10, 5));
+    assert!(selection.contains(20, 19));
+    assert!(selection.contains(40, 5));
+    assert!(!selection.contains(20, 4));
+    assert!(!selection.contains(20, 20));
+    assert!(!selection.contains(41, 10));
+}
+
 #[test]
 fn sorted() {
     let selection = Selection {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
         active: false,
+        block: false,
     };
     let sorted_selection = selection.sorted();
     assert_eq!(selection.start, sorted_selection.start);
@@ -102,6 +121,7 @@ fn sorted() {
         start: Position::new(10, 2),
         end: Position::new(1, 1),
         active: false,
+        block: false,
     };
     let sorted_selection = selection.sorted();
     assert_eq!(selection.end, sorted_selection.start);
@@ -114,6 +134,7 @@ fn line_indices() {
         start: Position::new(1, 1),
         end: Position::new(10, 2),
         active: false,
+        block: false,
     };
 
     assert_eq!(selection.line_indices(), (1..=10))
@@ -127,6 +148,7 @@ fn move_up_inactive() {
         start,
         end,
         active: false,
+        block: false,
     };
 
     inactive_selection.move_up(2);
@@ -145,6 +167,7 @@ fn move_up_active() {
         start,
         end,
         active: true,
+        block: false,
     };
 
     inactive_selection.move_up(2);
@@ -160,6 +183,7 @@ fn move_down_inactive() {
         start,
         end,
         active: false,
+        block: false,
     };
 
     inactive_selection.move_down(2);
@@ -178,6 +202,7 @@ fn move_down_active() {
         start,
         end,
         active: true,
+        block: false,
     };
 
     inactive_selection.move_down(2);
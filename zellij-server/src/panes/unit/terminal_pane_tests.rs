@@ -692,3 +692,45 @@ pub fn frameless_pane_position_is_on_frame() {
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 130)));
     assert!(!terminal_pane.position_is_on_frame(&Position::new(30, 131)));
 }
+
+#[test]
+pub fn pane_frame_title_template() {
+    use crate::os_input_output::TerminalProcesses;
+    use crate::ui::pane_boundaries_frame::{FrameParams, PaneFrame};
+
+    let frame_params = FrameParams {
+        focused_client: None,
+        is_main_client: false,
+        other_focused_clients: vec![],
+        style: Style::default(),
+        color: None,
+        other_cursors_exist_in_session: false,
+        pane_is_stacked_under: false,
+        pane_is_stacked_over: false,
+        should_draw_pane_frames: true,
+        show_pin_indication: false,
+        hidden_panes_with_activity: 0,
+        pane_is_pinned: false,
+    };
+    let terminal_processes = TerminalProcesses {
+        child_pids: vec![4242, 4243],
+        foreground_process: Some("vim".to_owned()),
+        cwd: Some("/home/user/code".into()),
+        running_processes: vec![],
+    };
+    let mut frame = PaneFrame::new(
+        PaneGeom::default().into(),
+        (12, 340),
+        "{cwd} is not a placeholder here".to_owned(),
+        frame_params,
+    );
+    frame.add_exit_status(Some(1));
+    frame.apply_title_template(
+        "{command} ({pid}) – {cwd} [{exit_status}] {scroll} | {title}",
+        Some(&terminal_processes),
+    );
+    assert_eq!(
+        frame.title, "vim (4242) – /home/user/code [1] 12/340 | {cwd} is not a placeholder here",
+        "Title template placeholders replaced"
+    );
+}
@@ -6,7 +6,7 @@ mod watch_filesystem;
 mod zellij_exports;
 use log::info;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
     path::PathBuf,
 };
@@ -18,7 +18,9 @@ use crate::{pty::PtyInstruction, thread_bus::Bus, ClientId, ServerInstruction};
 use wasm_bridge::WasmBridge;
 
 use zellij_utils::{
-    data::{Event, EventType, PermissionStatus, PermissionType, PluginCapabilities},
+    data::{
+        Event, EventType, MessageToPlugin, PermissionStatus, PermissionType, PluginCapabilities,
+    },
     errors::{prelude::*, ContextType, PluginContext},
     input::{
         command::TerminalAction,
@@ -38,6 +40,7 @@ pub type PluginId = u32;
 pub enum PluginInstruction {
     Load(
         Option<bool>,   // should float
+        bool,           // should be opened in place
         Option<String>, // pane title
         RunPlugin,
         usize, // tab index
@@ -81,6 +84,18 @@ pub enum PluginInstruction {
         String, // serialized message
         String, // serialized payload
     ),
+    PipeMessage(MessageToPlugin, PluginId), // message, source plugin id
+    CliPipe {
+        name: Option<String>,
+        payload: Option<String>,
+        args: BTreeMap<String, String>,
+        plugin: Option<String>, // url or alias, None sends to all running plugins
+        client_id: ClientId,
+    },
+    CliPipeMessageDelivered(ClientId),
+    BlockCliPipeInput(String, PluginId),    // pipe name
+    UnblockCliPipeInput(String, PluginId),  // pipe name
+    ChangePluginHostDir(PathBuf, PluginId), // new host folder
     PluginSubscribedToEvents(PluginId, ClientId, HashSet<EventType>),
     PermissionRequestResult(
         PluginId,
@@ -89,6 +104,7 @@ pub enum PluginInstruction {
         PermissionStatus,
         Option<PathBuf>,
     ),
+    PluginFileChanged(PathBuf),
     Exit,
 }
 
@@ -112,12 +128,21 @@ impl From<&PluginInstruction> for PluginContext {
                 PluginContext::PostMessageToPluginWorker
             },
             PluginInstruction::PostMessageToPlugin(..) => PluginContext::PostMessageToPlugin,
+            PluginInstruction::PipeMessage(..) => PluginContext::PipeMessage,
+            PluginInstruction::CliPipe { .. } => PluginContext::CliPipe,
+            PluginInstruction::CliPipeMessageDelivered(..) => {
+                PluginContext::CliPipeMessageDelivered
+            },
+            PluginInstruction::BlockCliPipeInput(..) => PluginContext::BlockCliPipeInput,
+            PluginInstruction::UnblockCliPipeInput(..) => PluginContext::UnblockCliPipeInput,
+            PluginInstruction::ChangePluginHostDir(..) => PluginContext::ChangePluginHostDir,
             PluginInstruction::PluginSubscribedToEvents(..) => {
                 PluginContext::PluginSubscribedToEvents
             },
             PluginInstruction::PermissionRequestResult(..) => {
                 PluginContext::PermissionRequestResult
             },
+            PluginInstruction::PluginFileChanged(..) => PluginContext::PluginFileChanged,
         }
     }
 }
@@ -133,6 +158,7 @@ pub(crate) fn plugin_thread_main(
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
+    watch_plugins: bool,
 ) -> Result<()> {
     info!("Wasm main thread starts");
 
@@ -150,27 +176,36 @@ pub(crate) fn plugin_thread_main(
         client_attributes,
         default_shell,
         layout.clone(),
+        watch_plugins,
     );
 
     loop {
         let (event, mut err_ctx) = bus.recv().expect("failed to receive event on channel");
         err_ctx.add_call(ContextType::Plugin((&event).into()));
         match event {
-            PluginInstruction::Load(should_float, pane_title, run, tab_index, client_id, size) => {
-                match wasm_bridge.load_plugin(&run, tab_index, size, Some(client_id)) {
-                    Ok(plugin_id) => {
-                        drop(bus.senders.send_to_screen(ScreenInstruction::AddPlugin(
-                            should_float,
-                            run,
-                            pane_title,
-                            tab_index,
-                            plugin_id,
-                        )));
-                    },
-                    Err(e) => {
-                        log::error!("Failed to load plugin: {e}");
-                    },
-                }
+            PluginInstruction::Load(
+                should_float,
+                should_be_open_in_place,
+                pane_title,
+                run,
+                tab_index,
+                client_id,
+                size,
+            ) => match wasm_bridge.load_plugin(&run, tab_index, size, None, Some(client_id)) {
+                Ok(plugin_id) => {
+                    drop(bus.senders.send_to_screen(ScreenInstruction::AddPlugin(
+                        should_float,
+                        should_be_open_in_place,
+                        run,
+                        pane_title,
+                        tab_index,
+                        plugin_id,
+                        Some(client_id),
+                    )));
+                },
+                Err(e) => {
+                    log::error!("Failed to load plugin: {e}");
+                },
             },
             PluginInstruction::Update(updates) => {
                 wasm_bridge.update_plugins(updates)?;
@@ -190,14 +225,17 @@ pub(crate) fn plugin_thread_main(
                             log::warn!("Plugin {} not found, starting it instead", run.location);
                             // we intentionally do not provide the client_id here because it belongs to
                             // the cli who spawned the command and is not an existing client_id
-                            match wasm_bridge.load_plugin(&run, tab_index, size, None) {
+                            match wasm_bridge.load_plugin(&run, tab_index, size, None, None) {
                                 Ok(plugin_id) => {
+                                    let should_be_open_in_place = false;
                                     drop(bus.senders.send_to_screen(ScreenInstruction::AddPlugin(
                                         should_float,
+                                        should_be_open_in_place,
                                         run,
                                         pane_title,
                                         tab_index,
                                         plugin_id,
+                                        None,
                                     )));
                                 },
                                 Err(e) => {
@@ -250,8 +288,13 @@ pub(crate) fn plugin_thread_main(
                 extracted_run_instructions.append(&mut extracted_floating_plugins);
                 for run_instruction in extracted_run_instructions {
                     if let Some(Run::Plugin(run)) = run_instruction {
-                        let plugin_id =
-                            wasm_bridge.load_plugin(&run, tab_index, size, Some(client_id))?;
+                        let plugin_id = wasm_bridge.load_plugin(
+                            &run,
+                            tab_index,
+                            size,
+                            cwd.clone(),
+                            Some(client_id),
+                        )?;
                         plugin_ids
                             .entry((run.location, run.configuration))
                             .or_default()
@@ -295,6 +338,30 @@ pub(crate) fn plugin_thread_main(
                 )];
                 wasm_bridge.update_plugins(updates)?;
             },
+            PluginInstruction::PipeMessage(message_to_plugin, source_plugin_id) => {
+                wasm_bridge.pipe_message(message_to_plugin, source_plugin_id)?;
+            },
+            PluginInstruction::CliPipe {
+                name,
+                payload,
+                args,
+                plugin,
+                client_id,
+            } => {
+                wasm_bridge.pipe_message_from_cli(name, payload, args, plugin, client_id)?;
+            },
+            PluginInstruction::CliPipeMessageDelivered(client_id) => {
+                wasm_bridge.cli_pipe_message_delivered(client_id)?;
+            },
+            PluginInstruction::BlockCliPipeInput(pipe_name, plugin_id) => {
+                wasm_bridge.block_cli_pipe_input(&pipe_name, plugin_id);
+            },
+            PluginInstruction::UnblockCliPipeInput(pipe_name, plugin_id) => {
+                wasm_bridge.unblock_cli_pipe_input(&pipe_name, plugin_id)?;
+            },
+            PluginInstruction::ChangePluginHostDir(new_host_dir, plugin_id) => {
+                wasm_bridge.change_plugin_host_dir(new_host_dir, plugin_id)?;
+            },
             PluginInstruction::PluginSubscribedToEvents(_plugin_id, _client_id, events) => {
                 for event in events {
                     if let EventType::FileSystemCreate
@@ -330,6 +397,9 @@ pub(crate) fn plugin_thread_main(
                 )];
                 wasm_bridge.update_plugins(updates)?;
             },
+            PluginInstruction::PluginFileChanged(path) => {
+                wasm_bridge.reload_plugins_from_file(path)?;
+            },
             PluginInstruction::Exit => {
                 wasm_bridge.cleanup();
                 break;
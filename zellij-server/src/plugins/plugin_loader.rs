@@ -60,6 +60,7 @@ pub struct PluginLoader<'a> {
     wasm_blob_on_hd: Option<(Vec<u8>, PathBuf)>,
     path_to_default_shell: PathBuf,
     zellij_cwd: PathBuf,
+    plugin_cwd: PathBuf,
     capabilities: PluginCapabilities,
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
@@ -141,6 +142,7 @@ impl<'a> PluginLoader<'a> {
         loading_indication: &mut LoadingIndication,
         path_to_default_shell: PathBuf,
         zellij_cwd: PathBuf,
+        plugin_cwd: PathBuf,
         capabilities: PluginCapabilities,
         client_attributes: ClientAttributes,
         default_shell: Option<TerminalAction>,
@@ -160,6 +162,7 @@ impl<'a> PluginLoader<'a> {
             size,
             path_to_default_shell,
             zellij_cwd,
+            plugin_cwd,
             capabilities,
             client_attributes,
             default_shell,
@@ -314,6 +317,7 @@ impl<'a> PluginLoader<'a> {
         size: Size,
         path_to_default_shell: PathBuf,
         zellij_cwd: PathBuf,
+        plugin_cwd: PathBuf,
         capabilities: PluginCapabilities,
         client_attributes: ClientAttributes,
         default_shell: Option<TerminalAction>,
@@ -340,6 +344,7 @@ impl<'a> PluginLoader<'a> {
             wasm_blob_on_hd: None,
             path_to_default_shell,
             zellij_cwd,
+            plugin_cwd,
             capabilities,
             client_attributes,
             default_shell,
@@ -376,6 +381,7 @@ impl<'a> PluginLoader<'a> {
             cols: running_plugin.columns,
         };
         let plugin_config = running_plugin.plugin_env.plugin.clone();
+        let plugin_cwd = running_plugin.plugin_env.plugin_cwd.clone();
         loading_indication.set_name(running_plugin.plugin_env.name());
         PluginLoader::new(
             plugin_cache,
@@ -390,6 +396,7 @@ impl<'a> PluginLoader<'a> {
             size,
             path_to_default_shell,
             zellij_cwd,
+            plugin_cwd,
             capabilities,
             client_attributes,
             default_shell,
@@ -427,6 +434,7 @@ impl<'a> PluginLoader<'a> {
             cols: running_plugin.columns,
         };
         let plugin_config = running_plugin.plugin_env.plugin.clone();
+        let plugin_cwd = running_plugin.plugin_env.plugin_cwd.clone();
         loading_indication.set_name(running_plugin.plugin_env.name());
         PluginLoader::new(
             plugin_cache,
@@ -441,6 +449,7 @@ impl<'a> PluginLoader<'a> {
             size,
             path_to_default_shell,
             zellij_cwd,
+            plugin_cwd,
             capabilities,
             client_attributes,
             default_shell,
@@ -752,7 +761,7 @@ impl<'a> PluginLoader<'a> {
         };
         let mut wasi_env = WasiState::new("Zellij")
             .env("CLICOLOR_FORCE", "1")
-            .map_dir("/host", self.zellij_cwd.clone())
+            .map_dir("/host", self.plugin_cwd.clone())
             .and_then(|wasi| wasi.map_dir("/data", &self.plugin_own_data_dir))
             .and_then(|wasi| wasi.map_dir("/tmp", ZELLIJ_TMP_DIR.as_path()))
             .and_then(|wasi| {
@@ -776,6 +785,7 @@ impl<'a> PluginLoader<'a> {
             senders: self.senders.clone(),
             wasi_env,
             plugin_own_data_dir: self.plugin_own_data_dir.clone(),
+            plugin_cwd: self.plugin_cwd.clone(),
             tab_index: self.tab_index,
             path_to_default_shell: self.path_to_default_shell.clone(),
             capabilities: self.capabilities.clone(),
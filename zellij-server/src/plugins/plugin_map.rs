@@ -200,6 +200,7 @@ pub struct PluginEnv {
     pub client_id: ClientId,
     #[allow(dead_code)]
     pub plugin_own_data_dir: PathBuf,
+    pub plugin_cwd: PathBuf, // the host folder, mapped to /host
     pub path_to_default_shell: PathBuf,
     pub capabilities: PluginCapabilities,
     pub client_attributes: ClientAttributes,
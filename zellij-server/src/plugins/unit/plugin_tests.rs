@@ -1,6 +1,6 @@
 use super::plugin_thread_main;
 use crate::screen::ScreenInstruction;
-use crate::{channels::SenderWithContext, thread_bus::Bus, ServerInstruction};
+use crate::{channels::SenderWithContext, thread_bus::Bus, ClientId, ServerInstruction};
 use insta::assert_snapshot;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
@@ -293,6 +293,7 @@ fn create_plugin_thread(
                 plugin_capabilities,
                 client_attributes,
                 default_shell_action,
+                false,
             )
             .expect("TEST")
         })
@@ -374,6 +375,7 @@ fn create_plugin_thread_with_server_receiver(
                 plugin_capabilities,
                 client_attributes,
                 default_shell_action,
+                false,
             )
             .expect("TEST")
         })
@@ -461,6 +463,7 @@ fn create_plugin_thread_with_pty_receiver(
                 plugin_capabilities,
                 client_attributes,
                 default_shell_action,
+                false,
             )
             .expect("TEST")
         })
@@ -533,6 +536,7 @@ pub fn load_new_plugin_from_hd() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -601,6 +605,7 @@ pub fn plugin_workers() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -672,6 +677,7 @@ pub fn plugin_workers_persist_state() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -747,6 +753,7 @@ pub fn can_subscribe_to_hd_events() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -817,6 +824,7 @@ pub fn switch_to_mode_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -885,6 +893,7 @@ pub fn switch_to_mode_plugin_command_permission_denied() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -953,6 +962,7 @@ pub fn new_tabs_with_layout_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1035,6 +1045,7 @@ pub fn new_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1103,6 +1114,7 @@ pub fn go_to_next_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1170,6 +1182,7 @@ pub fn go_to_previous_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1237,6 +1250,7 @@ pub fn resize_focused_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1304,6 +1318,7 @@ pub fn resize_focused_pane_with_direction_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1371,6 +1386,7 @@ pub fn focus_next_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1438,6 +1454,7 @@ pub fn focus_previous_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1505,6 +1522,7 @@ pub fn move_focus_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1572,6 +1590,7 @@ pub fn move_focus_or_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1639,6 +1658,7 @@ pub fn edit_scrollback_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1706,6 +1726,7 @@ pub fn write_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1773,6 +1794,7 @@ pub fn write_chars_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1840,6 +1862,7 @@ pub fn toggle_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1907,6 +1930,7 @@ pub fn move_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -1974,6 +1998,7 @@ pub fn move_pane_with_direction_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2042,6 +2067,7 @@ pub fn clear_screen_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2110,6 +2136,7 @@ pub fn scroll_up_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2177,6 +2204,7 @@ pub fn scroll_down_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2244,6 +2272,7 @@ pub fn scroll_to_top_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2311,6 +2340,7 @@ pub fn scroll_to_bottom_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2378,6 +2408,7 @@ pub fn page_scroll_up_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2445,6 +2476,7 @@ pub fn page_scroll_down_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2512,6 +2544,7 @@ pub fn toggle_focus_fullscreen_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2579,6 +2612,7 @@ pub fn toggle_pane_frames_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2646,6 +2680,7 @@ pub fn toggle_pane_embed_or_eject_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2713,6 +2748,7 @@ pub fn undo_rename_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2780,6 +2816,7 @@ pub fn close_focus_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2847,6 +2884,7 @@ pub fn toggle_active_tab_sync_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2914,6 +2952,7 @@ pub fn close_focused_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -2981,6 +3020,7 @@ pub fn undo_rename_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3048,6 +3088,7 @@ pub fn previous_swap_layout_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3115,6 +3156,7 @@ pub fn next_swap_layout_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3182,6 +3224,7 @@ pub fn go_to_tab_name_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3249,6 +3292,7 @@ pub fn focus_or_create_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3316,6 +3360,7 @@ pub fn go_to_tab() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3383,6 +3428,7 @@ pub fn start_or_reload_plugin() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3457,6 +3503,7 @@ pub fn quit_zellij_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3531,6 +3578,7 @@ pub fn detach_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3586,7 +3634,7 @@ pub fn open_file_floating_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3605,6 +3653,7 @@ pub fn open_file_floating_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3625,7 +3674,7 @@ pub fn open_file_floating_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -3660,7 +3709,7 @@ pub fn open_file_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3679,6 +3728,7 @@ pub fn open_file_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3699,7 +3749,7 @@ pub fn open_file_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -3734,7 +3784,7 @@ pub fn open_file_with_line_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3754,6 +3804,7 @@ pub fn open_file_with_line_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3774,7 +3825,7 @@ pub fn open_file_with_line_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -3809,7 +3860,7 @@ pub fn open_file_with_line_floating_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3828,6 +3879,7 @@ pub fn open_file_with_line_floating_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3848,7 +3900,7 @@ pub fn open_file_with_line_floating_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -3883,7 +3935,7 @@ pub fn open_terminal_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3902,6 +3954,7 @@ pub fn open_terminal_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3922,7 +3975,7 @@ pub fn open_terminal_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -3957,7 +4010,7 @@ pub fn open_terminal_floating_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -3976,6 +4029,7 @@ pub fn open_terminal_floating_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -3996,7 +4050,7 @@ pub fn open_terminal_floating_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -4031,7 +4085,7 @@ pub fn open_command_pane_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -4050,6 +4104,7 @@ pub fn open_command_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4070,7 +4125,7 @@ pub fn open_command_pane_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -4105,7 +4160,7 @@ pub fn open_command_pane_floating_plugin_command() {
     let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_thread = log_actions_in_thread!(
         received_pty_instructions,
-        PtyInstruction::SpawnTerminal,
+        PtyInstruction::SpawnTerminalForPlugin,
         pty_receiver,
         1
     );
@@ -4124,6 +4179,7 @@ pub fn open_command_pane_floating_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4144,7 +4200,7 @@ pub fn open_command_pane_floating_plugin_command() {
         .unwrap()
         .iter()
         .find_map(|i| {
-            if let PtyInstruction::SpawnTerminal(..) = i {
+            if let PtyInstruction::SpawnTerminalForPlugin(..) = i {
                 Some(i.clone())
             } else {
                 None
@@ -4191,6 +4247,7 @@ pub fn switch_to_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4253,6 +4310,7 @@ pub fn hide_self_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4315,6 +4373,7 @@ pub fn show_self_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4382,6 +4441,7 @@ pub fn close_terminal_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4449,6 +4509,7 @@ pub fn close_plugin_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4516,6 +4577,7 @@ pub fn focus_terminal_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4583,6 +4645,7 @@ pub fn focus_plugin_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4650,6 +4713,7 @@ pub fn rename_terminal_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4717,6 +4781,7 @@ pub fn rename_plugin_pane_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4784,6 +4849,7 @@ pub fn rename_tab_plugin_command() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4860,6 +4926,7 @@ pub fn send_configuration_to_plugins() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -4893,6 +4960,81 @@ pub fn send_configuration_to_plugins() {
     assert_snapshot!(format!("{:#?}", go_to_tab_event));
 }
 
+#[test]
+#[ignore]
+pub fn pipe_message_to_plugin_plugin_command() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let cache_path = plugin_host_folder.join("permissions_test.kdl");
+    let (plugin_thread_sender, screen_receiver, mut teardown) =
+        create_plugin_thread(Some(plugin_host_folder));
+    let plugin_should_float = Some(false);
+    let plugin_title = Some("test_plugin".to_owned());
+    // the plugin url is normalized when parsed, so we canonicalize the fixture path to match it
+    let plugin_path = PathBuf::from(&*PLUGIN_FIXTURE).canonicalize().unwrap();
+    let mut configuration = BTreeMap::new();
+    configuration.insert(
+        "pipe_to".to_owned(),
+        format!("file:{}", plugin_path.display()),
+    );
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(plugin_path),
+        configuration: PluginUserConfiguration::new(configuration),
+    };
+    let tab_index = 1;
+    let client_id = 1;
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = grant_permissions_and_log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::GoToTabName,
+        screen_receiver,
+        1,
+        &PermissionType::MessageOtherPlugins,
+        cache_path,
+        plugin_thread_sender,
+        client_id
+    );
+
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    let _ = plugin_thread_sender.send(PluginInstruction::Load(
+        plugin_should_float,
+        false,
+        plugin_title,
+        run_plugin,
+        tab_index,
+        client_id,
+        size,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    let _ = plugin_thread_sender.send(PluginInstruction::Update(vec![(
+        None,
+        Some(client_id),
+        Event::Key(Key::Ctrl('2')), // this triggers the enent in the fixture plugin
+    )]));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    teardown();
+    let go_to_tab_event = received_screen_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|i| {
+            if let ScreenInstruction::GoToTabName(..) = i {
+                Some(i.clone())
+            } else {
+                None
+            }
+        })
+        .clone();
+    assert_snapshot!(format!("{:#?}", go_to_tab_event));
+}
+
 #[test]
 #[ignore]
 pub fn request_plugin_permissions() {
@@ -4924,6 +5066,7 @@ pub fn request_plugin_permissions() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin,
         tab_index,
@@ -5012,6 +5155,7 @@ pub fn granted_permission_request_result() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin.clone(),
         tab_index,
@@ -5098,6 +5242,7 @@ pub fn denied_permission_request_result() {
     let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
     let _ = plugin_thread_sender.send(PluginInstruction::Load(
         plugin_should_float,
+        false,
         plugin_title,
         run_plugin.clone(),
         tab_index,
@@ -5118,3 +5263,188 @@ pub fn denied_permission_request_result() {
 
     assert_snapshot!(format!("{:#?}", permissions));
 }
+
+fn load_fixture_plugins_for_cli_pipes(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    screen_receiver: Receiver<(ScreenInstruction, ErrorContext)>,
+    client_id: ClientId,
+    plugin_count: usize,
+) {
+    let run_plugin = RunPlugin {
+        _allow_exec_host_cmd: false,
+        location: RunPluginLocation::File(PathBuf::from(&*PLUGIN_FIXTURE)),
+        configuration: Default::default(),
+    };
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    // the fixture plugin requests its permissions once it is loaded
+    let received_screen_instructions = Arc::new(Mutex::new(vec![]));
+    let screen_thread = log_actions_in_thread!(
+        received_screen_instructions,
+        ScreenInstruction::RequestPluginPermissions,
+        screen_receiver,
+        plugin_count
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::AddClient(client_id));
+    for tab_index in 1..=plugin_count {
+        let _ = plugin_thread_sender.send(PluginInstruction::Load(
+            Some(false),
+            false,
+            Some("test_plugin".to_owned()),
+            run_plugin.clone(),
+            tab_index,
+            client_id,
+            size,
+        ));
+    }
+    screen_thread.join().unwrap(); // this might take a while if the cache is cold
+    std::thread::sleep(std::time::Duration::from_millis(100));
+}
+
+fn pipe_from_cli(
+    plugin_thread_sender: &SenderWithContext<PluginInstruction>,
+    payload: &str,
+    cli_client_id: ClientId,
+) {
+    let _ = plugin_thread_sender.send(PluginInstruction::CliPipe {
+        name: Some("my-pipe".to_owned()),
+        payload: Some(payload.to_owned()),
+        args: BTreeMap::new(),
+        plugin: None,
+        client_id: cli_client_id,
+    });
+    std::thread::sleep(std::time::Duration::from_millis(200));
+}
+
+fn unblocked_cli_pipe_clients(
+    received_server_instructions: &Arc<Mutex<Vec<ServerInstruction>>>,
+) -> Vec<ClientId> {
+    received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            ServerInstruction::UnblockCliPipeInput(cli_client_id) => Some(*cli_client_id),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+#[ignore]
+pub fn cli_pipe_to_several_plugins_is_unblocked_once_all_of_them_are_done_with_it() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let (plugin_thread_sender, server_receiver, screen_receiver, mut teardown) =
+        create_plugin_thread_with_server_receiver(Some(plugin_host_folder));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread_naked_variant!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver,
+        1
+    );
+    let client_id = 1;
+    let cli_client_id = 2;
+    load_fixture_plugins_for_cli_pipes(&plugin_thread_sender, screen_receiver, client_id, 2);
+
+    pipe_from_cli(&plugin_thread_sender, "hi", cli_client_id);
+    assert_eq!(
+        unblocked_cli_pipe_clients(&received_server_instructions),
+        vec![cli_client_id],
+        "unblocked once, after both plugins handled the message"
+    );
+
+    pipe_from_cli(&plugin_thread_sender, "block", cli_client_id); // both plugins block the pipe
+    let _ = plugin_thread_sender.send(PluginInstruction::UnblockCliPipeInput(
+        "my-pipe".to_owned(),
+        0,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    assert_eq!(
+        unblocked_cli_pipe_clients(&received_server_instructions),
+        vec![cli_client_id],
+        "still blocked by the second plugin"
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::UnblockCliPipeInput(
+        "my-pipe".to_owned(),
+        1,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    teardown();
+    server_thread.join().unwrap();
+    assert_eq!(
+        unblocked_cli_pipe_clients(&received_server_instructions),
+        vec![cli_client_id, cli_client_id]
+    );
+}
+
+#[test]
+#[ignore]
+pub fn plugin_can_block_and_unblock_cli_pipe() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let (plugin_thread_sender, server_receiver, screen_receiver, mut teardown) =
+        create_plugin_thread_with_server_receiver(Some(plugin_host_folder));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread_naked_variant!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver,
+        1
+    );
+    let client_id = 1;
+    let cli_client_id = 2;
+    load_fixture_plugins_for_cli_pipes(&plugin_thread_sender, screen_receiver, client_id, 1);
+
+    pipe_from_cli(&plugin_thread_sender, "block", cli_client_id);
+    assert!(
+        unblocked_cli_pipe_clients(&received_server_instructions).is_empty(),
+        "pipe is blocked by the plugin"
+    );
+    pipe_from_cli(&plugin_thread_sender, "unblock", cli_client_id);
+    teardown();
+    server_thread.join().unwrap();
+    assert_eq!(
+        unblocked_cli_pipe_clients(&received_server_instructions),
+        vec![cli_client_id]
+    );
+}
+
+#[test]
+#[ignore]
+pub fn unloading_plugin_unblocks_the_cli_pipes_it_blocked() {
+    let temp_folder = tempdir().unwrap(); // placed explicitly in the test scope because its
+                                          // destructor removes the directory
+    let plugin_host_folder = PathBuf::from(temp_folder.path());
+    let (plugin_thread_sender, server_receiver, screen_receiver, mut teardown) =
+        create_plugin_thread_with_server_receiver(Some(plugin_host_folder));
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_thread = log_actions_in_thread_naked_variant!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver,
+        1
+    );
+    let client_id = 1;
+    let cli_client_id = 2;
+    load_fixture_plugins_for_cli_pipes(&plugin_thread_sender, screen_receiver, client_id, 1);
+
+    pipe_from_cli(&plugin_thread_sender, "block", cli_client_id);
+    assert!(
+        unblocked_cli_pipe_clients(&received_server_instructions).is_empty(),
+        "pipe is blocked by the plugin"
+    );
+    let _ = plugin_thread_sender.send(PluginInstruction::Unload(0));
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    teardown();
+    server_thread.join().unwrap();
+    assert_eq!(
+        unblocked_cli_pipe_clients(&received_server_instructions),
+        vec![cli_client_id]
+    );
+}
@@ -6,23 +6,27 @@ use crate::plugins::watch_filesystem::watch_filesystem;
 use crate::plugins::zellij_exports::{wasi_read_string, wasi_write_object};
 use log::info;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     path::PathBuf,
     str::FromStr,
     sync::{Arc, Mutex},
 };
 use wasmer::{Instance, Module, Store, Value};
 use zellij_utils::async_std::task::{self, JoinHandle};
-use zellij_utils::data::{PermissionStatus, PermissionType};
+use zellij_utils::data::{
+    MessageToPlugin, PermissionStatus, PermissionType, PipeMessage, PipeSource, PluginTag,
+};
+use zellij_utils::downloader::RemotePlugin;
 use zellij_utils::input::permission::PermissionCache;
 use zellij_utils::notify_debouncer_full::{notify::RecommendedWatcher, Debouncer, FileIdMap};
 use zellij_utils::plugin_api::event::ProtobufEvent;
+use zellij_utils::plugin_api::pipe_message::ProtobufPipeMessage;
 
 use zellij_utils::prost::Message;
 
 use crate::{
     background_jobs::BackgroundJob, screen::ScreenInstruction, thread_bus::ThreadSenders,
-    ui::loading_indication::LoadingIndication, ClientId,
+    ui::loading_indication::LoadingIndication, ClientId, ServerInstruction,
 };
 use zellij_utils::{
     data::{Event, EventType, PluginCapabilities},
@@ -60,6 +64,26 @@ pub struct WasmBridge {
     client_attributes: ClientAttributes,
     default_shell: Option<TerminalAction>,
     default_layout: Box<Layout>,
+    watch_plugins: bool,
+    cli_pipes: HashMap<ClientId, CliPipe>, // cli client id to the stream it is piping in
+}
+
+/// A `zellij pipe` client streaming its STDIN into plugins, it is sent its next line once this is
+/// neither pending nor blocked
+struct CliPipe {
+    name: String,
+    pending_deliveries: usize,
+    blocked_by: HashSet<PluginId>,
+}
+
+impl CliPipe {
+    pub fn new(name: String) -> Self {
+        CliPipe {
+            name,
+            pending_deliveries: 0,
+            blocked_by: HashSet::new(),
+        }
+    }
 }
 
 impl WasmBridge {
@@ -74,6 +98,7 @@ impl WasmBridge {
         client_attributes: ClientAttributes,
         default_shell: Option<TerminalAction>,
         default_layout: Box<Layout>,
+        watch_plugins: bool,
     ) -> Self {
         let plugin_map = Arc::new(Mutex::new(PluginMap::default()));
         let connected_clients: Arc<Mutex<Vec<ClientId>>> = Arc::new(Mutex::new(vec![]));
@@ -101,6 +126,8 @@ impl WasmBridge {
             client_attributes,
             default_shell,
             default_layout,
+            watch_plugins,
+            cli_pipes: HashMap::new(),
         }
     }
     pub fn load_plugin(
@@ -108,6 +135,7 @@ impl WasmBridge {
         run: &RunPlugin,
         tab_index: usize,
         size: Size,
+        cwd: Option<PathBuf>,
         client_id: Option<ClientId>,
     ) -> Result<PluginId> {
         // returns the plugin id
@@ -140,6 +168,14 @@ impl WasmBridge {
         self.cached_resizes_for_pending_plugins
             .insert(plugin_id, (size.rows, size.cols));
 
+        if self.watch_plugins {
+            if let RunPluginLocation::File(path) = &run.location {
+                let _ = self
+                    .senders
+                    .send_to_background_jobs(BackgroundJob::WatchPluginFile(path.clone()));
+            }
+        }
+
         let load_plugin_task = task::spawn({
             let plugin_dir = self.plugin_dir.clone();
             let plugin_cache = self.plugin_cache.clone();
@@ -149,6 +185,8 @@ impl WasmBridge {
             let connected_clients = self.connected_clients.clone();
             let path_to_default_shell = self.path_to_default_shell.clone();
             let zellij_cwd = self.zellij_cwd.clone();
+            // the plugin's host folder, plugins can later change it with change_host_folder
+            let plugin_cwd = cwd.unwrap_or_else(|| self.zellij_cwd.clone());
             let capabilities = self.capabilities.clone();
             let client_attributes = self.client_attributes.clone();
             let default_shell = self.default_shell.clone();
@@ -157,6 +195,29 @@ impl WasmBridge {
                 let _ =
                     senders.send_to_background_jobs(BackgroundJob::AnimatePluginLoading(plugin_id));
                 let mut loading_indication = LoadingIndication::new(plugin_name.clone());
+                let mut plugin = plugin;
+                if let Some(url) = plugin.remote_url() {
+                    let fetched = match RemotePlugin::new(url) {
+                        Ok(remote_plugin) => remote_plugin.fetch().await,
+                        Err(e) => Err(e),
+                    };
+                    match fetched {
+                        Ok(path) => plugin.path = path,
+                        Err(e) => {
+                            handle_plugin_loading_failure(
+                                &senders,
+                                plugin_id,
+                                &mut loading_indication,
+                                e,
+                            );
+                            let _ =
+                                senders.send_to_plugin(PluginInstruction::ApplyCachedEvents(vec![
+                                    plugin_id,
+                                ]));
+                            return;
+                        },
+                    }
+                }
                 match PluginLoader::start_plugin(
                     plugin_id,
                     client_id,
@@ -172,6 +233,7 @@ impl WasmBridge {
                     &mut loading_indication,
                     path_to_default_shell,
                     zellij_cwd.clone(),
+                    plugin_cwd,
                     capabilities,
                     client_attributes,
                     default_shell,
@@ -207,7 +269,22 @@ impl WasmBridge {
                 log::error!("Failed to remove cache dir for plugin: {:?}", e);
             }
         }
-        Ok(())
+        drop(plugin_map);
+        let _ = self
+            .senders
+            .send_to_background_jobs(BackgroundJob::StopReportingSystemState(pid));
+        let _ =
+            self.senders
+                .send_to_screen(ScreenInstruction::RemovePaneOutputSubscriptionsOfPlugin(
+                    pid,
+                ));
+        let _ = self
+            .senders
+            .send_to_background_jobs(BackgroundJob::ClearPluginInterval(pid));
+        for cli_pipe in self.cli_pipes.values_mut() {
+            cli_pipe.blocked_by.remove(&pid);
+        }
+        self.unblock_idle_cli_pipes()
     }
     pub fn reload_plugin(&mut self, run_plugin: &RunPlugin) -> Result<()> {
         if self.plugin_is_currently_being_loaded(&run_plugin.location) {
@@ -310,6 +387,103 @@ impl WasmBridge {
             .insert((first_plugin_id, run_plugin.clone()), load_plugin_task);
         Ok(())
     }
+    pub fn change_plugin_host_dir(
+        &mut self,
+        new_host_dir: PathBuf,
+        plugin_id: PluginId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to change host folder of plugin {plugin_id}");
+        let running_plugins: Vec<Arc<Mutex<RunningPlugin>>> = self
+            .plugin_map
+            .lock()
+            .unwrap()
+            .running_plugins()
+            .into_iter()
+            .filter(|(p_id, _client_id, _running_plugin)| p_id == &plugin_id)
+            .map(|(_p_id, _client_id, running_plugin)| running_plugin)
+            .collect();
+        if running_plugins.is_empty() {
+            return Err(ZellijError::PluginDoesNotExist).with_context(err_context);
+        }
+        let mut plugin_name = String::new();
+        for running_plugin in &running_plugins {
+            let mut running_plugin = running_plugin.lock().unwrap();
+            running_plugin.plugin_env.plugin_cwd = new_host_dir.clone();
+            plugin_name = running_plugin.plugin_env.plugin.location.to_string();
+        }
+        // the host folder is mapped when the plugin is instantiated, so we reload it to remap it
+        let (rows, columns) = self.size_of_plugin_id(plugin_id).unwrap_or((0, 0));
+        self.cached_events_for_pending_plugins
+            .insert(plugin_id, vec![]);
+        self.cached_resizes_for_pending_plugins
+            .insert(plugin_id, (rows, columns));
+        let mut loading_indication = LoadingIndication::new(plugin_name);
+        self.start_plugin_loading_indication(&[plugin_id], &loading_indication);
+        task::spawn({
+            let plugin_dir = self.plugin_dir.clone();
+            let plugin_cache = self.plugin_cache.clone();
+            let senders = self.senders.clone();
+            let store = self.store.clone();
+            let plugin_map = self.plugin_map.clone();
+            let connected_clients = self.connected_clients.clone();
+            let path_to_default_shell = self.path_to_default_shell.clone();
+            let zellij_cwd = self.zellij_cwd.clone();
+            let capabilities = self.capabilities.clone();
+            let client_attributes = self.client_attributes.clone();
+            let default_shell = self.default_shell.clone();
+            let default_layout = self.default_layout.clone();
+            async move {
+                match PluginLoader::reload_plugin_from_memory(
+                    plugin_id,
+                    plugin_dir,
+                    plugin_cache,
+                    senders.clone(),
+                    store,
+                    plugin_map,
+                    connected_clients,
+                    &mut loading_indication,
+                    path_to_default_shell,
+                    zellij_cwd,
+                    capabilities,
+                    client_attributes,
+                    default_shell,
+                    default_layout,
+                ) {
+                    Ok(_) => handle_plugin_successful_loading(&senders, plugin_id),
+                    Err(e) => handle_plugin_loading_failure(
+                        &senders,
+                        plugin_id,
+                        &mut loading_indication,
+                        e,
+                    ),
+                }
+                let _ =
+                    senders.send_to_plugin(PluginInstruction::ApplyCachedEvents(vec![plugin_id]));
+            }
+        });
+        Ok(())
+    }
+    pub fn reload_plugins_from_file(&mut self, path: PathBuf) -> Result<()> {
+        let location = RunPluginLocation::File(path);
+        let run_plugin = self
+            .loading_plugins
+            .keys()
+            .map(|(_plugin_id, run_plugin)| run_plugin)
+            .find(|run_plugin| run_plugin.location == location)
+            .cloned()
+            .unwrap_or_else(|| RunPlugin {
+                location,
+                ..Default::default()
+            });
+        match self.reload_plugin(&run_plugin) {
+            Ok(_) => Ok(()),
+            // all instances of this plugin have since been closed
+            Err(e) => match e.downcast_ref::<ZellijError>() {
+                Some(ZellijError::PluginDoesNotExist) => Ok(()),
+                _ => Err(e),
+            },
+        }
+    }
     pub fn add_client(&mut self, client_id: ClientId) -> Result<()> {
         let mut loading_indication = LoadingIndication::new("".into());
         match PluginLoader::add_client(
@@ -503,6 +677,164 @@ impl WasmBridge {
         }
         Ok(())
     }
+    pub fn pipe_message(
+        &mut self,
+        message_to_plugin: MessageToPlugin,
+        source_plugin_id: PluginId,
+    ) -> Result<()> {
+        let plugins_to_pipe_to = self.plugins_to_pipe_to(Some(&message_to_plugin.plugin_url));
+        if plugins_to_pipe_to.is_empty() {
+            log::error!(
+                "Failed to pipe message: no running plugin found for {}",
+                message_to_plugin.plugin_url
+            );
+            return Ok(());
+        }
+        let pipe_message = PipeMessage {
+            source: PipeSource::Plugin(source_plugin_id),
+            name: message_to_plugin.message_name,
+            payload: message_to_plugin.message_payload,
+            args: message_to_plugin.message_args,
+        };
+        self.send_pipe_message(plugins_to_pipe_to, pipe_message, None);
+        Ok(())
+    }
+    pub fn pipe_message_from_cli(
+        &mut self,
+        name: Option<String>,
+        payload: Option<String>,
+        args: BTreeMap<String, String>,
+        plugin_url: Option<String>,
+        cli_client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to pipe message from cli client {cli_client_id}");
+        let plugins_to_pipe_to = self.plugins_to_pipe_to(plugin_url.as_deref());
+        if plugins_to_pipe_to.is_empty() {
+            let error = match plugin_url {
+                Some(plugin_url) => format!("No running plugin found for {}", plugin_url),
+                None => "No running plugins".to_owned(),
+            };
+            return self
+                .senders
+                .send_to_server(ServerInstruction::Log(vec![error], cli_client_id))
+                .with_context(err_context);
+        }
+        let name = name.unwrap_or_else(|| format!("cli-pipe-{}", cli_client_id));
+        let cli_pipe = self
+            .cli_pipes
+            .entry(cli_client_id)
+            .or_insert_with(|| CliPipe::new(name.clone()));
+        cli_pipe.pending_deliveries += plugins_to_pipe_to.len();
+        let pipe_message = PipeMessage {
+            source: PipeSource::Cli,
+            name,
+            payload,
+            args,
+        };
+        self.send_pipe_message(plugins_to_pipe_to, pipe_message, Some(cli_client_id));
+        Ok(())
+    }
+    pub fn cli_pipe_message_delivered(&mut self, cli_client_id: ClientId) -> Result<()> {
+        if let Some(cli_pipe) = self.cli_pipes.get_mut(&cli_client_id) {
+            cli_pipe.pending_deliveries = cli_pipe.pending_deliveries.saturating_sub(1);
+        }
+        self.unblock_idle_cli_pipes()
+    }
+    pub fn block_cli_pipe_input(&mut self, pipe_name: &str, plugin_id: PluginId) {
+        for cli_pipe in self.cli_pipes.values_mut() {
+            if cli_pipe.name == pipe_name {
+                cli_pipe.blocked_by.insert(plugin_id);
+            }
+        }
+    }
+    pub fn unblock_cli_pipe_input(&mut self, pipe_name: &str, plugin_id: PluginId) -> Result<()> {
+        for cli_pipe in self.cli_pipes.values_mut() {
+            if cli_pipe.name == pipe_name {
+                cli_pipe.blocked_by.remove(&plugin_id);
+            }
+        }
+        self.unblock_idle_cli_pipes()
+    }
+    fn unblock_idle_cli_pipes(&mut self) -> Result<()> {
+        // a cli client sends its next message once all plugins handled the last one, and none of
+        // them asked to hold the stream back
+        for (cli_client_id, cli_pipe) in self.cli_pipes.iter() {
+            if cli_pipe.pending_deliveries == 0 && cli_pipe.blocked_by.is_empty() {
+                self.senders
+                    .send_to_server(ServerInstruction::UnblockCliPipeInput(*cli_client_id))
+                    .with_context(|| format!("failed to unblock cli client {cli_client_id}"))?;
+            }
+        }
+        self.cli_pipes.retain(|_, cli_pipe| {
+            cli_pipe.pending_deliveries > 0 || !cli_pipe.blocked_by.is_empty()
+        });
+        Ok(())
+    }
+    fn plugins_to_pipe_to(
+        &self,
+        plugin_url: Option<&str>,
+    ) -> Vec<(PluginId, ClientId, Arc<Mutex<RunningPlugin>>)> {
+        // plugin aliases (eg. "session-manager") are not valid urls, so we treat them as built-in
+        // plugin tags
+        let plugin_location = plugin_url.map(|plugin_url| {
+            RunPluginLocation::parse(plugin_url, Some(self.zellij_cwd.clone()))
+                .unwrap_or_else(|_| RunPluginLocation::Zellij(PluginTag::new(plugin_url)))
+        });
+        self.plugin_map
+            .lock()
+            .unwrap()
+            .running_plugins()
+            .into_iter()
+            .filter(|(_plugin_id, _client_id, running_plugin)| {
+                let running_plugin = running_plugin.lock().unwrap();
+                match &plugin_location {
+                    Some(plugin_location) => {
+                        &running_plugin.plugin_env.plugin.location == plugin_location
+                    },
+                    None => true,
+                }
+            })
+            .collect()
+    }
+    fn send_pipe_message(
+        &self,
+        plugins_to_pipe_to: Vec<(PluginId, ClientId, Arc<Mutex<RunningPlugin>>)>,
+        pipe_message: PipeMessage,
+        cli_client_id: Option<ClientId>,
+    ) {
+        for (plugin_id, client_id, running_plugin) in plugins_to_pipe_to {
+            task::spawn({
+                let senders = self.senders.clone();
+                let pipe_message = pipe_message.clone();
+                async move {
+                    let running_plugin = running_plugin.lock().unwrap();
+                    let mut plugin_bytes = vec![];
+                    match apply_pipe_message_to_plugin(
+                        plugin_id,
+                        client_id,
+                        &running_plugin,
+                        pipe_message,
+                        &mut plugin_bytes,
+                    ) {
+                        Ok(()) => {
+                            let _ = senders
+                                .send_to_screen(ScreenInstruction::PluginBytes(plugin_bytes));
+                        },
+                        Err(e) => {
+                            log::error!("{:?}", e);
+                            let stringified_error = format!("{e:?}").replace('\n', "\n\r");
+                            handle_plugin_crash(plugin_id, stringified_error, senders.clone());
+                        },
+                    }
+                    if let Some(cli_client_id) = cli_client_id {
+                        let _ = senders.send_to_plugin(PluginInstruction::CliPipeMessageDelivered(
+                            cli_client_id,
+                        ));
+                    }
+                }
+            });
+        }
+    }
     pub fn apply_cached_events(&mut self, plugin_ids: Vec<PluginId>) -> Result<()> {
         let mut applied_plugin_paths = HashSet::new();
         for plugin_id in plugin_ids {
@@ -525,6 +857,7 @@ impl WasmBridge {
             .lock()
             .unwrap()
             .retain(|c| c != &client_id);
+        self.cli_pipes.remove(&client_id);
     }
     pub fn cleanup(&mut self) {
         for (_plugin_id, loading_plugin_task) in self.loading_plugins.drain() {
@@ -786,6 +1119,7 @@ fn check_event_permission(
         | Event::CopyToClipboard(..)
         | Event::SystemClipboardFailure
         | Event::InputReceived => PermissionType::ReadApplicationState,
+        Event::PaneOutput(..) | Event::PaneContents(..) => PermissionType::ReadPaneContents,
         _ => return (PermissionStatus::Granted, None),
     };
 
@@ -860,6 +1194,55 @@ pub fn apply_event_to_plugin(
     Ok(())
 }
 
+fn apply_pipe_message_to_plugin(
+    plugin_id: PluginId,
+    client_id: ClientId,
+    running_plugin: &RunningPlugin,
+    pipe_message: PipeMessage,
+    plugin_bytes: &mut Vec<(PluginId, ClientId, Vec<u8>)>,
+) -> Result<()> {
+    let err_context = || format!("Failed to pipe message to plugin {plugin_id}");
+    let instance = &running_plugin.instance;
+    let plugin_env = &running_plugin.plugin_env;
+    let (rows, columns) = (running_plugin.rows, running_plugin.columns);
+    let pipe = match instance.exports.get_function("pipe") {
+        Ok(pipe) => pipe,
+        Err(_) => {
+            // plugins compiled against an older zellij-tile do not have this export
+            log::error!(
+                "Plugin {} does not implement the pipe method",
+                plugin_env.name()
+            );
+            return Ok(());
+        },
+    };
+    let protobuf_pipe_message: ProtobufPipeMessage = pipe_message
+        .try_into()
+        .map_err(|e| anyhow!("Failed to convert to protobuf: {:?}", e))?;
+    wasi_write_object(&plugin_env.wasi_env, &protobuf_pipe_message.encode_to_vec())
+        .with_context(err_context)?;
+    let pipe_return = pipe.call(&[]).with_context(err_context)?;
+    let should_render = match pipe_return.get(0) {
+        Some(Value::I32(n)) => *n == 1,
+        _ => false,
+    };
+    if rows > 0 && columns > 0 && should_render {
+        let rendered_bytes = instance
+            .exports
+            .get_function("render")
+            .map_err(anyError::new)
+            .and_then(|render| {
+                render
+                    .call(&[Value::I32(rows as i32), Value::I32(columns as i32)])
+                    .map_err(anyError::new)
+            })
+            .and_then(|_| wasi_read_string(&plugin_env.wasi_env))
+            .with_context(err_context)?;
+        plugin_bytes.push((plugin_id, client_id, rendered_bytes.as_bytes().to_vec()));
+    }
+    Ok(())
+}
+
 pub fn handle_plugin_crash(plugin_id: PluginId, message: String, senders: ThreadSenders) {
     let mut loading_indication = LoadingIndication::new("Panic!".to_owned());
     loading_indication.indicate_loading_error(message);
@@ -23,27 +23,37 @@ use zellij_utils::input::permission::PermissionCache;
 
 use url::Url;
 
-use crate::{panes::PaneId, screen::ScreenInstruction};
+use crate::{
+    background_jobs::BackgroundJob,
+    panes::PaneId,
+    pty::{NewPanePlacement, PtyInstruction},
+    screen::ScreenInstruction,
+};
 
 use zellij_utils::{
-    consts::VERSION,
+    consts::{VERSION, ZELLIJ_SOCK_DIR},
     data::{
-        CommandToRun, Direction, Event, EventType, FileToOpen, InputMode, PluginCommand, PluginIds,
-        PluginMessage, Resize, ResizeStrategy,
+        CommandToRun, Direction, Event, EventType, FileToOpen, HttpVerb, InputMode,
+        MessageToPlugin, PluginCommand, PluginIds, PluginMessage, Resize, ResizeStrategy,
     },
     errors::prelude::*,
     input::{
         actions::Action,
-        command::{RunCommand, RunCommandAction, TerminalAction},
+        command::{RunCommand, TerminalAction},
         layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation},
         plugins::PluginType,
     },
+    interprocess::local_socket::LocalSocketStream,
+    ipc::{ClientToServerMsg, IpcSenderWithContext},
     plugin_api::{
         plugin_command::ProtobufPluginCommand,
         plugin_ids::{ProtobufPluginIds, ProtobufZellijVersion},
     },
     prost::Message,
     serde,
+    session_serialization::{
+        is_valid_session_name, resurrectable_sessions, session_layout_cache_dir,
+    },
 };
 
 macro_rules! apply_action {
@@ -106,22 +116,40 @@ fn host_run_plugin_command(env: &ForeignFunctionEnv) {
                     PluginCommand::SetSelectable(selectable) => set_selectable(env, selectable),
                     PluginCommand::GetPluginIds => get_plugin_ids(env),
                     PluginCommand::GetZellijVersion => get_zellij_version(env),
-                    PluginCommand::OpenFile(file_to_open) => open_file(env, file_to_open),
+                    PluginCommand::OpenFile(file_to_open) => {
+                        open_file(env, file_to_open, NewPanePlacement::Tiled)
+                    },
                     PluginCommand::OpenFileFloating(file_to_open) => {
-                        open_file_floating(env, file_to_open)
+                        open_file(env, file_to_open, NewPanePlacement::Floating)
+                    },
+                    PluginCommand::OpenFileInPlace(file_to_open) => {
+                        open_file(env, file_to_open, NewPanePlacement::InPlace)
+                    },
+                    PluginCommand::OpenTerminal(cwd) => {
+                        open_terminal(env, cwd.path.try_into()?, NewPanePlacement::Tiled)
                     },
-                    PluginCommand::OpenTerminal(cwd) => open_terminal(env, cwd.path.try_into()?),
                     PluginCommand::OpenTerminalFloating(cwd) => {
-                        open_terminal_floating(env, cwd.path.try_into()?)
+                        open_terminal(env, cwd.path.try_into()?, NewPanePlacement::Floating)
+                    },
+                    PluginCommand::OpenTerminalInPlace(cwd) => {
+                        open_terminal(env, cwd.path.try_into()?, NewPanePlacement::InPlace)
                     },
                     PluginCommand::OpenCommandPane(command_to_run) => {
-                        open_command_pane(env, command_to_run)
+                        open_command_pane(env, command_to_run, NewPanePlacement::Tiled)
                     },
                     PluginCommand::OpenCommandPaneFloating(command_to_run) => {
-                        open_command_pane_floating(env, command_to_run)
+                        open_command_pane(env, command_to_run, NewPanePlacement::Floating)
+                    },
+                    PluginCommand::OpenCommandPaneInPlace(command_to_run) => {
+                        open_command_pane(env, command_to_run, NewPanePlacement::InPlace)
                     },
                     PluginCommand::SwitchTabTo(tab_index) => switch_tab_to(env, tab_index),
                     PluginCommand::SetTimeout(seconds) => set_timeout(env, seconds),
+                    PluginCommand::SetInterval(seconds) => set_interval(env, seconds)?,
+                    PluginCommand::ClearInterval => clear_interval(env)?,
+                    PluginCommand::CloseTabWithIndex(tab_index) => {
+                        close_tab_with_index(env, tab_index)?
+                    },
                     PluginCommand::ExecCmd(command_line) => exec_cmd(env, command_line),
                     PluginCommand::PostMessageTo(plugin_message) => {
                         post_message_to(env, plugin_message)?
@@ -213,7 +241,57 @@ fn host_run_plugin_command(env: &ForeignFunctionEnv) {
                         connect_to_session.name,
                         connect_to_session.tab_position,
                         connect_to_session.pane_id,
+                        connect_to_session.layout,
                     )?,
+                    PluginCommand::SubscribeToPaneOutput(terminal_pane_id) => {
+                        subscribe_to_pane_output(env, terminal_pane_id)?
+                    },
+                    PluginCommand::UnsubscribeFromPaneOutput(terminal_pane_id) => {
+                        unsubscribe_from_pane_output(env, terminal_pane_id)?
+                    },
+                    PluginCommand::PipeMessageToPlugin(message_to_plugin) => {
+                        pipe_message_to_plugin(env, message_to_plugin)?
+                    },
+                    PluginCommand::KillSessions(session_names) => kill_sessions(session_names),
+                    PluginCommand::DeleteDeadSession(session_name) => {
+                        delete_dead_session(session_name)?
+                    },
+                    PluginCommand::RenameSession(new_session_name) => {
+                        rename_session(env, new_session_name)?
+                    },
+                    PluginCommand::MoveTabToIndex(tab_index, new_index) => {
+                        move_tab_to_index(env, tab_index, new_index)?
+                    },
+                    PluginCommand::RebindKeys(kdl_keybinds, write_config_to_disk) => {
+                        rebind_keys(env, kdl_keybinds, write_config_to_disk)?
+                    },
+                    PluginCommand::BlockCliPipeInput(pipe_name) => {
+                        block_cli_pipe_input(env, pipe_name)?
+                    },
+                    PluginCommand::UnblockCliPipeInput(pipe_name) => {
+                        unblock_cli_pipe_input(env, pipe_name)?
+                    },
+                    PluginCommand::RunCommand(command_to_run, env_variables) => {
+                        run_command(env, command_to_run, env_variables)?
+                    },
+                    PluginCommand::WebRequest(url, verb, headers, body) => {
+                        web_request(env, url, verb, headers, body)?
+                    },
+                    PluginCommand::ChangeHostFolder(new_host_folder) => {
+                        change_host_folder(env, new_host_folder)?
+                    },
+                    PluginCommand::SubscribeToSystemState(interval) => {
+                        subscribe_to_system_state(env, interval)?
+                    },
+                    PluginCommand::DumpPaneContents(terminal_pane_id, full) => {
+                        dump_pane_contents(env, terminal_pane_id, full)?
+                    },
+                    PluginCommand::WriteToPaneId(bytes, terminal_pane_id) => {
+                        write_to_pane_id(env, bytes, terminal_pane_id)
+                    },
+                    PluginCommand::WriteCharsToPaneId(chars_to_write, terminal_pane_id) => {
+                        write_chars_to_pane_id(env, chars_to_write, terminal_pane_id)
+                    },
                 },
                 (PermissionStatus::Denied, permission) => {
                     log::error!(
@@ -307,6 +385,159 @@ fn request_permission(env: &ForeignFunctionEnv, permissions: Vec<PermissionType>
         ))
 }
 
+fn subscribe_to_pane_output(env: &ForeignFunctionEnv, terminal_pane_id: u32) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::SubscribeToPaneOutput(
+            terminal_pane_id,
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+        ))
+        .with_context(|| format!("failed to subscribe to output of pane {terminal_pane_id}"))
+}
+
+fn dump_pane_contents(env: &ForeignFunctionEnv, terminal_pane_id: u32, full: bool) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::DumpPaneContents(
+            terminal_pane_id,
+            full,
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+        ))
+        .with_context(|| format!("failed to dump contents of pane {terminal_pane_id}"))
+}
+
+fn unsubscribe_from_pane_output(env: &ForeignFunctionEnv, terminal_pane_id: u32) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::UnsubscribeFromPaneOutput(
+            terminal_pane_id,
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+        ))
+        .with_context(|| format!("failed to unsubscribe from output of pane {terminal_pane_id}"))
+}
+
+fn pipe_message_to_plugin(
+    env: &ForeignFunctionEnv,
+    message_to_plugin: MessageToPlugin,
+) -> Result<()> {
+    let plugin_url = message_to_plugin.plugin_url.clone();
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::PipeMessage(
+            message_to_plugin,
+            env.plugin_env.plugin_id,
+        ))
+        .with_context(|| format!("failed to pipe message to plugin {plugin_url}"))
+}
+
+fn block_cli_pipe_input(env: &ForeignFunctionEnv, pipe_name: String) -> Result<()> {
+    let err_context = || format!("failed to block cli pipe {pipe_name}");
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::BlockCliPipeInput(
+            pipe_name.clone(),
+            env.plugin_env.plugin_id,
+        ))
+        .with_context(err_context)
+}
+
+fn unblock_cli_pipe_input(env: &ForeignFunctionEnv, pipe_name: String) -> Result<()> {
+    let err_context = || format!("failed to unblock cli pipe {pipe_name}");
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::UnblockCliPipeInput(
+            pipe_name.clone(),
+            env.plugin_env.plugin_id,
+        ))
+        .with_context(err_context)
+}
+
+fn run_command(
+    env: &ForeignFunctionEnv,
+    command_to_run: CommandToRun,
+    env_variables: BTreeMap<String, String>,
+) -> Result<()> {
+    let err_context = || {
+        format!(
+            "failed to run command for plugin '{}'",
+            env.plugin_env.name()
+        )
+    };
+    env.plugin_env
+        .senders
+        .send_to_background_jobs(BackgroundJob::RunCommand(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            command_to_run.path.display().to_string(),
+            command_to_run.args,
+            env_variables,
+            command_to_run.cwd,
+        ))
+        .with_context(err_context)
+}
+
+fn web_request(
+    env: &ForeignFunctionEnv,
+    url: String,
+    verb: HttpVerb,
+    headers: BTreeMap<String, String>,
+    body: Vec<u8>,
+) -> Result<()> {
+    let err_context = || {
+        format!(
+            "failed to make web request for plugin '{}'",
+            env.plugin_env.name()
+        )
+    };
+    env.plugin_env
+        .senders
+        .send_to_background_jobs(BackgroundJob::WebRequest(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            url,
+            verb,
+            headers,
+            body,
+        ))
+        .with_context(err_context)
+}
+
+fn change_host_folder(env: &ForeignFunctionEnv, new_host_folder: PathBuf) -> Result<()> {
+    let err_context = || {
+        format!(
+            "failed to change host folder to {}",
+            new_host_folder.display()
+        )
+    };
+    env.plugin_env
+        .senders
+        .send_to_plugin(PluginInstruction::ChangePluginHostDir(
+            new_host_folder.clone(),
+            env.plugin_env.plugin_id,
+        ))
+        .with_context(err_context)
+}
+
+fn subscribe_to_system_state(env: &ForeignFunctionEnv, interval: f64) -> Result<()> {
+    let err_context = || {
+        format!(
+            "failed to subscribe plugin '{}' to the system state",
+            env.plugin_env.name()
+        )
+    };
+    env.plugin_env
+        .senders
+        .send_to_background_jobs(BackgroundJob::ReportSystemState(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            Duration::from_secs_f64(interval.max(0.1)),
+        ))
+        .with_context(err_context)
+}
+
 fn get_plugin_ids(env: &ForeignFunctionEnv) {
     let ids = PluginIds {
         plugin_id: env.plugin_env.plugin_id,
@@ -344,104 +575,63 @@ fn get_zellij_version(env: &ForeignFunctionEnv) {
     .non_fatal();
 }
 
-fn open_file(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
-    let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
-    let floating = false;
-    let action = Action::EditFile(
+fn open_file(env: &ForeignFunctionEnv, file_to_open: FileToOpen, placement: NewPanePlacement) {
+    let title = format!("Editing: {}", file_to_open.path.display());
+    let open_file = TerminalAction::OpenFile(
         file_to_open.path,
         file_to_open.line_number,
         file_to_open.cwd,
-        None,
-        floating,
     );
-    apply_action!(action, error_msg, env);
+    spawn_terminal(env, open_file, placement, Some(title));
 }
 
-fn open_file_floating(env: &ForeignFunctionEnv, file_to_open: FileToOpen) {
-    let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
-    let floating = true;
-    let action = Action::EditFile(
-        file_to_open.path,
-        file_to_open.line_number,
-        file_to_open.cwd,
-        None,
-        floating,
-    );
-    apply_action!(action, error_msg, env);
-}
-
-fn open_terminal(env: &ForeignFunctionEnv, cwd: PathBuf) {
-    let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
+fn open_terminal(env: &ForeignFunctionEnv, cwd: PathBuf, placement: NewPanePlacement) {
     let mut default_shell = env
         .plugin_env
         .default_shell
         .clone()
         .unwrap_or_else(|| TerminalAction::RunCommand(RunCommand::default()));
     default_shell.change_cwd(cwd);
-    let run_command_action: Option<RunCommandAction> = match default_shell {
-        TerminalAction::RunCommand(run_command) => Some(run_command.into()),
-        _ => None,
-    };
-    let action = Action::NewTiledPane(None, run_command_action, None);
-    apply_action!(action, error_msg, env);
+    let name = None;
+    spawn_terminal(env, default_shell, placement, name);
 }
 
-fn open_terminal_floating(env: &ForeignFunctionEnv, cwd: PathBuf) {
-    let error_msg = || format!("failed to open file in plugin {}", env.plugin_env.name());
-    let mut default_shell = env
-        .plugin_env
-        .default_shell
-        .clone()
-        .unwrap_or_else(|| TerminalAction::RunCommand(RunCommand::default()));
-    default_shell.change_cwd(cwd);
-    let run_command_action: Option<RunCommandAction> = match default_shell {
-        TerminalAction::RunCommand(run_command) => Some(run_command.into()),
-        _ => None,
+fn open_command_pane(
+    env: &ForeignFunctionEnv,
+    command_to_run: CommandToRun,
+    placement: NewPanePlacement,
+) {
+    let run_command = RunCommand {
+        command: command_to_run.path,
+        args: command_to_run.args,
+        cwd: command_to_run.cwd,
+        hold_on_close: true,
+        hold_on_start: false,
+        ..Default::default()
     };
-    let action = Action::NewFloatingPane(run_command_action, None);
-    apply_action!(action, error_msg, env);
-}
-
-fn open_command_pane(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
-    let error_msg = || format!("failed to open command in plugin {}", env.plugin_env.name());
-    let command = command_to_run.path;
-    let cwd = command_to_run.cwd;
-    let args = command_to_run.args;
-    let direction = None;
-    let hold_on_close = true;
-    let hold_on_start = false;
+    let run_command = TerminalAction::RunCommand(run_command);
     let name = None;
-    let run_command_action = RunCommandAction {
-        command,
-        args,
-        cwd,
-        direction,
-        hold_on_close,
-        hold_on_start,
-    };
-    let action = Action::NewTiledPane(direction, Some(run_command_action), name);
-    apply_action!(action, error_msg, env);
+    spawn_terminal(env, run_command, placement, name);
 }
 
-fn open_command_pane_floating(env: &ForeignFunctionEnv, command_to_run: CommandToRun) {
-    let error_msg = || format!("failed to open command in plugin {}", env.plugin_env.name());
-    let command = command_to_run.path;
-    let cwd = command_to_run.cwd;
-    let args = command_to_run.args;
-    let direction = None;
-    let hold_on_close = true;
-    let hold_on_start = false;
-    let name = None;
-    let run_command_action = RunCommandAction {
-        command,
-        args,
-        cwd,
-        direction,
-        hold_on_close,
-        hold_on_start,
-    };
-    let action = Action::NewFloatingPane(Some(run_command_action), name);
-    apply_action!(action, error_msg, env);
+fn spawn_terminal(
+    env: &ForeignFunctionEnv,
+    terminal_action: TerminalAction,
+    placement: NewPanePlacement,
+    name: Option<String>,
+) {
+    // the plugin is told the id of the new pane with an Event::PaneOpened once it is created
+    env.plugin_env
+        .senders
+        .send_to_pty(PtyInstruction::SpawnTerminalForPlugin(
+            Some(terminal_action),
+            placement,
+            name,
+            env.plugin_env.client_id,
+            env.plugin_env.plugin_id,
+        ))
+        .with_context(|| format!("failed to open pane in plugin {}", env.plugin_env.name()))
+        .non_fatal();
 }
 
 fn switch_tab_to(env: &ForeignFunctionEnv, tab_idx: u32) {
@@ -558,6 +748,29 @@ fn post_message_to_plugin(env: &ForeignFunctionEnv, plugin_message: PluginMessag
         ))
 }
 
+fn set_interval(env: &ForeignFunctionEnv, secs: f64) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_background_jobs(BackgroundJob::SetPluginInterval(
+            env.plugin_env.plugin_id,
+            env.plugin_env.client_id,
+            Duration::from_secs_f64(secs.max(0.01)),
+        ))
+        .with_context(|| {
+            format!(
+                "failed to set host interval of {secs} s for plugin {}",
+                env.plugin_env.name()
+            )
+        })
+}
+
+fn clear_interval(env: &ForeignFunctionEnv) -> Result<()> {
+    env.plugin_env
+        .senders
+        .send_to_background_jobs(BackgroundJob::ClearPluginInterval(env.plugin_env.plugin_id))
+        .with_context(|| format!("failed to clear host interval"))
+}
+
 fn hide_self(env: &ForeignFunctionEnv) -> Result<()> {
     env.plugin_env
         .senders
@@ -693,6 +906,7 @@ fn switch_session(
     session_name: Option<String>,
     tab_position: Option<usize>,
     pane_id: Option<(u32, bool)>,
+    layout: Option<String>,
 ) -> Result<()> {
     // pane_id is (id, is_plugin)
     let err_context = || format!("Failed to switch session");
@@ -702,6 +916,7 @@ fn switch_session(
         name: session_name,
         tab_position,
         pane_id,
+        layout,
     };
     env.plugin_env
         .senders
@@ -713,6 +928,80 @@ fn switch_session(
     Ok(())
 }
 
+fn kill_sessions(session_names: Vec<String>) {
+    for session_name in session_names {
+        let socket_path = ZELLIJ_SOCK_DIR.join(&session_name);
+        match LocalSocketStream::connect(&*socket_path) {
+            Ok(stream) => {
+                let _ = IpcSenderWithContext::new(stream).send(ClientToServerMsg::KillSession);
+            },
+            Err(e) => {
+                log::error!("Failed to kill session {}: {:?}", session_name, e);
+            },
+        }
+    }
+}
+
+fn delete_dead_session(session_name: String) -> Result<()> {
+    let err_context = || format!("Failed to delete dead session {session_name}");
+    if !is_valid_session_name(&session_name) {
+        return Err(anyhow!("Invalid session name: {:?}", session_name)).with_context(err_context);
+    }
+    if ZELLIJ_SOCK_DIR.join(&session_name).exists() {
+        return Err(anyhow!("Session {} is still running", session_name)).with_context(err_context);
+    }
+    // running sessions were ruled out above, so every session with a layout is a dead one here
+    if !resurrectable_sessions(&[])
+        .iter()
+        .any(|(dead_session_name, _age)| dead_session_name == &session_name)
+    {
+        return Err(anyhow!("No dead session named {}", session_name)).with_context(err_context);
+    }
+    std::fs::remove_dir_all(session_layout_cache_dir(&session_name)).with_context(err_context)
+}
+
+fn rename_session(env: &ForeignFunctionEnv, new_session_name: String) -> Result<()> {
+    let err_context = || format!("Failed to rename session to {new_session_name}");
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::RenameSession(new_session_name.clone()))
+        .with_context(err_context)
+}
+
+fn close_tab_with_index(env: &ForeignFunctionEnv, tab_index: u32) -> Result<()> {
+    let err_context = || format!("Failed to close tab {tab_index}");
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::CloseTabAtPosition(tab_index as usize))
+        .with_context(err_context)
+}
+
+fn move_tab_to_index(env: &ForeignFunctionEnv, tab_index: u32, new_index: u32) -> Result<()> {
+    let err_context = || format!("Failed to move tab {tab_index} to index {new_index}");
+    env.plugin_env
+        .senders
+        .send_to_screen(ScreenInstruction::MoveTabToIndex(
+            tab_index as usize,
+            new_index as usize,
+        ))
+        .with_context(err_context)
+}
+
+fn rebind_keys(
+    env: &ForeignFunctionEnv,
+    kdl_keybinds: String,
+    write_config_to_disk: bool,
+) -> Result<()> {
+    let err_context = || format!("Failed to rebind keys");
+    env.plugin_env
+        .senders
+        .send_to_server(ServerInstruction::RebindKeys(
+            kdl_keybinds,
+            write_config_to_disk,
+        ))
+        .with_context(err_context)
+}
+
 fn edit_scrollback(env: &ForeignFunctionEnv) {
     let action = Action::EditScrollback;
     let error_msg = || format!("Failed to edit scrollback");
@@ -731,6 +1020,30 @@ fn write_chars(env: &ForeignFunctionEnv, chars_to_write: String) {
     apply_action!(action, error_msg, env);
 }
 
+fn write_to_pane_id(env: &ForeignFunctionEnv, bytes: Vec<u8>, terminal_pane_id: u32) {
+    let error_msg = || {
+        format!(
+            "failed to write to pane {} in plugin {}",
+            terminal_pane_id,
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::WriteToPaneId(bytes, terminal_pane_id);
+    apply_action!(action, error_msg, env);
+}
+
+fn write_chars_to_pane_id(env: &ForeignFunctionEnv, chars_to_write: String, terminal_pane_id: u32) {
+    let error_msg = || {
+        format!(
+            "failed to write to pane {} in plugin {}",
+            terminal_pane_id,
+            env.plugin_env.name()
+        )
+    };
+    let action = Action::WriteCharsToPaneId(chars_to_write, terminal_pane_id);
+    apply_action!(action, error_msg, env);
+}
+
 fn toggle_tab(env: &ForeignFunctionEnv) {
     let error_msg = || format!("Failed to toggle tab");
     let action = Action::ToggleTab;
@@ -1090,16 +1403,30 @@ fn check_command_permission(
         return (PermissionStatus::Granted, None);
     }
     let permission = match command {
-        PluginCommand::OpenFile(..) | PluginCommand::OpenFileFloating(..) => {
-            PermissionType::OpenFiles
-        },
+        PluginCommand::OpenFile(..)
+        | PluginCommand::OpenFileFloating(..)
+        | PluginCommand::OpenFileInPlace(..) => PermissionType::OpenFiles,
         PluginCommand::OpenTerminal(..)
         | PluginCommand::StartOrReloadPlugin(..)
-        | PluginCommand::OpenTerminalFloating(..) => PermissionType::OpenTerminalsOrPlugins,
+        | PluginCommand::OpenTerminalFloating(..)
+        | PluginCommand::OpenTerminalInPlace(..) => PermissionType::OpenTerminalsOrPlugins,
         PluginCommand::OpenCommandPane(..)
         | PluginCommand::OpenCommandPaneFloating(..)
-        | PluginCommand::ExecCmd(..) => PermissionType::RunCommands,
-        PluginCommand::Write(..) | PluginCommand::WriteChars(..) => PermissionType::WriteToStdin,
+        | PluginCommand::OpenCommandPaneInPlace(..)
+        | PluginCommand::ExecCmd(..)
+        | PluginCommand::RunCommand(..) => PermissionType::RunCommands,
+        PluginCommand::Write(..)
+        | PluginCommand::WriteChars(..)
+        | PluginCommand::WriteToPaneId(..)
+        | PluginCommand::WriteCharsToPaneId(..) => PermissionType::WriteToStdin,
+        PluginCommand::SubscribeToPaneOutput(..) | PluginCommand::DumpPaneContents(..) => {
+            PermissionType::ReadPaneContents
+        },
+        PluginCommand::PipeMessageToPlugin(..) => PermissionType::MessageOtherPlugins,
+        PluginCommand::RebindKeys(..) => PermissionType::Reconfigure,
+        PluginCommand::WebRequest(..) => PermissionType::WebAccess,
+        PluginCommand::ChangeHostFolder(..) => PermissionType::FullHdAccess,
+        PluginCommand::SubscribeToSystemState(..) => PermissionType::ReadSystemState,
         PluginCommand::SwitchTabTo(..)
         | PluginCommand::SwitchToMode(..)
         | PluginCommand::NewTabsWithLayout(..)
@@ -1144,6 +1471,11 @@ fn check_command_permission(
         | PluginCommand::RenameTerminalPane(..)
         | PluginCommand::RenamePluginPane(..)
         | PluginCommand::SwitchSession(..)
+        | PluginCommand::KillSessions(..)
+        | PluginCommand::DeleteDeadSession(..)
+        | PluginCommand::RenameSession(..)
+        | PluginCommand::MoveTabToIndex(..)
+        | PluginCommand::CloseTabWithIndex(..)
         | PluginCommand::RenameTab(..) => PermissionType::ChangeApplicationState,
         _ => return (PermissionStatus::Granted, None),
     };
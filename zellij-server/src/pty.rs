@@ -1,16 +1,22 @@
 use crate::terminal_bytes::TerminalBytes;
 use crate::{
+    background_jobs::BackgroundJob,
     panes::PaneId,
-    plugins::PluginInstruction,
+    plugins::{PluginId, PluginInstruction},
     screen::ScreenInstruction,
     thread_bus::{Bus, ThreadSenders},
     ClientId, ServerInstruction,
 };
 use async_std::task::{self, JoinHandle};
-use std::{collections::HashMap, os::unix::io::RawFd, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    os::unix::io::RawFd,
+    path::PathBuf,
+};
 use zellij_utils::nix::unistd::Pid;
 use zellij_utils::{
     async_std,
+    data::Event,
     errors::prelude::*,
     errors::{ContextType, PtyContext},
     input::{
@@ -20,6 +26,9 @@ use zellij_utils::{
             TiledPaneLayout,
         },
     },
+    session_serialization::{
+        serialize_session_layout, session_layout_cache_dir, GlobalLayoutManifest,
+    },
 };
 
 pub type VteBytes = Vec<u8>;
@@ -31,6 +40,14 @@ pub enum ClientOrTabIndex {
     TabIndex(usize),
 }
 
+/// Where a pane opened by a plugin is placed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NewPanePlacement {
+    Tiled,
+    Floating,
+    InPlace, // temporarily replaces the focused pane until it is closed
+}
+
 /// Instructions related to PTYs (pseudoterminals).
 #[derive(Clone, Debug)]
 pub enum PtyInstruction {
@@ -41,6 +58,15 @@ pub enum PtyInstruction {
         ClientOrTabIndex,
     ), // bool (if Some) is
     // should_float, String is an optional pane name
+    SpawnTerminalForPlugin(
+        Option<TerminalAction>,
+        NewPanePlacement,
+        Option<String>,
+        ClientId,
+        PluginId,
+    ), // String is an optional pane name, the plugin is told the id of the new pane
+    SpawnInPlaceTerminal(Option<TerminalAction>, Option<String>, ClientId), // String is an
+    // optional pane name
     OpenInPlaceEditor(PathBuf, Option<usize>, ClientId), // Option<usize> is the optional line number
     SpawnTerminalVertically(Option<TerminalAction>, Option<String>, ClientId), // String is an
     // optional pane
@@ -62,6 +88,10 @@ pub enum PtyInstruction {
     ClosePane(PaneId),
     CloseTab(Vec<PaneId>),
     ReRunCommandInPane(PaneId, RunCommand),
+    SpawnScratchpad(Option<TerminalAction>, ClientId),
+    ReportTerminalProcesses,
+    DumpLayoutToHd(GlobalLayoutManifest, Option<ClientId>), // ClientId - the client to exit once the layout was dumped
+    DumpLayout(GlobalLayoutManifest, ClientId),
     Exit,
 }
 
@@ -69,6 +99,8 @@ impl From<&PtyInstruction> for PtyContext {
     fn from(pty_instruction: &PtyInstruction) -> Self {
         match *pty_instruction {
             PtyInstruction::SpawnTerminal(..) => PtyContext::SpawnTerminal,
+            PtyInstruction::SpawnTerminalForPlugin(..) => PtyContext::SpawnTerminalForPlugin,
+            PtyInstruction::SpawnInPlaceTerminal(..) => PtyContext::SpawnInPlaceTerminal,
             PtyInstruction::OpenInPlaceEditor(..) => PtyContext::OpenInPlaceEditor,
             PtyInstruction::SpawnTerminalVertically(..) => PtyContext::SpawnTerminalVertically,
             PtyInstruction::SpawnTerminalHorizontally(..) => PtyContext::SpawnTerminalHorizontally,
@@ -78,6 +110,10 @@ impl From<&PtyInstruction> for PtyContext {
             PtyInstruction::CloseTab(_) => PtyContext::CloseTab,
             PtyInstruction::NewTab(..) => PtyContext::NewTab,
             PtyInstruction::ReRunCommandInPane(..) => PtyContext::ReRunCommandInPane,
+            PtyInstruction::SpawnScratchpad(..) => PtyContext::SpawnScratchpad,
+            PtyInstruction::ReportTerminalProcesses => PtyContext::ReportTerminalProcesses,
+            PtyInstruction::DumpLayoutToHd(..) => PtyContext::DumpLayoutToHd,
+            PtyInstruction::DumpLayout(..) => PtyContext::DumpLayout,
             PtyInstruction::Exit => PtyContext::Exit,
         }
     }
@@ -164,6 +200,157 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::SpawnTerminalForPlugin(
+                terminal_action,
+                placement,
+                name,
+                client_id,
+                plugin_id,
+            ) => {
+                let err_context = || format!("failed to spawn terminal for plugin {plugin_id}");
+
+                let (hold_on_close, run_command, pane_title) = match &terminal_action {
+                    Some(TerminalAction::RunCommand(run_command)) => (
+                        run_command.hold_on_close,
+                        Some(run_command.clone()),
+                        Some(name.unwrap_or_else(|| run_command.to_string())),
+                    ),
+                    _ => (false, None, name),
+                };
+                let spawned_terminal = match pty
+                    .spawn_terminal(terminal_action, ClientOrTabIndex::ClientId(client_id))
+                    .with_context(err_context)
+                {
+                    Ok((pid, starts_held)) => {
+                        let hold_for_command = if starts_held {
+                            run_command.clone()
+                        } else {
+                            None
+                        };
+                        Some((pid, hold_for_command, false))
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) if hold_on_close => {
+                            let hold_for_command = None; // we do not hold an "error" pane
+                            Some((*terminal_id, hold_for_command, true))
+                        },
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            log::error!("Failed to spawn terminal: {:?}", err);
+                            pty.close_pane(PaneId::Terminal(*terminal_id))
+                                .with_context(err_context)?;
+                            None
+                        },
+                        _ => {
+                            Err::<(), _>(err).non_fatal();
+                            None
+                        },
+                    },
+                };
+                if let Some((terminal_id, hold_for_command, command_not_found)) = spawned_terminal {
+                    let pane_id = PaneId::Terminal(terminal_id);
+                    let screen_instruction = match placement {
+                        NewPanePlacement::InPlace => ScreenInstruction::ReplacePane(
+                            pane_id,
+                            pane_title,
+                            hold_for_command,
+                            client_id,
+                            Some(plugin_id),
+                        ),
+                        NewPanePlacement::Tiled | NewPanePlacement::Floating => {
+                            ScreenInstruction::NewPane(
+                                pane_id,
+                                pane_title,
+                                Some(placement == NewPanePlacement::Floating),
+                                hold_for_command,
+                                ClientOrTabIndex::ClientId(client_id),
+                            )
+                        },
+                    };
+                    pty.bus
+                        .senders
+                        .send_to_screen(screen_instruction)
+                        .with_context(err_context)?;
+                    if let Some(run_command) = run_command.filter(|_| command_not_found) {
+                        send_command_not_found_to_screen(
+                            pty.bus.senders.clone(),
+                            terminal_id,
+                            run_command,
+                            None,
+                        )
+                        .with_context(err_context)?;
+                    }
+                    pty.bus
+                        .senders
+                        .send_to_plugin(PluginInstruction::Update(vec![(
+                            Some(plugin_id),
+                            Some(client_id),
+                            Event::PaneOpened(terminal_id),
+                        )]))
+                        .with_context(err_context)?;
+                }
+            },
+            PtyInstruction::SpawnInPlaceTerminal(terminal_action, name, client_id) => {
+                let err_context =
+                    || format!("failed to spawn in-place terminal for client {client_id}");
+
+                let (hold_on_close, run_command, pane_title) = match &terminal_action {
+                    Some(TerminalAction::RunCommand(run_command)) => (
+                        run_command.hold_on_close,
+                        Some(run_command.clone()),
+                        Some(name.unwrap_or_else(|| run_command.to_string())),
+                    ),
+                    _ => (false, None, name),
+                };
+                match pty
+                    .spawn_terminal(terminal_action, ClientOrTabIndex::ClientId(client_id))
+                    .with_context(err_context)
+                {
+                    Ok((pid, starts_held)) => {
+                        let hold_for_command = if starts_held { run_command } else { None };
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::ReplacePane(
+                                PaneId::Terminal(pid),
+                                pane_title,
+                                hold_for_command,
+                                client_id,
+                                None,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(err) => match err.downcast_ref::<ZellijError>() {
+                        Some(ZellijError::CommandNotFound { terminal_id, .. }) => {
+                            if hold_on_close {
+                                let hold_for_command = None; // we do not hold an "error" pane
+                                pty.bus
+                                    .senders
+                                    .send_to_screen(ScreenInstruction::ReplacePane(
+                                        PaneId::Terminal(*terminal_id),
+                                        pane_title,
+                                        hold_for_command,
+                                        client_id,
+                                        None,
+                                    ))
+                                    .with_context(err_context)?;
+                                if let Some(run_command) = run_command {
+                                    send_command_not_found_to_screen(
+                                        pty.bus.senders.clone(),
+                                        *terminal_id,
+                                        run_command.clone(),
+                                        None,
+                                    )
+                                    .with_context(err_context)?;
+                                }
+                            } else {
+                                log::error!("Failed to spawn terminal: {:?}", err);
+                                pty.close_pane(PaneId::Terminal(*terminal_id))
+                                    .with_context(err_context)?;
+                            }
+                        },
+                        _ => Err::<(), _>(err).non_fatal(),
+                    },
+                }
+            },
             PtyInstruction::OpenInPlaceEditor(temp_file, line_number, client_id) => {
                 let err_context =
                     || format!("failed to open in-place editor for client {}", client_id);
@@ -186,6 +373,68 @@ pub(crate) fn pty_thread_main(mut pty: Pty, layout: Box<Layout>) -> Result<()> {
                     },
                 }
             },
+            PtyInstruction::SpawnScratchpad(terminal_action, client_id) => {
+                let err_context = || format!("failed to spawn scratchpad for client {client_id}");
+
+                match pty.spawn_terminal(terminal_action, ClientOrTabIndex::ClientId(client_id)) {
+                    Ok((pid, _starts_held)) => {
+                        pty.bus
+                            .senders
+                            .send_to_screen(ScreenInstruction::NewScratchpadPane(
+                                PaneId::Terminal(pid),
+                                client_id,
+                            ))
+                            .with_context(err_context)?;
+                    },
+                    Err(e) => {
+                        Err::<(), _>(e).with_context(err_context).non_fatal();
+                    },
+                }
+            },
+            PtyInstruction::ReportTerminalProcesses => {
+                pty.report_terminal_processes()?;
+            },
+            PtyInstruction::DumpLayoutToHd(mut global_layout_manifest, client_to_exit) => {
+                global_layout_manifest.default_layout = layout.clone();
+                let session_name = global_layout_manifest.session_name.clone();
+                let layout_dir = session_layout_cache_dir(&session_name);
+                match serialize_session_layout(global_layout_manifest, &layout_dir) {
+                    Ok((session_layout, pane_contents)) => {
+                        pty.bus
+                            .senders
+                            .send_to_background_jobs(BackgroundJob::WriteSessionLayout(
+                                session_name,
+                                session_layout,
+                                pane_contents,
+                            ))
+                            .context("failed to dump layout to hd")?;
+                    },
+                    Err(e) => {
+                        log::error!("Failed to serialize session layout: {}", e);
+                    },
+                }
+                if let Some(client_id) = client_to_exit {
+                    // the layout is written by the background jobs thread, which finishes its
+                    // pending jobs before the session exits
+                    pty.bus
+                        .senders
+                        .send_to_server(ServerInstruction::ClientExit(client_id))
+                        .context("failed to exit client after dumping layout to hd")?;
+                }
+            },
+            PtyInstruction::DumpLayout(mut global_layout_manifest, client_id) => {
+                global_layout_manifest.default_layout = layout.clone();
+                let layout_dir = session_layout_cache_dir(&global_layout_manifest.session_name);
+                let log_lines = match serialize_session_layout(global_layout_manifest, &layout_dir)
+                {
+                    Ok((session_layout, _pane_contents)) => vec![session_layout],
+                    Err(e) => vec![format!("Failed to serialize session layout: {}", e)],
+                };
+                pty.bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(log_lines, client_id))
+                    .with_context(|| format!("failed to dump layout for client {client_id}"))?;
+            },
             PtyInstruction::SpawnTerminalVertically(terminal_action, name, client_id) => {
                 let err_context =
                     || format!("failed to spawn terminal vertically for client {client_id}");
@@ -476,12 +725,35 @@ impl Pty {
                     args: vec![],
                     command: shell,
                     cwd, // note: this might also be filled by the calling function, eg. spawn_terminal
+                    env: BTreeMap::new(),
                     hold_on_close: false,
                     hold_on_start: false,
+                    waiting_client: None,
                 })
             },
         }
     }
+    pub fn report_terminal_processes(&self) -> Result<()> {
+        let terminal_processes = self
+            .bus
+            .os_input
+            .as_ref()
+            .map(|input| {
+                let terminal_id_to_pid = self
+                    .id_to_child_pid
+                    .iter()
+                    .map(|(terminal_id, pid)| (*terminal_id, Pid::from_raw(*pid)))
+                    .collect();
+                input.get_terminal_processes(&terminal_id_to_pid)
+            })
+            .unwrap_or_default();
+        self.bus
+            .senders
+            .send_to_screen(ScreenInstruction::UpdateTerminalProcesses(
+                terminal_processes,
+            ))
+            .context("failed to report terminal processes")
+    }
     fn fill_cwd(&self, terminal_action: &mut TerminalAction, client_id: ClientId) {
         if let TerminalAction::RunCommand(run_command) = terminal_action {
             if run_command.cwd.is_none() {
@@ -541,9 +813,14 @@ impl Pty {
             return Ok((terminal_id, starts_held));
         }
 
+        let waiting_client = match &terminal_action {
+            TerminalAction::RunCommand(run_command) => run_command.waiting_client,
+            _ => None,
+        };
         let quit_cb = Box::new({
             let senders = self.bus.senders.clone();
-            move |pane_id, exit_status, command| {
+            move |pane_id, exit_status, command: RunCommand| {
+                report_exit_status_to_waiting_client(&senders, &command, exit_status);
                 if hold_on_close {
                     let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                         pane_id,
@@ -565,6 +842,16 @@ impl Pty {
             .and_then(|os_input| {
                 os_input.spawn_terminal(terminal_action, quit_cb, self.default_editor.clone())
             })
+            .map_err(|e| {
+                if let Some(client_id) = waiting_client {
+                    // the command never ran, so it has no exit status
+                    let _ = self
+                        .bus
+                        .senders
+                        .send_to_server(ServerInstruction::CommandExited(None, client_id));
+                }
+                e
+            })
             .with_context(err_context)?;
         let terminal_bytes = task::spawn({
             let err_context =
@@ -923,6 +1210,11 @@ impl Pty {
                     .context("no OS I/O interface found")
                     .and_then(|os_input| os_input.clear_terminal_id(id))
                     .with_context(err_context)?;
+                drop(
+                    self.bus
+                        .senders
+                        .send_to_screen(ScreenInstruction::RemovePaneOutputSubscriptionsOfPane(id)),
+                );
             },
             PaneId::Plugin(pid) => drop(
                 self.bus
@@ -959,7 +1251,9 @@ impl Pty {
                 let hold_on_close = run_command.hold_on_close;
                 let quit_cb = Box::new({
                     let senders = self.bus.senders.clone();
-                    move |pane_id, exit_status, command| {
+                    move |pane_id, exit_status, command: RunCommand| {
+                        // this is also where commands started suspended first run
+                        report_exit_status_to_waiting_client(&senders, &command, exit_status);
                         if hold_on_close {
                             let _ = senders.send_to_screen(ScreenInstruction::HoldPane(
                                 pane_id,
@@ -1024,6 +1318,16 @@ impl Drop for Pty {
     }
 }
 
+fn report_exit_status_to_waiting_client(
+    senders: &ThreadSenders,
+    run_command: &RunCommand,
+    exit_status: Option<i32>,
+) {
+    if let Some(client_id) = run_command.waiting_client {
+        let _ = senders.send_to_server(ServerInstruction::CommandExited(exit_status, client_id));
+    }
+}
+
 fn send_command_not_found_to_screen(
     senders: ThreadSenders,
     terminal_id: u32,
@@ -1,7 +1,18 @@
+use std::time::Duration;
 use zellij_utils::errors::{prelude::*, ContextType, PtyWriteContext};
+use zellij_utils::nix::errno::Errno;
 
+use crate::os_input_output::ServerOsApi;
 use crate::thread_bus::Bus;
 
+// large writes (eg. pastes of a few megabytes) are split into chunks of this size and we wait for
+// each chunk to be drained before writing the next one, otherwise the pty buffer fills up faster
+// than the application inside it reads and input gets dropped
+const PTY_WRITE_CHUNK_SIZE: usize = 4096;
+// how long to wait for the pty to accept more input when it's full, before giving up on the write
+const PTY_WRITE_RETRY_INTERVAL: Duration = Duration::from_millis(10);
+const MAX_PTY_WRITE_RETRIES: usize = 1000;
+
 // we separate these instruction to a different thread because some programs get deadlocked if
 // you write into their STDIN while reading from their STDOUT (I'm looking at you, vim)
 // while the same has not been observed to happen with resizes, it could conceivably happen and we have this
@@ -40,12 +51,7 @@ pub(crate) fn pty_writer_main(bus: Bus<PtyWriteInstruction>) -> Result<()> {
             .with_context(err_context)?;
         match event {
             PtyWriteInstruction::Write(bytes, terminal_id) => {
-                os_input
-                    .write_to_tty_stdin(terminal_id, &bytes)
-                    .with_context(err_context)
-                    .non_fatal();
-                os_input
-                    .tcdrain(terminal_id)
+                write_in_chunks(&*os_input, terminal_id, &bytes)
                     .with_context(err_context)
                     .non_fatal();
             },
@@ -87,3 +93,51 @@ pub(crate) fn pty_writer_main(bus: Bus<PtyWriteInstruction>) -> Result<()> {
         }
     }
 }
+
+pub(crate) fn write_in_chunks(
+    os_input: &dyn ServerOsApi,
+    terminal_id: u32,
+    bytes: &[u8],
+) -> Result<()> {
+    let err_context = || {
+        format!(
+            "failed to write {} bytes to terminal {}",
+            bytes.len(),
+            terminal_id
+        )
+    };
+
+    for chunk in bytes.chunks(PTY_WRITE_CHUNK_SIZE) {
+        let mut written = 0;
+        let mut retries = 0;
+        while written < chunk.len() {
+            match os_input.write_to_tty_stdin(terminal_id, &chunk[written..]) {
+                Ok(0) => {},
+                Ok(count) => {
+                    written += count;
+                    retries = 0;
+                    continue;
+                },
+                Err(e) => match e.downcast_ref::<Errno>() {
+                    Some(Errno::EAGAIN) | Some(Errno::EINTR) => {},
+                    _ => return Err(e).with_context(err_context),
+                },
+            }
+            // the pty did not accept any input, give the application some time to read
+            retries += 1;
+            if retries > MAX_PTY_WRITE_RETRIES {
+                return Err(anyhow!(
+                    "timed out waiting for the terminal to accept input"
+                ))
+                .with_context(err_context);
+            }
+            std::thread::sleep(PTY_WRITE_RETRY_INTERVAL);
+        }
+        os_input.tcdrain(terminal_id).with_context(err_context)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[path = "./unit/pty_writer_tests.rs"]
+mod pty_writer_tests;
@@ -12,12 +12,12 @@ use crate::{
 };
 use zellij_utils::{
     channels::SenderWithContext,
-    data::{Direction, Event, PluginCapabilities, ResizeStrategy},
+    data::{Direction, Event, InputMode, PluginCapabilities, ResizeStrategy},
     errors::prelude::*,
     input::{
         actions::{Action, SearchDirection, SearchOption},
-        command::TerminalAction,
-        get_mode_info,
+        command::{RunCommand, TerminalAction},
+        get_custom_mode_info, get_mode_info,
         layout::Layout,
     },
     ipc::{
@@ -39,11 +39,11 @@ pub(crate) fn route_action(
     let mut should_break = false;
     let err_context = || format!("failed to route action for client {client_id}");
 
-    // forward the action to plugins unless it is a mousehold
+    // forward the action to plugins unless it is a mousehold or a mouse hover
     // this is a bit of a hack around the unfortunate architecture we use with plugins
     // this will change as soon as we refactor
     match action {
-        Action::MouseHoldLeft(..) | Action::MouseHoldRight(..) => {},
+        Action::MouseHoldLeft(..) | Action::MouseHoldRight(..) | Action::MouseHover(..) => {},
         _ => {
             senders
                 .send_to_plugin(PluginInstruction::Update(vec![(
@@ -78,6 +78,25 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::WriteCharacter(val, client_id))
                 .with_context(err_context)?;
         },
+        Action::WriteToPaneId(val, terminal_pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::WriteToPaneId(
+                    val,
+                    terminal_pane_id,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::WriteCharsToPaneId(val, terminal_pane_id) => {
+            let val = val.into_bytes();
+            senders
+                .send_to_screen(ScreenInstruction::WriteToPaneId(
+                    val,
+                    terminal_pane_id,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::SwitchToMode(mode) => {
             let attrs = &client_attributes;
             // TODO: use the palette from the client and remove it from the server os api
@@ -101,6 +120,23 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::Render)
                 .with_context(err_context)?;
         },
+        Action::SwitchToCustomMode(custom_mode) => {
+            let attrs = &client_attributes;
+            let mode_info = get_custom_mode_info(custom_mode, attrs, capabilities);
+            senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::ModeUpdate(mode_info.clone()),
+                )]))
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::ChangeMode(mode_info, client_id))
+                .with_context(err_context)?;
+            senders
+                .send_to_screen(ScreenInstruction::Render)
+                .with_context(err_context)?;
+        },
         Action::Resize(resize, direction) => {
             let screen_instr =
                 ScreenInstruction::Resize(client_id, ResizeStrategy::new(resize, direction));
@@ -134,6 +170,13 @@ pub(crate) fn route_action(
                 .send_to_screen(screen_instr)
                 .with_context(err_context)?;
         },
+        Action::MoveFocusOrDelegate(direction, key_bytes) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveFocusOrDelegate(
+                    direction, key_bytes, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::MoveFocusOrTab(direction) => {
             let screen_instr = match direction {
                 Direction::Left => ScreenInstruction::MoveFocusLeftOrPreviousTab(client_id),
@@ -167,9 +210,9 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ClearScreen(client_id))
                 .with_context(err_context)?;
         },
-        Action::DumpScreen(val, full) => {
+        Action::DumpScreen(val, full, ansi) => {
             senders
-                .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full))
+                .send_to_screen(ScreenInstruction::DumpScreen(val, client_id, full, ansi))
                 .with_context(err_context)?;
         },
         Action::EditScrollback => {
@@ -177,6 +220,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::EditScrollback(client_id))
                 .with_context(err_context)?;
         },
+        Action::EnterCopyMode => {
+            senders
+                .send_to_screen(ScreenInstruction::EnterCopyMode(client_id))
+                .with_context(err_context)?;
+        },
+        Action::LinkHints => {
+            senders
+                .send_to_screen(ScreenInstruction::ShowLinkHints(client_id))
+                .with_context(err_context)?;
+        },
         Action::ScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::ScrollUp(client_id))
@@ -197,6 +250,16 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollDownAt(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::ForceScrollUpAt(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::ForceScrollUpAt(point, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ForceScrollDownAt(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::ForceScrollDownAt(point, client_id))
+                .with_context(err_context)?;
+        },
         Action::ScrollToBottom => {
             senders
                 .send_to_screen(ScreenInstruction::ScrollToBottom(client_id))
@@ -207,6 +270,21 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ScrollToTop(client_id))
                 .with_context(err_context)?;
         },
+        Action::ScrollToPreviousPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToPreviousPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ScrollToNextPrompt => {
+            senders
+                .send_to_screen(ScreenInstruction::ScrollToNextPrompt(client_id))
+                .with_context(err_context)?;
+        },
+        Action::CopyLastCommandOutput => {
+            senders
+                .send_to_screen(ScreenInstruction::CopyLastCommandOutput(client_id))
+                .with_context(err_context)?;
+        },
         Action::PageScrollUp => {
             senders
                 .send_to_screen(ScreenInstruction::PageScrollUp(client_id))
@@ -232,6 +310,21 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ToggleActiveTerminalFullscreen(client_id))
                 .with_context(err_context)?;
         },
+        Action::ToggleExpandPane => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleExpandPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::FocusNextPaneInStack => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusNextPaneInStack(client_id))
+                .with_context(err_context)?;
+        },
+        Action::FocusPreviousPaneInStack => {
+            senders
+                .send_to_screen(ScreenInstruction::FocusPreviousPaneInStack(client_id))
+                .with_context(err_context)?;
+        },
         Action::TogglePaneFrames => {
             senders
                 .send_to_screen(ScreenInstruction::TogglePaneFrames)
@@ -312,7 +405,9 @@ pub(crate) fn route_action(
         Action::NewFloatingPane(run_command, name) => {
             let should_float = true;
             let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
+                .map(|cmd| {
+                    TerminalAction::RunCommand(RunCommand::from_action_for_client(cmd, client_id))
+                })
                 .or_else(|| default_shell.clone());
             senders
                 .send_to_pty(PtyInstruction::SpawnTerminal(
@@ -323,10 +418,24 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::NewInPlacePane(run_command, name) => {
+            let run_cmd = run_command
+                .map(|cmd| {
+                    TerminalAction::RunCommand(RunCommand::from_action_for_client(cmd, client_id))
+                })
+                .or_else(|| default_shell.clone());
+            senders
+                .send_to_pty(PtyInstruction::SpawnInPlaceTerminal(
+                    run_cmd, name, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::NewTiledPane(direction, run_command, name) => {
             let should_float = false;
             let run_cmd = run_command
-                .map(|cmd| TerminalAction::RunCommand(cmd.into()))
+                .map(|cmd| {
+                    TerminalAction::RunCommand(RunCommand::from_action_for_client(cmd, client_id))
+                })
                 .or_else(|| default_shell.clone());
             let pty_instr = match direction {
                 Some(Direction::Left) => {
@@ -356,6 +465,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::TogglePaneEmbedOrFloating(client_id))
                 .with_context(err_context)?;
         },
+        Action::TogglePanePinned => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePanePinned(client_id))
+                .with_context(err_context)?;
+        },
         Action::ToggleFloatingPanes => {
             senders
                 .send_to_screen(ScreenInstruction::ToggleFloatingPanes(
@@ -375,7 +489,9 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
         },
         Action::Run(command) => {
-            let run_cmd = Some(TerminalAction::RunCommand(command.clone().into()));
+            let run_cmd = Some(TerminalAction::RunCommand(
+                RunCommand::from_action_for_client(command.clone(), client_id),
+            ));
             let pty_instr = match command.direction {
                 Some(Direction::Left) => {
                     PtyInstruction::SpawnTerminalVertically(run_cmd, None, client_id)
@@ -443,11 +559,71 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::ToggleActiveSyncTab(client_id))
                 .with_context(err_context)?;
         },
+        Action::ToggleActivePaneSync => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleActivePaneSync(client_id))
+                .with_context(err_context)?;
+        },
+        Action::TogglePaneInGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::TogglePaneInGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ClearPaneGroup => {
+            senders
+                .send_to_screen(ScreenInstruction::ClearPaneGroup(client_id))
+                .with_context(err_context)?;
+        },
+        Action::CloseGroupedPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::CloseGroupedPanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::FloatGroupedPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::FloatGroupedPanes(client_id))
+                .with_context(err_context)?;
+        },
+        Action::BreakGroupedPanesToNewTab => {
+            senders
+                .send_to_screen(ScreenInstruction::BreakGroupedPanesToNewTab(
+                    default_layout.clone(),
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SyncGroupedPanes => {
+            senders
+                .send_to_screen(ScreenInstruction::SyncGroupedPanes(client_id))
+                .with_context(err_context)?;
+        },
         Action::CloseTab => {
             senders
                 .send_to_screen(ScreenInstruction::CloseTab(client_id))
                 .with_context(err_context)?;
         },
+        Action::ReopenClosedPane => {
+            senders
+                .send_to_screen(ScreenInstruction::ReopenClosedPane(
+                    default_shell.clone(),
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::ReopenClosedTab => {
+            let swap_layouts = (
+                default_layout.swap_tiled_layouts.clone(),
+                default_layout.swap_floating_layouts.clone(),
+            );
+            senders
+                .send_to_screen(ScreenInstruction::ReopenClosedTab(
+                    default_shell.clone(),
+                    swap_layouts,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::GoToTab(i) => {
             senders
                 .send_to_screen(ScreenInstruction::GoToTab(i, Some(client_id)))
@@ -489,11 +665,32 @@ pub(crate) fn route_action(
                 .with_context(err_context)?;
             should_break = true;
         },
+        Action::SerializeSessionAndQuit => {
+            senders
+                .send_to_screen(ScreenInstruction::SerializeSessionAndQuit(client_id))
+                .with_context(err_context)?;
+            should_break = true;
+        },
         Action::LeftClick(point) => {
             senders
                 .send_to_screen(ScreenInstruction::LeftClick(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::CtrlLeftClick(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::CtrlLeftClick(point, client_id))
+                .with_context(err_context)?;
+        },
+        Action::ShiftLeftClick(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::ShiftLeftClick(point, client_id))
+                .with_context(err_context)?;
+        },
+        Action::AltLeftClick(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::AltLeftClick(point, client_id))
+                .with_context(err_context)?;
+        },
         Action::RightClick(point) => {
             senders
                 .send_to_screen(ScreenInstruction::RightClick(point, client_id))
@@ -534,6 +731,11 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::MouseHoldMiddle(point, client_id))
                 .with_context(err_context)?;
         },
+        Action::MouseHover(point) => {
+            senders
+                .send_to_screen(ScreenInstruction::MouseHover(point, client_id))
+                .with_context(err_context)?;
+        },
         Action::Copy => {
             senders
                 .send_to_screen(ScreenInstruction::Copy(client_id))
@@ -560,6 +762,17 @@ pub(crate) fn route_action(
             _ => {},
         },
         Action::NoOp => {},
+        // handled by the client, which sends the keys typed so far as Action::PendingKeys
+        Action::KeySequence(..) => {},
+        Action::PendingKeys(pending_keys) => {
+            senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    None,
+                    Some(client_id),
+                    Event::PendingKeys(pending_keys),
+                )]))
+                .with_context(err_context)?;
+        },
         Action::SearchInput(c) => {
             senders
                 .send_to_screen(ScreenInstruction::UpdateSearch(c, client_id))
@@ -581,6 +794,7 @@ pub(crate) fn route_action(
                 },
                 SearchOption::WholeWord => ScreenInstruction::SearchToggleWholeWord(client_id),
                 SearchOption::Wrap => ScreenInstruction::SearchToggleWrap(client_id),
+                SearchOption::Regex => ScreenInstruction::SearchToggleRegex(client_id),
             };
             senders
                 .send_to_screen(instruction)
@@ -616,6 +830,13 @@ pub(crate) fn route_action(
                 ))
                 .with_context(err_context)?;
         },
+        Action::NewInPlacePluginPane(run_plugin, name) => {
+            senders
+                .send_to_screen(ScreenInstruction::NewInPlacePluginPane(
+                    run_plugin, name, client_id,
+                ))
+                .with_context(err_context)?;
+        },
         Action::StartOrReloadPlugin(run_plugin) => {
             senders
                 .send_to_screen(ScreenInstruction::StartOrReloadPluginPane(run_plugin, None))
@@ -710,6 +931,92 @@ pub(crate) fn route_action(
                 .send_to_screen(ScreenInstruction::BreakPaneLeft(client_id))
                 .with_context(err_context)?;
         },
+        Action::MovePaneToTab(tab_index) => {
+            senders
+                .send_to_screen(ScreenInstruction::MovePaneToTab(tab_index, client_id))
+                .with_context(err_context)?;
+        },
+        Action::MoveTab(direction) => {
+            senders
+                .send_to_screen(ScreenInstruction::MoveTab(direction, client_id))
+                .with_context(err_context)?;
+        },
+        Action::RespawnPane => {
+            senders
+                .send_to_screen(ScreenInstruction::RespawnPane(client_id))
+                .with_context(err_context)?;
+        },
+        Action::SwitchWorkspace => {
+            senders
+                .send_to_screen(ScreenInstruction::SwitchWorkspace(client_id))
+                .with_context(err_context)?;
+        },
+        Action::QueryPaneProcesses => {
+            senders
+                .send_to_screen(ScreenInstruction::QueryPaneProcesses(client_id))
+                .with_context(err_context)?;
+        },
+        Action::LoadBuffer(contents) => {
+            senders
+                .send_to_screen(ScreenInstruction::LoadBuffer(contents, client_id))
+                .with_context(err_context)?;
+        },
+        Action::SaveBuffer(file_path) => {
+            senders
+                .send_to_screen(ScreenInstruction::SaveBuffer(file_path, client_id))
+                .with_context(err_context)?;
+        },
+        Action::CliPipe {
+            name,
+            payload,
+            args,
+            plugin,
+            pane_id,
+        } => match pane_id {
+            Some(terminal_pane_id) => {
+                senders
+                    .send_to_screen(ScreenInstruction::PipeToPane(
+                        terminal_pane_id,
+                        payload.unwrap_or_default(),
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
+            },
+            None => {
+                senders
+                    .send_to_plugin(PluginInstruction::CliPipe {
+                        name,
+                        payload,
+                        args: args.unwrap_or_default(),
+                        plugin,
+                        client_id,
+                    })
+                    .with_context(err_context)?;
+            },
+        },
+        Action::PasteBuffer(terminal_pane_id) => {
+            senders
+                .send_to_screen(ScreenInstruction::PasteBuffer(terminal_pane_id, client_id))
+                .with_context(err_context)?;
+        },
+        Action::DumpLayout => {
+            senders
+                .send_to_screen(ScreenInstruction::DumpLayout(client_id))
+                .with_context(err_context)?;
+        },
+        Action::ToggleScratchpad => {
+            senders
+                .send_to_screen(ScreenInstruction::ToggleScratchpad(
+                    client_id,
+                    default_shell,
+                ))
+                .with_context(err_context)?;
+        },
+        Action::SwitchTheme(theme_name) => {
+            senders
+                .send_to_server(ServerInstruction::SwitchTheme(theme_name))
+                .with_context(err_context)?;
+        },
     }
     Ok(should_break)
 }
@@ -755,7 +1062,12 @@ pub(crate) fn route_thread_main(
                         ClientToServerMsg::Action(action, maybe_client_id) => {
                             let client_id = maybe_client_id.unwrap_or(client_id);
                             if let Some(rlocked_sessions) = rlocked_sessions.as_ref() {
-                                if let Action::SwitchToMode(input_mode) = action {
+                                let switch_to_mode = match &action {
+                                    Action::SwitchToMode(input_mode) => Some(*input_mode),
+                                    Action::SwitchToCustomMode(_) => Some(InputMode::Custom),
+                                    _ => None,
+                                };
+                                if let Some(input_mode) = switch_to_mode {
                                     let send_res = os_input.send_to_client(
                                         client_id,
                                         ServerToClientMsg::SwitchToMode(input_mode),
@@ -1,18 +1,24 @@
 //! Things related to [`Screen`]s.
 
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::str;
+use std::time::Duration;
 
 use zellij_utils::data::{
-    Direction, PaneManifest, PluginPermission, Resize, ResizeStrategy, SessionInfo,
+    ConnectToSession, Direction, PaneManifest, PluginPermission, Resize, ResizeStrategy,
+    SessionInfo,
 };
 use zellij_utils::errors::prelude::*;
 use zellij_utils::input::command::RunCommand;
 use zellij_utils::input::options::Clipboard;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::session_serialization::{
+    is_valid_session_name, serialize_session_layout, session_layout_cache_dir,
+    GlobalLayoutManifest, PaneLayoutManifest, TabLayoutManifest,
+};
 use zellij_utils::{
     input::command::TerminalAction,
     input::layout::{
@@ -23,29 +29,37 @@ use zellij_utils::{
 };
 
 use crate::background_jobs::BackgroundJob;
-use crate::os_input_output::ResizeCache;
+use crate::os_input_output::{ResizeCache, TerminalProcesses};
 use crate::panes::alacritty_functions::xparse_color;
 use crate::panes::terminal_character::AnsiCode;
 
 use crate::{
     output::Output,
+    panes::pane_output_decoder::PaneOutputDecoder,
     panes::sixel::SixelImageStore,
     panes::PaneId,
-    plugins::PluginInstruction,
-    pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
-    tab::Tab,
+    plugins::{PluginId, PluginInstruction},
+    pty::{get_default_shell, ClientOrTabIndex, PtyInstruction, VteBytes},
+    tab::{LinkOpener, Pane, Tab},
     thread_bus::Bus,
     ui::{
         loading_indication::LoadingIndication,
-        overlay::{Overlay, OverlayWindow},
+        overlay::{
+            close_pane_confirmation::{ClosePaneConfirmation, ClosePaneConfirmationInput},
+            link_hints::{LinkHints, LinkHintsInput},
+            Overlay, OverlayType, OverlayWindow, Overlayable,
+        },
     },
     ClientId, ServerInstruction,
 };
 use zellij_utils::{
+    consts::{MONITOR_SILENCE, PANE_CLOSE_CONFIRMATION, ZELLIJ_SOCK_DIR},
     data::{Event, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, TabInfo},
+    envs,
     errors::{ContextType, ScreenContext},
-    input::{get_mode_info, options::Options},
+    input::{get_mode_info, keybinds::Keybinds, options::Options, theme::Theme},
     ipc::{ClientAttributes, PixelDimensions, ServerToClientMsg},
+    shared::{default_palette, detect_theme_hue},
 };
 
 /// Get the active tab and call a closure on it
@@ -126,6 +140,9 @@ macro_rules! active_tab_and_connected_client_id {
     };
 }
 
+const SCRATCHPAD_PANE_TITLE: &str = "Scratchpad";
+const MAX_REOPENABLE_PANES_AND_TABS: usize = 20; // how many closed panes (and tabs) are remembered
+
 type InitialTitle = String;
 type ShouldFloat = bool;
 type HoldForCommand = Option<RunCommand>;
@@ -144,7 +161,15 @@ pub enum ScreenInstruction {
         ClientOrTabIndex,
     ),
     OpenInPlaceEditor(PaneId, ClientId),
+    ReplacePane(
+        PaneId,
+        Option<InitialTitle>,
+        HoldForCommand,
+        ClientId,
+        Option<u32>, // the plugin that opened this pane, if any, to be told when it is closed
+    ),
     TogglePaneEmbedOrFloating(ClientId),
+    TogglePanePinned(ClientId),
     ToggleFloatingPanes(ClientId, Option<TerminalAction>),
     HorizontalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
     VerticalSplit(PaneId, Option<InitialTitle>, HoldForCommand, ClientId),
@@ -159,6 +184,7 @@ pub enum ScreenInstruction {
     MoveFocusUp(ClientId),
     MoveFocusRight(ClientId),
     MoveFocusRightOrNextTab(ClientId),
+    MoveFocusOrDelegate(Direction, Vec<u8>, ClientId), // Vec<u8> is the pressed key
     MovePane(ClientId),
     MovePaneBackwards(ClientId),
     MovePaneUp(ClientId),
@@ -167,14 +193,21 @@ pub enum ScreenInstruction {
     MovePaneLeft(ClientId),
     Exit,
     ClearScreen(ClientId),
-    DumpScreen(String, ClientId, bool),
+    DumpScreen(String, ClientId, bool, bool), // file, client_id, full, ansi
     EditScrollback(ClientId),
+    EnterCopyMode(ClientId),
+    ShowLinkHints(ClientId),
     ScrollUp(ClientId),
     ScrollUpAt(Position, ClientId),
     ScrollDown(ClientId),
     ScrollDownAt(Position, ClientId),
+    ForceScrollUpAt(Position, ClientId),
+    ForceScrollDownAt(Position, ClientId),
     ScrollToBottom(ClientId),
     ScrollToTop(ClientId),
+    ScrollToPreviousPrompt(ClientId),
+    ScrollToNextPrompt(ClientId),
+    CopyLastCommandOutput(ClientId),
     PageScrollUp(ClientId),
     PageScrollDown(ClientId),
     HalfPageScrollUp(ClientId),
@@ -182,6 +215,9 @@ pub enum ScreenInstruction {
     ClearScroll(ClientId),
     CloseFocusedPane(ClientId),
     ToggleActiveTerminalFullscreen(ClientId),
+    ToggleExpandPane(ClientId),
+    FocusNextPaneInStack(ClientId),
+    FocusPreviousPaneInStack(ClientId),
     TogglePaneFrames,
     SetSelectable(PaneId, bool, usize),
     ClosePane(PaneId, Option<ClientId>),
@@ -215,7 +251,20 @@ pub enum ScreenInstruction {
     SwitchTabNext(ClientId),
     SwitchTabPrev(ClientId),
     ToggleActiveSyncTab(ClientId),
+    ToggleActivePaneSync(ClientId),
+    TogglePaneInGroup(ClientId),
+    ClearPaneGroup(ClientId),
+    CloseGroupedPanes(ClientId),
+    FloatGroupedPanes(ClientId),
+    BreakGroupedPanesToNewTab(Box<Layout>, Option<TerminalAction>, ClientId), // default layout, default shell
+    SyncGroupedPanes(ClientId),
     CloseTab(ClientId),
+    ReopenClosedPane(Option<TerminalAction>, ClientId), // Option<TerminalAction> is the default shell
+    ReopenClosedTab(
+        Option<TerminalAction>,                          // default_shell
+        (Vec<SwapTiledLayout>, Vec<SwapFloatingLayout>), // swap layouts
+        ClientId,
+    ),
     GoToTab(u32, Option<ClientId>), // this Option is a hacky workaround, please do not copy this behaviour
     GoToTabName(
         String,
@@ -234,7 +283,12 @@ pub enum ScreenInstruction {
     TerminalColorRegisters(Vec<(usize, String)>),
     ChangeMode(ModeInfo, ClientId),
     ChangeModeForAllClients(ModeInfo),
+    RebindKeys(Keybinds),
+    Reconfigure(Keybinds, Style, PluginCapabilities, Option<bool>), // Option<bool> - draw pane frames, if changed
     LeftClick(Position, ClientId),
+    CtrlLeftClick(Position, ClientId),
+    ShiftLeftClick(Position, ClientId),
+    AltLeftClick(Position, ClientId),
     RightClick(Position, ClientId),
     MiddleClick(Position, ClientId),
     LeftMouseRelease(Position, ClientId),
@@ -243,6 +297,7 @@ pub enum ScreenInstruction {
     MouseHoldLeft(Position, ClientId),
     MouseHoldRight(Position, ClientId),
     MouseHoldMiddle(Position, ClientId),
+    MouseHover(Position, ClientId),
     Copy(ClientId),
     AddClient(
         ClientId,
@@ -259,6 +314,7 @@ pub enum ScreenInstruction {
     SearchUp(ClientId),
     SearchToggleCaseSensitivity(ClientId),
     SearchToggleWholeWord(ClientId),
+    SearchToggleRegex(ClientId),
     SearchToggleWrap(ClientId),
     AddRedPaneFrameColorOverride(Vec<PaneId>, Option<String>), // Option<String> => optional error text
     ClearPaneFrameColorOverride(Vec<PaneId>),
@@ -269,13 +325,17 @@ pub enum ScreenInstruction {
     // optional pane title
     NewFloatingPluginPane(RunPlugin, Option<String>, ClientId), // Option<String> is an
     // optional pane title
+    NewInPlacePluginPane(RunPlugin, Option<String>, ClientId), // Option<String> is an
+    // optional pane title
     StartOrReloadPluginPane(RunPlugin, Option<String>),
     AddPlugin(
         Option<bool>, // should_float
+        bool,         // should be opened in place
         RunPlugin,
         Option<String>, // pane title
         usize,          // tab index
         u32,            // plugin id
+        Option<ClientId>,
     ),
     UpdatePluginLoadingStage(u32, LoadingIndication), // u32 - plugin_id
     StartPluginLoadingIndication(u32, LoadingIndication), // u32 - plugin_id
@@ -293,7 +353,35 @@ pub enum ScreenInstruction {
     BreakPane(Box<Layout>, Option<TerminalAction>, ClientId),
     BreakPaneRight(ClientId),
     BreakPaneLeft(ClientId),
-    UpdateSessionInfos(BTreeMap<String, SessionInfo>), // String is the session name
+    MovePaneToTab(u32, ClientId),
+    MoveTab(Direction, ClientId),
+    MoveTabToIndex(usize, usize), // tab position, new tab position
+    CloseTabAtPosition(usize),
+    RespawnPane(ClientId),
+    UpdateSessionInfos(
+        BTreeMap<String, SessionInfo>, // String is the session name
+        Vec<(String, Duration)>,       // resurrectable session names and their age
+    ),
+    MarkSilentPanes,
+    SwitchWorkspace(ClientId),
+    ToggleScratchpad(ClientId, Option<TerminalAction>),
+    NewScratchpadPane(PaneId, ClientId),
+    UpdateTerminalProcesses(HashMap<u32, TerminalProcesses>), // u32 is the terminal pane id
+    LoadBuffer(String, ClientId),
+    SaveBuffer(String, ClientId),          // String is the file path
+    PasteBuffer(Option<u32>, ClientId),    // u32 is the terminal pane id
+    PipeToPane(u32, String, ClientId),     // terminal pane id, payload
+    WriteToPaneId(Vec<u8>, u32, ClientId), // bytes, terminal pane id
+    QueryPaneProcesses(ClientId),
+    SubscribeToPaneOutput(u32, PluginId, ClientId), // u32 is the terminal pane id
+    UnsubscribeFromPaneOutput(u32, PluginId, ClientId), // u32 is the terminal pane id
+    RemovePaneOutputSubscriptionsOfPane(u32),       // u32 is the terminal pane id
+    RemovePaneOutputSubscriptionsOfPlugin(PluginId),
+    DumpPaneContents(u32, bool, PluginId, ClientId), // terminal pane id, include the scrollback
+    DumpLayoutToHd,
+    DumpLayout(ClientId),
+    RenameSession(String), // String is the new session name
+    SerializeSessionAndQuit(ClientId),
 }
 
 impl From<&ScreenInstruction> for ScreenContext {
@@ -304,9 +392,11 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::Render => ScreenContext::Render,
             ScreenInstruction::NewPane(..) => ScreenContext::NewPane,
             ScreenInstruction::OpenInPlaceEditor(..) => ScreenContext::OpenInPlaceEditor,
+            ScreenInstruction::ReplacePane(..) => ScreenContext::ReplacePane,
             ScreenInstruction::TogglePaneEmbedOrFloating(..) => {
                 ScreenContext::TogglePaneEmbedOrFloating
             },
+            ScreenInstruction::TogglePanePinned(..) => ScreenContext::TogglePanePinned,
             ScreenInstruction::ToggleFloatingPanes(..) => ScreenContext::ToggleFloatingPanes,
             ScreenInstruction::HorizontalSplit(..) => ScreenContext::HorizontalSplit,
             ScreenInstruction::VerticalSplit(..) => ScreenContext::VerticalSplit,
@@ -348,6 +438,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MoveFocusRightOrNextTab(..) => {
                 ScreenContext::MoveFocusRightOrNextTab
             },
+            ScreenInstruction::MoveFocusOrDelegate(..) => ScreenContext::MoveFocusOrDelegate,
             ScreenInstruction::MovePane(..) => ScreenContext::MovePane,
             ScreenInstruction::MovePaneBackwards(..) => ScreenContext::MovePaneBackwards,
             ScreenInstruction::MovePaneDown(..) => ScreenContext::MovePaneDown,
@@ -358,10 +449,15 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ClearScreen(..) => ScreenContext::ClearScreen,
             ScreenInstruction::DumpScreen(..) => ScreenContext::DumpScreen,
             ScreenInstruction::EditScrollback(..) => ScreenContext::EditScrollback,
+            ScreenInstruction::EnterCopyMode(..) => ScreenContext::EnterCopyMode,
+            ScreenInstruction::ShowLinkHints(..) => ScreenContext::ShowLinkHints,
             ScreenInstruction::ScrollUp(..) => ScreenContext::ScrollUp,
             ScreenInstruction::ScrollDown(..) => ScreenContext::ScrollDown,
             ScreenInstruction::ScrollToBottom(..) => ScreenContext::ScrollToBottom,
             ScreenInstruction::ScrollToTop(..) => ScreenContext::ScrollToTop,
+            ScreenInstruction::ScrollToPreviousPrompt(..) => ScreenContext::ScrollToPreviousPrompt,
+            ScreenInstruction::ScrollToNextPrompt(..) => ScreenContext::ScrollToNextPrompt,
+            ScreenInstruction::CopyLastCommandOutput(..) => ScreenContext::CopyLastCommandOutput,
             ScreenInstruction::PageScrollUp(..) => ScreenContext::PageScrollUp,
             ScreenInstruction::PageScrollDown(..) => ScreenContext::PageScrollDown,
             ScreenInstruction::HalfPageScrollUp(..) => ScreenContext::HalfPageScrollUp,
@@ -371,6 +467,11 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ToggleActiveTerminalFullscreen(..) => {
                 ScreenContext::ToggleActiveTerminalFullscreen
             },
+            ScreenInstruction::ToggleExpandPane(..) => ScreenContext::ToggleExpandPane,
+            ScreenInstruction::FocusNextPaneInStack(..) => ScreenContext::FocusNextPaneInStack,
+            ScreenInstruction::FocusPreviousPaneInStack(..) => {
+                ScreenContext::FocusPreviousPaneInStack
+            },
             ScreenInstruction::TogglePaneFrames => ScreenContext::TogglePaneFrames,
             ScreenInstruction::SetSelectable(..) => ScreenContext::SetSelectable,
             ScreenInstruction::ClosePane(..) => ScreenContext::ClosePane,
@@ -382,6 +483,8 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::SwitchTabNext(..) => ScreenContext::SwitchTabNext,
             ScreenInstruction::SwitchTabPrev(..) => ScreenContext::SwitchTabPrev,
             ScreenInstruction::CloseTab(..) => ScreenContext::CloseTab,
+            ScreenInstruction::ReopenClosedPane(..) => ScreenContext::ReopenClosedPane,
+            ScreenInstruction::ReopenClosedTab(..) => ScreenContext::ReopenClosedTab,
             ScreenInstruction::GoToTab(..) => ScreenContext::GoToTab,
             ScreenInstruction::GoToTabName(..) => ScreenContext::GoToTabName,
             ScreenInstruction::UpdateTabName(..) => ScreenContext::UpdateTabName,
@@ -401,10 +504,26 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::ChangeModeForAllClients(..) => {
                 ScreenContext::ChangeModeForAllClients
             },
+            ScreenInstruction::RebindKeys(..) => ScreenContext::RebindKeys,
+            ScreenInstruction::Reconfigure(..) => ScreenContext::Reconfigure,
             ScreenInstruction::ToggleActiveSyncTab(..) => ScreenContext::ToggleActiveSyncTab,
+            ScreenInstruction::ToggleActivePaneSync(..) => ScreenContext::ToggleActivePaneSync,
+            ScreenInstruction::TogglePaneInGroup(..) => ScreenContext::TogglePaneInGroup,
+            ScreenInstruction::ClearPaneGroup(..) => ScreenContext::ClearPaneGroup,
+            ScreenInstruction::CloseGroupedPanes(..) => ScreenContext::CloseGroupedPanes,
+            ScreenInstruction::FloatGroupedPanes(..) => ScreenContext::FloatGroupedPanes,
+            ScreenInstruction::BreakGroupedPanesToNewTab(..) => {
+                ScreenContext::BreakGroupedPanesToNewTab
+            },
+            ScreenInstruction::SyncGroupedPanes(..) => ScreenContext::SyncGroupedPanes,
             ScreenInstruction::ScrollUpAt(..) => ScreenContext::ScrollUpAt,
             ScreenInstruction::ScrollDownAt(..) => ScreenContext::ScrollDownAt,
+            ScreenInstruction::ForceScrollUpAt(..) => ScreenContext::ForceScrollUpAt,
+            ScreenInstruction::ForceScrollDownAt(..) => ScreenContext::ForceScrollDownAt,
             ScreenInstruction::LeftClick(..) => ScreenContext::LeftClick,
+            ScreenInstruction::CtrlLeftClick(..) => ScreenContext::CtrlLeftClick,
+            ScreenInstruction::ShiftLeftClick(..) => ScreenContext::ShiftLeftClick,
+            ScreenInstruction::AltLeftClick(..) => ScreenContext::AltLeftClick,
             ScreenInstruction::RightClick(..) => ScreenContext::RightClick,
             ScreenInstruction::MiddleClick(..) => ScreenContext::MiddleClick,
             ScreenInstruction::LeftMouseRelease(..) => ScreenContext::LeftMouseRelease,
@@ -413,6 +532,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::MouseHoldLeft(..) => ScreenContext::MouseHoldLeft,
             ScreenInstruction::MouseHoldRight(..) => ScreenContext::MouseHoldRight,
             ScreenInstruction::MouseHoldMiddle(..) => ScreenContext::MouseHoldMiddle,
+            ScreenInstruction::MouseHover(..) => ScreenContext::MouseHover,
             ScreenInstruction::Copy(..) => ScreenContext::Copy,
             ScreenInstruction::ToggleTab(..) => ScreenContext::ToggleTab,
             ScreenInstruction::AddClient(..) => ScreenContext::AddClient,
@@ -428,6 +548,7 @@ impl From<&ScreenInstruction> for ScreenContext {
                 ScreenContext::SearchToggleCaseSensitivity
             },
             ScreenInstruction::SearchToggleWholeWord(..) => ScreenContext::SearchToggleWholeWord,
+            ScreenInstruction::SearchToggleRegex(..) => ScreenContext::SearchToggleRegex,
             ScreenInstruction::SearchToggleWrap(..) => ScreenContext::SearchToggleWrap,
             ScreenInstruction::AddRedPaneFrameColorOverride(..) => {
                 ScreenContext::AddRedPaneFrameColorOverride
@@ -440,6 +561,7 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::QueryTabNames(..) => ScreenContext::QueryTabNames,
             ScreenInstruction::NewTiledPluginPane(..) => ScreenContext::NewTiledPluginPane,
             ScreenInstruction::NewFloatingPluginPane(..) => ScreenContext::NewFloatingPluginPane,
+            ScreenInstruction::NewInPlacePluginPane(..) => ScreenContext::NewInPlacePluginPane,
             ScreenInstruction::StartOrReloadPluginPane(..) => {
                 ScreenContext::StartOrReloadPluginPane
             },
@@ -467,7 +589,42 @@ impl From<&ScreenInstruction> for ScreenContext {
             ScreenInstruction::BreakPane(..) => ScreenContext::BreakPane,
             ScreenInstruction::BreakPaneRight(..) => ScreenContext::BreakPaneRight,
             ScreenInstruction::BreakPaneLeft(..) => ScreenContext::BreakPaneLeft,
+            ScreenInstruction::MovePaneToTab(..) => ScreenContext::MovePaneToTab,
+            ScreenInstruction::MoveTab(..) => ScreenContext::MoveTab,
+            ScreenInstruction::MoveTabToIndex(..) => ScreenContext::MoveTabToIndex,
+            ScreenInstruction::CloseTabAtPosition(..) => ScreenContext::CloseTabAtPosition,
+            ScreenInstruction::RespawnPane(..) => ScreenContext::RespawnPane,
             ScreenInstruction::UpdateSessionInfos(..) => ScreenContext::UpdateSessionInfos,
+            ScreenInstruction::MarkSilentPanes => ScreenContext::MarkSilentPanes,
+            ScreenInstruction::SwitchWorkspace(..) => ScreenContext::SwitchWorkspace,
+            ScreenInstruction::ToggleScratchpad(..) => ScreenContext::ToggleScratchpad,
+            ScreenInstruction::NewScratchpadPane(..) => ScreenContext::NewScratchpadPane,
+            ScreenInstruction::UpdateTerminalProcesses(..) => {
+                ScreenContext::UpdateTerminalProcesses
+            },
+            ScreenInstruction::QueryPaneProcesses(..) => ScreenContext::QueryPaneProcesses,
+            ScreenInstruction::LoadBuffer(..) => ScreenContext::LoadBuffer,
+            ScreenInstruction::SaveBuffer(..) => ScreenContext::SaveBuffer,
+            ScreenInstruction::PasteBuffer(..) => ScreenContext::PasteBuffer,
+            ScreenInstruction::PipeToPane(..) => ScreenContext::PipeToPane,
+            ScreenInstruction::WriteToPaneId(..) => ScreenContext::WriteToPaneId,
+            ScreenInstruction::SubscribeToPaneOutput(..) => ScreenContext::SubscribeToPaneOutput,
+            ScreenInstruction::UnsubscribeFromPaneOutput(..) => {
+                ScreenContext::UnsubscribeFromPaneOutput
+            },
+            ScreenInstruction::RemovePaneOutputSubscriptionsOfPane(..) => {
+                ScreenContext::RemovePaneOutputSubscriptionsOfPane
+            },
+            ScreenInstruction::RemovePaneOutputSubscriptionsOfPlugin(..) => {
+                ScreenContext::RemovePaneOutputSubscriptionsOfPlugin
+            },
+            ScreenInstruction::DumpPaneContents(..) => ScreenContext::DumpPaneContents,
+            ScreenInstruction::DumpLayoutToHd => ScreenContext::DumpLayoutToHd,
+            ScreenInstruction::DumpLayout(..) => ScreenContext::DumpLayout,
+            ScreenInstruction::RenameSession(..) => ScreenContext::RenameSession,
+            ScreenInstruction::SerializeSessionAndQuit(..) => {
+                ScreenContext::SerializeSessionAndQuit
+            },
         }
     }
 }
@@ -520,6 +677,7 @@ pub(crate) struct Screen {
     overlay: OverlayWindow,
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
+    uses_default_palette: bool, // no theme was configured, blend in the terminal emulator colors
     connected_clients: Rc<RefCell<HashSet<ClientId>>>,
     /// The indices of this [`Screen`]'s active [`Tab`]s.
     active_tab_indices: BTreeMap<ClientId, usize>,
@@ -530,9 +688,19 @@ pub(crate) struct Screen {
     draw_pane_frames: bool,
     auto_layout: bool,
     session_is_mirrored: bool,
+    tab_focus_is_mirrored: bool, // all clients focus the same tab
     copy_options: CopyOptions,
     debug: bool,
     session_name: String,
+    session_group: Option<String>,
+    session_serialization: bool,
+    serialize_pane_scrollback: bool,
+    scratchpad_pane_id: Option<PaneId>,
+    closed_panes: VecDeque<(PaneLayoutManifest, bool)>, // bool is whether the pane was floating, most recently closed last
+    closed_tabs: VecDeque<(String, TabLayoutManifest)>, // String is the tab name, most recently closed last
+    pane_output_subscriptions: HashMap<u32, (PaneOutputDecoder, HashSet<(PluginId, ClientId)>)>, // u32 is the terminal pane id
+    terminal_processes: HashMap<u32, TerminalProcesses>, // u32 is the terminal pane id
+    paste_buffer: Option<String>,
     session_infos_on_machine: BTreeMap<String, SessionInfo>, // String is the session name, can
                                                              // also be this session
 }
@@ -547,7 +715,11 @@ impl Screen {
         draw_pane_frames: bool,
         auto_layout: bool,
         session_is_mirrored: bool,
+        tab_focus_is_mirrored: bool,
         copy_options: CopyOptions,
+        session_group: Option<String>,
+        session_serialization: bool,
+        serialize_pane_scrollback: bool,
         debug: bool,
     ) -> Self {
         let session_name = mode_info.session_name.clone().unwrap_or_default();
@@ -568,15 +740,26 @@ impl Screen {
             overlay: OverlayWindow::default(),
             terminal_emulator_colors: Rc::new(RefCell::new(Palette::default())),
             terminal_emulator_color_codes: Rc::new(RefCell::new(HashMap::new())),
+            uses_default_palette: client_attributes.style.colors == default_palette(),
             tab_history: BTreeMap::new(),
             mode_info: BTreeMap::new(),
             default_mode_info: mode_info,
             draw_pane_frames,
             auto_layout,
             session_is_mirrored,
+            tab_focus_is_mirrored,
             copy_options,
             debug,
             session_name,
+            session_group,
+            session_serialization,
+            serialize_pane_scrollback,
+            scratchpad_pane_id: None,
+            closed_panes: VecDeque::new(),
+            closed_tabs: VecDeque::new(),
+            pane_output_subscriptions: HashMap::new(),
+            terminal_processes: HashMap::new(),
+            paste_buffer: None,
             session_infos_on_machine,
         }
     }
@@ -710,7 +893,7 @@ impl Screen {
 
                     let current_tab_index = current_tab.index;
                     let new_tab_index = new_tab.index;
-                    if self.session_is_mirrored {
+                    if self.tab_focus_is_mirrored {
                         self.move_clients_between_tabs(
                             current_tab_index,
                             new_tab_index,
@@ -863,6 +1046,51 @@ impl Screen {
         self.switch_active_tab_name(name, client_id)
     }
 
+    pub fn move_active_tab(&mut self, direction: Direction, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to move active tab {direction:?}");
+        let tab_count = self.tabs.len();
+        let active_tab_position = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .position;
+        let new_tab_position = match direction {
+            Direction::Right | Direction::Down => (active_tab_position + 1) % tab_count,
+            Direction::Left | Direction::Up => (active_tab_position + tab_count - 1) % tab_count,
+        };
+        self.move_tab_to_position(active_tab_position, new_tab_position)
+            .with_context(err_context)
+    }
+
+    pub fn move_tab_to_position(&mut self, position: usize, new_position: usize) -> Result<()> {
+        // the tabs between the old and new positions shift over by one to make room
+        let err_context = || format!("failed to move tab at position {position} to {new_position}");
+        if position == new_position || new_position >= self.tabs.len() {
+            return Ok(());
+        }
+        let tab_index = match self.tabs.values().find(|t| t.position == position) {
+            Some(tab) => tab.index,
+            None => return Ok(()),
+        };
+        for tab in self.tabs.values_mut() {
+            if tab.index == tab_index {
+                tab.position = new_position;
+            } else if position < new_position
+                && tab.position > position
+                && tab.position <= new_position
+            {
+                tab.position -= 1;
+            } else if new_position < position
+                && tab.position >= new_position
+                && tab.position < position
+            {
+                tab.position += 1;
+            }
+        }
+        self.log_and_report_session_state()
+            .with_context(err_context)?;
+        self.render().with_context(err_context)
+    }
+
     fn close_tab_at_index(&mut self, tab_index: usize) -> Result<()> {
         let err_context = || format!("failed to close tab at index {tab_index:?}");
 
@@ -902,6 +1130,15 @@ impl Screen {
         }
     }
 
+    pub fn close_tab_at_position(&mut self, position: usize) -> Result<()> {
+        let err_context = || format!("failed to close tab at position {position}");
+        let tab_index = match self.tabs.values().find(|t| t.position == position) {
+            Some(tab) => tab.index,
+            None => return Ok(()),
+        };
+        self.close_tab_at_index(tab_index).with_context(err_context)
+    }
+
     // Closes the client_id's focused tab
     pub fn close_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to close tab for client {client_id:?}");
@@ -979,6 +1216,65 @@ impl Screen {
         }
     }
 
+    /// The default palette with the background, foreground and the first 8 color registers
+    /// replaced by those reported by the terminal emulator
+    fn default_palette_from_terminal_emulator_colors(&self) -> Palette {
+        let mut palette = default_palette();
+        let terminal_emulator_colors = self.terminal_emulator_colors.borrow();
+        if let PaletteColor::Rgb(_) = terminal_emulator_colors.bg {
+            palette.bg = terminal_emulator_colors.bg;
+            palette.theme_hue = detect_theme_hue(terminal_emulator_colors.bg);
+        }
+        if let PaletteColor::Rgb(_) = terminal_emulator_colors.fg {
+            palette.fg = terminal_emulator_colors.fg;
+        }
+        let terminal_emulator_color_codes = self.terminal_emulator_color_codes.borrow();
+        let color_registers = [
+            &mut palette.black,
+            &mut palette.red,
+            &mut palette.green,
+            &mut palette.yellow,
+            &mut palette.blue,
+            &mut palette.magenta,
+            &mut palette.cyan,
+            &mut palette.white,
+        ];
+        for (color_register, palette_color) in color_registers.into_iter().enumerate() {
+            if let Some(AnsiCode::RgbCode((r, g, b))) = terminal_emulator_color_codes
+                .get(&color_register)
+                .and_then(|color_sequence| xparse_color(color_sequence.as_bytes()))
+            {
+                *palette_color = PaletteColor::Rgb((r, g, b));
+            }
+        }
+        palette
+    }
+
+    /// When the session uses the default palette, restyles it to blend in with the colors of
+    /// the user's terminal theme
+    pub fn apply_terminal_emulator_colors(&mut self) -> Result<()> {
+        let err_context = || "failed to apply terminal emulator colors";
+        if !self.uses_default_palette {
+            return Ok(());
+        }
+        let mut theme = Theme::from(self.default_palette_from_terminal_emulator_colors());
+        if !envs::terminal_supports_truecolor() {
+            theme.palette = theme.palette.to_eight_bit();
+            theme.styling = theme.styling.to_eight_bit();
+        }
+        if self.style.colors == theme.palette && self.style.styling == theme.styling {
+            return Ok(());
+        }
+        self.style.colors = theme.palette;
+        self.style.styling = theme.styling;
+        let style = self.style;
+        for tab in self.tabs.values_mut() {
+            tab.update_style(style);
+        }
+        self.update_mode_infos(|mode_info| mode_info.style = style)
+            .with_context(err_context)
+    }
+
     /// Renders this [`Screen`], which amounts to rendering its active [`Tab`].
     pub fn render(&mut self) -> Result<()> {
         let err_context = "failed to render screen";
@@ -998,6 +1294,17 @@ impl Screen {
         for tab_index in tabs_to_close {
             self.close_tab_at_index(tab_index).context(err_context)?;
         }
+        if !self.overlay.overlay_stack.is_empty() {
+            // overlays are drawn over everything else, leaving the cursor where the panes put it
+            let overlay_output = self
+                .overlay
+                .generate_overlay(self.size)
+                .context(err_context)?;
+            output.add_post_vte_instruction_to_multiple_clients(
+                self.connected_clients.borrow().iter().copied(),
+                &format!("\u{1b}7{}\u{1b}8", overlay_output),
+            );
+        }
         if output.is_dirty() {
             let serialized_output = output.serialize().context(err_context)?;
             self.bus
@@ -1063,6 +1370,151 @@ impl Screen {
         &mut self.overlay.overlay_stack
     }
 
+    /// Labels the links in the client's active pane so that one of them can be opened by typing
+    /// its label.
+    pub fn show_link_hints(&mut self, client_id: ClientId) -> Result<()> {
+        let links = self
+            .get_active_tab_mut(client_id)
+            .with_context(|| format!("failed to show link hints for client {client_id}"))?
+            .links_in_active_pane(client_id);
+        self.overlay
+            .overlay_stack
+            .retain(|overlay| !matches!(overlay.overlay_type, OverlayType::LinkHints(_)));
+        if !links.is_empty() {
+            self.overlay
+                .overlay_stack
+                .push(Overlay::new(OverlayType::LinkHints(LinkHints::new(links))));
+        }
+        Ok(())
+    }
+
+    /// Feeds the input to the link hints if they're shown, opening the link whose label was
+    /// typed. Returns whether the input was consumed by the link hints.
+    fn handle_link_hints_input(&mut self, input_bytes: &[u8], client_id: ClientId) -> Result<bool> {
+        let err_context = || format!("failed to handle link hints input for client {client_id}");
+
+        let link_hints_input = match self
+            .overlay
+            .overlay_stack
+            .last_mut()
+            .map(|overlay| &mut overlay.overlay_type)
+        {
+            Some(OverlayType::LinkHints(link_hints)) => link_hints.handle_input(input_bytes),
+            _ => return Ok(false),
+        };
+        if link_hints_input != LinkHintsInput::Pending {
+            self.overlay.overlay_stack.pop();
+            // redraw the panes the hints were drawn over
+            for tab in self.tabs.values_mut() {
+                tab.set_force_render();
+            }
+        }
+        if let (LinkHintsInput::Selected(link), Some(link_opener)) =
+            (link_hints_input, LinkOpener::from_config())
+        {
+            link_opener.open(&link).with_context(err_context)?;
+        }
+        self.render().with_context(err_context)?;
+        Ok(true)
+    }
+
+    /// Closes the pane focused by this client, unless it is running something (eg. a build) and
+    /// the `pane_close_confirmation` option is on, in which case the user is asked first
+    fn close_focused_pane_or_ask_to_confirm(&mut self, client_id: ClientId) -> Result<()> {
+        let client_id = match self.attached_client_id(client_id) {
+            Some(client_id) => client_id,
+            None => return Ok(()),
+        };
+        let busy_pane = self
+            .get_active_tab(client_id)
+            .ok()
+            .and_then(|tab| tab.get_active_pane_id(client_id))
+            .and_then(|pane_id| match pane_id {
+                PaneId::Terminal(terminal_id) => self
+                    .terminal_processes
+                    .get(&terminal_id)
+                    .filter(|processes| !processes.running_processes.is_empty())
+                    .map(|processes| (pane_id, processes.running_processes.clone())),
+                PaneId::Plugin(_) => None,
+            });
+        match busy_pane {
+            Some((pane_id, running_processes))
+                if PANE_CLOSE_CONFIRMATION.get().copied().unwrap_or(true) =>
+            {
+                self.overlay.overlay_stack.retain(|overlay| {
+                    !matches!(overlay.overlay_type, OverlayType::ClosePaneConfirmation(_))
+                });
+                self.overlay
+                    .overlay_stack
+                    .push(Overlay::new(OverlayType::ClosePaneConfirmation(
+                        ClosePaneConfirmation::new(pane_id, client_id, running_processes),
+                    )));
+                Ok(())
+            },
+            _ => self.close_focused_pane(client_id),
+        }
+    }
+
+    fn close_focused_pane(&mut self, client_id: ClientId) -> Result<()> {
+        self.remember_focused_pane(client_id);
+        self.get_active_tab_mut(client_id)
+            .and_then(|tab| tab.close_focused_pane(client_id))
+            .with_context(|| format!("failed to close focused pane for client {client_id}"))
+    }
+
+    /// Feeds the input to the close pane confirmation if it's shown and was asked for by this
+    /// client, closing the pane if it was confirmed. Returns whether the input was consumed by
+    /// the confirmation.
+    fn handle_close_pane_confirmation_input(
+        &mut self,
+        input_bytes: &[u8],
+        client_id: ClientId,
+    ) -> Result<bool> {
+        let err_context =
+            || format!("failed to handle close pane confirmation input for client {client_id}");
+
+        let attached_client_id = self.attached_client_id(client_id);
+        let confirmation_input = match self
+            .overlay
+            .overlay_stack
+            .last()
+            .map(|overlay| &overlay.overlay_type)
+        {
+            Some(OverlayType::ClosePaneConfirmation(confirmation))
+                if attached_client_id == Some(confirmation.client_id()) =>
+            {
+                confirmation.handle_input(input_bytes)
+            },
+            _ => return Ok(false),
+        };
+        if confirmation_input == ClosePaneConfirmationInput::Pending {
+            return Ok(true);
+        }
+        self.overlay.overlay_stack.pop();
+        // redraw the panes the confirmation was drawn over
+        for tab in self.tabs.values_mut() {
+            tab.set_force_render();
+        }
+        if let ClosePaneConfirmationInput::Confirmed(pane_id) = confirmation_input {
+            // the focus might have moved (eg. with the mouse) since the confirmation was shown
+            let pane_is_still_focused = self.attached_client_id(client_id).and_then(|client_id| {
+                self.get_active_tab(client_id)
+                    .ok()
+                    .and_then(|tab| tab.get_active_pane_id(client_id))
+                    .filter(|focused_pane_id| *focused_pane_id == pane_id)
+                    .map(|_| client_id)
+            });
+            if let Some(client_id) = pane_is_still_focused {
+                self.close_focused_pane(client_id)
+                    .with_context(err_context)?;
+                self.log_and_report_session_state()
+                    .with_context(err_context)?;
+            }
+        }
+        self.render().with_context(err_context)?;
+        Ok(true)
+    }
+
     /// Returns a mutable reference to this [`Screen`]'s indexed [`Tab`].
     pub fn get_indexed_tab_mut(&mut self, tab_index: usize) -> Option<&mut Tab> {
         self.get_tabs_mut().get_mut(&tab_index)
@@ -1145,7 +1597,7 @@ impl Screen {
         let err_context = || format!("failed to apply layout for tab {tab_index:?}",);
 
         // move the relevant clients out of the current tab and place them in the new one
-        let drained_clients = if self.session_is_mirrored {
+        let drained_clients = if self.tab_focus_is_mirrored {
             let client_mode_infos_in_source_tab =
                 if let Ok(active_tab) = self.get_active_tab_mut(client_id) {
                     let client_mode_infos_in_source_tab = active_tab.drain_connected_clients(None);
@@ -1261,6 +1713,12 @@ impl Screen {
     pub fn generate_and_report_tab_state(&mut self) -> Result<Vec<TabInfo>> {
         let mut plugin_updates = vec![];
         let mut tab_infos_for_screen_state = BTreeMap::new();
+        for tab in self.tabs.values_mut() {
+            if self.active_tab_indices.values().any(|i| *i == tab.index) {
+                tab.has_unseen_output = false;
+            }
+        }
+        let first_client_id = self.active_tab_indices.keys().next().copied();
         for tab in self.tabs.values() {
             let all_focused_clients: Vec<ClientId> = self
                 .active_tab_indices
@@ -1270,6 +1728,11 @@ impl Screen {
                 .copied()
                 .collect();
             let (active_swap_layout_name, is_swap_layout_dirty) = tab.swap_layout_info();
+            let focused_pane_title = all_focused_clients
+                .first()
+                .copied()
+                .or(first_client_id)
+                .and_then(|client_id| tab.focused_pane_title(client_id));
             let tab_info_for_screen = TabInfo {
                 position: tab.position,
                 name: tab.name.clone(),
@@ -1281,13 +1744,16 @@ impl Screen {
                 other_focused_clients: all_focused_clients,
                 active_swap_layout_name,
                 is_swap_layout_dirty,
+                pane_count: tab.selectable_pane_count(),
+                has_unseen_output: tab.has_unseen_output,
+                focused_pane_title,
             };
             tab_infos_for_screen_state.insert(tab.position, tab_info_for_screen);
         }
         for (client_id, active_tab_index) in self.active_tab_indices.iter() {
             let mut plugin_tab_updates = vec![];
             for tab in self.tabs.values() {
-                let other_focused_clients: Vec<ClientId> = if self.session_is_mirrored {
+                let other_focused_clients: Vec<ClientId> = if self.tab_focus_is_mirrored {
                     vec![]
                 } else {
                     self.active_tab_indices
@@ -1311,9 +1777,13 @@ impl Screen {
                     other_focused_clients,
                     active_swap_layout_name,
                     is_swap_layout_dirty,
+                    pane_count: tab.selectable_pane_count(),
+                    has_unseen_output: tab.has_unseen_output,
+                    focused_pane_title: tab.focused_pane_title(*client_id),
                 };
                 plugin_tab_updates.push(tab_info_for_plugins);
             }
+            plugin_tab_updates.sort_by_key(|tab_info| tab_info.position);
             plugin_updates.push((None, Some(*client_id), Event::TabUpdate(plugin_tab_updates)));
         }
         self.bus
@@ -1322,10 +1792,34 @@ impl Screen {
             .context("failed to update tabs")?;
         Ok(tab_infos_for_screen_state.values().cloned().collect())
     }
+    /// Marks a tab that is not focused by any client as having unseen output, reporting this to
+    /// plugins (eg. the tab-bar) only once until the tab is focused again
+    fn mark_unseen_output(&mut self, tab_index: usize) -> Result<()> {
+        let tab_is_focused = self.active_tab_indices.values().any(|i| *i == tab_index);
+        match self.tabs.get_mut(&tab_index) {
+            Some(tab) if !tab_is_focused && !tab.has_unseen_output => {
+                tab.has_unseen_output = true;
+                self.log_and_report_session_state()
+                    .with_context(|| format!("failed to mark unseen output in tab {tab_index}"))
+            },
+            _ => Ok(()),
+        }
+    }
     fn generate_and_report_pane_state(&mut self) -> Result<PaneManifest> {
         let mut pane_manifest = PaneManifest::default();
-        for tab in self.tabs.values() {
-            pane_manifest.panes.insert(tab.position, tab.pane_infos());
+        for tab in self.tabs.values_mut() {
+            // the state is about to be reported, so panes that are focused are no longer marked
+            tab.clear_monitored_state_of_focused_panes();
+            tab.take_pane_monitor_changes();
+            let mut pane_infos = tab.pane_infos();
+            for pane_info in pane_infos.iter_mut().filter(|p| !p.is_plugin) {
+                if let Some(terminal_processes) = self.terminal_processes.get(&pane_info.id) {
+                    pane_info.child_pids = terminal_processes.child_pids.clone();
+                    pane_info.foreground_process = terminal_processes.foreground_process.clone();
+                    pane_info.cwd = terminal_processes.cwd.clone();
+                }
+            }
+            pane_manifest.panes.insert(tab.position, pane_infos);
         }
         self.bus
             .senders
@@ -1349,6 +1843,7 @@ impl Screen {
             panes: pane_manifest,
             connected_clients: self.active_tab_indices.keys().len(),
             is_current_session: true,
+            group: self.session_group.clone(),
         };
         self.bus
             .senders
@@ -1361,12 +1856,267 @@ impl Screen {
             .senders
             .send_to_background_jobs(BackgroundJob::ReadAllSessionInfosOnMachine)
             .with_context(err_context)?;
+        // the processes running in terminals are only shown to (and asked about by) connected
+        // clients, so there is no need to poll the process table while the session is detached
+        let terminal_processes_job = if self.connected_clients.borrow().is_empty() {
+            BackgroundJob::StopPollingTerminalProcesses
+        } else {
+            BackgroundJob::PollTerminalProcesses
+        };
+        self.bus
+            .senders
+            .send_to_background_jobs(terminal_processes_job)
+            .with_context(err_context)?;
+        if MONITOR_SILENCE.get().is_some() {
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::MonitorPaneSilence)
+                .with_context(err_context)?;
+        }
+        if self.session_serialization {
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::SerializeSessionLayout)
+                .with_context(err_context)?;
+        }
+
+        Ok(())
+    }
+    pub fn dump_layout_to_hd(&mut self) -> Result<()> {
+        let err_context = || "Failed to dump layout to hd".to_string();
+        let global_layout_manifest = self.global_layout_manifest(self.serialize_pane_scrollback);
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::DumpLayoutToHd(global_layout_manifest, None))
+            .with_context(err_context)
+    }
+    /// Serializes the session to disk regardless of the `session_serialization` option, and
+    /// quits this client once it was written
+    pub fn serialize_session_and_quit(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("Failed to serialize session and quit client {client_id}");
+        let global_layout_manifest = self.global_layout_manifest(self.serialize_pane_scrollback);
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::DumpLayoutToHd(
+                global_layout_manifest,
+                Some(client_id),
+            ))
+            .with_context(err_context)
+    }
+    pub fn dump_layout(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("Failed to dump layout for client {client_id}");
+        // the pane contents are only meaningful next to the files they are written to when
+        // resurrecting a session, so they are never included here
+        let global_layout_manifest = self.global_layout_manifest(false);
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::DumpLayout(
+                global_layout_manifest,
+                client_id,
+            ))
+            .with_context(err_context)
+    }
+    fn terminal_cwds(&self) -> HashMap<u32, PathBuf> {
+        self.terminal_processes
+            .iter()
+            .filter_map(|(terminal_id, terminal_processes)| {
+                terminal_processes
+                    .cwd
+                    .clone()
+                    .map(|cwd| (*terminal_id, cwd))
+            })
+            .collect()
+    }
+    fn global_layout_manifest(&mut self, serialize_pane_scrollback: bool) -> GlobalLayoutManifest {
+        let cwds = self.terminal_cwds();
+        let focused_tab_index = self.active_tab_indices.values().next().copied();
+        let mut tabs: Vec<&mut Tab> = self.tabs.values_mut().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        let tabs = tabs
+            .into_iter()
+            .map(|tab| {
+                let mut tab_layout_manifest = tab.layout_manifest(&cwds, serialize_pane_scrollback);
+                tab_layout_manifest.is_focused = focused_tab_index == Some(tab.index);
+                (tab.name.clone(), tab_layout_manifest)
+            })
+            .collect();
+        GlobalLayoutManifest {
+            session_name: self.session_name.clone(),
+            // the default layout is kept by the pty thread, which fills it in
+            default_layout: Default::default(),
+            tabs,
+        }
+    }
+    // clients that are not attached (eg. the cli) act on the tab of the first client, like they
+    // do for other actions
+    fn attached_client_id(&self, client_id: ClientId) -> Option<ClientId> {
+        if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        }
+    }
+    /// Remembers the pane focused by this client before it is closed, so that it can be reopened
+    fn remember_focused_pane(&mut self, client_id: ClientId) {
+        let client_id = match self.attached_client_id(client_id) {
+            Some(client_id) => client_id,
+            None => return,
+        };
+        let cwds = self.terminal_cwds();
+        let closed_pane = self.get_active_tab_mut(client_id).ok().and_then(|tab| {
+            tab.get_active_pane_id(client_id)
+                .and_then(|pane_id| tab.terminal_pane_manifest(pane_id, &cwds))
+        });
+        if let Some(closed_pane) = closed_pane {
+            if self.closed_panes.len() == MAX_REOPENABLE_PANES_AND_TABS {
+                self.closed_panes.pop_front();
+            }
+            self.closed_panes.push_back(closed_pane);
+        }
+    }
+    /// Remembers the tab focused by this client before it is closed, so that it can be reopened
+    fn remember_active_tab(&mut self, client_id: ClientId) {
+        let client_id = match self.attached_client_id(client_id) {
+            Some(client_id) => client_id,
+            None => return,
+        };
+        let cwds = self.terminal_cwds();
+        let closed_tab = self
+            .get_active_tab_mut(client_id)
+            .ok()
+            .map(|tab| (tab.name.clone(), tab.layout_manifest(&cwds, false)));
+        if let Some(closed_tab) = closed_tab {
+            if self.closed_tabs.len() == MAX_REOPENABLE_PANES_AND_TABS {
+                self.closed_tabs.pop_front();
+            }
+            self.closed_tabs.push_back(closed_tab);
+        }
+    }
+    pub fn reopen_closed_pane(
+        &mut self,
+        default_shell: Option<TerminalAction>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to reopen closed pane for client {client_id}");
+
+        let (closed_pane, should_float) = match self.closed_panes.pop_back() {
+            Some(closed_pane) => closed_pane,
+            None => return Ok(()),
+        };
+        // commands are held until the user chooses to run them again, like when resurrecting a
+        // session
+        let terminal_action = match closed_pane.run {
+            Some(Run::Command(run_command)) => Some(TerminalAction::RunCommand(run_command)),
+            Some(Run::EditFile(path, line_number, cwd)) => {
+                Some(TerminalAction::OpenFile(path, line_number, cwd))
+            },
+            Some(Run::Cwd(cwd)) => match default_shell {
+                Some(TerminalAction::RunCommand(mut run_command)) => {
+                    run_command.cwd = Some(cwd);
+                    Some(TerminalAction::RunCommand(run_command))
+                },
+                Some(default_shell) => Some(default_shell),
+                None => Some(TerminalAction::RunCommand(RunCommand {
+                    command: get_default_shell(),
+                    cwd: Some(cwd),
+                    ..Default::default()
+                })),
+            },
+            Some(Run::Plugin(_)) | None => default_shell,
+        };
+        self.bus
+            .senders
+            .send_to_pty(PtyInstruction::SpawnTerminal(
+                terminal_action,
+                Some(should_float),
+                closed_pane.title,
+                ClientOrTabIndex::ClientId(client_id),
+            ))
+            .with_context(err_context)
+    }
+    /// The name and layout of the most recently closed tab, if any
+    fn pop_closed_tab_layout(
+        &mut self,
+    ) -> Result<Option<(Option<String>, TiledPaneLayout, Vec<FloatingPaneLayout>)>> {
+        let err_context = || "failed to get the layout of the last closed tab";
 
+        let closed_tab = match self.closed_tabs.pop_back() {
+            Some(closed_tab) => closed_tab,
+            None => return Ok(None),
+        };
+        // the tab is serialized and parsed back the same way a session is resurrected, the pane
+        // contents are not serialized so nothing is written to the layout cache dir
+        let global_layout_manifest = GlobalLayoutManifest {
+            session_name: self.session_name.clone(),
+            default_layout: Default::default(),
+            tabs: vec![closed_tab],
+        };
+        let (raw_layout, _pane_contents) = serialize_session_layout(
+            global_layout_manifest,
+            &session_layout_cache_dir(&self.session_name),
+        )
+        .map_err(|e| anyhow!(e))
+        .with_context(err_context)?;
+        let layout = Layout::from_str(&raw_layout, "Closed tab".to_owned(), None, None)
+            .map_err(|e| anyhow!("Failed to parse layout: {:?}", e))
+            .with_context(err_context)?;
+        Ok(layout.tabs().into_iter().next())
+    }
+    pub fn rename_session(&mut self, new_session_name: String) -> Result<()> {
+        let err_context = || format!("Failed to rename session to {new_session_name}");
+        if new_session_name == self.session_name {
+            return Ok(());
+        }
+        if !is_valid_session_name(&new_session_name) {
+            log::error!(
+                "Cannot rename session to {:?}: invalid name",
+                new_session_name
+            );
+            return Ok(());
+        }
+        let new_socket_path = ZELLIJ_SOCK_DIR.join(&new_session_name);
+        if new_socket_path.exists() || session_layout_cache_dir(&new_session_name).exists() {
+            log::error!(
+                "Cannot rename session to {}: a session with this name already exists",
+                new_session_name
+            );
+            return Ok(());
+        }
+        std::fs::rename(ZELLIJ_SOCK_DIR.join(&self.session_name), &new_socket_path)
+            .with_context(err_context)?;
+        // the serialized layout refers to the pane contents by their full path, so rather than
+        // moving it we serialize the session again under its new name
+        let _ = std::fs::remove_dir_all(session_layout_cache_dir(&self.session_name));
+        envs::set_session_name(new_session_name.clone());
+        self.session_name = new_session_name.clone();
+        self.default_mode_info.session_name = Some(new_session_name.clone());
+        let connected_client_ids: Vec<ClientId> = self.active_tab_indices.keys().copied().collect();
+        for client_id in connected_client_ids {
+            let mode_info = self
+                .mode_info
+                .entry(client_id)
+                .or_insert_with(|| self.default_mode_info.clone());
+            mode_info.session_name = Some(new_session_name.clone());
+            let mode_info = mode_info.clone();
+            for tab in self.tabs.values_mut() {
+                tab.change_mode_info(mode_info.clone(), client_id);
+                tab.mark_active_pane_for_rerender(client_id);
+            }
+        }
+        for tab in self.tabs.values_mut() {
+            tab.update_input_modes().with_context(err_context)?;
+        }
+        self.log_and_report_session_state()
+            .with_context(err_context)?;
+        if self.session_serialization {
+            self.dump_layout_to_hd().with_context(err_context)?;
+        }
         Ok(())
     }
     pub fn update_session_infos(
         &mut self,
         new_session_infos: BTreeMap<String, SessionInfo>,
+        resurrectable_sessions: Vec<(String, Duration)>,
     ) -> Result<()> {
         self.session_infos_on_machine = new_session_infos;
         self.bus
@@ -1374,58 +2124,428 @@ impl Screen {
             .send_to_plugin(PluginInstruction::Update(vec![(
                 None,
                 None,
-                Event::SessionUpdate(self.session_infos_on_machine.values().cloned().collect()),
+                Event::SessionUpdate(
+                    self.session_infos_on_machine.values().cloned().collect(),
+                    resurrectable_sessions,
+                ),
             )]))
             .context("failed to update session info")?;
         Ok(())
     }
+    fn save_paste_buffer(&mut self, file_path: String) -> Result<()> {
+        let err_context = || format!("failed to save paste buffer to {file_path}");
 
-    pub fn update_active_tab_name(&mut self, buf: Vec<u8>, client_id: ClientId) -> Result<()> {
-        let err_context =
-            || format!("failed to update active tabs name for client id: {client_id:?}");
-
-        let client_id = if self.get_active_tab(client_id).is_ok() {
-            Some(client_id)
-        } else {
-            self.get_first_client_id()
+        let paste_buffer = match &self.paste_buffer {
+            Some(paste_buffer) => paste_buffer.clone(),
+            None => {
+                log::error!("Paste buffer is empty, not saving it to {}", file_path);
+                return Ok(());
+            },
         };
+        if let Some(os_input) = self.bus.os_input.as_mut() {
+            os_input
+                .write_to_file(paste_buffer, Some(file_path.clone()))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+    fn pipe_to_pane(
+        &mut self,
+        terminal_pane_id: u32,
+        payload: String,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to pipe to terminal pane {terminal_pane_id}");
 
-        match client_id {
-            Some(client_id) => {
-                let s = str::from_utf8(&buf)
-                    .with_context(|| format!("failed to construct tab name from buf: {buf:?}"))
+        let pane_id = PaneId::Terminal(terminal_pane_id);
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => {
+                // every line sent with `zellij pipe` is written as a line
+                tab.write_to_pane_id(format!("{payload}\n").into_bytes(), pane_id, None)
+                    .with_context(err_context)?;
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::UnblockCliPipeInput(client_id))
+                    .with_context(err_context)?;
+            },
+            None => {
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(
+                        vec![format!("No terminal pane with id {terminal_pane_id}")],
+                        client_id,
+                    ))
                     .with_context(err_context)?;
-                match self.get_active_tab_mut(client_id) {
-                    Ok(active_tab) => {
-                        match s {
-                            "\0" => {
-                                active_tab.name = String::new();
-                            },
-                            "\u{007F}" | "\u{0008}" => {
-                                // delete and backspace keys
-                                active_tab.name.pop();
-                            },
-                            c => {
-                                // It only allows printable unicode
-                                if buf.iter().all(|u| matches!(u, 0x20..=0x7E | 0xA0..=0xFF)) {
-                                    active_tab.name.push_str(c);
-                                }
-                            },
-                        }
-                        self.log_and_report_session_state()
-                            .with_context(err_context)
-                    },
-                    Err(err) => {
-                        Err::<(), _>(err).with_context(err_context).non_fatal();
-                        Ok(())
-                    },
-                }
             },
-            None => Ok(()),
         }
+        Ok(())
     }
-    pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
-        let err_context = || format!("failed to undo active tab rename for client {}", client_id);
+    fn write_to_pane_id(
+        &mut self,
+        bytes: Vec<u8>,
+        terminal_pane_id: u32,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to write to terminal pane {terminal_pane_id}");
+
+        let pane_id = PaneId::Terminal(terminal_pane_id);
+        match self
+            .tabs
+            .values_mut()
+            .find(|tab| tab.has_pane_with_pid(&pane_id))
+        {
+            Some(tab) => {
+                if tab
+                    .write_to_pane_id(bytes, pane_id, None)
+                    .with_context(err_context)?
+                {
+                    self.render().with_context(err_context)?;
+                    self.log_and_report_session_state()
+                        .with_context(err_context)?;
+                }
+            },
+            None => {
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(
+                        vec![format!("No terminal pane with id {terminal_pane_id}")],
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
+            },
+        }
+        Ok(())
+    }
+    fn paste_buffer_to_pane(
+        &mut self,
+        terminal_pane_id: Option<u32>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to paste buffer for client {client_id}");
+
+        let paste_buffer = match &self.paste_buffer {
+            Some(paste_buffer) => paste_buffer.clone(),
+            None => {
+                log::error!("Paste buffer is empty, nothing to paste");
+                return Ok(());
+            },
+        };
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            client_id
+        } else if let Some(first_client_id) = self.get_first_client_id() {
+            first_client_id
+        } else {
+            return Ok(());
+        };
+        match terminal_pane_id {
+            Some(terminal_pane_id) => {
+                let pane_id = PaneId::Terminal(terminal_pane_id);
+                match self
+                    .tabs
+                    .values_mut()
+                    .find(|tab| tab.has_pane_with_pid(&pane_id))
+                {
+                    Some(tab) => tab
+                        .paste_to_pane(&paste_buffer, pane_id, client_id)
+                        .with_context(err_context)?,
+                    None => {
+                        log::error!("Could not find terminal pane with id {}", terminal_pane_id);
+                    },
+                }
+            },
+            None => {
+                let active_tab = self
+                    .get_active_tab_mut(client_id)
+                    .with_context(err_context)?;
+                if let Some(pane_id) = active_tab.get_active_pane_id(client_id) {
+                    active_tab
+                        .paste_to_pane(&paste_buffer, pane_id, client_id)
+                        .with_context(err_context)?;
+                }
+            },
+        }
+        Ok(())
+    }
+    /// One line per terminal pane, in tab order: the pane id, its foreground process, the
+    /// process's working directory and all pids running in the pane, separated by tabs
+    fn pane_processes(&self) -> Vec<String> {
+        let mut tabs: Vec<&Tab> = self.tabs.values().collect();
+        tabs.sort_by_key(|tab| tab.position);
+        let mut pane_processes = vec![];
+        for tab in tabs {
+            let mut terminal_ids: Vec<u32> = tab
+                .pane_infos()
+                .iter()
+                .filter(|pane_info| !pane_info.is_plugin)
+                .map(|pane_info| pane_info.id)
+                .collect();
+            terminal_ids.sort_unstable();
+            for terminal_id in terminal_ids {
+                let terminal_processes = self
+                    .terminal_processes
+                    .get(&terminal_id)
+                    .cloned()
+                    .unwrap_or_default();
+                let child_pids: Vec<String> = terminal_processes
+                    .child_pids
+                    .iter()
+                    .map(|pid| pid.to_string())
+                    .collect();
+                pane_processes.push(format!(
+                    "terminal_{}\t{}\t{}\t{}",
+                    terminal_id,
+                    terminal_processes
+                        .foreground_process
+                        .unwrap_or_else(|| "-".to_owned()),
+                    terminal_processes
+                        .cwd
+                        .map(|cwd| cwd.display().to_string())
+                        .unwrap_or_else(|| "-".to_owned()),
+                    child_pids.join(" "),
+                ));
+            }
+        }
+        pane_processes
+    }
+    pub fn subscribe_to_pane_output(
+        &mut self,
+        terminal_pane_id: u32,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) {
+        self.pane_output_subscriptions
+            .entry(terminal_pane_id)
+            .or_insert_with(|| (PaneOutputDecoder::default(), HashSet::new()))
+            .1
+            .insert((plugin_id, client_id));
+    }
+    pub fn unsubscribe_from_pane_output(
+        &mut self,
+        terminal_pane_id: u32,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) {
+        if let Some((_decoder, subscribers)) =
+            self.pane_output_subscriptions.get_mut(&terminal_pane_id)
+        {
+            subscribers.remove(&(plugin_id, client_id));
+            if subscribers.is_empty() {
+                self.pane_output_subscriptions.remove(&terminal_pane_id);
+            }
+        }
+    }
+    /// For when the terminal closed, so that its decoder and subscribers do not outlive it
+    pub fn remove_pane_output_subscriptions_of_pane(&mut self, terminal_pane_id: u32) {
+        self.pane_output_subscriptions.remove(&terminal_pane_id);
+    }
+    /// For when the plugin is unloaded, terminals left without subscribers are dropped
+    pub fn remove_pane_output_subscriptions_of_plugin(&mut self, plugin_id: PluginId) {
+        self.pane_output_subscriptions
+            .retain(|_terminal_pane_id, (_decoder, subscribers)| {
+                subscribers.retain(|(subscribed_plugin_id, _)| *subscribed_plugin_id != plugin_id);
+                !subscribers.is_empty()
+            });
+    }
+    fn report_pane_output(&mut self, terminal_pane_id: u32, bytes: &[u8]) -> Result<()> {
+        if let Some((decoder, subscribers)) =
+            self.pane_output_subscriptions.get_mut(&terminal_pane_id)
+        {
+            if let Some(output) = decoder.decode(bytes) {
+                let plugin_updates = subscribers
+                    .iter()
+                    .map(|(plugin_id, client_id)| {
+                        (
+                            Some(*plugin_id),
+                            Some(*client_id),
+                            Event::PaneOutput(terminal_pane_id, output.clone()),
+                        )
+                    })
+                    .collect();
+                self.bus
+                    .senders
+                    .send_to_plugin(PluginInstruction::Update(plugin_updates))
+                    .context("failed to report pane output")?;
+            }
+        }
+        Ok(())
+    }
+    pub fn dump_pane_contents(
+        &mut self,
+        terminal_pane_id: u32,
+        full: bool,
+        plugin_id: PluginId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let contents = self
+            .tabs
+            .values_mut()
+            .find_map(|tab| tab.dump_terminal_pane_screen(terminal_pane_id, client_id, full));
+        match contents {
+            Some(contents) => self
+                .bus
+                .senders
+                .send_to_plugin(PluginInstruction::Update(vec![(
+                    Some(plugin_id),
+                    Some(client_id),
+                    Event::PaneContents(terminal_pane_id, contents),
+                )]))
+                .with_context(|| format!("failed to dump contents of pane {terminal_pane_id}")),
+            None => {
+                log::error!("Failed to find terminal pane {terminal_pane_id} to dump its contents");
+                Ok(())
+            },
+        }
+    }
+    pub fn toggle_scratchpad(
+        &mut self,
+        client_id: ClientId,
+        default_shell: Option<TerminalAction>,
+    ) -> Result<()> {
+        let err_context = || format!("failed to toggle scratchpad for client {client_id}");
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            client_id
+        } else if let Some(first_client_id) = self.get_first_client_id() {
+            first_client_id
+        } else {
+            log::error!("No client ids in screen found");
+            return Ok(());
+        };
+        let active_tab_index = self
+            .get_active_tab(client_id)
+            .with_context(err_context)?
+            .index;
+        let scratchpad = self.scratchpad_pane_id.and_then(|pane_id| {
+            self.tabs
+                .values()
+                .find(|tab| tab.has_pane_with_pid(&pane_id))
+                .map(|tab| (pane_id, tab.index))
+        });
+        match scratchpad {
+            Some((pane_id, tab_index)) if tab_index == active_tab_index => self
+                .get_active_tab_mut(client_id)
+                .and_then(|tab| {
+                    tab.toggle_pane_visibility(pane_id, client_id)?;
+                    tab.center_floating_pane(pane_id)
+                })
+                .with_context(err_context),
+            Some((pane_id, tab_index)) => {
+                // the scratchpad follows the user around, so we bring it over from the tab it was
+                // last shown in
+                let scratchpad_pane = self
+                    .tabs
+                    .get_mut(&tab_index)
+                    .and_then(|tab| tab.extract_pane(pane_id, Some(client_id)))
+                    .with_context(err_context)?;
+                let active_tab = self
+                    .get_active_tab_mut(client_id)
+                    .with_context(err_context)?;
+                active_tab.show_floating_panes();
+                active_tab
+                    .add_floating_pane(scratchpad_pane, pane_id, Some(client_id))
+                    .and_then(|_| active_tab.center_floating_pane(pane_id))
+                    .with_context(err_context)
+            },
+            None => {
+                // first use, or the scratchpad's shell has exited
+                self.scratchpad_pane_id = None;
+                self.bus
+                    .senders
+                    .send_to_pty(PtyInstruction::SpawnScratchpad(default_shell, client_id))
+                    .with_context(err_context)
+            },
+        }
+    }
+    pub fn switch_to_next_session_in_group(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to switch workspace for client {client_id}");
+        let group = match &self.session_group {
+            Some(group) => group,
+            None => {
+                log::warn!("Cannot switch workspace: this session does not belong to a group");
+                return Ok(());
+            },
+        };
+        // session_infos_on_machine is keyed (and thus sorted) by session name
+        let mut sessions_in_group: Vec<&String> = self
+            .session_infos_on_machine
+            .values()
+            .filter(|s| s.group.as_ref() == Some(group))
+            .map(|s| &s.name)
+            .collect();
+        if !sessions_in_group.contains(&&self.session_name) {
+            sessions_in_group.push(&self.session_name);
+            sessions_in_group.sort();
+        }
+        let current_position = sessions_in_group
+            .iter()
+            .position(|s| *s == &self.session_name)
+            .with_context(err_context)?;
+        let next_session = sessions_in_group[(current_position + 1) % sessions_in_group.len()];
+        if next_session != &self.session_name {
+            let connect_to_session = ConnectToSession {
+                name: Some(next_session.clone()),
+                ..Default::default()
+            };
+            self.bus
+                .senders
+                .send_to_server(ServerInstruction::SwitchSession(
+                    connect_to_session,
+                    client_id,
+                ))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
+    pub fn update_active_tab_name(&mut self, buf: Vec<u8>, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to update active tabs name for client id: {client_id:?}");
+
+        let client_id = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        };
+
+        match client_id {
+            Some(client_id) => {
+                let s = str::from_utf8(&buf)
+                    .with_context(|| format!("failed to construct tab name from buf: {buf:?}"))
+                    .with_context(err_context)?;
+                match self.get_active_tab_mut(client_id) {
+                    Ok(active_tab) => {
+                        match s {
+                            "\0" => {
+                                active_tab.name = String::new();
+                            },
+                            "\u{007F}" | "\u{0008}" => {
+                                // delete and backspace keys
+                                active_tab.name.pop();
+                            },
+                            c => {
+                                // It only allows printable unicode
+                                if !c.chars().any(char::is_control) {
+                                    active_tab.name.push_str(c);
+                                }
+                            },
+                        }
+                        self.log_and_report_session_state()
+                            .with_context(err_context)
+                    },
+                    Err(err) => {
+                        Err::<(), _>(err).with_context(err_context).non_fatal();
+                        Ok(())
+                    },
+                }
+            },
+            None => Ok(()),
+        }
+    }
+    pub fn undo_active_rename_tab(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to undo active tab rename for client {}", client_id);
 
         let client_id = if self.get_active_tab(client_id).is_ok() {
             Some(client_id)
@@ -1528,6 +2648,55 @@ impl Screen {
         }
         Ok(())
     }
+    pub fn rebind_keys(&mut self, keybinds: Keybinds) -> Result<()> {
+        let keybinds = keybinds.to_keybinds_vec();
+        self.update_mode_infos(|mode_info| mode_info.keybinds = keybinds.clone())
+            .context("failed to update plugins with new keybindings")
+    }
+    pub fn reconfigure(
+        &mut self,
+        keybinds: Keybinds,
+        style: Style,
+        capabilities: PluginCapabilities,
+        draw_pane_frames: Option<bool>,
+    ) -> Result<()> {
+        let err_context = || "failed to reconfigure session";
+        self.style = style;
+        self.uses_default_palette = style.colors == default_palette();
+        for tab in self.tabs.values_mut() {
+            tab.update_style(style);
+        }
+        if let Some(draw_pane_frames) = draw_pane_frames {
+            self.draw_pane_frames = draw_pane_frames;
+            for tab in self.tabs.values_mut() {
+                tab.set_pane_frames(draw_pane_frames);
+            }
+        }
+        let keybinds = keybinds.to_keybinds_vec();
+        self.update_mode_infos(|mode_info| {
+            mode_info.keybinds = keybinds.clone();
+            mode_info.style = style;
+            mode_info.capabilities = capabilities;
+        })
+        .with_context(err_context)?;
+        self.apply_terminal_emulator_colors()
+            .with_context(err_context)?;
+        self.render().with_context(err_context)
+    }
+    fn update_mode_infos(&mut self, update: impl Fn(&mut ModeInfo)) -> Result<()> {
+        // updates the mode info of all clients and lets plugins know about it
+        update(&mut self.default_mode_info);
+        for (client_id, mode_info) in self.mode_info.iter_mut() {
+            update(mode_info);
+            for tab in self.tabs.values_mut() {
+                tab.change_mode_info(mode_info.clone(), *client_id);
+            }
+        }
+        for tab in self.tabs.values_mut() {
+            tab.update_input_modes()?;
+        }
+        Ok(())
+    }
     pub fn move_focus_left_or_previous_tab(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || {
             format!(
@@ -1687,18 +2856,32 @@ impl Screen {
             .iter()
             .find(|(_tab_index, tab)| tab.has_pane_with_pid(&pane_id))
             .map(|(tab_index, _tab)| *tab_index);
-        match tab_index {
-            Some(tab_index) => {
-                self.go_to_tab(tab_index + 1, client_id)?;
+        // cli clients are not focused on any tab, the pane is focused for the first client instead
+        let client_id_to_focus = if self.get_active_tab(client_id).is_ok() {
+            Some(client_id)
+        } else {
+            self.get_first_client_id()
+        };
+        match (tab_index, client_id_to_focus) {
+            (Some(tab_index), Some(client_id_to_focus)) => {
+                self.go_to_tab(tab_index + 1, client_id_to_focus)?;
                 self.tabs
                     .get_mut(&tab_index)
                     .with_context(err_context)?
-                    .focus_pane_with_id(pane_id, should_float_if_hidden, client_id)
+                    .focus_pane_with_id(pane_id, should_float_if_hidden, client_id_to_focus)
                     .context("failed to focus pane with id")?;
             },
-            None => {
+            (None, _) => {
                 log::error!("Could not find pane with id: {:?}", pane_id);
+                self.bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(
+                        vec![format!("No pane with id {:?}", pane_id)],
+                        client_id,
+                    ))
+                    .with_context(err_context)?;
             },
+            (Some(_), None) => {},
         };
         Ok(())
     }
@@ -1765,6 +2948,66 @@ impl Screen {
         }
         Ok(())
     }
+    /// The first grouped tiled pane is tiled in the new tab, the rest of the grouped panes float
+    /// in it
+    pub fn break_grouped_panes_to_new_tab(
+        &mut self,
+        default_shell: Option<TerminalAction>,
+        default_layout: Box<Layout>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || "failed to break grouped panes out of tab".to_string();
+        let active_tab = self.get_active_tab_mut(client_id)?;
+        let grouped_pane_ids = active_tab.grouped_pane_ids();
+        if grouped_pane_ids.is_empty() {
+            self.display_error_in_active_pane("No panes are grouped!", client_id)
+                .with_context(err_context)?;
+            self.unblock_input()?;
+            return Ok(());
+        }
+        if active_tab.all_tiled_panes_are_grouped() {
+            self.bus
+                .senders
+                .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                    grouped_pane_ids,
+                    "Cannot break all panes out!".into(),
+                ))
+                .with_context(err_context)?;
+            self.unblock_input()?;
+            return Ok(());
+        }
+        let mut grouped_panes = active_tab.take_grouped_panes(client_id);
+        let tab_index = self.get_new_tab_index();
+        let swap_layouts = (
+            default_layout.swap_tiled_layouts.clone(),
+            default_layout.swap_floating_layouts.clone(),
+        );
+        self.new_tab(tab_index, swap_layouts, None, client_id)?;
+        let tab = self.tabs.get_mut(&tab_index).with_context(err_context)?;
+        let (mut tiled_panes_layout, floating_panes_layout) = default_layout.new_tab();
+        let tiled_pane_position = grouped_panes
+            .iter()
+            .position(|(_pane_id, _pane, is_floating)| !is_floating)
+            .unwrap_or(0);
+        let (tiled_pane_id, tiled_pane, _is_floating) = grouped_panes.remove(tiled_pane_position);
+        tiled_panes_layout.ignore_run_instruction(tiled_pane.invoked_with().clone());
+        tab.add_tiled_pane(tiled_pane, tiled_pane_id, Some(client_id))?;
+        if !grouped_panes.is_empty() {
+            tab.show_floating_panes();
+        }
+        for (pane_id, pane, _is_floating) in grouped_panes {
+            tab.add_floating_pane(pane, pane_id, Some(client_id))?;
+        }
+        self.bus.senders.send_to_plugin(PluginInstruction::NewTab(
+            None,
+            default_shell,
+            Some(tiled_panes_layout),
+            floating_panes_layout,
+            tab_index,
+            client_id,
+        ))?;
+        Ok(())
+    }
     pub fn break_pane_to_new_tab(
         &mut self,
         direction: Direction,
@@ -1772,17 +3015,8 @@ impl Screen {
     ) -> Result<()> {
         let err_context = || "failed break pane out of tab".to_string();
         if self.tabs.len() > 1 {
-            let (active_pane_id, active_pane, pane_to_break_is_floating) = {
-                let active_tab = self.get_active_tab_mut(client_id)?;
-                let active_pane_id = active_tab
-                    .get_active_pane_id(client_id)
-                    .with_context(err_context)?;
-                let pane_to_break_is_floating = active_tab.are_floating_panes_visible();
-                let active_pane = active_tab
-                    .close_pane(active_pane_id, false, Some(client_id))
-                    .with_context(err_context)?;
-                (active_pane_id, active_pane, pane_to_break_is_floating)
-            };
+            let (active_pane_id, active_pane, pane_to_break_is_floating) =
+                self.take_active_pane(client_id).with_context(err_context)?;
             let update_mode_infos = false;
             match direction {
                 Direction::Right | Direction::Down => {
@@ -1792,36 +3026,94 @@ impl Screen {
                     self.switch_tab_prev(None, update_mode_infos, client_id)?;
                 },
             };
-            let new_active_tab = self.get_active_tab_mut(client_id)?;
+            self.add_pane_to_active_tab(
+                active_pane_id,
+                active_pane,
+                pane_to_break_is_floating,
+                client_id,
+            )
+            .with_context(err_context)?;
 
-            if pane_to_break_is_floating {
-                new_active_tab.show_floating_panes();
-                new_active_tab.add_floating_pane(active_pane, active_pane_id, Some(client_id))?;
-            } else {
-                new_active_tab.hide_floating_panes();
-                new_active_tab.add_tiled_pane(active_pane, active_pane_id, Some(client_id))?;
-            }
+            self.log_and_report_session_state()?;
+        } else {
+            self.display_error_in_active_pane("No other tabs to add pane to!", client_id)
+                .with_context(err_context)?;
+        }
+        self.unblock_input()?;
+        self.render()?;
+        Ok(())
+    }
+    pub fn move_pane_to_tab(&mut self, tab_index: usize, client_id: ClientId) -> Result<()> {
+        // tab_index is 1 indexed, like the one of GoToTab
+        let err_context = || format!("failed to move pane to tab {tab_index}");
+        let active_tab_position = self.get_active_tab(client_id)?.position;
+        let tab_exists = self.tabs.values().any(|tab| tab.position + 1 == tab_index);
+        if tab_exists && active_tab_position + 1 != tab_index {
+            let (active_pane_id, active_pane, pane_to_move_is_floating) =
+                self.take_active_pane(client_id).with_context(err_context)?;
+            let update_mode_infos = false;
+            self.switch_active_tab(tab_index - 1, None, update_mode_infos, client_id)?;
+            self.add_pane_to_active_tab(
+                active_pane_id,
+                active_pane,
+                pane_to_move_is_floating,
+                client_id,
+            )
+            .with_context(err_context)?;
 
             self.log_and_report_session_state()?;
+        } else if tab_exists {
+            self.display_error_in_active_pane("Pane is already in this tab!", client_id)
+                .with_context(err_context)?;
         } else {
-            let active_pane_id = {
-                let active_tab = self.get_active_tab_mut(client_id)?;
-                active_tab
-                    .get_active_pane_id(client_id)
-                    .with_context(err_context)?
-            };
-            self.bus
-                .senders
-                .send_to_background_jobs(BackgroundJob::DisplayPaneError(
-                    vec![active_pane_id],
-                    "No other tabs to add pane to!".into(),
-                ))
+            self.display_error_in_active_pane(&format!("No tab #{tab_index}!"), client_id)
                 .with_context(err_context)?;
         }
         self.unblock_input()?;
         self.render()?;
         Ok(())
     }
+    fn take_active_pane(&mut self, client_id: ClientId) -> Result<(PaneId, Box<dyn Pane>, bool)> {
+        // returns the pane along with whether it was floating, its process keeps running
+        let err_context = || format!("failed to take active pane of client {client_id}");
+        let active_tab = self.get_active_tab_mut(client_id)?;
+        let active_pane_id = active_tab
+            .get_active_pane_id(client_id)
+            .with_context(err_context)?;
+        let pane_is_floating = active_tab.are_floating_panes_visible();
+        let active_pane = active_tab
+            .close_pane(active_pane_id, false, Some(client_id))
+            .with_context(err_context)?;
+        Ok((active_pane_id, active_pane, pane_is_floating))
+    }
+    fn add_pane_to_active_tab(
+        &mut self,
+        pane_id: PaneId,
+        pane: Box<dyn Pane>,
+        should_float: bool,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let active_tab = self.get_active_tab_mut(client_id)?;
+        if should_float {
+            active_tab.show_floating_panes();
+            active_tab.add_floating_pane(pane, pane_id, Some(client_id))
+        } else {
+            active_tab.hide_floating_panes();
+            active_tab.add_tiled_pane(pane, pane_id, Some(client_id))
+        }
+    }
+    fn display_error_in_active_pane(&mut self, error: &str, client_id: ClientId) -> Result<()> {
+        let active_pane_id = self
+            .get_active_tab(client_id)?
+            .get_active_pane_id(client_id)
+            .with_context(|| format!("failed to find active pane of client {client_id}"))?;
+        self.bus
+            .senders
+            .send_to_background_jobs(BackgroundJob::DisplayPaneError(
+                vec![active_pane_id],
+                error.into(),
+            ))
+    }
 
     fn unblock_input(&self) -> Result<()> {
         self.bus
@@ -1845,6 +3137,10 @@ pub(crate) fn screen_thread_main(
     let draw_pane_frames = config_options.pane_frames.unwrap_or(true);
     let auto_layout = config_options.auto_layout.unwrap_or(true);
     let session_is_mirrored = config_options.mirror_session.unwrap_or(false);
+    let tab_focus_is_mirrored =
+        session_is_mirrored && config_options.mirror_tab_focus.unwrap_or(true);
+    let session_serialization = config_options.session_serialization.unwrap_or(true);
+    let serialize_pane_scrollback = config_options.serialize_pane_scrollback.unwrap_or(false);
     let copy_options = CopyOptions::new(
         config_options.copy_command,
         config_options.copy_clipboard.unwrap_or_default(),
@@ -1866,7 +3162,11 @@ pub(crate) fn screen_thread_main(
         draw_pane_frames,
         auto_layout,
         session_is_mirrored,
+        tab_focus_is_mirrored,
         copy_options,
+        config_options.session_group.clone(),
+        session_serialization,
+        serialize_pane_scrollback,
         debug,
     );
 
@@ -1874,6 +3174,10 @@ pub(crate) fn screen_thread_main(
     let mut pending_tab_switches: HashSet<(usize, ClientId)> = HashSet::new(); // usize is the
                                                                                // tab_index
 
+    // clients that left while tabs were still pending (eg. the client that created a session in
+    // the background), removed once those tabs have their layout applied
+    let mut pending_client_removals: HashSet<ClientId> = HashSet::new();
+
     loop {
         let (event, mut err_ctx) = screen
             .bus
@@ -1886,14 +3190,25 @@ pub(crate) fn screen_thread_main(
 
         match event {
             ScreenInstruction::PtyBytes(pid, vte_bytes) => {
+                screen.report_pane_output(pid, &vte_bytes)?;
+                let mut tab_with_output = None;
+                let mut pane_monitor_changed = false;
                 let all_tabs = screen.get_tabs_mut();
                 for tab in all_tabs.values_mut() {
                     if tab.has_terminal_pid(pid) {
                         tab.handle_pty_bytes(pid, vte_bytes)
                             .context("failed to process pty bytes")?;
+                        pane_monitor_changed = tab.take_pane_monitor_changes();
+                        tab_with_output = Some(tab.index);
                         break;
                     }
                 }
+                if let Some(tab_index) = tab_with_output {
+                    screen.mark_unseen_output(tab_index)?;
+                }
+                if pane_monitor_changed {
+                    screen.log_and_report_session_state()?;
+                }
             },
             ScreenInstruction::PluginBytes(mut plugin_bytes) => {
                 for (pid, client_id, vte_bytes) in plugin_bytes.drain(..) {
@@ -1972,6 +3287,43 @@ pub(crate) fn screen_thread_main(
 
                 screen.render()?;
             },
+            ScreenInstruction::ReplacePane(
+                pid,
+                initial_pane_title,
+                hold_for_command,
+                client_id,
+                launcher_plugin_id,
+            ) => {
+                // the client_id might belong to a cli client (eg. `zellij run --in-place`), in
+                // which case we replace the active pane of the first connected client
+                active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                    .replace_active_pane(pid, initial_pane_title, None, client_id), ?);
+                if let Some(launcher_plugin_id) = launcher_plugin_id {
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, _client_id: ClientId| tab
+                            .add_in_place_pane_launcher(pid, launcher_plugin_id)
+                    );
+                }
+                if let Some(hold_for_command) = hold_for_command {
+                    let is_first_run = true;
+                    active_tab_and_connected_client_id!(
+                        screen,
+                        client_id,
+                        |tab: &mut Tab, _client_id: ClientId| tab.hold_pane(
+                            pid,
+                            None,
+                            is_first_run,
+                            hold_for_command
+                        )
+                    );
+                }
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render()?;
+            },
             ScreenInstruction::TogglePaneEmbedOrFloating(client_id) => {
                 active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
                     .toggle_pane_embed_or_floating(client_id), ?);
@@ -1980,6 +3332,15 @@ pub(crate) fn screen_thread_main(
 
                 screen.render()?;
             },
+            ScreenInstruction::TogglePanePinned(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_pinned(client_id)
+                );
+                screen.unblock_input()?;
+                screen.render()?;
+            },
             ScreenInstruction::ToggleFloatingPanes(client_id, default_shell) => {
                 active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
                     .toggle_floating_panes(Some(client_id), default_shell), ?);
@@ -2047,6 +3408,12 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
             },
             ScreenInstruction::WriteCharacter(bytes, client_id) => {
+                if screen.handle_link_hints_input(&bytes, client_id)? {
+                    continue;
+                }
+                if screen.handle_close_pane_confirmation_input(&bytes, client_id)? {
+                    continue;
+                }
                 let mut state_changed = false;
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2064,6 +3431,7 @@ pub(crate) fn screen_thread_main(
                     ?
                 );
                 if state_changed {
+                    screen.render()?;
                     screen.log_and_report_session_state()?;
                 }
             },
@@ -2118,6 +3486,19 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::MoveFocusOrDelegate(direction, key_bytes, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.move_focus_or_delegate(
+                        direction, key_bytes, client_id
+                    ),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::MoveFocusLeftOrPreviousTab(client_id) => {
                 screen.move_focus_left_or_previous_tab(client_id)?;
                 screen.unblock_input()?;
@@ -2175,14 +3556,15 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::DumpScreen(file, client_id, full) => {
+            ScreenInstruction::DumpScreen(file, client_id, full, ansi) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab.dump_active_terminal_screen(
                         Some(file.to_string()),
                         client_id,
-                        full
+                        full,
+                        ansi
                     ),
                     ?
                 );
@@ -2199,6 +3581,20 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::EnterCopyMode(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.enter_copy_mode(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ShowLinkHints(client_id) => {
+                screen.show_link_hints(client_id)?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::ScrollUp(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2242,77 +3638,117 @@ pub(crate) fn screen_thread_main(
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_up(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_up(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::MovePaneRight(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_right(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::MovePaneLeft(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_left(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::ScrollUpAt(point, client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .handle_scrollwheel_up(&point, 3, client_id), ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ScrollDown(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.scroll_active_terminal_down(client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
-                screen.log_and_report_session_state()?;
             },
-            ScreenInstruction::MovePaneRight(client_id) => {
+            ScreenInstruction::ScrollDownAt(point, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_right(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .handle_scrollwheel_down(&point, 3, client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
-                screen.log_and_report_session_state()?;
             },
-            ScreenInstruction::MovePaneLeft(client_id) => {
+            ScreenInstruction::ForceScrollUpAt(point, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.move_active_pane_left(client_id)
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .handle_forced_scrollwheel_up(&point, 3, client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
-                screen.log_and_report_session_state()?;
             },
-            ScreenInstruction::ScrollUpAt(point, client_id) => {
+            ScreenInstruction::ForceScrollDownAt(point, client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .handle_scrollwheel_up(&point, 3, client_id), ?
+                        .handle_forced_scrollwheel_down(&point, 3, client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::ScrollDown(client_id) => {
+            ScreenInstruction::ScrollToBottom(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.scroll_active_terminal_down(client_id), ?
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .scroll_active_terminal_to_bottom(client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::ScrollDownAt(point, client_id) => {
+            ScreenInstruction::ScrollToTop(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .handle_scrollwheel_down(&point, 3, client_id), ?
+                        .scroll_active_terminal_to_top(client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::ScrollToBottom(client_id) => {
+            ScreenInstruction::ScrollToPreviousPrompt(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_to_bottom(client_id), ?
+                        .scroll_active_terminal_to_previous_prompt(client_id)
                 );
                 screen.render()?;
                 screen.unblock_input()?;
             },
-            ScreenInstruction::ScrollToTop(client_id) => {
+            ScreenInstruction::ScrollToNextPrompt(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
                     client_id,
                     |tab: &mut Tab, client_id: ClientId| tab
-                        .scroll_active_terminal_to_top(client_id), ?
+                        .scroll_active_terminal_to_next_prompt(client_id), ?
                 );
                 screen.render()?;
                 screen.unblock_input()?;
@@ -2368,11 +3804,7 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
             },
             ScreenInstruction::CloseFocusedPane(client_id) => {
-                active_tab_and_connected_client_id!(
-                    screen,
-                    client_id,
-                    |tab: &mut Tab, client_id: ClientId| tab.close_focused_pane(client_id), ?
-                );
+                screen.close_focused_pane_or_ask_to_confirm(client_id)?;
                 screen.render()?;
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
@@ -2473,6 +3905,37 @@ pub(crate) fn screen_thread_main(
                 screen.unblock_input()?;
                 screen.log_and_report_session_state()?;
             },
+            ScreenInstruction::ToggleExpandPane(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_stacked(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::FocusNextPaneInStack(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.focus_next_pane_in_stack(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
+            ScreenInstruction::FocusPreviousPaneInStack(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .focus_previous_pane_in_stack(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+            },
             ScreenInstruction::TogglePaneFrames => {
                 screen.draw_pane_frames = !screen.draw_pane_frames;
                 for tab in screen.tabs.values_mut() {
@@ -2493,10 +3956,38 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
             },
             ScreenInstruction::CloseTab(client_id) => {
+                screen.remember_active_tab(client_id);
                 screen.close_tab(client_id)?;
                 screen.unblock_input()?;
                 screen.render()?;
             },
+            ScreenInstruction::ReopenClosedPane(default_shell, client_id) => {
+                screen.reopen_closed_pane(default_shell, client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ReopenClosedTab(default_shell, swap_layouts, client_id) => {
+                match screen.pop_closed_tab_layout()? {
+                    Some((tab_name, layout, floating_panes_layout)) => {
+                        let tab_index = screen.get_new_tab_index();
+                        pending_tab_ids.insert(tab_index);
+                        screen.new_tab(tab_index, swap_layouts, tab_name, client_id)?;
+                        screen
+                            .bus
+                            .senders
+                            .send_to_plugin(PluginInstruction::NewTab(
+                                None,
+                                default_shell,
+                                Some(layout),
+                                floating_panes_layout,
+                                tab_index,
+                                client_id,
+                            ))?;
+                    },
+                    None => {
+                        screen.unblock_input()?;
+                    },
+                }
+            },
             ScreenInstruction::NewTab(
                 cwd,
                 default_shell,
@@ -2544,6 +4035,10 @@ pub(crate) fn screen_thread_main(
                     for (tab_index, client_id) in pending_tab_switches.drain() {
                         screen.go_to_tab(tab_index as usize, client_id)?;
                     }
+                    for client_id in pending_client_removals.drain() {
+                        screen.remove_client(client_id)?;
+                        screen.log_and_report_session_state()?;
+                    }
                 }
                 screen.unblock_input()?;
                 screen.render()?;
@@ -2636,12 +4131,18 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::TerminalBackgroundColor(background_color_instruction) => {
                 screen.update_terminal_background_color(background_color_instruction);
+                screen.apply_terminal_emulator_colors()?;
+                screen.render()?;
             },
             ScreenInstruction::TerminalForegroundColor(background_color_instruction) => {
                 screen.update_terminal_foreground_color(background_color_instruction);
+                screen.apply_terminal_emulator_colors()?;
+                screen.render()?;
             },
             ScreenInstruction::TerminalColorRegisters(color_registers) => {
                 screen.update_terminal_color_registers(color_registers);
+                screen.apply_terminal_emulator_colors()?;
+                screen.render()?;
             },
             ScreenInstruction::ChangeMode(mode_info, client_id) => {
                 screen.change_mode(mode_info, client_id)?;
@@ -2653,6 +4154,12 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::RebindKeys(keybinds) => {
+                screen.rebind_keys(keybinds)?;
+            },
+            ScreenInstruction::Reconfigure(keybinds, style, capabilities, draw_pane_frames) => {
+                screen.reconfigure(keybinds, style, capabilities, draw_pane_frames)?;
+            },
             ScreenInstruction::ToggleActiveSyncTab(client_id) => {
                 active_tab_and_connected_client_id!(
                     screen,
@@ -2663,6 +4170,71 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::ToggleActivePaneSync(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_sync(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::TogglePaneInGroup(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_active_pane_in_group(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ClearPaneGroup(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.clear_pane_group()
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::CloseGroupedPanes(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.close_grouped_panes(client_id),
+                    ?
+                );
+                screen.log_and_report_session_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::FloatGroupedPanes(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.float_grouped_panes(client_id),
+                    ?
+                );
+                screen.log_and_report_session_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::BreakGroupedPanesToNewTab(
+                default_layout,
+                default_shell,
+                client_id,
+            ) => {
+                screen.break_grouped_panes_to_new_tab(default_shell, default_layout, client_id)?;
+            },
+            ScreenInstruction::SyncGroupedPanes(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, _client_id: ClientId| tab.sync_grouped_panes()
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::LeftClick(point, client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .handle_left_click(&point, client_id), ?);
@@ -2670,6 +4242,26 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::CtrlLeftClick(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_ctrl_left_click(&point, client_id), ?);
+                screen.log_and_report_session_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ShiftLeftClick(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_shift_left_click(&point, client_id), ?);
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::AltLeftClick(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_alt_left_click(&point, client_id), ?);
+                screen.log_and_report_session_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::RightClick(point, client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .handle_right_click(&point, client_id), ?);
@@ -2715,12 +4307,27 @@ pub(crate) fn screen_thread_main(
                     .handle_mouse_hold_middle(&point, client_id), ?);
                 screen.render()?;
             },
+            ScreenInstruction::MouseHover(point, client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .handle_mouse_hover(&point, client_id), ?);
+                screen.render()?;
+            },
             ScreenInstruction::Copy(client_id) => {
                 active_tab!(screen, client_id, |tab: &mut Tab| tab
                     .copy_selection(client_id), ?);
                 screen.render()?;
             },
+            ScreenInstruction::CopyLastCommandOutput(client_id) => {
+                active_tab!(screen, client_id, |tab: &mut Tab| tab
+                    .copy_last_command_output(client_id), ?);
+                screen.unblock_input()?;
+            },
             ScreenInstruction::Exit => {
+                // the periodic serialization might be up to a minute old, so the session is
+                // serialized once more as it ends (unless it ended because all its tabs closed)
+                if screen.session_serialization && !screen.tabs.is_empty() {
+                    screen.dump_layout_to_hd().non_fatal();
+                }
                 break;
             },
             ScreenInstruction::ToggleTab(client_id) => {
@@ -2729,6 +4336,7 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
             },
             ScreenInstruction::AddClient(client_id, tab_position_to_focus, pane_id_to_focus) => {
+                pending_client_removals.remove(&client_id);
                 screen.add_client(client_id)?;
                 let pane_id = pane_id_to_focus.map(|(pane_id, is_plugin)| {
                     if is_plugin {
@@ -2746,9 +4354,14 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
             },
             ScreenInstruction::RemoveClient(client_id) => {
-                screen.remove_client(client_id)?;
-                screen.log_and_report_session_state()?;
-                screen.render()?;
+                if pending_tab_ids.is_empty() {
+                    screen.remove_client(client_id)?;
+                    screen.log_and_report_session_state()?;
+                    screen.render()?;
+                } else {
+                    // the pending tabs would otherwise add this client back once they're applied
+                    pending_client_removals.insert(client_id);
+                }
             },
             ScreenInstruction::AddOverlay(overlay, _client_id) => {
                 screen.get_active_overlays_mut().pop();
@@ -2830,6 +4443,15 @@ pub(crate) fn screen_thread_main(
                 screen.render()?;
                 screen.unblock_input()?;
             },
+            ScreenInstruction::SearchToggleRegex(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.toggle_search_regex(client_id)
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
             ScreenInstruction::AddRedPaneFrameColorOverride(pane_ids, error_text) => {
                 let all_tabs = screen.get_tabs_mut();
                 for pane_id in pane_ids {
@@ -2887,12 +4509,51 @@ pub(crate) fn screen_thread_main(
                     .senders
                     .send_to_server(ServerInstruction::Log(tab_names, client_id))?;
             },
+            ScreenInstruction::QueryPaneProcesses(client_id) => {
+                let pane_processes = screen.pane_processes();
+                screen
+                    .bus
+                    .senders
+                    .send_to_server(ServerInstruction::Log(pane_processes, client_id))?;
+            },
+            ScreenInstruction::LoadBuffer(contents, _client_id) => {
+                screen.paste_buffer = Some(contents);
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::SaveBuffer(file_path, _client_id) => {
+                screen.save_paste_buffer(file_path)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::PasteBuffer(terminal_pane_id, client_id) => {
+                screen.paste_buffer_to_pane(terminal_pane_id, client_id)?;
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::PipeToPane(terminal_pane_id, payload, client_id) => {
+                screen.pipe_to_pane(terminal_pane_id, payload, client_id)?;
+            },
+            ScreenInstruction::WriteToPaneId(bytes, terminal_pane_id, client_id) => {
+                screen.write_to_pane_id(bytes, terminal_pane_id, client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::UpdateTerminalProcesses(terminal_processes) => {
+                if screen.terminal_processes != terminal_processes {
+                    for tab in screen.tabs.values_mut() {
+                        tab.update_terminal_processes(&terminal_processes);
+                    }
+                    screen.terminal_processes = terminal_processes;
+                    screen.log_and_report_session_state()?;
+                    screen.render()?;
+                }
+            },
             ScreenInstruction::NewTiledPluginPane(run_plugin, pane_title, client_id) => {
                 let tab_index = screen.active_tab_indices.values().next().unwrap_or(&1);
                 let size = Size::default();
                 let should_float = Some(false);
+                let should_be_open_in_place = false;
                 screen.bus.senders.send_to_plugin(PluginInstruction::Load(
                     should_float,
+                    should_be_open_in_place,
                     pane_title,
                     run_plugin,
                     *tab_index,
@@ -2905,8 +4566,33 @@ pub(crate) fn screen_thread_main(
                     Some(tab_index) => {
                         let size = Size::default();
                         let should_float = Some(true);
+                        let should_be_open_in_place = false;
+                        screen.bus.senders.send_to_plugin(PluginInstruction::Load(
+                            should_float,
+                            should_be_open_in_place,
+                            pane_title,
+                            run_plugin,
+                            *tab_index,
+                            client_id,
+                            size,
+                        ))?;
+                    },
+                    None => {
+                        log::error!(
+                            "Could not find an active tab - is there at least 1 connected user?"
+                        );
+                    },
+                }
+            },
+            ScreenInstruction::NewInPlacePluginPane(run_plugin, pane_title, client_id) => {
+                match screen.active_tab_indices.values().next() {
+                    Some(tab_index) => {
+                        let size = Size::default();
+                        let should_float = None;
+                        let should_be_open_in_place = true;
                         screen.bus.senders.send_to_plugin(PluginInstruction::Load(
                             should_float,
+                            should_be_open_in_place,
                             pane_title,
                             run_plugin,
                             *tab_index,
@@ -2938,15 +4624,32 @@ pub(crate) fn screen_thread_main(
             },
             ScreenInstruction::AddPlugin(
                 should_float,
+                should_be_open_in_place,
                 run_plugin_location,
                 pane_title,
                 tab_index,
                 plugin_id,
+                client_id,
             ) => {
                 let pane_title =
                     pane_title.unwrap_or_else(|| run_plugin_location.location.to_string());
                 let run_plugin = Run::Plugin(run_plugin_location);
-                if let Some(active_tab) = screen.tabs.get_mut(&tab_index) {
+                if should_be_open_in_place {
+                    match client_id {
+                        Some(client_id) => {
+                            active_tab_and_connected_client_id!(screen, client_id, |tab: &mut Tab, client_id: ClientId| tab
+                                .replace_active_pane(
+                                    PaneId::Plugin(plugin_id),
+                                    Some(pane_title),
+                                    Some(run_plugin),
+                                    client_id
+                                ), ?);
+                        },
+                        None => {
+                            log::error!("Cannot open plugin in place without a client");
+                        },
+                    }
+                } else if let Some(active_tab) = screen.tabs.get_mut(&tab_index) {
                     active_tab.new_pane(
                         PaneId::Plugin(plugin_id),
                         Some(pane_title),
@@ -3026,8 +4729,10 @@ pub(crate) fn screen_thread_main(
                             screen.render()?;
                             screen.log_and_report_session_state()?;
                         } else {
+                            let should_be_open_in_place = false;
                             screen.bus.senders.send_to_plugin(PluginInstruction::Load(
                                 Some(should_float),
+                                should_be_open_in_place,
                                 None,
                                 run_plugin,
                                 tab_index,
@@ -3053,6 +4758,8 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::FocusPaneWithId(pane_id, should_float_if_hidden, client_id) => {
                 screen.focus_pane_with_id(pane_id, should_float_if_hidden, client_id)?;
                 screen.log_and_report_session_state()?;
+                screen.render()?;
+                screen.unblock_input()?;
             },
             ScreenInstruction::RenamePane(pane_id, new_name) => {
                 let all_tabs = screen.get_tabs_mut();
@@ -3067,13 +4774,18 @@ pub(crate) fn screen_thread_main(
                 }
                 screen.log_and_report_session_state()?;
             },
-            ScreenInstruction::RenameTab(tab_index, new_name) => {
-                match screen.tabs.get_mut(&tab_index.saturating_sub(1)) {
+            ScreenInstruction::RenameTab(tab_position, new_name) => {
+                // tab_position is 1 indexed, and tabs keep their index when they are moved around
+                match screen
+                    .tabs
+                    .values_mut()
+                    .find(|tab| tab.position == tab_position.saturating_sub(1))
+                {
                     Some(tab) => {
                         tab.name = String::from_utf8_lossy(&new_name).to_string();
                     },
                     None => {
-                        log::error!("Failed to find tab with index: {:?}", tab_index);
+                        log::error!("Failed to find tab with position: {:?}", tab_position);
                     },
                 }
                 screen.log_and_report_session_state()?;
@@ -3105,8 +4817,109 @@ pub(crate) fn screen_thread_main(
             ScreenInstruction::BreakPaneLeft(client_id) => {
                 screen.break_pane_to_new_tab(Direction::Left, client_id)?;
             },
-            ScreenInstruction::UpdateSessionInfos(new_session_infos) => {
-                screen.update_session_infos(new_session_infos)?;
+            ScreenInstruction::MovePaneToTab(tab_index, client_id) => {
+                screen.move_pane_to_tab(tab_index as usize, client_id)?;
+            },
+            ScreenInstruction::MoveTab(direction, client_id) => {
+                screen.move_active_tab(direction, client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::MoveTabToIndex(tab_position, new_tab_position) => {
+                screen.move_tab_to_position(tab_position, new_tab_position)?;
+            },
+            ScreenInstruction::CloseTabAtPosition(tab_position) => {
+                screen.close_tab_at_position(tab_position)?;
+            },
+            ScreenInstruction::RespawnPane(client_id) => {
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab.respawn_active_pane(client_id),
+                    ?
+                );
+                screen.render()?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::MarkSilentPanes => {
+                if let Some(monitor_silence) = MONITOR_SILENCE.get() {
+                    let mut pane_monitor_changed = false;
+                    for tab in screen.get_tabs_mut().values_mut() {
+                        tab.mark_silent_panes(Duration::from_secs(*monitor_silence));
+                        pane_monitor_changed |= tab.take_pane_monitor_changes();
+                    }
+                    if pane_monitor_changed {
+                        screen.log_and_report_session_state()?;
+                    }
+                }
+            },
+            ScreenInstruction::UpdateSessionInfos(new_session_infos, resurrectable_sessions) => {
+                screen.update_session_infos(new_session_infos, resurrectable_sessions)?;
+            },
+            ScreenInstruction::SwitchWorkspace(client_id) => {
+                screen.switch_to_next_session_in_group(client_id)?;
+                screen.unblock_input()?;
+            },
+            ScreenInstruction::ToggleScratchpad(client_id, default_shell) => {
+                screen.toggle_scratchpad(client_id, default_shell)?;
+                screen.unblock_input()?;
+                screen.log_and_report_session_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::NewScratchpadPane(pid, client_id) => {
+                screen.scratchpad_pane_id = Some(pid);
+                let should_float = Some(true);
+                active_tab_and_connected_client_id!(
+                    screen,
+                    client_id,
+                    |tab: &mut Tab, client_id: ClientId| tab
+                        .new_pane(
+                            pid,
+                            Some(SCRATCHPAD_PANE_TITLE.to_owned()),
+                            should_float,
+                            None,
+                            Some(client_id)
+                        )
+                        .and_then(|_| tab.center_floating_pane(pid)),
+                    ?
+                );
+                screen.log_and_report_session_state()?;
+
+                screen.render()?;
+            },
+            ScreenInstruction::SubscribeToPaneOutput(terminal_pane_id, plugin_id, client_id) => {
+                screen.subscribe_to_pane_output(terminal_pane_id, plugin_id, client_id);
+            },
+            ScreenInstruction::UnsubscribeFromPaneOutput(
+                terminal_pane_id,
+                plugin_id,
+                client_id,
+            ) => {
+                screen.unsubscribe_from_pane_output(terminal_pane_id, plugin_id, client_id);
+            },
+            ScreenInstruction::RemovePaneOutputSubscriptionsOfPane(terminal_pane_id) => {
+                screen.remove_pane_output_subscriptions_of_pane(terminal_pane_id);
+            },
+            ScreenInstruction::RemovePaneOutputSubscriptionsOfPlugin(plugin_id) => {
+                screen.remove_pane_output_subscriptions_of_plugin(plugin_id);
+            },
+            ScreenInstruction::DumpPaneContents(terminal_pane_id, full, plugin_id, client_id) => {
+                screen.dump_pane_contents(terminal_pane_id, full, plugin_id, client_id)?;
+            },
+            ScreenInstruction::DumpLayoutToHd => {
+                if screen.session_serialization {
+                    screen.dump_layout_to_hd()?;
+                }
+            },
+            ScreenInstruction::DumpLayout(client_id) => {
+                screen.dump_layout(client_id)?;
+            },
+            ScreenInstruction::RenameSession(new_session_name) => {
+                screen.rename_session(new_session_name)?;
+                screen.render()?;
+            },
+            ScreenInstruction::SerializeSessionAndQuit(client_id) => {
+                screen.serialize_session_and_quit(client_id)?;
             },
         }
     }
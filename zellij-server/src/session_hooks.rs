@@ -0,0 +1,62 @@
+use std::process::{Command, Stdio};
+
+use crate::ClientId;
+use zellij_utils::anyhow::{Context, Result};
+use zellij_utils::consts::{
+    ON_CLIENT_ATTACH_COMMAND, ON_CLIENT_DETACH_COMMAND, ON_SESSION_CREATE_COMMAND,
+};
+use zellij_utils::envs;
+
+/// The points in the life of a session at which a user command can be run, set with the
+/// `on_session_create_command`, `on_client_attach_command` and `on_client_detach_command` options
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionHook {
+    SessionCreate,
+    ClientAttach(ClientId, usize), // usize - number of connected clients, including this one
+    ClientDetach(ClientId, usize), // usize - number of clients still connected
+}
+
+impl SessionHook {
+    fn command(&self) -> Option<&'static String> {
+        match self {
+            SessionHook::SessionCreate => ON_SESSION_CREATE_COMMAND.get(),
+            SessionHook::ClientAttach(..) => ON_CLIENT_ATTACH_COMMAND.get(),
+            SessionHook::ClientDetach(..) => ON_CLIENT_DETACH_COMMAND.get(),
+        }
+    }
+}
+
+/// Runs the command set for `hook` (if any) without waiting for it, the session metadata is
+/// passed to it in environment variables
+pub fn run_session_hook(hook: SessionHook) -> Result<()> {
+    let hook_command = match hook.command() {
+        Some(hook_command) => hook_command,
+        None => return Ok(()),
+    };
+    let mut command = Command::new("sh");
+    command
+        .arg("-c")
+        .arg(hook_command)
+        .env(
+            "ZELLIJ_SESSION_NAME",
+            envs::get_session_name().unwrap_or_default(),
+        )
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    match hook {
+        SessionHook::ClientAttach(client_id, connected_clients)
+        | SessionHook::ClientDetach(client_id, connected_clients) => {
+            command
+                .env("ZELLIJ_CLIENT_ID", client_id.to_string())
+                .env("ZELLIJ_CONNECTED_CLIENTS", connected_clients.to_string());
+        },
+        SessionHook::SessionCreate => {},
+    }
+    let mut process = command
+        .spawn()
+        .with_context(|| format!("couldn't spawn {:?} command {}", hook, hook_command))?;
+    // reap the command once it exits so it doesn't linger as a zombie
+    std::thread::spawn(move || process.wait());
+    Ok(())
+}
@@ -290,6 +290,11 @@ impl<'a> LayoutApplier<'a> {
                             if let Some(exclude_from_sync) = layout.exclude_from_sync {
                                 new_pane.set_exclude_from_sync(exclude_from_sync);
                             }
+                            if let Some(pane_initial_contents) = &layout.pane_initial_contents {
+                                new_pane.handle_pty_bytes(
+                                    pane_initial_contents.replace('\n', "\n\r").into_bytes(),
+                                );
+                            }
                             if let Some(held_command) = hold_for_command {
                                 new_pane.hold(None, true, held_command.clone());
                             }
@@ -408,6 +413,10 @@ impl<'a> LayoutApplier<'a> {
                 );
                 new_pane.set_borderless(false);
                 new_pane.set_content_offset(Offset::frame(1));
+                if let Some(pane_initial_contents) = &floating_pane_layout.pane_initial_contents {
+                    new_pane
+                        .handle_pty_bytes(pane_initial_contents.replace('\n', "\n\r").into_bytes());
+                }
                 if let Some(held_command) = hold_for_command {
                     new_pane.hold(None, true, held_command.clone());
                 }
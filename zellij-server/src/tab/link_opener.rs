@@ -0,0 +1,40 @@
+use std::process::{Command, Stdio};
+
+use zellij_utils::anyhow::{Context, Result};
+use zellij_utils::consts::LINK_OPENER;
+
+pub struct LinkOpener {
+    command: String,
+    args: Vec<String>,
+}
+
+impl LinkOpener {
+    pub fn new(command: String) -> Self {
+        let mut command_with_args = command.split(' ').map(String::from);
+
+        Self {
+            command: command_with_args.next().expect("missing command"),
+            args: command_with_args.collect(),
+        }
+    }
+    /// The opener set with the `link_opener` option (or the platform default)
+    pub fn from_config() -> Option<Self> {
+        LINK_OPENER
+            .get()
+            .map(|command| Self::new(command.to_owned()))
+    }
+    pub fn open(&self, link: &str) -> Result<()> {
+        let mut process = Command::new(self.command.clone())
+            .args(self.args.clone())
+            .arg(link)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .with_context(|| format!("couldn't spawn {}", self.command))?;
+        // reap the opener once it exits so it doesn't linger as a zombie
+        std::thread::spawn(move || process.wait());
+
+        Ok(())
+    }
+}
@@ -4,9 +4,13 @@
 mod clipboard;
 mod copy_command;
 mod layout_applier;
+mod link_opener;
+mod pane_monitor;
 mod swap_layouts;
 
 use copy_command::CopyCommand;
+pub use link_opener::LinkOpener;
+use pane_monitor::{run_bell_command, PaneMonitor};
 use std::env::temp_dir;
 use uuid::Uuid;
 use zellij_utils::data::{
@@ -20,17 +24,22 @@ use zellij_utils::{position::Position, serde};
 use crate::background_jobs::BackgroundJob;
 use crate::pty_writer::PtyWriteInstruction;
 use crate::screen::CopyOptions;
-use crate::ui::{loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams};
+use crate::ui::{
+    loading_indication::LoadingIndication, pane_boundaries_frame::FrameParams,
+    pane_contents_and_ui::pin_indication_is_visible,
+};
 use layout_applier::LayoutApplier;
 use swap_layouts::SwapLayouts;
 
 use self::clipboard::ClipboardProvider;
 use crate::{
-    os_input_output::ServerOsApi,
+    os_input_output::{ServerOsApi, TerminalProcesses},
     output::{CharacterChunk, Output, SixelImageChunk},
     panes::sixel::SixelImageStore,
     panes::{FloatingPanes, TiledPanes},
-    panes::{LinkHandler, PaneId, PluginPane, TerminalPane},
+    panes::{
+        LinkHandler, PaneId, PluginPane, TerminalPane, BRACKETED_PASTE_BEGIN, BRACKETED_PASTE_END,
+    },
     plugins::PluginInstruction,
     pty::{ClientOrTabIndex, PtyInstruction, VteBytes},
     thread_bus::ThreadSenders,
@@ -38,8 +47,9 @@ use crate::{
 };
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::{
     collections::{HashMap, HashSet},
     str,
@@ -55,6 +65,7 @@ use zellij_utils::{
         parse_keys,
     },
     pane_size::{Offset, PaneGeom, Size, SizeInPixels, Viewport},
+    session_serialization::{PaneLayoutManifest, TabLayoutManifest},
 };
 
 #[macro_export]
@@ -148,6 +159,9 @@ pub(crate) struct Tab {
     tiled_panes: TiledPanes,
     floating_panes: FloatingPanes,
     suppressed_panes: HashMap<PaneId, Box<dyn Pane>>,
+    in_place_pane_launchers: HashMap<PaneId, u32>, // in-place pane to the plugin that opened it
+    pane_monitor: PaneMonitor,
+    terminal_title: Option<String>, // the last one set for the terminals of the clients in this tab
     max_panes: Option<usize>,
     viewport: Rc<RefCell<Viewport>>, // includes all non-UI panes
     display_area: Rc<RefCell<Size>>, // includes all panes (including eg. the status bar and tab bar in the default layout)
@@ -172,6 +186,9 @@ pub(crate) struct Tab {
     focus_pane_id: Option<PaneId>,
     copy_on_select: bool,
     last_mouse_hold_position: Option<Position>,
+    resizing_pane_with_mouse: Option<(PaneId, Direction)>, // the pane and the side of it being dragged
+    moving_pane_with_mouse: Option<PaneId>, // a tiled pane being dragged by its title bar
+    dragging_in_plugin_pane: Option<PaneId>, // an unselectable plugin pane (eg. the tab-bar) receiving a mouse drag
     terminal_emulator_colors: Rc<RefCell<Palette>>,
     terminal_emulator_color_codes: Rc<RefCell<HashMap<usize, String>>>,
     pids_waiting_resize: HashSet<u32>, // u32 is the terminal_id
@@ -183,6 +200,7 @@ pub(crate) struct Tab {
     // pending and need to be re-applied
     swap_layouts: SwapLayouts,
     debug: bool,
+    pub has_unseen_output: bool, // a pane had output since this tab was last focused
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -238,7 +256,7 @@ pub trait Pane {
         cursor_color: PaletteColor,
         text_color: PaletteColor,
     ) -> Option<String>;
-    fn render_terminal_title(&mut self, _input_mode: InputMode) -> String;
+    fn render_terminal_title(&mut self, _input_mode: InputMode, _tab_name: &str) -> String;
     fn update_name(&mut self, name: &str);
     fn pid(&self) -> PaneId;
     fn reduce_height(&mut self, percent: f64);
@@ -253,6 +271,9 @@ pub trait Pane {
     fn dump_screen(&mut self, _client_id: ClientId, _full: bool) -> String {
         "".to_owned()
     }
+    fn dump_screen_with_ansi_styling(&mut self, _client_id: ClientId, _full: bool) -> String {
+        "".to_owned()
+    }
     fn scroll_up(&mut self, count: usize, client_id: ClientId);
     fn scroll_down(&mut self, count: usize, client_id: ClientId);
     fn clear_scroll(&mut self);
@@ -271,12 +292,26 @@ pub trait Pane {
         }
     }
     fn start_selection(&mut self, _start: &Position, _client_id: ClientId) {}
+    fn extend_selection(&mut self, _to: &Position, _client_id: ClientId) {}
+    fn start_block_selection(&mut self, _start: &Position, _client_id: ClientId) {}
     fn update_selection(&mut self, _position: &Position, _client_id: ClientId) {}
     fn end_selection(&mut self, _end: &Position, _client_id: ClientId) {}
     fn reset_selection(&mut self) {}
     fn get_selected_text(&self) -> Option<String> {
         None
     }
+    fn scroll_to_previous_prompt(&mut self) {}
+    fn scroll_to_next_prompt(&mut self) {}
+    fn last_command_output(&self) -> Option<String> {
+        None
+    }
+    fn enter_copy_mode(&mut self) {}
+    fn is_in_copy_mode(&self) -> bool {
+        false
+    }
+    fn handle_copy_mode_input(&mut self, _input_bytes: &[u8]) -> Option<String> {
+        None
+    }
 
     fn right_boundary_x_coords(&self) -> usize {
         self.x() + self.cols()
@@ -350,6 +385,18 @@ pub trait Pane {
     fn drain_clipboard_update(&mut self) -> Option<String> {
         None
     }
+    /// Whether the pane rang the bell since this was last called
+    fn drain_bell_notification(&mut self) -> bool {
+        false
+    }
+    /// Whether the application in the pane claimed the directional navigation keys
+    fn navigation_is_delegated(&self) -> bool {
+        false
+    }
+    /// A move the application in the pane handed back since this was last called
+    fn drain_navigation_bounce(&mut self) -> Option<Direction> {
+        None
+    }
     fn render_full_viewport(&mut self) {}
     fn relative_position(&self, position_on_screen: &Position) -> Position {
         position_on_screen.relative_to(self.get_content_y(), self.get_content_x())
@@ -381,15 +428,48 @@ pub trait Pane {
         }
         false
     }
+    /// The side of the pane whose frame (or boundary) is at this position, if any
+    fn frame_side_at(&self, position: &Position) -> Option<Direction> {
+        if !self.position_is_on_frame(position) {
+            None
+        } else if position.column() < self.get_content_x() {
+            Some(Direction::Left)
+        } else if position.column() >= self.get_content_x() + self.get_content_columns() {
+            Some(Direction::Right)
+        } else if position.line() < self.get_content_y() as isize {
+            Some(Direction::Up)
+        } else {
+            Some(Direction::Down)
+        }
+    }
     fn store_pane_name(&mut self);
     fn load_pane_name(&mut self);
     fn set_borderless(&mut self, borderless: bool);
     fn borderless(&self) -> bool;
     fn set_exclude_from_sync(&mut self, exclude_from_sync: bool);
     fn exclude_from_sync(&self) -> bool;
+    fn set_sync_group_member(&mut self, _is_sync_group_member: bool) {}
+    fn is_sync_group_member(&self) -> bool {
+        false
+    }
+    fn set_pane_group_member(&mut self, _is_pane_group_member: bool) {}
+    fn is_pane_group_member(&self) -> bool {
+        false
+    }
+    fn update_terminal_processes(&mut self, _terminal_processes: Option<TerminalProcesses>) {}
+    fn link_at(&self, _position: &Position) -> Option<String> {
+        None
+    }
+    fn set_hovered_link(&mut self, _position: Option<&Position>) {}
+    fn links_in_viewport(&self) -> Vec<(Position, String)> {
+        vec![]
+    }
 
     // TODO: this should probably be merged with the mouse_right_click
     fn handle_right_click(&mut self, _to: &Position, _client_id: ClientId) {}
+    fn handle_middle_click(&mut self, _to: &Position, _client_id: ClientId) {}
+    // position is None when the mouse is not over the pane (or is over its frame)
+    fn handle_mouse_hover(&mut self, _position: Option<&Position>, _client_id: ClientId) {}
     fn mouse_left_click(&self, _position: &Position, _is_held: bool) -> Option<String> {
         None
     }
@@ -441,6 +521,9 @@ pub trait Pane {
     fn toggle_search_wrap(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
+    fn toggle_search_regex(&mut self) {
+        // No-op by default (only terminal-panes currently have search capability)
+    }
     fn clear_search(&mut self) {
         // No-op by default (only terminal-panes currently have search capability)
     }
@@ -456,10 +539,14 @@ pub trait Pane {
     fn frame_color_override(&self) -> Option<PaletteColor>;
     fn invoked_with(&self) -> &Option<Run>;
     fn set_title(&mut self, title: String);
+    fn update_style(&mut self, style: Style);
     fn update_loading_indication(&mut self, _loading_indication: LoadingIndication) {} // only relevant for plugins
     fn start_loading_indication(&mut self, _loading_indication: LoadingIndication) {} // only relevant for plugins
     fn progress_animation_offset(&mut self) {} // only relevant for plugins
     fn current_title(&self) -> String;
+    fn custom_title(&self) -> Option<String> {
+        None
+    }
     fn is_held(&self) -> bool {
         false
     }
@@ -470,6 +557,10 @@ pub trait Pane {
         None
     }
     fn rename(&mut self, _buf: Vec<u8>) {}
+    fn respawn(&mut self) -> Option<RunCommand> {
+        // only held terminal panes can be respawned, returns the command to re-run
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -580,6 +671,9 @@ impl Tab {
             tiled_panes,
             floating_panes,
             suppressed_panes: HashMap::new(),
+            in_place_pane_launchers: HashMap::new(),
+            pane_monitor: PaneMonitor::default(),
+            terminal_title: None,
             name: name.clone(),
             prev_name: name,
             max_panes,
@@ -604,6 +698,9 @@ impl Tab {
             focus_pane_id: None,
             copy_on_select: copy_options.copy_on_select,
             last_mouse_hold_position: None,
+            resizing_pane_with_mouse: None,
+            moving_pane_with_mouse: None,
+            dragging_in_plugin_pane: None,
             terminal_emulator_colors,
             terminal_emulator_color_codes,
             pids_waiting_resize: HashSet::new(),
@@ -612,6 +709,7 @@ impl Tab {
             pending_instructions: vec![],
             swap_layouts,
             debug,
+            has_unseen_output: false,
         }
     }
 
@@ -836,6 +934,8 @@ impl Tab {
         Ok(())
     }
     pub fn add_client(&mut self, client_id: ClientId, mode_info: Option<ModeInfo>) -> Result<()> {
+        // the terminal of the client might show the title of another tab
+        self.terminal_title = None;
         let other_clients_exist_in_tab = { !self.connected_clients.borrow().is_empty() };
         if other_clients_exist_in_tab {
             if let Some(first_active_floating_pane_id) =
@@ -1075,16 +1175,25 @@ impl Tab {
         }
     }
     pub fn suppress_active_pane(&mut self, pid: PaneId, client_id: ClientId) -> Result<()> {
+        self.replace_active_pane(pid, Some("EDITING SCROLLBACK".to_owned()), None, client_id)
+    }
+    pub fn replace_active_pane(
+        &mut self,
+        pid: PaneId,
+        initial_pane_title: Option<String>,
+        run_plugin: Option<Run>, // only relevant if this is a plugin pane
+        client_id: ClientId,
+    ) -> Result<()> {
         // this method creates a new pane from pid and replaces it with the active pane
         // the active pane is then suppressed (hidden and not rendered) until the current
         // created pane is closed, in which case it will be replaced back by it
         let err_context = || format!("failed to suppress active pane for client {client_id}");
 
-        match pid {
-            PaneId::Terminal(pid) => {
+        let new_pane = match pid {
+            PaneId::Terminal(term_pid) => {
                 let next_terminal_position = self.get_next_terminal_position(); // TODO: this is not accurate in this case
                 let mut new_pane = TerminalPane::new(
-                    pid,
+                    term_pid,
                     PaneGeom::default(), // the initial size will be set later
                     self.style,
                     next_terminal_position,
@@ -1098,48 +1207,73 @@ impl Tab {
                     None,
                     self.debug,
                 );
-                new_pane.update_name("EDITING SCROLLBACK"); // we do this here and not in the
-                                                            // constructor so it won't be overrided
-                                                            // by the editor
-                let replaced_pane = if self.floating_panes.panes_are_visible() {
-                    self.floating_panes
-                        .replace_active_pane(Box::new(new_pane), client_id)
-                        .ok()
-                } else {
-                    self.tiled_panes
-                        .replace_active_pane(Box::new(new_pane), client_id)
-                };
-                match replaced_pane {
-                    Some(replaced_pane) => {
-                        self.suppressed_panes
-                            .insert(PaneId::Terminal(pid), replaced_pane);
-                        self.get_active_pane(client_id)
-                            .with_context(|| format!("no active pane found for client {client_id}"))
-                            .and_then(|current_active_pane| {
-                                resize_pty!(
-                                    current_active_pane,
-                                    self.os_api,
-                                    self.senders,
-                                    self.character_cell_size
-                                )
-                            })
-                            .with_context(err_context)?;
-                    },
-                    None => {
-                        Err::<(), _>(anyhow!(
-                            "Could not find editor pane to replace - is no pane focused?"
-                        ))
-                        .with_context(err_context)
-                        .non_fatal();
-                    },
+                if let Some(initial_pane_title) = initial_pane_title {
+                    // we do this here and not in the constructor so it won't be overrided by the
+                    // program running in the pane
+                    new_pane.update_name(&initial_pane_title);
                 }
+                Box::new(new_pane) as Box<dyn Pane>
             },
-            PaneId::Plugin(_pid) => {
-                // TBD, currently unsupported
+            PaneId::Plugin(plugin_pid) => Box::new(PluginPane::new(
+                plugin_pid,
+                PaneGeom::default(), // the initial size will be set later
+                self.senders
+                    .to_plugin
+                    .as_ref()
+                    .with_context(err_context)?
+                    .clone(),
+                initial_pane_title.unwrap_or("".to_owned()),
+                String::new(),
+                self.sixel_image_store.clone(),
+                self.terminal_emulator_colors.clone(),
+                self.terminal_emulator_color_codes.clone(),
+                self.link_handler.clone(),
+                self.character_cell_size.clone(),
+                self.connected_clients.borrow().iter().copied().collect(),
+                self.style,
+                run_plugin,
+                self.debug,
+            )) as Box<dyn Pane>,
+        };
+        let replaced_pane = if self.floating_panes.panes_are_visible() {
+            self.floating_panes
+                .replace_active_pane(new_pane, client_id)
+                .ok()
+        } else {
+            self.tiled_panes.replace_active_pane(new_pane, client_id)
+        };
+        match replaced_pane {
+            Some(replaced_pane) => {
+                self.suppressed_panes.insert(pid, replaced_pane);
+                self.get_active_pane(client_id)
+                    .with_context(|| format!("no active pane found for client {client_id}"))
+                    .and_then(|current_active_pane| {
+                        resize_pty!(
+                            current_active_pane,
+                            self.os_api,
+                            self.senders,
+                            self.character_cell_size
+                        )
+                    })
+                    .with_context(err_context)?;
+            },
+            None => {
+                Err::<(), _>(anyhow!(
+                    "Could not find editor pane to replace - is no pane focused?"
+                ))
+                .with_context(err_context)
+                .non_fatal();
             },
         }
         Ok(())
     }
+    /// Tell the plugin with `launcher_plugin_id` once the in-place pane `pid` (which must already
+    /// have replaced the active pane) is closed
+    pub fn add_in_place_pane_launcher(&mut self, pid: PaneId, launcher_plugin_id: u32) {
+        if self.suppressed_panes.contains_key(&pid) {
+            self.in_place_pane_launchers.insert(pid, launcher_plugin_id);
+        }
+    }
     pub fn horizontal_split(
         &mut self,
         pid: PaneId,
@@ -1330,6 +1464,9 @@ impl Tab {
             return Ok(());
         }
         let err_context = || format!("failed to handle pty bytes from fd {pid}");
+        // panes hidden behind a fullscreen pane keep handling their output, the fullscreen pane
+        // only indicates that they had some
+        self.tiled_panes.mark_pane_activity(PaneId::Terminal(pid));
         if let Some(terminal_output) = self
             .tiled_panes
             .get_pane_mut(PaneId::Terminal(pid))
@@ -1411,6 +1548,8 @@ impl Tab {
             terminal_output.handle_pty_bytes(bytes);
             let messages_to_pty = terminal_output.drain_messages_to_pty();
             let clipboard_update = terminal_output.drain_clipboard_update();
+            let rang_bell = terminal_output.drain_bell_notification();
+            let navigation_bounce = terminal_output.drain_navigation_bounce();
             for message in messages_to_pty {
                 self.write_to_pane_id(message, PaneId::Terminal(pid), None)
                     .with_context(err_context)?;
@@ -1419,9 +1558,83 @@ impl Tab {
                 self.write_selection_to_clipboard(&string)
                     .with_context(err_context)?;
             }
+            self.monitor_pane_output(PaneId::Terminal(pid), rang_bell);
+            if let Some(direction) = navigation_bounce {
+                self.bounce_navigation(PaneId::Terminal(pid), direction)
+                    .with_context(err_context)?;
+            }
+        }
+        Ok(())
+    }
+    /// Moves the focus of the clients focused on a pane whose application handed a move back to us
+    fn bounce_navigation(&mut self, pane_id: PaneId, direction: Direction) -> Result<()> {
+        let clients_focused_on_pane: Vec<ClientId> = self
+            .connected_clients
+            .borrow()
+            .iter()
+            .copied()
+            .filter(|client_id| self.get_active_pane_id(*client_id) == Some(pane_id))
+            .collect();
+        for client_id in clients_focused_on_pane {
+            self.move_focus(direction, client_id)?;
         }
         Ok(())
     }
+    fn pane_is_focused(&self, pane_id: PaneId) -> bool {
+        self.connected_clients
+            .borrow()
+            .iter()
+            .any(|client_id| self.get_active_pane_id(*client_id) == Some(pane_id))
+    }
+    fn monitor_pane_output(&mut self, pane_id: PaneId, rang_bell: bool) {
+        let pane_is_focused = self.pane_is_focused(pane_id);
+        let rang_bell_while_unfocused =
+            self.pane_monitor
+                .pane_had_output(pane_id, rang_bell, pane_is_focused);
+        if rang_bell_while_unfocused {
+            let pane_title = self
+                .tiled_panes
+                .get_pane(pane_id)
+                .or_else(|| self.floating_panes.get_pane(pane_id))
+                .or_else(|| {
+                    self.suppressed_panes
+                        .values()
+                        .find(|s_p| s_p.pid() == pane_id)
+                })
+                .map(|pane| pane.current_title())
+                .unwrap_or_default();
+            if let Err(e) = run_bell_command(pane_id, &pane_title, &self.name) {
+                Err::<(), _>(e).non_fatal();
+            }
+        }
+    }
+    /// Marks the panes that are not focused and had no output for `silence` as silent
+    pub fn mark_silent_panes(&mut self, silence: Duration) {
+        let focused_panes: HashSet<PaneId> = self
+            .connected_clients
+            .borrow()
+            .iter()
+            .filter_map(|client_id| self.get_active_pane_id(*client_id))
+            .collect();
+        self.pane_monitor
+            .mark_silent_panes(silence, |pane_id| focused_panes.contains(&pane_id));
+    }
+    /// Clears the bell, activity and silence state of the panes that are now focused
+    pub fn clear_monitored_state_of_focused_panes(&mut self) {
+        let focused_panes: Vec<PaneId> = self
+            .connected_clients
+            .borrow()
+            .iter()
+            .filter_map(|client_id| self.get_active_pane_id(*client_id))
+            .collect();
+        for pane_id in focused_panes {
+            self.pane_monitor.clear_pane(pane_id);
+        }
+    }
+    /// Whether the bell, activity or silence state of any pane changed since this was last called
+    pub fn take_pane_monitor_changes(&mut self) -> bool {
+        self.pane_monitor.take_changes()
+    }
 
     pub fn write_to_terminals_on_current_tab(
         &mut self,
@@ -1472,11 +1685,57 @@ impl Tab {
                 .get_active_pane_id(client_id)
                 .with_context(err_context)?
         };
+        if let Some(pane) = self
+            .floating_panes
+            .get_mut(&pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .filter(|pane| pane.is_in_copy_mode())
+        {
+            // keys move the copy mode cursor rather than reaching the terminal
+            if let Some(selected_text) = pane.handle_copy_mode_input(&input_bytes) {
+                self.write_selection_to_clipboard(&selected_text)
+                    .with_context(|| format!("failed to copy selection for client {client_id}"))?;
+            }
+            return Ok(true);
+        }
+        let sync_group_pane_ids = self.sync_group_pane_ids();
+        if sync_group_pane_ids.contains(&pane_id) {
+            // input typed into a pane in the sync group is sent to all of its panes
+            let mut should_trigger_ui_change = false;
+            for pane_id in sync_group_pane_ids {
+                let ui_change_triggered = self
+                    .write_to_pane_id(input_bytes.clone(), pane_id, Some(client_id))
+                    .with_context(|| {
+                        format!("failed to write to sync group for client {client_id}")
+                    })?;
+                if ui_change_triggered {
+                    should_trigger_ui_change = true;
+                }
+            }
+            return Ok(should_trigger_ui_change);
+        }
         // Can't use 'err_context' here since it borrows 'input_bytes'
         self.write_to_pane_id(input_bytes, pane_id, Some(client_id))
             .with_context(|| format!("failed to write to active terminal for client {client_id}"))
     }
 
+    pub fn paste_to_pane(
+        &mut self,
+        text: &str,
+        pane_id: PaneId,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to paste to pane with id {pane_id:?}");
+
+        // like the client does when the user pastes, the pane drops the bracketed paste
+        // sequences if the application running inside it did not ask for them
+        for input_bytes in [BRACKETED_PASTE_BEGIN, text.as_bytes(), BRACKETED_PASTE_END] {
+            self.write_to_pane_id(input_bytes.to_vec(), pane_id, Some(client_id))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
+
     pub fn write_to_terminal_at(
         &mut self,
         input_bytes: Vec<u8>,
@@ -1627,6 +1886,24 @@ impl Tab {
         }
         self.tiled_panes.toggle_active_pane_fullscreen(client_id);
     }
+    pub fn toggle_active_pane_stacked(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        self.tiled_panes.toggle_active_pane_stacked(client_id);
+    }
+    pub fn focus_next_pane_in_stack(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        self.tiled_panes.focus_next_pane_in_stack(client_id);
+    }
+    pub fn focus_previous_pane_in_stack(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            return;
+        }
+        self.tiled_panes.focus_previous_pane_in_stack(client_id);
+    }
     pub fn is_fullscreen_active(&self) -> bool {
         self.tiled_panes.fullscreen_is_active()
     }
@@ -1683,6 +1960,129 @@ impl Tab {
     pub fn toggle_sync_panes_is_active(&mut self) {
         self.synchronize_is_active = !self.synchronize_is_active;
     }
+    pub fn toggle_active_pane_sync(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let is_sync_group_member = active_pane.is_sync_group_member();
+            active_pane.set_sync_group_member(!is_sync_group_member);
+            active_pane.set_should_render(true);
+        }
+    }
+    fn sync_group_pane_ids(&self) -> Vec<PaneId> {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_pane_id, pane)| pane.is_sync_group_member())
+            .map(|(pane_id, _pane)| *pane_id)
+            .collect()
+    }
+    pub fn toggle_active_pane_in_group(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            let is_pane_group_member = active_pane.is_pane_group_member();
+            active_pane.set_pane_group_member(!is_pane_group_member);
+            active_pane.set_should_render(true);
+        }
+    }
+    pub fn toggle_pane_in_group(&mut self, pane_id: PaneId) {
+        if let Some(pane) = self
+            .tiled_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+        {
+            let is_pane_group_member = pane.is_pane_group_member();
+            pane.set_pane_group_member(!is_pane_group_member);
+            pane.set_should_render(true);
+        }
+    }
+    /// Tiled panes come first
+    pub fn grouped_pane_ids(&self) -> Vec<PaneId> {
+        self.tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(_pane_id, pane)| pane.is_pane_group_member())
+            .map(|(pane_id, _pane)| *pane_id)
+            .collect()
+    }
+    pub fn clear_pane_group(&mut self) {
+        for pane_id in self.grouped_pane_ids() {
+            self.toggle_pane_in_group(pane_id);
+        }
+    }
+    /// Whether removing all grouped panes would leave this tab without any tiled panes
+    pub fn all_tiled_panes_are_grouped(&self) -> bool {
+        self.get_selectable_tiled_panes()
+            .all(|(_pane_id, pane)| pane.is_pane_group_member())
+    }
+    pub fn close_grouped_panes(&mut self, client_id: ClientId) -> Result<()> {
+        for pane_id in self.grouped_pane_ids() {
+            self.close_pane(pane_id, false, Some(client_id));
+            self.senders
+                .send_to_pty(PtyInstruction::ClosePane(pane_id))
+                .with_context(|| {
+                    format!("failed to close grouped pane {pane_id:?} for client {client_id}")
+                })?;
+        }
+        Ok(())
+    }
+    pub fn float_grouped_panes(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to float grouped panes for client {client_id}");
+        if self.tiled_panes.fullscreen_is_active() {
+            self.tiled_panes.unset_fullscreen();
+        }
+        let grouped_tiled_pane_ids: Vec<PaneId> = self
+            .grouped_pane_ids()
+            .into_iter()
+            .filter(|pane_id| self.tiled_panes.panes_contain(pane_id))
+            .collect();
+        for pane_id in grouped_tiled_pane_ids {
+            if self.get_selectable_tiled_panes().count() <= 1 {
+                // don't close the only pane on screen...
+                break;
+            }
+            if let Some(pane_to_float) = self.close_pane(pane_id, true, Some(client_id)) {
+                self.show_floating_panes();
+                self.add_floating_pane(pane_to_float, pane_id, Some(client_id))
+                    .with_context(err_context)?;
+            }
+        }
+        self.clear_pane_group();
+        Ok(())
+    }
+    /// Removes the grouped panes from this tab, returning them with whether each was floating
+    pub fn take_grouped_panes(
+        &mut self,
+        client_id: ClientId,
+    ) -> Vec<(PaneId, Box<dyn Pane>, bool)> {
+        let mut grouped_panes = vec![];
+        for pane_id in self.grouped_pane_ids() {
+            let is_floating = self.floating_panes.panes_contain(&pane_id);
+            if let Some(mut pane) = self.close_pane(pane_id, false, Some(client_id)) {
+                pane.set_pane_group_member(false);
+                grouped_panes.push((pane_id, pane, is_floating));
+            }
+        }
+        grouped_panes
+    }
+    /// Adds all grouped panes to the sync group, or removes them from it if they are all already
+    /// in it
+    pub fn sync_grouped_panes(&mut self) {
+        let grouped_pane_ids = self.grouped_pane_ids();
+        let all_are_synced = self
+            .tiled_panes
+            .get_panes()
+            .chain(self.floating_panes.get_panes())
+            .filter(|(pane_id, _pane)| grouped_pane_ids.contains(pane_id))
+            .all(|(_pane_id, pane)| pane.is_sync_group_member());
+        for pane_id in grouped_pane_ids {
+            if let Some(pane) = self
+                .tiled_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.floating_panes.get_pane_mut(pane_id))
+            {
+                pane.set_sync_group_member(!all_are_synced);
+                pane.set_should_render(true);
+            }
+        }
+    }
     pub fn mark_active_pane_for_rerender(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_mut(client_id) {
             active_pane.set_should_render(true);
@@ -1723,11 +2123,18 @@ impl Tab {
         );
 
         self.tiled_panes
-            .render(output, self.floating_panes.panes_are_visible())
+            .render(
+                output,
+                self.floating_panes.panes_are_visible(),
+                &self.name,
+                &mut self.terminal_title,
+            )
             .with_context(err_context)?;
-        if self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes() {
+        if (self.floating_panes.panes_are_visible() && self.floating_panes.has_active_panes())
+            || self.floating_panes.has_pinned_panes()
+        {
             self.floating_panes
-                .render(output)
+                .render(output, &self.name, &mut self.terminal_title)
                 .with_context(err_context)?;
         }
 
@@ -1950,6 +2357,39 @@ impl Tab {
         }
     }
     // returns a boolean that indicates whether the focus moved
+    fn move_focus(&mut self, direction: Direction, client_id: ClientId) -> Result<bool> {
+        match direction {
+            Direction::Left => self.move_focus_left(client_id),
+            Direction::Right => self.move_focus_right(client_id),
+            Direction::Up => self.move_focus_up(client_id),
+            Direction::Down => self.move_focus_down(client_id),
+        }
+    }
+    /// Moves the focus, unless the focused pane claimed the directional navigation keys (eg. vim
+    /// with splits of its own), in which case it gets the key that was pressed instead
+    pub fn move_focus_or_delegate(
+        &mut self,
+        direction: Direction,
+        key_bytes: Vec<u8>,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || format!("failed to move focus or delegate for client {}", client_id);
+        let delegate_to = self
+            .get_active_pane(client_id)
+            .filter(|pane| pane.navigation_is_delegated())
+            .map(|pane| pane.pid());
+        match delegate_to {
+            Some(pane_id) => {
+                self.write_to_pane_id(key_bytes, pane_id, Some(client_id))
+                    .with_context(err_context)?;
+            },
+            None => {
+                self.move_focus(direction, client_id)
+                    .with_context(err_context)?;
+            },
+        }
+        Ok(())
+    }
     pub fn move_focus_left(&mut self, client_id: ClientId) -> Result<bool> {
         let err_context = || format!("failed to move focus left for client {}", client_id);
 
@@ -2200,9 +2640,17 @@ impl Tab {
         //
         // TODO: separate the "close_pane" logic and the "move_pane_somewhere_else" logic, they're
         // overloaded here and that's not great
+        if !ignore_suppressed_panes {
+            self.pane_monitor.remove_pane(id);
+        }
         if !ignore_suppressed_panes && self.suppressed_panes.contains_key(&id) {
             return match self.replace_pane_with_suppressed_pane(id) {
-                Ok(pane) => pane,
+                Ok(mut pane) => {
+                    if let Some(launcher_plugin_id) = self.in_place_pane_launchers.remove(&id) {
+                        self.report_in_place_pane_closed(id, pane.as_mut(), launcher_plugin_id);
+                    }
+                    pane
+                },
                 Err(e) => {
                     Err::<(), _>(e)
                         .with_context(|| format!("failed to close pane {:?}", id))
@@ -2361,6 +2809,33 @@ impl Tab {
                 )
             })
     }
+    fn report_in_place_pane_closed(
+        &self,
+        id: PaneId,
+        pane: Option<&mut Box<dyn Pane>>,
+        launcher_plugin_id: u32,
+    ) {
+        if let (PaneId::Terminal(terminal_id), Some(pane)) = (id, pane) {
+            // the "return value" of a pane is the last line it printed, eg. the selection of a
+            // picker such as fzf
+            let client_id = self.connected_clients.borrow().iter().next().copied();
+            let return_value = client_id
+                .map(|client_id| pane.dump_screen(client_id, false))
+                .and_then(|contents| {
+                    contents
+                        .lines()
+                        .rev()
+                        .find(|line| !line.trim().is_empty())
+                        .map(|line| line.trim_end().to_owned())
+                })
+                .unwrap_or_default();
+            let _ = self.senders.send_to_plugin(PluginInstruction::Update(vec![(
+                Some(launcher_plugin_id),
+                None,
+                Event::InPlacePaneClosed(terminal_id, return_value),
+            )]));
+        }
+    }
     pub fn close_focused_pane(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = |pane_id| {
             format!("failed to close focused pane (ID {pane_id:?}) for client {client_id}")
@@ -2389,23 +2864,68 @@ impl Tab {
         }
         Ok(())
     }
+    pub fn respawn_active_pane(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to respawn active pane for client {client_id}");
+
+        let respawned = self
+            .get_active_pane_or_floating_pane_mut(client_id)
+            .and_then(|active_pane| match active_pane.pid() {
+                PaneId::Terminal(terminal_id) => active_pane
+                    .respawn()
+                    .map(|run_command| (terminal_id, run_command)),
+                PaneId::Plugin(_) => None,
+            });
+        if let Some((terminal_id, run_command)) = respawned {
+            self.pids_waiting_resize.insert(terminal_id);
+            self.senders
+                .send_to_pty(PtyInstruction::ReRunCommandInPane(
+                    PaneId::Terminal(terminal_id),
+                    run_command,
+                ))
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
     pub fn dump_active_terminal_screen(
         &mut self,
         file: Option<String>,
         client_id: ClientId,
         full: bool,
+        ansi: bool,
     ) -> Result<()> {
         let err_context =
             || format!("failed to dump active terminal screen for client {client_id}");
 
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
-            let dump = active_pane.dump_screen(client_id, full);
+            let dump = if ansi {
+                active_pane.dump_screen_with_ansi_styling(client_id, full)
+            } else {
+                active_pane.dump_screen(client_id, full)
+            };
             self.os_api
                 .write_to_file(dump, file)
                 .with_context(err_context)?;
         }
         Ok(())
     }
+    pub fn dump_terminal_pane_screen(
+        &mut self,
+        terminal_pane_id: u32,
+        client_id: ClientId,
+        full: bool,
+    ) -> Option<String> {
+        // returns None if the pane is not in this tab
+        let pane_id = PaneId::Terminal(terminal_pane_id);
+        self.floating_panes
+            .get_pane_mut(pane_id)
+            .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            .or_else(|| {
+                self.suppressed_panes
+                    .values_mut()
+                    .find(|pane| pane.pid() == pane_id)
+            })
+            .map(|pane| pane.dump_screen_with_ansi_styling(client_id, full))
+    }
     pub fn edit_scrollback(&mut self, client_id: ClientId) -> Result<()> {
         let err_context = || format!("failed to edit scrollback for client {client_id}");
 
@@ -2415,6 +2935,7 @@ impl Tab {
             Some(String::from(file.to_string_lossy())),
             client_id,
             true,
+            false,
         )
         .with_context(err_context)?;
         let line_number = self
@@ -2428,6 +2949,11 @@ impl Tab {
             ))
             .with_context(err_context)
     }
+    pub fn enter_copy_mode(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.enter_copy_mode();
+        }
+    }
     pub fn scroll_active_terminal_up(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.scroll_up(1, client_id);
@@ -2525,6 +3051,28 @@ impl Tab {
         Ok(())
     }
 
+    pub fn scroll_active_terminal_to_previous_prompt(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_previous_prompt();
+        }
+    }
+
+    pub fn scroll_active_terminal_to_next_prompt(&mut self, client_id: ClientId) -> Result<()> {
+        let err_context =
+            || format!("failed to scroll to the next prompt in active pane for client {client_id}");
+
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.scroll_to_next_prompt();
+            if !active_pane.is_scrolled() {
+                if let PaneId::Terminal(raw_fd) = active_pane.pid() {
+                    self.process_pending_vte_events(raw_fd)
+                        .with_context(err_context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     pub fn clear_active_terminal_scroll(&mut self, client_id: ClientId) -> Result<()> {
         // TODO: is this a thing?
         let err_context =
@@ -2608,6 +3156,52 @@ impl Tab {
         Ok(())
     }
 
+    /// Scrolls zellij's scrollback of the pane at `point`, bypassing the application running in
+    /// it, even if it asked for mouse events
+    pub fn handle_forced_scrollwheel_up(
+        &mut self,
+        point: &Position,
+        lines: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle forced scrollwheel up at position {point:?} for client {client_id}"
+            )
+        };
+
+        if let Some(pane) = self.get_pane_at(point, false).with_context(err_context)? {
+            pane.scroll_up(lines, client_id);
+        }
+        Ok(())
+    }
+
+    /// Scrolls zellij's scrollback of the pane at `point`, bypassing the application running in
+    /// it, even if it asked for mouse events
+    pub fn handle_forced_scrollwheel_down(
+        &mut self,
+        point: &Position,
+        lines: usize,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle forced scrollwheel down at position {point:?} for client {client_id}"
+            )
+        };
+
+        if let Some(pane) = self.get_pane_at(point, false).with_context(err_context)? {
+            pane.scroll_down(lines, client_id);
+            if !pane.is_scrolled() {
+                if let PaneId::Terminal(pid) = pane.pid() {
+                    self.process_pending_vte_events(pid)
+                        .with_context(err_context)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn get_pane_at(
         &mut self,
         point: &Position,
@@ -2623,6 +3217,8 @@ impl Tab {
             {
                 return Ok(self.floating_panes.get_pane_mut(pane_id));
             }
+        } else if let Some(pane_id) = self.floating_panes.get_pinned_pane_id_at(point) {
+            return Ok(self.floating_panes.get_pane_mut(pane_id));
         }
         if let Some(pane_id) = self
             .get_pane_id_at(point, search_selectable)
@@ -2672,6 +3268,10 @@ impl Tab {
             )
         };
 
+        if self.toggle_pane_pinned_at(position, client_id) {
+            return Ok(());
+        }
+
         self.focus_pane_at(position, client_id)
             .with_context(err_context)?;
 
@@ -2686,6 +3286,13 @@ impl Tab {
             return Ok(());
         }
 
+        if !self.floating_panes.panes_are_visible()
+            && (self.start_resizing_pane_with_mouse(position)
+                || self.start_moving_pane_with_mouse(position))
+        {
+            return Ok(());
+        }
+
         if let Some(pane) = self
             .get_pane_at(position, false)
             .with_context(err_context)?
@@ -2698,14 +3305,157 @@ impl Tab {
                 }
             } else {
                 pane.start_selection(&relative_position, client_id);
-                if let PaneId::Terminal(_) = pane.pid() {
-                    self.selecting_with_mouse = true;
+                match pane.pid() {
+                    PaneId::Terminal(_) => self.selecting_with_mouse = true,
+                    // unselectable plugin panes never become active, so the rest of the drag
+                    // has to be routed to them explicitly
+                    PaneId::Plugin(_) if !pane.selectable() => {
+                        self.dragging_in_plugin_pane = Some(pane.pid())
+                    },
+                    PaneId::Plugin(_) => {},
                 }
             }
         };
         Ok(())
     }
 
+    pub fn handle_ctrl_left_click(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle mouse ctrl-left click at position {position:?} for client {client_id}"
+            )
+        };
+
+        let link = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+            .filter(|pane| !pane.position_is_on_frame(position))
+            .and_then(|pane| pane.link_at(&pane.relative_position(position)));
+        match (link, LinkOpener::from_config()) {
+            (Some(link), Some(link_opener)) => link_opener.open(&link).with_context(err_context),
+            _ => self
+                .handle_left_click(position, client_id)
+                .with_context(err_context),
+        }
+    }
+
+    /// Extends the selection in the focused pane up to the clicked position, anywhere else this
+    /// is a normal click
+    pub fn handle_shift_left_click(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle mouse shift-left click at position {position:?} for client {client_id}"
+            )
+        };
+
+        let active_pane_id = self.get_active_pane_id(client_id);
+        if let Some(pane) = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+            .filter(|pane| Some(pane.pid()) == active_pane_id)
+            .filter(|pane| matches!(pane.pid(), PaneId::Terminal(_)))
+            .filter(|pane| !pane.position_is_on_frame(position))
+        {
+            let relative_position = pane.relative_position(position);
+            if pane.mouse_left_click(&relative_position, false).is_none() {
+                pane.extend_selection(&relative_position, client_id);
+                self.selecting_with_mouse = true;
+                return Ok(());
+            }
+        }
+        self.handle_left_click(position, client_id)
+            .with_context(err_context)
+    }
+
+    /// Starts selecting a rectangle of columns in the clicked pane, if the application in it
+    /// asked for mouse events this is a normal click
+    pub fn handle_alt_left_click(
+        &mut self,
+        position: &Position,
+        client_id: ClientId,
+    ) -> Result<()> {
+        let err_context = || {
+            format!(
+                "failed to handle mouse alt-left click at position {position:?} for client {client_id}"
+            )
+        };
+
+        if let Some(pane_id) = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+            .filter(|pane| pane.position_is_on_frame(position))
+            .map(|pane| pane.pid())
+        {
+            // alt-clicking a frame adds its pane to the pane group (or removes it)
+            self.toggle_pane_in_group(pane_id);
+            return Ok(());
+        }
+        let selects_block = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+            .filter(|pane| matches!(pane.pid(), PaneId::Terminal(_)))
+            .filter(|pane| !pane.position_is_on_frame(position))
+            .map(|pane| {
+                pane.mouse_left_click(&pane.relative_position(position), false)
+                    .is_none()
+            })
+            .unwrap_or(false);
+        if !selects_block {
+            return self
+                .handle_left_click(position, client_id)
+                .with_context(err_context);
+        }
+        self.focus_pane_at(position, client_id)
+            .with_context(err_context)?;
+        if let Some(pane) = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+        {
+            let relative_position = pane.relative_position(position);
+            pane.start_block_selection(&relative_position, client_id);
+            self.selecting_with_mouse = true;
+        }
+        Ok(())
+    }
+
+    pub fn links_in_active_pane(&mut self, client_id: ClientId) -> Vec<(Position, String)> {
+        self.get_active_pane_or_floating_pane_mut(client_id)
+            .map(|active_pane| active_pane.links_in_viewport())
+            .unwrap_or_default()
+    }
+
+    pub fn handle_mouse_hover(&mut self, position: &Position, client_id: ClientId) -> Result<()> {
+        let err_context = || format!("failed to handle mouse hover at position {position:?}");
+
+        let hovered_pane_id = self
+            .get_pane_at(position, false)
+            .with_context(err_context)?
+            .filter(|pane| !pane.position_is_on_frame(position))
+            .map(|pane| pane.pid());
+        for pane_id in self.get_all_pane_ids() {
+            if let Some(pane) = self
+                .floating_panes
+                .get_pane_mut(pane_id)
+                .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            {
+                let relative_position = pane.relative_position(position);
+                let hovered_position =
+                    (hovered_pane_id == Some(pane_id)).then_some(&relative_position);
+                pane.set_hovered_link(hovered_position);
+                pane.handle_mouse_hover(hovered_position, client_id);
+            }
+        }
+        Ok(())
+    }
+
     pub fn handle_right_click(&mut self, position: &Position, client_id: ClientId) -> Result<()> {
         let err_context = || {
             format!(
@@ -2753,11 +3503,46 @@ impl Tab {
                     self.write_to_active_terminal(mouse_event.into_bytes(), client_id)
                         .with_context(err_context)?;
                 }
+            } else {
+                pane.handle_middle_click(&relative_position, client_id);
             }
         };
         Ok(())
     }
 
+    fn toggle_pane_pinned_at(&mut self, point: &Position, client_id: ClientId) -> bool {
+        let client_mode = self
+            .mode_info
+            .borrow()
+            .get(&client_id)
+            .map(|mode_info| mode_info.mode)
+            .unwrap_or(self.default_mode_info.mode);
+        match self
+            .floating_panes
+            .get_pane_id_with_pin_indication_at(point)
+        {
+            Some(pane_id)
+                if pin_indication_is_visible(
+                    true,
+                    self.floating_panes.pane_is_pinned(&pane_id),
+                    client_mode,
+                ) =>
+            {
+                self.floating_panes.toggle_pane_pinned(pane_id);
+                self.set_force_render();
+                true
+            },
+            _ => false,
+        }
+    }
+
+    pub fn toggle_active_pane_pinned(&mut self, client_id: ClientId) {
+        if self.floating_panes.panes_are_visible() {
+            self.floating_panes.toggle_active_pane_pinned(client_id);
+            self.set_force_render();
+        }
+    }
+
     fn focus_pane_at(&mut self, point: &Position, client_id: ClientId) -> Result<()> {
         let err_context =
             || format!("failed to focus pane at position {point:?} for client {client_id}");
@@ -2772,6 +3557,13 @@ impl Tab {
                 self.set_pane_active_at(clicked_pane);
                 return Ok(());
             }
+        } else if let Some(clicked_pane) = self.floating_panes.get_pinned_pane_id_at(point) {
+            // clicking a pinned pane brings back the rest of the floating panes along with it
+            self.show_floating_panes();
+            self.floating_panes.focus_pane(clicked_pane, client_id);
+            self.set_pane_active_at(clicked_pane);
+            self.set_force_render();
+            return Ok(());
         }
         if let Some(clicked_pane) = self.get_pane_id_at(point, true).with_context(err_context)? {
             self.tiled_panes.focus_pane(clicked_pane, client_id);
@@ -2868,6 +3660,28 @@ impl Tab {
             return Ok(());
         }
 
+        if self.resizing_pane_with_mouse.take().is_some() {
+            return Ok(());
+        }
+
+        if let Some(pane_id) = self.moving_pane_with_mouse.take() {
+            return self
+                .stop_moving_pane_with_mouse(pane_id, position)
+                .with_context(err_context);
+        }
+
+        if let Some(pane_id) = self.dragging_in_plugin_pane.take() {
+            if let Some(pane) = self
+                .floating_panes
+                .get_mut(&pane_id)
+                .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+            {
+                let relative_position = pane.relative_position(position);
+                pane.end_selection(&relative_position, client_id);
+            }
+            return Ok(());
+        }
+
         // read these here to avoid use of borrowed `*self`, since we are holding active_pane
         let selecting = self.selecting_with_mouse;
         let copy_on_release = self.copy_on_select;
@@ -2948,6 +3762,34 @@ impl Tab {
                                      // return;
         }
 
+        if self.resizing_pane_with_mouse.is_some() {
+            if is_repeated {
+                return Ok(false);
+            }
+            return self
+                .resize_pane_with_mouse(position_on_screen)
+                .with_context(err_context);
+        }
+
+        if self.moving_pane_with_mouse.is_some() {
+            // the pane is only moved once the mouse is released over its destination
+            return Ok(false);
+        }
+
+        if let Some(pane_id) = self.dragging_in_plugin_pane {
+            if !is_repeated {
+                if let Some(pane) = self
+                    .floating_panes
+                    .get_mut(&pane_id)
+                    .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+                {
+                    let relative_position = pane.relative_position(position_on_screen);
+                    pane.update_selection(&relative_position, client_id);
+                }
+            }
+            return Ok(false); // the plugin renders itself if it needs to
+        }
+
         let selecting = self.selecting_with_mouse;
         let active_pane = self.get_active_pane_or_floating_pane_mut(client_id);
 
@@ -2979,6 +3821,100 @@ impl Tab {
         Ok(false) // we shouldn't even get here, but might as well not needlessly render if we do
     }
 
+    fn start_resizing_pane_with_mouse(&mut self, position: &Position) -> bool {
+        // returns true if the position is on a pane border that can be dragged
+        if self.tiled_panes.fullscreen_is_active() {
+            return false;
+        }
+        let pane_and_side = self
+            .get_tiled_panes()
+            .find(|(_, pane)| pane.contains(position))
+            .and_then(|(pane_id, pane)| pane.frame_side_at(position).map(|side| (*pane_id, side)));
+        match pane_and_side {
+            // the top frame is the title bar, which is used to move the pane instead - the
+            // boundary above it can still be dragged through the bottom frame of the pane above
+            Some((pane_id, side))
+                if side != Direction::Up
+                    && self
+                        .tiled_panes
+                        .pane_has_neighbors_in_direction(pane_id, side) =>
+            {
+                self.resizing_pane_with_mouse = Some((pane_id, side));
+                true
+            },
+            _ => false,
+        }
+    }
+
+    fn start_moving_pane_with_mouse(&mut self, position: &Position) -> bool {
+        // returns true if the position is on the title bar of a tiled pane that can be moved
+        if self.tiled_panes.fullscreen_is_active() || self.tiled_panes.visible_panes_count() < 2 {
+            return false;
+        }
+        let pane_id = self
+            .get_tiled_panes()
+            .find(|(_, pane)| pane.contains(position))
+            .and_then(|(pane_id, pane)| match pane.frame_side_at(position) {
+                Some(Direction::Up) => Some(*pane_id),
+                _ => None,
+            });
+        self.moving_pane_with_mouse = pane_id;
+        pane_id.is_some()
+    }
+
+    fn stop_moving_pane_with_mouse(&mut self, pane_id: PaneId, position: &Position) -> Result<()> {
+        let destination_pane_id = self
+            .get_tiled_panes()
+            .find(|(_, pane)| pane.contains(position))
+            .map(|(pane_id, _)| *pane_id);
+        match destination_pane_id {
+            Some(destination_pane_id) if destination_pane_id != pane_id => {
+                self.tiled_panes
+                    .swap_pane_positions(pane_id, destination_pane_id)
+                    .with_context(|| format!("failed to move pane {pane_id:?} with the mouse"))?;
+                self.swap_layouts.set_is_tiled_damaged();
+                self.set_force_render();
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    fn resize_pane_with_mouse(&mut self, position_on_screen: &Position) -> Result<bool> {
+        // returns true if the pane was resized and we should render
+        let (pane_id, side) = match self.resizing_pane_with_mouse {
+            Some(pane_id_and_side) => pane_id_and_side,
+            None => return Ok(false),
+        };
+        let geom = match self.tiled_panes.get_pane(pane_id) {
+            Some(pane) => pane.position_and_size(),
+            None => {
+                self.resizing_pane_with_mouse = None;
+                return Ok(false);
+            },
+        };
+        // how far the dragged border should move outwards (negative values are inwards), we
+        // measure this from the border itself rather than from the previous mouse position so
+        // that rounding in the pane sizes does not accumulate
+        let column = position_on_screen.column() as isize;
+        let line = position_on_screen.line();
+        let change_by = match side {
+            Direction::Left => geom.x as isize - column,
+            Direction::Right => column - (geom.x + geom.cols.as_usize()) as isize + 1,
+            Direction::Up => geom.y as isize - line,
+            Direction::Down => line - (geom.y + geom.rows.as_usize()) as isize + 1,
+        };
+        let resized = self
+            .tiled_panes
+            .resize_pane_with_mouse(pane_id, side, change_by)
+            .with_context(|| format!("failed to resize pane {pane_id:?} with the mouse"))?;
+        if resized {
+            self.swap_layouts.set_is_tiled_damaged();
+            self.set_force_render();
+        }
+        Ok(resized)
+    }
+
     pub fn handle_mouse_hold_right(
         &mut self,
         position_on_screen: &Position,
@@ -3090,6 +4026,19 @@ impl Tab {
         Ok(())
     }
 
+    pub fn copy_last_command_output(&self, client_id: ClientId) -> Result<()> {
+        let last_command_output = self
+            .get_active_pane(client_id)
+            .and_then(|p| p.last_command_output());
+        if let Some(last_command_output) = last_command_output {
+            self.write_selection_to_clipboard(&last_command_output)
+                .with_context(|| {
+                    format!("failed to copy last command output for client {client_id}")
+                })?;
+        }
+        Ok(())
+    }
+
     fn write_selection_to_clipboard(&self, selection: &str) -> Result<()> {
         let err_context = || format!("failed to write selection to clipboard: '{}'", selection);
 
@@ -3160,13 +4109,15 @@ impl Tab {
             }
             .with_context(err_context)?;
 
-            // It only allows printable unicode, delete and backspace keys.
-            let is_updatable = buf
-                .iter()
-                .all(|u| matches!(u, 0x20..=0x7E | 0xA0..=0xFF | 0x08 | 0x7F));
-            if is_updatable {
-                let s = str::from_utf8(&buf).with_context(err_context)?;
-                active_terminal.update_name(s);
+            // It only allows printable unicode, delete and backspace keys and the terminating
+            // character that clears the name
+            if let Ok(s) = str::from_utf8(&buf) {
+                let is_updatable = s
+                    .chars()
+                    .all(|c| !c.is_control() || matches!(c, '\u{0000}' | '\u{0008}' | '\u{007F}'));
+                if is_updatable {
+                    active_terminal.update_name(s);
+                }
             }
         }
         Ok(())
@@ -3189,6 +4140,23 @@ impl Tab {
         Ok(())
     }
 
+    pub fn update_terminal_processes(
+        &mut self,
+        terminal_processes: &HashMap<u32, TerminalProcesses>, // u32 is the terminal pane id
+    ) {
+        for pane_id in self.get_all_pane_ids() {
+            if let PaneId::Terminal(terminal_id) = pane_id {
+                if let Some(pane) = self
+                    .floating_panes
+                    .get_pane_mut(pane_id)
+                    .or_else(|| self.tiled_panes.get_pane_mut(pane_id))
+                {
+                    pane.update_terminal_processes(terminal_processes.get(&terminal_id).cloned());
+                }
+            }
+        }
+    }
+
     pub fn undo_active_rename_pane(&mut self, client_id: ClientId) -> Result<()> {
         if let Some(active_terminal_id) = self.get_active_terminal_id(client_id) {
             let active_terminal = if self.are_floating_panes_visible() {
@@ -3223,6 +4191,15 @@ impl Tab {
             && column <= viewport.x + viewport.cols)
     }
 
+    pub fn update_style(&mut self, style: Style) {
+        self.style = style;
+        self.tiled_panes.update_style(style);
+        self.floating_panes.update_style(style);
+        for pane in self.suppressed_panes.values_mut() {
+            pane.update_style(style);
+        }
+        self.set_force_render();
+    }
     pub fn set_pane_frames(&mut self, should_set_pane_frames: bool) {
         self.tiled_panes.set_pane_frames(should_set_pane_frames);
         self.draw_pane_frames = should_set_pane_frames;
@@ -3232,6 +4209,23 @@ impl Tab {
     pub fn panes_to_hide_count(&self) -> usize {
         self.tiled_panes.panes_to_hide_count()
     }
+    pub fn selectable_pane_count(&self) -> usize {
+        let selectable_tiled_panes = self
+            .tiled_panes
+            .get_panes()
+            .filter(|(_, p)| p.selectable())
+            .count();
+        let selectable_floating_panes = self
+            .floating_panes
+            .get_panes()
+            .filter(|(_, p)| p.selectable())
+            .count();
+        selectable_tiled_panes + selectable_floating_panes
+    }
+    pub fn focused_pane_title(&self, client_id: ClientId) -> Option<String> {
+        self.get_active_pane(client_id)
+            .map(|active_pane| active_pane.current_title())
+    }
 
     pub fn update_search_term(&mut self, buf: Vec<u8>, client_id: ClientId) -> Result<()> {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
@@ -3279,6 +4273,12 @@ impl Tab {
         }
     }
 
+    pub fn toggle_search_regex(&mut self, client_id: ClientId) {
+        if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
+            active_pane.toggle_search_regex();
+        }
+    }
+
     pub fn clear_search(&mut self, client_id: ClientId) {
         if let Some(active_pane) = self.get_active_pane_or_floating_pane_mut(client_id) {
             active_pane.clear_search();
@@ -3426,6 +4426,29 @@ impl Tab {
                 None => Ok(()),
             })
     }
+    pub fn toggle_pane_visibility(&mut self, pane_id: PaneId, client_id: ClientId) -> Result<()> {
+        let is_visible = self.tiled_panes.panes_contain(&pane_id)
+            || (self.floating_panes.panes_contain(&pane_id) && self.are_floating_panes_visible());
+        if is_visible {
+            self.suppress_pane(pane_id, client_id);
+            Ok(())
+        } else {
+            let should_float = true;
+            self.focus_pane_with_id(pane_id, should_float, client_id)
+        }
+    }
+    /// Moves a floating pane to the middle of the viewport (eg. the scratchpad, so that it shows
+    /// up in the same place every time)
+    pub fn center_floating_pane(&mut self, pane_id: PaneId) -> Result<()> {
+        let err_context = || format!("failed to center floating pane {pane_id:?}");
+        let centered_geom = self.floating_panes.centered_pane_geom();
+        self.floating_panes.set_pane_geom(pane_id, centered_geom);
+        if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+            resize_pty!(pane, self.os_api, self.senders, self.character_cell_size)
+                .with_context(err_context)?;
+        }
+        Ok(())
+    }
     pub fn suppress_pane(&mut self, pane_id: PaneId, client_id: ClientId) {
         if let Some(pane) = self.close_pane(pane_id, true, Some(client_id)) {
             self.suppressed_panes.insert(pane_id, pane);
@@ -3445,8 +4468,68 @@ impl Tab {
             pane_info_for_suppressed_pane.is_fullscreen = false;
             pane_info.push(pane_info_for_suppressed_pane);
         }
+        for pane_info in pane_info.iter_mut().filter(|p| !p.is_plugin) {
+            let pane_id = PaneId::Terminal(pane_info.id);
+            pane_info.has_bell = self.pane_monitor.has_bell(&pane_id);
+            pane_info.has_activity = self.pane_monitor.has_activity(&pane_id);
+            pane_info.is_silent = self.pane_monitor.is_silent(&pane_id);
+        }
         pane_info
     }
+    /// The run command (with its cwd), title and geometry of a terminal pane along with whether it
+    /// is floating, so that it can be reopened once it is closed
+    pub fn terminal_pane_manifest(
+        &mut self,
+        pane_id: PaneId,
+        cwds: &HashMap<u32, PathBuf>,
+    ) -> Option<(PaneLayoutManifest, bool)> {
+        if let PaneId::Plugin(_) = pane_id {
+            return None;
+        }
+        if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+            return Some((pane_layout_manifest(pane, cwds, false, None), true));
+        }
+        self.tiled_panes
+            .get_pane_mut(pane_id)
+            .map(|pane| (pane_layout_manifest(pane, cwds, false, None), false))
+    }
+    /// The tiled and floating panes of this tab as they should be serialized, `cwds` are the
+    /// current working directories of the terminal panes (by terminal id)
+    pub fn layout_manifest(
+        &mut self,
+        cwds: &HashMap<u32, PathBuf>,
+        serialize_pane_contents: bool,
+    ) -> TabLayoutManifest {
+        let first_client_id = self.connected_clients.borrow().iter().next().copied();
+        let focused_tiled_pane_id = self.tiled_panes.first_active_pane_id();
+        let focused_floating_pane_id = if self.floating_panes.panes_are_visible() {
+            self.floating_panes.first_active_floating_pane_id()
+        } else {
+            None
+        };
+        let tiled_pane_ids: Vec<PaneId> = self.tiled_panes.pane_ids().copied().collect();
+        let floating_pane_ids: Vec<PaneId> = self.floating_panes.pane_ids().copied().collect();
+        let mut tab_layout_manifest = TabLayoutManifest::default();
+        for pane_id in tiled_pane_ids {
+            if let Some(pane) = self.tiled_panes.get_pane_mut(pane_id) {
+                let mut pane_layout_manifest =
+                    pane_layout_manifest(pane, cwds, serialize_pane_contents, first_client_id);
+                pane_layout_manifest.is_focused = focused_tiled_pane_id == Some(pane_id);
+                tab_layout_manifest.tiled_panes.push(pane_layout_manifest);
+            }
+        }
+        for pane_id in floating_pane_ids {
+            if let Some(pane) = self.floating_panes.get_pane_mut(pane_id) {
+                let mut pane_layout_manifest =
+                    pane_layout_manifest(pane, cwds, serialize_pane_contents, first_client_id);
+                pane_layout_manifest.is_focused = focused_floating_pane_id == Some(pane_id);
+                tab_layout_manifest
+                    .floating_panes
+                    .push(pane_layout_manifest);
+            }
+        }
+        tab_layout_manifest
+    }
     pub fn add_floating_pane(
         &mut self,
         mut pane: Box<dyn Pane>,
@@ -3559,6 +4642,47 @@ pub fn pane_info_for_pane(pane_id: &PaneId, pane: &Box<dyn Pane>) -> PaneInfo {
     pane_info
 }
 
+fn pane_layout_manifest(
+    pane: &mut Box<dyn Pane>,
+    cwds: &HashMap<u32, PathBuf>,
+    serialize_pane_contents: bool,
+    client_id: Option<ClientId>,
+) -> PaneLayoutManifest {
+    let run = match pane.pid() {
+        PaneId::Terminal(terminal_id) => {
+            let cwd = cwds.get(&terminal_id).cloned();
+            match pane.invoked_with() {
+                Some(Run::Command(run_command)) => {
+                    // commands are not re-run automatically when the session is resurrected
+                    let mut run_command = run_command.clone();
+                    run_command.hold_on_start = true;
+                    if cwd.is_some() {
+                        run_command.cwd = cwd;
+                    }
+                    Some(Run::Command(run_command))
+                },
+                Some(Run::EditFile(..)) => pane.invoked_with().clone(),
+                _ => cwd.map(Run::Cwd),
+            }
+        },
+        PaneId::Plugin(_) => pane.invoked_with().clone(),
+    };
+    let pane_contents = match (pane.pid(), client_id) {
+        (PaneId::Terminal(_), Some(client_id)) if serialize_pane_contents => {
+            Some(pane.dump_screen(client_id, true))
+        },
+        _ => None,
+    };
+    PaneLayoutManifest {
+        geom: pane.position_and_size(),
+        run,
+        is_borderless: pane.borderless(),
+        title: pane.custom_title(),
+        is_focused: false,
+        pane_contents,
+    }
+}
+
 #[cfg(test)]
 #[path = "./unit/tab_tests.rs"]
 mod tab_tests;
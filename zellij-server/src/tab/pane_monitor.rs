@@ -0,0 +1,111 @@
+use std::collections::{HashMap, HashSet};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use crate::panes::PaneId;
+use zellij_utils::anyhow::{Context, Result};
+use zellij_utils::consts::BELL_COMMAND;
+
+/// Tracks which terminal panes rang the bell, had output or went silent while they were not
+/// focused, so that this can be reported to plugins (eg. the tab-bar) until they are focused again
+#[derive(Debug, Default)]
+pub struct PaneMonitor {
+    panes_with_bell: HashSet<PaneId>,
+    panes_with_activity: HashSet<PaneId>,
+    silent_panes: HashSet<PaneId>,
+    last_output: HashMap<PaneId, Instant>,
+    has_changes: bool, // whether any of the above changed since the last time it was reported
+}
+
+impl PaneMonitor {
+    /// Returns `true` if the pane rang the bell for the first time since it was last focused
+    pub fn pane_had_output(
+        &mut self,
+        pane_id: PaneId,
+        rang_bell: bool,
+        pane_is_focused: bool,
+    ) -> bool {
+        self.last_output.insert(pane_id, Instant::now());
+        if self.silent_panes.remove(&pane_id) {
+            self.has_changes = true;
+        }
+        if pane_is_focused {
+            return false;
+        }
+        if self.panes_with_activity.insert(pane_id) {
+            self.has_changes = true;
+        }
+        if rang_bell && self.panes_with_bell.insert(pane_id) {
+            self.has_changes = true;
+            return true;
+        }
+        false
+    }
+    /// Marks the panes that had no output for at least `silence`, `pane_is_focused` is used to
+    /// leave out the panes the user is looking at
+    pub fn mark_silent_panes(
+        &mut self,
+        silence: Duration,
+        pane_is_focused: impl Fn(PaneId) -> bool,
+    ) {
+        for (pane_id, last_output) in &self.last_output {
+            if last_output.elapsed() >= silence
+                && !self.silent_panes.contains(pane_id)
+                && !pane_is_focused(*pane_id)
+            {
+                self.silent_panes.insert(*pane_id);
+                self.has_changes = true;
+            }
+        }
+    }
+    pub fn clear_pane(&mut self, pane_id: PaneId) {
+        let had_bell = self.panes_with_bell.remove(&pane_id);
+        let had_activity = self.panes_with_activity.remove(&pane_id);
+        let was_silent = self.silent_panes.remove(&pane_id);
+        if had_bell || had_activity || was_silent {
+            self.has_changes = true;
+        }
+    }
+    pub fn remove_pane(&mut self, pane_id: PaneId) {
+        self.clear_pane(pane_id);
+        self.last_output.remove(&pane_id);
+    }
+    pub fn has_bell(&self, pane_id: &PaneId) -> bool {
+        self.panes_with_bell.contains(pane_id)
+    }
+    pub fn has_activity(&self, pane_id: &PaneId) -> bool {
+        self.panes_with_activity.contains(pane_id)
+    }
+    pub fn is_silent(&self, pane_id: &PaneId) -> bool {
+        self.silent_panes.contains(pane_id)
+    }
+    pub fn take_changes(&mut self) -> bool {
+        std::mem::take(&mut self.has_changes)
+    }
+}
+
+/// Runs the command set with the `bell_command` option (if any) for a pane that rang the bell
+pub fn run_bell_command(pane_id: PaneId, pane_title: &str, tab_name: &str) -> Result<()> {
+    let bell_command = match BELL_COMMAND.get() {
+        Some(bell_command) => bell_command,
+        None => return Ok(()),
+    };
+    let pane_id = match pane_id {
+        PaneId::Terminal(id) => format!("terminal_{}", id),
+        PaneId::Plugin(id) => format!("plugin_{}", id),
+    };
+    let mut process = Command::new("sh")
+        .arg("-c")
+        .arg(bell_command)
+        .env("ZELLIJ_BELL_PANE_ID", pane_id)
+        .env("ZELLIJ_BELL_PANE_TITLE", pane_title)
+        .env("ZELLIJ_BELL_TAB_NAME", tab_name)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("couldn't spawn bell command {}", bell_command))?;
+    // reap the command once it exits so it doesn't linger as a zombie
+    std::thread::spawn(move || process.wait());
+    Ok(())
+}
@@ -720,7 +720,7 @@ fn dump_screen() {
     tab.handle_pty_bytes(2, Vec::from("scratch".as_bytes()))
         .unwrap();
     let file = "/tmp/log.sh";
-    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false)
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, false)
         .unwrap();
     assert_eq!(
         map.lock().unwrap().get(file).unwrap(),
@@ -729,6 +729,34 @@ fn dump_screen() {
     );
 }
 
+#[test]
+fn dump_screen_with_ansi_styling() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let map = Arc::new(Mutex::new(HashMap::new()));
+    tab.os_api = Box::new(FakeInputOutput {
+        file_dumps: map.clone(),
+        ..Default::default()
+    });
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(2, Vec::from("\u{1b}[31mred\u{1b}[m plain".as_bytes()))
+        .unwrap();
+    let file = "/tmp/log-ansi.sh";
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, true)
+        .unwrap();
+    assert_eq!(
+        map.lock().unwrap().get(file).unwrap(),
+        "\u{1b}[31mred\u{1b}[m plain",
+        "screen was dumped with its styling"
+    );
+}
+
 #[test]
 fn clear_screen() {
     let size = Size {
@@ -749,7 +777,7 @@ fn clear_screen() {
         .unwrap();
     let file = "/tmp/log-clear-screen.sh";
     tab.clear_active_terminal_screen(client_id).unwrap();
-    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false)
+    tab.dump_active_terminal_screen(Some(file.to_string()), client_id, false, false)
         .unwrap();
     assert_eq!(
         map.lock().unwrap().get(file).unwrap(),
@@ -877,6 +905,90 @@ fn toggle_floating_panes_on() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn pinned_floating_pane_stays_visible_when_floating_panes_are_hidden() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    let mut output = Output::default();
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(
+        2,
+        Vec::from("\n\n\n                   I am scratch terminal".as_bytes()),
+    )
+    .unwrap();
+    tab.toggle_active_pane_pinned(client_id);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert!(
+        !tab.are_floating_panes_visible(),
+        "floating panes were hidden"
+    );
+    assert!(
+        snapshot.contains("I am scratch terminal"),
+        "pinned pane is still rendered"
+    );
+    assert!(
+        snapshot.contains("PIN [+]"),
+        "pinned pane shows it is pinned"
+    );
+}
+
+#[test]
+fn toggle_floating_pane_pinned_with_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mode_info = ModeInfo {
+        mode: InputMode::Pane,
+        ..Default::default()
+    };
+    let mut tab = create_new_tab(size, mode_info);
+    let new_pane_id = PaneId::Terminal(2);
+    tab.toggle_floating_panes(Some(client_id), None).unwrap();
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    let pane_geom = tab
+        .floating_panes
+        .get_pane(new_pane_id)
+        .unwrap()
+        .current_geom();
+    let pin_indication_position = Position::new(
+        pane_geom.y as i32,
+        (pane_geom.x + pane_geom.cols.as_usize() - 3) as u16,
+    );
+    tab.handle_left_click(&pin_indication_position, client_id)
+        .unwrap();
+    tab.handle_left_mouse_release(&pin_indication_position, client_id)
+        .unwrap();
+    assert!(
+        tab.floating_panes.pane_is_pinned(&new_pane_id),
+        "pane was pinned by clicking its pin indication"
+    );
+    tab.handle_left_click(&pin_indication_position, client_id)
+        .unwrap();
+    tab.handle_left_mouse_release(&pin_indication_position, client_id)
+        .unwrap();
+    assert!(
+        !tab.floating_panes.pane_is_pinned(&new_pane_id),
+        "pane was unpinned by clicking its pin indication again"
+    );
+}
+
 #[test]
 fn five_new_floating_panes() {
     let size = Size {
@@ -1929,6 +2041,30 @@ fn rename_embedded_pane() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn rename_pane_with_unicode_name_replacing_previous_name() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    tab.update_active_pane_name("first name".as_bytes().to_vec(), client_id)
+        .unwrap();
+    // this is what renaming a pane from the cli sends: a terminating character to clear the
+    // previous name followed by the whole new name
+    tab.update_active_pane_name(vec![0], client_id).unwrap();
+    tab.update_active_pane_name("日本語 ☺ pane".as_bytes().to_vec(), client_id)
+        .unwrap();
+    tab.update_active_pane_name("\u{1b}[A".as_bytes().to_vec(), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane(client_id).unwrap().custom_title(),
+        Some("日本語 ☺ pane".to_owned()),
+        "Pane renamed, control characters ignored"
+    );
+}
+
 #[test]
 fn rename_floating_pane() {
     let size = Size {
@@ -2831,6 +2967,98 @@ fn pane_bracketed_paste_ignored_when_not_in_bracketed_paste_mode() {
     assert_eq!(pty_instruction_bus.clone_output(), vec!["", "test", ""]);
 }
 
+#[test]
+fn pane_bracketed_paste_forwarded_with_multi_megabyte_paste_in_bracketed_paste_mode() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id: u16 = 1;
+
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    let bracketed_paste_mode = String::from("\u{1b}[?2004h");
+    tab.handle_pty_bytes(1, bracketed_paste_mode.as_bytes().to_vec())
+        .unwrap();
+
+    let bracketed_paste_start = vec![27, 91, 50, 48, 48, 126]; // \u{1b}[200~
+    let bracketed_paste_end = vec![27, 91, 50, 48, 49, 126]; // \u{1b}[201
+    let pasted_text = "pasted line\n".repeat(300 * 1024);
+    tab.write_to_active_terminal(bracketed_paste_start, client_id)
+        .unwrap();
+    tab.write_to_active_terminal(pasted_text.as_bytes().to_vec(), client_id)
+        .unwrap();
+    tab.write_to_active_terminal(bracketed_paste_end, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert!(
+        pty_instruction_bus.clone_output()
+            == vec![
+                "\u{1b}[200~".to_owned(),
+                pasted_text,
+                "\u{1b}[201~".to_owned()
+            ],
+        "paste forwarded whole and surrounded by the bracketed paste sequences"
+    );
+}
+
+#[test]
+fn move_focus_or_delegate_to_pane_that_claimed_navigation() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id: u16 = 1;
+
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+    tab.new_pane(PaneId::Terminal(2), None, None, None, Some(client_id))
+        .unwrap();
+    let ctrl_h = vec![8];
+
+    tab.handle_pty_bytes(2, "\u{1b}]7711;claim\u{7}".as_bytes().to_vec())
+        .unwrap();
+    tab.move_focus_or_delegate(Direction::Left, ctrl_h.clone(), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2)),
+        "key delegated to the pane that claimed navigation"
+    );
+
+    tab.handle_pty_bytes(2, "\u{1b}]7711;move;left\u{7}".as_bytes().to_vec())
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(1)),
+        "move bounced back by the pane"
+    );
+
+    tab.move_focus_or_delegate(Direction::Right, ctrl_h.clone(), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(PaneId::Terminal(2)),
+        "focus moved since the pane did not claim navigation"
+    );
+
+    pty_instruction_bus.exit();
+    assert_eq!(pty_instruction_bus.clone_output(), vec!["\u{8}"]);
+}
+
 #[test]
 fn pane_faux_scrolling_in_alternate_mode() {
     let size = Size {
@@ -2884,6 +3112,48 @@ fn pane_faux_scrolling_in_alternate_mode() {
     assert_eq!(pty_instruction_bus.clone_output(), expected);
 }
 
+#[test]
+fn forced_scrolling_bypasses_mouse_reporting_in_alternate_mode() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id: u16 = 1;
+    let lines_to_scroll = 3;
+
+    let mut pty_instruction_bus = MockPtyInstructionBus::new();
+    let mut tab = create_new_tab_with_mock_pty_writer(
+        size,
+        ModeInfo::default(),
+        pty_instruction_bus.pty_write_sender(),
+    );
+    pty_instruction_bus.start();
+
+    let enable_alternate_screen = String::from("\u{1b}[?1049h");
+    let sgr_mouse_mode = String::from("\u{1b}[?1000;1006h"); // normal event tracking (1000) with sgr encoding (1006)
+    tab.handle_pty_bytes(1, enable_alternate_screen.as_bytes().to_vec())
+        .unwrap();
+    tab.handle_pty_bytes(1, sgr_mouse_mode.as_bytes().to_vec())
+        .unwrap();
+
+    // forced scrolling is handled by zellij, nothing is sent to the application
+    tab.handle_forced_scrollwheel_up(&Position::new(5, 5), lines_to_scroll, client_id)
+        .unwrap();
+    tab.handle_forced_scrollwheel_down(&Position::new(5, 5), lines_to_scroll, client_id)
+        .unwrap();
+    tab.handle_scrollwheel_up(&Position::new(5, 5), lines_to_scroll, client_id)
+        .unwrap();
+    tab.handle_scrollwheel_down(&Position::new(5, 5), lines_to_scroll, client_id)
+        .unwrap();
+
+    pty_instruction_bus.exit();
+
+    assert_eq!(
+        pty_instruction_bus.clone_output(),
+        vec!["\u{1b}[<64;5;5M", "\u{1b}[<65;5;5M"]
+    );
+}
+
 #[test]
 fn move_pane_focus_sends_tty_csi_event() {
     let size = Size {
@@ -4898,6 +5168,96 @@ fn stacked_panes_can_become_fullscreen() {
     assert_snapshot!(snapshot);
 }
 
+#[test]
+fn fullscreen_pane_indicates_activity_in_hidden_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.toggle_active_pane_fullscreen(client_id);
+    tab.handle_pty_bytes(1, Vec::from("I am hidden".as_bytes()))
+        .unwrap();
+    let mut output = Output::default();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert!(
+        snapshot.contains("ACTIVITY IN 1 HIDDEN PANE"),
+        "fullscreen pane indicates output in the pane hidden behind it"
+    );
+
+    tab.toggle_active_pane_fullscreen(client_id);
+    let mut output = Output::default();
+    tab.render(&mut output).unwrap();
+    let snapshot = take_snapshot(
+        output.serialize().unwrap().get(&client_id).unwrap(),
+        size.rows,
+        size.cols,
+        Palette::default(),
+    );
+    assert!(
+        snapshot.contains("I am hidden"),
+        "output of the hidden pane was handled while it was hidden"
+    );
+    assert!(
+        !snapshot.contains("ACTIVITY"),
+        "activity indication is removed once the pane is visible again"
+    );
+}
+
+#[test]
+fn bell_in_unfocused_pane_is_reported_until_it_is_focused() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size, ModeInfo::default());
+    let new_pane_id = PaneId::Terminal(2);
+    tab.new_pane(new_pane_id, None, None, None, Some(client_id))
+        .unwrap();
+    tab.handle_pty_bytes(1, Vec::from("\u{7}".as_bytes()))
+        .unwrap();
+    let pane_info = |tab: &Tab, id: u32| {
+        tab.pane_infos()
+            .into_iter()
+            .find(|p| !p.is_plugin && p.id == id)
+            .unwrap()
+    };
+    assert!(pane_info(&tab, 1).has_bell, "unfocused pane reports bell");
+    assert!(
+        pane_info(&tab, 1).has_activity,
+        "unfocused pane reports activity"
+    );
+    tab.handle_pty_bytes(2, Vec::from("\u{7}".as_bytes()))
+        .unwrap();
+    assert!(
+        !pane_info(&tab, 2).has_bell,
+        "focused pane does not report bell"
+    );
+
+    tab.focus_pane_with_id(PaneId::Terminal(1), false, client_id)
+        .unwrap();
+    tab.clear_monitored_state_of_focused_panes();
+    assert!(
+        !pane_info(&tab, 1).has_bell,
+        "bell is cleared once the pane is focused"
+    );
+    assert!(
+        !pane_info(&tab, 1).has_activity,
+        "activity is cleared once the pane is focused"
+    );
+}
+
 #[test]
 fn layout_with_plugins_and_commands_swaped_properly() {
     let size = Size {
@@ -13,6 +13,7 @@ use zellij_utils::errors::prelude::*;
 use zellij_utils::input::layout::{SplitDirection, SplitSize, TiledPaneLayout};
 use zellij_utils::ipc::IpcReceiverWithContext;
 use zellij_utils::pane_size::{Size, SizeInPixels};
+use zellij_utils::position::Position;
 
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
@@ -340,6 +341,44 @@ fn write_to_suppressed_pane() {
         .unwrap();
 }
 
+#[test]
+fn replace_active_pane_until_it_is_closed() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    let replacing_pane_id = PaneId::Terminal(2);
+    tab.replace_active_pane(
+        replacing_pane_id,
+        Some("my task".to_owned()),
+        None,
+        client_id,
+    )
+    .unwrap();
+    assert_eq!(
+        tab.get_active_pane_id(client_id),
+        Some(replacing_pane_id),
+        "replacing pane is focused"
+    );
+    assert_eq!(
+        tab.get_active_pane(client_id).unwrap().current_title(),
+        "my task"
+    );
+    assert!(
+        tab.suppressed_panes.contains_key(&replacing_pane_id),
+        "replaced pane is suppressed"
+    );
+
+    tab.close_pane(replacing_pane_id, false, Some(client_id));
+    assert!(
+        tab.tiled_panes.panes.contains_key(&PaneId::Terminal(1)),
+        "replaced pane is back once the replacing pane is closed"
+    );
+    assert_eq!(tab.tiled_panes.panes.len(), 1, "the tab has one pane");
+}
+
 #[test]
 fn split_panes_vertically() {
     let size = Size {
@@ -936,6 +975,113 @@ fn switch_to_prev_pane_fullscreen() {
     );
 }
 
+fn y_rows_and_is_stacked(tab: &Tab, pane_id: u32) -> (usize, usize, bool) {
+    let geom = tab
+        .tiled_panes
+        .panes
+        .get(&PaneId::Terminal(pane_id))
+        .unwrap()
+        .position_and_size();
+    (geom.y, geom.rows.as_usize(), geom.is_stacked)
+}
+
+#[test]
+pub fn toggle_expand_pane_stacks_and_spreads_out_the_panes_of_a_column() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1).unwrap();
+    tab.horizontal_split(PaneId::Terminal(4), None, 1).unwrap();
+    tab.move_focus_up(1).unwrap();
+
+    tab.toggle_active_pane_stacked(1);
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 1),
+        (0, 20, false),
+        "pane to the left is untouched"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 2),
+        (0, 1, true),
+        "pane above is collapsed"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 3),
+        (1, 18, true),
+        "focused pane is expanded"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 4),
+        (19, 1, true),
+        "pane below is collapsed"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(3)));
+
+    tab.toggle_active_pane_stacked(1);
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 1),
+        (0, 20, false),
+        "pane to the left is untouched"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 2),
+        (0, 7, false),
+        "first pane is spread out"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 3),
+        (7, 7, false),
+        "second pane is spread out"
+    );
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 4),
+        (14, 6, false),
+        "third pane is spread out"
+    );
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(3)));
+}
+
+#[test]
+pub fn focus_next_and_previous_pane_in_stack_wraps_around() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.horizontal_split(PaneId::Terminal(3), None, 1).unwrap();
+    tab.toggle_active_pane_stacked(1);
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 3),
+        (2, 18, true),
+        "lowest pane is expanded"
+    );
+
+    tab.focus_next_pane_in_stack(1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(1)));
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 1),
+        (0, 18, true),
+        "topmost pane is expanded"
+    );
+    assert_eq!(y_rows_and_is_stacked(&tab, 2), (18, 1, true));
+    assert_eq!(y_rows_and_is_stacked(&tab, 3), (19, 1, true));
+
+    tab.focus_previous_pane_in_stack(1);
+    tab.focus_previous_pane_in_stack(1);
+    assert_eq!(tab.get_active_pane_id(1), Some(PaneId::Terminal(2)));
+    assert_eq!(y_rows_and_is_stacked(&tab, 1), (0, 1, true));
+    assert_eq!(
+        y_rows_and_is_stacked(&tab, 2),
+        (1, 18, true),
+        "middle pane is expanded"
+    );
+    assert_eq!(y_rows_and_is_stacked(&tab, 3), (19, 1, true));
+}
+
 #[test]
 pub fn close_pane_with_another_pane_above_it() {
     // ┌───────────┐            ┌───────────┐
@@ -14408,3 +14554,138 @@ fn correctly_resize_frameless_panes_on_pane_close() {
     let content_size = (pane.get_content_columns(), pane.get_content_rows());
     assert_eq!(content_size, (cols, rows));
 }
+
+#[test]
+pub fn resize_tiled_pane_by_dragging_its_border_with_the_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.handle_left_click(&Position::new(5, 60), client_id)
+        .unwrap();
+    tab.handle_mouse_hold_left(&Position::new(5, 50), client_id)
+        .unwrap();
+    tab.handle_left_mouse_release(&Position::new(5, 50), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .cols
+            .as_usize(),
+        51,
+        "pane 1 shrank to the mouse position"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        51,
+        "pane 2 grew to the left"
+    );
+}
+
+#[test]
+pub fn resize_tiled_pane_by_dragging_its_bottom_border_with_the_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.horizontal_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.handle_left_click(&Position::new(9, 30), client_id)
+        .unwrap();
+    tab.handle_mouse_hold_left(&Position::new(13, 30), client_id)
+        .unwrap();
+    tab.handle_left_mouse_release(&Position::new(13, 30), client_id)
+        .unwrap();
+    assert!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .rows
+            .as_usize()
+            > 10,
+        "pane 1 grew downwards"
+    );
+}
+
+#[test]
+pub fn move_tiled_pane_by_dragging_its_title_bar_with_the_mouse() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 1;
+    let mut tab = create_new_tab(size);
+    tab.vertical_split(PaneId::Terminal(2), None, 1).unwrap();
+    tab.handle_left_click(&Position::new(0, 10), client_id)
+        .unwrap();
+    tab.handle_mouse_hold_left(&Position::new(3, 80), client_id)
+        .unwrap();
+    tab.handle_left_mouse_release(&Position::new(5, 90), client_id)
+        .unwrap();
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(1))
+            .unwrap()
+            .position_and_size()
+            .x,
+        61,
+        "pane 1 moved to the right"
+    );
+    assert_eq!(
+        tab.tiled_panes
+            .panes
+            .get(&PaneId::Terminal(2))
+            .unwrap()
+            .position_and_size()
+            .x,
+        0,
+        "pane 2 moved to the left"
+    );
+}
+
+#[test]
+pub fn float_grouped_panes() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut tab = create_new_tab(size);
+    for i in 2..4 {
+        let new_pane_id = PaneId::Terminal(i);
+        tab.new_pane(new_pane_id, None, None, None, Some(1))
+            .unwrap();
+    }
+    tab.toggle_active_pane_in_group(1);
+    tab.toggle_pane_in_group(PaneId::Terminal(2));
+    assert_eq!(
+        tab.grouped_pane_ids().len(),
+        2,
+        "the active pane and pane 2 are grouped"
+    );
+    tab.float_grouped_panes(1).unwrap();
+    assert!(
+        tab.floating_panes.panes_contain(&PaneId::Terminal(2))
+            && tab.floating_panes.panes_contain(&PaneId::Terminal(3)),
+        "grouped panes are floating"
+    );
+    assert!(
+        tab.tiled_panes.panes_contain(&PaneId::Terminal(1)),
+        "ungrouped pane is still tiled"
+    );
+    assert!(tab.grouped_pane_ids().is_empty(), "pane group was cleared");
+}
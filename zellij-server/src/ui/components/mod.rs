@@ -0,0 +1,182 @@
+//! Renders the UI components (eg. tables) that plugins print with `zellij_tile::ui_components`,
+//! using the user's theme so that plugins match the rest of the UI. See there for how the
+//! components are serialized.
+mod nested_list;
+mod ribbon;
+mod table;
+mod text;
+
+use crate::pty::VteBytes;
+use std::fmt::Write;
+use unicode_width::UnicodeWidthChar;
+use zellij_utils::data::{PaletteColor, Style};
+
+use nested_list::nested_list;
+use ribbon::ribbon;
+use table::table;
+
+const COMPONENT_START: &[u8] = b"\x1bPz";
+const COMPONENT_END: &[u8] = b"\x1b\\";
+
+/// A rendered line of a component, one styled character at a time
+type Line = Vec<(char, ansi_term::Style)>;
+
+/// Where the plugin asked for a component to be printed, relative to the top left corner of its
+/// pane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Coordinates {
+    x: usize,
+    y: usize,
+    width: Option<usize>,
+    height: Option<usize>,
+}
+
+impl Coordinates {
+    fn parse(coordinates: &str) -> Option<Self> {
+        let mut parts = coordinates.split('/');
+        let x = parts.next()?.parse().ok()?;
+        let y = parts.next()?.parse().ok()?;
+        let width = parts.next().and_then(|width| width.parse().ok());
+        let height = parts.next().and_then(|height| height.parse().ok());
+        Some(Coordinates {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// Replaces the components in `bytes` with their ANSI rendition, anything else is left as is
+pub fn render_ui_components(bytes: VteBytes, style: &Style) -> VteBytes {
+    if find(&bytes, COMPONENT_START).is_none() {
+        return bytes;
+    }
+    let mut rendered = Vec::with_capacity(bytes.len());
+    let mut remaining = &bytes[..];
+    while let Some(start) = find(remaining, COMPONENT_START) {
+        rendered.extend_from_slice(&remaining[..start]);
+        let component = &remaining[start + COMPONENT_START.len()..];
+        match find(component, COMPONENT_END) {
+            Some(end) => {
+                let serialized = String::from_utf8_lossy(&component[..end]);
+                match render_component(&serialized, style) {
+                    Some(ansi) => rendered.extend_from_slice(ansi.as_bytes()),
+                    None => log::error!("Failed to render UI component: {:?}", serialized),
+                }
+                remaining = &component[end + COMPONENT_END.len()..];
+            },
+            None => {
+                // an unterminated component is dropped, like any other unterminated DCS
+                remaining = &[];
+            },
+        }
+    }
+    rendered.extend_from_slice(remaining);
+    rendered
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn render_component(serialized: &str, style: &Style) -> Option<String> {
+    let mut fields = split_fields(serialized).into_iter();
+    let component = fields.next()?;
+    let coordinates = match fields.next()?.as_str() {
+        "" => None,
+        coordinates => Some(Coordinates::parse(coordinates)?),
+    };
+    let fields: Vec<String> = fields.collect();
+    let lines = match component.as_str() {
+        "table" => table(&fields, style)?,
+        "ribbon" => ribbon(&fields, style)?,
+        "nested_list" => nested_list(&fields, style)?,
+        _ => return None,
+    };
+    Some(lines_to_ansi(&lines, coordinates))
+}
+
+/// Splits on `;`, unless escaped with a `\` (which is itself escaped as `\\`)
+fn split_fields(serialized: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut characters = serialized.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => {
+                if let (Some(escaped), Some(field)) = (characters.next(), fields.last_mut()) {
+                    field.push(escaped);
+                }
+            },
+            ';' => fields.push(String::new()),
+            _ => {
+                if let Some(field) = fields.last_mut() {
+                    field.push(character);
+                }
+            },
+        }
+    }
+    fields
+}
+
+/// Without coordinates, the component starts at the cursor and each of its lines starts below
+/// the start of the previous one
+fn lines_to_ansi(lines: &[Line], coordinates: Option<Coordinates>) -> String {
+    let max_width = coordinates.and_then(|c| c.width);
+    let max_height = coordinates.and_then(|c| c.height).unwrap_or(usize::MAX);
+    let mut output = String::new();
+    let mut previous_line_width = 0;
+    for (index, line) in lines.iter().take(max_height).enumerate() {
+        match coordinates {
+            Some(coordinates) => {
+                let _ = write!(
+                    output,
+                    "\u{1b}[{};{}H",
+                    coordinates.y + index + 1,
+                    coordinates.x + 1
+                );
+            },
+            None if index > 0 => {
+                if previous_line_width > 0 {
+                    let _ = write!(output, "\u{1b}[{}D", previous_line_width);
+                }
+                output.push_str("\u{1b}[1B");
+            },
+            None => {},
+        }
+        let mut line_width = 0;
+        let mut runs: Vec<(ansi_term::Style, String)> = vec![];
+        for (character, style) in line {
+            let character_width = character.width().unwrap_or(0);
+            if max_width
+                .map(|max_width| line_width + character_width > max_width)
+                .unwrap_or(false)
+            {
+                break;
+            }
+            line_width += character_width;
+            match runs.last_mut() {
+                Some((run_style, run)) if *run_style == *style => run.push(*character),
+                _ => runs.push((*style, character.to_string())),
+            }
+        }
+        for (style, run) in runs {
+            let _ = write!(output, "{}", style.paint(run));
+        }
+        previous_line_width = line_width;
+    }
+    output
+}
+
+fn color(palette_color: PaletteColor) -> ansi_term::Color {
+    match palette_color {
+        PaletteColor::Rgb((r, g, b)) => ansi_term::Color::RGB(r, g, b),
+        PaletteColor::EightBit(color) => ansi_term::Color::Fixed(color),
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/components_tests.rs"]
+mod components_tests;
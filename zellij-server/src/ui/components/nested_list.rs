@@ -0,0 +1,24 @@
+use super::{text::Text, Line};
+use zellij_utils::data::Style;
+
+/// A bulleted list whose items are indented by their level. Fields are pairs of an
+/// indentation level and the item's text
+pub fn nested_list(fields: &[String], style: &Style) -> Option<Vec<Line>> {
+    fields
+        .chunks(2)
+        .map(|item| {
+            let indentation_level: usize = item.first()?.parse().ok()?;
+            let text = Text::parse(item.get(1)?)?;
+            let base = ansi_term::Style::new();
+            let declaration = text.declaration(&style.styling);
+            let bullet_style = text.base_style(base, &declaration);
+            let mut line: Line = std::iter::repeat((' ', base))
+                .take(indentation_level * 2)
+                .collect();
+            line.push(('-', bullet_style));
+            line.push((' ', bullet_style));
+            line.append(&mut text.styled(base, &declaration));
+            Some(line)
+        })
+        .collect()
+}
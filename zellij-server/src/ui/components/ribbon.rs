@@ -0,0 +1,30 @@
+use super::{color, text::Text, Line};
+use zellij_utils::data::Style;
+
+const ARROW_SEPARATOR: char = '\u{e0b0}';
+
+/// A single arrow-shaped segment, like the tabs of the tab-bar (selected ribbons look like the
+/// active tab)
+pub fn ribbon(fields: &[String], style: &Style) -> Option<Vec<Line>> {
+    let text = Text::parse(fields.first()?)?;
+    let declaration = if text.selected {
+        style.styling.ribbon_selected
+    } else {
+        style.styling.ribbon_unselected
+    };
+    let foreground = color(declaration.base);
+    let background = color(declaration.background);
+    let left_separator_style = ansi_term::Style::new().fg(foreground).on(background);
+    let right_separator_style = ansi_term::Style::new().fg(background).on(foreground);
+    let base = left_separator_style.bold();
+    // the ribbon's own colors already show whether it is selected
+    let text = Text {
+        selected: false,
+        ..text
+    };
+    let mut line = vec![(ARROW_SEPARATOR, left_separator_style), (' ', base)];
+    line.append(&mut text.styled(base, &declaration));
+    line.push((' ', base));
+    line.push((ARROW_SEPARATOR, right_separator_style));
+    Some(vec![line])
+}
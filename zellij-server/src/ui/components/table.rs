@@ -0,0 +1,48 @@
+use super::{text::Text, Line};
+use zellij_utils::data::Style;
+
+/// Rows of cells aligned in columns, the first row is the title and is bold. Fields are the
+/// number of columns followed by the cells, row by row
+pub fn table(fields: &[String], style: &Style) -> Option<Vec<Line>> {
+    let (columns, cells) = fields.split_first()?;
+    let columns: usize = columns.parse().ok()?;
+    if columns == 0 {
+        return Some(vec![]);
+    }
+    let cells = cells
+        .iter()
+        .map(|cell| Text::parse(cell))
+        .collect::<Option<Vec<_>>>()?;
+    let rows: Vec<&[Text]> = cells.chunks(columns).collect();
+    let column_widths: Vec<usize> = (0..columns)
+        .map(|column| {
+            rows.iter()
+                .filter_map(|row| row.get(column))
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let lines = rows
+        .iter()
+        .enumerate()
+        .map(|(row_index, row)| {
+            let base = if row_index == 0 {
+                ansi_term::Style::new().bold()
+            } else {
+                ansi_term::Style::new()
+            };
+            let mut line = vec![];
+            for (column, cell) in row.iter().enumerate() {
+                line.append(&mut cell.styled(base, &cell.declaration(&style.styling)));
+                // columns are separated by a space, a selected cell keeps its background there
+                let padding = column_widths[column].saturating_sub(cell.width())
+                    + if column + 1 < row.len() { 1 } else { 0 };
+                let padding_style = cell.base_style(base, &cell.declaration(&style.styling));
+                line.extend(std::iter::repeat((' ', padding_style)).take(padding));
+            }
+            line
+        })
+        .collect();
+    Some(lines)
+}
@@ -0,0 +1,87 @@
+use super::{color, Line};
+use std::ops::Range;
+use unicode_width::UnicodeWidthStr;
+use zellij_utils::data::{PaletteColor, StyleDeclaration, Styling};
+
+/// A text field of a component, see `zellij_tile::ui_components::Text`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Text {
+    pub text: String,
+    pub selected: bool,
+    pub color_ranges: Vec<(usize, Range<usize>)>,
+}
+
+impl Text {
+    /// Parses `{meta}|{text}`, where `meta` is a comma separated list of `s` (selected) and
+    /// `c{level}:{start}:{end}` (color range)
+    pub fn parse(field: &str) -> Option<Self> {
+        let (meta, text) = field.split_once('|')?;
+        let mut parsed = Text {
+            text: text.to_owned(),
+            ..Default::default()
+        };
+        for token in meta.split(',').filter(|token| !token.is_empty()) {
+            if token == "s" {
+                parsed.selected = true;
+            } else {
+                let mut parts = token.strip_prefix('c')?.split(':');
+                let level = parts.next()?.parse().ok()?;
+                let start = parts.next()?.parse().ok()?;
+                let end = parts.next()?.parse().ok()?;
+                parsed.color_ranges.push((level, start..end));
+            }
+        }
+        Some(parsed)
+    }
+    pub fn width(&self) -> usize {
+        self.text.width()
+    }
+    /// The theme's colors for this text, depending on whether it is selected
+    pub fn declaration(&self, styling: &Styling) -> StyleDeclaration {
+        if self.selected {
+            styling.text_selected
+        } else {
+            styling.text_unselected
+        }
+    }
+    /// `base` in the declaration's base color, selected text also gets its background
+    pub fn base_style(
+        &self,
+        base: ansi_term::Style,
+        declaration: &StyleDeclaration,
+    ) -> ansi_term::Style {
+        let base = base.fg(color(declaration.base));
+        if self.selected {
+            base.on(color(declaration.background))
+        } else {
+            base
+        }
+    }
+    pub fn styled(&self, base: ansi_term::Style, declaration: &StyleDeclaration) -> Line {
+        let base = self.base_style(base, declaration);
+        self.text
+            .chars()
+            .enumerate()
+            .map(|(index, character)| {
+                // later ranges take precedence over earlier ones
+                let style = self
+                    .color_ranges
+                    .iter()
+                    .rev()
+                    .find(|(_, range)| range.contains(&index))
+                    .map(|(level, _)| base.fg(color(emphasis(*level, declaration))).bold())
+                    .unwrap_or(base);
+                (character, style)
+            })
+            .collect()
+    }
+}
+
+fn emphasis(level: usize, declaration: &StyleDeclaration) -> PaletteColor {
+    match level {
+        0 => declaration.emphasis_0,
+        1 => declaration.emphasis_1,
+        2 => declaration.emphasis_2,
+        _ => declaration.emphasis_3,
+    }
+}
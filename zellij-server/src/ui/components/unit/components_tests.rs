@@ -0,0 +1,72 @@
+use super::text::Text;
+use super::*;
+
+fn render(serialized: &str) -> String {
+    let bytes = render_ui_components(serialized.as_bytes().to_vec(), &Style::default());
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn fields_are_split_on_unescaped_semicolons() {
+    assert_eq!(
+        split_fields(r"table;;a\;b;c\\\;d"),
+        vec!["table", "", "a;b", r"c\;d"]
+    );
+}
+
+#[test]
+fn text_with_selection_and_color_ranges_is_parsed() {
+    let text = Text::parse("s,c1:0:3,c2:4:5|foo;bar").unwrap();
+    assert_eq!(
+        text,
+        Text {
+            text: "foo;bar".to_owned(),
+            selected: true,
+            color_ranges: vec![(1, 0..3), (2, 4..5)],
+        }
+    );
+    assert_eq!(Text::parse("no separator"), None);
+}
+
+#[test]
+fn coordinates_with_and_without_size_are_parsed() {
+    assert_eq!(
+        Coordinates::parse("1/2/10/"),
+        Some(Coordinates {
+            x: 1,
+            y: 2,
+            width: Some(10),
+            height: None,
+        })
+    );
+    assert_eq!(Coordinates::parse("1"), None);
+}
+
+#[test]
+fn bytes_without_components_are_left_as_is() {
+    let bytes = b"plain \x1b[1mtext\x1b[m".to_vec();
+    assert_eq!(
+        render_ui_components(bytes.clone(), &Style::default()),
+        bytes
+    );
+}
+
+#[test]
+fn table_columns_are_aligned() {
+    let rendered = render("before\x1bPztable;;2;|a;|bbb;|cc;|d\x1b\\after");
+    assert_eq!(
+        rendered,
+        "before\u{1b}[1;38;5;0ma  bbb\u{1b}[0m\u{1b}[6D\u{1b}[1B\u{1b}[38;5;0mcc d  \u{1b}[0mafter"
+    );
+}
+
+#[test]
+fn component_with_coordinates_is_truncated_to_its_size() {
+    let rendered = render("\x1bPznested_list;2/3/4/1;0;|first;1;|second\x1b\\");
+    assert_eq!(rendered, "\u{1b}[4;3H\u{1b}[38;5;0m- fi\u{1b}[0m");
+}
+
+#[test]
+fn unknown_component_is_dropped() {
+    assert_eq!(render("a\x1bPzunknown;;field\x1b\\b"), "ab");
+}
@@ -0,0 +1,71 @@
+use zellij_utils::pane_size::Size;
+
+use super::{Overlay, Overlayable};
+use crate::panes::PaneId;
+use crate::ClientId;
+use zellij_utils::errors::prelude::*;
+
+use std::fmt::Write;
+
+/// Asks whether a pane that is running something (eg. a build) should really be closed
+#[derive(Clone, Debug)]
+pub struct ClosePaneConfirmation {
+    pane_id: PaneId,
+    client_id: ClientId, // the client that asked to close the pane and should answer
+
+    running_processes: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ClosePaneConfirmationInput {
+    Pending,
+    Denied,
+    Confirmed(PaneId),
+}
+
+impl ClosePaneConfirmation {
+    pub fn new(pane_id: PaneId, client_id: ClientId, running_processes: Vec<String>) -> Self {
+        Self {
+            pane_id,
+            client_id,
+            running_processes,
+        }
+    }
+    pub fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+    /// `y` confirms, `n`, Esc or ctrl-c deny, anything else is ignored until one of them is typed
+    pub fn handle_input(&self, input_bytes: &[u8]) -> ClosePaneConfirmationInput {
+        for character in String::from_utf8_lossy(input_bytes).chars() {
+            match character {
+                'y' | 'Y' => return ClosePaneConfirmationInput::Confirmed(self.pane_id),
+                'n' | 'N' | '\u{1b}' | '\u{3}' => return ClosePaneConfirmationInput::Denied,
+                _ => {},
+            }
+        }
+        ClosePaneConfirmationInput::Pending
+    }
+}
+
+impl Overlayable for ClosePaneConfirmation {
+    fn generate_overlay(&self, size: Size) -> Result<String> {
+        let mut output = String::new();
+        let mut vte_output = format!(
+            " Pane is running {}, close it anyway? [Y]es / [N]o",
+            self.running_processes.join(", ")
+        );
+        Overlay::pad_cols(&mut vte_output, size.cols);
+        for (x, h) in vte_output.chars().take(size.cols).enumerate() {
+            write!(
+                &mut output,
+                "\u{1b}[{};{}H\u{1b}[48;5;238m{}",
+                size.rows,
+                x + 1,
+                h,
+            )
+            .context("failed to generate VTE output from close pane confirmation")?;
+        }
+        output.push_str("\u{1b}[0m");
+        Ok(output)
+    }
+}
@@ -0,0 +1,120 @@
+use zellij_utils::pane_size::Size;
+use zellij_utils::position::Position;
+
+use super::Overlayable;
+use zellij_utils::errors::prelude::*;
+
+use std::fmt::Write;
+
+const HINT_ALPHABET: &str = "asdfghjklqwertyuiopzxcvbnm";
+
+/// Labels drawn over the links in a pane, one of which is selected by typing its label
+#[derive(Clone, Debug)]
+pub struct LinkHints {
+    hints: Vec<LinkHint>,
+    typed: String,
+}
+
+#[derive(Clone, Debug)]
+struct LinkHint {
+    label: String,
+    position: Position,
+    link: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LinkHintsInput {
+    Pending,
+    Cancelled,
+    Selected(String),
+}
+
+impl LinkHints {
+    pub fn new(links: Vec<(Position, String)>) -> Self {
+        let hints = hint_labels(links.len())
+            .into_iter()
+            .zip(links)
+            .map(|(label, (position, link))| LinkHint {
+                label,
+                position,
+                link,
+            })
+            .collect();
+        Self {
+            hints,
+            typed: String::new(),
+        }
+    }
+    /// Narrows the hints down to the ones starting with the typed characters, selecting a link
+    /// once its whole label was typed. Esc, ctrl-c or a character that continues no label cancels.
+    pub fn handle_input(&mut self, input_bytes: &[u8]) -> LinkHintsInput {
+        for character in String::from_utf8_lossy(input_bytes).chars() {
+            match character {
+                '\u{1b}' | '\u{3}' => return LinkHintsInput::Cancelled,
+                '\u{7f}' | '\u{8}' => {
+                    self.typed.pop();
+                },
+                character => self.typed.push(character.to_ascii_lowercase()),
+            }
+            if let Some(hint) = self.hints.iter().find(|hint| hint.label == self.typed) {
+                return LinkHintsInput::Selected(hint.link.clone());
+            }
+            if !self
+                .hints
+                .iter()
+                .any(|hint| hint.label.starts_with(&self.typed))
+            {
+                return LinkHintsInput::Cancelled;
+            }
+        }
+        LinkHintsInput::Pending
+    }
+}
+
+impl Overlayable for LinkHints {
+    fn generate_overlay(&self, size: Size) -> Result<String> {
+        let mut output = String::new();
+        for hint in &self.hints {
+            let (line, column) = (hint.position.line(), hint.position.column());
+            if !hint.label.starts_with(&self.typed)
+                || line < 0
+                || line as usize >= size.rows
+                || column >= size.cols
+            {
+                continue;
+            }
+            let remaining_label: String = hint.label[self.typed.len()..]
+                .chars()
+                .take(size.cols - column)
+                .collect();
+            write!(
+                &mut output,
+                "\u{1b}[{};{}H\u{1b}[0;1;30;43m{}\u{1b}[0m",
+                line + 1,
+                column + 1,
+                remaining_label,
+            )
+            .context("failed to generate VTE output from link hints")?;
+        }
+        Ok(output)
+    }
+}
+
+/// Single letter labels while there are few enough links, otherwise two letter labels, so that no
+/// label is the prefix of another
+fn hint_labels(count: usize) -> Vec<String> {
+    let alphabet: Vec<char> = HINT_ALPHABET.chars().collect();
+    if count <= alphabet.len() {
+        alphabet.iter().take(count).map(|c| c.to_string()).collect()
+    } else {
+        alphabet
+            .iter()
+            .flat_map(|first| {
+                alphabet
+                    .iter()
+                    .map(move |second| format!("{first}{second}"))
+            })
+            .take(count)
+            .collect()
+    }
+}
@@ -5,7 +5,13 @@
 //! prompt's:
 //!
 //! notification's:
+//!
+//! link hints:
+//!
+//! close pane confirmation's:
 
+pub mod close_pane_confirmation;
+pub mod link_hints;
 pub mod prompt;
 
 use crate::ServerInstruction;
@@ -26,6 +32,8 @@ pub trait Overlayable {
 #[derive(Clone, Debug)]
 pub enum OverlayType {
     Prompt(prompt::Prompt),
+    LinkHints(link_hints::LinkHints),
+    ClosePaneConfirmation(close_pane_confirmation::ClosePaneConfirmation),
 }
 
 impl Overlayable for OverlayType {
@@ -34,6 +42,12 @@ impl Overlayable for OverlayType {
             OverlayType::Prompt(prompt) => prompt
                 .generate_overlay(size)
                 .context("failed to generate VTE output from overlay type"),
+            OverlayType::LinkHints(link_hints) => link_hints
+                .generate_overlay(size)
+                .context("failed to generate VTE output from overlay type"),
+            OverlayType::ClosePaneConfirmation(close_pane_confirmation) => close_pane_confirmation
+                .generate_overlay(size)
+                .context("failed to generate VTE output from overlay type"),
         }
     }
 }
@@ -65,11 +79,13 @@ impl Overlay {
     pub fn prompt_confirm(self) -> Option<Box<ServerInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.confirm(),
+            OverlayType::LinkHints(_) | OverlayType::ClosePaneConfirmation(_) => None,
         }
     }
     pub fn prompt_deny(self) -> Option<Box<ServerInstruction>> {
         match self.overlay_type {
             OverlayType::Prompt(p) => p.deny(),
+            OverlayType::LinkHints(_) | OverlayType::ClosePaneConfirmation(_) => None,
         }
     }
 }
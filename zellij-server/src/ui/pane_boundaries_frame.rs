@@ -1,13 +1,30 @@
+use crate::os_input_output::TerminalProcesses;
 use crate::output::CharacterChunk;
 use crate::panes::{AnsiCode, CharacterStyles, TerminalCharacter, EMPTY_TERMINAL_CHARACTER};
 use crate::ui::boundaries::boundary_type;
 use crate::ClientId;
 use zellij_utils::data::{client_id_to_colors, PaletteColor, Style};
 use zellij_utils::errors::prelude::*;
-use zellij_utils::pane_size::Viewport;
+use zellij_utils::pane_size::{PaneGeom, Viewport};
+use zellij_utils::position::Position;
+use zellij_utils::shared::fill_template;
 
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+// shown at the right edge of the title line of floating panes, clicking it toggles the pin
+const PINNED_INDICATION: &str = " PIN [+] ";
+const UNPINNED_INDICATION: &str = " PIN [ ] ";
+
+/// Whether `position` is on the pin indication in the frame of a floating pane with `pane_geom`
+pub fn position_is_on_pin_indication(pane_geom: &PaneGeom, position: &Position) -> bool {
+    // the indication ends right before the top right corner of the frame
+    let indication_end = (pane_geom.x + pane_geom.cols.as_usize()).saturating_sub(1);
+    let indication_start = indication_end.saturating_sub(PINNED_INDICATION.width());
+    position.line() == pane_geom.y as isize
+        && position.column() >= indication_start
+        && position.column() < indication_end
+}
+
 fn foreground_color(characters: &str, color: Option<PaletteColor>) -> Vec<TerminalCharacter> {
     let mut colored_string = Vec::new();
     for character in characters.chars() {
@@ -78,6 +95,9 @@ pub struct FrameParams {
     pub pane_is_stacked_under: bool,
     pub pane_is_stacked_over: bool,
     pub should_draw_pane_frames: bool,
+    pub show_pin_indication: bool,
+    pub pane_is_pinned: bool,
+    pub hidden_panes_with_activity: usize,
 }
 
 #[derive(Default, PartialEq)]
@@ -96,6 +116,9 @@ pub struct PaneFrame {
     pane_is_stacked_over: bool,
     pane_is_stacked_under: bool,
     should_draw_pane_frames: bool,
+    show_pin_indication: bool,
+    pane_is_pinned: bool,
+    hidden_panes_with_activity: usize,
 }
 
 impl PaneFrame {
@@ -120,6 +143,9 @@ impl PaneFrame {
             pane_is_stacked_over: frame_params.pane_is_stacked_over,
             pane_is_stacked_under: frame_params.pane_is_stacked_under,
             should_draw_pane_frames: frame_params.should_draw_pane_frames,
+            show_pin_indication: frame_params.show_pin_indication,
+            pane_is_pinned: frame_params.pane_is_pinned,
+            hidden_panes_with_activity: frame_params.hidden_panes_with_activity,
         }
     }
     pub fn add_exit_status(&mut self, exit_status: Option<i32>) {
@@ -134,6 +160,46 @@ impl PaneFrame {
     pub fn override_color(&mut self, color: PaletteColor) {
         self.color = Some(color);
     }
+    /// Replaces the title with the `pane_frames { title "..." }` template from the configuration
+    ///
+    /// Placeholders: `{title}` (the title that would otherwise be shown), `{command}` (the process
+    /// in the foreground of the terminal), `{pid}` (of the process the terminal was spawned with),
+    /// `{cwd}`, `{exit_status}` and `{scroll}` (the scroll position, if scrolled up)
+    pub fn apply_title_template(
+        &mut self,
+        title_template: &str,
+        terminal_processes: Option<&TerminalProcesses>,
+    ) {
+        let command = terminal_processes
+            .and_then(|p| p.foreground_process.clone())
+            .unwrap_or_default();
+        let pid = terminal_processes
+            .and_then(|p| p.child_pids.first())
+            .map(|pid| pid.to_string())
+            .unwrap_or_default();
+        let cwd = terminal_processes
+            .and_then(|p| p.cwd.as_ref())
+            .map(|cwd| cwd.display().to_string())
+            .unwrap_or_default();
+        let exit_status = match self.exit_status {
+            Some(ExitStatus::Code(exit_status)) => exit_status.to_string(),
+            Some(ExitStatus::Exited) | None => String::new(),
+        };
+        let scroll = if self.scroll_position.0 > 0 {
+            format!("{}/{}", self.scroll_position.0, self.scroll_position.1)
+        } else {
+            String::new()
+        };
+        self.title = fill_template(title_template, |placeholder| match placeholder {
+            "title" => Some(self.title.clone()),
+            "command" => Some(command.clone()),
+            "pid" => Some(pid.clone()),
+            "cwd" => Some(cwd.clone()),
+            "exit_status" => Some(exit_status.clone()),
+            "scroll" => Some(scroll.clone()),
+            _ => None,
+        });
+    }
     fn client_cursor(&self, client_id: ClientId) -> Vec<TerminalCharacter> {
         let color = client_id_to_colors(client_id, self.style.colors);
         background_color(" ", color.map(|c| c.0))
@@ -167,6 +233,84 @@ impl PaneFrame {
         max_length: usize,
     ) -> Option<(Vec<TerminalCharacter>, usize)> {
         // string and length because of color
+        // indications are given space in order of precedence, but the clickable pin indication is
+        // always rendered last so that it stays next to the corner
+        let pin_indication = self.render_pin_indication(max_length);
+        let pin_indication_len = pin_indication.as_ref().map(|(_, len)| *len).unwrap_or(0);
+        let activity_indication =
+            self.render_activity_indication(max_length.saturating_sub(pin_indication_len));
+        let activity_indication_len = activity_indication
+            .as_ref()
+            .map(|(_, len)| *len)
+            .unwrap_or(0);
+        let scroll_indication = self.render_scroll_indication(
+            max_length.saturating_sub(pin_indication_len + activity_indication_len),
+        );
+        let indications: Vec<_> = [scroll_indication, activity_indication, pin_indication]
+            .into_iter()
+            .flatten()
+            .collect();
+        if indications.is_empty() {
+            return None;
+        }
+        Some(indications.into_iter().fold(
+            (vec![], 0),
+            |(mut title, title_len), (mut indication, indication_len)| {
+                title.append(&mut indication);
+                (title, title_len + indication_len)
+            },
+        ))
+    }
+    fn render_activity_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
+        if self.hidden_panes_with_activity == 0 {
+            return None;
+        }
+        let full_indication = if self.hidden_panes_with_activity == 1 {
+            String::from(" ACTIVITY IN 1 HIDDEN PANE ")
+        } else {
+            format!(
+                " ACTIVITY IN {} HIDDEN PANES ",
+                self.hidden_panes_with_activity
+            )
+        };
+        let short_indication = format!(" ACTIVITY: {} ", self.hidden_panes_with_activity);
+        if full_indication.width() <= max_length {
+            Some((
+                foreground_color(&full_indication, self.color),
+                full_indication.width(),
+            ))
+        } else if short_indication.width() <= max_length {
+            Some((
+                foreground_color(&short_indication, self.color),
+                short_indication.width(),
+            ))
+        } else {
+            None
+        }
+    }
+    fn render_pin_indication(&self, max_length: usize) -> Option<(Vec<TerminalCharacter>, usize)> {
+        if !self.show_pin_indication {
+            return None;
+        }
+        let indication = if self.pane_is_pinned {
+            PINNED_INDICATION
+        } else {
+            UNPINNED_INDICATION
+        };
+        let indication_len = indication.width();
+        if indication_len <= max_length {
+            Some((foreground_color(indication, self.color), indication_len))
+        } else {
+            None
+        }
+    }
+    fn render_scroll_indication(
+        &self,
+        max_length: usize,
+    ) -> Option<(Vec<TerminalCharacter>, usize)> {
         if self.scroll_position.0 > 0 || self.scroll_position.1 > 0 {
             let prefix = " SCROLL: ";
             let full_indication =
@@ -623,11 +767,26 @@ impl PaneFrame {
     }
     fn title_line_without_middle(&self) -> Vec<TerminalCharacter> {
         let total_title_length = self.geom.cols.saturating_sub(2); // 2 for the left and right corners
-        let left_side = self.render_title_left_side(total_title_length);
-        let right_side = left_side.as_ref().and_then(|(_left_side, left_side_len)| {
-            let space_left = total_title_length.saturating_sub(*left_side_len + 1); // 1 for a middle separator
-            self.render_title_right_side(space_left)
-        });
+
+        // the pin indication of floating panes takes precedence over the title, so that it is
+        // always displayed where it can be clicked
+        let reserved_for_pin_indication = if self.show_pin_indication {
+            PINNED_INDICATION.width() + 1 // 1 for a middle separator
+        } else {
+            0
+        };
+        let left_side = self
+            .render_title_left_side(total_title_length.saturating_sub(reserved_for_pin_indication));
+        let space_left = match &left_side {
+            Some((_left_side, left_side_len)) => {
+                total_title_length.saturating_sub(*left_side_len + 1) // 1 for a middle separator
+            },
+            None if self.show_pin_indication || self.hidden_panes_with_activity > 0 => {
+                total_title_length
+            },
+            None => 0,
+        };
+        let right_side = self.render_title_right_side(space_left);
         match (left_side, right_side) {
             (Some((left_side, left_side_len)), Some((right_side, right_side_len))) => {
                 self.two_part_title_line(left_side, &left_side_len, right_side, &right_side_len)
@@ -635,7 +794,10 @@ impl PaneFrame {
             (Some((left_side, left_side_len)), None) => {
                 self.left_only_title_line(left_side, &left_side_len)
             },
-            _ => self.empty_title_line(),
+            (None, Some((right_side, right_side_len))) => {
+                self.two_part_title_line(vec![], &0, right_side, &right_side_len)
+            },
+            (None, None) => self.empty_title_line(),
         }
     }
     fn render_title(&self) -> Result<Vec<TerminalCharacter>> {
@@ -5,9 +5,8 @@ use crate::ui::boundaries::Boundaries;
 use crate::ui::pane_boundaries_frame::FrameParams;
 use crate::ClientId;
 use std::collections::HashMap;
-use zellij_utils::data::{
-    client_id_to_colors, single_client_color, InputMode, PaletteColor, Style,
-};
+use zellij_utils::consts::SET_TERMINAL_TITLE;
+use zellij_utils::data::{client_id_to_colors, InputMode, PaletteColor, Style};
 use zellij_utils::errors::prelude::*;
 pub struct PaneContentsAndUi<'a> {
     pane: &'a mut Box<dyn Pane>,
@@ -19,9 +18,12 @@ pub struct PaneContentsAndUi<'a> {
     pane_is_stacked_under: bool,
     pane_is_stacked_over: bool,
     should_draw_pane_frames: bool,
+    pane_is_pinned: bool,
+    hidden_panes_with_activity: usize,
 }
 
 impl<'a> PaneContentsAndUi<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pane: &'a mut Box<dyn Pane>,
         output: &'a mut Output,
@@ -32,6 +34,8 @@ impl<'a> PaneContentsAndUi<'a> {
         pane_is_stacked_under: bool,
         pane_is_stacked_over: bool,
         should_draw_pane_frames: bool,
+        pane_is_pinned: bool,
+        hidden_panes_with_activity: usize,
     ) -> Self {
         let mut focused_clients: Vec<ClientId> = active_panes
             .iter()
@@ -49,6 +53,8 @@ impl<'a> PaneContentsAndUi<'a> {
             pane_is_stacked_under,
             pane_is_stacked_over,
             should_draw_pane_frames,
+            pane_is_pinned,
+            hidden_panes_with_activity,
         }
     }
     pub fn render_pane_contents_to_multiple_clients(
@@ -155,12 +161,13 @@ impl<'a> PaneContentsAndUi<'a> {
         &mut self,
         client_id: ClientId,
         client_mode: InputMode,
+        tab_name: &str,
         previous_title: &mut Option<String>,
     ) {
-        if !self.focused_clients.contains(&client_id) {
+        if !self.focused_clients.contains(&client_id) || SET_TERMINAL_TITLE.get() == Some(&false) {
             return;
         }
-        let vte_output = self.pane.render_terminal_title(client_mode);
+        let vte_output = self.pane.render_terminal_title(client_mode, tab_name);
         if let Some(previous_title) = previous_title {
             if *previous_title == vte_output {
                 return;
@@ -188,6 +195,8 @@ impl<'a> PaneContentsAndUi<'a> {
         let pane_focused_for_differet_client = !other_focused_clients.is_empty();
 
         let frame_color = self.frame_color(client_id, client_mode, session_is_mirrored);
+        let show_pin_indication =
+            pin_indication_is_visible(self.z_index.is_some(), self.pane_is_pinned, client_mode);
         let focused_client = if pane_focused_for_client_id {
             Some(client_id)
         } else if pane_focused_for_differet_client {
@@ -206,6 +215,9 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_stacked_over: self.pane_is_stacked_over,
                 pane_is_stacked_under: self.pane_is_stacked_under,
                 should_draw_pane_frames: self.should_draw_pane_frames,
+                show_pin_indication,
+                pane_is_pinned: self.pane_is_pinned,
+                hidden_panes_with_activity: self.hidden_panes_with_activity,
             }
         } else {
             FrameParams {
@@ -218,6 +230,9 @@ impl<'a> PaneContentsAndUi<'a> {
                 pane_is_stacked_over: self.pane_is_stacked_over,
                 pane_is_stacked_under: self.pane_is_stacked_under,
                 should_draw_pane_frames: self.should_draw_pane_frames,
+                show_pin_indication,
+                pane_is_pinned: self.pane_is_pinned,
+                hidden_panes_with_activity: self.hidden_panes_with_activity,
             }
         };
 
@@ -259,17 +274,30 @@ impl<'a> PaneContentsAndUi<'a> {
             match mode {
                 InputMode::Normal | InputMode::Locked => {
                     if session_is_mirrored || !self.multiple_users_exist_in_session {
-                        let colors = single_client_color(self.style.colors); // mirrored sessions only have one focused color
-                        Some(colors.0)
+                        // mirrored sessions only have one focused color
+                        Some(self.style.styling.frame_selected.base)
                     } else {
                         let colors = client_id_to_colors(client_id, self.style.colors);
                         colors.map(|colors| colors.0)
                     }
                 },
-                _ => Some(self.style.colors.orange),
+                _ => Some(self.style.styling.frame_highlight.base),
             }
         } else {
-            None
+            self.style
+                .styling
+                .frame_unselected
+                .map(|frame_unselected| frame_unselected.base)
         }
     }
 }
+
+/// Floating panes always show their pin indication once pinned, unpinned ones only show it (as a
+/// clickable toggle) while the client is in pane mode so that it doesn't clutter their frames.
+pub(crate) fn pin_indication_is_visible(
+    pane_is_floating: bool,
+    pane_is_pinned: bool,
+    client_mode: InputMode,
+) -> bool {
+    pane_is_floating && (pane_is_pinned || client_mode == InputMode::Pane)
+}
@@ -49,3 +49,80 @@ fn get_cwd() {
         pid
     );
 }
+
+#[test]
+fn get_terminal_processes() {
+    let test_terminal = TestTerminal::new();
+    let test_termios =
+        termios::tcgetattr(test_terminal.slave()).expect("Could not configure the termios");
+
+    let server = ServerOsInputOutput {
+        orig_termios: Arc::new(Mutex::new(test_termios)),
+        client_senders: Arc::default(),
+        terminal_id_to_raw_fd: Arc::default(),
+        cached_resizes: Arc::default(),
+    };
+
+    let pid = nix::unistd::getpid();
+    let mut terminal_id_to_pid = HashMap::new();
+    terminal_id_to_pid.insert(1, pid);
+    let terminal_processes = server.get_terminal_processes(&terminal_id_to_pid);
+    let processes_of_terminal = terminal_processes
+        .get(&1)
+        .expect("Get processes of terminal running in the test process");
+    assert!(
+        processes_of_terminal
+            .child_pids
+            .contains(&(pid.as_raw() as u32)),
+        "Terminal processes include PID {}",
+        pid
+    );
+    assert!(
+        processes_of_terminal.foreground_process.is_some(),
+        "Foreground process falls back to PID {}",
+        pid
+    );
+}
+
+#[test]
+fn spawned_terminal_has_its_pane_id_in_the_environment() {
+    let test_terminal = TestTerminal::new();
+    let test_termios =
+        termios::tcgetattr(test_terminal.slave()).expect("Could not configure the termios");
+    let temp_dir = tempfile::tempdir().expect("Could not create a temp dir");
+    let env_file = temp_dir.path().join("pane_id");
+    let command = RunCommand {
+        command: PathBuf::from("sh"),
+        args: vec![
+            "-c".to_owned(),
+            format!(
+                "printf %s \"${}\" > '{}'",
+                PANE_ID_ENV_KEY,
+                env_file.display()
+            ),
+        ],
+        ..Default::default()
+    };
+    let (exit_sender, exit_receiver) = std::sync::mpsc::channel();
+    let quit_cb = Box::new(
+        move |pane_id: PaneId, exit_status: Option<i32>, _: RunCommand| {
+            let _ = exit_sender.send((pane_id, exit_status));
+        },
+    );
+    let terminal_id = 7;
+    let (primary_fd, _child_pid) =
+        handle_terminal(command, None, test_termios, quit_cb, terminal_id)
+            .expect("Could not spawn the command");
+    let (pane_id, exit_status) = exit_receiver
+        .recv_timeout(std::time::Duration::from_secs(10))
+        .expect("Command exited");
+    let _ = close(primary_fd);
+    assert_eq!(pane_id, PaneId::Terminal(terminal_id));
+    assert_eq!(exit_status, Some(0));
+    assert_eq!(
+        std::fs::read_to_string(&env_file).expect("Could not read the written environment"),
+        terminal_id.to_string(),
+        "{} is set to the id of the pane",
+        PANE_ID_ENV_KEY
+    );
+}
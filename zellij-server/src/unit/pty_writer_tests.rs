@@ -0,0 +1,177 @@
+use super::write_in_chunks;
+use crate::panes::PaneId;
+use crate::{
+    os_input_output::{AsyncReader, Pid, ServerOsApi},
+    ClientId,
+};
+use std::os::unix::io::RawFd;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use zellij_utils::data::Palette;
+use zellij_utils::errors::prelude::*;
+use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::interprocess::local_socket::LocalSocketStream;
+use zellij_utils::ipc::{ClientToServerMsg, IpcReceiverWithContext, ServerToClientMsg};
+use zellij_utils::nix::errno::Errno;
+
+#[derive(Clone, Default)]
+struct FakeInputOutput {
+    tty_stdin_bytes: Arc<Mutex<Vec<u8>>>,
+    // the amount of bytes written when tcdrain was called
+    drained_at: Arc<Mutex<Vec<usize>>>,
+    // like a pty whose buffer is almost full, accepts at most this many bytes at a time
+    max_bytes_per_write: usize,
+    // the errors to fail the next writes with, eg. when the pty is full
+    write_errors: Arc<Mutex<Vec<Errno>>>,
+}
+
+impl FakeInputOutput {
+    fn new(max_bytes_per_write: usize) -> Self {
+        FakeInputOutput {
+            max_bytes_per_write,
+            ..Default::default()
+        }
+    }
+}
+
+impl ServerOsApi for FakeInputOutput {
+    fn set_terminal_size_using_terminal_id(
+        &self,
+        _terminal_id: u32,
+        _cols: u16,
+        _rows: u16,
+        _width_in_pixels: Option<u16>,
+        _height_in_pixels: Option<u16>,
+    ) -> Result<()> {
+        unimplemented!()
+    }
+    fn spawn_terminal(
+        &self,
+        _file_to_open: TerminalAction,
+        _quit_db: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>,
+        _default_editor: Option<PathBuf>,
+    ) -> Result<(u32, RawFd, RawFd)> {
+        unimplemented!()
+    }
+    fn read_from_tty_stdout(&self, _fd: RawFd, _buf: &mut [u8]) -> Result<usize> {
+        unimplemented!()
+    }
+    fn async_file_reader(&self, _fd: RawFd) -> Box<dyn AsyncReader> {
+        unimplemented!()
+    }
+    fn write_to_tty_stdin(&self, _id: u32, buf: &[u8]) -> Result<usize> {
+        if let Some(error) = self.write_errors.lock().unwrap().pop() {
+            return Err(error).context("failed to write to fake tty");
+        }
+        let count = std::cmp::min(buf.len(), self.max_bytes_per_write);
+        self.tty_stdin_bytes
+            .lock()
+            .unwrap()
+            .extend_from_slice(&buf[..count]);
+        Ok(count)
+    }
+    fn tcdrain(&self, _id: u32) -> Result<()> {
+        let written = self.tty_stdin_bytes.lock().unwrap().len();
+        self.drained_at.lock().unwrap().push(written);
+        Ok(())
+    }
+    fn kill(&self, _pid: Pid) -> Result<()> {
+        unimplemented!()
+    }
+    fn force_kill(&self, _pid: Pid) -> Result<()> {
+        unimplemented!()
+    }
+    fn box_clone(&self) -> Box<dyn ServerOsApi> {
+        Box::new((*self).clone())
+    }
+    fn send_to_client(&self, _client_id: ClientId, _msg: ServerToClientMsg) -> Result<()> {
+        unimplemented!()
+    }
+    fn new_client(
+        &mut self,
+        _client_id: ClientId,
+        _stream: LocalSocketStream,
+    ) -> Result<IpcReceiverWithContext<ClientToServerMsg>> {
+        unimplemented!()
+    }
+    fn remove_client(&mut self, _client_id: ClientId) -> Result<()> {
+        unimplemented!()
+    }
+    fn load_palette(&self) -> Palette {
+        unimplemented!()
+    }
+    fn get_cwd(&self, _pid: Pid) -> Option<PathBuf> {
+        unimplemented!()
+    }
+    fn write_to_file(&mut self, _buf: String, _name: Option<String>) -> Result<()> {
+        unimplemented!()
+    }
+    fn re_run_command_in_terminal(
+        &self,
+        _terminal_id: u32,
+        _run_command: RunCommand,
+        _quit_cb: Box<dyn Fn(PaneId, Option<i32>, RunCommand) + Send>, // u32 is the exit status
+    ) -> Result<(RawFd, RawFd)> {
+        unimplemented!()
+    }
+    fn clear_terminal_id(&self, _terminal_id: u32) -> Result<()> {
+        unimplemented!()
+    }
+}
+
+fn multi_megabyte_paste() -> Vec<u8> {
+    "a line of pasted text that is long enough to wrap in most panes, number "
+        .bytes()
+        .chain(b"0123456789\n".iter().copied())
+        .cycle()
+        .take(3 * 1024 * 1024 + 17)
+        .collect()
+}
+
+#[test]
+fn multi_megabyte_write_is_drained_in_chunks() {
+    let os_input = FakeInputOutput::new(usize::MAX);
+    let paste = multi_megabyte_paste();
+    write_in_chunks(&os_input, 1, &paste).unwrap();
+    assert!(
+        *os_input.tty_stdin_bytes.lock().unwrap() == paste,
+        "all pasted bytes were written in order"
+    );
+    let drained_at = os_input.drained_at.lock().unwrap().clone();
+    assert_eq!(drained_at.len(), 769, "drained after every chunk");
+    assert_eq!(drained_at[0], 4096);
+    assert_eq!(drained_at[1], 8192);
+    assert_eq!(drained_at.last(), Some(&paste.len()));
+}
+
+#[test]
+fn multi_megabyte_write_survives_partial_writes() {
+    let os_input = FakeInputOutput::new(1000);
+    let paste = multi_megabyte_paste();
+    write_in_chunks(&os_input, 1, &paste).unwrap();
+    assert!(
+        *os_input.tty_stdin_bytes.lock().unwrap() == paste,
+        "all pasted bytes were written in order"
+    );
+    assert_eq!(
+        os_input.drained_at.lock().unwrap()[0],
+        4096,
+        "chunk was fully written before draining"
+    );
+}
+
+#[test]
+fn write_is_retried_while_pty_is_full() {
+    let os_input = FakeInputOutput::new(usize::MAX);
+    *os_input.write_errors.lock().unwrap() = vec![Errno::EAGAIN, Errno::EINTR, Errno::EAGAIN];
+    write_in_chunks(&os_input, 1, b"pasted text").unwrap();
+    assert_eq!(*os_input.tty_stdin_bytes.lock().unwrap(), b"pasted text");
+}
+
+#[test]
+fn write_fails_on_other_errors() {
+    let os_input = FakeInputOutput::new(usize::MAX);
+    *os_input.write_errors.lock().unwrap() = vec![Errno::EIO];
+    assert!(write_in_chunks(&os_input, 1, b"pasted text").is_err());
+    assert!(os_input.tty_stdin_bytes.lock().unwrap().is_empty());
+}
@@ -2,7 +2,7 @@ use super::{screen_thread_main, CopyOptions, Screen, ScreenInstruction};
 use crate::panes::PaneId;
 use crate::{
     channels::SenderWithContext,
-    os_input_output::{AsyncReader, Pid, ServerOsApi},
+    os_input_output::{AsyncReader, Pid, ServerOsApi, TerminalProcesses},
     route::route_action,
     thread_bus::Bus,
     ClientId, ServerInstruction, SessionMetaData, ThreadSenders,
@@ -10,10 +10,11 @@ use crate::{
 use insta::assert_snapshot;
 use std::path::PathBuf;
 use zellij_utils::cli::CliAction;
-use zellij_utils::data::{Event, Resize};
+use zellij_utils::data::{Event, Resize, SessionInfo};
 use zellij_utils::errors::{prelude::*, ErrorContext};
 use zellij_utils::input::actions::Action;
 use zellij_utils::input::command::{RunCommand, TerminalAction};
+use zellij_utils::input::keybinds::Keybinds;
 use zellij_utils::input::layout::{
     FloatingPaneLayout, Layout, Run, RunPlugin, RunPluginLocation, SplitDirection, TiledPaneLayout,
 };
@@ -32,16 +33,19 @@ use zellij_utils::ipc::PixelDimensions;
 
 use zellij_utils::{
     channels::{self, ChannelWithContext, Receiver},
-    data::{Direction, InputMode, ModeInfo, Palette, PluginCapabilities},
+    data::{
+        Direction, InputMode, ModeInfo, Palette, PaletteColor, PluginCapabilities, Style, ThemeHue,
+    },
     interprocess::local_socket::LocalSocketStream,
     ipc::{ClientAttributes, ClientToServerMsg, ServerToClientMsg},
+    shared::default_palette,
 };
 
 use crate::panes::grid::Grid;
 use crate::panes::link_handler::LinkHandler;
 use crate::panes::sixel::SixelImageStore;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 use zellij_utils::vte;
 
@@ -232,7 +236,10 @@ fn create_new_screen(size: Size) -> Screen {
     let draw_pane_frames = false;
     let auto_layout = true;
     let session_is_mirrored = true;
+    let tab_focus_is_mirrored = true;
     let copy_options = CopyOptions::default();
+    let session_serialization = true;
+    let serialize_pane_scrollback = false;
 
     let debug = false;
     let screen = Screen::new(
@@ -243,7 +250,11 @@ fn create_new_screen(size: Size) -> Screen {
         draw_pane_frames,
         auto_layout,
         session_is_mirrored,
+        tab_focus_is_mirrored,
         copy_options,
+        None,
+        session_serialization,
+        serialize_pane_scrollback,
         debug,
     );
     screen
@@ -398,6 +409,8 @@ impl MockScreen {
             capabilities: self.session_metadata.capabilities.clone(),
             client_attributes: self.session_metadata.client_attributes.clone(),
             default_shell: self.session_metadata.default_shell.clone(),
+            config_options: self.session_metadata.config_options.clone(),
+            config_file_path: self.session_metadata.config_file_path.clone(),
             screen_thread: None,
             pty_thread: None,
             plugin_thread: None,
@@ -454,6 +467,8 @@ impl MockScreen {
             capabilities,
             default_shell: None,
             client_attributes: client_attributes.clone(),
+            config_options: Box::new(Options::default()),
+            config_file_path: None,
             screen_thread: None,
             pty_thread: None,
             plugin_thread: None,
@@ -552,6 +567,147 @@ fn open_new_tab() {
     );
 }
 
+#[test]
+fn rebind_keys_updates_keybinds_in_mode_info() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen.change_mode(ModeInfo::default(), 1).expect("TEST");
+    let keybinds = Keybinds::from_kdl_string(
+        r#"keybinds { pane { bind "z" { SwitchToMode "Resize"; }; }; }"#,
+        Keybinds::default(),
+        &Options::default(),
+    )
+    .expect("TEST");
+    screen.rebind_keys(keybinds.clone()).expect("TEST");
+
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().keybinds,
+        keybinds.to_keybinds_vec(),
+        "Client mode info has the new keybinds"
+    );
+    assert_eq!(
+        screen.default_mode_info.keybinds,
+        keybinds.to_keybinds_vec(),
+        "Default mode info has the new keybinds"
+    );
+}
+
+#[test]
+fn reconfigure_updates_style_and_pane_frames() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen.change_mode(ModeInfo::default(), 1).expect("TEST");
+    let style = Style {
+        rounded_corners: true,
+        ..Default::default()
+    };
+    let capabilities = PluginCapabilities { arrow_fonts: true };
+    screen
+        .reconfigure(Keybinds::default(), style, capabilities, Some(true))
+        .expect("TEST");
+
+    let mode_info = screen.mode_info.get(&1).unwrap();
+    assert_eq!(mode_info.style, style, "Client mode info has the new style");
+    assert_eq!(
+        mode_info.capabilities, capabilities,
+        "Client mode info has the new capabilities"
+    );
+    assert_eq!(screen.style, style, "Screen has the new style");
+    assert!(screen.draw_pane_frames, "Pane frames were turned on");
+}
+
+#[test]
+fn default_palette_blends_in_terminal_emulator_colors() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    screen.change_mode(ModeInfo::default(), 1).expect("TEST");
+    let style = Style {
+        colors: default_palette(),
+        ..Default::default()
+    };
+    screen
+        .reconfigure(
+            Keybinds::default(),
+            style,
+            PluginCapabilities::default(),
+            None,
+        )
+        .expect("TEST");
+    screen.update_terminal_background_color("rgb:ffff/ffff/ffff".to_owned());
+    screen.update_terminal_color_registers(vec![(1, "rgb:cccc/0000/0000".to_owned())]);
+    screen.apply_terminal_emulator_colors().expect("TEST");
+
+    assert_ne!(
+        screen.style.colors,
+        default_palette(),
+        "Palette was updated"
+    );
+    assert_eq!(
+        screen.style.colors.theme_hue,
+        ThemeHue::Light,
+        "Theme hue follows the terminal background"
+    );
+    assert_ne!(
+        screen.style.colors.red,
+        default_palette().red,
+        "Color registers replace the palette colors"
+    );
+    assert_eq!(
+        screen.mode_info.get(&1).unwrap().style,
+        screen.style,
+        "Client mode info has the blended style"
+    );
+    assert_eq!(
+        screen.style.colors.green,
+        default_palette().green,
+        "Colors the terminal did not report are kept"
+    );
+}
+
+#[test]
+fn configured_theme_ignores_terminal_emulator_colors() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    let mut colors = default_palette();
+    colors.bg = PaletteColor::Rgb((40, 42, 54));
+    let style = Style {
+        colors,
+        ..Default::default()
+    };
+    screen
+        .reconfigure(
+            Keybinds::default(),
+            style,
+            PluginCapabilities::default(),
+            None,
+        )
+        .expect("TEST");
+    screen.update_terminal_background_color("rgb:ffff/ffff/ffff".to_owned());
+    screen.apply_terminal_emulator_colors().expect("TEST");
+
+    assert_eq!(screen.style, style, "Configured theme is kept");
+}
+
 #[test]
 pub fn switch_to_prev_tab() {
     let size = Size {
@@ -591,6 +747,68 @@ pub fn switch_to_next_tab() {
     );
 }
 
+#[test]
+pub fn switch_tab_in_mirrored_session_moves_all_clients() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.add_client(2).expect("TEST");
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to previous tab"
+    );
+    assert_eq!(
+        screen.get_active_tab(2).unwrap().position,
+        0,
+        "Other client followed to the previous tab"
+    );
+}
+
+#[test]
+pub fn switch_tab_without_mirrored_tab_focus_moves_only_one_client() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    screen.tab_focus_is_mirrored = false;
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    screen.add_client(2).expect("TEST");
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        0,
+        "Active tab switched to previous tab"
+    );
+    assert_eq!(
+        screen.get_active_tab(2).unwrap().position,
+        1,
+        "Other client stayed on its own tab"
+    );
+    let tab_infos = screen.generate_and_report_tab_state().expect("TEST");
+    assert_eq!(
+        tab_infos[0].other_focused_clients,
+        vec![1],
+        "First tab is focused by the first client"
+    );
+    assert_eq!(
+        tab_infos[1].other_focused_clients,
+        vec![2],
+        "Second tab is focused by the second client"
+    );
+}
+
 #[test]
 pub fn switch_to_tab_name() {
     let size = Size {
@@ -667,6 +885,111 @@ pub fn close_the_middle_tab() {
     );
 }
 
+#[test]
+fn close_an_unfocused_tab_by_position() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    new_tab(&mut screen, 2, 2);
+    new_tab(&mut screen, 3, 3);
+    screen.close_tab_at_position(0).expect("TEST");
+
+    assert_eq!(screen.tabs.len(), 2, "Two tabs left");
+    let active_tab = screen.get_active_tab(1).unwrap();
+    assert_eq!(active_tab.index, 3, "Focused tab stays focused");
+    assert_eq!(
+        active_tab.position, 1,
+        "Tabs after the closed one shifted over"
+    );
+}
+
+#[test]
+fn move_pane_to_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 1);
+    {
+        let active_tab = screen.get_active_tab_mut(1).unwrap();
+        active_tab
+            .new_pane(PaneId::Terminal(2), None, None, None, Some(1))
+            .unwrap();
+    }
+    new_tab(&mut screen, 3, 2);
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+    screen.move_pane_to_tab(2, 1).expect("TEST");
+
+    let active_tab = screen.get_active_tab(1).unwrap();
+    assert_eq!(
+        active_tab.position, 1,
+        "Active tab switched to the target tab"
+    );
+    assert!(
+        active_tab.get_all_pane_ids().contains(&PaneId::Terminal(2)),
+        "Pane moved to the target tab"
+    );
+    assert_eq!(
+        screen.get_indexed_tab_mut(1).unwrap().get_all_pane_ids(),
+        vec![PaneId::Terminal(1)],
+        "Pane removed from its original tab"
+    );
+}
+
+#[test]
+fn move_active_tab() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    new_tab(&mut screen, 3, 2);
+    screen.go_to_tab(1, 1).expect("TEST");
+    screen.move_active_tab(Direction::Right, 1).expect("TEST");
+
+    let tab_positions = |screen: &Screen| -> Vec<(usize, usize)> {
+        screen
+            .tabs
+            .values()
+            .map(|tab| (tab.index, tab.position))
+            .collect()
+    };
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 1), (1, 0), (2, 2)],
+        "Active tab moved right"
+    );
+    assert_eq!(
+        screen.get_active_tab(1).unwrap().position,
+        1,
+        "Moved tab stays active"
+    );
+
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    screen.move_active_tab(Direction::Left, 1).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 2), (1, 0), (2, 1)],
+        "Active tab wrapped around to the last position"
+    );
+
+    screen.move_tab_to_position(0, 2).expect("TEST");
+    assert_eq!(
+        tab_positions(&screen),
+        vec![(0, 1), (1, 2), (2, 0)],
+        "Tabs in between shifted over"
+    );
+}
+
 #[test]
 fn move_focus_left_at_left_screen_edge_changes_tab() {
     let size = Size {
@@ -1021,6 +1344,100 @@ fn attach_after_first_tab_closed() {
     screen.add_client(1).expect("TEST");
 }
 
+#[test]
+fn toggle_scratchpad_hides_shows_and_follows_across_tabs() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    new_tab(&mut screen, 2, 1);
+    let scratchpad_pane_id = PaneId::Terminal(3);
+    screen.scratchpad_pane_id = Some(scratchpad_pane_id);
+    screen
+        .get_active_tab_mut(1)
+        .unwrap()
+        .new_pane(scratchpad_pane_id, None, Some(true), None, Some(1))
+        .unwrap();
+
+    screen.toggle_scratchpad(1, None).expect("TEST");
+    assert!(
+        !screen
+            .get_active_tab(1)
+            .unwrap()
+            .has_non_suppressed_pane_with_pid(&scratchpad_pane_id),
+        "scratchpad hidden"
+    );
+    screen.toggle_scratchpad(1, None).expect("TEST");
+    assert!(
+        screen
+            .get_active_tab(1)
+            .unwrap()
+            .has_non_suppressed_pane_with_pid(&scratchpad_pane_id),
+        "scratchpad shown again"
+    );
+
+    screen.switch_tab_prev(None, true, 1).expect("TEST");
+    screen.toggle_scratchpad(1, None).expect("TEST");
+    let active_tab = screen.get_active_tab(1).unwrap();
+    assert_eq!(active_tab.index, 0, "switched to first tab");
+    assert!(
+        active_tab.has_non_suppressed_pane_with_pid(&scratchpad_pane_id),
+        "scratchpad moved to the active tab"
+    );
+    assert!(
+        active_tab.are_floating_panes_visible(),
+        "scratchpad shown as a floating pane"
+    );
+    assert!(
+        !screen
+            .tabs
+            .get(&1)
+            .unwrap()
+            .has_pane_with_pid(&scratchpad_pane_id),
+        "scratchpad removed from its previous tab"
+    );
+}
+
+#[test]
+fn toggle_scratchpad_shows_it_in_the_middle_of_the_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+
+    new_tab(&mut screen, 1, 0);
+    let floating_pane_id = PaneId::Terminal(2);
+    let scratchpad_pane_id = PaneId::Terminal(3);
+    screen.scratchpad_pane_id = Some(scratchpad_pane_id);
+    let active_tab = screen.get_active_tab_mut(1).unwrap();
+    active_tab
+        .new_pane(floating_pane_id, None, Some(true), None, Some(1))
+        .unwrap();
+    // the middle is taken, so the scratchpad is placed elsewhere at first
+    active_tab
+        .new_pane(scratchpad_pane_id, None, Some(true), None, Some(1))
+        .unwrap();
+
+    screen.toggle_scratchpad(1, None).expect("TEST");
+    screen.toggle_scratchpad(1, None).expect("TEST");
+    let scratchpad = screen
+        .get_active_tab(1)
+        .unwrap()
+        .get_active_pane(1)
+        .unwrap();
+    assert_eq!(scratchpad.pid(), scratchpad_pane_id);
+    let geom = scratchpad.position_and_size();
+    assert_eq!(
+        (geom.x, geom.y, geom.cols.as_usize(), geom.rows.as_usize()),
+        (30, 5, 60, 10),
+        "scratchpad centered over the other floating pane"
+    );
+}
+
 // Following are tests for sending CLI actions
 // these tests are only partially relevant to Screen
 // and are included here for two reasons:
@@ -1047,6 +1464,7 @@ pub fn send_cli_write_chars_action_to_screen() {
     );
     let cli_action = CliAction::WriteChars {
         chars: "input from the cli".into(),
+        pane_id: None,
     };
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1073,6 +1491,7 @@ pub fn send_cli_write_action_to_screen() {
     );
     let cli_action = CliAction::Write {
         bytes: vec![102, 111, 111],
+        pane_id: None,
     };
     send_cli_action_to_server(&session_metadata, cli_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1081,23 +1500,66 @@ pub fn send_cli_write_action_to_screen() {
 }
 
 #[test]
-pub fn send_cli_resize_action_to_screen() {
-    let size = Size { cols: 80, rows: 20 };
+pub fn send_cli_write_action_with_pane_id_to_screen() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
     initial_layout.children_split_direction = SplitDirection::Vertical;
     initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
     let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
     let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
-    let received_server_instructions = Arc::new(Mutex::new(vec![]));
-    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
     let pty_writer_thread = log_actions_in_thread!(
-        received_server_instructions,
-        ServerInstruction::KillSession,
-        server_receiver
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
     );
-    let resize_cli_action = CliAction::Resize {
+    let cli_action = CliAction::WriteChars {
+        chars: "foo".into(),
+        pane_id: Some(1),
+    };
+    send_cli_action_to_server(&session_metadata, cli_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let writes: Vec<(Vec<u8>, u32)> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, terminal_id) => Some((bytes.clone(), *terminal_id)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        writes,
+        vec![(vec![102, 111, 111], 1)],
+        "Wrote to the pane with the given id rather than the focused one"
+    );
+}
+
+#[test]
+pub fn send_cli_resize_action_to_screen() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let pty_writer_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let resize_cli_action = CliAction::Resize {
         resize: Resize::Increase,
         direction: Some(Direction::Left),
     };
@@ -1147,6 +1609,59 @@ pub fn send_cli_focus_next_pane_action() {
     assert_snapshot!(format!("{}", snapshot_count));
 }
 
+#[test]
+pub fn send_cli_focus_pane_with_id_action() {
+    let size = Size { cols: 80, rows: 20 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_instruction = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let focus_pane_action = CliAction::FocusPane {
+        id: 1,
+        plugin: false,
+        float_if_hidden: false,
+    };
+    send_cli_action_to_server(&session_metadata, focus_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    let focus_missing_pane_action = CliAction::FocusPane {
+        id: 5,
+        plugin: false,
+        float_if_hidden: false,
+    };
+    send_cli_action_to_server(&session_metadata, focus_missing_pane_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_instruction, screen_thread]);
+    let received_server_instructions = received_server_instructions.lock().unwrap();
+    let snapshots = take_snapshots_and_cursor_coordinates_from_render_events(
+        received_server_instructions.iter(),
+        size,
+    );
+    let (cursor_coordinates, _snapshot) = snapshots.last().unwrap();
+    assert!(
+        matches!(cursor_coordinates, Some((x, _y)) if *x >= 40),
+        "Focused the pane on the right, cursor is at {:?}",
+        cursor_coordinates
+    );
+    let reported_missing_pane = received_server_instructions.iter().any(|instruction| {
+        matches!(instruction, ServerInstruction::Log(lines, log_client_id)
+            if *log_client_id == client_id && lines == &vec!["No pane with id Terminal(5)".to_owned()])
+    });
+    assert!(
+        reported_missing_pane,
+        "Reported the missing pane to the cli"
+    );
+}
+
 #[test]
 pub fn send_cli_focus_previous_pane_action() {
     let size = Size { cols: 80, rows: 20 };
@@ -1304,6 +1819,7 @@ pub fn send_cli_dump_screen_action() {
     let cli_action = CliAction::DumpScreen {
         path: PathBuf::from("/tmp/foo"),
         full: true,
+        ansi: false,
     };
     let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
         0,
@@ -1828,6 +2344,7 @@ pub fn send_cli_toggle_active_tab_sync_action() {
     let cli_toggle_active_tab_sync_action = CliAction::ToggleActiveSyncTab;
     let cli_write_action = CliAction::Write {
         bytes: vec![102, 111, 111],
+        pane_id: None,
     };
     send_cli_action_to_server(
         &session_metadata,
@@ -1840,6 +2357,121 @@ pub fn send_cli_toggle_active_tab_sync_action() {
     assert_snapshot!(format!("{:?}", *received_pty_instructions.lock().unwrap()));
 }
 
+#[test]
+pub fn send_cli_toggle_active_pane_sync_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+    ];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_write_action = CliAction::Write {
+        bytes: vec![102, 111, 111],
+        pane_id: None,
+    };
+    // two of the three panes are added to the sync group
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ToggleActivePaneSync,
+        client_id,
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::FocusNextPane, client_id);
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::ToggleActivePaneSync,
+        client_id,
+    );
+    send_cli_action_to_server(&session_metadata, cli_write_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let written_pane_ids: Vec<u32> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, pane_id) if bytes == &vec![102, 111, 111] => {
+                Some(*pane_id)
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_pane_ids.len(),
+        2,
+        "input written to both panes in the sync group"
+    );
+}
+
+#[test]
+pub fn send_cli_sync_grouped_panes_action() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+        TiledPaneLayout::default(),
+    ];
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let cli_write_action = CliAction::Write {
+        bytes: vec![102, 111, 111],
+        pane_id: None,
+    };
+    // two of the three panes are grouped, and then the whole group is synced
+    send_cli_action_to_server(&session_metadata, CliAction::TogglePaneInGroup, client_id);
+    send_cli_action_to_server(&session_metadata, CliAction::FocusNextPane, client_id);
+    send_cli_action_to_server(&session_metadata, CliAction::TogglePaneInGroup, client_id);
+    send_cli_action_to_server(&session_metadata, CliAction::SyncGroupedPanes, client_id);
+    send_cli_action_to_server(&session_metadata, cli_write_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let written_pane_ids: Vec<u32> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, pane_id) if bytes == &vec![102, 111, 111] => {
+                Some(*pane_id)
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        written_pane_ids.len(),
+        2,
+        "input written to both grouped panes"
+    );
+}
+
 #[test]
 pub fn send_cli_new_pane_action_with_default_parameters() {
     let size = Size {
@@ -1870,6 +2502,8 @@ pub fn send_cli_new_pane_action_with_default_parameters() {
         close_on_exit: false,
         start_suspended: false,
         configuration: None,
+        in_place: false,
+        wait: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1907,6 +2541,8 @@ pub fn send_cli_new_pane_action_with_split_direction() {
         close_on_exit: false,
         start_suspended: false,
         configuration: None,
+        in_place: false,
+        wait: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -1944,6 +2580,8 @@ pub fn send_cli_new_pane_action_with_command_and_cwd() {
         close_on_exit: false,
         start_suspended: false,
         configuration: None,
+        in_place: false,
+        wait: false,
     };
     send_cli_action_to_server(&session_metadata, cli_new_pane_action, client_id);
     std::thread::sleep(std::time::Duration::from_millis(100)); // give time for actions to be
@@ -2196,71 +2834,555 @@ pub fn send_cli_close_pane_action() {
 }
 
 #[test]
-pub fn send_cli_new_tab_action_default_params() {
+pub fn send_cli_close_pane_action_with_running_process_asks_for_confirmation() {
     let size = Size { cols: 80, rows: 10 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
     initial_layout.children_split_direction = SplitDirection::Vertical;
     initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
     let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
     let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
-    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
-    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
-    let plugin_thread = log_actions_in_thread!(
-        received_plugin_instructions,
-        PluginInstruction::Exit,
-        plugin_receiver
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
     );
-    let new_tab_action = CliAction::NewTab {
-        name: None,
-        layout: None,
-        layout_dir: None,
-        cwd: None,
-    };
-    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    let mut terminal_processes = HashMap::new();
+    terminal_processes.insert(
+        0,
+        TerminalProcesses {
+            child_pids: vec![1234, 1240, 1241],
+            foreground_process: Some("cargo".to_owned()),
+            cwd: Some(PathBuf::from("/tmp")),
+            running_processes: vec!["cargo".to_owned(), "rustc".to_owned()],
+        },
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::UpdateTerminalProcesses(
+            terminal_processes,
+        ));
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![plugin_thread, screen_thread]);
-    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
-    let new_tab_action =
-        received_plugin_instructions
+    let count_closed_panes = |received_pty_instructions: &Arc<Mutex<Vec<PtyInstruction>>>| {
+        received_pty_instructions
+            .lock()
+            .unwrap()
             .iter()
-            .find(|instruction| match instruction {
-                PluginInstruction::NewTab(..) => true,
-                _ => false,
-            });
-    assert_snapshot!(format!("{:#?}", new_tab_action));
+            .filter(|instruction| {
+                matches!(instruction, PtyInstruction::ClosePane(PaneId::Terminal(0)))
+            })
+            .count()
+    };
+    send_cli_action_to_server(&session_metadata, CliAction::ClosePane, client_id);
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::WriteChars {
+            chars: "n".into(),
+            pane_id: None,
+        },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    assert_eq!(
+        count_closed_panes(&received_pty_instructions),
+        0,
+        "pane was not closed when closing it was denied"
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::ClosePane, client_id);
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::WriteChars {
+            chars: "y".into(),
+            pane_id: None,
+        },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    assert_eq!(
+        count_closed_panes(&received_pty_instructions),
+        1,
+        "pane was closed once closing it was confirmed"
+    );
 }
 
 #[test]
-pub fn send_cli_new_tab_action_with_name_and_layout() {
+pub fn close_pane_confirmation_only_takes_input_from_the_client_that_asked() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    new_tab(&mut screen, 1, 1);
+    screen.add_client(2).expect("TEST");
+    screen.terminal_processes.insert(
+        1,
+        TerminalProcesses {
+            child_pids: vec![1234],
+            foreground_process: Some("cargo".to_owned()),
+            cwd: None,
+            running_processes: vec!["cargo".to_owned()],
+        },
+    );
+    screen
+        .close_focused_pane_or_ask_to_confirm(1)
+        .expect("TEST");
+    assert!(
+        !screen
+            .handle_close_pane_confirmation_input(b"y", 2)
+            .expect("TEST"),
+        "input from another client is not consumed by the confirmation"
+    );
+    assert_eq!(
+        screen.overlay.overlay_stack.len(),
+        1,
+        "confirmation is still shown"
+    );
+    assert!(
+        screen
+            .handle_close_pane_confirmation_input(b"n", 1)
+            .expect("TEST"),
+        "input from the client that asked is consumed by the confirmation"
+    );
+    assert!(
+        screen.overlay.overlay_stack.is_empty(),
+        "confirmation was dismissed"
+    );
+}
+
+#[test]
+pub fn send_cli_respawn_pane_action() {
     let size = Size { cols: 80, rows: 10 };
     let client_id = 10; // fake client id should not appear in the screen's state
     let mut initial_layout = TiledPaneLayout::default();
     initial_layout.children_split_direction = SplitDirection::Vertical;
     initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
     let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
     let session_metadata = mock_screen.clone_session_metadata();
     let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
-    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
-    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
-    let plugin_thread = log_actions_in_thread!(
-        received_plugin_instructions,
-        PluginInstruction::Exit,
-        plugin_receiver
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
     );
-    let new_tab_action = CliAction::NewTab {
-        name: Some("my-awesome-tab-name".into()),
-        layout: Some(PathBuf::from(format!(
-            "{}/src/unit/fixtures/layout-with-three-panes.kdl",
-            env!("CARGO_MANIFEST_DIR")
-        ))),
-        layout_dir: None,
-        cwd: None,
+    let run_command = RunCommand {
+        command: PathBuf::from("tail"),
+        args: vec!["-f".to_owned(), "/tmp/foo".to_owned()],
+        hold_on_close: true,
+        ..Default::default()
     };
-    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(1),
+        run_command.clone(),
+        None,
+        None,
+    ));
     std::thread::sleep(std::time::Duration::from_millis(100));
-    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    send_cli_action_to_server(&session_metadata, CliAction::RespawnPane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    let respawned_pane =
+        received_pty_instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                PtyInstruction::ReRunCommandInPane(pane_id, run_command) => {
+                    Some((*pane_id, run_command.clone()))
+                },
+                _ => None,
+            });
+    assert_eq!(
+        respawned_pane,
+        Some((PaneId::Terminal(0), run_command)),
+        "pane respawned in place with its original command"
+    );
+}
+
+#[test]
+pub fn send_cli_reopen_closed_pane_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let run_command = RunCommand {
+        command: PathBuf::from("tail"),
+        args: vec!["-f".to_owned(), "/tmp/foo".to_owned()],
+        hold_on_close: true,
+        ..Default::default()
+    };
+    let _ = mock_screen.to_screen.send(ScreenInstruction::HoldPane(
+        PaneId::Terminal(0),
+        Some(1),
+        run_command.clone(),
+        None,
+        None,
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(&session_metadata, CliAction::ClosePane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(&session_metadata, CliAction::ReopenClosedPane, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let received_pty_instructions = received_pty_instructions.lock().unwrap();
+    let reopened_pane =
+        received_pty_instructions
+            .iter()
+            .find_map(|instruction| match instruction {
+                PtyInstruction::SpawnTerminal(
+                    Some(TerminalAction::RunCommand(run_command)),
+                    should_float,
+                    ..,
+                ) => Some((run_command.clone(), *should_float)),
+                _ => None,
+            });
+    let expected_run_command = RunCommand {
+        hold_on_start: true,
+        ..run_command
+    };
+    assert_eq!(
+        reopened_pane,
+        Some((expected_run_command, Some(false))),
+        "closed pane reopened with its command held until it is run again"
+    );
+}
+
+#[test]
+pub fn send_cli_switch_workspace_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    mock_screen.config_options.session_group = Some("work".to_owned());
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let mut session_infos = BTreeMap::new();
+    for (name, group) in [
+        ("a-other-work-session", Some("work")),
+        ("b-unrelated-session", None),
+        ("c-play-session", Some("play")),
+        ("zellij-test", Some("work")),
+    ] {
+        let mut session_info = SessionInfo::new(name.to_owned());
+        session_info.group = group.map(|g| g.to_owned());
+        session_infos.insert(name.to_owned(), session_info);
+    }
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::UpdateSessionInfos(session_infos, vec![]));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    send_cli_action_to_server(&session_metadata, CliAction::SwitchWorkspace, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let switched_to_session = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::SwitchSession(connect_to_session, _client_id) => {
+                connect_to_session.name.clone()
+            },
+            _ => None,
+        });
+    assert_eq!(
+        switched_to_session,
+        Some("a-other-work-session".to_owned()),
+        "switched to the next session in the group, wrapping around"
+    );
+}
+
+#[test]
+pub fn send_cli_toggle_scratchpad_action_spawns_it_on_first_use() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::ToggleScratchpad, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let spawned_scratchpad = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|instruction| matches!(instruction, PtyInstruction::SpawnScratchpad(..)));
+    assert!(spawned_scratchpad, "scratchpad spawned on first toggle");
+}
+
+#[test]
+pub fn subscribed_plugin_receives_decoded_pane_output() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let plugin_id = 1;
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::SubscribeToPaneOutput(
+            0, plugin_id, client_id,
+        ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "\u{1b}[31mhello\u{1b}[0m\r\n".as_bytes().to_vec(),
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        1,
+        "not subscribed".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
+    let pane_output_events: Vec<&Event> = received_plugin_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PluginInstruction::Update(updates) => Some(updates),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|(update_plugin_id, update_client_id, event)| match event {
+            Event::PaneOutput(..) => {
+                assert_eq!(*update_plugin_id, Some(plugin_id));
+                assert_eq!(*update_client_id, Some(client_id));
+                Some(event)
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        pane_output_events,
+        vec![&Event::PaneOutput(0, "hello\n".to_owned())]
+    );
+}
+
+#[test]
+pub fn pane_output_subscriptions_are_removed_with_their_pane_or_plugin() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let unloaded_plugin_id = 1;
+    let plugin_id = 2;
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    for (terminal_pane_id, plugin_id) in [(0, unloaded_plugin_id), (0, plugin_id), (1, plugin_id)] {
+        let _ = mock_screen
+            .to_screen
+            .send(ScreenInstruction::SubscribeToPaneOutput(
+                terminal_pane_id,
+                plugin_id,
+                client_id,
+            ));
+    }
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RemovePaneOutputSubscriptionsOfPlugin(
+            unloaded_plugin_id,
+        ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::RemovePaneOutputSubscriptionsOfPane(1));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "hello\r\n".as_bytes().to_vec(),
+    ));
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        1,
+        "closed\r\n".as_bytes().to_vec(),
+    ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
+    let pane_output_updates: Vec<&(Option<u32>, Option<ClientId>, Event)> =
+        received_plugin_instructions
+            .iter()
+            .filter_map(|instruction| match instruction {
+                PluginInstruction::Update(updates) => Some(updates),
+                _ => None,
+            })
+            .flatten()
+            .filter(|(_, _, event)| matches!(event, Event::PaneOutput(..)))
+            .collect();
+    assert_eq!(
+        pane_output_updates,
+        vec![&(
+            Some(plugin_id),
+            Some(client_id),
+            Event::PaneOutput(0, "hello\n".to_owned())
+        )]
+    );
+}
+
+#[test]
+pub fn plugin_receives_styled_pane_contents() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let plugin_id = 1;
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::PtyBytes(
+        0,
+        "\u{1b}[31mhello\u{1b}[0m\r\n".as_bytes().to_vec(),
+    ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::DumpPaneContents(
+            0, false, plugin_id, client_id,
+        ));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
+    let pane_contents: Vec<(u32, String)> = received_plugin_instructions
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PluginInstruction::Update(updates) => Some(updates),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|(update_plugin_id, update_client_id, event)| match event {
+            Event::PaneContents(pane_id, contents) => {
+                assert_eq!(*update_plugin_id, Some(plugin_id));
+                assert_eq!(*update_client_id, Some(client_id));
+                Some((*pane_id, contents.clone()))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(pane_contents.len(), 1, "Pane contents sent once");
+    let (pane_id, contents) = &pane_contents[0];
+    assert_eq!(*pane_id, 0);
+    assert!(
+        contents.contains("hello"),
+        "Contents include the pane output"
+    );
+    assert!(
+        contents.contains("\u{1b}["),
+        "Contents include the ANSI styling"
+    );
+}
+
+#[test]
+pub fn send_cli_new_tab_action_default_params() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let new_tab_action = CliAction::NewTab {
+        name: None,
+        layout: None,
+        layout_dir: None,
+        cwd: None,
+    };
+    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let received_plugin_instructions = received_plugin_instructions.lock().unwrap();
+    let new_tab_action =
+        received_plugin_instructions
+            .iter()
+            .find(|instruction| match instruction {
+                PluginInstruction::NewTab(..) => true,
+                _ => false,
+            });
+    assert_snapshot!(format!("{:#?}", new_tab_action));
+}
+
+#[test]
+pub fn send_cli_new_tab_action_with_name_and_layout() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let new_tab_action = CliAction::NewTab {
+        name: Some("my-awesome-tab-name".into()),
+        layout: Some(PathBuf::from(format!(
+            "{}/src/unit/fixtures/layout-with-three-panes.kdl",
+            env!("CARGO_MANIFEST_DIR")
+        ))),
+        layout_dir: None,
+        cwd: None,
+    };
+    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
     let new_tab_instruction = received_plugin_instructions
         .lock()
         .unwrap()
@@ -2278,6 +3400,48 @@ pub fn send_cli_new_tab_action_with_name_and_layout() {
     assert_snapshot!(format!("{:#?}", new_tab_instruction));
 }
 
+#[test]
+pub fn send_cli_new_tab_action_with_multiple_tabs_layout() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_plugin_instructions = Arc::new(Mutex::new(vec![]));
+    let plugin_receiver = mock_screen.plugin_receiver.take().unwrap();
+    let plugin_thread = log_actions_in_thread!(
+        received_plugin_instructions,
+        PluginInstruction::Exit,
+        plugin_receiver
+    );
+    let new_tab_action = CliAction::NewTab {
+        name: None,
+        layout: Some(PathBuf::from(format!(
+            "{}/src/unit/fixtures/layout-with-two-tabs.kdl",
+            env!("CARGO_MANIFEST_DIR")
+        ))),
+        layout_dir: None,
+        cwd: None,
+    };
+    send_cli_action_to_server(&session_metadata, new_tab_action, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![plugin_thread, screen_thread]);
+    let new_tab_instruction_count = received_plugin_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|i| matches!(i, PluginInstruction::NewTab(..)))
+        .count();
+    // the first one is for the initial tab of the session
+    assert_eq!(
+        new_tab_instruction_count, 3,
+        "a new tab opened for each tab in the layout"
+    );
+}
+
 #[test]
 pub fn send_cli_next_tab_action() {
     let size = Size { cols: 80, rows: 10 };
@@ -2548,6 +3712,311 @@ pub fn send_cli_query_tab_names_action() {
     assert_snapshot!(format!("{:#?}", log_tab_names_instruction));
 }
 
+#[test]
+pub fn send_cli_query_pane_processes_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_server_instructions = Arc::new(Mutex::new(vec![]));
+    let server_receiver = mock_screen.server_receiver.take().unwrap();
+    let server_thread = log_actions_in_thread!(
+        received_server_instructions,
+        ServerInstruction::KillSession,
+        server_receiver
+    );
+    let mut terminal_processes = HashMap::new();
+    terminal_processes.insert(
+        0,
+        TerminalProcesses {
+            child_pids: vec![1234, 1240],
+            foreground_process: Some("vim".to_owned()),
+            cwd: Some(PathBuf::from("/tmp")),
+            running_processes: vec!["vim".to_owned()],
+        },
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::UpdateTerminalProcesses(
+            terminal_processes,
+        ));
+    send_cli_action_to_server(&session_metadata, CliAction::QueryPaneProcesses, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![server_thread, screen_thread]);
+    let logged_pane_processes = received_server_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            ServerInstruction::Log(lines, _) => Some(lines.clone()),
+            _ => None,
+        });
+    assert_eq!(
+        logged_pane_processes,
+        Some(vec![
+            "terminal_0\tvim\t/tmp\t1234 1240".to_owned(),
+            "terminal_1\t-\t-\t".to_owned(),
+        ])
+    );
+}
+
+#[test]
+pub fn send_cli_dump_layout_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    send_cli_action_to_server(&session_metadata, CliAction::DumpLayout, client_id);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let (global_layout_manifest, dump_layout_client_id) = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PtyInstruction::DumpLayout(global_layout_manifest, client_id) => {
+                Some((global_layout_manifest.clone(), *client_id))
+            },
+            _ => None,
+        })
+        .expect("layout was dumped");
+    assert_eq!(dump_layout_client_id, client_id);
+    assert_eq!(global_layout_manifest.tabs.len(), 1);
+    let (_tab_name, tab_layout_manifest) = &global_layout_manifest.tabs[0];
+    assert_eq!(tab_layout_manifest.tiled_panes.len(), 2);
+    assert!(
+        tab_layout_manifest
+            .tiled_panes
+            .iter()
+            .all(|p| p.pane_contents.is_none()),
+        "pane contents are never dumped to the cli"
+    );
+}
+
+#[test]
+pub fn screen_can_dump_layout_to_hd() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let mut terminal_processes = HashMap::new();
+    terminal_processes.insert(
+        0,
+        TerminalProcesses {
+            child_pids: vec![1234],
+            foreground_process: Some("bash".to_owned()),
+            cwd: Some(PathBuf::from("/tmp")),
+            running_processes: vec![],
+        },
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::UpdateTerminalProcesses(
+            terminal_processes,
+        ));
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::DumpLayoutToHd);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let global_layout_manifest = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .find_map(|instruction| match instruction {
+            PtyInstruction::DumpLayoutToHd(global_layout_manifest, None) => {
+                Some(global_layout_manifest.clone())
+            },
+            _ => None,
+        })
+        .expect("layout was dumped");
+    assert_eq!(global_layout_manifest.session_name, "zellij-test");
+    assert_eq!(global_layout_manifest.tabs.len(), 1);
+    let (_tab_name, tab_layout_manifest) = &global_layout_manifest.tabs[0];
+    assert!(tab_layout_manifest.is_focused);
+    assert_eq!(tab_layout_manifest.tiled_panes.len(), 2);
+    assert!(tab_layout_manifest.floating_panes.is_empty());
+    let first_pane = tab_layout_manifest
+        .tiled_panes
+        .iter()
+        .find(|p| p.geom.x == 0)
+        .unwrap();
+    assert_eq!(first_pane.run, Some(Run::Cwd(PathBuf::from("/tmp"))));
+    assert_eq!(
+        tab_layout_manifest
+            .tiled_panes
+            .iter()
+            .filter(|p| p.is_focused)
+            .count(),
+        1
+    );
+}
+
+#[test]
+fn rename_session_rejects_names_that_are_not_a_single_path_component() {
+    let size = Size {
+        cols: 121,
+        rows: 20,
+    };
+    let mut screen = create_new_screen(size);
+    for new_session_name in ["../../escaped", "nested/name", ".."] {
+        screen.rename_session(new_session_name.to_owned()).unwrap();
+        assert_eq!(screen.session_name, "zellij-test");
+    }
+}
+
+#[test]
+pub fn screen_serializes_session_as_it_exits() {
+    let size = Size { cols: 80, rows: 10 };
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::Exit);
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let dumped_layout = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|instruction| matches!(instruction, PtyInstruction::DumpLayoutToHd(_, None)));
+    assert!(dumped_layout, "layout was dumped as the screen exited");
+}
+
+#[test]
+pub fn screen_serializes_session_before_quitting() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 1;
+    let mut mock_screen = MockScreen::new(size);
+    let pty_receiver = mock_screen.pty_receiver.take().unwrap();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyInstruction::Exit,
+        pty_receiver
+    );
+    let _ = mock_screen
+        .to_screen
+        .send(ScreenInstruction::SerializeSessionAndQuit(client_id));
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_thread, screen_thread]);
+    let client_to_exit =
+        received_pty_instructions.lock().unwrap().iter().find_map(
+            |instruction| match instruction {
+                PtyInstruction::DumpLayoutToHd(_, client_to_exit) => *client_to_exit,
+                _ => None,
+            },
+        );
+    assert_eq!(
+        client_to_exit,
+        Some(client_id),
+        "layout was dumped before quitting the client"
+    );
+}
+
+#[test]
+pub fn send_cli_paste_buffer_action_to_pane() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut initial_layout = TiledPaneLayout::default();
+    initial_layout.children_split_direction = SplitDirection::Vertical;
+    initial_layout.children = vec![TiledPaneLayout::default(), TiledPaneLayout::default()];
+    let mut mock_screen = MockScreen::new(size);
+    let pty_writer_receiver = mock_screen.pty_writer_receiver.take().unwrap();
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(Some(initial_layout), vec![]);
+    let received_pty_instructions = Arc::new(Mutex::new(vec![]));
+    let pty_writer_thread = log_actions_in_thread!(
+        received_pty_instructions,
+        PtyWriteInstruction::Exit,
+        pty_writer_receiver
+    );
+    let _ = mock_screen.to_screen.send(ScreenInstruction::LoadBuffer(
+        "text from the paste buffer".to_owned(),
+        client_id,
+    ));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::PasteBuffer { pane: Some(1) },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![pty_writer_thread, screen_thread]);
+    let pasted_bytes: Vec<(u32, Vec<u8>)> = received_pty_instructions
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|instruction| match instruction {
+            PtyWriteInstruction::Write(bytes, terminal_id) if !bytes.is_empty() => {
+                Some((*terminal_id, bytes.clone()))
+            },
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        pasted_bytes,
+        vec![(1, "text from the paste buffer".as_bytes().to_vec())],
+        "paste buffer written to the requested pane without bracketed paste sequences"
+    );
+}
+
+#[test]
+pub fn send_cli_save_buffer_action() {
+    let size = Size { cols: 80, rows: 10 };
+    let client_id = 10; // fake client id should not appear in the screen's state
+    let mut mock_screen = MockScreen::new(size);
+    let session_metadata = mock_screen.clone_session_metadata();
+    let screen_thread = mock_screen.run(None, vec![]);
+    let _ = mock_screen.to_screen.send(ScreenInstruction::LoadBuffer(
+        "text from the paste buffer".to_owned(),
+        client_id,
+    ));
+    send_cli_action_to_server(
+        &session_metadata,
+        CliAction::SaveBuffer {
+            path: PathBuf::from("buffer.txt"),
+        },
+        client_id,
+    );
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    mock_screen.teardown(vec![screen_thread]);
+    let fake_filesystem = mock_screen.os_input.fake_filesystem.lock().unwrap();
+    assert_eq!(
+        fake_filesystem.get("./buffer.txt"),
+        Some(&"text from the paste buffer".to_owned())
+    );
+}
+
 #[test]
 pub fn send_cli_launch_or_focus_plugin_action() {
     let size = Size {
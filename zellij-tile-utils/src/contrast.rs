@@ -0,0 +1,82 @@
+use zellij_tile::prelude::{Palette, PaletteColor};
+
+/// Whichever of the palette's black and white is more readable on `background`, for text drawn
+/// on a colored background (eg. a tab) whose color comes from the user's theme
+pub fn contrasting_foreground(background: PaletteColor, palette: &Palette) -> PaletteColor {
+    let background_luminance = relative_luminance(background);
+    if contrast_ratio(relative_luminance(palette.black), background_luminance)
+        >= contrast_ratio(relative_luminance(palette.white), background_luminance)
+    {
+        palette.black
+    } else {
+        palette.white
+    }
+}
+
+// https://www.w3.org/TR/WCAG21/#dfn-contrast-ratio
+fn contrast_ratio(first_luminance: f64, second_luminance: f64) -> f64 {
+    let (lighter, darker) = if first_luminance > second_luminance {
+        (first_luminance, second_luminance)
+    } else {
+        (second_luminance, first_luminance)
+    };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+// https://www.w3.org/TR/WCAG21/#dfn-relative-luminance
+fn relative_luminance(color: PaletteColor) -> f64 {
+    let (r, g, b) = to_rgb(color);
+    let linear = |channel: u8| {
+        let channel = channel as f64 / 255.0;
+        if channel <= 0.03928 {
+            channel / 12.92
+        } else {
+            ((channel + 0.055) / 1.055).powf(2.4)
+        }
+    };
+    0.2126 * linear(r) + 0.7152 * linear(g) + 0.0722 * linear(b)
+}
+
+/// Eight bit colors as xterm shows them by default, terminals are free to customize the first
+/// 16 so this is only an approximation for those
+fn to_rgb(color: PaletteColor) -> (u8, u8, u8) {
+    const BASE_COLORS: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (205, 0, 0),
+        (0, 205, 0),
+        (205, 205, 0),
+        (0, 0, 238),
+        (205, 0, 205),
+        (0, 205, 205),
+        (229, 229, 229),
+        (127, 127, 127),
+        (255, 0, 0),
+        (0, 255, 0),
+        (255, 255, 0),
+        (92, 92, 255),
+        (255, 0, 255),
+        (0, 255, 255),
+        (255, 255, 255),
+    ];
+    match color {
+        PaletteColor::Rgb(rgb) => rgb,
+        PaletteColor::EightBit(index @ 0..=15) => BASE_COLORS[index as usize],
+        PaletteColor::EightBit(index @ 16..=231) => {
+            let cube_level = |level: u8| if level == 0 { 0 } else { 55 + level * 40 };
+            let index = index - 16;
+            (
+                cube_level(index / 36),
+                cube_level((index / 6) % 6),
+                cube_level(index % 6),
+            )
+        },
+        PaletteColor::EightBit(index) => {
+            let gray_level = 8 + (index - 232) * 10;
+            (gray_level, gray_level, gray_level)
+        },
+    }
+}
+
+#[cfg(test)]
+#[path = "./unit/contrast_test.rs"]
+mod contrast_test;
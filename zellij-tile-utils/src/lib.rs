@@ -1,3 +1,10 @@
+mod contrast;
+mod menu;
+
+pub use contrast::contrasting_foreground;
+pub use menu::MiniMenu;
+pub use zellij_tile::ui_components::Text;
+
 #[macro_export]
 macro_rules! rgb {
     ($a:expr) => {
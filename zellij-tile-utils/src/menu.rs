@@ -0,0 +1,92 @@
+use std::ops::Range;
+use zellij_tile::prelude::{PaletteColor, Styling};
+
+/// A one line menu drawn over a plugin's own content (eg. a context menu in a bar that only has
+/// one line to draw on), clicks are mapped back to its items with `item_at`
+///
+/// ```ignore
+/// let menu = MiniMenu::new(vec!["Rename", "Close"]).title("Tab #2");
+/// print!("{}", menu.render(cols, &mode_info.style.styling));
+/// // later, on Mouse::LeftClick(_, col)
+/// match menu.item_at(col) { Some(0) => ..., Some(1) => ..., _ => {} }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MiniMenu {
+    title: Option<String>,
+    items: Vec<String>,
+}
+
+impl MiniMenu {
+    pub fn new(items: Vec<impl Into<String>>) -> Self {
+        MiniMenu {
+            title: None,
+            items: items.into_iter().map(|item| item.into()).collect(),
+        }
+    }
+    /// Shown before the items, eg. the name of what the menu was opened on
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+    fn title_width(&self) -> usize {
+        self.title
+            .as_ref()
+            .map(|title| title.chars().count() + 2)
+            .unwrap_or(0)
+    }
+    /// The columns each item takes up, items are padded with a space on each side
+    fn item_ranges(&self) -> Vec<Range<usize>> {
+        let mut start = self.title_width();
+        self.items
+            .iter()
+            .map(|item| {
+                let end = start + item.chars().count() + 2;
+                let range = start..end;
+                start = end;
+                range
+            })
+            .collect()
+    }
+    /// The index of the item rendered at column `col`, if any
+    pub fn item_at(&self, col: usize) -> Option<usize> {
+        self.item_ranges()
+            .iter()
+            .position(|range| range.contains(&col))
+    }
+    /// Items that do not fit in `cols` are left out
+    pub fn render(&self, cols: usize, styling: &Styling) -> String {
+        let mut rendered = vec![];
+        if let Some(title) = &self.title {
+            if self.title_width() > cols {
+                return String::new();
+            }
+            let declaration = styling.text_selected;
+            let style = ansi_term::Style::new()
+                .fg(ansi_color(declaration.base))
+                .on(ansi_color(declaration.background));
+            rendered.push(style.paint(format!(" {title} ")));
+        }
+        let declaration = styling.text_unselected;
+        let base = ansi_term::Style::new().fg(ansi_color(declaration.base));
+        let emphasized = base.fg(ansi_color(declaration.emphasis_2)).bold();
+        for (item, range) in self.items.iter().zip(self.item_ranges()) {
+            if range.end > cols {
+                break;
+            }
+            // the first letter is emphasized, like a keybinding hint
+            let mut characters = item.chars();
+            let first_letter: String = characters.next().into_iter().collect();
+            rendered.push(base.paint(" "));
+            rendered.push(emphasized.paint(first_letter));
+            rendered.push(base.paint(format!("{} ", characters.as_str())));
+        }
+        ansi_term::ANSIStrings(&rendered).to_string()
+    }
+}
+
+fn ansi_color(color: PaletteColor) -> ansi_term::Color {
+    match color {
+        PaletteColor::Rgb((r, g, b)) => ansi_term::Color::RGB(r, g, b),
+        PaletteColor::EightBit(color) => ansi_term::Color::Fixed(color),
+    }
+}
@@ -0,0 +1,85 @@
+use super::*;
+
+fn black_and_white_palette() -> Palette {
+    Palette {
+        black: PaletteColor::Rgb((0, 0, 0)),
+        white: PaletteColor::Rgb((255, 255, 255)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn rgb_colors_are_left_as_is() {
+    assert_eq!(to_rgb(PaletteColor::Rgb((1, 2, 3))), (1, 2, 3));
+}
+
+#[test]
+fn base_eight_bit_colors_are_converted_to_xterm_defaults() {
+    assert_eq!(to_rgb(PaletteColor::EightBit(0)), (0, 0, 0));
+    assert_eq!(to_rgb(PaletteColor::EightBit(1)), (205, 0, 0));
+    assert_eq!(to_rgb(PaletteColor::EightBit(12)), (92, 92, 255));
+    assert_eq!(to_rgb(PaletteColor::EightBit(15)), (255, 255, 255));
+}
+
+#[test]
+fn eight_bit_color_cube_is_converted_to_rgb() {
+    assert_eq!(to_rgb(PaletteColor::EightBit(16)), (0, 0, 0));
+    assert_eq!(to_rgb(PaletteColor::EightBit(17)), (0, 0, 95));
+    assert_eq!(to_rgb(PaletteColor::EightBit(67)), (95, 135, 175));
+    assert_eq!(to_rgb(PaletteColor::EightBit(231)), (255, 255, 255));
+}
+
+#[test]
+fn eight_bit_grayscale_is_converted_to_rgb() {
+    assert_eq!(to_rgb(PaletteColor::EightBit(232)), (8, 8, 8));
+    assert_eq!(to_rgb(PaletteColor::EightBit(244)), (128, 128, 128));
+    assert_eq!(to_rgb(PaletteColor::EightBit(255)), (238, 238, 238));
+}
+
+#[test]
+fn black_foreground_on_light_backgrounds() {
+    let palette = black_and_white_palette();
+    for background in [
+        PaletteColor::Rgb((255, 255, 255)),
+        PaletteColor::Rgb((255, 255, 0)),
+        PaletteColor::Rgb((0, 255, 0)),
+        PaletteColor::EightBit(229),
+    ] {
+        assert_eq!(
+            contrasting_foreground(background, &palette),
+            palette.black,
+            "background: {background:?}"
+        );
+    }
+}
+
+#[test]
+fn white_foreground_on_dark_backgrounds() {
+    let palette = black_and_white_palette();
+    for background in [
+        PaletteColor::Rgb((0, 0, 0)),
+        PaletteColor::Rgb((0, 0, 255)),
+        PaletteColor::Rgb((128, 0, 0)),
+        PaletteColor::EightBit(17),
+    ] {
+        assert_eq!(
+            contrasting_foreground(background, &palette),
+            palette.white,
+            "background: {background:?}"
+        );
+    }
+}
+
+#[test]
+fn picks_whichever_palette_color_contrasts_more() {
+    // themes are free to make their black lighter than their white
+    let palette = Palette {
+        black: PaletteColor::Rgb((255, 255, 255)),
+        white: PaletteColor::Rgb((0, 0, 0)),
+        ..Default::default()
+    };
+    assert_eq!(
+        contrasting_foreground(PaletteColor::Rgb((0, 0, 0)), &palette),
+        palette.black
+    );
+}
@@ -8,6 +8,7 @@
 //! [`register_plugin!`](register_plugin) macro to register them.
 //! - The list of [commands](shim) representing what a plugin can do.
 //! - The list of [`Events`](prelude::Event) a plugin can subscribe to
+//! - [UI components](ui_components) (eg. tables) that are rendered with the user's theme
 //! - The [`ZellijWorker`] trait for implementing background workers combined with the
 //! [`register_worker!`](register_worker) macro to register them
 //!
@@ -17,10 +18,11 @@
 //!
 pub mod prelude;
 pub mod shim;
+pub mod ui_components;
 
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
-use zellij_utils::data::Event;
+use zellij_utils::data::{Event, PipeMessage};
 
 // use zellij_tile::shim::plugin_api::event::ProtobufEvent;
 
@@ -39,6 +41,13 @@ pub trait ZellijPlugin: Default {
     /// Will be called either after an `update` that requested it, or when the plugin otherwise needs to be re-rendered (eg. on startup, or when the plugin is resized).
     /// The `rows` and `cols` values represent the "content size" of the plugin (this will not include its surrounding frame if the user has pane frames enabled).
     fn render(&mut self, rows: usize, cols: usize) {}
+    /// Will be called with a [`PipeMessage`](prelude::PipeMessage) whenever another plugin sends
+    /// this plugin a message with [`pipe_message_to_plugin`](shim::pipe_message_to_plugin), or
+    /// when data is sent to it from the command line with `zellij pipe`.
+    /// If the plugin returns `true` from this function, Zellij will know it should be rendered and call its `render` function.
+    fn pipe(&mut self, pipe_message: PipeMessage) -> bool {
+        false
+    } // return true if it should render
 }
 
 /// This trait is used to create workers. Workers can be used by plugins to run longer running
@@ -135,6 +144,20 @@ macro_rules! register_plugin {
             })
         }
 
+        #[no_mangle]
+        pub fn pipe() -> bool {
+            use std::convert::TryInto;
+            use zellij_tile::shim::plugin_api::pipe_message::ProtobufPipeMessage;
+            use zellij_tile::shim::prost::Message;
+            STATE.with(|state| {
+                let protobuf_bytes: Vec<u8> = $crate::shim::object_from_stdin().unwrap();
+                let protobuf_pipe_message: ProtobufPipeMessage =
+                    ProtobufPipeMessage::decode(protobuf_bytes.as_slice()).unwrap();
+                let pipe_message = protobuf_pipe_message.try_into().unwrap();
+                state.borrow_mut().pipe(pipe_message)
+            })
+        }
+
         #[no_mangle]
         pub fn render(rows: i32, cols: i32) {
             STATE.with(|state| {
@@ -1,4 +1,5 @@
 pub use crate::shim::*;
+pub use crate::ui_components::*;
 pub use crate::*;
 pub use zellij_utils::consts::VERSION;
 pub use zellij_utils::data::*;
@@ -1,6 +1,9 @@
 use serde::{de::DeserializeOwned, Serialize};
-use std::collections::HashSet;
-use std::{io, path::Path};
+use std::collections::{BTreeMap, HashSet};
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
 use zellij_utils::data::*;
 use zellij_utils::errors::prelude::*;
 pub use zellij_utils::plugin_api;
@@ -71,6 +74,10 @@ pub fn get_zellij_version() -> String {
 
 // Host Functions
 
+// The functions opening panes (eg. `open_terminal`, `open_command_pane`) do not block until the
+// pane is created, its id is then sent to the plugin as an `Event::PaneOpened` (requires a
+// subscription to `EventType::PaneOpened`)
+
 /// Open a file in the user's default `$EDITOR` in a new pane
 pub fn open_file(file_to_open: FileToOpen) {
     let plugin_command = PluginCommand::OpenFile(file_to_open);
@@ -87,6 +94,15 @@ pub fn open_file_floating(file_to_open: FileToOpen) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Open a file in the user's default `$EDITOR`, temporarily replacing the focused pane until the
+/// editor is closed
+pub fn open_file_in_place(file_to_open: FileToOpen) {
+    let plugin_command = PluginCommand::OpenFileInPlace(file_to_open);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Open a new terminal pane to the specified location on the host filesystem
 pub fn open_terminal<P: AsRef<Path>>(path: P) {
     let file_to_open = FileToOpen::new(path.as_ref().to_path_buf());
@@ -105,6 +121,16 @@ pub fn open_terminal_floating<P: AsRef<Path>>(path: P) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Open a new terminal pane to the specified location on the host filesystem, temporarily replacing
+/// the focused pane until it is closed
+pub fn open_terminal_in_place<P: AsRef<Path>>(path: P) {
+    let file_to_open = FileToOpen::new(path.as_ref().to_path_buf());
+    let plugin_command = PluginCommand::OpenTerminalInPlace(file_to_open);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Open a new command pane with the specified command and args (this sort of pane allows the user to control the command, re-run it and see its exit status through the Zellij UI).
 // pub fn open_command_pane<P: AsRef<Path>, A: AsRef<str>>(path: P, args: Vec<A>) {
 pub fn open_command_pane(command_to_run: CommandToRun) {
@@ -123,6 +149,16 @@ pub fn open_command_pane_floating(command_to_run: CommandToRun) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Open a new command pane with the specified command and args, temporarily replacing the focused
+/// pane until it is closed. Once it is, the plugin receives an `Event::InPlacePaneClosed` with the
+/// last line the command printed (requires a subscription to `EventType::InPlacePaneClosed`)
+pub fn open_command_pane_in_place(command_to_run: CommandToRun) {
+    let plugin_command = PluginCommand::OpenCommandPaneInPlace(command_to_run);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Change the focused tab to the specified index (corresponding with the default tab names, to starting at `1`, `0` will be considered as `1`).
 pub fn switch_tab_to(tab_idx: u32) {
     let plugin_command = PluginCommand::SwitchTabTo(tab_idx);
@@ -148,6 +184,72 @@ pub fn exec_cmd(cmd: &[&str]) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Run a command in the background on the host machine, its exit code, stdout and stderr are sent
+/// back to the plugin as an `Event::RunCommandResult` once it exits (requires the `RunCommands`
+/// permission and a subscription to `EventType::RunCommandResult`)
+pub fn run_command(
+    cmd: &str,
+    args: &[&str],
+    env_variables: BTreeMap<String, String>,
+    cwd: Option<PathBuf>,
+) {
+    let command_to_run = CommandToRun {
+        path: PathBuf::from(cmd),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        cwd,
+    };
+    let plugin_command = PluginCommand::RunCommand(command_to_run, env_variables);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Make an http request in the background, its status code, headers and body are sent back to the
+/// plugin as an `Event::WebRequestResult` once it finishes (requires the `WebAccess` permission
+/// and a subscription to `EventType::WebRequestResult`)
+pub fn web_request(url: &str, verb: HttpVerb, headers: BTreeMap<String, String>, body: Vec<u8>) {
+    let plugin_command = PluginCommand::WebRequest(url.to_owned(), verb, headers, body);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Map a different folder of the host machine to the plugin's `/host` folder, this reloads the
+/// plugin (requires the `FullHdAccess` permission)
+pub fn change_host_folder(new_host_folder: PathBuf) {
+    let plugin_command = PluginCommand::ChangeHostFolder(new_host_folder);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Receive the system state (eg. the time, load average and battery level) every `interval`
+/// seconds as an `Event::SystemStateUpdate`, starting right away, calling this again changes the
+/// interval (requires the `ReadSystemState` permission and a subscription to
+/// `EventType::SystemStateUpdate`)
+pub fn subscribe_to_system_state(interval: f64) {
+    let plugin_command = PluginCommand::SubscribeToSystemState(interval);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Call the plugins [update](./plugin-api-events#update) method with the [`Timer`](./plugin-api-events.md#timer) event every `secs` seconds (or fractions thereof) until [`clear_interval`] is called, calling this again replaces the previous interval
+pub fn set_interval(secs: f64) {
+    let plugin_command = PluginCommand::SetInterval(secs);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stop the interval started with [`set_interval`]
+pub fn clear_interval() {
+    let plugin_command = PluginCommand::ClearInterval;
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Hide the plugin pane (suppress it) from the UI
 pub fn hide_self() {
     let plugin_command = PluginCommand::HideSelf;
@@ -302,6 +404,24 @@ pub fn write_chars(chars: &str) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Write bytes to the `STDIN` of the terminal pane with the given id, whether or not it is
+/// focused (requires the `WriteToStdin` permission)
+pub fn write_to_pane_id(bytes: Vec<u8>, terminal_pane_id: u32) {
+    let plugin_command = PluginCommand::WriteToPaneId(bytes, terminal_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Write characters to the `STDIN` of the terminal pane with the given id, whether or not it is
+/// focused (requires the `WriteToStdin` permission)
+pub fn write_chars_to_pane_id(chars: &str, terminal_pane_id: u32) {
+    let plugin_command = PluginCommand::WriteCharsToPaneId(chars.to_owned(), terminal_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 /// Focused the previously focused tab (regardless of the tab position)
 pub fn toggle_tab() {
     let plugin_command = PluginCommand::ToggleTab;
@@ -530,6 +650,17 @@ pub fn focus_plugin_pane(plugin_pane_id: u32, should_float_if_hidden: bool) {
     unsafe { host_run_plugin_command() };
 }
 
+/// Changes the focus to the pane with the specified id, either a terminal or a plugin pane (eg. the
+/// `id` and `is_plugin` of its `PaneInfo`), unsuppressing it if it was suppressed and switching to
+/// its tab and layer (eg. floating/tiled).
+pub fn focus_pane_with_id(pane_id: u32, is_plugin: bool, should_float_if_hidden: bool) {
+    if is_plugin {
+        focus_plugin_pane(pane_id, should_float_if_hidden);
+    } else {
+        focus_terminal_pane(pane_id, should_float_if_hidden);
+    }
+}
+
 /// Changes the name (the title that appears in the UI) of the terminal pane with the specified id.
 pub fn rename_terminal_pane<S: AsRef<str>>(terminal_pane_id: u32, new_name: S)
 where
@@ -585,12 +716,139 @@ pub fn switch_session_with_focus(
         name: Some(name.to_owned()),
         tab_position,
         pane_id,
+        ..Default::default()
     });
     let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
     object_to_stdout(&protobuf_plugin_command.encode_to_vec());
     unsafe { host_run_plugin_command() };
 }
 
+/// Switch to a session with the given name (or a new one if no name is given), if the session is
+/// created it will be started with the given layout (either a layout name or a path to a layout
+/// file)
+pub fn switch_session_with_layout(name: Option<&str>, layout: &str) {
+    let plugin_command = PluginCommand::SwitchSession(ConnectToSession {
+        name: name.map(|n| n.to_string()),
+        layout: Some(layout.to_owned()),
+        ..Default::default()
+    });
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Kill the running sessions with the given names
+pub fn kill_sessions<S: AsRef<str>>(session_names: &[S]) {
+    let plugin_command = PluginCommand::KillSessions(
+        session_names
+            .iter()
+            .map(|s| s.as_ref().to_owned())
+            .collect(),
+    );
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Delete the serialized layout of a dead session, so that it can no longer be resurrected
+pub fn delete_dead_session(session_name: &str) {
+    let plugin_command = PluginCommand::DeleteDeadSession(session_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Rename the current session
+pub fn rename_session(new_session_name: &str) {
+    let plugin_command = PluginCommand::RenameSession(new_session_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Close the tab at position `tab_index` (zero indexed), whether or not it is focused
+pub fn close_tab_with_index(tab_index: u32) {
+    let plugin_command = PluginCommand::CloseTabWithIndex(tab_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Move the tab at position `tab_index` (zero indexed) to position `new_index`, shifting the tabs
+/// in between over by one
+pub fn move_tab_to_index(tab_index: u32, new_index: u32) {
+    let plugin_command = PluginCommand::MoveTabToIndex(tab_index, new_index);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Rebind keys for all clients of the session using a `keybinds { .. }` block in the same format
+/// as the configuration file, on top of the current bindings (requires the `Reconfigure`
+/// permission). If `write_config_to_disk` is true, the block is also added to the configuration
+/// file and `Event::ConfigWasWrittenToDisk` is sent once it is saved
+pub fn rebind_keys(keybinds: &str, write_config_to_disk: bool) {
+    let plugin_command = PluginCommand::RebindKeys(keybinds.to_owned(), write_config_to_disk);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Subscribe to the output of the terminal pane with the given id, which will then be delivered
+/// in chunks as `Event::PaneOutput` (requires the `ReadPaneContents` permission and a subscription
+/// to `EventType::PaneOutput`)
+pub fn subscribe_to_pane_output(terminal_pane_id: u32) {
+    let plugin_command = PluginCommand::SubscribeToPaneOutput(terminal_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stop receiving the output of the terminal pane with the given id
+pub fn unsubscribe_from_pane_output(terminal_pane_id: u32) {
+    let plugin_command = PluginCommand::UnsubscribeFromPaneOutput(terminal_pane_id);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Request the contents of the terminal pane with the given id, with their ANSI styling, either
+/// only its viewport or (if `full` is true) along with its scrollback. They are sent back to the
+/// plugin as an `Event::PaneContents` (requires the `ReadPaneContents` permission and a
+/// subscription to `EventType::PaneContents`)
+pub fn dump_pane_contents(terminal_pane_id: u32, full: bool) {
+    let plugin_command = PluginCommand::DumpPaneContents(terminal_pane_id, full);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Send a message to all running instances of the plugin with the given url or alias, which will
+/// receive it in their `pipe` method (requires the `MessageOtherPlugins` permission)
+pub fn pipe_message_to_plugin(message_to_plugin: MessageToPlugin) {
+    let plugin_command = PluginCommand::PipeMessageToPlugin(message_to_plugin);
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Stop `zellij pipe` from sending the next message on the pipe with this name until
+/// `unblock_cli_pipe_input` is called, eg. while a previous message is still being processed
+pub fn block_cli_pipe_input(pipe_name: &str) {
+    let plugin_command = PluginCommand::BlockCliPipeInput(pipe_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
+/// Let `zellij pipe` send the next message on the pipe with this name
+pub fn unblock_cli_pipe_input(pipe_name: &str) {
+    let plugin_command = PluginCommand::UnblockCliPipeInput(pipe_name.to_owned());
+    let protobuf_plugin_command: ProtobufPluginCommand = plugin_command.try_into().unwrap();
+    object_to_stdout(&protobuf_plugin_command.encode_to_vec());
+    unsafe { host_run_plugin_command() };
+}
+
 // Utility Functions
 
 #[allow(unused)]
@@ -0,0 +1,235 @@
+//! UI components that plugins can print instead of hand-rolling their own ANSI.
+//!
+//! The components are printed to STDOUT in a serialized form and rendered by Zellij with the
+//! user's theme, so that plugins match the rest of the UI without having to know about colors.
+//!
+//! Each component is printed as `ESC P z {component};{coordinates};{fields...} ESC \`, where
+//! fields are separated by `;` (escaped as `\;` when part of a field, with `\` escaped as `\\`)
+//! and a text field is `{meta}|{text}`. `meta` is a comma separated list of `s` (selected) and
+//! `c{level}:{start}:{end}` (color the characters from `start` up to `end` with color `level`).
+use std::fmt::Write;
+use std::ops::Range;
+
+const COMPONENT_START: &str = "\u{1b}Pz";
+const COMPONENT_END: &str = "\u{1b}\\";
+
+/// A piece of text to be used in components, it can be selected (drawn with a background) and
+/// parts of it can be emphasized with one of the four emphasis colors (0 to 3) of the user's theme
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Text {
+    text: String,
+    selected: bool,
+    color_ranges: Vec<(usize, Range<usize>)>,
+}
+
+impl Text {
+    pub fn new(text: impl Into<String>) -> Self {
+        Text {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+    pub fn selected(mut self) -> Self {
+        self.selected = true;
+        self
+    }
+    /// Color the characters (not bytes) in `range` with the color of `level`
+    pub fn color_range(mut self, level: usize, range: Range<usize>) -> Self {
+        self.color_ranges.push((level, range));
+        self
+    }
+    /// Color the whole text with the color of `level`
+    pub fn color_all(self, level: usize) -> Self {
+        let len = self.text.chars().count();
+        self.color_range(level, 0..len)
+    }
+    fn serialize(&self) -> String {
+        let mut meta = vec![];
+        if self.selected {
+            meta.push("s".to_owned());
+        }
+        for (level, range) in &self.color_ranges {
+            meta.push(format!("c{}:{}:{}", level, range.start, range.end));
+        }
+        format!("{}|{}", meta.join(","), self.text)
+    }
+}
+
+impl From<&str> for Text {
+    fn from(text: &str) -> Self {
+        Text::new(text)
+    }
+}
+
+impl From<String> for Text {
+    fn from(text: String) -> Self {
+        Text::new(text)
+    }
+}
+
+/// A table whose first row is its title
+#[derive(Debug, Default, Clone)]
+pub struct Table {
+    rows: Vec<Vec<Text>>,
+}
+
+impl Table {
+    pub fn new() -> Self {
+        Table::default()
+    }
+    pub fn add_row(mut self, row: Vec<impl Into<Text>>) -> Self {
+        self.rows
+            .push(row.into_iter().map(|cell| cell.into()).collect());
+        self
+    }
+    pub fn add_styled_row(mut self, row: Vec<Text>) -> Self {
+        self.rows.push(row);
+        self
+    }
+    fn fields(&self) -> Vec<String> {
+        let columns = self.rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let mut fields = vec![columns.to_string()];
+        for row in &self.rows {
+            for column in 0..columns {
+                fields.push(row.get(column).cloned().unwrap_or_default().serialize());
+            }
+        }
+        fields
+    }
+}
+
+/// An item of a nested list, indented `indentation_level` levels deep
+#[derive(Debug, Default, Clone)]
+pub struct NestedListItem {
+    indentation_level: usize,
+    content: Text,
+}
+
+impl NestedListItem {
+    pub fn new(text: impl Into<String>) -> Self {
+        NestedListItem {
+            content: Text::new(text),
+            ..Default::default()
+        }
+    }
+    pub fn indent(mut self, indentation_level: usize) -> Self {
+        self.indentation_level = indentation_level;
+        self
+    }
+    pub fn selected(mut self) -> Self {
+        self.content = self.content.selected();
+        self
+    }
+    pub fn color_range(mut self, level: usize, range: Range<usize>) -> Self {
+        self.content = self.content.color_range(level, range);
+        self
+    }
+}
+
+/// Where to print a component, relative to the top left corner of the plugin pane. Components
+/// that do not fit in `width` and `height` (when given) are truncated
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Coordinates {
+    pub x: usize,
+    pub y: usize,
+    pub width: Option<usize>,
+    pub height: Option<usize>,
+}
+
+impl Coordinates {
+    pub fn new(x: usize, y: usize, width: Option<usize>, height: Option<usize>) -> Self {
+        Coordinates {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+    fn serialize(&self) -> String {
+        let optional = |value: Option<usize>| value.map(|v| v.to_string()).unwrap_or_default();
+        format!(
+            "{}/{}/{}/{}",
+            self.x,
+            self.y,
+            optional(self.width),
+            optional(self.height)
+        )
+    }
+}
+
+fn escape_field(field: &str) -> String {
+    field
+        .replace('\u{1b}', "")
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+}
+
+fn serialize_component(
+    component: &str,
+    coordinates: Option<Coordinates>,
+    fields: Vec<String>,
+) -> String {
+    let mut serialized = format!(
+        "{}{};{}",
+        COMPONENT_START,
+        component,
+        coordinates.map(|c| c.serialize()).unwrap_or_default()
+    );
+    for field in fields {
+        let _ = write!(serialized, ";{}", escape_field(&field));
+    }
+    serialized.push_str(COMPONENT_END);
+    serialized
+}
+
+/// Print a table at the cursor position
+pub fn print_table(table: Table) {
+    print!("{}", serialize_component("table", None, table.fields()));
+}
+
+/// Print a table at `coordinates`
+pub fn print_table_with_coordinates(table: Table, coordinates: Coordinates) {
+    print!(
+        "{}",
+        serialize_component("table", Some(coordinates), table.fields())
+    );
+}
+
+/// Print a ribbon (eg. a tab in the tab-bar) at the cursor position
+pub fn print_ribbon(text: Text) {
+    print!(
+        "{}",
+        serialize_component("ribbon", None, vec![text.serialize()])
+    );
+}
+
+/// Print a ribbon (eg. a tab in the tab-bar) at `coordinates`
+pub fn print_ribbon_with_coordinates(text: Text, coordinates: Coordinates) {
+    print!(
+        "{}",
+        serialize_component("ribbon", Some(coordinates), vec![text.serialize()])
+    );
+}
+
+fn nested_list_fields(items: Vec<NestedListItem>) -> Vec<String> {
+    items
+        .iter()
+        .flat_map(|item| [item.indentation_level.to_string(), item.content.serialize()])
+        .collect()
+}
+
+/// Print a nested list at the cursor position
+pub fn print_nested_list(items: Vec<NestedListItem>) {
+    print!(
+        "{}",
+        serialize_component("nested_list", None, nested_list_fields(items))
+    );
+}
+
+/// Print a nested list at `coordinates`
+pub fn print_nested_list_with_coordinates(items: Vec<NestedListItem>, coordinates: Coordinates) {
+    print!(
+        "{}",
+        serialize_component("nested_list", Some(coordinates), nested_list_fields(items))
+    );
+}
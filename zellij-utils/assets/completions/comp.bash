@@ -2,3 +2,23 @@ function zr () { zellij run --name "$*" -- bash -ic "$*";}
 function zrf () { zellij run --name "$*" --floating -- bash -ic "$*";}
 function ze () { zellij edit "$*";}
 function zef () { zellij edit --floating "$*";}
+
+_zellij_dynamic() {
+    local cur prev
+    cur="${COMP_WORDS[COMP_CWORD]}"
+    prev="${COMP_WORDS[COMP_CWORD-1]}"
+    case "${prev}" in
+        attach|a|kill-session|k|--session|-s)
+            COMPREPLY=($(compgen -W "$(zellij setup --list-sessions-for-completion 2>/dev/null)" -- "${cur}"))
+            return 0
+            ;;
+        --layout|-l|--default-layout)
+            COMPREPLY=($(compgen -W "$(zellij setup --list-layouts-for-completion 2>/dev/null)" -- "${cur}"))
+            # layouts can also be given as a path to a file
+            COMPREPLY+=($(compgen -f -- "${cur}"))
+            return 0
+            ;;
+    esac
+    _zellij "$@"
+}
+complete -F _zellij_dynamic -o bashdefault -o default zellij
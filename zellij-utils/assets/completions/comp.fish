@@ -1,10 +1,16 @@
 function __fish_complete_sessions
-    zellij list-sessions 2>/dev/null
+    zellij setup --list-sessions-for-completion 2>/dev/null
+end
+function __fish_complete_layouts
+    zellij setup --list-layouts-for-completion 2>/dev/null
 end
 complete -c zellij -n "__fish_seen_subcommand_from attach" -f -a "(__fish_complete_sessions)" -d "Session"
 complete -c zellij -n "__fish_seen_subcommand_from a" -f -a "(__fish_complete_sessions)" -d "Session"
 complete -c zellij -n "__fish_seen_subcommand_from kill-session" -f -a "(__fish_complete_sessions)" -d "Session"
 complete -c zellij -n "__fish_seen_subcommand_from k" -f -a "(__fish_complete_sessions)" -d "Session"
+complete -c zellij -s s -l session -f -a "(__fish_complete_sessions)" -d "Session"
+complete -c zellij -s l -l layout -a "(__fish_complete_layouts)" -d "Layout"
+complete -c zellij -n "__fish_seen_subcommand_from options" -l default-layout -a "(__fish_complete_layouts)" -d "Layout"
 complete -c zellij -n "__fish_seen_subcommand_from setup" -l "generate-completion" -x -a "bash elvish fish zsh powershell" -d "Shell"
 function zr
   command zellij run --name "$argv" -- fish -c "$argv"
@@ -5,7 +5,7 @@ pub struct Action {
     pub name: i32,
     #[prost(
         oneof = "action::OptionalPayload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56"
     )]
     pub optional_payload: ::core::option::Option<action::OptionalPayload>,
 }
@@ -100,6 +100,30 @@ pub mod action {
         RenamePluginPanePayload(super::IdAndName),
         #[prost(message, tag = "44")]
         RenameTabPayload(super::IdAndName),
+        #[prost(string, tag = "45")]
+        LoadBufferPayload(::prost::alloc::string::String),
+        #[prost(string, tag = "46")]
+        SaveBufferPayload(::prost::alloc::string::String),
+        #[prost(uint32, tag = "47")]
+        PasteBufferPayload(u32),
+        #[prost(uint32, tag = "48")]
+        MovePaneToTabPayload(u32),
+        #[prost(enumeration = "super::super::resize::ResizeDirection", tag = "49")]
+        MoveTabPayload(i32),
+        #[prost(message, tag = "50")]
+        CtrlLeftClickPayload(super::Position),
+        #[prost(message, tag = "51")]
+        MouseHoverPayload(super::Position),
+        #[prost(message, tag = "52")]
+        NewInPlacePanePayload(super::NewFloatingPanePayload),
+        #[prost(message, tag = "53")]
+        NewInPlacePluginPanePayload(super::NewPluginPanePayload),
+        #[prost(message, tag = "54")]
+        ShiftLeftClickPayload(super::Position),
+        #[prost(message, tag = "55")]
+        AltLeftClickPayload(super::Position),
+        #[prost(string, tag = "56")]
+        SwitchThemePayload(::prost::alloc::string::String),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -219,6 +243,8 @@ pub struct DumpScreenPayload {
     pub file_path: ::prost::alloc::string::String,
     #[prost(bool, tag = "2")]
     pub include_scrollback: bool,
+    #[prost(bool, tag = "3")]
+    pub include_ansi_styling: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -292,6 +318,7 @@ pub enum SearchOption {
     CaseSensitivity = 0,
     WholeWord = 1,
     Wrap = 2,
+    Regex = 3,
 }
 impl SearchOption {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -303,6 +330,7 @@ impl SearchOption {
             SearchOption::CaseSensitivity => "CaseSensitivity",
             SearchOption::WholeWord => "WholeWord",
             SearchOption::Wrap => "Wrap",
+            SearchOption::Regex => "Regex",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -311,6 +339,7 @@ impl SearchOption {
             "CaseSensitivity" => Some(Self::CaseSensitivity),
             "WholeWord" => Some(Self::WholeWord),
             "Wrap" => Some(Self::Wrap),
+            "Regex" => Some(Self::Regex),
             _ => None,
         }
     }
@@ -398,6 +427,41 @@ pub enum ActionName {
     BreakPane = 77,
     BreakPaneRight = 78,
     BreakPaneLeft = 79,
+    RespawnPane = 80,
+    SwitchWorkspace = 81,
+    ToggleScratchpad = 82,
+    QueryPaneProcesses = 83,
+    LoadBuffer = 84,
+    SaveBuffer = 85,
+    PasteBuffer = 86,
+    DumpLayout = 87,
+    EnterCopyMode = 88,
+    ToggleActivePaneSync = 89,
+    MovePaneToTab = 90,
+    MoveTab = 91,
+    CtrlLeftClick = 92,
+    MouseHover = 93,
+    LinkHints = 94,
+    NewInPlacePane = 95,
+    NewInPlacePluginPane = 96,
+    TogglePanePinned = 97,
+    ScrollToPreviousPrompt = 98,
+    ScrollToNextPrompt = 99,
+    CopyLastCommandOutput = 100,
+    ReopenClosedPane = 101,
+    ReopenClosedTab = 102,
+    ShiftLeftClick = 103,
+    AltLeftClick = 104,
+    TogglePaneInGroup = 105,
+    ClearPaneGroup = 106,
+    CloseGroupedPanes = 107,
+    FloatGroupedPanes = 108,
+    BreakGroupedPanesToNewTab = 109,
+    SyncGroupedPanes = 110,
+    SwitchTheme = 111,
+    ToggleExpandPane = 112,
+    FocusNextPaneInStack = 113,
+    FocusPreviousPaneInStack = 114,
 }
 impl ActionName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -486,6 +550,41 @@ impl ActionName {
             ActionName::BreakPane => "BreakPane",
             ActionName::BreakPaneRight => "BreakPaneRight",
             ActionName::BreakPaneLeft => "BreakPaneLeft",
+            ActionName::RespawnPane => "RespawnPane",
+            ActionName::SwitchWorkspace => "SwitchWorkspace",
+            ActionName::ToggleScratchpad => "ToggleScratchpad",
+            ActionName::QueryPaneProcesses => "QueryPaneProcesses",
+            ActionName::LoadBuffer => "LoadBuffer",
+            ActionName::SaveBuffer => "SaveBuffer",
+            ActionName::PasteBuffer => "PasteBuffer",
+            ActionName::DumpLayout => "DumpLayout",
+            ActionName::EnterCopyMode => "EnterCopyMode",
+            ActionName::ToggleActivePaneSync => "ToggleActivePaneSync",
+            ActionName::MovePaneToTab => "MovePaneToTab",
+            ActionName::MoveTab => "MoveTab",
+            ActionName::CtrlLeftClick => "CtrlLeftClick",
+            ActionName::MouseHover => "MouseHover",
+            ActionName::LinkHints => "LinkHints",
+            ActionName::NewInPlacePane => "NewInPlacePane",
+            ActionName::NewInPlacePluginPane => "NewInPlacePluginPane",
+            ActionName::TogglePanePinned => "TogglePanePinned",
+            ActionName::ScrollToPreviousPrompt => "ScrollToPreviousPrompt",
+            ActionName::ScrollToNextPrompt => "ScrollToNextPrompt",
+            ActionName::CopyLastCommandOutput => "CopyLastCommandOutput",
+            ActionName::ReopenClosedPane => "ReopenClosedPane",
+            ActionName::ReopenClosedTab => "ReopenClosedTab",
+            ActionName::ShiftLeftClick => "ShiftLeftClick",
+            ActionName::AltLeftClick => "AltLeftClick",
+            ActionName::TogglePaneInGroup => "TogglePaneInGroup",
+            ActionName::ClearPaneGroup => "ClearPaneGroup",
+            ActionName::CloseGroupedPanes => "CloseGroupedPanes",
+            ActionName::FloatGroupedPanes => "FloatGroupedPanes",
+            ActionName::BreakGroupedPanesToNewTab => "BreakGroupedPanesToNewTab",
+            ActionName::SyncGroupedPanes => "SyncGroupedPanes",
+            ActionName::SwitchTheme => "SwitchTheme",
+            ActionName::ToggleExpandPane => "ToggleExpandPane",
+            ActionName::FocusNextPaneInStack => "FocusNextPaneInStack",
+            ActionName::FocusPreviousPaneInStack => "FocusPreviousPaneInStack",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -571,6 +670,41 @@ impl ActionName {
             "BreakPane" => Some(Self::BreakPane),
             "BreakPaneRight" => Some(Self::BreakPaneRight),
             "BreakPaneLeft" => Some(Self::BreakPaneLeft),
+            "RespawnPane" => Some(Self::RespawnPane),
+            "SwitchWorkspace" => Some(Self::SwitchWorkspace),
+            "ToggleScratchpad" => Some(Self::ToggleScratchpad),
+            "QueryPaneProcesses" => Some(Self::QueryPaneProcesses),
+            "LoadBuffer" => Some(Self::LoadBuffer),
+            "SaveBuffer" => Some(Self::SaveBuffer),
+            "PasteBuffer" => Some(Self::PasteBuffer),
+            "DumpLayout" => Some(Self::DumpLayout),
+            "EnterCopyMode" => Some(Self::EnterCopyMode),
+            "ToggleActivePaneSync" => Some(Self::ToggleActivePaneSync),
+            "MovePaneToTab" => Some(Self::MovePaneToTab),
+            "MoveTab" => Some(Self::MoveTab),
+            "CtrlLeftClick" => Some(Self::CtrlLeftClick),
+            "MouseHover" => Some(Self::MouseHover),
+            "LinkHints" => Some(Self::LinkHints),
+            "NewInPlacePane" => Some(Self::NewInPlacePane),
+            "NewInPlacePluginPane" => Some(Self::NewInPlacePluginPane),
+            "TogglePanePinned" => Some(Self::TogglePanePinned),
+            "ScrollToPreviousPrompt" => Some(Self::ScrollToPreviousPrompt),
+            "ScrollToNextPrompt" => Some(Self::ScrollToNextPrompt),
+            "CopyLastCommandOutput" => Some(Self::CopyLastCommandOutput),
+            "ReopenClosedPane" => Some(Self::ReopenClosedPane),
+            "ReopenClosedTab" => Some(Self::ReopenClosedTab),
+            "ShiftLeftClick" => Some(Self::ShiftLeftClick),
+            "AltLeftClick" => Some(Self::AltLeftClick),
+            "TogglePaneInGroup" => Some(Self::TogglePaneInGroup),
+            "ClearPaneGroup" => Some(Self::ClearPaneGroup),
+            "CloseGroupedPanes" => Some(Self::CloseGroupedPanes),
+            "FloatGroupedPanes" => Some(Self::FloatGroupedPanes),
+            "BreakGroupedPanesToNewTab" => Some(Self::BreakGroupedPanesToNewTab),
+            "SyncGroupedPanes" => Some(Self::SyncGroupedPanes),
+            "SwitchTheme" => Some(Self::SwitchTheme),
+            "ToggleExpandPane" => Some(Self::ToggleExpandPane),
+            "FocusNextPaneInStack" => Some(Self::FocusNextPaneInStack),
+            "FocusPreviousPaneInStack" => Some(Self::FocusPreviousPaneInStack),
             _ => None,
         }
     }
@@ -9,7 +9,10 @@ pub struct EventNameList {
 pub struct Event {
     #[prost(enumeration = "EventType", tag = "1")]
     pub name: i32,
-    #[prost(oneof = "event::Payload", tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13")]
+    #[prost(
+        oneof = "event::Payload",
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21"
+    )]
     pub payload: ::core::option::Option<event::Payload>,
 }
 /// Nested message and enum types in `Event`.
@@ -41,13 +44,119 @@ pub mod event {
         PermissionRequestResultPayload(super::PermissionRequestResultPayload),
         #[prost(message, tag = "13")]
         SessionUpdatePayload(super::SessionUpdatePayload),
+        #[prost(message, tag = "14")]
+        PaneOutputPayload(super::PaneOutputPayload),
+        #[prost(uint32, tag = "15")]
+        PaneOpenedPayload(u32),
+        #[prost(message, tag = "16")]
+        InPlacePaneClosedPayload(super::InPlacePaneClosedPayload),
+        #[prost(message, tag = "17")]
+        RunCommandResultPayload(super::RunCommandResultPayload),
+        #[prost(message, tag = "18")]
+        WebRequestResultPayload(super::WebRequestResultPayload),
+        #[prost(message, tag = "19")]
+        SystemStatePayload(super::SystemStatePayload),
+        #[prost(message, tag = "20")]
+        PendingKeysPayload(super::PendingKeysPayload),
+        #[prost(message, tag = "21")]
+        PaneContentsPayload(super::PaneContentsPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneContentsPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, tag = "2")]
+    pub contents: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingKeysPayload {
+    #[prost(message, repeated, tag = "1")]
+    pub keys: ::prost::alloc::vec::Vec<super::key::Key>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SystemStatePayload {
+    #[prost(uint64, tag = "1")]
+    pub unix_time: u64,
+    #[prost(int32, tag = "2")]
+    pub utc_offset: i32,
+    /// empty if it could not be read
+    #[prost(double, repeated, tag = "3")]
+    pub load_average: ::prost::alloc::vec::Vec<f64>,
+    #[prost(message, optional, tag = "4")]
+    pub battery: ::core::option::Option<BatteryState>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BatteryState {
+    #[prost(uint32, tag = "1")]
+    pub percentage: u32,
+    #[prost(bool, tag = "2")]
+    pub charging: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebRequestResultPayload {
+    #[prost(uint32, optional, tag = "1")]
+    pub status: ::core::option::Option<u32>,
+    #[prost(message, repeated, tag = "2")]
+    pub headers: ::prost::alloc::vec::Vec<Header>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub body: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Header {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub value: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunCommandResultPayload {
+    #[prost(int32, optional, tag = "1")]
+    pub exit_code: ::core::option::Option<i32>,
+    #[prost(bytes = "vec", tag = "2")]
+    pub stdout: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes = "vec", tag = "3")]
+    pub stderr: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InPlacePaneClosedPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, tag = "2")]
+    pub return_value: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaneOutputPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(string, tag = "2")]
+    pub output: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SessionUpdatePayload {
     #[prost(message, repeated, tag = "1")]
     pub session_manifests: ::prost::alloc::vec::Vec<SessionManifest>,
+    #[prost(message, repeated, tag = "2")]
+    pub resurrectable_sessions: ::prost::alloc::vec::Vec<ResurrectableSession>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ResurrectableSession {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// seconds since the session was last serialized
+    #[prost(uint64, tag = "2")]
+    pub creation_time: u64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -123,6 +232,8 @@ pub struct SessionManifest {
     pub connected_clients: u32,
     #[prost(bool, tag = "5")]
     pub is_current_session: bool,
+    #[prost(string, optional, tag = "6")]
+    pub group: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -171,6 +282,18 @@ pub struct PaneInfo {
     pub plugin_url: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "22")]
     pub is_selectable: bool,
+    #[prost(uint32, repeated, tag = "23")]
+    pub child_pids: ::prost::alloc::vec::Vec<u32>,
+    #[prost(string, optional, tag = "24")]
+    pub foreground_process: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "25")]
+    pub cwd: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(bool, tag = "26")]
+    pub has_bell: bool,
+    #[prost(bool, tag = "27")]
+    pub has_activity: bool,
+    #[prost(bool, tag = "28")]
+    pub is_silent: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -195,6 +318,12 @@ pub struct TabInfo {
     pub active_swap_layout_name: ::core::option::Option<::prost::alloc::string::String>,
     #[prost(bool, tag = "10")]
     pub is_swap_layout_dirty: bool,
+    #[prost(uint32, tag = "11")]
+    pub pane_count: u32,
+    #[prost(bool, tag = "12")]
+    pub has_unseen_output: bool,
+    #[prost(string, optional, tag = "13")]
+    pub focused_pane_title: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -209,6 +338,8 @@ pub struct ModeUpdatePayload {
     pub arrow_fonts_support: bool,
     #[prost(string, optional, tag = "5")]
     pub session_name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub custom_mode: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -261,6 +392,24 @@ pub enum EventType {
     FileSystemDelete = 14,
     PermissionRequestResult = 15,
     SessionUpdate = 16,
+    /// / Output was received in a terminal pane this plugin subscribed to
+    PaneOutput = 17,
+    /// / A terminal pane this plugin opened was created
+    PaneOpened = 18,
+    /// / The configuration file was updated by a plugin
+    ConfigWasWrittenToDisk = 19,
+    /// / A terminal pane this plugin opened in place was closed
+    InPlacePaneClosed = 20,
+    /// / A command this plugin ran exited
+    RunCommandResult = 21,
+    /// / A web request this plugin made finished
+    WebRequestResult = 22,
+    /// / The system state, sent periodically to plugins that asked for it
+    SystemStateUpdate = 23,
+    /// / The keys typed so far of an unfinished key sequence
+    PendingKeys = 24,
+    /// / The contents of a terminal pane this plugin asked for
+    PaneContents = 25,
 }
 impl EventType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -286,6 +435,15 @@ impl EventType {
             EventType::FileSystemDelete => "FileSystemDelete",
             EventType::PermissionRequestResult => "PermissionRequestResult",
             EventType::SessionUpdate => "SessionUpdate",
+            EventType::PaneOutput => "PaneOutput",
+            EventType::PaneOpened => "PaneOpened",
+            EventType::ConfigWasWrittenToDisk => "ConfigWasWrittenToDisk",
+            EventType::InPlacePaneClosed => "InPlacePaneClosed",
+            EventType::RunCommandResult => "RunCommandResult",
+            EventType::WebRequestResult => "WebRequestResult",
+            EventType::SystemStateUpdate => "SystemStateUpdate",
+            EventType::PendingKeys => "PendingKeys",
+            EventType::PaneContents => "PaneContents",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -308,6 +466,15 @@ impl EventType {
             "FileSystemDelete" => Some(Self::FileSystemDelete),
             "PermissionRequestResult" => Some(Self::PermissionRequestResult),
             "SessionUpdate" => Some(Self::SessionUpdate),
+            "PaneOutput" => Some(Self::PaneOutput),
+            "PaneOpened" => Some(Self::PaneOpened),
+            "ConfigWasWrittenToDisk" => Some(Self::ConfigWasWrittenToDisk),
+            "InPlacePaneClosed" => Some(Self::InPlacePaneClosed),
+            "RunCommandResult" => Some(Self::RunCommandResult),
+            "WebRequestResult" => Some(Self::WebRequestResult),
+            "SystemStateUpdate" => Some(Self::SystemStateUpdate),
+            "PendingKeys" => Some(Self::PendingKeys),
+            "PaneContents" => Some(Self::PaneContents),
             _ => None,
         }
     }
@@ -350,6 +517,9 @@ pub enum MouseEventName {
     MouseRightClick = 3,
     MouseHold = 4,
     MouseRelease = 5,
+    MouseMiddleClick = 6,
+    MouseHover = 7,
+    MouseHoverEnd = 8,
 }
 impl MouseEventName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -364,6 +534,9 @@ impl MouseEventName {
             MouseEventName::MouseRightClick => "MouseRightClick",
             MouseEventName::MouseHold => "MouseHold",
             MouseEventName::MouseRelease => "MouseRelease",
+            MouseEventName::MouseMiddleClick => "MouseMiddleClick",
+            MouseEventName::MouseHover => "MouseHover",
+            MouseEventName::MouseHoverEnd => "MouseHoverEnd",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -375,6 +548,9 @@ impl MouseEventName {
             "MouseRightClick" => Some(Self::MouseRightClick),
             "MouseHold" => Some(Self::MouseHold),
             "MouseRelease" => Some(Self::MouseRelease),
+            "MouseMiddleClick" => Some(Self::MouseMiddleClick),
+            "MouseHover" => Some(Self::MouseHover),
+            "MouseHoverEnd" => Some(Self::MouseHoverEnd),
             _ => None,
         }
     }
@@ -37,6 +37,9 @@ pub enum InputMode {
     Prompt = 12,
     /// / `Tmux` mode allows for basic tmux keybindings functionality
     Tmux = 13,
+    /// / `Custom` mode is any of the modes defined in the keybinds config, the name of the active
+    /// / one is sent alongside it
+    Custom = 14,
 }
 impl InputMode {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -59,6 +62,7 @@ impl InputMode {
             InputMode::Move => "Move",
             InputMode::Prompt => "Prompt",
             InputMode::Tmux => "Tmux",
+            InputMode::Custom => "Custom",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -78,6 +82,7 @@ impl InputMode {
             "Move" => Some(Self::Move),
             "Prompt" => Some(Self::Prompt),
             "Tmux" => Some(Self::Tmux),
+            "Custom" => Some(Self::Custom),
             _ => None,
         }
     }
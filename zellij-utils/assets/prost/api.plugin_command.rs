@@ -5,7 +5,7 @@ pub struct PluginCommand {
     pub name: i32,
     #[prost(
         oneof = "plugin_command::Payload",
-        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39"
+        tags = "2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61"
     )]
     pub payload: ::core::option::Option<plugin_command::Payload>,
 }
@@ -90,10 +90,90 @@ pub mod plugin_command {
         RequestPluginPermissionPayload(super::RequestPluginPermissionPayload),
         #[prost(message, tag = "39")]
         SwitchSessionPayload(super::SwitchSessionPayload),
+        #[prost(uint32, tag = "40")]
+        SubscribeToPaneOutputPayload(u32),
+        #[prost(uint32, tag = "41")]
+        UnsubscribeFromPaneOutputPayload(u32),
+        #[prost(message, tag = "42")]
+        MessageToPluginPayload(super::MessageToPluginPayload),
+        #[prost(message, tag = "43")]
+        KillSessionsPayload(super::KillSessionsPayload),
+        #[prost(string, tag = "44")]
+        DeleteDeadSessionPayload(::prost::alloc::string::String),
+        #[prost(string, tag = "45")]
+        RenameSessionPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "46")]
+        MoveTabToIndexPayload(super::MoveTabToIndexPayload),
+        #[prost(message, tag = "47")]
+        OpenFileInPlacePayload(super::OpenFilePayload),
+        #[prost(message, tag = "48")]
+        OpenTerminalInPlacePayload(super::OpenFilePayload),
+        #[prost(message, tag = "49")]
+        OpenCommandPaneInPlacePayload(super::OpenCommandPanePayload),
+        #[prost(message, tag = "50")]
+        RebindKeysPayload(super::RebindKeysPayload),
+        #[prost(string, tag = "51")]
+        BlockCliPipeInputPayload(::prost::alloc::string::String),
+        #[prost(string, tag = "52")]
+        UnblockCliPipeInputPayload(::prost::alloc::string::String),
+        #[prost(message, tag = "53")]
+        RunCommandPayload(super::RunCommandPayload),
+        #[prost(message, tag = "54")]
+        WebRequestPayload(super::WebRequestPayload),
+        #[prost(string, tag = "55")]
+        ChangeHostFolderPayload(::prost::alloc::string::String),
+        #[prost(double, tag = "56")]
+        SubscribeToSystemStatePayload(f64),
+        #[prost(message, tag = "57")]
+        SetIntervalPayload(super::SetTimeoutPayload),
+        #[prost(uint32, tag = "58")]
+        CloseTabWithIndexPayload(u32),
+        #[prost(message, tag = "59")]
+        DumpPaneContentsPayload(super::DumpPaneContentsPayload),
+        #[prost(message, tag = "60")]
+        WriteToPaneIdPayload(super::WriteToPaneIdPayload),
+        #[prost(message, tag = "61")]
+        WriteCharsToPaneIdPayload(super::WriteCharsToPaneIdPayload),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteToPaneIdPayload {
+    #[prost(bytes = "vec", tag = "1")]
+    pub bytes_to_write: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub pane_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteCharsToPaneIdPayload {
+    #[prost(string, tag = "1")]
+    pub chars_to_write: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub pane_id: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DumpPaneContentsPayload {
+    #[prost(uint32, tag = "1")]
+    pub pane_id: u32,
+    #[prost(bool, tag = "2")]
+    pub full: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MessageToPluginPayload {
+    #[prost(string, tag = "1")]
+    pub plugin_url: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message_name: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "3")]
+    pub message_payload: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(message, repeated, tag = "4")]
+    pub message_args: ::prost::alloc::vec::Vec<super::pipe_message::Arg>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SwitchSessionPayload {
     #[prost(string, optional, tag = "1")]
     pub name: ::core::option::Option<::prost::alloc::string::String>,
@@ -103,6 +183,58 @@ pub struct SwitchSessionPayload {
     pub pane_id: ::core::option::Option<u32>,
     #[prost(bool, optional, tag = "4")]
     pub pane_id_is_plugin: ::core::option::Option<bool>,
+    #[prost(string, optional, tag = "5")]
+    pub layout: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct KillSessionsPayload {
+    #[prost(string, repeated, tag = "1")]
+    pub session_names: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MoveTabToIndexPayload {
+    #[prost(uint32, tag = "1")]
+    pub tab_index: u32,
+    #[prost(uint32, tag = "2")]
+    pub new_index: u32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RebindKeysPayload {
+    #[prost(string, tag = "1")]
+    pub keybinds: ::prost::alloc::string::String,
+    #[prost(bool, tag = "2")]
+    pub write_config_to_disk: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RunCommandPayload {
+    #[prost(message, optional, tag = "1")]
+    pub command_to_run: ::core::option::Option<super::command::Command>,
+    #[prost(message, repeated, tag = "2")]
+    pub env_variables: ::prost::alloc::vec::Vec<EnvVariable>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WebRequestPayload {
+    #[prost(string, tag = "1")]
+    pub url: ::prost::alloc::string::String,
+    #[prost(enumeration = "HttpVerb", tag = "2")]
+    pub verb: i32,
+    #[prost(message, repeated, tag = "3")]
+    pub headers: ::prost::alloc::vec::Vec<super::event::Header>,
+    #[prost(bytes = "vec", tag = "4")]
+    pub body: ::prost::alloc::vec::Vec<u8>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EnvVariable {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub value: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -254,6 +386,29 @@ pub enum CommandName {
     ReportCrash = 65,
     RequestPluginPermissions = 66,
     SwitchSession = 67,
+    SubscribeToPaneOutput = 68,
+    UnsubscribeFromPaneOutput = 69,
+    PipeMessageToPlugin = 70,
+    KillSessions = 71,
+    DeleteDeadSession = 72,
+    RenameSession = 73,
+    MoveTabToIndex = 74,
+    OpenFileInPlace = 75,
+    OpenTerminalInPlace = 76,
+    OpenCommandPaneInPlace = 77,
+    RebindKeys = 78,
+    BlockCliPipeInput = 79,
+    UnblockCliPipeInput = 80,
+    RunCommand = 81,
+    WebRequest = 82,
+    ChangeHostFolder = 83,
+    SubscribeToSystemState = 84,
+    SetInterval = 85,
+    ClearInterval = 86,
+    CloseTabWithIndex = 87,
+    DumpPaneContents = 88,
+    WriteToPaneId = 89,
+    WriteCharsToPaneId = 90,
 }
 impl CommandName {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -330,6 +485,29 @@ impl CommandName {
             CommandName::ReportCrash => "ReportCrash",
             CommandName::RequestPluginPermissions => "RequestPluginPermissions",
             CommandName::SwitchSession => "SwitchSession",
+            CommandName::SubscribeToPaneOutput => "SubscribeToPaneOutput",
+            CommandName::UnsubscribeFromPaneOutput => "UnsubscribeFromPaneOutput",
+            CommandName::PipeMessageToPlugin => "PipeMessageToPlugin",
+            CommandName::KillSessions => "KillSessions",
+            CommandName::DeleteDeadSession => "DeleteDeadSession",
+            CommandName::RenameSession => "RenameSession",
+            CommandName::MoveTabToIndex => "MoveTabToIndex",
+            CommandName::OpenFileInPlace => "OpenFileInPlace",
+            CommandName::OpenTerminalInPlace => "OpenTerminalInPlace",
+            CommandName::OpenCommandPaneInPlace => "OpenCommandPaneInPlace",
+            CommandName::RebindKeys => "RebindKeys",
+            CommandName::BlockCliPipeInput => "BlockCliPipeInput",
+            CommandName::UnblockCliPipeInput => "UnblockCliPipeInput",
+            CommandName::RunCommand => "RunCommand",
+            CommandName::WebRequest => "WebRequest",
+            CommandName::ChangeHostFolder => "ChangeHostFolder",
+            CommandName::SubscribeToSystemState => "SubscribeToSystemState",
+            CommandName::SetInterval => "SetInterval",
+            CommandName::ClearInterval => "ClearInterval",
+            CommandName::CloseTabWithIndex => "CloseTabWithIndex",
+            CommandName::DumpPaneContents => "DumpPaneContents",
+            CommandName::WriteToPaneId => "WriteToPaneId",
+            CommandName::WriteCharsToPaneId => "WriteCharsToPaneId",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -403,6 +581,61 @@ impl CommandName {
             "ReportCrash" => Some(Self::ReportCrash),
             "RequestPluginPermissions" => Some(Self::RequestPluginPermissions),
             "SwitchSession" => Some(Self::SwitchSession),
+            "SubscribeToPaneOutput" => Some(Self::SubscribeToPaneOutput),
+            "UnsubscribeFromPaneOutput" => Some(Self::UnsubscribeFromPaneOutput),
+            "PipeMessageToPlugin" => Some(Self::PipeMessageToPlugin),
+            "KillSessions" => Some(Self::KillSessions),
+            "DeleteDeadSession" => Some(Self::DeleteDeadSession),
+            "RenameSession" => Some(Self::RenameSession),
+            "MoveTabToIndex" => Some(Self::MoveTabToIndex),
+            "OpenFileInPlace" => Some(Self::OpenFileInPlace),
+            "OpenTerminalInPlace" => Some(Self::OpenTerminalInPlace),
+            "OpenCommandPaneInPlace" => Some(Self::OpenCommandPaneInPlace),
+            "RebindKeys" => Some(Self::RebindKeys),
+            "BlockCliPipeInput" => Some(Self::BlockCliPipeInput),
+            "UnblockCliPipeInput" => Some(Self::UnblockCliPipeInput),
+            "RunCommand" => Some(Self::RunCommand),
+            "WebRequest" => Some(Self::WebRequest),
+            "ChangeHostFolder" => Some(Self::ChangeHostFolder),
+            "SubscribeToSystemState" => Some(Self::SubscribeToSystemState),
+            "SetInterval" => Some(Self::SetInterval),
+            "ClearInterval" => Some(Self::ClearInterval),
+            "CloseTabWithIndex" => Some(Self::CloseTabWithIndex),
+            "DumpPaneContents" => Some(Self::DumpPaneContents),
+            "WriteToPaneId" => Some(Self::WriteToPaneId),
+            "WriteCharsToPaneId" => Some(Self::WriteCharsToPaneId),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum HttpVerb {
+    Get = 0,
+    Post = 1,
+    Put = 2,
+    Delete = 3,
+}
+impl HttpVerb {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            HttpVerb::Get => "Get",
+            HttpVerb::Post => "Post",
+            HttpVerb::Put => "Put",
+            HttpVerb::Delete => "Delete",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "Get" => Some(Self::Get),
+            "Post" => Some(Self::Post),
+            "Put" => Some(Self::Put),
+            "Delete" => Some(Self::Delete),
             _ => None,
         }
     }
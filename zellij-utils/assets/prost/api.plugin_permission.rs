@@ -7,6 +7,12 @@ pub enum PermissionType {
     RunCommands = 3,
     OpenTerminalsOrPlugins = 4,
     WriteToStdin = 5,
+    ReadPaneContents = 6,
+    MessageOtherPlugins = 7,
+    Reconfigure = 8,
+    WebAccess = 9,
+    FullHdAccess = 10,
+    ReadSystemState = 11,
 }
 impl PermissionType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -21,6 +27,12 @@ impl PermissionType {
             PermissionType::RunCommands => "RunCommands",
             PermissionType::OpenTerminalsOrPlugins => "OpenTerminalsOrPlugins",
             PermissionType::WriteToStdin => "WriteToStdin",
+            PermissionType::ReadPaneContents => "ReadPaneContents",
+            PermissionType::MessageOtherPlugins => "MessageOtherPlugins",
+            PermissionType::Reconfigure => "Reconfigure",
+            PermissionType::WebAccess => "WebAccess",
+            PermissionType::FullHdAccess => "FullHdAccess",
+            PermissionType::ReadSystemState => "ReadSystemState",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -32,6 +44,12 @@ impl PermissionType {
             "RunCommands" => Some(Self::RunCommands),
             "OpenTerminalsOrPlugins" => Some(Self::OpenTerminalsOrPlugins),
             "WriteToStdin" => Some(Self::WriteToStdin),
+            "ReadPaneContents" => Some(Self::ReadPaneContents),
+            "MessageOtherPlugins" => Some(Self::MessageOtherPlugins),
+            "Reconfigure" => Some(Self::Reconfigure),
+            "WebAccess" => Some(Self::WebAccess),
+            "FullHdAccess" => Some(Self::FullHdAccess),
+            "ReadSystemState" => Some(Self::ReadSystemState),
             _ => None,
         }
     }
@@ -7,6 +7,42 @@ pub struct Style {
     pub rounded_corners: bool,
     #[prost(bool, tag = "3")]
     pub hide_session_name: bool,
+    #[prost(message, optional, tag = "4")]
+    pub styling: ::core::option::Option<Styling>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Styling {
+    #[prost(message, optional, tag = "1")]
+    pub text_unselected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "2")]
+    pub text_selected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "3")]
+    pub ribbon_unselected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "4")]
+    pub ribbon_selected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "5")]
+    pub frame_unselected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "6")]
+    pub frame_selected: ::core::option::Option<StyleDeclaration>,
+    #[prost(message, optional, tag = "7")]
+    pub frame_highlight: ::core::option::Option<StyleDeclaration>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct StyleDeclaration {
+    #[prost(message, optional, tag = "1")]
+    pub base: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "2")]
+    pub background: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "3")]
+    pub emphasis_0: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "4")]
+    pub emphasis_1: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "5")]
+    pub emphasis_2: ::core::option::Option<Color>,
+    #[prost(message, optional, tag = "6")]
+    pub emphasis_3: ::core::option::Option<Color>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -46,6 +46,10 @@ pub struct CliArgs {
     /// Specify emitting additional debug information
     #[clap(short, long, value_parser)]
     pub debug: bool,
+
+    /// Reload plugins whenever their wasm file changes on disk (useful when developing plugins)
+    #[clap(long, value_parser)]
+    pub watch_plugins: bool,
 }
 
 #[derive(Debug, Subcommand, Clone, Serialize, Deserialize)]
@@ -74,7 +78,11 @@ pub enum SessionCommand {
 pub enum Sessions {
     /// List active sessions
     #[clap(visible_alias = "ls")]
-    ListSessions,
+    ListSessions {
+        /// Only list sessions belonging to this session group
+        #[clap(short, long, value_parser)]
+        group: Option<String>,
+    },
 
     /// Attach to a session
     #[clap(visible_alias = "a")]
@@ -87,15 +95,39 @@ pub enum Sessions {
         #[clap(short, long, value_parser)]
         create: bool,
 
+        /// Create a detached session in the background if one does not exist, without attaching
+        /// to it.
+        #[clap(short = 'b', long, value_parser)]
+        create_background: bool,
+
         /// Number of the session index in the active sessions ordered creation date.
         #[clap(long, value_parser)]
         index: Option<usize>,
 
+        /// Resurrect the session from its serialized layout if it is no longer running.
+        #[clap(short, long, value_parser)]
+        resurrect: bool,
+
         /// Change the behaviour of zellij
         #[clap(subcommand, name = "options")]
         options: Option<Box<SessionCommand>>,
     },
 
+    /// Run a session in the foreground without a terminal or a client attached to it, eg. as a
+    /// systemd service. When started through systemd's socket activation the session listens on
+    /// the socket it was passed, which must be at the session's path in the zellij socket
+    /// directory.
+    Daemon {
+        /// Name of the session, a name is generated if none is given (required with socket
+        /// activation)
+        #[clap(value_parser)]
+        session_name: Option<String>,
+
+        /// Resurrect the session from its serialized layout if there is one.
+        #[clap(short, long, value_parser)]
+        resurrect: bool,
+    },
+
     /// Kill the specific session
     #[clap(visible_alias = "k")]
     KillSession {
@@ -115,6 +147,35 @@ pub enum Sessions {
     #[clap(visible_alias = "ac")]
     #[clap(subcommand)]
     Action(CliAction),
+    /// Drive a session with a subset of tmux's control mode (`tmux -CC`) commands, read from
+    /// STDIN
+    #[clap(visible_alias = "cc")]
+    ControlMode,
+    /// Send data to plugins (or to a terminal pane's STDIN), read from STDIN line by line unless
+    /// a payload is given. Each line is only sent once the previous one was handled, plugins can
+    /// hold the stream back for as long as they need.
+    Pipe {
+        /// Name of the pipe, plugins use it to tell pipes apart
+        #[clap(short, long, value_parser)]
+        name: Option<String>,
+
+        /// Data to send instead of reading it from STDIN
+        #[clap(value_parser)]
+        payload: Option<String>,
+
+        /// Arguments sent along with every message, eg. "key=value,key2=value2"
+        #[clap(short, long, value_parser)]
+        args: Option<PluginUserConfiguration>,
+
+        /// Plugin URL or alias to send the data to, all running plugins get it if none is given
+        #[clap(short, long, value_parser, conflicts_with("pane_id"))]
+        plugin: Option<String>,
+
+        /// Send the data to the STDIN of the terminal pane with this id instead (eg. the
+        /// ZELLIJ_PANE_ID of a shell running in it)
+        #[clap(long, value_parser)]
+        pane_id: Option<u32>,
+    },
     /// Run a command in a new pane
     #[clap(visible_alias = "r")]
     Run {
@@ -145,6 +206,48 @@ pub enum Sessions {
         /// Start the command suspended, only running after you first presses ENTER
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         start_suspended: bool,
+
+        /// Open the new pane in place of the current pane, temporarily suspending it
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating"),
+            conflicts_with("direction")
+        )]
+        in_place: bool,
+
+        /// Block until the command exits and exit with its exit status
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        wait: bool,
+    },
+    /// Load a plugin in a new pane
+    #[clap(visible_alias = "p")]
+    Plugin {
+        /// Plugin URL, can either start with http(s), file: or zellij:, or be a plugin alias
+        #[clap(last(true), required(true))]
+        url: String,
+
+        /// Plugin configuration
+        #[clap(short, long, value_parser)]
+        configuration: Option<PluginUserConfiguration>,
+
+        /// Open the new pane in floating mode
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        floating: bool,
+
+        /// Open the new pane in place of the current pane, temporarily suspending it
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating")
+        )]
+        in_place: bool,
     },
     /// Edit file with default $EDITOR / $VISUAL
     #[clap(visible_alias = "e")]
@@ -167,13 +270,22 @@ pub enum Sessions {
         #[clap(long, value_parser)]
         cwd: Option<PathBuf>,
     },
+    /// Convert a configuration file of the YAML format of older versions to KDL, the converted
+    /// configuration is printed to STDOUT
     ConvertConfig {
+        /// The YAML configuration file to convert
         old_config_file: PathBuf,
     },
+    /// Convert a layout file of the YAML format of older versions to KDL, the converted layout is
+    /// printed to STDOUT
     ConvertLayout {
+        /// The YAML layout file to convert
         old_layout_file: PathBuf,
     },
+    /// Convert a theme file of the YAML format of older versions to KDL, the converted theme is
+    /// printed to STDOUT
     ConvertTheme {
+        /// The YAML theme file to convert
         old_theme_file: PathBuf,
     },
 }
@@ -183,10 +295,20 @@ pub enum CliAction {
     /// Write bytes to the terminal.
     Write {
         bytes: Vec<u8>,
+
+        /// Write to the terminal pane with this id (eg. the ZELLIJ_PANE_ID of a shell running in
+        /// it) instead of the focused one
+        #[clap(long, value_parser)]
+        pane_id: Option<u32>,
     },
     /// Write characters to the terminal.
     WriteChars {
         chars: String,
+
+        /// Write to the terminal pane with this id (eg. the ZELLIJ_PANE_ID of a shell running in
+        /// it) instead of the focused one
+        #[clap(long, value_parser)]
+        pane_id: Option<u32>,
     },
     /// [increase|decrease] the focused panes area at the [left|down|up|right] border.
     Resize {
@@ -206,6 +328,20 @@ pub enum CliAction {
     MoveFocusOrTab {
         direction: Direction,
     },
+    /// Focus the pane with this id, switching to its tab and showing it if it is suppressed
+    FocusPane {
+        /// The id of the pane (eg. the ZELLIJ_PANE_ID of a shell running in it)
+        #[clap(long, value_parser)]
+        id: u32,
+
+        /// The id is that of a plugin pane rather than of a terminal pane
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        plugin: bool,
+
+        /// Show the floating panes if the pane is a floating one and they are hidden
+        #[clap(long, value_parser, default_value("false"), takes_value(false))]
+        float_if_hidden: bool,
+    },
     /// Change the location of the focused pane in the specified direction or rotate forwrads
     /// [right|left|up|down]
     MovePane {
@@ -222,6 +358,10 @@ pub enum CliAction {
         /// Dump the pane with full scrollback
         #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
         full: bool,
+
+        /// Preserve the ANSI styling (colors, bold, etc.) of the pane contents
+        #[clap(short, long, value_parser, default_value("false"), takes_value(false))]
+        ansi: bool,
     },
     /// Open the pane scrollback in your default editor
     EditScrollback,
@@ -233,6 +373,15 @@ pub enum CliAction {
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt in focus pane (needs a shell emitting OSC 133
+    /// prompt marks).
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt in focus pane (needs a shell emitting OSC 133
+    /// prompt marks).
+    ScrollToNextPrompt,
+    /// Copy the output of the last command run in focus pane to the clipboard (needs a shell
+    /// emitting OSC 133 prompt marks).
+    CopyLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -243,10 +392,35 @@ pub enum CliAction {
     HalfPageScrollDown,
     /// Toggle between fullscreen focus pane and normal layout.
     ToggleFullscreen,
+    /// Spread out the stack of the focused pane, or stack the focused pane with the panes of the
+    /// same width above and below it with only the focused pane expanded.
+    ToggleExpandPane,
+    /// Focus and expand the next pane in the stack of the focused pane, wrapping around.
+    FocusNextPaneInStack,
+    /// Focus and expand the previous pane in the stack of the focused pane, wrapping around.
+    FocusPreviousPaneInStack,
     /// Toggle frames around panes in the UI
     TogglePaneFrames,
     /// Toggle between sending text commands to all panes on the current tab and normal mode.
     ToggleActiveSyncTab,
+    /// Toggle whether the focused pane is in the sync group of its tab, input typed into a pane in
+    /// the sync group is sent to all of its panes
+    ToggleActivePaneSync,
+    /// Add the focused pane to the pane group of its tab or remove it from it, bulk actions apply to
+    /// all of the panes in the group
+    TogglePaneInGroup,
+    /// Remove all panes from the pane group of the focused tab
+    ClearPaneGroup,
+    /// Close all panes in the pane group of the focused tab
+    CloseGroupedPanes,
+    /// Turn the tiled panes in the pane group of the focused tab into floating panes
+    FloatGroupedPanes,
+    /// Move all panes in the pane group of the focused tab into a new tab, one of them is tiled and
+    /// the rest float in it
+    BreakGroupedPanesToNewTab,
+    /// Add all panes in the pane group of the focused tab to its sync group, or remove them from it
+    /// if they are all already in it
+    SyncGroupedPanes,
     /// Open a new pane in the specified direction [right|down]
     /// If no direction is specified, will try to use the biggest available space.
     NewPane {
@@ -294,6 +468,26 @@ pub enum CliAction {
         start_suspended: bool,
         #[clap(short, long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
+        /// Open the new pane in place of the current pane, temporarily suspending it
+        #[clap(
+            short,
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            conflicts_with("floating"),
+            conflicts_with("direction")
+        )]
+        in_place: bool,
+        /// Block until the command exits and exit with its exit status
+        #[clap(
+            long,
+            value_parser,
+            default_value("false"),
+            takes_value(false),
+            requires("command")
+        )]
+        wait: bool,
     },
     /// Open the specified file in a new zellij pane with your default EDITOR
     Edit {
@@ -323,6 +517,9 @@ pub enum CliAction {
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all fdirectionloating panes in the current Tab, open one if none exist
     ToggleFloatingPanes,
+    /// Pin the focused floating pane so it stays visible when floating panes are hidden, or unpin
+    /// it if it is pinned
+    TogglePanePinned,
     /// Close the focused pane.
     ClosePane,
     /// Renames the focused pane
@@ -337,6 +534,11 @@ pub enum CliAction {
     GoToPreviousTab,
     /// Close the current tab.
     CloseTab,
+    /// Reopen the most recently closed pane (commands are held until they are run again)
+    ReopenClosedPane,
+    /// Reopen the most recently closed tab with its layout (commands are held until they are run
+    /// again)
+    ReopenClosedTab,
     /// Go to tab with index [index]
     GoToTab {
         index: u32,
@@ -348,7 +550,7 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         create: bool,
     },
-    /// Renames the focused pane
+    /// Renames the focused tab
     RenameTab {
         name: String,
     },
@@ -356,7 +558,7 @@ pub enum CliAction {
     UndoRenameTab,
     /// Create a new tab, optionally with a specified tab layout and name
     NewTab {
-        /// Layout to use for the new tab
+        /// Layout to use for the new tab, all of its tabs are opened if it has more than one
         #[clap(short, long, value_parser)]
         layout: Option<PathBuf>,
 
@@ -390,4 +592,55 @@ pub enum CliAction {
         #[clap(short, long, value_parser)]
         configuration: Option<PluginUserConfiguration>,
     },
+    /// Re-run the command of the focused pane in place after it has exited
+    RespawnPane,
+    /// Switch to the next session in the same session group
+    SwitchWorkspace,
+    /// Show or hide the session's scratchpad floating pane, spawning it on first use
+    ToggleScratchpad,
+    /// Switch the session to another theme, its colors are changed for all connected clients and
+    /// plugins (use `zellij setup --list-themes` to see the available themes)
+    SwitchTheme {
+        /// The name of the theme
+        theme: String,
+    },
+    /// Query the foreground process, working directory and child pids of all terminal panes
+    QueryPaneProcesses,
+    /// Load the contents of a file into the paste buffer, reads from STDIN if no file or `-` is
+    /// given
+    LoadBuffer {
+        path: Option<PathBuf>,
+    },
+    /// Save the contents of the paste buffer to a file
+    SaveBuffer {
+        path: PathBuf,
+    },
+    /// Paste the contents of the paste buffer into the focused pane
+    PasteBuffer {
+        /// Paste into the terminal pane with this id instead of the focused pane
+        #[clap(short, long, value_parser)]
+        pane: Option<u32>,
+    },
+    /// Dump the layout of the current session, including its floating panes and the commands
+    /// running in it, as KDL to stdout
+    DumpLayout,
+    /// Enter copy mode in the focused pane to select and copy text with the keyboard
+    EnterCopyMode,
+    /// Label the urls, paths and hyperlinks in the focused pane, opening the one whose label is
+    /// typed next with the configured link opener
+    LinkHints,
+    /// Move the focused pane into a new tab of its own, keeping its process running
+    BreakPane,
+    /// Move the focused pane to the tab on the right of the current one
+    BreakPaneRight,
+    /// Move the focused pane to the tab on the left of the current one
+    BreakPaneLeft,
+    /// Move the focused pane to the tab with index [index]
+    MovePaneToTab {
+        index: u32,
+    },
+    /// Move the active tab one position in the specified direction [right|left]
+    MoveTab {
+        direction: Direction,
+    },
 }
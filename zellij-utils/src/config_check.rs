@@ -0,0 +1,527 @@
+//! Checks configuration and layout files for `zellij setup --check`.
+//!
+//! Loading a configuration stops at its first error, the checks here go through the whole file
+//! so that every problem in it is reported at once, along with the line and column it is on. They
+//! also look for mistakes that loading the file lets through, like layouts or plugins that don't
+//! exist and theme colors that are out of range.
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use kdl::{KdlDocument, KdlNode};
+
+#[cfg(not(target_family = "wasm"))]
+use crate::consts::ASSET_MAP;
+use crate::data::Key;
+use crate::input::actions::Action;
+use crate::input::config::{Config, ConfigError};
+use crate::input::keybinds::Keybinds;
+use crate::input::layout::{Layout, RunPluginLocation};
+use crate::input::options::Options;
+use crate::input::theme::Themes;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warning => write!(f, "warning"),
+        }
+    }
+}
+
+/// A problem found in a file, `line` and `column` start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub line: usize,
+    pub column: usize,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Diagnostic {
+    fn new(raw: &str, offset: usize, severity: Severity, message: impl Into<String>) -> Self {
+        // spans start with the whitespace before a node, the diagnostic points at the node itself
+        let offset = raw
+            .get(offset..)
+            .map(|rest| offset + rest.len() - rest.trim_start().len())
+            .unwrap_or(offset);
+        let before_offset = raw.get(..offset).unwrap_or(raw);
+        let line = before_offset.matches('\n').count() + 1;
+        let column = before_offset
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        Diagnostic {
+            line,
+            column,
+            severity,
+            message: message.into(),
+        }
+    }
+    fn from_config_error(raw: &str, error: ConfigError, fallback_offset: usize) -> Self {
+        match error {
+            ConfigError::KdlError(kdl_error) => Diagnostic::new(
+                raw,
+                kdl_error.offset.unwrap_or(fallback_offset),
+                Severity::Error,
+                kdl_error.error_message,
+            ),
+            ConfigError::KdlDeserializationError(kdl_error) => Diagnostic::new(
+                raw,
+                kdl_error.span.offset(),
+                Severity::Error,
+                kdl_error
+                    .help
+                    .or(kdl_error.label)
+                    .unwrap_or("Failed to parse KDL"),
+            ),
+            error => Diagnostic::new(raw, fallback_offset, Severity::Error, error.to_string()),
+        }
+    }
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}: {}",
+            self.line, self.column, self.severity, self.message
+        )
+    }
+}
+
+/// Checks a configuration file, the layouts and plugins it refers to are looked for in
+/// `layout_dir` and `plugin_dir` (as well as among the builtin ones)
+pub fn check_config(
+    raw_config: &str,
+    layout_dir: Option<&Path>,
+    plugin_dir: &Path,
+) -> Vec<Diagnostic> {
+    let mut checker = ConfigChecker {
+        raw_config,
+        layout_dir,
+        plugin_dir,
+        plugin_aliases: vec![],
+        diagnostics: vec![],
+    };
+    checker.check();
+    checker.diagnostics
+}
+
+/// Checks a layout file
+pub fn check_layout(raw_layout: &str, path_to_layout: &Path) -> Vec<Diagnostic> {
+    match Layout::from_kdl(raw_layout, path_to_layout.display().to_string(), None, None) {
+        Ok(_) => vec![],
+        Err(e) => vec![Diagnostic::from_config_error(raw_layout, e, 0)],
+    }
+}
+
+struct ConfigChecker<'a> {
+    raw_config: &'a str,
+    layout_dir: Option<&'a Path>,
+    plugin_dir: &'a Path,
+    plugin_aliases: Vec<String>, // the ones of the default configuration and of this one
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> ConfigChecker<'a> {
+    fn check(&mut self) {
+        let document: KdlDocument = match self.raw_config.parse() {
+            Ok(document) => document,
+            Err(e) => {
+                // nothing else can be checked in a file that isn't valid KDL
+                self.report_config_error(ConfigError::KdlDeserializationError(e), 0);
+                return;
+            },
+        };
+        let options = match Options::from_kdl(&document) {
+            Ok(options) => options,
+            Err(e) => {
+                self.report_config_error(e, 0);
+                Options::default()
+            },
+        };
+        self.check_layout_references(&document);
+        if let Some(plugins) = document.get("plugins") {
+            self.check_plugins(plugins);
+        }
+        if let Some(keybinds) = document.get("keybinds") {
+            self.check_keybinds(keybinds, &options);
+        }
+        if let Some(themes) = document.get("themes") {
+            self.check_themes(themes);
+        }
+        // loading whole sections again reports some problems that were already found, the
+        // first diagnostic at a position is the one with the more specific message
+        self.diagnostics
+            .sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        self.diagnostics
+            .dedup_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+    }
+    fn report(&mut self, offset: usize, severity: Severity, message: impl Into<String>) {
+        self.diagnostics
+            .push(Diagnostic::new(self.raw_config, offset, severity, message));
+    }
+    fn report_config_error(&mut self, error: ConfigError, fallback_offset: usize) {
+        self.diagnostics.push(Diagnostic::from_config_error(
+            self.raw_config,
+            error,
+            fallback_offset,
+        ));
+    }
+    fn check_layout_references(&mut self, document: &KdlDocument) {
+        if let Some(default_layout) = document.get("default_layout") {
+            if let Some(layout) = default_layout.get(0).and_then(|l| l.value().as_string()) {
+                let layout_dir = self.layout_dir.map(|d| d.to_path_buf());
+                if Layout::stringified_from_path_or_default(
+                    Some(&PathBuf::from(layout)),
+                    layout_dir,
+                )
+                .is_err()
+                {
+                    self.report(
+                        default_layout.span().offset(),
+                        Severity::Error,
+                        format!("Layout not found: '{}'", layout),
+                    );
+                }
+            }
+        }
+        for dir_option in ["layout_dir", "theme_dir"] {
+            if let Some(dir_node) = document.get(dir_option) {
+                if let Some(dir) = dir_node.get(0).and_then(|d| d.value().as_string()) {
+                    if !Path::new(dir).is_dir() {
+                        self.report(
+                            dir_node.span().offset(),
+                            Severity::Warning,
+                            format!("Directory not found: '{}'", dir),
+                        );
+                    }
+                }
+            }
+        }
+    }
+    fn check_plugins(&mut self, plugins: &KdlNode) {
+        let plugin_nodes = plugins.children().map(|c| c.nodes()).unwrap_or_default();
+        for plugin in plugin_nodes {
+            self.plugin_aliases.push(plugin.name().value().to_owned());
+            let plugin_config = plugin.children();
+            let location = plugin_config
+                .and_then(|c| c.get_arg("location"))
+                .and_then(|l| l.as_string());
+            let path = match location {
+                Some(location) => match RunPluginLocation::parse(location, None) {
+                    Ok(RunPluginLocation::File(path)) => path,
+                    Ok(RunPluginLocation::Zellij(tag)) => PathBuf::from(tag.to_string()),
+                    Ok(RunPluginLocation::Remote(_)) => continue,
+                    Err(e) => {
+                        self.report(plugin.span().offset(), Severity::Error, e.to_string());
+                        continue;
+                    },
+                },
+                None => match plugin_config
+                    .and_then(|c| c.get_arg("path"))
+                    .and_then(|p| p.as_string())
+                {
+                    Some(path) => PathBuf::from(path),
+                    None => {
+                        self.report(
+                            plugin.span().offset(),
+                            Severity::Error,
+                            "Plugin path or location not found or invalid",
+                        );
+                        continue;
+                    },
+                },
+            };
+            if !self.plugin_exists(&path) {
+                self.report(
+                    plugin.span().offset(),
+                    Severity::Error,
+                    format!("Plugin not found: '{}'", path.display()),
+                );
+            }
+        }
+    }
+    fn plugin_exists(&self, path: &Path) -> bool {
+        // the same lookup as when the plugin is loaded
+        is_builtin_plugin(path)
+            || path.exists()
+            || path.with_extension("wasm").exists()
+            || self.plugin_dir.join(path).with_extension("wasm").exists()
+    }
+    fn check_keybinds(&mut self, keybinds: &KdlNode, options: &Options) {
+        // plugins this file refers to can also be ones defined by the default configuration
+        if let Ok(default_config) = Config::from_default_assets() {
+            self.plugin_aliases
+                .extend(default_config.plugins.0.keys().map(|tag| tag.to_string()));
+        }
+        let mode_blocks = keybinds.children().map(|c| c.nodes()).unwrap_or_default();
+        for mode_block in mode_blocks {
+            let bind_nodes = mode_block
+                .children()
+                .map(|c| c.nodes())
+                .unwrap_or_default()
+                .iter()
+                .filter(|n| n.name().value() == "bind");
+            let mut bound_keys: Vec<Key> = vec![];
+            for bind_node in bind_nodes {
+                self.check_bound_keys(bind_node, &mut bound_keys);
+                let action_nodes = bind_node.children().map(|c| c.nodes()).unwrap_or_default();
+                for action_node in action_nodes {
+                    match Action::try_from((action_node, options)) {
+                        Ok(action) => self.check_action_arguments(action_node, &action),
+                        Err(e) => self.report_config_error(e, action_node.span().offset()),
+                    }
+                }
+            }
+        }
+        // whatever spans more than a single binding (eg. the names of modes)
+        if let Err(e) = Keybinds::from_kdl(keybinds, Keybinds::default(), options) {
+            self.report_config_error(e, keybinds.span().offset());
+        }
+    }
+    fn check_bound_keys(&mut self, bind_node: &KdlNode, bound_keys: &mut Vec<Key>) {
+        for entry in bind_node.entries() {
+            let keys_str = match entry.value().as_string() {
+                Some(keys_str) => keys_str,
+                None => {
+                    self.report(entry.span().offset(), Severity::Error, "Not a string");
+                    continue;
+                },
+            };
+            match Key::sequence_from_str(keys_str) {
+                Ok(keys) => match keys.as_slice() {
+                    [key] if bound_keys.contains(key) => {
+                        self.report(
+                            entry.span().offset(),
+                            Severity::Warning,
+                            format!(
+                                "'{}' is bound more than once in this block, only its last binding is used",
+                                keys_str
+                            ),
+                        )
+                    },
+                    [key] => bound_keys.push(*key),
+                    _ => {},
+                },
+                Err(_) => self.report(
+                    entry.span().offset(),
+                    Severity::Error,
+                    format!("Invalid key: '{}'", keys_str),
+                ),
+            }
+        }
+    }
+    fn check_action_arguments(&mut self, action_node: &KdlNode, action: &Action) {
+        match action {
+            Action::GoToTab(0) => self.report(
+                action_node.span().offset(),
+                Severity::Warning,
+                "Tabs are numbered from 1, GoToTab 0 goes to the first tab",
+            ),
+            Action::LaunchOrFocusPlugin(run_plugin, ..) => match &run_plugin.location {
+                RunPluginLocation::File(path) if !self.plugin_exists(path) => self.report(
+                    action_node.span().offset(),
+                    Severity::Error,
+                    format!("Plugin not found: '{}'", path.display()),
+                ),
+                RunPluginLocation::Zellij(tag)
+                    if !self.plugin_aliases.contains(&tag.to_string()) =>
+                {
+                    self.report(
+                        action_node.span().offset(),
+                        Severity::Error,
+                        format!(
+                            "Unknown plugin: '{}', it needs to be defined in the plugins block",
+                            tag
+                        ),
+                    )
+                },
+                _ => {},
+            },
+            _ => {},
+        }
+    }
+    fn check_themes(&mut self, themes: &KdlNode) {
+        let theme_nodes = themes.children().map(|c| c.nodes()).unwrap_or_default();
+        for theme in theme_nodes {
+            self.check_theme_colors(theme);
+        }
+        // whatever spans more than a single color (eg. missing colors)
+        if let Err(e) = Themes::from_kdl(themes) {
+            self.report_config_error(e, themes.span().offset());
+        }
+    }
+    fn check_theme_colors(&mut self, node: &KdlNode) {
+        if let Some(children) = node.children() {
+            // a theme or one of its UI elements (eg. "frame_selected { base 0 255 0; }")
+            for child in children.nodes() {
+                self.check_theme_colors(child);
+            }
+            return;
+        }
+        let values: Vec<_> = node.entries().iter().map(|e| e.value()).collect();
+        let is_valid_hex = |s: &str| {
+            s.starts_with('#')
+                && (s.len() == 4 || s.len() == 7)
+                && s[1..].chars().all(|c| c.is_ascii_hexdigit())
+        };
+        let in_range = |n: i64| (0..=255).contains(&n);
+        let problem = match values.as_slice() {
+            [value] => match (value.as_string(), value.as_i64()) {
+                (Some(hex), _) if !is_valid_hex(hex) => {
+                    Some("Invalid hex color, expected #rgb or #rrggbb")
+                },
+                (_, Some(n)) if !in_range(n) => Some("Color index out of range, expected 0 to 255"),
+                (None, None) => Some("Invalid color"),
+                _ => None,
+            },
+            [r, g, b] => match (r.as_i64(), g.as_i64(), b.as_i64()) {
+                (Some(r), Some(g), Some(b)) if in_range(r) && in_range(g) && in_range(b) => None,
+                (Some(_), Some(_), Some(_)) => Some("RGB value out of range, expected 0 to 255"),
+                _ => Some("Invalid RGB color, expected three numbers"),
+            },
+            _ => Some("Invalid color, expected a hex color, an RGB color or a color index"),
+        };
+        if let Some(problem) = problem {
+            self.report(
+                node.span().offset(),
+                Severity::Error,
+                format!("{}: '{}'", problem, node.to_string().trim()),
+            );
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn is_builtin_plugin(path: &Path) -> bool {
+    !cfg!(feature = "disable_automatic_asset_installation")
+        && ASSET_MAP.contains_key(&PathBuf::from("plugins").join(path.with_extension("wasm")))
+}
+
+#[cfg(target_family = "wasm")]
+fn is_builtin_plugin(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(raw_config: &str) -> Vec<String> {
+        check_config(raw_config, None, Path::new("/nonexistent"))
+            .iter()
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn well_defined_config_has_no_diagnostics() {
+        let config = String::from_utf8(crate::setup::DEFAULT_CONFIG.to_vec()).unwrap();
+        assert_eq!(check(&config), Vec::<String>::new());
+    }
+
+    #[test]
+    fn reports_every_invalid_action_with_its_line() {
+        let config = r#"
+keybinds {
+    normal {
+        bind "Alt h" { MoveFocus "Sideways"; }
+        bind "Alt j" { MoveFocus "Down"; }
+        bind "Alt k" { NoSuchAction; }
+    }
+}
+"#;
+        let diagnostics = check(config);
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert!(
+            diagnostics[0].starts_with("4:24: error:"),
+            "{:?}",
+            diagnostics
+        );
+        assert!(
+            diagnostics[1].starts_with("6:24: error:"),
+            "{:?}",
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn reports_keys_bound_twice_in_a_block() {
+        let config = r#"
+keybinds {
+    normal {
+        bind "Alt n" { NewPane; }
+        bind "Alt n" { NewTab; }
+    }
+}
+"#;
+        let diagnostics = check(config);
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert!(
+            diagnostics[0].starts_with("5:14: warning:"),
+            "{:?}",
+            diagnostics
+        );
+    }
+
+    #[test]
+    fn reports_theme_colors_out_of_range() {
+        let config = r##"
+themes {
+    mine {
+        fg 300 0 0
+        bg "#12345g"
+        black 0 0 0
+        red 0 0 0
+        green 0 0 0
+        yellow 0 0 0
+        blue 0 0 0
+        magenta 0 0 0
+        cyan 0 0 0
+        white 255 255 255
+        orange 256
+    }
+}
+"##;
+        let diagnostics = check(config);
+        assert_eq!(diagnostics.len(), 3, "{:?}", diagnostics);
+        assert!(diagnostics[0].starts_with("4:9: error: RGB value out of range"));
+        assert!(diagnostics[1].starts_with("5:9: error: Invalid hex color"));
+        assert!(diagnostics[2].starts_with("14:9: error: Color index out of range"));
+    }
+
+    #[test]
+    fn reports_missing_layouts_and_plugins() {
+        let config = r#"
+default_layout "no-such-layout"
+plugins {
+    mine { location "file:/no/such/plugin.wasm"; }
+}
+"#;
+        let diagnostics = check(config);
+        assert_eq!(diagnostics.len(), 2, "{:?}", diagnostics);
+        assert!(diagnostics[0].starts_with("2:1: error: Layout not found"));
+        assert!(diagnostics[1].starts_with("4:5: error: Plugin not found"));
+    }
+
+    #[test]
+    fn reports_invalid_kdl() {
+        let config = "keybinds {\n    normal {\n}\n";
+        let diagnostics = check(config);
+        assert_eq!(diagnostics.len(), 1, "{:?}", diagnostics);
+        assert!(diagnostics[0].contains("error"));
+    }
+}
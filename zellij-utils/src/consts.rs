@@ -12,7 +12,25 @@ pub const ZELLIJ_CONFIG_DIR_ENV: &str = "ZELLIJ_CONFIG_DIR";
 pub const ZELLIJ_LAYOUT_DIR_ENV: &str = "ZELLIJ_LAYOUT_DIR";
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const DEFAULT_SCROLL_BUFFER_SIZE: usize = 10_000;
+#[cfg(target_os = "macos")]
+pub const DEFAULT_LINK_OPENER: &str = "open";
+#[cfg(not(target_os = "macos"))]
+pub const DEFAULT_LINK_OPENER: &str = "xdg-open";
 pub static SCROLL_BUFFER_SIZE: OnceCell<usize> = OnceCell::new();
+pub static SCROLLBACK_SPILLOVER: OnceCell<bool> = OnceCell::new();
+pub const DEFAULT_SCROLLBACK_SPILLOVER_LIMIT: usize = 100_000;
+pub static SCROLLBACK_SPILLOVER_LIMIT: OnceCell<usize> = OnceCell::new();
+pub static WORD_CHARACTERS: OnceCell<String> = OnceCell::new();
+pub static PANE_FRAME_TITLE: OnceCell<String> = OnceCell::new();
+pub static LINK_OPENER: OnceCell<String> = OnceCell::new();
+pub static BELL_COMMAND: OnceCell<String> = OnceCell::new();
+pub static MONITOR_SILENCE: OnceCell<u64> = OnceCell::new(); // seconds
+pub static PANE_CLOSE_CONFIRMATION: OnceCell<bool> = OnceCell::new();
+pub static ON_SESSION_CREATE_COMMAND: OnceCell<String> = OnceCell::new();
+pub static ON_CLIENT_ATTACH_COMMAND: OnceCell<String> = OnceCell::new();
+pub static ON_CLIENT_DETACH_COMMAND: OnceCell<String> = OnceCell::new();
+pub static SET_TERMINAL_TITLE: OnceCell<bool> = OnceCell::new();
+pub static TERMINAL_TITLE_TEMPLATE: OnceCell<String> = OnceCell::new();
 pub static DEBUG_MODE: OnceCell<bool> = OnceCell::new();
 
 pub const SYSTEM_DEFAULT_CONFIG_DIR: &str = "/etc/zellij";
@@ -40,6 +58,13 @@ lazy_static! {
         ZELLIJ_CACHE_DIR.join("permissions.kdl");
     pub static ref ZELLIJ_SESSION_INFO_CACHE_DIR: PathBuf =
         ZELLIJ_CACHE_DIR.join(VERSION).join("session_info");
+    pub static ref ZELLIJ_SESSION_LAYOUT_CACHE_DIR: PathBuf =
+        ZELLIJ_CACHE_DIR.join(VERSION).join("session_layouts");
+    pub static ref ZELLIJ_SESSION_LAYOUT_STAGING_DIR: PathBuf = ZELLIJ_CACHE_DIR
+        .join(VERSION)
+        .join("session_layouts_staging");
+    pub static ref ZELLIJ_PLUGIN_DOWNLOAD_CACHE_DIR: PathBuf =
+        ZELLIJ_CACHE_DIR.join("plugin_downloads");
 }
 
 pub const FEATURES: &[&str] = &[
@@ -95,6 +120,7 @@ mod not_wasm {
             add_plugin!(assets, "tab-bar.wasm");
             add_plugin!(assets, "strider.wasm");
             add_plugin!(assets, "session-manager.wasm");
+            add_plugin!(assets, "pane-switcher.wasm");
             assets
         };
     }
@@ -2,10 +2,11 @@ use crate::input::actions::Action;
 use crate::input::config::ConversionError;
 use clap::ArgEnum;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use strum_macros::{Display, EnumDiscriminants, EnumIter, EnumString, ToString};
 
 pub type ClientId = u16; // TODO: merge with crate type?
@@ -150,6 +151,30 @@ impl FromStr for Key {
     }
 }
 
+impl Key {
+    /// Parses keys that are typed one after the other, separated by spaces (eg. "g t" or
+    /// "Ctrl a c"), a modifier applies to the key right after it
+    pub fn sequence_from_str(keys_str: &str) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        let mut keys = vec![];
+        let mut parts = keys_str.split_ascii_whitespace();
+        while let Some(part) = parts.next() {
+            let key = if part == "Ctrl" || part == "Alt" {
+                match parts.next() {
+                    Some(main_key) => Key::from_str(&format!("{} {}", part, main_key))?,
+                    None => return Err(format!("Failed to parse key: {}", keys_str).into()),
+                }
+            } else {
+                Key::from_str(part)?
+            };
+            keys.push(key);
+        }
+        if keys.is_empty() {
+            return Err(format!("Failed to parse key: {}", keys_str).into());
+        }
+        Ok(keys)
+    }
+}
+
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -448,12 +473,15 @@ impl fmt::Display for ResizeStrategy {
 // left click) and the `ScrollUp` and `ScrollDown` events could probably be
 // merged into a single `Scroll(isize)` event.
 pub enum Mouse {
-    ScrollUp(usize),          // number of lines
-    ScrollDown(usize),        // number of lines
-    LeftClick(isize, usize),  // line and column
-    RightClick(isize, usize), // line and column
-    Hold(isize, usize),       // line and column
-    Release(isize, usize),    // line and column
+    ScrollUp(usize),           // number of lines
+    ScrollDown(usize),         // number of lines
+    LeftClick(isize, usize),   // line and column
+    RightClick(isize, usize),  // line and column
+    MiddleClick(isize, usize), // line and column
+    Hold(isize, usize),        // line and column
+    Release(isize, usize),     // line and column
+    Hover(isize, usize),       // line and column
+    HoverEnd,                  // the mouse left the pane
 }
 
 /// These events can be subscribed to with subscribe method exported by `zellij-tile`.
@@ -495,7 +523,44 @@ pub enum Event {
     FileSystemDelete(Vec<PathBuf>),
     /// A Result of plugin permission request
     PermissionRequestResult(PermissionStatus),
-    SessionUpdate(Vec<SessionInfo>),
+    /// The sessions running on this machine, along with the dead sessions that can be resurrected
+    /// (and how long ago they were last serialized)
+    SessionUpdate(
+        Vec<SessionInfo>,
+        Vec<(String, Duration)>, // resurrectable session names and their age
+    ),
+    /// A chunk of output (with ANSI escape sequences stripped) from a terminal pane this plugin
+    /// subscribed to with `subscribe_to_pane_output`
+    PaneOutput(u32, String), // terminal pane id, output
+    /// A terminal pane this plugin opened (eg. with `open_terminal` or `open_command_pane`) was
+    /// created, these are sent in the same order the panes were requested in
+    PaneOpened(u32), // terminal pane id
+    /// The configuration file was updated following a `rebind_keys` call of this or another plugin
+    ConfigWasWrittenToDisk,
+    /// A terminal pane this plugin opened in place (eg. with `open_command_pane_in_place`) was
+    /// closed and the pane it covered was restored, along with the last line of output it printed
+    /// (eg. the selection of a picker)
+    InPlacePaneClosed(u32, String), // terminal pane id, return value
+    /// A command this plugin ran with `run_command` exited
+    RunCommandResult(
+        Option<i32>, // exit code, None if the command was killed by a signal or failed to start
+        Vec<u8>,     // stdout
+        Vec<u8>,     // stderr
+    ),
+    /// A web request this plugin made with `web_request` finished
+    WebRequestResult(
+        Option<u16>,              // status code, None if the request failed (eg. a network error)
+        BTreeMap<String, String>, // response headers
+        Vec<u8>,                  // body, or the error if the request failed
+    ),
+    /// Sent periodically to plugins that asked for it with `subscribe_to_system_state`
+    SystemStateUpdate(SystemState),
+    /// The keys typed so far of a key sequence (eg. `bind "g t"`) that was started but not yet
+    /// finished, empty once it is finished, cancelled or timed out
+    PendingKeys(Vec<Key>),
+    /// The contents of a terminal pane, with their ANSI styling, requested with
+    /// `dump_pane_contents`
+    PaneContents(u32, String), // terminal pane id, contents
 }
 
 #[derive(
@@ -522,6 +587,12 @@ pub enum Permission {
     RunCommands,
     OpenTerminalsOrPlugins,
     WriteToStdin,
+    ReadPaneContents,
+    MessageOtherPlugins,
+    Reconfigure,
+    WebAccess,
+    FullHdAccess,
+    ReadSystemState,
 }
 
 impl PermissionType {
@@ -537,6 +608,14 @@ impl PermissionType {
             PermissionType::RunCommands => "Run commands".to_owned(),
             PermissionType::OpenTerminalsOrPlugins => "Start new terminals and plugins".to_owned(),
             PermissionType::WriteToStdin => "Write to standard input (STDIN)".to_owned(),
+            PermissionType::ReadPaneContents => "Read the output of terminal panes".to_owned(),
+            PermissionType::MessageOtherPlugins => "Send messages to other plugins".to_owned(),
+            PermissionType::Reconfigure => "Change Zellij runtime configuration".to_owned(),
+            PermissionType::WebAccess => "Make web requests".to_owned(),
+            PermissionType::FullHdAccess => "Full access to the hard-drive".to_owned(),
+            PermissionType::ReadSystemState => {
+                "Read the system state (eg. the load average or battery level)".to_owned()
+            },
         }
     }
 }
@@ -613,6 +692,12 @@ pub enum InputMode {
     /// `Tmux` mode allows for basic tmux keybindings functionality
     #[serde(alias = "tmux")]
     Tmux,
+    /// `Custom` mode is any of the modes defined in the keybinds config with `mode "<name>"`, the
+    /// name of the active one is kept alongside it (eg. in [`ModeInfo::custom_mode`])
+    #[serde(alias = "custom")]
+    #[strum(disabled)]
+    #[clap(skip)]
+    Custom,
 }
 
 impl Default for InputMode {
@@ -700,11 +785,224 @@ pub struct Palette {
     pub brown: PaletteColor,
 }
 
+impl Palette {
+    /// The same palette with all of its true colors downsampled to the 256 color palette, for
+    /// terminals that do not support true color
+    pub fn to_eight_bit(&self) -> Palette {
+        Palette {
+            source: self.source,
+            theme_hue: self.theme_hue,
+            fg: self.fg.to_eight_bit(),
+            bg: self.bg.to_eight_bit(),
+            black: self.black.to_eight_bit(),
+            red: self.red.to_eight_bit(),
+            green: self.green.to_eight_bit(),
+            yellow: self.yellow.to_eight_bit(),
+            blue: self.blue.to_eight_bit(),
+            magenta: self.magenta.to_eight_bit(),
+            cyan: self.cyan.to_eight_bit(),
+            white: self.white.to_eight_bit(),
+            orange: self.orange.to_eight_bit(),
+            gray: self.gray.to_eight_bit(),
+            purple: self.purple.to_eight_bit(),
+            gold: self.gold.to_eight_bit(),
+            silver: self.silver.to_eight_bit(),
+            pink: self.pink.to_eight_bit(),
+            brown: self.brown.to_eight_bit(),
+        }
+    }
+}
+
+impl PaletteColor {
+    /// The closest color in the 256 color palette, true colors are matched against the 6x6x6
+    /// color cube and the grayscale ramp (the first 16 colors are left alone since terminals
+    /// customize them)
+    pub fn to_eight_bit(&self) -> PaletteColor {
+        let (r, g, b) = match self {
+            PaletteColor::EightBit(_) => return *self,
+            PaletteColor::Rgb(rgb) => *rgb,
+        };
+        let cube_index = |channel: u8| -> u8 {
+            if channel < 48 {
+                0
+            } else if channel < 115 {
+                1
+            } else {
+                (channel - 35) / 40
+            }
+        };
+        let cube_level = |index: u8| -> u8 {
+            if index == 0 {
+                0
+            } else {
+                55 + index * 40
+            }
+        };
+        let distance = |(r2, g2, b2): (u8, u8, u8)| -> u32 {
+            let dr = r as i32 - r2 as i32;
+            let dg = g as i32 - g2 as i32;
+            let db = b as i32 - b2 as i32;
+            (dr * dr + dg * dg + db * db) as u32
+        };
+
+        let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+        let cube_color = (cube_level(ri), cube_level(gi), cube_level(bi));
+
+        let average = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+        let gray_index = if average > 238 {
+            23
+        } else {
+            average.saturating_sub(3) / 10
+        };
+        let gray_level = 8 + gray_index * 10;
+        let gray_color = (gray_level, gray_level, gray_level);
+
+        if distance(gray_color) < distance(cube_color) {
+            PaletteColor::EightBit(232 + gray_index)
+        } else {
+            PaletteColor::EightBit(16 + 36 * ri + 6 * gi + bi)
+        }
+    }
+}
+
+/// The colors of a single UI element: a base (foreground) color, a background and up to four
+/// colors used to emphasize parts of it (eg. the key in a keybinding hint)
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct StyleDeclaration {
+    pub base: PaletteColor,
+    pub background: PaletteColor,
+    pub emphasis_0: PaletteColor,
+    pub emphasis_1: PaletteColor,
+    pub emphasis_2: PaletteColor,
+    pub emphasis_3: PaletteColor,
+}
+
+impl StyleDeclaration {
+    pub fn to_eight_bit(&self) -> StyleDeclaration {
+        StyleDeclaration {
+            base: self.base.to_eight_bit(),
+            background: self.background.to_eight_bit(),
+            emphasis_0: self.emphasis_0.to_eight_bit(),
+            emphasis_1: self.emphasis_1.to_eight_bit(),
+            emphasis_2: self.emphasis_2.to_eight_bit(),
+            emphasis_3: self.emphasis_3.to_eight_bit(),
+        }
+    }
+}
+
+/// Per UI element colors of a theme
+///
+/// Themes defined with the flat color palette get these from their palette (see the `From`
+/// implementations below), themes can also define each element explicitly.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub struct Styling {
+    pub text_unselected: StyleDeclaration,
+    pub text_selected: StyleDeclaration,
+    pub ribbon_unselected: StyleDeclaration,
+    pub ribbon_selected: StyleDeclaration,
+    pub frame_unselected: Option<StyleDeclaration>, // None means the terminal's default colors
+    pub frame_selected: StyleDeclaration,
+    pub frame_highlight: StyleDeclaration,
+}
+
+impl Styling {
+    pub fn to_eight_bit(&self) -> Styling {
+        Styling {
+            text_unselected: self.text_unselected.to_eight_bit(),
+            text_selected: self.text_selected.to_eight_bit(),
+            ribbon_unselected: self.ribbon_unselected.to_eight_bit(),
+            ribbon_selected: self.ribbon_selected.to_eight_bit(),
+            frame_unselected: self.frame_unselected.map(|f| f.to_eight_bit()),
+            frame_selected: self.frame_selected.to_eight_bit(),
+            frame_highlight: self.frame_highlight.to_eight_bit(),
+        }
+    }
+}
+
+impl From<Palette> for Styling {
+    fn from(palette: Palette) -> Self {
+        Styling {
+            text_unselected: StyleDeclaration {
+                base: palette.fg,
+                background: palette.bg,
+                emphasis_0: palette.orange,
+                emphasis_1: palette.cyan,
+                emphasis_2: palette.green,
+                emphasis_3: palette.magenta,
+            },
+            text_selected: StyleDeclaration {
+                base: palette.fg,
+                background: palette.black,
+                emphasis_0: palette.orange,
+                emphasis_1: palette.cyan,
+                emphasis_2: palette.green,
+                emphasis_3: palette.magenta,
+            },
+            ribbon_unselected: StyleDeclaration {
+                base: palette.black,
+                background: palette.fg,
+                emphasis_0: palette.red,
+                emphasis_1: palette.white,
+                emphasis_2: palette.blue,
+                emphasis_3: palette.magenta,
+            },
+            ribbon_selected: StyleDeclaration {
+                base: palette.black,
+                background: palette.green,
+                emphasis_0: palette.red,
+                emphasis_1: palette.orange,
+                emphasis_2: palette.magenta,
+                emphasis_3: palette.yellow,
+            },
+            frame_unselected: None,
+            frame_selected: StyleDeclaration {
+                base: palette.green,
+                background: palette.bg,
+                emphasis_0: palette.orange,
+                emphasis_1: palette.cyan,
+                emphasis_2: palette.magenta,
+                emphasis_3: palette.red,
+            },
+            frame_highlight: StyleDeclaration {
+                base: palette.orange,
+                background: palette.bg,
+                emphasis_0: palette.magenta,
+                emphasis_1: palette.purple,
+                emphasis_2: palette.green,
+                emphasis_3: palette.red,
+            },
+        }
+    }
+}
+
+impl From<Styling> for Palette {
+    // the inverse of the above, so that UI parts that still use the palette follow themes that
+    // were defined per UI element
+    fn from(styling: Styling) -> Self {
+        Palette {
+            fg: styling.text_unselected.base,
+            bg: styling.text_unselected.background,
+            black: styling.ribbon_unselected.base,
+            red: styling.ribbon_unselected.emphasis_0,
+            green: styling.frame_selected.base,
+            yellow: styling.ribbon_selected.emphasis_3,
+            blue: styling.ribbon_unselected.emphasis_2,
+            magenta: styling.text_unselected.emphasis_3,
+            cyan: styling.text_unselected.emphasis_1,
+            white: styling.ribbon_unselected.emphasis_1,
+            orange: styling.text_unselected.emphasis_0,
+            purple: styling.frame_highlight.emphasis_1,
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct Style {
     pub colors: Palette,
     pub rounded_corners: bool,
     pub hide_session_name: bool,
+    pub styling: Styling,
 }
 
 // FIXME: Poor devs hashtable since HashTable can't derive `Default`...
@@ -718,6 +1016,9 @@ pub struct ModeInfo {
     pub style: Style,
     pub capabilities: PluginCapabilities,
     pub session_name: Option<String>,
+    /// The name of the active custom mode when `mode` is `InputMode::Custom`, its bindings are
+    /// the ones of `InputMode::Custom` in `keybinds`
+    pub custom_mode: Option<String>,
 }
 
 impl ModeInfo {
@@ -742,6 +1043,7 @@ pub struct SessionInfo {
     pub panes: PaneManifest,
     pub connected_clients: usize,
     pub is_current_session: bool,
+    pub group: Option<String>,
 }
 
 use std::hash::{Hash, Hasher};
@@ -791,6 +1093,12 @@ pub struct TabInfo {
     pub active_swap_layout_name: Option<String>,
     /// Whether the user manually changed the layout, moving out of the swap layout scheme
     pub is_swap_layout_dirty: bool,
+    /// The number of selectable panes in this tab (eg. not counting the tab-bar or status-bar)
+    pub pane_count: usize,
+    /// Whether a pane in this tab had output since the tab was last focused
+    pub has_unseen_output: bool,
+    /// The title of the focused pane of this tab (usually the command running in it)
+    pub focused_pane_title: Option<String>,
 }
 
 /// The `PaneManifest` contains a dictionary of panes, indexed by the tab position (0 indexed).
@@ -856,6 +1164,20 @@ pub struct PaneInfo {
     /// Unselectable panes are often used for UI elements that do not have direct user interaction
     /// (eg. the default `status-bar` or `tab-bar`).
     pub is_selectable: bool,
+    /// The pids of the processes running inside this terminal pane (its shell and all of the
+    /// shell's descendants), empty for plugin panes
+    pub child_pids: Vec<u32>,
+    /// The name of the process currently in the foreground of this terminal pane (eg. `vim`)
+    pub foreground_process: Option<String>,
+    /// The current working directory of the foreground process of this terminal pane
+    pub cwd: Option<PathBuf>,
+    /// Whether this terminal pane rang the bell since it was last focused
+    pub has_bell: bool,
+    /// Whether this terminal pane had output since it was last focused
+    pub has_activity: bool,
+    /// Whether this terminal pane had no output for the duration configured with the
+    /// `monitor_silence` option since it was last focused
+    pub is_silent: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
@@ -963,6 +1285,7 @@ pub struct ConnectToSession {
     pub name: Option<String>,
     pub tab_position: Option<usize>,
     pub pane_id: Option<(u32, bool)>, // (id, is_plugin)
+    pub layout: Option<String>,       // layout name or path, used if the session is created
 }
 
 #[derive(Debug, Default, Clone)]
@@ -989,6 +1312,76 @@ impl PluginMessage {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HttpVerb {
+    Get,
+    Post,
+    Put,
+    Delete,
+}
+
+/// The state of the machine Zellij runs on, things that plugins can't find out by themselves
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SystemState {
+    pub unix_time: u64,
+    pub utc_offset: i32, // seconds east of UTC of the local time zone
+    pub load_average: Option<[f64; 3]>, // over the last 1, 5 and 15 minutes
+    pub battery: Option<BatteryState>,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatteryState {
+    pub percentage: u8,
+    pub charging: bool,
+}
+
+/// A message sent from one plugin to another with `pipe_message_to_plugin`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MessageToPlugin {
+    /// The url (eg. `file:/path/to/plugin.wasm`) or alias (eg. `session-manager`) of the plugin
+    /// to send this message to
+    pub plugin_url: String,
+    pub message_name: String,
+    pub message_payload: Option<String>,
+    pub message_args: BTreeMap<String, String>,
+}
+
+impl MessageToPlugin {
+    pub fn new(plugin_url: impl Into<String>, message_name: impl Into<String>) -> Self {
+        MessageToPlugin {
+            plugin_url: plugin_url.into(),
+            message_name: message_name.into(),
+            ..Default::default()
+        }
+    }
+    pub fn with_payload(mut self, payload: impl Into<String>) -> Self {
+        self.message_payload = Some(payload.into());
+        self
+    }
+    pub fn with_args(mut self, args: BTreeMap<String, String>) -> Self {
+        self.message_args = args;
+        self
+    }
+}
+
+/// Where a [`PipeMessage`] was sent from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PipeSource {
+    Plugin(u32), // plugin id
+    /// Sent from the command line with `zellij pipe`, which waits for every message to be
+    /// handled before sending the next one (see `block_cli_pipe_input`)
+    Cli,
+}
+
+/// A message received by a plugin in its `pipe` method
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipeMessage {
+    pub source: PipeSource,
+    pub name: String,
+    pub payload: Option<String>,
+    pub args: BTreeMap<String, String>,
+}
+
 #[derive(Debug, Clone, EnumDiscriminants, ToString)]
 #[strum_discriminants(derive(EnumString, Hash, Serialize, Deserialize))]
 #[strum_discriminants(name(CommandType))]
@@ -1061,4 +1454,27 @@ pub enum PluginCommand {
     ReportPanic(String),             // stringified panic
     RequestPluginPermissions(Vec<PermissionType>),
     SwitchSession(ConnectToSession),
+    SubscribeToPaneOutput(u32),     // terminal pane id
+    UnsubscribeFromPaneOutput(u32), // terminal pane id
+    PipeMessageToPlugin(MessageToPlugin),
+    KillSessions(Vec<String>), // session names
+    DeleteDeadSession(String), // dead session name
+    RenameSession(String),     // new session name
+    MoveTabToIndex(u32, u32),  // tab position, new tab position
+    OpenFileInPlace(FileToOpen),
+    OpenTerminalInPlace(FileToOpen), // only used for the path as cwd
+    OpenCommandPaneInPlace(CommandToRun),
+    RebindKeys(String, bool),    // kdl keybinds block, write to config file
+    BlockCliPipeInput(String),   // pipe name
+    UnblockCliPipeInput(String), // pipe name
+    RunCommand(CommandToRun, BTreeMap<String, String>), // command, env variables
+    WebRequest(String, HttpVerb, BTreeMap<String, String>, Vec<u8>), // url, method, headers, body
+    ChangeHostFolder(PathBuf),   // new host folder, mapped to /host in the plugin
+    SubscribeToSystemState(f64), // interval in seconds
+    SetInterval(f64),            // seconds
+    ClearInterval,
+    CloseTabWithIndex(u32),          // tab position
+    DumpPaneContents(u32, bool),     // terminal pane id, include the scrollback
+    WriteToPaneId(Vec<u8>, u32),     // bytes, terminal pane id
+    WriteCharsToPaneId(String, u32), // chars, terminal pane id
 }
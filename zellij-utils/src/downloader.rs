@@ -0,0 +1,233 @@
+//! Download plugins from https urls into a content-addressed cache.
+//!
+//! Downloaded plugins are stored as `<sha256 of contents>.wasm` in
+//! [`ZELLIJ_PLUGIN_DOWNLOAD_CACHE_DIR`], alongside a small index (`urls/<sha256 of url>`) pointing
+//! each url at the digest of the plugin it was last downloaded as. This way a plugin that was
+//! downloaded once can be loaded again without network access.
+use crate::consts::ZELLIJ_PLUGIN_DOWNLOAD_CACHE_DIR;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+const CHECKSUM_FRAGMENT_PREFIX: &str = "sha256=";
+
+#[derive(Error, Debug)]
+pub enum DownloaderError {
+    #[error("Failed to download plugin from '{0}': {1}")]
+    Request(String, String),
+    #[error(
+        "Plugin downloaded from '{url}' has the sha256 checksum {actual}, expected {expected}"
+    )]
+    ChecksumMismatch {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+    #[error("Invalid sha256 checksum '{1}' in plugin url '{0}', expected 64 hex digits")]
+    InvalidChecksum(String, String),
+    #[error("Failed to write downloaded plugin to the cache: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// A plugin located at an `https:` url, with an optional expected checksum given as a
+/// `#sha256=<hex digest>` fragment of the url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemotePlugin {
+    url: String,
+    sha256: Option<String>,
+}
+
+impl RemotePlugin {
+    pub fn new(location: &str) -> Result<Self, DownloaderError> {
+        match location.split_once('#') {
+            Some((url, fragment)) => {
+                let sha256 = match fragment.strip_prefix(CHECKSUM_FRAGMENT_PREFIX) {
+                    // the checksum becomes a file name in the cache, so it has to be a digest
+                    Some(digest) if is_sha256_hex(digest) => Some(digest.to_ascii_lowercase()),
+                    Some(digest) => {
+                        return Err(DownloaderError::InvalidChecksum(
+                            location.to_owned(),
+                            digest.to_owned(),
+                        ))
+                    },
+                    None => None,
+                };
+                Ok(RemotePlugin {
+                    url: url.to_owned(),
+                    sha256,
+                })
+            },
+            None => Ok(RemotePlugin {
+                url: location.to_owned(),
+                sha256: None,
+            }),
+        }
+    }
+    /// The path of the downloaded plugin, downloading it first if it is not already cached.
+    pub async fn fetch(&self) -> Result<PathBuf, DownloaderError> {
+        self.fetch_with_cache_dir(&ZELLIJ_PLUGIN_DOWNLOAD_CACHE_DIR)
+            .await
+    }
+    async fn fetch_with_cache_dir(&self, cache_dir: &Path) -> Result<PathBuf, DownloaderError> {
+        match self.cached_path(cache_dir) {
+            Some(path) => {
+                log::debug!("Loading plugin '{}' from {}", self.url, path.display());
+                Ok(path)
+            },
+            None => {
+                let bytes = self.download().await?;
+                self.write_to_cache(&bytes, cache_dir)
+            },
+        }
+    }
+    fn cached_path(&self, cache_dir: &Path) -> Option<PathBuf> {
+        let digest = match &self.sha256 {
+            Some(sha256) => sha256.clone(),
+            None => fs::read_to_string(url_index_path(&self.url, cache_dir))
+                .ok()?
+                .trim()
+                .to_owned(),
+        };
+        let path = plugin_path(&digest, cache_dir);
+        if path.exists() {
+            Some(path)
+        } else {
+            None
+        }
+    }
+    async fn download(&self) -> Result<Vec<u8>, DownloaderError> {
+        let request_error =
+            |e: surf::Error| DownloaderError::Request(self.url.clone(), e.to_string());
+        log::info!("Downloading plugin from '{}'", self.url);
+        let client = surf::client().with(surf::middleware::Redirect::default());
+        let mut response = client.get(&self.url).await.map_err(request_error)?;
+        if !response.status().is_success() {
+            return Err(DownloaderError::Request(
+                self.url.clone(),
+                response.status().to_string(),
+            ));
+        }
+        response.body_bytes().await.map_err(request_error)
+    }
+    fn write_to_cache(&self, bytes: &[u8], cache_dir: &Path) -> Result<PathBuf, DownloaderError> {
+        let digest = sha256_hex(bytes);
+        if let Some(expected) = &self.sha256 {
+            if expected != &digest {
+                return Err(DownloaderError::ChecksumMismatch {
+                    url: self.url.clone(),
+                    expected: expected.clone(),
+                    actual: digest,
+                });
+            }
+        }
+        let path = plugin_path(&digest, cache_dir);
+        let index_path = url_index_path(&self.url, cache_dir);
+        if let Some(index_dir) = index_path.parent() {
+            fs::create_dir_all(index_dir)?;
+        }
+        fs::write(&path, bytes)?;
+        fs::write(&index_path, &digest)?;
+        Ok(path)
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn is_sha256_hex(digest: &str) -> bool {
+    digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn plugin_path(digest: &str, cache_dir: &Path) -> PathBuf {
+    cache_dir.join(digest).with_extension("wasm")
+}
+
+fn url_index_path(url: &str, cache_dir: &Path) -> PathBuf {
+    cache_dir.join("urls").join(sha256_hex(url.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn checksum_is_parsed_from_url_fragment() {
+        let digest = "ABCDEF".repeat(10) + "0123";
+        let remote_plugin = RemotePlugin::new(&format!(
+            "https://example.com/plugin.wasm#sha256={}",
+            digest
+        ))
+        .unwrap();
+        assert_eq!(
+            remote_plugin,
+            RemotePlugin {
+                url: "https://example.com/plugin.wasm".to_owned(),
+                sha256: Some(digest.to_ascii_lowercase()),
+            }
+        );
+        let remote_plugin = RemotePlugin::new("https://example.com/plugin.wasm").unwrap();
+        assert_eq!(remote_plugin.sha256, None);
+    }
+
+    #[test]
+    fn checksum_that_is_not_a_sha256_digest_is_rejected() {
+        let too_short = "https://example.com/plugin.wasm#sha256=abcdef";
+        let not_hex = format!("https://example.com/plugin.wasm#sha256={}", "g".repeat(64));
+        let path_traversal = format!(
+            "https://example.com/plugin.wasm#sha256=../../{}",
+            "a".repeat(58)
+        );
+        for location in [too_short, &not_hex, &path_traversal] {
+            assert!(matches!(
+                RemotePlugin::new(location),
+                Err(DownloaderError::InvalidChecksum(..))
+            ));
+        }
+    }
+
+    #[test]
+    fn cached_plugin_is_found_by_url_or_checksum() {
+        let cache_dir = tempdir().unwrap();
+        let bytes = b"not really wasm";
+        let remote_plugin = RemotePlugin::new("https://example.com/plugin.wasm").unwrap();
+        assert_eq!(remote_plugin.cached_path(cache_dir.path()), None);
+
+        let path = remote_plugin
+            .write_to_cache(bytes, cache_dir.path())
+            .unwrap();
+        assert_eq!(fs::read(&path).unwrap(), bytes);
+        assert_eq!(
+            remote_plugin.cached_path(cache_dir.path()),
+            Some(path.clone())
+        );
+
+        let pinned_plugin = RemotePlugin::new(&format!(
+            "https://mirror.example.com/plugin.wasm#sha256={}",
+            sha256_hex(bytes)
+        ))
+        .unwrap();
+        assert_eq!(pinned_plugin.cached_path(cache_dir.path()), Some(path));
+    }
+
+    #[test]
+    fn plugin_with_wrong_checksum_is_not_cached() {
+        let cache_dir = tempdir().unwrap();
+        let remote_plugin = RemotePlugin::new(&format!(
+            "https://example.com/plugin.wasm#sha256={}",
+            "0".repeat(64)
+        ))
+        .unwrap();
+        let result = remote_plugin.write_to_cache(b"not really wasm", cache_dir.path());
+        assert!(matches!(
+            result,
+            Err(DownloaderError::ChecksumMismatch { .. })
+        ));
+        assert_eq!(remote_plugin.cached_path(cache_dir.path()), None);
+    }
+}
@@ -26,11 +26,23 @@ pub fn set_session_name(v: String) {
     set_var(SESSION_NAME_ENV_KEY, v);
 }
 
+/// Set in every terminal pane to the id of the pane, which stays the same for as long as the pane
+/// exists (eg. across layout changes or when the pane is moved to another tab)
+///
+/// There is no equivalent for the tab, since the pane can be moved to another tab after its
+/// process was started and the value would go stale.
+pub const PANE_ID_ENV_KEY: &str = "ZELLIJ_PANE_ID";
+
 pub const SOCKET_DIR_ENV_KEY: &str = "ZELLIJ_SOCKET_DIR";
 pub fn get_socket_dir() -> Result<String> {
     Ok(var(SOCKET_DIR_ENV_KEY)?)
 }
 
+pub fn terminal_supports_truecolor() -> bool {
+    // this is the de-facto standard way for terminals to advertise true color support
+    matches!(var("COLORTERM").as_deref(), Ok("truecolor") | Ok("24bit"))
+}
+
 /// Manage ENVIRONMENT VARIABLES from the configuration and the layout files
 #[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
 pub struct EnvironmentVariables {
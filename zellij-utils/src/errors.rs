@@ -219,6 +219,7 @@ pub enum ScreenContext {
     ShowFloatingPanes,
     HideFloatingPanes,
     TogglePaneEmbedOrFloating,
+    TogglePanePinned,
     HorizontalSplit,
     VerticalSplit,
     WriteCharacter,
@@ -244,6 +245,7 @@ pub enum ScreenContext {
     FocusPaneAt,
     MoveFocusLeft,
     MoveFocusLeftOrPreviousTab,
+    MoveFocusOrDelegate,
     MoveFocusDown,
     MoveFocusUp,
     MoveFocusRight,
@@ -258,12 +260,19 @@ pub enum ScreenContext {
     ClearScreen,
     DumpScreen,
     EditScrollback,
+    EnterCopyMode,
+    ShowLinkHints,
     ScrollUp,
     ScrollUpAt,
     ScrollDown,
     ScrollDownAt,
+    ForceScrollUpAt,
+    ForceScrollDownAt,
     ScrollToBottom,
     ScrollToTop,
+    ScrollToPreviousPrompt,
+    ScrollToNextPrompt,
+    CopyLastCommandOutput,
     PageScrollUp,
     PageScrollDown,
     HalfPageScrollUp,
@@ -271,7 +280,17 @@ pub enum ScreenContext {
     ClearScroll,
     CloseFocusedPane,
     ToggleActiveSyncTab,
+    ToggleActivePaneSync,
+    TogglePaneInGroup,
+    ClearPaneGroup,
+    CloseGroupedPanes,
+    FloatGroupedPanes,
+    BreakGroupedPanesToNewTab,
+    SyncGroupedPanes,
     ToggleActiveTerminalFullscreen,
+    ToggleExpandPane,
+    FocusNextPaneInStack,
+    FocusPreviousPaneInStack,
     TogglePaneFrames,
     SetSelectable,
     SetInvisibleBorders,
@@ -286,6 +305,8 @@ pub enum ScreenContext {
     SwitchTabNext,
     SwitchTabPrev,
     CloseTab,
+    ReopenClosedPane,
+    ReopenClosedTab,
     GoToTab,
     GoToTabName,
     UpdateTabName,
@@ -298,6 +319,9 @@ pub enum ScreenContext {
     ChangeMode,
     ChangeModeForAllClients,
     LeftClick,
+    CtrlLeftClick,
+    ShiftLeftClick,
+    AltLeftClick,
     RightClick,
     MiddleClick,
     LeftMouseRelease,
@@ -306,6 +330,7 @@ pub enum ScreenContext {
     MouseHoldLeft,
     MouseHoldRight,
     MouseHoldMiddle,
+    MouseHover,
     Copy,
     ToggleTab,
     AddClient,
@@ -320,6 +345,7 @@ pub enum ScreenContext {
     SearchToggleCaseSensitivity,
     SearchToggleWholeWord,
     SearchToggleWrap,
+    SearchToggleRegex,
     AddRedPaneFrameColorOverride,
     ClearPaneFrameColorOverride,
     PreviousSwapLayout,
@@ -328,6 +354,7 @@ pub enum ScreenContext {
     NewTiledPluginPane,
     StartOrReloadPluginPane,
     NewFloatingPluginPane,
+    NewInPlacePluginPane,
     AddPlugin,
     UpdatePluginLoadingStage,
     ProgressPluginLoadingOffset,
@@ -342,13 +369,43 @@ pub enum ScreenContext {
     BreakPane,
     BreakPaneRight,
     BreakPaneLeft,
+    MovePaneToTab,
+    MoveTab,
+    MoveTabToIndex,
+    CloseTabAtPosition,
+    RespawnPane,
     UpdateSessionInfos,
+    MarkSilentPanes,
+    SwitchWorkspace,
+    ToggleScratchpad,
+    NewScratchpadPane,
+    SubscribeToPaneOutput,
+    UnsubscribeFromPaneOutput,
+    RemovePaneOutputSubscriptionsOfPane,
+    RemovePaneOutputSubscriptionsOfPlugin,
+    DumpPaneContents,
+    DumpLayoutToHd,
+    DumpLayout,
+    RenameSession,
+    SerializeSessionAndQuit,
+    UpdateTerminalProcesses,
+    QueryPaneProcesses,
+    LoadBuffer,
+    SaveBuffer,
+    PasteBuffer,
+    PipeToPane,
+    WriteToPaneId,
+    ReplacePane,
+    RebindKeys,
+    Reconfigure,
 }
 
 /// Stack call representations corresponding to the different types of [`PtyInstruction`]s.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PtyContext {
     SpawnTerminal,
+    SpawnTerminalForPlugin,
+    SpawnInPlaceTerminal,
     OpenInPlaceEditor,
     SpawnTerminalVertically,
     SpawnTerminalHorizontally,
@@ -358,6 +415,10 @@ pub enum PtyContext {
     ClosePane,
     CloseTab,
     ReRunCommandInPane,
+    SpawnScratchpad,
+    ReportTerminalProcesses,
+    DumpLayoutToHd,
+    DumpLayout,
     Exit,
 }
 
@@ -378,8 +439,15 @@ pub enum PluginContext {
     ApplyCachedWorkerMessages,
     PostMessageToPluginWorker,
     PostMessageToPlugin,
+    PipeMessage,
+    CliPipe,
+    CliPipeMessageDelivered,
+    BlockCliPipeInput,
+    UnblockCliPipeInput,
+    ChangePluginHostDir,
     PluginSubscribedToEvents,
     PermissionRequestResult,
+    PluginFileChanged,
 }
 
 /// Stack call representations corresponding to the different types of [`ClientInstruction`]s.
@@ -394,10 +462,13 @@ pub enum ClientContext {
     Connected,
     ActiveClients,
     Log,
+    CommandExited,
     OwnClientId,
     StartedParsingStdinQuery,
     DoneParsingStdinQuery,
     SwitchSession,
+    RebindKeys,
+    UnblockCliPipeInput,
 }
 
 /// Stack call representations corresponding to the different types of [`ServerInstruction`]s.
@@ -415,7 +486,12 @@ pub enum ServerContext {
     ConnStatus,
     ActiveClients,
     Log,
+    CommandExited,
     SwitchSession,
+    RebindKeys,
+    ReloadConfig,
+    UnblockCliPipeInput,
+    SwitchTheme,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -434,6 +510,20 @@ pub enum BackgroundJobContext {
     StopPluginLoadingAnimation,
     ReadAllSessionInfosOnMachine,
     ReportSessionInfo,
+    PollTerminalProcesses,
+    StopPollingTerminalProcesses,
+    MonitorPaneSilence,
+    SerializeSessionLayout,
+    WriteSessionLayout,
+    WriteKeybindsToConfigFile,
+    WatchConfigFile,
+    WatchPluginFile,
+    RunCommand,
+    WebRequest,
+    ReportSystemState,
+    StopReportingSystemState,
+    SetPluginInterval,
+    ClearPluginInterval,
     Exit,
 }
 
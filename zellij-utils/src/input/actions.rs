@@ -6,14 +6,16 @@ use super::layout::{
     TiledPaneLayout,
 };
 use crate::cli::CliAction;
-use crate::data::InputMode;
 use crate::data::{Direction, Resize};
+use crate::data::{InputMode, Key};
 use crate::input::config::{Config, ConfigError, KdlError};
 use crate::input::options::OnForceClose;
 use crate::setup::{find_default_config_dir, get_layout_dir};
 use miette::{NamedSource, Report};
 use serde::{Deserialize, Serialize};
 
+use std::collections::BTreeMap;
+use std::io::Read;
 use std::path::PathBuf;
 use std::str::FromStr;
 
@@ -71,6 +73,7 @@ pub enum SearchOption {
     CaseSensitivity,
     WholeWord,
     Wrap,
+    Regex,
 }
 
 impl FromStr for SearchOption {
@@ -82,6 +85,7 @@ impl FromStr for SearchOption {
             },
             "WholeWord" | "wholeword" | "Wholeword" => Ok(SearchOption::WholeWord),
             "Wrap" | "wrap" => Ok(SearchOption::Wrap),
+            "Regex" | "regex" => Ok(SearchOption::Regex),
             _ => Err(format!(
                 "Failed to parse SearchOption. Unknown SearchOption: {}",
                 s
@@ -103,8 +107,14 @@ pub enum Action {
     Write(Vec<u8>),
     /// Write Characters to the terminal.
     WriteChars(String),
+    /// Write to the terminal pane with this id, whether or not it is focused
+    WriteToPaneId(Vec<u8>, u32),
+    /// Write Characters to the terminal pane with this id, whether or not it is focused
+    WriteCharsToPaneId(String, u32),
     /// Switch to the specified input mode.
     SwitchToMode(InputMode),
+    /// Switch to the custom input mode with the specified name (defined in the keybinds config).
+    SwitchToCustomMode(String),
     /// Switch all connected clients to the specified input mode.
     SwitchModeForAllClients(InputMode),
     /// Shrink/enlarge focused pane at specified border
@@ -118,12 +128,16 @@ pub enum Action {
     /// Tries to move the focus pane in specified direction.
     /// If there is no pane in the direction, move to previous/next Tab.
     MoveFocusOrTab(Direction),
+    /// Moves the focus in the specified direction, unless the focused pane claimed the directional
+    /// navigation keys, in which case it gets the pressed key (its bytes are filled in by the
+    /// client)
+    MoveFocusOrDelegate(Direction, Vec<u8>),
     MovePane(Option<Direction>),
     MovePaneBackwards,
     /// Clear all buffers of a current screen
     ClearScreen,
-    /// Dumps the screen to a file
-    DumpScreen(String, bool),
+    /// Dumps the screen to a file, optionally with the full scrollback and with its ANSI styling
+    DumpScreen(String, bool, bool),
     /// Scroll up in focus pane.
     EditScrollback,
     ScrollUp,
@@ -133,10 +147,20 @@ pub enum Action {
     ScrollDown,
     /// Scroll down at point
     ScrollDownAt(Position),
+    /// Scroll zellij's scrollback up at point, even if the pane there asked for mouse events
+    ForceScrollUpAt(Position),
+    /// Scroll zellij's scrollback down at point, even if the pane there asked for mouse events
+    ForceScrollDownAt(Position),
     /// Scroll down to bottom in focus pane.
     ScrollToBottom,
     /// Scroll up to top in focus pane.
     ScrollToTop,
+    /// Scroll up to the previous shell prompt in focus pane (needs shell integration, OSC 133).
+    ScrollToPreviousPrompt,
+    /// Scroll down to the next shell prompt in focus pane (needs shell integration, OSC 133).
+    ScrollToNextPrompt,
+    /// Copy the output of the last command run in focus pane (needs shell integration, OSC 133).
+    CopyLastCommandOutput,
     /// Scroll up one page in focus pane.
     PageScrollUp,
     /// Scroll down one page in focus pane.
@@ -147,10 +171,35 @@ pub enum Action {
     HalfPageScrollDown,
     /// Toggle between fullscreen focus pane and normal layout.
     ToggleFocusFullscreen,
+    /// Spread out the stack of the focused pane, or stack the focused pane with the panes of the
+    /// same width above and below it with only the focused pane expanded.
+    ToggleExpandPane,
+    /// Focus and expand the next pane in the stack of the focused pane.
+    FocusNextPaneInStack,
+    /// Focus and expand the previous pane in the stack of the focused pane.
+    FocusPreviousPaneInStack,
     /// Toggle frames around panes in the UI
     TogglePaneFrames,
     /// Toggle between sending text commands to all panes on the current tab and normal mode.
     ToggleActiveSyncTab,
+    /// Add the focused pane to the sync group of its tab or remove it from it, input typed into a
+    /// pane in the sync group is sent to all of its panes
+    ToggleActivePaneSync,
+    /// Add the focused pane to the pane group of its tab or remove it from it, bulk actions apply to
+    /// all of the panes in the group
+    TogglePaneInGroup,
+    /// Remove all panes from the pane group of the focused tab
+    ClearPaneGroup,
+    /// Close all panes in the pane group of the focused tab
+    CloseGroupedPanes,
+    /// Turn the tiled panes in the pane group of the focused tab into floating panes
+    FloatGroupedPanes,
+    /// Move all panes in the pane group of the focused tab into a new tab, one of them is tiled and
+    /// the rest float in it
+    BreakGroupedPanesToNewTab,
+    /// Add all panes in the pane group of the focused tab to its sync group, or remove them from it
+    /// if they are all already in it
+    SyncGroupedPanes,
     /// Open a new pane in the specified direction (relative to focus).
     /// If no direction is specified, will try to use the biggest available space.
     NewPane(Option<Direction>, Option<String>), // String is an optional pane name
@@ -168,10 +217,15 @@ pub enum Action {
     NewTiledPane(Option<Direction>, Option<RunCommandAction>, Option<String>), // String is an
     // optional pane
     // name
+    /// Open a new pane in place of the focused pane, restoring the focused pane once it is closed
+    NewInPlacePane(Option<RunCommandAction>, Option<String>), // String is an optional pane name
     /// Embed focused pane in tab if floating or float focused pane if embedded
     TogglePaneEmbedOrFloating,
     /// Toggle the visibility of all floating panes (if any) in the current Tab
     ToggleFloatingPanes,
+    /// Pin the focused floating pane so that it stays visible when floating panes are hidden, or
+    /// unpin it if it is already pinned
+    TogglePanePinned,
     /// Close the focus pane.
     CloseFocus,
     PaneNameInput(Vec<u8>),
@@ -192,6 +246,10 @@ pub enum Action {
     GoToPreviousTab,
     /// Close the current tab.
     CloseTab,
+    /// Reopen the most recently closed pane.
+    ReopenClosedPane,
+    /// Reopen the most recently closed tab.
+    ReopenClosedTab,
     GoToTab(u32),
     GoToTabName(String, bool),
     ToggleTab,
@@ -201,7 +259,12 @@ pub enum Action {
     Run(RunCommandAction),
     /// Detach session and exit
     Detach,
+    /// Serialize the session (so that it can be resurrected) and quit, used on force close
+    SerializeSessionAndQuit,
     LeftClick(Position),
+    CtrlLeftClick(Position),
+    ShiftLeftClick(Position),
+    AltLeftClick(Position),
     RightClick(Position),
     MiddleClick(Position),
     LaunchOrFocusPlugin(RunPlugin, bool, bool), // bools => should float, move_to_focused_tab
@@ -211,6 +274,7 @@ pub enum Action {
     MouseHoldLeft(Position),
     MouseHoldRight(Position),
     MouseHoldMiddle(Position),
+    MouseHover(Position),
     Copy,
     /// Confirm a prompt
     Confirm,
@@ -218,6 +282,12 @@ pub enum Action {
     Deny,
     /// Confirm an action that invokes a prompt automatically
     SkipConfirm(Box<Action>),
+    /// Bound to the first key of one or more key sequences (eg. `bind "g t"`), holds the rest of
+    /// the keys of each sequence and the actions to run once they are typed
+    KeySequence(Vec<(Vec<Key>, Vec<Action>)>),
+    /// The keys typed so far of an unfinished key sequence, sent by the client so that they can be
+    /// shown (eg. in the status-bar)
+    PendingKeys(Vec<Key>),
     /// Search for String
     SearchInput(Vec<u8>),
     /// Search for something
@@ -232,6 +302,7 @@ pub enum Action {
     /// Open a new tiled (embedded, non-floating) plugin pane
     NewTiledPluginPane(RunPlugin, Option<String>), // String is an optional name
     NewFloatingPluginPane(RunPlugin, Option<String>), // String is an optional name
+    NewInPlacePluginPane(RunPlugin, Option<String>),  // String is an optional name
     StartOrReloadPlugin(RunPlugin),
     CloseTerminalPane(u32),
     ClosePluginPane(u32),
@@ -240,9 +311,51 @@ pub enum Action {
     RenameTerminalPane(u32, Vec<u8>),
     RenamePluginPane(u32, Vec<u8>),
     RenameTab(u32, Vec<u8>),
+    /// Move the focused pane into a new tab of its own
     BreakPane,
+    /// Move the focused pane to the tab on the right of the current one
     BreakPaneRight,
+    /// Move the focused pane to the tab on the left of the current one
     BreakPaneLeft,
+    /// Move the focused pane to the tab with this position (1 indexed)
+    MovePaneToTab(u32),
+    /// Move the active tab one position to the left or right, wrapping around at the ends
+    MoveTab(Direction),
+    /// Re-run the command (or shell) of the focused pane in place once it has exited, keeping its
+    /// id and geometry
+    RespawnPane,
+    /// Switch to the next session (by name) that shares this session's group, wrapping around
+    SwitchWorkspace,
+    /// Toggle the session's scratchpad: a floating shell pane that is spawned on first use and
+    /// then hidden and shown again with its state intact
+    ToggleScratchpad,
+    /// Switch the session to the theme with this name, one of those zellij ships with or of the
+    /// configuration
+    SwitchTheme(String),
+    /// Query the foreground process, working directory and child pids of all terminal panes
+    QueryPaneProcesses,
+    /// Replace the contents of the paste buffer
+    LoadBuffer(String),
+    /// Write the contents of the paste buffer to a file
+    SaveBuffer(String),
+    /// Paste the contents of the paste buffer into the terminal pane with this id, or into the
+    /// focused pane if none is given
+    PasteBuffer(Option<u32>),
+    /// Dump the layout of the current session as KDL
+    DumpLayout,
+    /// Enter copy mode in the focused pane, selecting and copying text with the keyboard
+    EnterCopyMode,
+    /// Label the links in the focused pane, opening the one whose label is typed next
+    LinkHints,
+    /// Send a message from the command line (`zellij pipe`) to the running plugins with this
+    /// url or alias (or to all of them), or to the STDIN of the terminal pane with this id
+    CliPipe {
+        name: Option<String>,
+        payload: Option<String>,
+        args: Option<BTreeMap<String, String>>,
+        plugin: Option<String>,
+        pane_id: Option<u32>,
+    },
 }
 
 impl Action {
@@ -254,6 +367,17 @@ impl Action {
             _ => self == other_action,
         }
     }
+    /// Whether this action runs a command whose exit status should be reported back to the
+    /// (cli) client that sent it
+    pub fn waits_for_command_exit(&self) -> bool {
+        match self {
+            Action::NewTiledPane(_, Some(run_command), _)
+            | Action::NewFloatingPane(Some(run_command), _)
+            | Action::NewInPlacePane(Some(run_command), _)
+            | Action::Run(run_command) => run_command.wait_for_exit,
+            _ => false,
+        }
+    }
 
     pub fn actions_from_cli(
         cli_action: CliAction,
@@ -261,32 +385,60 @@ impl Action {
         config: Option<Config>,
     ) -> Result<Vec<Action>, String> {
         match cli_action {
-            CliAction::Write { bytes } => Ok(vec![Action::Write(bytes)]),
-            CliAction::WriteChars { chars } => Ok(vec![Action::WriteChars(chars)]),
+            CliAction::Write { bytes, pane_id } => match pane_id {
+                Some(pane_id) => Ok(vec![Action::WriteToPaneId(bytes, pane_id)]),
+                None => Ok(vec![Action::Write(bytes)]),
+            },
+            CliAction::WriteChars { chars, pane_id } => match pane_id {
+                Some(pane_id) => Ok(vec![Action::WriteCharsToPaneId(chars, pane_id)]),
+                None => Ok(vec![Action::WriteChars(chars)]),
+            },
             CliAction::Resize { resize, direction } => Ok(vec![Action::Resize(resize, direction)]),
             CliAction::FocusNextPane => Ok(vec![Action::FocusNextPane]),
             CliAction::FocusPreviousPane => Ok(vec![Action::FocusPreviousPane]),
             CliAction::MoveFocus { direction } => Ok(vec![Action::MoveFocus(direction)]),
+            CliAction::FocusPane {
+                id,
+                plugin,
+                float_if_hidden,
+            } => match plugin {
+                true => Ok(vec![Action::FocusPluginPaneWithId(id, float_if_hidden)]),
+                false => Ok(vec![Action::FocusTerminalPaneWithId(id, float_if_hidden)]),
+            },
             CliAction::MoveFocusOrTab { direction } => Ok(vec![Action::MoveFocusOrTab(direction)]),
             CliAction::MovePane { direction } => Ok(vec![Action::MovePane(direction)]),
             CliAction::MovePaneBackwards => Ok(vec![Action::MovePaneBackwards]),
             CliAction::Clear => Ok(vec![Action::ClearScreen]),
-            CliAction::DumpScreen { path, full } => Ok(vec![Action::DumpScreen(
+            CliAction::DumpScreen { path, full, ansi } => Ok(vec![Action::DumpScreen(
                 path.as_os_str().to_string_lossy().into(),
                 full,
+                ansi,
             )]),
             CliAction::EditScrollback => Ok(vec![Action::EditScrollback]),
             CliAction::ScrollUp => Ok(vec![Action::ScrollUp]),
             CliAction::ScrollDown => Ok(vec![Action::ScrollDown]),
             CliAction::ScrollToBottom => Ok(vec![Action::ScrollToBottom]),
             CliAction::ScrollToTop => Ok(vec![Action::ScrollToTop]),
+            CliAction::ScrollToPreviousPrompt => Ok(vec![Action::ScrollToPreviousPrompt]),
+            CliAction::ScrollToNextPrompt => Ok(vec![Action::ScrollToNextPrompt]),
+            CliAction::CopyLastCommandOutput => Ok(vec![Action::CopyLastCommandOutput]),
             CliAction::PageScrollUp => Ok(vec![Action::PageScrollUp]),
             CliAction::PageScrollDown => Ok(vec![Action::PageScrollDown]),
             CliAction::HalfPageScrollUp => Ok(vec![Action::HalfPageScrollUp]),
             CliAction::HalfPageScrollDown => Ok(vec![Action::HalfPageScrollDown]),
             CliAction::ToggleFullscreen => Ok(vec![Action::ToggleFocusFullscreen]),
+            CliAction::ToggleExpandPane => Ok(vec![Action::ToggleExpandPane]),
+            CliAction::FocusNextPaneInStack => Ok(vec![Action::FocusNextPaneInStack]),
+            CliAction::FocusPreviousPaneInStack => Ok(vec![Action::FocusPreviousPaneInStack]),
             CliAction::TogglePaneFrames => Ok(vec![Action::TogglePaneFrames]),
             CliAction::ToggleActiveSyncTab => Ok(vec![Action::ToggleActiveSyncTab]),
+            CliAction::ToggleActivePaneSync => Ok(vec![Action::ToggleActivePaneSync]),
+            CliAction::TogglePaneInGroup => Ok(vec![Action::TogglePaneInGroup]),
+            CliAction::ClearPaneGroup => Ok(vec![Action::ClearPaneGroup]),
+            CliAction::CloseGroupedPanes => Ok(vec![Action::CloseGroupedPanes]),
+            CliAction::FloatGroupedPanes => Ok(vec![Action::FloatGroupedPanes]),
+            CliAction::BreakGroupedPanesToNewTab => Ok(vec![Action::BreakGroupedPanesToNewTab]),
+            CliAction::SyncGroupedPanes => Ok(vec![Action::SyncGroupedPanes]),
             CliAction::NewPane {
                 direction,
                 command,
@@ -297,6 +449,8 @@ impl Action {
                 close_on_exit,
                 start_suspended,
                 configuration,
+                in_place,
+                wait,
             } => {
                 let current_dir = get_current_dir();
                 let cwd = cwd
@@ -311,7 +465,9 @@ impl Action {
                         location,
                         configuration: user_configuration,
                     };
-                    if floating {
+                    if in_place {
+                        Ok(vec![Action::NewInPlacePluginPane(plugin, name)])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPluginPane(plugin, name)])
                     } else {
                         // it is intentional that a new tiled plugin pane cannot include a
@@ -336,8 +492,11 @@ impl Action {
                         direction,
                         hold_on_close,
                         hold_on_start,
+                        wait_for_exit: wait,
                     };
-                    if floating {
+                    if in_place {
+                        Ok(vec![Action::NewInPlacePane(Some(run_command_action), name)])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPane(
                             Some(run_command_action),
                             name,
@@ -350,7 +509,9 @@ impl Action {
                         )])
                     }
                 } else {
-                    if floating {
+                    if in_place {
+                        Ok(vec![Action::NewInPlacePane(None, name)])
+                    } else if floating {
                         Ok(vec![Action::NewFloatingPane(None, name)])
                     } else {
                         Ok(vec![Action::NewTiledPane(direction, None, name)])
@@ -387,15 +548,18 @@ impl Action {
             },
             CliAction::TogglePaneEmbedOrFloating => Ok(vec![Action::TogglePaneEmbedOrFloating]),
             CliAction::ToggleFloatingPanes => Ok(vec![Action::ToggleFloatingPanes]),
+            CliAction::TogglePanePinned => Ok(vec![Action::TogglePanePinned]),
             CliAction::ClosePane => Ok(vec![Action::CloseFocus]),
             CliAction::RenamePane { name } => Ok(vec![
-                Action::UndoRenamePane,
+                Action::PaneNameInput(vec![0]),
                 Action::PaneNameInput(name.as_bytes().to_vec()),
             ]),
             CliAction::UndoRenamePane => Ok(vec![Action::UndoRenamePane]),
             CliAction::GoToNextTab => Ok(vec![Action::GoToNextTab]),
             CliAction::GoToPreviousTab => Ok(vec![Action::GoToPreviousTab]),
             CliAction::CloseTab => Ok(vec![Action::CloseTab]),
+            CliAction::ReopenClosedPane => Ok(vec![Action::ReopenClosedPane]),
+            CliAction::ReopenClosedTab => Ok(vec![Action::ReopenClosedTab]),
             CliAction::GoToTab { index } => Ok(vec![Action::GoToTab(index)]),
             CliAction::GoToTabName { name, create } => Ok(vec![Action::GoToTabName(name, create)]),
             CliAction::RenameTab { name } => Ok(vec![
@@ -452,22 +616,30 @@ impl Action {
                         };
                         stringified_error
                     })?;
-                    let mut tabs = layout.tabs();
-                    if tabs.len() > 1 {
-                        return Err(format!("Tab layout cannot itself have tabs"));
-                    } else if !tabs.is_empty() {
+                    let tabs = layout.tabs();
+                    if !tabs.is_empty() {
+                        // a layout with several tabs opens all of them, after the existing ones
                         let swap_tiled_layouts = Some(layout.swap_tiled_layouts.clone());
                         let swap_floating_layouts = Some(layout.swap_floating_layouts.clone());
-                        let (tab_name, layout, floating_panes_layout) =
-                            tabs.drain(..).next().unwrap();
-                        let name = tab_name.or(name);
-                        Ok(vec![Action::NewTab(
-                            Some(layout),
-                            floating_panes_layout,
-                            swap_tiled_layouts,
-                            swap_floating_layouts,
-                            name,
-                        )])
+                        let is_single_tab = tabs.len() == 1;
+                        Ok(tabs
+                            .into_iter()
+                            .map(|(tab_name, layout, floating_panes_layout)| {
+                                // the name given on the command line only applies to a single tab
+                                let name = if is_single_tab {
+                                    tab_name.or_else(|| name.clone())
+                                } else {
+                                    tab_name
+                                };
+                                Action::NewTab(
+                                    Some(layout),
+                                    floating_panes_layout,
+                                    swap_tiled_layouts.clone(),
+                                    swap_floating_layouts.clone(),
+                                    name,
+                                )
+                            })
+                            .collect())
                     } else {
                         let swap_tiled_layouts = Some(layout.swap_tiled_layouts.clone());
                         let swap_floating_layouts = Some(layout.swap_floating_layouts.clone());
@@ -518,6 +690,39 @@ impl Action {
                     move_to_focused_tab,
                 )])
             },
+            CliAction::RespawnPane => Ok(vec![Action::RespawnPane]),
+            CliAction::SwitchWorkspace => Ok(vec![Action::SwitchWorkspace]),
+            CliAction::ToggleScratchpad => Ok(vec![Action::ToggleScratchpad]),
+            CliAction::SwitchTheme { theme } => Ok(vec![Action::SwitchTheme(theme)]),
+            CliAction::QueryPaneProcesses => Ok(vec![Action::QueryPaneProcesses]),
+            CliAction::LoadBuffer { path } => {
+                let mut contents = String::new();
+                match path {
+                    Some(path) if path.as_os_str() != "-" => {
+                        let path = get_current_dir().join(path);
+                        contents = std::fs::read_to_string(&path)
+                            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+                    },
+                    _ => {
+                        std::io::stdin()
+                            .read_to_string(&mut contents)
+                            .map_err(|e| format!("Failed to read from STDIN: {e}"))?;
+                    },
+                }
+                Ok(vec![Action::LoadBuffer(contents)])
+            },
+            CliAction::SaveBuffer { path } => Ok(vec![Action::SaveBuffer(
+                get_current_dir().join(path).to_string_lossy().into(),
+            )]),
+            CliAction::PasteBuffer { pane } => Ok(vec![Action::PasteBuffer(pane)]),
+            CliAction::DumpLayout => Ok(vec![Action::DumpLayout]),
+            CliAction::EnterCopyMode => Ok(vec![Action::EnterCopyMode]),
+            CliAction::LinkHints => Ok(vec![Action::LinkHints]),
+            CliAction::BreakPane => Ok(vec![Action::BreakPane]),
+            CliAction::BreakPaneRight => Ok(vec![Action::BreakPaneRight]),
+            CliAction::BreakPaneLeft => Ok(vec![Action::BreakPaneLeft]),
+            CliAction::MovePaneToTab { index } => Ok(vec![Action::MovePaneToTab(index)]),
+            CliAction::MoveTab { direction } => Ok(vec![Action::MoveTab(direction)]),
         }
     }
 }
@@ -527,6 +732,7 @@ impl From<OnForceClose> for Action {
         match ofc {
             OnForceClose::Quit => Action::Quit,
             OnForceClose::Detach => Action::Detach,
+            OnForceClose::SerializeAndQuit => Action::SerializeSessionAndQuit,
         }
     }
 }
@@ -1,6 +1,7 @@
 //! Trigger a command
-use crate::data::Direction;
+use crate::data::{ClientId, Direction};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
 #[derive(Debug, Clone)]
@@ -31,10 +32,17 @@ pub struct RunCommand {
     pub args: Vec<String>,
     #[serde(default)]
     pub cwd: Option<PathBuf>,
+    /// Environment variables set for the command, on top of those of the session
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
     #[serde(default)]
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    /// The cli client waiting for this command to exit (eg. `zellij run --wait`), to be told its
+    /// exit status
+    #[serde(skip)]
+    pub waiting_client: Option<ClientId>,
 }
 
 impl std::fmt::Display for RunCommand {
@@ -68,6 +76,8 @@ pub struct RunCommandAction {
     pub hold_on_close: bool,
     #[serde(default)]
     pub hold_on_start: bool,
+    #[serde(default)]
+    pub wait_for_exit: bool,
 }
 
 impl From<RunCommandAction> for RunCommand {
@@ -76,8 +86,10 @@ impl From<RunCommandAction> for RunCommand {
             command: action.command,
             args: action.args,
             cwd: action.cwd,
+            env: BTreeMap::new(),
             hold_on_close: action.hold_on_close,
             hold_on_start: action.hold_on_start,
+            waiting_client: None,
         }
     }
 }
@@ -91,6 +103,7 @@ impl From<RunCommand> for RunCommandAction {
             direction: None,
             hold_on_close: run_command.hold_on_close,
             hold_on_start: run_command.hold_on_start,
+            wait_for_exit: run_command.waiting_client.is_some(),
         }
     }
 }
@@ -106,4 +119,14 @@ impl RunCommand {
         self.cwd = Some(cwd);
         self
     }
+    /// Like `RunCommand::from`, but reporting the exit status of the command to `client_id` if
+    /// the action asks to wait for it
+    pub fn from_action_for_client(action: RunCommandAction, client_id: ClientId) -> Self {
+        let wait_for_exit = action.wait_for_exit;
+        let mut run_command = RunCommand::from(action);
+        if wait_for_exit {
+            run_command.waiting_client = Some(client_id);
+        }
+        run_command
+    }
 }
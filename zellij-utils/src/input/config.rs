@@ -1,8 +1,7 @@
-use crate::data::Palette;
 use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
 use std::fs::File;
 use std::io::{self, Read};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 use std::convert::TryFrom;
@@ -10,7 +9,7 @@ use std::convert::TryFrom;
 use super::keybinds::Keybinds;
 use super::options::Options;
 use super::plugins::{PluginsConfig, PluginsConfigError};
-use super::theme::{Themes, UiConfig};
+use super::theme::{Theme, Themes, UiConfig};
 use crate::cli::{CliArgs, Command};
 use crate::envs::EnvironmentVariables;
 use crate::setup;
@@ -96,6 +95,25 @@ pub enum ConfigError {
     PluginsError(#[from] PluginsConfigError),
     #[error("{0}")]
     ConversionError(#[from] ConversionError),
+    // A file in the YAML format of older versions, `subcommand` converts it to KDL
+    #[error(
+        "{} is in the YAML format, which is no longer supported. Convert it to KDL with: zellij {subcommand} {} > {}",
+        .path.display(),
+        .path.display(),
+        .path.with_extension("kdl").display()
+    )]
+    DeprecatedYaml {
+        path: PathBuf,
+        subcommand: &'static str,
+    },
+}
+
+/// Whether `path` is a configuration, layout or theme file of the YAML format of older versions
+pub fn is_yaml_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|extension| extension.to_str()),
+        Some("yaml") | Some("yml")
+    )
 }
 
 impl ConfigError {
@@ -160,10 +178,19 @@ impl TryFrom<&CliArgs> for Config {
 }
 
 impl Config {
-    pub fn theme_config(&self, opts: &Options) -> Option<Palette> {
+    /// The configuration file these arguments point to, whether it exists or not
+    pub fn file_path(opts: &CliArgs) -> Option<PathBuf> {
+        opts.config.clone().or_else(|| {
+            opts.config_dir
+                .clone()
+                .or_else(setup::find_default_config_dir)
+                .map(|config_dir| config_dir.join(DEFAULT_CONFIG_FILE_NAME))
+        })
+    }
+    pub fn theme_config(&self, opts: &Options) -> Option<Theme> {
         match &opts.theme {
-            Some(theme_name) => self.themes.get_theme(theme_name).map(|theme| theme.palette),
-            None => self.themes.get_theme("default").map(|theme| theme.palette),
+            Some(theme_name) => self.themes.get_theme(theme_name).cloned(),
+            None => self.themes.get_theme("default").cloned(),
         }
     }
     /// Gets default configuration from assets
@@ -179,6 +206,10 @@ impl Config {
     }
     pub fn from_path(path: &PathBuf, default_config: Option<Config>) -> ConfigResult {
         match File::open(path) {
+            Ok(_) if is_yaml_file(path) => Err(ConfigError::DeprecatedYaml {
+                path: path.to_path_buf(),
+                subcommand: "convert-config",
+            }),
             Ok(mut file) => {
                 let mut kdl_config = String::new();
                 file.read_to_string(&mut kdl_config)
@@ -222,17 +253,32 @@ impl Config {
             Err(e) => Err(ConfigError::IoPath(e, path.into())),
         }
     }
+    /// Adds the bindings in a stringified `keybinds { .. }` block to the configuration file at
+    /// `path`, creating the file if it does not exist yet
+    pub fn write_keybinds_to_file(kdl_keybinds: &str, path: &PathBuf) -> Result<(), ConfigError> {
+        let kdl_config = match std::fs::read_to_string(path) {
+            Ok(kdl_config) => kdl_config,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(ConfigError::IoPath(e, path.into())),
+        };
+        let kdl_config = Keybinds::add_to_kdl_config(kdl_keybinds, &kdl_config)?;
+        if let Some(config_dir) = path.parent() {
+            std::fs::create_dir_all(config_dir)
+                .map_err(|e| ConfigError::IoPath(e, config_dir.into()))?;
+        }
+        std::fs::write(path, kdl_config).map_err(|e| ConfigError::IoPath(e, path.into()))
+    }
 }
 
 #[cfg(test)]
 mod config_test {
     use super::*;
     use crate::data::{InputMode, Palette, PaletteColor, PluginTag};
-    use crate::input::layout::RunPluginLocation;
+    use crate::input::layout::{PluginUserConfiguration, RunPlugin, RunPluginLocation};
     use crate::input::options::{Clipboard, OnForceClose};
     use crate::input::plugins::{PluginConfig, PluginType, PluginsConfig};
     use crate::input::theme::{FrameConfig, Theme, Themes, UiConfig};
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, HashMap};
     use std::io::Write;
     use tempfile::tempdir;
 
@@ -277,6 +323,28 @@ mod config_test {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn loading_a_yaml_config_points_to_its_conversion() {
+        let tmp = tempdir().unwrap();
+        let yaml_config = tmp.path().join("config.yaml");
+        File::create(&yaml_config)
+            .unwrap()
+            .write_all(b"keybinds:\n  unbind: true\n")
+            .unwrap();
+        let error = Config::from_path(&yaml_config, None).unwrap_err();
+        assert!(matches!(
+            error,
+            ConfigError::DeprecatedYaml {
+                subcommand: "convert-config",
+                ..
+            }
+        ));
+        assert!(error.to_string().ends_with(&format!(
+            "zellij convert-config {0}.yaml > {0}.kdl",
+            tmp.path().join("config").display()
+        )));
+    }
+
     #[test]
     fn try_from_cli_args_with_config_dir_without_config() {
         let mut opts = CliArgs::default();
@@ -286,6 +354,31 @@ mod config_test {
         assert_eq!(result.unwrap(), Config::from_default_assets().unwrap());
     }
 
+    #[test]
+    fn write_keybinds_to_file_creates_missing_config_file() {
+        let tmp = tempdir().unwrap();
+        let mut opts = CliArgs::default();
+        opts.config_dir = Some(tmp.path().join("zellij"));
+        let path = Config::file_path(&opts).unwrap();
+        let kdl_keybinds = r#"
+            keybinds {
+                pane {
+                    bind "z" { SwitchToMode "Resize"; }
+                }
+            }
+        "#;
+        Config::write_keybinds_to_file(kdl_keybinds, &path).unwrap();
+        let config = Config::try_from(&opts).unwrap();
+        assert_eq!(
+            config
+                .keybinds
+                .get_actions_for_key_in_mode(&InputMode::Pane, &crate::data::Key::Char('z')),
+            Some(&vec![crate::input::actions::Action::SwitchToMode(
+                InputMode::Resize
+            )]),
+        );
+    }
+
     #[test]
     fn try_from_cli_args_default() {
         let opts = CliArgs::default();
@@ -305,16 +398,27 @@ mod config_test {
             layout_dir "/path/to/my/layout-dir"
             theme_dir "/path/to/my/theme-dir"
             mouse_mode false
+            support_kitty_keyboard_protocol false
             pane_frames false
             mirror_session true
+            mirror_tab_focus false
             on_force_close "quit"
+            on_sighup "detach"
+            on_sigterm "serialize-and-quit"
+            on_force_close_command "/path/to/my/hook"
             scroll_buffer_size 100000
             copy_command "/path/to/my/copy-command"
             copy_clipboard "primary"
             copy_on_select false
+            word_characters "-_./~"
+            link_opener "firefox"
             scrollback_editor "/path/to/my/scrollback-editor"
             session_name "my awesome session"
+            session_name_template "{git_repo}-{cwd_base}"
             attach_to_session true
+            session_group "work"
+            headless_columns 200
+            headless_rows 50
         "#;
         let config = Config::from_kdl(config_contents, None).unwrap();
         assert_eq!(
@@ -362,6 +466,11 @@ mod config_test {
             Some(false),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.support_kitty_keyboard_protocol,
+            Some(false),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.pane_frames,
             Some(false),
@@ -372,11 +481,31 @@ mod config_test {
             Some(true),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.mirror_tab_focus,
+            Some(false),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.on_force_close,
             Some(OnForceClose::Quit),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.on_sighup,
+            Some(OnForceClose::Detach),
+            "Option set in config"
+        );
+        assert_eq!(
+            config.options.on_sigterm,
+            Some(OnForceClose::SerializeAndQuit),
+            "Option set in config"
+        );
+        assert_eq!(
+            config.options.on_force_close_command,
+            Some("/path/to/my/hook".to_string()),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.scroll_buffer_size,
             Some(100000),
@@ -397,6 +526,16 @@ mod config_test {
             Some(false),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.word_characters,
+            Some(String::from("-_./~")),
+            "Option set in config"
+        );
+        assert_eq!(
+            config.options.link_opener,
+            Some(String::from("firefox")),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.scrollback_editor,
             Some(PathBuf::from("/path/to/my/scrollback-editor")),
@@ -407,11 +546,31 @@ mod config_test {
             Some(String::from("my awesome session")),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.session_name_template,
+            Some(String::from("{git_repo}-{cwd_base}")),
+            "Option set in config"
+        );
         assert_eq!(
             config.options.attach_to_session,
             Some(true),
             "Option set in config"
         );
+        assert_eq!(
+            config.options.session_group,
+            Some(String::from("work")),
+            "Option set in config"
+        );
+        assert_eq!(
+            config.options.headless_columns,
+            Some(200),
+            "Option set in config"
+        );
+        assert_eq!(
+            config.options.headless_rows,
+            Some(50),
+            "Option set in config"
+        );
     }
 
     #[test]
@@ -437,22 +596,20 @@ mod config_test {
         let mut expected_themes = HashMap::new();
         expected_themes.insert(
             "dracula".into(),
-            Theme {
-                palette: Palette {
-                    fg: PaletteColor::Rgb((248, 248, 242)),
-                    bg: PaletteColor::Rgb((40, 42, 54)),
-                    red: PaletteColor::Rgb((255, 85, 85)),
-                    green: PaletteColor::Rgb((80, 250, 123)),
-                    yellow: PaletteColor::Rgb((241, 250, 140)),
-                    blue: PaletteColor::Rgb((98, 114, 164)),
-                    magenta: PaletteColor::Rgb((255, 121, 198)),
-                    orange: PaletteColor::Rgb((255, 184, 108)),
-                    cyan: PaletteColor::Rgb((139, 233, 253)),
-                    black: PaletteColor::Rgb((0, 0, 0)),
-                    white: PaletteColor::Rgb((255, 255, 255)),
-                    ..Default::default()
-                },
-            },
+            Theme::from(Palette {
+                fg: PaletteColor::Rgb((248, 248, 242)),
+                bg: PaletteColor::Rgb((40, 42, 54)),
+                red: PaletteColor::Rgb((255, 85, 85)),
+                green: PaletteColor::Rgb((80, 250, 123)),
+                yellow: PaletteColor::Rgb((241, 250, 140)),
+                blue: PaletteColor::Rgb((98, 114, 164)),
+                magenta: PaletteColor::Rgb((255, 121, 198)),
+                orange: PaletteColor::Rgb((255, 184, 108)),
+                cyan: PaletteColor::Rgb((139, 233, 253)),
+                black: PaletteColor::Rgb((0, 0, 0)),
+                white: PaletteColor::Rgb((255, 255, 255)),
+                ..Default::default()
+            }),
         );
         let expected_themes = Themes::from_data(expected_themes);
         assert_eq!(config.themes, expected_themes, "Theme defined in config");
@@ -494,41 +651,37 @@ mod config_test {
         let mut expected_themes = HashMap::new();
         expected_themes.insert(
             "dracula".into(),
-            Theme {
-                palette: Palette {
-                    fg: PaletteColor::Rgb((248, 248, 242)),
-                    bg: PaletteColor::Rgb((40, 42, 54)),
-                    red: PaletteColor::Rgb((255, 85, 85)),
-                    green: PaletteColor::Rgb((80, 250, 123)),
-                    yellow: PaletteColor::Rgb((241, 250, 140)),
-                    blue: PaletteColor::Rgb((98, 114, 164)),
-                    magenta: PaletteColor::Rgb((255, 121, 198)),
-                    orange: PaletteColor::Rgb((255, 184, 108)),
-                    cyan: PaletteColor::Rgb((139, 233, 253)),
-                    black: PaletteColor::Rgb((0, 0, 0)),
-                    white: PaletteColor::Rgb((255, 255, 255)),
-                    ..Default::default()
-                },
-            },
+            Theme::from(Palette {
+                fg: PaletteColor::Rgb((248, 248, 242)),
+                bg: PaletteColor::Rgb((40, 42, 54)),
+                red: PaletteColor::Rgb((255, 85, 85)),
+                green: PaletteColor::Rgb((80, 250, 123)),
+                yellow: PaletteColor::Rgb((241, 250, 140)),
+                blue: PaletteColor::Rgb((98, 114, 164)),
+                magenta: PaletteColor::Rgb((255, 121, 198)),
+                orange: PaletteColor::Rgb((255, 184, 108)),
+                cyan: PaletteColor::Rgb((139, 233, 253)),
+                black: PaletteColor::Rgb((0, 0, 0)),
+                white: PaletteColor::Rgb((255, 255, 255)),
+                ..Default::default()
+            }),
         );
         expected_themes.insert(
             "nord".into(),
-            Theme {
-                palette: Palette {
-                    fg: PaletteColor::Rgb((216, 222, 233)),
-                    bg: PaletteColor::Rgb((46, 52, 64)),
-                    black: PaletteColor::Rgb((59, 66, 82)),
-                    red: PaletteColor::Rgb((191, 97, 106)),
-                    green: PaletteColor::Rgb((163, 190, 140)),
-                    yellow: PaletteColor::Rgb((235, 203, 139)),
-                    blue: PaletteColor::Rgb((129, 161, 193)),
-                    magenta: PaletteColor::Rgb((180, 142, 173)),
-                    cyan: PaletteColor::Rgb((136, 192, 208)),
-                    white: PaletteColor::Rgb((229, 233, 240)),
-                    orange: PaletteColor::Rgb((208, 135, 112)),
-                    ..Default::default()
-                },
-            },
+            Theme::from(Palette {
+                fg: PaletteColor::Rgb((216, 222, 233)),
+                bg: PaletteColor::Rgb((46, 52, 64)),
+                black: PaletteColor::Rgb((59, 66, 82)),
+                red: PaletteColor::Rgb((191, 97, 106)),
+                green: PaletteColor::Rgb((163, 190, 140)),
+                yellow: PaletteColor::Rgb((235, 203, 139)),
+                blue: PaletteColor::Rgb((129, 161, 193)),
+                magenta: PaletteColor::Rgb((180, 142, 173)),
+                cyan: PaletteColor::Rgb((136, 192, 208)),
+                white: PaletteColor::Rgb((229, 233, 240)),
+                orange: PaletteColor::Rgb((208, 135, 112)),
+                ..Default::default()
+            }),
         );
         let expected_themes = Themes::from_data(expected_themes);
         assert_eq!(config.themes, expected_themes, "Theme defined in config");
@@ -557,22 +710,20 @@ mod config_test {
         let mut expected_themes = HashMap::new();
         expected_themes.insert(
             "eight_bit_theme".into(),
-            Theme {
-                palette: Palette {
-                    fg: PaletteColor::EightBit(248),
-                    bg: PaletteColor::EightBit(40),
-                    red: PaletteColor::EightBit(255),
-                    green: PaletteColor::EightBit(80),
-                    yellow: PaletteColor::EightBit(241),
-                    blue: PaletteColor::EightBit(98),
-                    magenta: PaletteColor::EightBit(255),
-                    orange: PaletteColor::EightBit(255),
-                    cyan: PaletteColor::EightBit(139),
-                    black: PaletteColor::EightBit(1),
-                    white: PaletteColor::EightBit(255),
-                    ..Default::default()
-                },
-            },
+            Theme::from(Palette {
+                fg: PaletteColor::EightBit(248),
+                bg: PaletteColor::EightBit(40),
+                red: PaletteColor::EightBit(255),
+                green: PaletteColor::EightBit(80),
+                yellow: PaletteColor::EightBit(241),
+                blue: PaletteColor::EightBit(98),
+                magenta: PaletteColor::EightBit(255),
+                orange: PaletteColor::EightBit(255),
+                cyan: PaletteColor::EightBit(139),
+                black: PaletteColor::EightBit(1),
+                white: PaletteColor::EightBit(255),
+                ..Default::default()
+            }),
         );
         let expected_themes = Themes::from_data(expected_themes);
         assert_eq!(config.themes, expected_themes, "Theme defined in config");
@@ -640,6 +791,42 @@ mod config_test {
         );
     }
 
+    #[test]
+    fn can_define_plugin_aliases_in_configfile() {
+        let config_contents = r#"
+            plugins {
+                filepicker {
+                    location "file:/path/to/my/strider.wasm"
+                    show_hidden_files true
+                    sort_by "name"
+                }
+            }
+        "#;
+        let config = Config::from_kdl(config_contents, None).unwrap();
+        let mut run_configuration = BTreeMap::new();
+        run_configuration.insert("sort_by".to_owned(), "size".to_owned());
+        let run_plugin = RunPlugin {
+            location: RunPluginLocation::parse("filepicker", None).unwrap(),
+            configuration: PluginUserConfiguration::new(run_configuration),
+            ..Default::default()
+        };
+        let plugin_config = config.plugins.get(&run_plugin).unwrap();
+        let mut expected_configuration = BTreeMap::new();
+        expected_configuration.insert("show_hidden_files".to_owned(), "true".to_owned());
+        expected_configuration.insert("sort_by".to_owned(), "size".to_owned());
+        assert_eq!(
+            plugin_config,
+            PluginConfig {
+                path: PathBuf::from("/path/to/my/strider.wasm"),
+                run: PluginType::Pane(None),
+                location: RunPluginLocation::Zellij(PluginTag::new("filepicker")),
+                _allow_exec_host_cmd: false,
+                userspace_configuration: PluginUserConfiguration::new(expected_configuration),
+            },
+            "Alias resolved with its default configuration overridden by the run configuration"
+        );
+    }
+
     #[test]
     fn can_define_ui_configuration_in_configfile() {
         let config_contents = r#"
@@ -647,6 +834,7 @@ mod config_test {
                 pane_frames {
                     rounded_corners true
                     hide_session_name true
+                    title "{command} – {cwd}"
                 }
             }
         "#;
@@ -655,6 +843,7 @@ mod config_test {
             pane_frames: FrameConfig {
                 rounded_corners: true,
                 hide_session_name: true,
+                title: Some("{command} – {cwd}".to_owned()),
             },
         };
         assert_eq!(config.ui, expected_ui_config, "Ui config defined in config");
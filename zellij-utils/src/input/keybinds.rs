@@ -6,9 +6,12 @@ use crate::data::{InputMode, Key, KeybindsVec};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-/// Used in the config struct
+/// Used in the config struct, the keybindings of the custom modes are kept by mode name
 #[derive(Clone, PartialEq, Deserialize, Serialize, Default)]
-pub struct Keybinds(pub HashMap<InputMode, HashMap<Key, Vec<Action>>>);
+pub struct Keybinds(
+    pub HashMap<InputMode, HashMap<Key, Vec<Action>>>,
+    pub BTreeMap<String, HashMap<Key, Vec<Action>>>,
+);
 
 impl fmt::Debug for Keybinds {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -20,7 +23,16 @@ impl fmt::Debug for Keybinds {
             }
             stable_sorted.insert(mode, stable_sorted_mode_keybinds);
         }
-        write!(f, "{:#?}", stable_sorted)
+        write!(f, "{:#?}", stable_sorted)?;
+        if !self.1.is_empty() {
+            let mut stable_sorted_custom_modes = BTreeMap::new();
+            for (custom_mode, keybinds) in self.1.iter() {
+                let stable_sorted_mode_keybinds: BTreeMap<_, _> = keybinds.iter().collect();
+                stable_sorted_custom_modes.insert(custom_mode, stable_sorted_mode_keybinds);
+            }
+            write!(f, "\n{:#?}", stable_sorted_custom_modes)?;
+        }
+        Ok(())
     }
 }
 
@@ -45,6 +57,42 @@ impl Keybinds {
     pub fn get_input_mode_mut(&mut self, input_mode: &InputMode) -> &mut HashMap<Key, Vec<Action>> {
         self.0.entry(*input_mode).or_insert_with(HashMap::new)
     }
+    pub fn get_actions_for_key_in_custom_mode_or_default_action(
+        &self,
+        custom_mode: &str,
+        key: &Key,
+    ) -> Vec<Action> {
+        self.1
+            .get(custom_mode)
+            .and_then(|custom_mode_keybindings| custom_mode_keybindings.get(key))
+            .cloned()
+            .unwrap_or_else(|| vec![Action::NoOp])
+    }
+    pub fn get_custom_mode_mut(&mut self, custom_mode: &str) -> &mut HashMap<Key, Vec<Action>> {
+        self.1
+            .entry(custom_mode.to_owned())
+            .or_insert_with(HashMap::new)
+    }
+    pub fn has_custom_mode(&self, custom_mode: &str) -> bool {
+        self.1.contains_key(custom_mode)
+    }
+    /// Binds `actions` to typing `first_key` and then `rest`, the first key is bound to an
+    /// `Action::KeySequence` holding the rest of the keys of all sequences that start with it
+    /// (replacing what it was bound to by itself)
+    pub fn bind_key_sequence(
+        input_mode_keybinds: &mut HashMap<Key, Vec<Action>>,
+        first_key: Key,
+        rest: Vec<Key>,
+        actions: Vec<Action>,
+    ) {
+        let mut sequences = match input_mode_keybinds.remove(&first_key).as_deref() {
+            Some([Action::KeySequence(sequences)]) => sequences.to_vec(),
+            _ => vec![],
+        };
+        sequences.retain(|(keys, _actions)| keys != &rest);
+        sequences.push((rest, actions));
+        input_mode_keybinds.insert(first_key, vec![Action::KeySequence(sequences)]);
+    }
     pub fn default_action_for_mode(&self, mode: &InputMode, raw_bytes: Vec<u8>) -> Action {
         match *mode {
             InputMode::Normal | InputMode::Locked => Action::Write(raw_bytes),
@@ -54,6 +102,17 @@ impl Keybinds {
             _ => Action::NoOp,
         }
     }
+    pub fn custom_mode_keybinds_vec(&self, custom_mode: &str) -> Vec<(Key, Vec<Action>)> {
+        self.1
+            .get(custom_mode)
+            .map(|custom_mode_keybinds| {
+                custom_mode_keybinds
+                    .iter()
+                    .map(|(key, actions)| (key.clone(), actions.clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
     pub fn to_keybinds_vec(&self) -> KeybindsVec {
         let mut ret = vec![];
         for (mode, mode_binds) in &self.0 {
@@ -0,0 +1,192 @@
+//! Key events encoded with the kitty keyboard protocol
+//! (<https://sw.kovidgoyal.net/kitty/keyboard-protocol/>), as sent by the terminal emulator once
+//! its "disambiguate escape codes" enhancement is enabled.
+use crate::data::{CharOrArrow, Key};
+
+/// The progressive enhancement flags we support, currently only "disambiguate escape codes"
+pub const SUPPORTED_FLAGS: u8 = 0b1;
+
+const SHIFT: u8 = 0b1;
+const ALT: u8 = 0b10;
+const CTRL: u8 = 0b100;
+
+const ESC: u32 = 27;
+const ENTER: u32 = 13;
+const TAB: u32 = 9;
+const BACKSPACE: u32 = 127;
+const KEYPAD_0: u32 = 57399;
+const KEYPAD_EQUAL: u32 = 57415;
+
+/// A key event sent as `CSI unicode-key-code ; modifiers u`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KittyKey {
+    code: u32,
+    modifiers: u8,
+    is_release: bool,
+}
+
+impl KittyKey {
+    /// Parses a key event from the start of the input, returning it along with the length of its
+    /// escape sequence.
+    pub fn parse(input: &[u8]) -> Option<(KittyKey, usize)> {
+        let params_and_final_byte = input.strip_prefix(b"\x1b[")?;
+        let params_length = params_and_final_byte
+            .iter()
+            .position(|b| !b.is_ascii_digit() && *b != b';' && *b != b':')?;
+        if params_and_final_byte[params_length] != b'u'
+            || !params_and_final_byte.first()?.is_ascii_digit()
+        {
+            return None;
+        }
+        let params_end = params_length + 2;
+        let params = std::str::from_utf8(&input[2..params_end]).ok()?;
+        let mut params = params.split(';');
+        // alternate key codes (after a colon) are only sent with the "report alternate keys"
+        // enhancement
+        let code = params.next()?.split(':').next()?.parse().ok()?;
+        let mut modifiers_and_event = params.next().unwrap_or("1").split(':');
+        // the modifiers are sent as one more than their bitmask, which includes the lock keys
+        let modifiers: u16 = match modifiers_and_event.next() {
+            Some("") | None => 1,
+            Some(modifiers) => modifiers.parse().ok()?,
+        };
+        let is_release = modifiers_and_event.next() == Some("3");
+        let key = KittyKey {
+            code,
+            modifiers: modifiers.saturating_sub(1) as u8,
+            is_release,
+        };
+        Some((key, params_end + 1))
+    }
+    pub fn is_release(&self) -> bool {
+        self.is_release
+    }
+    /// The key as matched against keybindings, if it can be represented as one.
+    pub fn to_key(&self) -> Option<Key> {
+        let shift = self.modifiers & SHIFT != 0;
+        let alt = self.modifiers & ALT != 0;
+        let ctrl = self.modifiers & CTRL != 0;
+        match self.code {
+            ESC => Some(Key::Esc),
+            ENTER => Some(Key::Char('\n')),
+            TAB if shift => Some(Key::BackTab),
+            TAB => Some(Key::Char('\t')),
+            BACKSPACE => Some(Key::Backspace),
+            _ => {
+                let character = match self.character()? {
+                    '\r' => '\n', // the keypad enter key
+                    character => character,
+                };
+                let character = if shift {
+                    character.to_ascii_uppercase()
+                } else {
+                    character
+                };
+                if ctrl {
+                    Some(Key::Ctrl(character.to_ascii_lowercase()))
+                } else if alt {
+                    Some(Key::Alt(CharOrArrow::Char(character)))
+                } else {
+                    Some(Key::Char(character))
+                }
+            },
+        }
+    }
+    /// The bytes sent for this key by terminal emulators that don't speak the kitty keyboard
+    /// protocol, eg. a control character for ctrl and an escape prefix for alt.
+    pub fn to_legacy_bytes(&self) -> Vec<u8> {
+        let shift = self.modifiers & SHIFT != 0;
+        let alt = self.modifiers & ALT != 0;
+        let ctrl = self.modifiers & CTRL != 0;
+        let mut bytes = if alt { vec![0x1b] } else { vec![] };
+        match self.code {
+            ESC => bytes.push(0x1b),
+            ENTER => bytes.push(b'\r'),
+            TAB if shift => bytes.extend_from_slice(b"\x1b[Z"),
+            TAB => bytes.push(b'\t'),
+            BACKSPACE if ctrl => bytes.push(0x08),
+            BACKSPACE => bytes.push(0x7f),
+            _ => match self.character() {
+                Some(character) if ctrl && character.is_ascii() => bytes.push(ctrl_byte(character)),
+                Some(character) => {
+                    let character = if shift {
+                        character.to_ascii_uppercase()
+                    } else {
+                        character
+                    };
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(character.encode_utf8(&mut buffer).as_bytes());
+                },
+                None => return vec![],
+            },
+        }
+        bytes
+    }
+    fn character(&self) -> Option<char> {
+        match self.code {
+            KEYPAD_0..=KEYPAD_EQUAL => "0123456789./*-+\r="
+                .chars()
+                .nth((self.code - KEYPAD_0) as usize),
+            // the rest of the private use area holds keys without a character (eg. media keys)
+            0xE000..=0xF8FF => None,
+            code => char::from_u32(code),
+        }
+    }
+}
+
+/// The control character xterm sends when the character is typed while holding ctrl
+fn ctrl_byte(character: char) -> u8 {
+    match character {
+        ' ' | '2' => 0x00,
+        '3'..='7' => character as u8 - b'3' + 0x1b,
+        '8' | '?' => 0x7f,
+        '/' => 0x1f,
+        'a'..='z' => character as u8 - b'a' + 1,
+        '@'..='_' => character as u8 - b'@',
+        _ => character as u8,
+    }
+}
+
+/// Splits the input into key events encoded with the kitty keyboard protocol and the input
+/// between them, keeping their order.
+pub fn split_kitty_keys(input: &[u8]) -> Vec<(Option<KittyKey>, Vec<u8>)> {
+    let mut segments = vec![];
+    let mut other_input = vec![];
+    let mut index = 0;
+    while index < input.len() {
+        match KittyKey::parse(&input[index..]) {
+            Some((key, length)) => {
+                if !other_input.is_empty() {
+                    segments.push((None, std::mem::take(&mut other_input)));
+                }
+                segments.push((Some(key), input[index..index + length].to_vec()));
+                index += length;
+            },
+            None => {
+                other_input.push(input[index]);
+                index += 1;
+            },
+        }
+    }
+    if !other_input.is_empty() {
+        segments.push((None, other_input));
+    }
+    segments
+}
+
+/// Replaces the key events encoded with the kitty keyboard protocol in the input with their
+/// legacy encoding, for applications that did not enable the protocol.
+pub fn to_legacy_input(input: &[u8]) -> Vec<u8> {
+    split_kitty_keys(input)
+        .into_iter()
+        .flat_map(|(key, bytes)| match key {
+            Some(key) if key.is_release() => vec![],
+            Some(key) => key.to_legacy_bytes(),
+            None => bytes,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[path = "./unit/kitty_keyboard_test.rs"]
+mod kitty_keyboard_test;
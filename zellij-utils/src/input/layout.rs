@@ -12,7 +12,7 @@ use crate::{
     data::Direction,
     input::{
         command::RunCommand,
-        config::{Config, ConfigError},
+        config::{is_yaml_file, Config, ConfigError},
     },
     pane_size::{Dimension, PaneGeom},
     setup,
@@ -91,6 +91,9 @@ impl Run {
                 if merged.args.is_empty() && !base_run_command.args.is_empty() {
                     merged.args = base_run_command.args.clone();
                 }
+                let mut env = base_run_command.env.clone();
+                env.extend(merged.env);
+                merged.env = env;
                 Some(Run::Command(merged))
             },
             (Some(Run::Command(base_run_command)), Some(Run::Cwd(other_cwd))) => {
@@ -172,6 +175,15 @@ impl Run {
             }
         }
     }
+    pub fn add_env(&mut self, env: Option<BTreeMap<String, String>>) {
+        // adds to the env of a Run::Command if it is Some, overriding variables that
+        // are set in both
+        if let Some(env) = env {
+            if let Run::Command(run_command) = self {
+                run_command.env.extend(env);
+            }
+        }
+    }
     pub fn add_close_on_exit(&mut self, close_on_exit: Option<bool>) {
         // overrides the hold_on_close of a Run::Command if it is Some
         // and not empty
@@ -255,6 +267,12 @@ impl PluginUserConfiguration {
     pub fn inner(&self) -> &BTreeMap<String, String> {
         &self.0
     }
+    /// Merges two PluginUserConfiguration structs into one, `other` overrides the keys of `self`.
+    pub fn merge(&self, other: &PluginUserConfiguration) -> Self {
+        let mut configuration = self.0.clone();
+        configuration.extend(other.0.clone());
+        PluginUserConfiguration(configuration)
+    }
 }
 
 impl FromStr for PluginUserConfiguration {
@@ -277,6 +295,7 @@ impl FromStr for PluginUserConfiguration {
 pub enum RunPluginLocation {
     File(PathBuf),
     Zellij(PluginTag),
+    Remote(String), // https url, optionally suffixed with #sha256=<hex digest>
 }
 
 impl Default for RunPluginLocation {
@@ -287,7 +306,14 @@ impl Default for RunPluginLocation {
 
 impl RunPluginLocation {
     pub fn parse(location: &str, cwd: Option<PathBuf>) -> Result<Self, PluginsConfigError> {
-        let url = Url::parse(location)?;
+        let url = match Url::parse(location) {
+            Ok(url) => url,
+            // a bare name (eg. "filepicker") refers to a plugin alias from the plugins config
+            Err(url::ParseError::RelativeUrlWithoutBase) if is_plugin_alias(location) => {
+                return Ok(Self::Zellij(PluginTag::new(location)));
+            },
+            Err(e) => return Err(e.into()),
+        };
 
         let decoded_path = percent_encoding::percent_decode_str(url.path()).decode_utf8_lossy();
 
@@ -322,11 +348,19 @@ impl RunPluginLocation {
                 };
                 Ok(Self::File(path))
             },
+            "https" => Ok(Self::Remote(location.to_owned())),
             _ => Err(PluginsConfigError::InvalidUrlScheme(url)),
         }
     }
 }
 
+fn is_plugin_alias(location: &str) -> bool {
+    !location.is_empty()
+        && location
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+}
+
 impl From<&RunPluginLocation> for Url {
     fn from(location: &RunPluginLocation) -> Self {
         let url = match location {
@@ -335,6 +369,7 @@ impl From<&RunPluginLocation> for Url {
                 path.clone().into_os_string().into_string().unwrap()
             ),
             RunPluginLocation::Zellij(tag) => format!("zellij:{}", tag),
+            RunPluginLocation::Remote(url) => url.clone(),
         };
         Self::parse(&url).unwrap()
     }
@@ -350,6 +385,7 @@ impl fmt::Display for RunPluginLocation {
             ),
 
             Self::Zellij(tag) => write!(f, "{}", tag),
+            Self::Remote(url) => write!(f, "{}", url),
         }
     }
 }
@@ -438,6 +474,7 @@ pub struct FloatingPaneLayout {
     pub run: Option<Run>,
     pub focus: Option<bool>,
     pub already_running: bool,
+    pub pane_initial_contents: Option<String>,
 }
 
 impl FloatingPaneLayout {
@@ -476,6 +513,7 @@ pub struct TiledPaneLayout {
     pub is_expanded_in_stack: bool,
     pub exclude_from_sync: Option<bool>,
     pub run_instructions_to_ignore: Vec<Option<Run>>,
+    pub pane_initial_contents: Option<String>,
 }
 
 impl TiledPaneLayout {
@@ -831,9 +869,18 @@ impl Layout {
         layout_path: &Path,
     ) -> Result<(String, String, Option<(String, String)>), ConfigError> {
         // (path_to_layout as String, stringified_layout, Option<path_to_swap_layout as String, stringified_swap_layout>)
-        let mut layout_file = File::open(&layout_path)
-            .or_else(|_| File::open(&layout_path.with_extension("kdl")))
-            .map_err(|e| ConfigError::IoPath(e, layout_path.into()))?;
+        let mut layout_file = match File::open(&layout_path) {
+            // only when the yaml file itself exists, otherwise we fall back to its kdl sibling
+            Ok(_) if is_yaml_file(layout_path) => {
+                return Err(ConfigError::DeprecatedYaml {
+                    path: layout_path.to_path_buf(),
+                    subcommand: "convert-layout",
+                });
+            },
+            Ok(layout_file) => layout_file,
+            Err(_) => File::open(&layout_path.with_extension("kdl"))
+                .map_err(|e| ConfigError::IoPath(e, layout_path.into()))?,
+        };
 
         let swap_layout_and_path = Layout::swap_layout_and_path(&layout_path);
 
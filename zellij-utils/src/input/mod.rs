@@ -2,6 +2,7 @@ pub mod actions;
 pub mod command;
 pub mod config;
 pub mod keybinds;
+pub mod kitty_keyboard;
 pub mod layout;
 pub mod options;
 pub mod permission;
@@ -20,6 +21,7 @@ mod not_wasm {
     use crate::{
         data::{CharOrArrow, Direction, InputMode, Key, ModeInfo, PluginCapabilities},
         envs,
+        input::kitty_keyboard::split_kitty_keys,
         ipc::ClientAttributes,
     };
     use termwiz::input::{InputEvent, InputParser, KeyCode, KeyEvent, Modifiers};
@@ -40,19 +42,44 @@ mod not_wasm {
             style: attributes.style,
             capabilities,
             session_name,
+            custom_mode: None,
         }
     }
 
+    /// Creates a [`ModeInfo`] struct for the custom mode called `custom_mode`, with its keybinds
+    /// listed as those of [`InputMode::Custom`]
+    pub fn get_custom_mode_info(
+        custom_mode: String,
+        attributes: &ClientAttributes,
+        capabilities: PluginCapabilities,
+    ) -> ModeInfo {
+        let mut mode_info = get_mode_info(InputMode::Custom, attributes, capabilities);
+        mode_info.keybinds.push((
+            InputMode::Custom,
+            attributes.keybinds.custom_mode_keybinds_vec(&custom_mode),
+        ));
+        mode_info.custom_mode = Some(custom_mode);
+        mode_info
+    }
+
     pub fn parse_keys(input_bytes: &[u8]) -> Vec<Key> {
         let mut ret = vec![];
-        let mut input_parser = InputParser::new(); // this is the termwiz InputParser
-        let maybe_more = false;
-        let parse_input_event = |input_event: InputEvent| {
-            if let InputEvent::Key(key_event) = input_event {
-                ret.push(cast_termwiz_key(key_event, input_bytes));
+        for (kitty_key, segment_bytes) in split_kitty_keys(input_bytes) {
+            match kitty_key {
+                Some(kitty_key) if kitty_key.is_release() => {},
+                Some(kitty_key) => ret.extend(kitty_key.to_key()),
+                None => {
+                    let mut input_parser = InputParser::new(); // this is the termwiz InputParser
+                    let maybe_more = false;
+                    let parse_input_event = |input_event: InputEvent| {
+                        if let InputEvent::Key(key_event) = input_event {
+                            ret.push(cast_termwiz_key(key_event, &segment_bytes));
+                        }
+                    };
+                    input_parser.parse(&segment_bytes, parse_input_event, maybe_more);
+                },
             }
-        };
-        input_parser.parse(input_bytes, parse_input_event, maybe_more);
+        }
         ret
     }
 
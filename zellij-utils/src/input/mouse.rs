@@ -9,6 +9,18 @@ pub enum MouseEvent {
     ///
     /// The coordinates are zero-based.
     Press(MouseButton, Position),
+    /// A mouse button was pressed while ctrl was held down.
+    ///
+    /// The coordinates are zero-based.
+    CtrlPress(MouseButton, Position),
+    /// A mouse button was pressed while shift was held down.
+    ///
+    /// The coordinates are zero-based.
+    ShiftPress(MouseButton, Position),
+    /// A mouse button was pressed while alt was held down.
+    ///
+    /// The coordinates are zero-based.
+    AltPress(MouseButton, Position),
     /// A mouse button was released.
     ///
     /// The coordinates are zero-based.
@@ -26,10 +38,17 @@ impl From<termwiz::input::MouseEvent> for MouseEvent {
             .mouse_buttons
             .contains(termwiz::input::MouseButtons::LEFT)
         {
-            MouseEvent::Press(
-                MouseButton::Left,
-                Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1)),
-            )
+            let position =
+                Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1));
+            if event.modifiers.contains(termwiz::input::Modifiers::CTRL) {
+                MouseEvent::CtrlPress(MouseButton::Left, position)
+            } else if event.modifiers.contains(termwiz::input::Modifiers::SHIFT) {
+                MouseEvent::ShiftPress(MouseButton::Left, position)
+            } else if event.modifiers.contains(termwiz::input::Modifiers::ALT) {
+                MouseEvent::AltPress(MouseButton::Left, position)
+            } else {
+                MouseEvent::Press(MouseButton::Left, position)
+            }
         } else if event
             .mouse_buttons
             .contains(termwiz::input::MouseButtons::RIGHT)
@@ -50,19 +69,20 @@ impl From<termwiz::input::MouseEvent> for MouseEvent {
             .mouse_buttons
             .contains(termwiz::input::MouseButtons::VERT_WHEEL)
         {
-            if event
+            let button = if event
                 .mouse_buttons
                 .contains(termwiz::input::MouseButtons::WHEEL_POSITIVE)
             {
-                MouseEvent::Press(
-                    MouseButton::WheelUp,
-                    Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1)),
-                )
+                MouseButton::WheelUp
+            } else {
+                MouseButton::WheelDown
+            };
+            let position =
+                Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1));
+            if event.modifiers.contains(termwiz::input::Modifiers::CTRL) {
+                MouseEvent::CtrlPress(button, position)
             } else {
-                MouseEvent::Press(
-                    MouseButton::WheelDown,
-                    Position::new(event.y.saturating_sub(1) as i32, event.x.saturating_sub(1)),
-                )
+                MouseEvent::Press(button, position)
             }
         } else if event
             .mouse_buttons
@@ -12,6 +12,8 @@ pub enum OnForceClose {
     Quit,
     #[serde(alias = "detach")]
     Detach,
+    #[serde(alias = "serialize-and-quit")]
+    SerializeAndQuit,
 }
 
 impl Default for OnForceClose {
@@ -27,6 +29,7 @@ impl FromStr for OnForceClose {
         match s {
             "quit" => Ok(Self::Quit),
             "detach" => Ok(Self::Detach),
+            "serialize-and-quit" => Ok(Self::SerializeAndQuit),
             e => Err(e.to_string().into()),
         }
     }
@@ -73,18 +76,54 @@ pub struct Options {
     pub mouse_mode: Option<bool>,
     #[clap(long, value_parser)]
     #[serde(default)]
+    /// Ask the terminal emulator to send disambiguated key events with the kitty keyboard
+    /// protocol, passing them on to applications that enabled it (true or false)
+    pub support_kitty_keyboard_protocol: Option<bool>,
+    #[clap(long, value_parser)]
+    #[serde(default)]
     /// Set display of the pane frames (true or false)
     pub pane_frames: Option<bool>,
     #[clap(long, value_parser)]
     #[serde(default)]
     /// Mirror session when multiple users are connected (true or false)
     pub mirror_session: Option<bool>,
-    /// Set behaviour on force close (quit or detach)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    /// Whether all clients of a mirrored session switch tabs together (true), or each client
+    /// focuses its own tab and only mirrors the clients on the same tab (false)
+    pub mirror_tab_focus: Option<bool>,
+    /// Set behaviour on force close (quit, detach or serialize-and-quit)
     #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
     pub on_force_close: Option<OnForceClose>,
+    /// Set behaviour on SIGHUP, eg. when the terminal window is closed (defaults to
+    /// on_force_close)
+    #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    #[serde(default)]
+    pub on_sighup: Option<OnForceClose>,
+    /// Set behaviour on SIGTERM (defaults to on_force_close)
+    #[clap(long, arg_enum, hide_possible_values = true, value_parser)]
+    #[serde(default)]
+    pub on_sigterm: Option<OnForceClose>,
+    /// A command to run on force close before quitting or detaching, the signal that was received
+    /// is passed to it in the ZELLIJ_FORCE_CLOSE_SIGNAL environment variable
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub on_force_close_command: Option<String>,
     #[clap(long, value_parser)]
     pub scroll_buffer_size: Option<usize>,
 
+    /// Spill scrollback lines beyond `scroll_buffer_size` to a temporary file instead of
+    /// discarding them, paging them back in when scrolling or searching (true or false)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub scrollback_spillover: Option<bool>,
+
+    /// The maximum number of lines spilled to disk with `scrollback_spillover`, the oldest ones
+    /// are discarded beyond it (default: 100000)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub scrollback_spillover_limit: Option<usize>,
+
     /// Switch to using a user supplied command for clipboard instead of OSC52
     #[clap(long, value_parser)]
     #[serde(default)]
@@ -106,6 +145,18 @@ pub struct Options {
     #[serde(default)]
     pub copy_on_select: Option<bool>,
 
+    /// Characters, besides alphanumerics, that are considered part of a word when selecting
+    /// text with a double-click
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub word_characters: Option<String>,
+
+    /// Command used to open hyperlinks (OSC 8) that are ctrl-clicked in a pane
+    /// (default is xdg-open, or open on macOS)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub link_opener: Option<String>,
+
     /// Explicit full path to open the scrollback editor (default is $EDITOR or $VISUAL)
     #[clap(long, value_parser)]
     pub scrollback_editor: Option<PathBuf>,
@@ -115,15 +166,106 @@ pub struct Options {
     #[serde(default)]
     pub session_name: Option<String>,
 
+    /// A template for the name of sessions created without an explicit name, with the
+    /// placeholders {cwd_base}, {git_repo} and {git_branch} (default is a random name)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub session_name_template: Option<String>,
+
     /// Whether to attach to a session specified in "session-name" if it exists
     #[clap(long, value_parser)]
     #[serde(default)]
     pub attach_to_session: Option<bool>,
 
+    /// The group (workspace) this session belongs to, used to list and cycle between related
+    /// sessions
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub session_group: Option<String>,
+
+    /// The number of columns panes are laid out in while no client is attached, eg. for sessions
+    /// started with `zellij attach --create-background` (default is 80)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub headless_columns: Option<usize>,
+
+    /// The number of rows panes are laid out in while no client is attached (default is 24)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub headless_rows: Option<usize>,
+
     /// Whether to lay out panes in a predefined set of layouts whenever possible
     #[clap(long, value_parser)]
     #[serde(default)]
     pub auto_layout: Option<bool>,
+
+    /// Whether sessions should be serialized to the cache folder (including their tabs/panes, cwds
+    /// and running commands) so that they can later be resurrected, default is true
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub session_serialization: Option<bool>,
+
+    /// Whether pane viewports (and scrollback) are serialized along with the session, default is
+    /// false
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub serialize_pane_scrollback: Option<bool>,
+
+    /// A command to run when a pane that is not focused rings the bell, eg. to show a desktop
+    /// notification. The id and title of the pane and the name of its tab are passed to it in the
+    /// ZELLIJ_BELL_PANE_ID, ZELLIJ_BELL_PANE_TITLE and ZELLIJ_BELL_TAB_NAME environment variables
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub bell_command: Option<String>,
+
+    /// Mark panes that are not focused as silent once they had no output for this many seconds
+    /// (default is not to monitor silence)
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub monitor_silence: Option<u64>,
+
+    /// Ask for confirmation before closing a pane that is running a process other than its shell
+    /// (eg. a build), default is true
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub pane_close_confirmation: Option<bool>,
+
+    /// A command to run when the session is created, the name of the session is passed to it in
+    /// the ZELLIJ_SESSION_NAME environment variable
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub on_session_create_command: Option<String>,
+
+    /// A command to run when a client attaches to the session, the name of the session, the id of
+    /// the client and the number of connected clients are passed to it in the ZELLIJ_SESSION_NAME,
+    /// ZELLIJ_CLIENT_ID and ZELLIJ_CONNECTED_CLIENTS environment variables
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub on_client_attach_command: Option<String>,
+
+    /// A command to run when a client detaches from the session, it is passed the same
+    /// environment variables as the `on_client_attach_command`
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub on_client_detach_command: Option<String>,
+
+    /// Set the title of the terminal zellij runs in (eg. shown in its tab or window decoration) to
+    /// that of the focused pane, default is true
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub set_terminal_title: Option<bool>,
+
+    /// The title to set for the terminal, with the {session}, {tab}, {pane} and {command}
+    /// placeholders (eg. "{session}:{tab}:{pane}"), default is "Zellij ({session}) - {pane}"
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub terminal_title_template: Option<String>,
+
+    /// How long to wait (in milliseconds) for the next key of a key sequence binding (eg. "Ctrl a
+    /// c") before dropping the keys typed so far, default is 1000
+    #[clap(long, value_parser)]
+    #[serde(default)]
+    pub key_sequence_timeout: Option<u64>,
 }
 
 #[derive(ArgEnum, Deserialize, Serialize, Debug, Clone, Copy, PartialEq)]
@@ -164,9 +306,17 @@ impl Options {
     // TODO: Maybe a good candidate for a macro?
     pub fn merge(&self, other: Options) -> Options {
         let mouse_mode = other.mouse_mode.or(self.mouse_mode);
+        let support_kitty_keyboard_protocol = other
+            .support_kitty_keyboard_protocol
+            .or(self.support_kitty_keyboard_protocol);
         let pane_frames = other.pane_frames.or(self.pane_frames);
         let auto_layout = other.auto_layout.or(self.auto_layout);
+        let session_serialization = other.session_serialization.or(self.session_serialization);
+        let serialize_pane_scrollback = other
+            .serialize_pane_scrollback
+            .or(self.serialize_pane_scrollback);
         let mirror_session = other.mirror_session.or(self.mirror_session);
+        let mirror_tab_focus = other.mirror_tab_focus.or(self.mirror_tab_focus);
         let simplified_ui = other.simplified_ui.or(self.simplified_ui);
         let default_mode = other.default_mode.or(self.default_mode);
         let default_shell = other.default_shell.or_else(|| self.default_shell.clone());
@@ -176,17 +326,55 @@ impl Options {
         let theme_dir = other.theme_dir.or_else(|| self.theme_dir.clone());
         let theme = other.theme.or_else(|| self.theme.clone());
         let on_force_close = other.on_force_close.or(self.on_force_close);
+        let on_sighup = other.on_sighup.or(self.on_sighup);
+        let on_sigterm = other.on_sigterm.or(self.on_sigterm);
+        let on_force_close_command = other
+            .on_force_close_command
+            .or_else(|| self.on_force_close_command.clone());
         let scroll_buffer_size = other.scroll_buffer_size.or(self.scroll_buffer_size);
+        let scrollback_spillover = other.scrollback_spillover.or(self.scrollback_spillover);
+        let scrollback_spillover_limit = other
+            .scrollback_spillover_limit
+            .or(self.scrollback_spillover_limit);
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
+        let word_characters = other
+            .word_characters
+            .or_else(|| self.word_characters.clone());
+        let link_opener = other.link_opener.or_else(|| self.link_opener.clone());
         let scrollback_editor = other
             .scrollback_editor
             .or_else(|| self.scrollback_editor.clone());
         let session_name = other.session_name.or_else(|| self.session_name.clone());
+        let session_name_template = other
+            .session_name_template
+            .or_else(|| self.session_name_template.clone());
         let attach_to_session = other
             .attach_to_session
             .or_else(|| self.attach_to_session.clone());
+        let session_group = other.session_group.or_else(|| self.session_group.clone());
+        let headless_columns = other.headless_columns.or(self.headless_columns);
+        let headless_rows = other.headless_rows.or(self.headless_rows);
+        let bell_command = other.bell_command.or_else(|| self.bell_command.clone());
+        let monitor_silence = other.monitor_silence.or(self.monitor_silence);
+        let pane_close_confirmation = other
+            .pane_close_confirmation
+            .or(self.pane_close_confirmation);
+        let on_session_create_command = other
+            .on_session_create_command
+            .or_else(|| self.on_session_create_command.clone());
+        let on_client_attach_command = other
+            .on_client_attach_command
+            .or_else(|| self.on_client_attach_command.clone());
+        let on_client_detach_command = other
+            .on_client_detach_command
+            .or_else(|| self.on_client_detach_command.clone());
+        let set_terminal_title = other.set_terminal_title.or(self.set_terminal_title);
+        let terminal_title_template = other
+            .terminal_title_template
+            .or_else(|| self.terminal_title_template.clone());
+        let key_sequence_timeout = other.key_sequence_timeout.or(self.key_sequence_timeout);
 
         Options {
             simplified_ui,
@@ -198,17 +386,41 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            support_kitty_keyboard_protocol,
             pane_frames,
             mirror_session,
+            mirror_tab_focus,
             on_force_close,
+            on_sighup,
+            on_sigterm,
+            on_force_close_command,
             scroll_buffer_size,
+            scrollback_spillover,
+            scrollback_spillover_limit,
             copy_command,
             copy_clipboard,
             copy_on_select,
+            word_characters,
+            link_opener,
             scrollback_editor,
             session_name,
+            session_name_template,
             attach_to_session,
+            session_group,
+            headless_columns,
+            headless_rows,
             auto_layout,
+            session_serialization,
+            serialize_pane_scrollback,
+            bell_command,
+            monitor_silence,
+            pane_close_confirmation,
+            on_session_create_command,
+            on_client_attach_command,
+            on_client_detach_command,
+            set_terminal_title,
+            terminal_title_template,
+            key_sequence_timeout,
         }
     }
 
@@ -229,9 +441,25 @@ impl Options {
 
         let simplified_ui = merge_bool(other.simplified_ui, self.simplified_ui);
         let mouse_mode = merge_bool(other.mouse_mode, self.mouse_mode);
+        let support_kitty_keyboard_protocol = merge_bool(
+            other.support_kitty_keyboard_protocol,
+            self.support_kitty_keyboard_protocol,
+        );
         let pane_frames = merge_bool(other.pane_frames, self.pane_frames);
         let auto_layout = merge_bool(other.auto_layout, self.auto_layout);
+        let session_serialization =
+            merge_bool(other.session_serialization, self.session_serialization);
+        let serialize_pane_scrollback = merge_bool(
+            other.serialize_pane_scrollback,
+            self.serialize_pane_scrollback,
+        );
         let mirror_session = merge_bool(other.mirror_session, self.mirror_session);
+        let mirror_tab_focus = merge_bool(other.mirror_tab_focus, self.mirror_tab_focus);
+        let scrollback_spillover =
+            merge_bool(other.scrollback_spillover, self.scrollback_spillover);
+        let scrollback_spillover_limit = other
+            .scrollback_spillover_limit
+            .or(self.scrollback_spillover_limit);
 
         let default_mode = other.default_mode.or(self.default_mode);
         let default_shell = other.default_shell.or_else(|| self.default_shell.clone());
@@ -241,17 +469,51 @@ impl Options {
         let theme_dir = other.theme_dir.or_else(|| self.theme_dir.clone());
         let theme = other.theme.or_else(|| self.theme.clone());
         let on_force_close = other.on_force_close.or(self.on_force_close);
+        let on_sighup = other.on_sighup.or(self.on_sighup);
+        let on_sigterm = other.on_sigterm.or(self.on_sigterm);
+        let on_force_close_command = other
+            .on_force_close_command
+            .or_else(|| self.on_force_close_command.clone());
         let scroll_buffer_size = other.scroll_buffer_size.or(self.scroll_buffer_size);
         let copy_command = other.copy_command.or_else(|| self.copy_command.clone());
         let copy_clipboard = other.copy_clipboard.or(self.copy_clipboard);
         let copy_on_select = other.copy_on_select.or(self.copy_on_select);
+        let word_characters = other
+            .word_characters
+            .or_else(|| self.word_characters.clone());
+        let link_opener = other.link_opener.or_else(|| self.link_opener.clone());
         let scrollback_editor = other
             .scrollback_editor
             .or_else(|| self.scrollback_editor.clone());
         let session_name = other.session_name.or_else(|| self.session_name.clone());
+        let session_name_template = other
+            .session_name_template
+            .or_else(|| self.session_name_template.clone());
         let attach_to_session = other
             .attach_to_session
             .or_else(|| self.attach_to_session.clone());
+        let session_group = other.session_group.or_else(|| self.session_group.clone());
+        let headless_columns = other.headless_columns.or(self.headless_columns);
+        let headless_rows = other.headless_rows.or(self.headless_rows);
+        let bell_command = other.bell_command.or_else(|| self.bell_command.clone());
+        let monitor_silence = other.monitor_silence.or(self.monitor_silence);
+        let pane_close_confirmation = other
+            .pane_close_confirmation
+            .or(self.pane_close_confirmation);
+        let on_session_create_command = other
+            .on_session_create_command
+            .or_else(|| self.on_session_create_command.clone());
+        let on_client_attach_command = other
+            .on_client_attach_command
+            .or_else(|| self.on_client_attach_command.clone());
+        let on_client_detach_command = other
+            .on_client_detach_command
+            .or_else(|| self.on_client_detach_command.clone());
+        let set_terminal_title = other.set_terminal_title.or(self.set_terminal_title);
+        let terminal_title_template = other
+            .terminal_title_template
+            .or_else(|| self.terminal_title_template.clone());
+        let key_sequence_timeout = other.key_sequence_timeout.or(self.key_sequence_timeout);
 
         Options {
             simplified_ui,
@@ -263,17 +525,41 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            support_kitty_keyboard_protocol,
             pane_frames,
             mirror_session,
+            mirror_tab_focus,
             on_force_close,
+            on_sighup,
+            on_sigterm,
+            on_force_close_command,
             scroll_buffer_size,
+            scrollback_spillover,
+            scrollback_spillover_limit,
             copy_command,
             copy_clipboard,
             copy_on_select,
+            word_characters,
+            link_opener,
             scrollback_editor,
             session_name,
+            session_name_template,
             attach_to_session,
+            session_group,
+            headless_columns,
+            headless_rows,
             auto_layout,
+            session_serialization,
+            serialize_pane_scrollback,
+            bell_command,
+            monitor_silence,
+            pane_close_confirmation,
+            on_session_create_command,
+            on_client_attach_command,
+            on_client_detach_command,
+            set_terminal_title,
+            terminal_title_template,
+            key_sequence_timeout,
         }
     }
 
@@ -322,17 +608,41 @@ impl From<CliOptions> for Options {
             layout_dir: opts.layout_dir,
             theme_dir: opts.theme_dir,
             mouse_mode: opts.mouse_mode,
+            support_kitty_keyboard_protocol: opts.support_kitty_keyboard_protocol,
             pane_frames: opts.pane_frames,
             mirror_session: opts.mirror_session,
+            mirror_tab_focus: opts.mirror_tab_focus,
             on_force_close: opts.on_force_close,
+            on_sighup: opts.on_sighup,
+            on_sigterm: opts.on_sigterm,
+            on_force_close_command: opts.on_force_close_command,
             scroll_buffer_size: opts.scroll_buffer_size,
+            scrollback_spillover: opts.scrollback_spillover,
+            scrollback_spillover_limit: opts.scrollback_spillover_limit,
             copy_command: opts.copy_command,
             copy_clipboard: opts.copy_clipboard,
             copy_on_select: opts.copy_on_select,
+            word_characters: opts.word_characters,
+            link_opener: opts.link_opener,
             scrollback_editor: opts.scrollback_editor,
             session_name: opts.session_name,
+            session_name_template: opts.session_name_template,
             attach_to_session: opts.attach_to_session,
+            session_group: opts.session_group,
+            headless_columns: opts.headless_columns,
+            headless_rows: opts.headless_rows,
             auto_layout: opts.auto_layout,
+            session_serialization: opts.session_serialization,
+            serialize_pane_scrollback: opts.serialize_pane_scrollback,
+            bell_command: opts.bell_command,
+            monitor_silence: opts.monitor_silence,
+            pane_close_confirmation: opts.pane_close_confirmation,
+            on_session_create_command: opts.on_session_create_command,
+            on_client_attach_command: opts.on_client_attach_command,
+            on_client_detach_command: opts.on_client_detach_command,
+            set_terminal_title: opts.set_terminal_title,
+            terminal_title_template: opts.terminal_title_template,
+            key_sequence_timeout: opts.key_sequence_timeout,
             ..Default::default()
         }
     }
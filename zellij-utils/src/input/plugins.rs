@@ -52,9 +52,17 @@ impl PluginsConfig {
             }),
             RunPluginLocation::Zellij(tag) => self.0.get(tag).cloned().map(|plugin| PluginConfig {
                 _allow_exec_host_cmd: run._allow_exec_host_cmd,
-                userspace_configuration: run.configuration.clone(),
+                userspace_configuration: plugin.userspace_configuration.merge(&run.configuration),
                 ..plugin
             }),
+            RunPluginLocation::Remote(url) => Some(PluginConfig {
+                // this is replaced by the path of the downloaded plugin before it is loaded
+                path: PathBuf::from(url),
+                run: PluginType::Pane(None),
+                _allow_exec_host_cmd: run._allow_exec_host_cmd,
+                location: run.location.clone(),
+                userspace_configuration: run.configuration.clone(),
+            }),
         }
     }
 
@@ -203,6 +211,18 @@ impl PluginConfig {
     pub fn is_builtin(&self) -> bool {
         matches!(self.location, RunPluginLocation::Zellij(_))
     }
+
+    /// The url this plugin should be downloaded from, either because it was loaded from an
+    /// `https:` location or through an alias pointing to one.
+    pub fn remote_url(&self) -> Option<&str> {
+        match &self.location {
+            RunPluginLocation::Remote(url) => Some(url),
+            _ => self
+                .path
+                .to_str()
+                .filter(|path| path.starts_with("https://")),
+        }
+    }
 }
 
 /// Type of the plugin. Defaults to Pane.
@@ -231,7 +251,7 @@ pub enum PluginsConfigError {
     DuplicatePlugins(PluginTag),
     #[error("Failed to parse url: {0:?}")]
     InvalidUrl(#[from] url::ParseError),
-    #[error("Only 'file:' and 'zellij:' url schemes are supported for plugin lookup. '{0}' does not match either.")]
+    #[error("Only 'file:', 'zellij:' and 'https:' url schemes are supported for plugin lookup. '{0}' does not match any of them.")]
     InvalidUrlScheme(Url),
     #[error("Could not find plugin at the path: '{0:?}'")]
     InvalidPluginLocation(PathBuf),
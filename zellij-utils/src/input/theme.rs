@@ -7,9 +7,9 @@ use std::{
     fmt,
 };
 
-use crate::data::Palette;
+use crate::data::{Palette, Styling};
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct UiConfig {
     pub pane_frames: FrameConfig,
 }
@@ -22,10 +22,12 @@ impl UiConfig {
     }
 }
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct FrameConfig {
     pub rounded_corners: bool,
     pub hide_session_name: bool,
+    /// A template for the titles of terminal pane frames, eg. `"{command} – {cwd}"`
+    pub title: Option<String>,
 }
 
 impl FrameConfig {
@@ -33,6 +35,7 @@ impl FrameConfig {
         let mut merged = self.clone();
         merged.rounded_corners = other.rounded_corners;
         merged.hide_session_name = other.hide_session_name;
+        merged.title = other.title.or(merged.title);
         merged
     }
 }
@@ -67,12 +70,38 @@ impl Themes {
     pub fn get_theme(&self, theme_name: &str) -> Option<&Theme> {
         self.0.get(theme_name)
     }
+    /// The names of the themes, sorted
+    pub fn theme_names(&self) -> Vec<String> {
+        let mut theme_names: Vec<String> = self.0.keys().cloned().collect();
+        theme_names.sort();
+        theme_names
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct Theme {
     #[serde(flatten)]
     pub palette: Palette,
+    #[serde(default)]
+    pub styling: Styling,
+}
+
+impl From<Palette> for Theme {
+    fn from(palette: Palette) -> Self {
+        Theme {
+            palette,
+            styling: Styling::from(palette),
+        }
+    }
+}
+
+impl From<Styling> for Theme {
+    fn from(styling: Styling) -> Self {
+        Theme {
+            palette: Palette::from(styling),
+            styling,
+        }
+    }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
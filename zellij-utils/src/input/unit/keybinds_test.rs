@@ -542,3 +542,198 @@ fn error_received_on_unknown_key_instruction() {
     let config_error = Config::from_kdl(config_contents, None).unwrap_err();
     assert_snapshot!(format!("{:?}", config_error));
 }
+
+#[test]
+fn can_rebind_keys_from_a_keybinds_block() {
+    let config_contents = r#"
+        keybinds {
+            pane {
+                bind "z" { TogglePaneFrames; SwitchToMode "Normal"; }
+                bind "r" { SwitchToMode "Resize"; }
+            }
+        }
+    "#;
+    let keybinds_contents = r#"
+        keybinds {
+            pane {
+                bind "z" { SwitchToMode "Resize"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    let keybinds =
+        Keybinds::from_kdl_string(keybinds_contents, config.keybinds, &config.options).unwrap();
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Pane, &Key::Char('z')),
+        Some(&vec![Action::SwitchToMode(InputMode::Resize)]),
+        "Keybinding was rebound"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Pane, &Key::Char('r')),
+        Some(&vec![Action::SwitchToMode(InputMode::Resize)]),
+        "Other keybindings were left in place"
+    );
+}
+
+#[test]
+fn rebound_keys_are_added_to_config_file() {
+    let config_contents = r#"
+        keybinds {
+            pane {
+                bind "z" { TogglePaneFrames; SwitchToMode "Normal"; }
+                bind "r" { SwitchToMode "Resize"; }
+            }
+        }
+        pane_frames false
+    "#;
+    let keybinds_contents = r#"
+        keybinds {
+            pane {
+                bind "z" { SwitchToMode "Resize"; }
+            }
+        }
+    "#;
+    let updated_config_contents =
+        Keybinds::add_to_kdl_config(keybinds_contents, config_contents).unwrap();
+    let config = Config::from_kdl(&updated_config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Pane, &Key::Char('z')),
+        Some(&vec![Action::SwitchToMode(InputMode::Resize)]),
+        "New keybinding takes precedence over the one already in the file"
+    );
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Pane, &Key::Char('r')),
+        Some(&vec![Action::SwitchToMode(InputMode::Resize)]),
+        "Existing keybindings were kept"
+    );
+    assert_eq!(
+        config.options.pane_frames,
+        Some(false),
+        "Rest of the config file was kept"
+    );
+}
+
+#[test]
+fn rebound_keys_create_keybinds_block_in_config_file() {
+    let keybinds_contents = r#"
+        keybinds {
+            pane {
+                bind "z" { SwitchToMode "Resize"; }
+            }
+        }
+    "#;
+    let updated_config_contents =
+        Keybinds::add_to_kdl_config(keybinds_contents, "pane_frames false\n").unwrap();
+    let config = Config::from_kdl(&updated_config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Pane, &Key::Char('z')),
+        Some(&vec![Action::SwitchToMode(InputMode::Resize)]),
+        "Keybinds block was added to the config file"
+    );
+}
+
+#[test]
+fn can_define_key_sequences_in_configfile() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Ctrl a c" { GoToNextTab; }
+                bind "Ctrl a x" "Ctrl a Ctrl x" { CloseFocus; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('a')),
+        Some(&vec![Action::KeySequence(vec![
+            (vec![Key::Char('c')], vec![Action::GoToNextTab]),
+            (vec![Key::Char('x')], vec![Action::CloseFocus]),
+            (vec![Key::Ctrl('x')], vec![Action::CloseFocus]),
+        ])]),
+        "Sequences starting with the same key are bound to it together"
+    );
+}
+
+#[test]
+fn can_define_custom_modes_in_configfile() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Alt g" { SwitchToMode "git"; }
+            }
+            mode "git" {
+                bind "s" { WriteChars "git status"; SwitchToMode "Normal"; }
+            }
+            shared_except "locked" {
+                bind "Ctrl g" { SwitchToMode "Locked"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Alt(CharOrArrow::Char('g'))),
+        Some(&vec![Action::SwitchToCustomMode("git".to_owned())]),
+        "Custom mode can be switched to"
+    );
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_custom_mode_or_default_action("git", &Key::Char('s')),
+        vec![
+            Action::WriteChars("git status".to_owned()),
+            Action::SwitchToMode(InputMode::Normal)
+        ],
+        "Keybind bound in custom mode"
+    );
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_custom_mode_or_default_action("git", &Key::Ctrl('g')),
+        vec![Action::SwitchToMode(InputMode::Locked)],
+        "Shared keybind bound in custom mode"
+    );
+}
+
+#[test]
+fn switching_to_undefined_mode_is_an_error() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Alt g" { SwitchToMode "gti"; }
+            }
+            mode "git" {
+                bind "Esc" { SwitchToMode "Normal"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None);
+    assert!(config.is_err(), "Unknown mode is an error");
+}
+
+#[test]
+fn can_bind_switching_themes_in_configfile() {
+    let config_contents = r#"
+        keybinds {
+            normal {
+                bind "Alt t" { SwitchTheme "dracula"; }
+            }
+        }
+    "#;
+    let config = Config::from_kdl(config_contents, None).unwrap();
+    assert_eq!(
+        config
+            .keybinds
+            .get_actions_for_key_in_mode(&InputMode::Normal, &Key::Alt(CharOrArrow::Char('t'))),
+        Some(&vec![Action::SwitchTheme("dracula".into())]),
+    );
+}
@@ -0,0 +1,75 @@
+use super::super::kitty_keyboard::*;
+use crate::data::{CharOrArrow, Key};
+
+fn parse_key(input: &str) -> KittyKey {
+    let (key, length) = KittyKey::parse(input.as_bytes()).unwrap();
+    assert_eq!(length, input.len());
+    key
+}
+
+#[test]
+fn parse_keys_with_modifiers() {
+    assert_eq!(parse_key("\u{1b}[27u").to_key(), Some(Key::Esc));
+    assert_eq!(parse_key("\u{1b}[105;5u").to_key(), Some(Key::Ctrl('i')));
+    assert_eq!(
+        parse_key("\u{1b}[97;3u").to_key(),
+        Some(Key::Alt(CharOrArrow::Char('a')))
+    );
+    assert_eq!(
+        parse_key("\u{1b}[97;4u").to_key(),
+        Some(Key::Alt(CharOrArrow::Char('A')))
+    );
+    assert_eq!(parse_key("\u{1b}[9;2u").to_key(), Some(Key::BackTab));
+    // the caps lock modifier does not change the key
+    assert_eq!(parse_key("\u{1b}[99;69u").to_key(), Some(Key::Ctrl('c')));
+}
+
+#[test]
+fn parse_rejects_other_sequences() {
+    assert!(KittyKey::parse(b"\x1b[u").is_none()); // restore cursor
+    assert!(KittyKey::parse(b"\x1b[?1u").is_none()); // flags query response
+    assert!(KittyKey::parse(b"\x1b[1;5A").is_none());
+    assert!(KittyKey::parse(b"\x1b[27").is_none());
+}
+
+#[test]
+fn keys_to_legacy_bytes() {
+    assert_eq!(parse_key("\u{1b}[27u").to_legacy_bytes(), vec![0x1b]);
+    assert_eq!(parse_key("\u{1b}[99;5u").to_legacy_bytes(), vec![0x03]);
+    assert_eq!(parse_key("\u{1b}[91;5u").to_legacy_bytes(), vec![0x1b]);
+    assert_eq!(
+        parse_key("\u{1b}[97;3u").to_legacy_bytes(),
+        b"\x1ba".to_vec()
+    );
+    assert_eq!(parse_key("\u{1b}[13;2u").to_legacy_bytes(), b"\r".to_vec());
+    assert_eq!(parse_key("\u{1b}[57400u").to_legacy_bytes(), b"1".to_vec());
+    assert_eq!(
+        parse_key("\u{1b}[57428u").to_legacy_bytes(),
+        Vec::<u8>::new()
+    );
+}
+
+#[test]
+fn input_to_legacy_input() {
+    assert_eq!(
+        to_legacy_input(b"ab\x1b[99;5u\x1b[1;5Ac"),
+        b"ab\x03\x1b[1;5Ac".to_vec()
+    );
+    assert_eq!(to_legacy_input(b"\x1b[97;1:3u"), Vec::<u8>::new());
+}
+
+#[test]
+fn split_input_around_kitty_keys() {
+    let segments = split_kitty_keys(b"a\x1b[27ub");
+    assert_eq!(
+        segments
+            .iter()
+            .map(|(key, bytes)| (key.is_some(), bytes.clone()))
+            .collect::<Vec<_>>(),
+        vec![
+            (false, b"a".to_vec()),
+            (true, b"\x1b[27u".to_vec()),
+            (false, b"b".to_vec()),
+        ]
+    );
+}
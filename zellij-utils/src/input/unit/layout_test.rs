@@ -468,6 +468,45 @@ fn layout_with_command_panes_and_cwd_and_args() {
     assert_eq!(layout, expected_layout);
 }
 
+#[test]
+fn layout_with_command_panes_and_env() {
+    let kdl_layout = r#"
+        layout {
+            pane command="cargo" {
+                args "run"
+                env {
+                    RUST_LOG "debug"
+                    PORT 8080
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let mut env = BTreeMap::new();
+    env.insert(String::from("PORT"), String::from("8080"));
+    env.insert(String::from("RUST_LOG"), String::from("debug"));
+    let expected_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![TiledPaneLayout {
+                    run: Some(Run::Command(RunCommand {
+                        command: PathBuf::from("cargo"),
+                        args: vec![String::from("run")],
+                        env,
+                        hold_on_close: true,
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    assert_eq!(layout, expected_layout);
+}
+
 #[test]
 fn layout_with_command_panes_and_close_on_exit() {
     let kdl_layout = r#"
@@ -1352,6 +1391,83 @@ fn args_added_to_args_in_template() {
     assert_snapshot!(format!("{:#?}", layout));
 }
 
+#[test]
+fn pane_template_instantiated_in_multiple_tabs_with_overrides() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="jumphost" { command "ssh"; args "host"; }
+            tab name="prod" {
+                jumphost
+                jumphost { args "other-host"; }
+            }
+            tab name="staging" {
+                jumphost cwd="/tmp"
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let ssh = |args: &[&str], cwd: Option<&str>| {
+        Some(Run::Command(RunCommand {
+            command: PathBuf::from("ssh"),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: cwd.map(PathBuf::from),
+            hold_on_close: true,
+            ..Default::default()
+        }))
+    };
+    let runs: Vec<Vec<Option<Run>>> = layout
+        .tabs
+        .iter()
+        .map(|(_tab_name, tiled_panes, _floating_panes)| {
+            tiled_panes
+                .children
+                .iter()
+                .map(|pane| pane.run.clone())
+                .collect()
+        })
+        .collect();
+    assert_eq!(
+        runs,
+        vec![
+            vec![ssh(&["host"], None), ssh(&["other-host"], None)],
+            vec![ssh(&["host"], Some("/tmp"))],
+        ],
+        "template instantiated in each tab with its overrides"
+    );
+}
+
+#[test]
+fn env_added_to_env_in_template() {
+    let kdl_layout = r#"
+        layout {
+            pane_template name="server" {
+                command "cargo"
+                env {
+                    RUST_LOG "info"
+                    PORT 8080
+                }
+            }
+            server {
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let (tiled_panes, _floating_panes) = layout.template.unwrap();
+    let mut expected_env = BTreeMap::new();
+    expected_env.insert(String::from("PORT"), String::from("8080"));
+    expected_env.insert(String::from("RUST_LOG"), String::from("debug"));
+    match &tiled_panes.children[0].run {
+        Some(Run::Command(run_command)) => assert_eq!(
+            run_command.env, expected_env,
+            "consumer env merged into the template env"
+        ),
+        run => panic!("expected a command pane, got: {:?}", run),
+    }
+}
+
 #[test]
 fn close_on_exit_added_to_close_on_exit_in_template() {
     let kdl_layout = r#"
@@ -1444,6 +1560,21 @@ fn error_on_bare_args_without_command() {
     assert!(layout.is_err(), "error provided");
 }
 
+#[test]
+fn error_on_bare_env_without_command() {
+    let kdl_layout = r#"
+        layout {
+            pane {
+                env {
+                    RUST_LOG "debug"
+                }
+            }
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "error provided");
+}
+
 #[test]
 fn error_on_bare_close_on_exit_without_command() {
     let kdl_layout = r#"
@@ -2024,6 +2155,9 @@ fn run_plugin_location_parsing() {
             pane {
                 plugin location="file:c:/absolute/windows/plugin.wasm"
             }
+            pane {
+                plugin location="https://example.com/plugin.wasm#sha256=abcdef"
+            }
         }
     "#;
     let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
@@ -2087,6 +2221,16 @@ fn run_plugin_location_parsing() {
                         })),
                         ..Default::default()
                     },
+                    TiledPaneLayout {
+                        run: Some(Run::Plugin(RunPlugin {
+                            _allow_exec_host_cmd: false,
+                            location: RunPluginLocation::Remote(
+                                "https://example.com/plugin.wasm#sha256=abcdef".to_owned(),
+                            ),
+                            configuration: Default::default(),
+                        })),
+                        ..Default::default()
+                    },
                 ],
                 ..Default::default()
             },
@@ -2153,3 +2297,81 @@ fn env_var_missing() {
     let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
     assert!(layout.is_err(), "invalid env var lookup should fail");
 }
+
+#[test]
+fn new_tab_template_is_used_as_the_layout_template() {
+    let kdl_layout = r#"
+        layout {
+            default_tab_template {
+                pane size=1 borderless=true
+                children
+            }
+            new_tab_template {
+                pane size=2 borderless=true
+                children
+            }
+            tab name="first"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let (template, _floating_panes) = layout.template.unwrap();
+    assert_eq!(template.children.len(), 2);
+    assert_eq!(template.children[0].split_size, Some(SplitSize::Fixed(2)));
+}
+
+#[test]
+fn pane_contents_file_is_read_into_the_pane_layout() {
+    let contents_file = std::env::temp_dir().join("zellij-layout-test-pane-contents.txt");
+    std::fs::write(&contents_file, "some pane contents").unwrap();
+    let kdl_layout = format!(
+        r#"
+        layout {{
+            pane contents_file="{}"
+            floating_panes {{
+                pane contents_file="{}"
+            }}
+        }}
+    "#,
+        contents_file.display(),
+        contents_file.display()
+    );
+    let layout = Layout::from_kdl(&kdl_layout, "layout_file_name".into(), None, None).unwrap();
+    let _ = std::fs::remove_file(&contents_file);
+    let (tiled_panes, floating_panes) = layout.new_tab();
+    assert_eq!(
+        tiled_panes.children[0].pane_initial_contents.as_deref(),
+        Some("some pane contents")
+    );
+    assert_eq!(
+        floating_panes[0].pane_initial_contents.as_deref(),
+        Some("some pane contents")
+    );
+}
+
+#[test]
+fn missing_pane_contents_file_is_an_error() {
+    let kdl_layout = r#"
+        layout {
+            pane contents_file="/this/file/does/not/exist.txt"
+        }
+    "#;
+    let layout = Layout::from_kdl(kdl_layout, "layout_file_name".into(), None, None);
+    assert!(layout.is_err(), "missing contents file should fail");
+}
+
+#[test]
+fn yaml_layout_path_falls_back_to_its_kdl_conversion() {
+    let layout_dir = tempfile::tempdir().unwrap();
+    let yaml_layout_path = layout_dir.path().join("my_layout.yaml");
+    std::fs::write(layout_dir.path().join("my_layout.kdl"), "layout").unwrap();
+    let (path, raw_layout, _swap_layouts) =
+        Layout::stringified_from_path(&yaml_layout_path).unwrap();
+    assert_eq!(path, yaml_layout_path.to_string_lossy());
+    assert_eq!(raw_layout, "layout");
+
+    std::fs::write(&yaml_layout_path, "---\ntabs: []").unwrap();
+    assert!(matches!(
+        Layout::stringified_from_path(&yaml_layout_path),
+        Err(ConfigError::DeprecatedYaml { .. })
+    ));
+}
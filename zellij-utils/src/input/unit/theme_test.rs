@@ -1,4 +1,5 @@
 use super::super::theme::*;
+use crate::data::PaletteColor;
 use insta::assert_snapshot;
 use std::path::{Path, PathBuf};
 
@@ -21,3 +22,56 @@ fn no_theme_is_err() {
     let theme = Themes::from_path(path);
     assert!(theme.is_err());
 }
+
+#[test]
+fn theme_with_ui_elements_from_file() {
+    let path = theme_test_dir("catppuccin-mocha.kdl".into());
+    let theme = Themes::from_path(path).unwrap();
+    assert_snapshot!(format!("{:#?}", theme));
+}
+
+#[test]
+fn theme_with_missing_ui_element_is_err() {
+    let theme = Themes::from_string(
+        r#"
+        themes {
+            incomplete {
+                text_unselected {
+                    base 205 214 244
+                    background 24 24 37
+                    emphasis_0 250 179 135
+                    emphasis_1 137 220 235
+                    emphasis_2 166 227 161
+                    emphasis_3 245 194 231
+                }
+            }
+        }
+        "#
+        .to_owned(),
+    );
+    assert!(theme.is_err());
+}
+
+#[test]
+fn true_colors_are_downsampled_to_256_colors() {
+    assert_eq!(
+        PaletteColor::Rgb((255, 0, 0)).to_eight_bit(),
+        PaletteColor::EightBit(196),
+        "pure colors map to the color cube"
+    );
+    assert_eq!(
+        PaletteColor::Rgb((250, 179, 135)).to_eight_bit(),
+        PaletteColor::EightBit(216),
+        "other colors map to the closest color in the cube"
+    );
+    assert_eq!(
+        PaletteColor::Rgb((24, 24, 37)).to_eight_bit(),
+        PaletteColor::EightBit(234),
+        "dark grays map to the grayscale ramp"
+    );
+    assert_eq!(
+        PaletteColor::EightBit(2).to_eight_bit(),
+        PaletteColor::EightBit(2),
+        "256 colors are left alone"
+    );
+}
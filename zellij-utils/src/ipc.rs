@@ -42,6 +42,7 @@ pub struct ClientAttributes {
     pub size: Size,
     pub style: Style,
     pub keybinds: Keybinds,
+    pub pane_frame_title: Option<String>,
 }
 
 #[derive(Default, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
@@ -101,7 +102,10 @@ pub enum ServerToClientMsg {
     Connected,
     ActiveClients(Vec<ClientId>),
     Log(Vec<String>),
+    CommandExited(Option<i32>), // exit status, if the command could be run
     SwitchSession(ConnectToSession),
+    RebindKeys(Keybinds),
+    UnblockCliPipeInput, // the last message sent with `zellij pipe` was handled
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -39,6 +39,7 @@ pub struct KdlLayoutParser<'a> {
     tab_templates: HashMap<String, (TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
     pane_templates: HashMap<String, (PaneOrFloatingPane, KdlNode)>,
     default_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>, KdlNode)>,
+    new_tab_template: Option<(TiledPaneLayout, Vec<FloatingPaneLayout>)>,
 }
 
 impl<'a> KdlLayoutParser<'a> {
@@ -48,6 +49,7 @@ impl<'a> KdlLayoutParser<'a> {
             tab_templates: HashMap::new(),
             pane_templates: HashMap::new(),
             default_tab_template: None,
+            new_tab_template: None,
             global_cwd,
         }
     }
@@ -59,6 +61,7 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "pane_template"
             || word == "tab_template"
             || word == "default_tab_template"
+            || word == "new_tab_template"
             || word == "command"
             || word == "edit"
             || word == "plugin"
@@ -73,6 +76,7 @@ impl<'a> KdlLayoutParser<'a> {
             || word == "size"
             || word == "cwd"
             || word == "split_direction"
+            || word == "contents_file"
             || word == "swap_tiled_layout"
             || word == "swap_floating_layout"
     }
@@ -86,6 +90,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "split_direction"
@@ -94,6 +99,7 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "stacked"
             || property_name == "expanded"
             || property_name == "exclude_from_sync"
+            || property_name == "contents_file"
     }
     fn is_a_valid_floating_pane_property(&self, property_name: &str) -> bool {
         property_name == "borderless"
@@ -104,12 +110,14 @@ impl<'a> KdlLayoutParser<'a> {
             || property_name == "edit"
             || property_name == "cwd"
             || property_name == "args"
+            || property_name == "env"
             || property_name == "close_on_exit"
             || property_name == "start_suspended"
             || property_name == "x"
             || property_name == "y"
             || property_name == "width"
             || property_name == "height"
+            || property_name == "contents_file"
     }
     fn is_a_valid_tab_property(&self, property_name: &str) -> bool {
         property_name == "focus"
@@ -383,6 +391,28 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_env(
+        &self,
+        pane_node: &KdlNode,
+    ) -> Result<Option<BTreeMap<String, String>>, ConfigError> {
+        match kdl_get_child!(pane_node, "env") {
+            Some(kdl_env) => {
+                let mut env = BTreeMap::new();
+                for env_var in kdl_children_nodes!(kdl_env).unwrap_or(&[]) {
+                    let env_var_value = kdl_first_entry_as_string!(env_var)
+                        .map(|s| s.to_string())
+                        .or_else(|| kdl_first_entry_as_i64!(env_var).map(|i| i.to_string()))
+                        .ok_or(kdl_parsing_error!(
+                            format!("Failed to parse env var: {:?}", kdl_name!(env_var)),
+                            env_var
+                        ))?;
+                    env.insert(kdl_name!(env_var).to_owned(), env_var_value);
+                }
+                Ok(Some(env))
+            },
+            None => Ok(None),
+        }
+    }
     fn cwd_prefix(&self, tab_cwd: Option<&PathBuf>) -> Result<Option<PathBuf>, ConfigError> {
         Ok(match (&self.global_cwd, tab_cwd) {
             (Some(global_cwd), Some(tab_cwd)) => Some(global_cwd.join(tab_cwd)),
@@ -404,6 +434,30 @@ impl<'a> KdlLayoutParser<'a> {
             None => Ok(None),
         }
     }
+    fn parse_contents_file(&self, kdl_node: &KdlNode) -> Result<Option<String>, ConfigError> {
+        // the contents are read when parsing so that the server does not need access to the file
+        match self.parse_path(kdl_node, "contents_file")? {
+            Some(contents_file) => {
+                let contents_file = match &self.global_cwd {
+                    Some(global_cwd) => global_cwd.join(contents_file),
+                    None => contents_file,
+                };
+                std::fs::read_to_string(&contents_file)
+                    .map(Some)
+                    .map_err(|e| {
+                        kdl_parsing_error!(
+                            format!(
+                                "Failed to read contents_file {}: {}",
+                                contents_file.display(),
+                                e
+                            ),
+                            kdl_node
+                        )
+                    })
+            },
+            None => Ok(None),
+        }
+    }
     fn parse_pane_command(
         &self,
         pane_node: &KdlNode,
@@ -413,6 +467,7 @@ impl<'a> KdlLayoutParser<'a> {
         let edit = self.parse_path(pane_node, "edit")?;
         let cwd = self.parse_path(pane_node, "cwd")?;
         let args = self.parse_args(pane_node)?;
+        let env = self.parse_env(pane_node)?;
         let close_on_exit =
             kdl_get_bool_property_or_child_value_with_error!(pane_node, "close_on_exit");
         let start_suspended =
@@ -421,6 +476,7 @@ impl<'a> KdlLayoutParser<'a> {
             self.assert_no_bare_attributes_in_pane_node(
                 &command,
                 &args,
+                &env,
                 &close_on_exit,
                 &start_suspended,
                 pane_node,
@@ -434,8 +490,10 @@ impl<'a> KdlLayoutParser<'a> {
                 command,
                 args: args.unwrap_or_else(|| vec![]),
                 cwd,
+                env: env.unwrap_or_default(),
                 hold_on_close,
                 hold_on_start,
+                waiting_client: None,
             }))),
             (None, Some(edit), Some(cwd)) => {
                 Ok(Some(Run::EditFile(cwd.join(edit), None, Some(cwd))))
@@ -513,6 +571,7 @@ impl<'a> KdlLayoutParser<'a> {
             kdl_get_bool_property_or_child_value_with_error!(kdl_node, "exclude_from_sync");
         let split_size = self.parse_split_size(kdl_node)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let pane_initial_contents = self.parse_contents_file(kdl_node)?;
         let children_split_direction = self.parse_split_direction(kdl_node)?;
         let (external_children_index, children) = match kdl_children_nodes!(kdl_node) {
             Some(children) => {
@@ -553,6 +612,7 @@ impl<'a> KdlLayoutParser<'a> {
             children,
             children_are_stacked,
             is_expanded_in_stack,
+            pane_initial_contents,
             ..Default::default()
         })
     }
@@ -566,6 +626,7 @@ impl<'a> KdlLayoutParser<'a> {
         let x = self.parse_percent_or_fixed(kdl_node, "x", true)?;
         let y = self.parse_percent_or_fixed(kdl_node, "y", true)?;
         let run = self.parse_command_plugin_or_edit_block(kdl_node)?;
+        let pane_initial_contents = self.parse_contents_file(kdl_node)?;
         let focus = kdl_get_bool_property_or_child_value_with_error!(kdl_node, "focus");
         let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
             .map(|name| name.to_string());
@@ -578,6 +639,7 @@ impl<'a> KdlLayoutParser<'a> {
             y,
             run,
             focus,
+            pane_initial_contents,
             ..Default::default()
         })
     }
@@ -666,6 +728,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let is_expanded_in_stack =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "expanded");
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -684,6 +747,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -698,6 +762,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -772,6 +837,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -781,6 +847,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -790,6 +857,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -823,6 +891,7 @@ impl<'a> KdlLayoutParser<'a> {
                 let name = kdl_get_string_property_or_child_value_with_error!(kdl_node, "name")
                     .map(|name| name.to_string());
                 let args = self.parse_args(kdl_node)?;
+                let env = self.parse_env(kdl_node)?;
                 let close_on_exit =
                     kdl_get_bool_property_or_child_value_with_error!(kdl_node, "close_on_exit");
                 let start_suspended =
@@ -832,6 +901,7 @@ impl<'a> KdlLayoutParser<'a> {
                     &run,
                     &pane_template.run,
                     &args,
+                    &env,
                     &close_on_exit,
                     &start_suspended,
                     kdl_node,
@@ -841,6 +911,7 @@ impl<'a> KdlLayoutParser<'a> {
                     // we need to do this because panes consuming a pane_template
                     // can have bare args without a command
                     pane_template_run_command.add_args(args);
+                    pane_template_run_command.add_env(env);
                     pane_template_run_command.add_close_on_exit(close_on_exit);
                     pane_template_run_command.add_start_suspended(start_suspended);
                 };
@@ -1260,6 +1331,7 @@ impl<'a> KdlLayoutParser<'a> {
         pane_run: &Option<Run>,
         pane_template_run: &Option<Run>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1270,6 +1342,12 @@ impl<'a> KdlLayoutParser<'a> {
                 pane_node
             ));
         }
+        if let (None, None, true) = (pane_run, pane_template_run, env.is_some()) {
+            return Err(kdl_parsing_error!(
+                format!("env can only be specified if a command was specified either in the pane_template or in the pane"),
+                pane_node
+            ));
+        }
         if let (None, None, true) = (pane_run, pane_template_run, close_on_exit.is_some()) {
             return Err(kdl_parsing_error!(
                 format!("close_on_exit can only be specified if a command was specified either in the pane_template or in the pane"),
@@ -1288,6 +1366,7 @@ impl<'a> KdlLayoutParser<'a> {
         &self,
         command: &Option<PathBuf>,
         args: &Option<Vec<String>>,
+        env: &Option<BTreeMap<String, String>>,
         close_on_exit: &Option<bool>,
         start_suspended: &Option<bool>,
         pane_node: &KdlNode,
@@ -1314,6 +1393,13 @@ impl<'a> KdlLayoutParser<'a> {
                     pane_node.span().len(),
                 ));
             }
+            if env.is_some() {
+                return Err(ConfigError::new_layout_kdl_error(
+                    "env can only be set if a command was specified".into(),
+                    pane_node.span().offset(),
+                    pane_node.span().len(),
+                ));
+            }
         }
         Ok(())
     }
@@ -1587,6 +1673,19 @@ impl<'a> KdlLayoutParser<'a> {
             Some((tab_template, tab_template_floating_panes, kdl_node.clone()));
         Ok(())
     }
+    fn populate_new_tab_template(&mut self, kdl_node: &KdlNode) -> Result<(), ConfigError> {
+        // unlike the default_tab_template, this is only used for tabs opened after the layout was
+        // applied and not for the tabs in the layout itself
+        let (mut tab_template, tab_template_floating_panes) =
+            self.parse_tab_template_node(kdl_node)?;
+        if let Some(children_index) = tab_template.external_children_index.take() {
+            tab_template
+                .children
+                .insert(children_index, TiledPaneLayout::default())
+        }
+        self.new_tab_template = Some((tab_template, tab_template_floating_panes));
+        Ok(())
+    }
     fn parse_tab_template_node(
         &self,
         kdl_node: &KdlNode,
@@ -1788,6 +1887,8 @@ impl<'a> KdlLayoutParser<'a> {
                 self.populate_one_tab_template(child)?;
             } else if child_name == "default_tab_template" {
                 self.populate_default_tab_template(child)?;
+            } else if child_name == "new_tab_template" {
+                self.populate_new_tab_template(child)?;
             }
         }
         Ok(())
@@ -2031,13 +2132,18 @@ impl<'a> KdlLayoutParser<'a> {
         swap_tiled_layouts: Vec<SwapTiledLayout>,
         swap_floating_layouts: Vec<SwapFloatingLayout>,
     ) -> Result<Layout, ConfigError> {
-        let template = self
-            .default_template()?
-            .unwrap_or_else(|| TiledPaneLayout::default());
+        let template = match &self.new_tab_template {
+            Some(new_tab_template) => new_tab_template.clone(),
+            None => (
+                self.default_template()?
+                    .unwrap_or_else(|| TiledPaneLayout::default()),
+                vec![],
+            ),
+        };
 
         Ok(Layout {
             tabs,
-            template: Some((template, vec![])),
+            template: Some(template),
             focused_tab_index,
             swap_tiled_layouts,
             swap_floating_layouts,
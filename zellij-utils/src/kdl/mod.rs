@@ -1,12 +1,12 @@
 mod kdl_layout_parser;
 use crate::data::{
     Direction, InputMode, Key, Palette, PaletteColor, PaneInfo, PaneManifest, PermissionType,
-    Resize, SessionInfo, TabInfo,
+    Resize, SessionInfo, StyleDeclaration, Styling, TabInfo,
 };
 use crate::envs::EnvironmentVariables;
-use crate::input::config::{Config, ConfigError, KdlError};
+use crate::input::config::{is_yaml_file, Config, ConfigError, KdlError};
 use crate::input::keybinds::Keybinds;
-use crate::input::layout::{Layout, PluginUserConfiguration, RunPlugin, RunPluginLocation};
+use crate::input::layout::{Layout, RunPlugin, RunPluginLocation};
 use crate::input::options::{Clipboard, OnForceClose, Options};
 use crate::input::permission::{GrantedPermission, PermissionCache};
 use crate::input::plugins::{PluginConfig, PluginTag, PluginType, PluginsConfig};
@@ -42,25 +42,43 @@ macro_rules! parse_kdl_action_arguments {
                 "FocusPreviousPane" => Ok(Action::FocusPreviousPane),
                 "SwitchFocus" => Ok(Action::SwitchFocus),
                 "EditScrollback" => Ok(Action::EditScrollback),
+                "EnterCopyMode" => Ok(Action::EnterCopyMode),
+                "LinkHints" => Ok(Action::LinkHints),
                 "ScrollUp" => Ok(Action::ScrollUp),
                 "ScrollDown" => Ok(Action::ScrollDown),
                 "ScrollToBottom" => Ok(Action::ScrollToBottom),
                 "ScrollToTop" => Ok(Action::ScrollToTop),
+                "ScrollToPreviousPrompt" => Ok(Action::ScrollToPreviousPrompt),
+                "ScrollToNextPrompt" => Ok(Action::ScrollToNextPrompt),
+                "CopyLastCommandOutput" => Ok(Action::CopyLastCommandOutput),
                 "PageScrollUp" => Ok(Action::PageScrollUp),
                 "PageScrollDown" => Ok(Action::PageScrollDown),
                 "HalfPageScrollUp" => Ok(Action::HalfPageScrollUp),
                 "HalfPageScrollDown" => Ok(Action::HalfPageScrollDown),
                 "ToggleFocusFullscreen" => Ok(Action::ToggleFocusFullscreen),
+                "ToggleExpandPane" => Ok(Action::ToggleExpandPane),
+                "FocusNextPaneInStack" => Ok(Action::FocusNextPaneInStack),
+                "FocusPreviousPaneInStack" => Ok(Action::FocusPreviousPaneInStack),
                 "TogglePaneFrames" => Ok(Action::TogglePaneFrames),
                 "ToggleActiveSyncTab" => Ok(Action::ToggleActiveSyncTab),
+                "ToggleActivePaneSync" => Ok(Action::ToggleActivePaneSync),
+                "TogglePaneInGroup" => Ok(Action::TogglePaneInGroup),
+                "ClearPaneGroup" => Ok(Action::ClearPaneGroup),
+                "CloseGroupedPanes" => Ok(Action::CloseGroupedPanes),
+                "FloatGroupedPanes" => Ok(Action::FloatGroupedPanes),
+                "BreakGroupedPanesToNewTab" => Ok(Action::BreakGroupedPanesToNewTab),
+                "SyncGroupedPanes" => Ok(Action::SyncGroupedPanes),
                 "TogglePaneEmbedOrFloating" => Ok(Action::TogglePaneEmbedOrFloating),
                 "ToggleFloatingPanes" => Ok(Action::ToggleFloatingPanes),
+                "TogglePanePinned" => Ok(Action::TogglePanePinned),
                 "CloseFocus" => Ok(Action::CloseFocus),
                 "UndoRenamePane" => Ok(Action::UndoRenamePane),
                 "NoOp" => Ok(Action::NoOp),
                 "GoToNextTab" => Ok(Action::GoToNextTab),
                 "GoToPreviousTab" => Ok(Action::GoToPreviousTab),
                 "CloseTab" => Ok(Action::CloseTab),
+                "ReopenClosedPane" => Ok(Action::ReopenClosedPane),
+                "ReopenClosedTab" => Ok(Action::ReopenClosedTab),
                 "ToggleTab" => Ok(Action::ToggleTab),
                 "UndoRenameTab" => Ok(Action::UndoRenameTab),
                 "Detach" => Ok(Action::Detach),
@@ -71,6 +89,9 @@ macro_rules! parse_kdl_action_arguments {
                 "PreviousSwapLayout" => Ok(Action::PreviousSwapLayout),
                 "NextSwapLayout" => Ok(Action::NextSwapLayout),
                 "Clear" => Ok(Action::ClearScreen),
+                "RespawnPane" => Ok(Action::RespawnPane),
+                "SwitchWorkspace" => Ok(Action::SwitchWorkspace),
+                "ToggleScratchpad" => Ok(Action::ToggleScratchpad),
                 _ => Err(ConfigError::new_kdl_error(
                     format!("Unsupported action: {:?}", $action_name),
                     $action_node.span().offset(),
@@ -385,6 +406,16 @@ impl Action {
                 })? as u32;
                 Ok(Action::GoToTab(tab_index))
             },
+            "MovePaneToTab" => {
+                let tab_index = *bytes.get(0).ok_or_else(|| {
+                    ConfigError::new_kdl_error(
+                        format!("Missing tab index"),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })? as u32;
+                Ok(Action::MovePaneToTab(tab_index))
+            },
             _ => Err(ConfigError::new_kdl_error(
                 "Failed to parse action".into(),
                 action_node.span().offset(),
@@ -401,14 +432,10 @@ impl Action {
             "WriteChars" => Ok(Action::WriteChars(string)),
             "SwitchToMode" => match InputMode::from_str(string.as_str()) {
                 Ok(input_mode) => Ok(Action::SwitchToMode(input_mode)),
-                Err(_e) => {
-                    return Err(ConfigError::new_kdl_error(
-                        format!("Unknown InputMode '{}'", string),
-                        action_node.span().offset(),
-                        action_node.span().len(),
-                    ))
-                },
+                // checked against the custom modes once all the keybinds are parsed
+                Err(_e) => Ok(Action::SwitchToCustomMode(string)),
             },
+            "SwitchTheme" => Ok(Action::SwitchTheme(string)),
             "Resize" => {
                 let mut resize: Option<Resize> = None;
                 let mut direction: Option<Direction> = None;
@@ -443,6 +470,16 @@ impl Action {
                 })?;
                 Ok(Action::MoveFocus(direction))
             },
+            "MoveTab" => {
+                let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid direction: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::MoveTab(direction))
+            },
             "MoveFocusOrTab" => {
                 let direction = Direction::from_str(string.as_str()).map_err(|_| {
                     ConfigError::new_kdl_error(
@@ -453,6 +490,16 @@ impl Action {
                 })?;
                 Ok(Action::MoveFocusOrTab(direction))
             },
+            "MoveFocusOrDelegate" => {
+                let direction = Direction::from_str(string.as_str()).map_err(|_| {
+                    ConfigError::new_kdl_error(
+                        format!("Invalid direction: '{}'", string),
+                        action_node.span().offset(),
+                        action_node.span().len(),
+                    )
+                })?;
+                Ok(Action::MoveFocusOrDelegate(direction, vec![]))
+            },
             "MovePane" => {
                 if string.is_empty() {
                     return Ok(Action::MovePane(None));
@@ -468,7 +515,8 @@ impl Action {
                 }
             },
             "MovePaneBackwards" => Ok(Action::MovePaneBackwards),
-            "DumpScreen" => Ok(Action::DumpScreen(string, false)),
+            "DumpScreen" => Ok(Action::DumpScreen(string, false, false)),
+            "DumpScrollback" => Ok(Action::DumpScreen(string, true, false)),
             "NewPane" => {
                 if string.is_empty() {
                     return Ok(Action::NewPane(None, None));
@@ -630,6 +678,31 @@ impl TryFrom<(&str, &KdlDocument)> for PaletteColor {
     }
 }
 
+impl TryFrom<(&str, &KdlDocument)> for StyleDeclaration {
+    type Error = ConfigError;
+
+    fn try_from(
+        (element_name, theme_elements): (&str, &KdlDocument),
+    ) -> Result<StyleDeclaration, Self::Error> {
+        let element = theme_elements
+            .get(element_name)
+            .ok_or(ConfigError::new_kdl_error(
+                format!("Missing theme element: {}", element_name),
+                theme_elements.span().offset(),
+                theme_elements.span().len(),
+            ))?;
+        let element_colors = kdl_children_or_error!(element, "empty theme element");
+        Ok(StyleDeclaration {
+            base: PaletteColor::try_from(("base", element_colors))?,
+            background: PaletteColor::try_from(("background", element_colors))?,
+            emphasis_0: PaletteColor::try_from(("emphasis_0", element_colors))?,
+            emphasis_1: PaletteColor::try_from(("emphasis_1", element_colors))?,
+            emphasis_2: PaletteColor::try_from(("emphasis_2", element_colors))?,
+            emphasis_3: PaletteColor::try_from(("emphasis_3", element_colors))?,
+        })
+    }
+}
+
 impl TryFrom<(&KdlNode, &Options)> for Action {
     type Error = ConfigError;
     fn try_from((kdl_action, config_options): (&KdlNode, &Options)) -> Result<Self, Self::Error> {
@@ -648,6 +721,10 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "EditScrollback" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "EnterCopyMode" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "LinkHints" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "ScrollUp" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "ScrollDown" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "ScrollToBottom" => {
@@ -656,6 +733,15 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ScrollToTop" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ScrollToPreviousPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ScrollToNextPrompt" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "CopyLastCommandOutput" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "PageScrollUp" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -671,18 +757,51 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "ToggleFocusFullscreen" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ToggleExpandPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "FocusNextPaneInStack" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "FocusPreviousPaneInStack" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TogglePaneFrames" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
             "ToggleActiveSyncTab" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "ToggleActivePaneSync" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "TogglePaneInGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ClearPaneGroup" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "CloseGroupedPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "FloatGroupedPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "BreakGroupedPanesToNewTab" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "SyncGroupedPanes" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TogglePaneEmbedOrFloating" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
             "ToggleFloatingPanes" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
+            "TogglePanePinned" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "CloseFocus" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "UndoRenamePane" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
@@ -693,6 +812,12 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
             },
             "CloseTab" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "ReopenClosedPane" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ReopenClosedTab" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
             "ToggleTab" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "UndoRenameTab" => {
                 parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
@@ -705,6 +830,18 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
             "Clear" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Confirm" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
             "Deny" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "RespawnPane" => parse_kdl_action_arguments!(action_name, action_arguments, kdl_action),
+            "SwitchWorkspace" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "ToggleScratchpad" => {
+                parse_kdl_action_arguments!(action_name, action_arguments, kdl_action)
+            },
+            "SwitchTheme" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "Write" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
             "WriteChars" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
@@ -736,11 +873,21 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "MoveTab" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MoveFocusOrTab" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
                 kdl_action
             ),
+            "MoveFocusOrDelegate" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "MovePane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -756,6 +903,11 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 action_arguments,
                 kdl_action
             ),
+            "DumpScrollback" => parse_kdl_action_char_or_string_arguments!(
+                action_name,
+                action_arguments,
+                kdl_action
+            ),
             "NewPane" => parse_kdl_action_char_or_string_arguments!(
                 action_name,
                 action_arguments,
@@ -844,6 +996,9 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                 }
             },
             "GoToTab" => parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action),
+            "MovePaneToTab" => {
+                parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
+            },
             "TabNameInput" => {
                 parse_kdl_action_u8_arguments!(action_name, action_arguments, kdl_action)
             },
@@ -893,6 +1048,7 @@ impl TryFrom<(&KdlNode, &Options)> for Action {
                     direction,
                     hold_on_close,
                     hold_on_start,
+                    wait_for_exit: false,
                 };
                 if floating {
                     Ok(Action::NewFloatingPane(Some(run_command_action), name))
@@ -1348,16 +1504,23 @@ macro_rules! kdl_get_int_entry {
 
 impl Options {
     pub fn from_kdl(kdl_options: &KdlDocument) -> Result<Self, ConfigError> {
-        let on_force_close =
-            match kdl_property_first_arg_as_string_or_error!(kdl_options, "on_force_close") {
-                Some((string, entry)) => Some(OnForceClose::from_str(string).map_err(|_| {
+        let on_force_close_option = |option_name: &str| -> Result<_, ConfigError> {
+            match kdl_property_first_arg_as_string_or_error!(kdl_options, option_name) {
+                Some((string, entry)) => OnForceClose::from_str(string).map(Some).map_err(|_| {
                     kdl_parsing_error!(
-                        format!("Invalid value for on_force_close: '{}'", string),
+                        format!("Invalid value for {}: '{}'", option_name, string),
                         entry
                     )
-                })?),
-                None => None,
-            };
+                }),
+                None => Ok(None),
+            }
+        };
+        let on_force_close = on_force_close_option("on_force_close")?;
+        let on_sighup = on_force_close_option("on_sighup")?;
+        let on_sigterm = on_force_close_option("on_sigterm")?;
+        let on_force_close_command =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "on_force_close_command")
+                .map(|(on_force_close_command, _entry)| on_force_close_command.to_string());
         let simplified_ui =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "simplified_ui").map(|(v, _)| v);
         let default_shell =
@@ -1387,9 +1550,20 @@ impl Options {
             .map(|(string, _entry)| PathBuf::from(string));
         let mouse_mode =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "mouse_mode").map(|(v, _)| v);
+        let support_kitty_keyboard_protocol = kdl_property_first_arg_as_bool_or_error!(
+            kdl_options,
+            "support_kitty_keyboard_protocol"
+        )
+        .map(|(v, _)| v);
         let scroll_buffer_size =
             kdl_property_first_arg_as_i64_or_error!(kdl_options, "scroll_buffer_size")
                 .map(|(scroll_buffer_size, _entry)| scroll_buffer_size as usize);
+        let scrollback_spillover =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "scrollback_spillover")
+                .map(|(v, _)| v);
+        let scrollback_spillover_limit =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "scrollback_spillover_limit")
+                .map(|(scrollback_spillover_limit, _entry)| scrollback_spillover_limit as usize);
         let copy_command = kdl_property_first_arg_as_string_or_error!(kdl_options, "copy_command")
             .map(|(copy_command, _entry)| copy_command.to_string());
         let copy_clipboard =
@@ -1404,16 +1578,67 @@ impl Options {
             };
         let copy_on_select =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "copy_on_select").map(|(v, _)| v);
+        let word_characters =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "word_characters")
+                .map(|(word_characters, _entry)| word_characters.to_string());
+        let link_opener = kdl_property_first_arg_as_string_or_error!(kdl_options, "link_opener")
+            .map(|(link_opener, _entry)| link_opener.to_string());
         let scrollback_editor =
             kdl_property_first_arg_as_string_or_error!(kdl_options, "scrollback_editor")
                 .map(|(string, _entry)| PathBuf::from(string));
         let mirror_session =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "mirror_session").map(|(v, _)| v);
+        let mirror_tab_focus =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "mirror_tab_focus")
+                .map(|(v, _)| v);
         let session_name = kdl_property_first_arg_as_string_or_error!(kdl_options, "session_name")
             .map(|(session_name, _entry)| session_name.to_string());
+        let session_name_template =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "session_name_template")
+                .map(|(session_name_template, _entry)| session_name_template.to_string());
         let attach_to_session =
             kdl_property_first_arg_as_bool_or_error!(kdl_options, "attach_to_session")
                 .map(|(v, _)| v);
+        let session_group =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "session_group")
+                .map(|(session_group, _entry)| session_group.to_string());
+        let headless_columns =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "headless_columns")
+                .map(|(headless_columns, _entry)| headless_columns as usize);
+        let headless_rows = kdl_property_first_arg_as_i64_or_error!(kdl_options, "headless_rows")
+            .map(|(headless_rows, _entry)| headless_rows as usize);
+        let session_serialization =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "session_serialization")
+                .map(|(v, _)| v);
+        let serialize_pane_scrollback =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "serialize_pane_scrollback")
+                .map(|(v, _)| v);
+        let bell_command = kdl_property_first_arg_as_string_or_error!(kdl_options, "bell_command")
+            .map(|(bell_command, _entry)| bell_command.to_string());
+        let monitor_silence =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "monitor_silence")
+                .map(|(monitor_silence, _entry)| monitor_silence as u64);
+        let pane_close_confirmation =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "pane_close_confirmation")
+                .map(|(v, _)| v);
+        let on_session_create_command =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "on_session_create_command")
+                .map(|(command, _entry)| command.to_string());
+        let on_client_attach_command =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "on_client_attach_command")
+                .map(|(command, _entry)| command.to_string());
+        let on_client_detach_command =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "on_client_detach_command")
+                .map(|(command, _entry)| command.to_string());
+        let set_terminal_title =
+            kdl_property_first_arg_as_bool_or_error!(kdl_options, "set_terminal_title")
+                .map(|(v, _)| v);
+        let terminal_title_template =
+            kdl_property_first_arg_as_string_or_error!(kdl_options, "terminal_title_template")
+                .map(|(template, _entry)| template.to_string());
+        let key_sequence_timeout =
+            kdl_property_first_arg_as_i64_or_error!(kdl_options, "key_sequence_timeout")
+                .map(|(key_sequence_timeout, _entry)| key_sequence_timeout as u64);
         Ok(Options {
             simplified_ui,
             theme,
@@ -1424,17 +1649,41 @@ impl Options {
             layout_dir,
             theme_dir,
             mouse_mode,
+            support_kitty_keyboard_protocol,
             pane_frames,
             mirror_session,
+            mirror_tab_focus,
             on_force_close,
+            on_sighup,
+            on_sigterm,
+            on_force_close_command,
             scroll_buffer_size,
+            scrollback_spillover,
+            scrollback_spillover_limit,
             copy_command,
             copy_clipboard,
             copy_on_select,
+            word_characters,
+            link_opener,
             scrollback_editor,
             session_name,
+            session_name_template,
             attach_to_session,
+            session_group,
+            headless_columns,
+            headless_rows,
             auto_layout,
+            session_serialization,
+            serialize_pane_scrollback,
+            bell_command,
+            monitor_silence,
+            pane_close_confirmation,
+            on_session_create_command,
+            on_client_attach_command,
+            on_client_detach_command,
+            set_terminal_title,
+            terminal_title_template,
+            key_sequence_timeout,
         })
     }
 }
@@ -1564,37 +1813,39 @@ impl Keybinds {
         } else {
             base_keybinds
         };
+        // custom modes are declared with `mode "<name>" { .. }`, the shared blocks apply to them
+        // as well
+        let mut custom_modes: Vec<String> = keybinds_from_config.1.keys().cloned().collect();
         for block in kdl_children_nodes_or_error!(kdl_keybinds, "keybindings with no children") {
-            if kdl_name!(block) == "shared_except" || kdl_name!(block) == "shared" {
-                let mut modes_to_exclude = vec![];
-                for mode_name in kdl_string_arguments!(block) {
-                    modes_to_exclude.push(InputMode::from_str(mode_name).map_err(|_| {
-                        ConfigError::new_kdl_error(
-                            format!("Invalid mode: '{}'", mode_name),
-                            block.name().span().offset(),
-                            block.name().span().len(),
-                        )
-                    })?);
+            if kdl_name!(block) == "mode" {
+                let custom_mode = Keybinds::custom_mode_name(block)?;
+                if !custom_modes.contains(&custom_mode) {
+                    custom_modes.push(custom_mode);
                 }
+            }
+        }
+        for block in kdl_children_nodes_or_error!(kdl_keybinds, "keybindings with no children") {
+            let is_shared_among = kdl_name!(block) == "shared_among";
+            if kdl_name!(block) == "shared_except"
+                || kdl_name!(block) == "shared"
+                || is_shared_among
+            {
+                let (listed_modes, listed_custom_modes) =
+                    Keybinds::modes_from_kdl_arguments(block, &custom_modes)?;
                 for mode in InputMode::iter() {
-                    if modes_to_exclude.contains(&mode) {
+                    if listed_modes.contains(&mode) != is_shared_among {
                         continue;
                     }
                     let mut input_mode_keybinds = keybinds_from_config.get_input_mode_mut(&mode);
                     Keybinds::bind_keys_in_block(block, &mut input_mode_keybinds, config_options)?;
                 }
-            }
-            if kdl_name!(block) == "shared_among" {
-                let mut modes_to_include = vec![];
-                for mode_name in kdl_string_arguments!(block) {
-                    modes_to_include.push(InputMode::from_str(mode_name)?);
-                }
-                for mode in InputMode::iter() {
-                    if !modes_to_include.contains(&mode) {
+                for custom_mode in &custom_modes {
+                    if listed_custom_modes.contains(custom_mode) != is_shared_among {
                         continue;
                     }
-                    let mut input_mode_keybinds = keybinds_from_config.get_input_mode_mut(&mode);
-                    Keybinds::bind_keys_in_block(block, &mut input_mode_keybinds, config_options)?;
+                    let mut custom_mode_keybinds =
+                        keybinds_from_config.get_custom_mode_mut(custom_mode);
+                    Keybinds::bind_keys_in_block(block, &mut custom_mode_keybinds, config_options)?;
                 }
             }
         }
@@ -1606,24 +1857,160 @@ impl Keybinds {
             {
                 continue;
             }
-            let mut input_mode_keybinds =
-                Keybinds::input_mode_keybindings(mode, &mut keybinds_from_config)?;
+            let mut input_mode_keybinds = if kdl_name!(mode) == "mode" {
+                let custom_mode = Keybinds::custom_mode_name(mode)?;
+                keybinds_from_config.get_custom_mode_mut(&custom_mode)
+            } else {
+                Keybinds::input_mode_keybindings(mode, &mut keybinds_from_config)?
+            };
             Keybinds::bind_keys_in_block(mode, &mut input_mode_keybinds, config_options)?;
         }
         if let Some(global_unbind) = kdl_keybinds.children().and_then(|c| c.get("unbind")) {
             Keybinds::unbind_keys_in_all_modes(global_unbind, &mut keybinds_from_config)?;
         };
+        Keybinds::check_custom_modes_exist(kdl_keybinds, &keybinds_from_config)?;
         Ok(keybinds_from_config)
     }
+    fn custom_mode_name(block: &KdlNode) -> Result<String, ConfigError> {
+        kdl_first_entry_as_string!(block)
+            .map(|custom_mode| custom_mode.to_owned())
+            .ok_or_else(|| {
+                ConfigError::new_kdl_error(
+                    "A custom mode needs a name, eg. mode \"git\" { .. }".into(),
+                    block.span().offset(),
+                    block.span().len(),
+                )
+            })
+    }
+    fn modes_from_kdl_arguments(
+        block: &KdlNode,
+        custom_modes: &[String],
+    ) -> Result<(Vec<InputMode>, Vec<String>), ConfigError> {
+        let mut modes = vec![];
+        let mut listed_custom_modes = vec![];
+        for mode_name in kdl_string_arguments!(block) {
+            match InputMode::from_str(mode_name) {
+                Ok(mode) => modes.push(mode),
+                Err(_) if custom_modes.iter().any(|c| c == mode_name) => {
+                    listed_custom_modes.push(mode_name.to_owned())
+                },
+                Err(_) => {
+                    return Err(ConfigError::new_kdl_error(
+                        format!("Invalid mode: '{}'", mode_name),
+                        block.name().span().offset(),
+                        block.name().span().len(),
+                    ))
+                },
+            }
+        }
+        Ok((modes, listed_custom_modes))
+    }
+    fn check_custom_modes_exist(
+        kdl_keybinds: &KdlNode,
+        keybinds: &Keybinds,
+    ) -> Result<(), ConfigError> {
+        fn unknown_custom_mode<'a>(
+            actions: &'a [Action],
+            keybinds: &Keybinds,
+        ) -> Option<&'a String> {
+            actions.iter().find_map(|action| match action {
+                Action::SwitchToCustomMode(custom_mode)
+                    if !keybinds.has_custom_mode(custom_mode) =>
+                {
+                    Some(custom_mode)
+                },
+                Action::KeySequence(sequences) => sequences
+                    .iter()
+                    .find_map(|(_keys, actions)| unknown_custom_mode(actions, keybinds)),
+                _ => None,
+            })
+        }
+        let all_actions = keybinds
+            .0
+            .values()
+            .chain(keybinds.1.values())
+            .flat_map(|mode_keybinds| mode_keybinds.values());
+        for actions in all_actions {
+            if let Some(custom_mode) = unknown_custom_mode(actions, keybinds) {
+                return Err(ConfigError::new_kdl_error(
+                    format!("Unknown InputMode '{}'", custom_mode),
+                    kdl_keybinds.span().offset(),
+                    kdl_keybinds.span().len(),
+                ));
+            }
+        }
+        Ok(())
+    }
+    /// Parses a stringified `keybinds { .. }` block (eg. one sent by a plugin) on top of the given
+    /// keybindings
+    pub fn from_kdl_string(
+        kdl_keybinds: &str,
+        base_keybinds: Keybinds,
+        config_options: &Options,
+    ) -> Result<Self, ConfigError> {
+        let kdl_keybinds: KdlDocument = kdl_keybinds.parse()?;
+        let kdl_keybinds = Keybinds::keybinds_node(&kdl_keybinds)?;
+        Keybinds::from_kdl(kdl_keybinds, base_keybinds, config_options)
+    }
+    /// Adds the bindings in a stringified `keybinds { .. }` block to those of a stringified
+    /// configuration file, returning the updated configuration file
+    ///
+    /// The new bindings are placed after the existing ones so that they take precedence over them
+    /// when the file is next loaded. The rest of the file is left as is.
+    pub fn add_to_kdl_config(kdl_keybinds: &str, kdl_config: &str) -> Result<String, ConfigError> {
+        let kdl_keybinds: KdlDocument = kdl_keybinds.parse()?;
+        let new_keybinds = Keybinds::keybinds_node(&kdl_keybinds)?;
+        let clear_defaults = kdl_arg_is_truthy!(new_keybinds, "clear-defaults");
+        let mut kdl_config: KdlDocument = kdl_config.parse()?;
+        match kdl_config.get_mut("keybinds") {
+            Some(existing_keybinds) if !clear_defaults => {
+                let new_bindings = new_keybinds
+                    .children()
+                    .map(|c| c.nodes().to_vec())
+                    .unwrap_or_default();
+                existing_keybinds
+                    .ensure_children()
+                    .nodes_mut()
+                    .extend(new_bindings);
+            },
+            Some(existing_keybinds) => {
+                *existing_keybinds = new_keybinds.clone();
+            },
+            None => {
+                kdl_config.nodes_mut().push(new_keybinds.clone());
+            },
+        }
+        Ok(kdl_config.to_string())
+    }
+    fn keybinds_node(kdl_document: &KdlDocument) -> Result<&KdlNode, ConfigError> {
+        kdl_document.get("keybinds").ok_or_else(|| {
+            ConfigError::new_kdl_error(
+                "Expected a keybinds block".into(),
+                kdl_document.span().offset(),
+                kdl_document.span().len(),
+            )
+        })
+    }
     fn bind_actions_for_each_key(
         key_block: &KdlNode,
         input_mode_keybinds: &mut HashMap<Key, Vec<Action>>,
         config_options: &Options,
     ) -> Result<(), ConfigError> {
-        let keys: Vec<Key> = keys_from_kdl!(key_block);
         let actions: Vec<Action> = actions_from_kdl!(key_block, config_options);
-        for key in keys {
-            input_mode_keybinds.insert(key, actions.clone());
+        for keys_str in kdl_string_arguments!(key_block) {
+            let mut keys = Key::sequence_from_str(keys_str).map_err(|_| {
+                ConfigError::new_kdl_error(
+                    format!("Invalid key: '{}'", keys_str),
+                    key_block.span().offset(),
+                    key_block.span().len(),
+                )
+            })?;
+            let first_key = keys.remove(0);
+            if keys.is_empty() {
+                input_mode_keybinds.insert(first_key, actions.clone());
+            } else {
+                Keybinds::bind_key_sequence(input_mode_keybinds, first_key, keys, actions.clone());
+            }
         }
         Ok(())
     }
@@ -1642,7 +2029,8 @@ impl Keybinds {
         keybinds_from_config: &mut Keybinds,
     ) -> Result<(), ConfigError> {
         let keys: Vec<Key> = keys_from_kdl!(global_unbind);
-        for mode in keybinds_from_config.0.values_mut() {
+        let custom_modes = keybinds_from_config.1.values_mut();
+        for mode in keybinds_from_config.0.values_mut().chain(custom_modes) {
             for key in &keys {
                 mode.remove(&key);
             }
@@ -1711,24 +2099,34 @@ impl PluginsConfig {
         {
             let plugin_name = kdl_name!(plugin_config);
             let plugin_tag = PluginTag::new(plugin_name);
-            let path = kdl_children_property_first_arg_as_string!(plugin_config, "path")
-                .map(|path| PathBuf::from(path))
-                .ok_or(ConfigError::new_kdl_error(
-                    "Plugin path not found or invalid".into(),
-                    plugin_config.span().offset(),
-                    plugin_config.span().len(),
-                ))?;
+            let path = match kdl_children_property_first_arg_as_string!(plugin_config, "location") {
+                // an alias, eg. `filepicker { location "file:~/my/strider.wasm"; }`
+                Some(location) => match RunPluginLocation::parse(location, None)? {
+                    RunPluginLocation::File(path) => path,
+                    RunPluginLocation::Zellij(tag) => PathBuf::from(tag.to_string()),
+                    RunPluginLocation::Remote(url) => PathBuf::from(url),
+                },
+                None => kdl_children_property_first_arg_as_string!(plugin_config, "path")
+                    .map(|path| PathBuf::from(path))
+                    .ok_or(ConfigError::new_kdl_error(
+                        "Plugin path or location not found or invalid".into(),
+                        plugin_config.span().offset(),
+                        plugin_config.span().len(),
+                    ))?,
+            };
             let allow_exec_host_cmd =
                 kdl_children_property_first_arg_as_bool!(plugin_config, "_allow_exec_host_cmd")
                     .unwrap_or(false);
+            // everything else in the block is default configuration for the plugin, overridden
+            // by the configuration of wherever the plugin is loaded
+            let userspace_configuration =
+                KdlLayoutParser::parse_plugin_user_configuration(&plugin_config)?;
             let plugin_config = PluginConfig {
                 path,
                 run: PluginType::Pane(None),
                 location: RunPluginLocation::Zellij(plugin_tag.clone()),
                 _allow_exec_host_cmd: allow_exec_host_cmd,
-                userspace_configuration: PluginUserConfiguration::new(BTreeMap::new()), // TODO: consider removing the whole
-                                                                                        // "plugins" section in the config
-                                                                                        // because it's not used???
+                userspace_configuration,
             };
             plugins.insert(plugin_tag, plugin_config);
         }
@@ -1744,9 +2142,12 @@ impl UiConfig {
                     .unwrap_or(false);
             let hide_session_name =
                 kdl_get_child_entry_bool_value!(pane_frames, "hide_session_name").unwrap_or(false);
+            let title =
+                kdl_get_child_entry_string_value!(pane_frames, "title").map(|t| t.to_owned());
             let frame_config = FrameConfig {
                 rounded_corners,
                 hide_session_name,
+                title,
             };
             ui_config.pane_frames = frame_config;
         }
@@ -1760,8 +2161,29 @@ impl Themes {
         for theme_config in kdl_children_nodes_or_error!(themes_from_kdl, "no themes found") {
             let theme_name = kdl_name!(theme_config);
             let theme_colors = kdl_children_or_error!(theme_config, "empty theme");
-            let theme = Theme {
-                palette: Palette {
+            // themes either define each UI element (eg. "frame_selected { base 0 255 0; ... }")
+            // or a flat palette (eg. "green 0 255 0")
+            let theme = if theme_colors.get("text_unselected").is_some() {
+                Theme::from(Styling {
+                    text_unselected: StyleDeclaration::try_from(("text_unselected", theme_colors))?,
+                    text_selected: StyleDeclaration::try_from(("text_selected", theme_colors))?,
+                    ribbon_unselected: StyleDeclaration::try_from((
+                        "ribbon_unselected",
+                        theme_colors,
+                    ))?,
+                    ribbon_selected: StyleDeclaration::try_from(("ribbon_selected", theme_colors))?,
+                    frame_unselected: match theme_colors.get("frame_unselected") {
+                        Some(_) => Some(StyleDeclaration::try_from((
+                            "frame_unselected",
+                            theme_colors,
+                        ))?),
+                        None => None,
+                    },
+                    frame_selected: StyleDeclaration::try_from(("frame_selected", theme_colors))?,
+                    frame_highlight: StyleDeclaration::try_from(("frame_highlight", theme_colors))?,
+                })
+            } else {
+                Theme::from(Palette {
                     fg: PaletteColor::try_from(("fg", theme_colors))?,
                     bg: PaletteColor::try_from(("bg", theme_colors))?,
                     red: PaletteColor::try_from(("red", theme_colors))?,
@@ -1774,7 +2196,7 @@ impl Themes {
                     black: PaletteColor::try_from(("black", theme_colors))?,
                     white: PaletteColor::try_from(("white", theme_colors))?,
                     ..Default::default()
-                },
+                })
             };
             themes.insert(theme_name.into(), theme);
         }
@@ -1810,6 +2232,14 @@ impl Themes {
             if let Some(extension) = path.extension() {
                 if extension == "kdl" {
                     themes = themes.merge(Themes::from_path(path)?);
+                } else if is_yaml_file(&path) && !path.with_extension("kdl").exists() {
+                    log::warn!(
+                        "{}",
+                        ConfigError::DeprecatedYaml {
+                            path,
+                            subcommand: "convert-theme",
+                        }
+                    );
                 }
             }
         }
@@ -1899,6 +2329,11 @@ impl SessionInfo {
             .and_then(|p| p.children())
             .map(|p| PaneManifest::decode_from_kdl(p))
             .ok_or("Failed to parse panes")?;
+        let group = kdl_document
+            .get("group")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_string())
+            .map(|s| s.to_owned());
         let is_current_session = name == current_session_name;
         Ok(SessionInfo {
             name,
@@ -1906,6 +2341,7 @@ impl SessionInfo {
             panes,
             connected_clients,
             is_current_session,
+            group,
         })
     }
     pub fn to_string(&self) -> String {
@@ -1934,6 +2370,11 @@ impl SessionInfo {
         kdl_document.nodes_mut().push(tabs);
         kdl_document.nodes_mut().push(panes);
         kdl_document.nodes_mut().push(connected_clients);
+        if let Some(group) = &self.group {
+            let mut group_node = KdlNode::new("group");
+            group_node.push(group.clone());
+            kdl_document.nodes_mut().push(group_node);
+        }
         kdl_document.fmt();
         kdl_document.to_string()
     }
@@ -2000,6 +2441,19 @@ impl TabInfo {
         }
         let active_swap_layout_name = optional_string_node!("active_swap_layout_name");
         let is_swap_layout_dirty = bool_node!("is_swap_layout_dirty");
+        // these are optional so that we can read the session info of older zellij versions
+        let pane_count = kdl_document
+            .get("pane_count")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_i64())
+            .map(|e| e as usize)
+            .unwrap_or(0);
+        let has_unseen_output = kdl_document
+            .get("has_unseen_output")
+            .and_then(|n| n.entries().iter().next())
+            .and_then(|e| e.value().as_bool())
+            .unwrap_or(false);
+        let focused_pane_title = optional_string_node!("focused_pane_title");
         Ok(TabInfo {
             position,
             name,
@@ -2011,6 +2465,9 @@ impl TabInfo {
             other_focused_clients,
             active_swap_layout_name,
             is_swap_layout_dirty,
+            pane_count,
+            has_unseen_output,
+            focused_pane_title,
         })
     }
     pub fn encode_to_kdl(&self) -> KdlDocument {
@@ -2062,6 +2519,20 @@ impl TabInfo {
         is_swap_layout_dirty.push(self.is_swap_layout_dirty);
         kdl_doucment.nodes_mut().push(is_swap_layout_dirty);
 
+        let mut pane_count = KdlNode::new("pane_count");
+        pane_count.push(self.pane_count as i64);
+        kdl_doucment.nodes_mut().push(pane_count);
+
+        let mut has_unseen_output = KdlNode::new("has_unseen_output");
+        has_unseen_output.push(self.has_unseen_output);
+        kdl_doucment.nodes_mut().push(has_unseen_output);
+
+        if let Some(focused_pane_title) = self.focused_pane_title.as_ref() {
+            let mut focused_pane_title_node = KdlNode::new("focused_pane_title");
+            focused_pane_title_node.push(focused_pane_title.to_string());
+            kdl_doucment.nodes_mut().push(focused_pane_title_node);
+        }
+
         kdl_doucment
     }
 }
@@ -2189,6 +2660,29 @@ impl PaneInfo {
         let terminal_command = optional_string_node!("terminal_command");
         let plugin_url = optional_string_node!("plugin_url");
         let is_selectable = bool_node!("is_selectable");
+        let child_pids = kdl_document
+            .get("child_pids")
+            .map(|n| {
+                n.entries()
+                    .iter()
+                    .filter_map(|e| e.value().as_i64())
+                    .map(|pid| pid as u32)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let foreground_process = optional_string_node!("foreground_process");
+        let cwd = optional_string_node!("cwd").map(PathBuf::from);
+        // monitoring flags are only serialized when set
+        let optional_bool_node = |name: &str| {
+            kdl_document
+                .get(name)
+                .and_then(|n| n.entries().iter().next())
+                .and_then(|e| e.value().as_bool())
+                .unwrap_or(false)
+        };
+        let has_bell = optional_bool_node("has_bell");
+        let has_activity = optional_bool_node("has_activity");
+        let is_silent = optional_bool_node("is_silent");
 
         let pane_info = PaneInfo {
             id,
@@ -2213,6 +2707,12 @@ impl PaneInfo {
             terminal_command,
             plugin_url,
             is_selectable,
+            child_pids,
+            foreground_process,
+            cwd,
+            has_bell,
+            has_activity,
+            is_silent,
         };
         Ok((tab_position, pane_info))
     }
@@ -2273,6 +2773,28 @@ impl PaneInfo {
             string_node!("plugin_url", plugin_url.to_string());
         }
         bool_node!("is_selectable", self.is_selectable);
+        if !self.child_pids.is_empty() {
+            let mut child_pids = KdlNode::new("child_pids");
+            for pid in &self.child_pids {
+                child_pids.push(*pid as i64);
+            }
+            kdl_doucment.nodes_mut().push(child_pids);
+        }
+        if let Some(foreground_process) = &self.foreground_process {
+            string_node!("foreground_process", foreground_process.to_string());
+        }
+        if let Some(cwd) = &self.cwd {
+            string_node!("cwd", cwd.display().to_string());
+        }
+        if self.has_bell {
+            bool_node!("has_bell", self.has_bell);
+        }
+        if self.has_activity {
+            bool_node!("has_activity", self.has_activity);
+        }
+        if self.is_silent {
+            bool_node!("is_silent", self.is_silent);
+        }
         kdl_doucment
     }
 }
@@ -2360,6 +2882,12 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            child_pids: vec![1234, 1240],
+            foreground_process: Some("vim".to_owned()),
+            cwd: Some(PathBuf::from("/tmp")),
+            has_bell: false,
+            has_activity: false,
+            is_silent: false,
         },
         PaneInfo {
             id: 1,
@@ -2384,6 +2912,12 @@ fn serialize_and_deserialize_session_info_with_data() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            child_pids: vec![],
+            foreground_process: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            is_silent: false,
         },
     ];
     let mut panes = HashMap::new();
@@ -2402,6 +2936,9 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: Some("BASE".to_owned()),
                 is_swap_layout_dirty: true,
+                pane_count: 3,
+                has_unseen_output: false,
+                focused_pane_title: Some("vim".to_owned()),
             },
             TabInfo {
                 position: 1,
@@ -2414,11 +2951,15 @@ fn serialize_and_deserialize_session_info_with_data() {
                 other_focused_clients: vec![2, 3],
                 active_swap_layout_name: None,
                 is_swap_layout_dirty: false,
+                pane_count: 1,
+                has_unseen_output: true,
+                focused_pane_title: None,
             },
         ],
         panes: PaneManifest { panes },
         connected_clients: 2,
         is_current_session: false,
+        group: Some("work".to_owned()),
     };
     let serialized = session_info.to_string();
     let deserealized = SessionInfo::from_string(&serialized, "not this session").unwrap();
@@ -1,4 +1,5 @@
 pub mod cli;
+pub mod config_check;
 pub mod consts;
 pub mod data;
 pub mod envs;
@@ -8,13 +9,17 @@ pub mod kdl;
 pub mod pane_size;
 pub mod plugin_api;
 pub mod position;
+pub mod session_serialization;
 pub mod setup;
 pub mod shared;
+pub mod tmux_converter;
 
 // The following modules can't be used when targeting wasm
 #[cfg(not(target_family = "wasm"))]
 pub mod channels; // Requires async_std
 #[cfg(not(target_family = "wasm"))]
+pub mod downloader; // Requires surf
+#[cfg(not(target_family = "wasm"))]
 pub mod ipc; // Requires interprocess
 #[cfg(not(target_family = "wasm"))]
 pub mod logging; // Requires log4rs
@@ -22,7 +27,7 @@ pub mod logging; // Requires log4rs
 #[cfg(not(target_family = "wasm"))]
 pub use ::{
     anyhow, async_channel, async_std, clap, interprocess, lazy_static, libc, miette, nix,
-    notify_debouncer_full, regex, serde, signal_hook, tempfile, termwiz, vte,
+    notify_debouncer_full, regex, serde, signal_hook, surf, tempfile, termwiz, vte,
 };
 
 pub use ::prost;
@@ -9,6 +9,7 @@ use std::{
 
 use log::LevelFilter;
 
+use log4rs::append::console::{ConsoleAppender, Target};
 use log4rs::append::rolling_file::{
     policy::compound::{
         roll::fixed_window::FixedWindowRoller, trigger::size::SizeTrigger, CompoundPolicy,
@@ -24,6 +25,16 @@ use crate::shared::set_permissions;
 const LOG_MAX_BYTES: u64 = 1024 * 1024 * 16; // 16 MiB per log
 
 pub fn configure_logger() {
+    init_logger(false);
+}
+
+/// Also logs to stderr, so that the logs of a daemon end up with its service manager (eg. in the
+/// systemd journal)
+pub fn configure_daemon_logger() {
+    init_logger(true);
+}
+
+fn init_logger(log_to_stderr: bool) {
     atomic_create_dir(&*ZELLIJ_TMP_DIR).unwrap();
     atomic_create_dir(&*ZELLIJ_TMP_LOG_DIR).unwrap();
     atomic_create_file(&*ZELLIJ_TMP_LOG_FILE).unwrap();
@@ -70,9 +81,21 @@ pub fn configure_logger() {
     // Set the default logging level to "info" and log it to zellij.log file
     // Decrease verbosity for `wasmer_compiler_cranelift` module because it has a lot of useless info logs
     // For `zellij_server::logging_pipe`, we use custom format as we use logging macros to forward stderr output from plugins
+    let log_stderr = ConsoleAppender::builder()
+        .encoder(Box::new(PatternEncoder::new(
+            "{level:<6} |{module:<25.25}| [{thread:<10.15}]: {message} {n}",
+        )))
+        .target(Target::Stderr)
+        .build();
+
+    let mut root = Root::builder().appender("logFile");
+    if log_to_stderr {
+        root = root.appender("logStderr");
+    }
     let config = Config::builder()
         .appender(Appender::builder().build("logFile", Box::new(log_file)))
         .appender(Appender::builder().build("logPlugin", Box::new(log_plugin)))
+        .appender(Appender::builder().build("logStderr", Box::new(log_stderr)))
         .logger(
             Logger::builder()
                 .appender("logFile")
@@ -84,7 +107,7 @@ pub fn configure_logger() {
                 .additive(false)
                 .build("zellij_server::logging_pipe", LevelFilter::Trace),
         )
-        .build(Root::builder().appender("logFile").build(LevelFilter::Info))
+        .build(root.build(LevelFilter::Info))
         .unwrap();
 
     let _ = log4rs::init_config(config).unwrap();
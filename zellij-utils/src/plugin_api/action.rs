@@ -133,7 +133,12 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(OptionalPayload::DumpScreenPayload(payload)) => {
                     let file_path = payload.file_path;
                     let include_scrollback = payload.include_scrollback;
-                    Ok(Action::DumpScreen(file_path, include_scrollback))
+                    let include_ansi_styling = payload.include_ansi_styling;
+                    Ok(Action::DumpScreen(
+                        file_path,
+                        include_scrollback,
+                        include_ansi_styling,
+                    ))
                 },
                 _ => Err("Wrong payload for Action::DumpScreen"),
             },
@@ -177,6 +182,24 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("ScrollToTop should not have a payload"),
                 None => Ok(Action::ScrollToTop),
             },
+            Some(ProtobufActionName::ScrollToPreviousPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToPreviousPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToPreviousPrompt),
+                }
+            },
+            Some(ProtobufActionName::ScrollToNextPrompt) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ScrollToNextPrompt should not have a payload"),
+                    None => Ok(Action::ScrollToNextPrompt),
+                }
+            },
+            Some(ProtobufActionName::CopyLastCommandOutput) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("CopyLastCommandOutput should not have a payload"),
+                    None => Ok(Action::CopyLastCommandOutput),
+                }
+            },
             Some(ProtobufActionName::PageScrollUp) => match protobuf_action.optional_payload {
                 Some(_) => Err("PageScrollUp should not have a payload"),
                 None => Ok(Action::PageScrollUp),
@@ -201,6 +224,22 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::ToggleFocusFullscreen),
                 }
             },
+            Some(ProtobufActionName::ToggleExpandPane) => match protobuf_action.optional_payload {
+                Some(_) => Err("ToggleExpandPane should not have a payload"),
+                None => Ok(Action::ToggleExpandPane),
+            },
+            Some(ProtobufActionName::FocusNextPaneInStack) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("FocusNextPaneInStack should not have a payload"),
+                    None => Ok(Action::FocusNextPaneInStack),
+                }
+            },
+            Some(ProtobufActionName::FocusPreviousPaneInStack) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("FocusPreviousPaneInStack should not have a payload"),
+                    None => Ok(Action::FocusPreviousPaneInStack),
+                }
+            },
             Some(ProtobufActionName::TogglePaneFrames) => match protobuf_action.optional_payload {
                 Some(_) => Err("TogglePaneFrames should not have a payload"),
                 None => Ok(Action::TogglePaneFrames),
@@ -211,6 +250,38 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::ToggleActiveSyncTab),
                 }
             },
+            Some(ProtobufActionName::ToggleActivePaneSync) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("ToggleActivePaneSync should not have a payload"),
+                    None => Ok(Action::ToggleActivePaneSync),
+                }
+            },
+            Some(ProtobufActionName::TogglePaneInGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("TogglePaneInGroup should not have a payload"),
+                None => Ok(Action::TogglePaneInGroup),
+            },
+            Some(ProtobufActionName::ClearPaneGroup) => match protobuf_action.optional_payload {
+                Some(_) => Err("ClearPaneGroup should not have a payload"),
+                None => Ok(Action::ClearPaneGroup),
+            },
+            Some(ProtobufActionName::CloseGroupedPanes) => match protobuf_action.optional_payload {
+                Some(_) => Err("CloseGroupedPanes should not have a payload"),
+                None => Ok(Action::CloseGroupedPanes),
+            },
+            Some(ProtobufActionName::FloatGroupedPanes) => match protobuf_action.optional_payload {
+                Some(_) => Err("FloatGroupedPanes should not have a payload"),
+                None => Ok(Action::FloatGroupedPanes),
+            },
+            Some(ProtobufActionName::BreakGroupedPanesToNewTab) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("BreakGroupedPanesToNewTab should not have a payload"),
+                    None => Ok(Action::BreakGroupedPanesToNewTab),
+                }
+            },
+            Some(ProtobufActionName::SyncGroupedPanes) => match protobuf_action.optional_payload {
+                Some(_) => Err("SyncGroupedPanes should not have a payload"),
+                None => Ok(Action::SyncGroupedPanes),
+            },
             Some(ProtobufActionName::NewPane) => match protobuf_action.optional_payload {
                 Some(OptionalPayload::NewPanePayload(payload)) => {
                     let direction: Option<Direction> = payload
@@ -280,6 +351,10 @@ impl TryFrom<ProtobufAction> for Action {
                     None => Ok(Action::TogglePaneEmbedOrFloating),
                 }
             },
+            Some(ProtobufActionName::TogglePanePinned) => match protobuf_action.optional_payload {
+                Some(_) => Err("TogglePanePinned should not have a payload"),
+                None => Ok(Action::TogglePanePinned),
+            },
             Some(ProtobufActionName::ToggleFloatingPanes) => {
                 match protobuf_action.optional_payload {
                     Some(_) => Err("ToggleFloatingPanes should not have a payload"),
@@ -325,6 +400,14 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("CloseTab should not have a payload"),
                 None => Ok(Action::CloseTab),
             },
+            Some(ProtobufActionName::ReopenClosedPane) => match protobuf_action.optional_payload {
+                Some(_) => Err("ReopenClosedPane should not have a payload"),
+                None => Ok(Action::ReopenClosedPane),
+            },
+            Some(ProtobufActionName::ReopenClosedTab) => match protobuf_action.optional_payload {
+                Some(_) => Err("ReopenClosedTab should not have a payload"),
+                None => Ok(Action::ReopenClosedTab),
+            },
             Some(ProtobufActionName::GoToTab) => match protobuf_action.optional_payload {
                 Some(OptionalPayload::GoToTabPayload(index)) => Ok(Action::GoToTab(index)),
                 _ => Err("Wrong payload for Action::GoToTab"),
@@ -622,6 +705,131 @@ impl TryFrom<ProtobufAction> for Action {
                 Some(_) => Err("BreakPaneLeft should not have a payload"),
                 None => Ok(Action::BreakPaneLeft),
             },
+            Some(ProtobufActionName::MovePaneToTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MovePaneToTabPayload(index)) => {
+                    Ok(Action::MovePaneToTab(index))
+                },
+                _ => Err("Wrong payload for Action::MovePaneToTab"),
+            },
+            Some(ProtobufActionName::MoveTab) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MoveTabPayload(move_tab_payload)) => {
+                    let direction: Direction = ProtobufResizeDirection::from_i32(move_tab_payload)
+                        .ok_or("Malformed resize direction for Action::MoveTab")?
+                        .try_into()?;
+                    Ok(Action::MoveTab(direction))
+                },
+                _ => Err("Wrong payload for Action::MoveTab"),
+            },
+            Some(ProtobufActionName::CtrlLeftClick) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::CtrlLeftClickPayload(payload)) => {
+                    let position = payload.try_into()?;
+                    Ok(Action::CtrlLeftClick(position))
+                },
+                _ => Err("Wrong payload for Action::CtrlLeftClick"),
+            },
+            Some(ProtobufActionName::ShiftLeftClick) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::ShiftLeftClickPayload(payload)) => {
+                    let position = payload.try_into()?;
+                    Ok(Action::ShiftLeftClick(position))
+                },
+                _ => Err("Wrong payload for Action::ShiftLeftClick"),
+            },
+            Some(ProtobufActionName::AltLeftClick) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::AltLeftClickPayload(payload)) => {
+                    let position = payload.try_into()?;
+                    Ok(Action::AltLeftClick(position))
+                },
+                _ => Err("Wrong payload for Action::AltLeftClick"),
+            },
+            Some(ProtobufActionName::MouseHover) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::MouseHoverPayload(payload)) => {
+                    let position = payload.try_into()?;
+                    Ok(Action::MouseHover(position))
+                },
+                _ => Err("Wrong payload for Action::MouseHover"),
+            },
+            Some(ProtobufActionName::RespawnPane) => match protobuf_action.optional_payload {
+                Some(_) => Err("RespawnPane should not have a payload"),
+                None => Ok(Action::RespawnPane),
+            },
+            Some(ProtobufActionName::SwitchWorkspace) => match protobuf_action.optional_payload {
+                Some(_) => Err("SwitchWorkspace should not have a payload"),
+                None => Ok(Action::SwitchWorkspace),
+            },
+            Some(ProtobufActionName::ToggleScratchpad) => match protobuf_action.optional_payload {
+                Some(_) => Err("ToggleScratchpad should not have a payload"),
+                None => Ok(Action::ToggleScratchpad),
+            },
+            Some(ProtobufActionName::SwitchTheme) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SwitchThemePayload(theme)) => Ok(Action::SwitchTheme(theme)),
+                _ => Err("Wrong payload for Action::SwitchTheme"),
+            },
+            Some(ProtobufActionName::QueryPaneProcesses) => {
+                match protobuf_action.optional_payload {
+                    Some(_) => Err("QueryPaneProcesses should not have a payload"),
+                    None => Ok(Action::QueryPaneProcesses),
+                }
+            },
+            Some(ProtobufActionName::LoadBuffer) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::LoadBufferPayload(payload)) => {
+                    Ok(Action::LoadBuffer(payload))
+                },
+                _ => Err("Wrong payload for Action::LoadBuffer"),
+            },
+            Some(ProtobufActionName::SaveBuffer) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::SaveBufferPayload(payload)) => {
+                    Ok(Action::SaveBuffer(payload))
+                },
+                _ => Err("Wrong payload for Action::SaveBuffer"),
+            },
+            Some(ProtobufActionName::PasteBuffer) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::PasteBufferPayload(terminal_pane_id)) => {
+                    Ok(Action::PasteBuffer(Some(terminal_pane_id)))
+                },
+                None => Ok(Action::PasteBuffer(None)),
+                _ => Err("Wrong payload for Action::PasteBuffer"),
+            },
+            Some(ProtobufActionName::DumpLayout) => match protobuf_action.optional_payload {
+                Some(_) => Err("DumpLayout should not have a payload"),
+                None => Ok(Action::DumpLayout),
+            },
+            Some(ProtobufActionName::EnterCopyMode) => match protobuf_action.optional_payload {
+                Some(_) => Err("EnterCopyMode should not have a payload"),
+                None => Ok(Action::EnterCopyMode),
+            },
+            Some(ProtobufActionName::LinkHints) => match protobuf_action.optional_payload {
+                Some(_) => Err("LinkHints should not have a payload"),
+                None => Ok(Action::LinkHints),
+            },
+            Some(ProtobufActionName::NewInPlacePane) => match protobuf_action.optional_payload {
+                Some(OptionalPayload::NewInPlacePanePayload(payload)) => {
+                    if let Some(payload) = payload.command {
+                        let pane_name = payload.pane_name.clone();
+                        let run_command_action: RunCommandAction = payload.try_into()?;
+                        Ok(Action::NewInPlacePane(Some(run_command_action), pane_name))
+                    } else {
+                        Ok(Action::NewInPlacePane(None, None))
+                    }
+                },
+                _ => Err("Wrong payload for Action::NewInPlacePane"),
+            },
+            Some(ProtobufActionName::NewInPlacePluginPane) => {
+                match protobuf_action.optional_payload {
+                    Some(OptionalPayload::NewInPlacePluginPanePayload(payload)) => {
+                        let run_plugin_location =
+                            RunPluginLocation::parse(&payload.plugin_url, None)
+                                .map_err(|_| "Malformed NewInPlacePluginPane payload")?;
+                        let run_plugin = RunPlugin {
+                            location: run_plugin_location,
+                            _allow_exec_host_cmd: false,
+                            configuration: PluginUserConfiguration::default(),
+                        };
+                        let pane_name = payload.pane_name;
+                        Ok(Action::NewInPlacePluginPane(run_plugin, pane_name))
+                    },
+                    _ => Err("Wrong payload for Action::NewInPlacePluginPane"),
+                }
+            },
             _ => Err("Unknown Action"),
         }
     }
@@ -728,13 +936,16 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ClearScreen as i32,
                 optional_payload: None,
             }),
-            Action::DumpScreen(file_path, include_scrollback) => Ok(ProtobufAction {
-                name: ProtobufActionName::DumpScreen as i32,
-                optional_payload: Some(OptionalPayload::DumpScreenPayload(DumpScreenPayload {
-                    file_path,
-                    include_scrollback,
-                })),
-            }),
+            Action::DumpScreen(file_path, include_scrollback, include_ansi_styling) => {
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::DumpScreen as i32,
+                    optional_payload: Some(OptionalPayload::DumpScreenPayload(DumpScreenPayload {
+                        file_path,
+                        include_scrollback,
+                        include_ansi_styling,
+                    })),
+                })
+            },
             Action::EditScrollback => Ok(ProtobufAction {
                 name: ProtobufActionName::EditScrollback as i32,
                 optional_payload: None,
@@ -773,6 +984,18 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ScrollToTop as i32,
                 optional_payload: None,
             }),
+            Action::ScrollToPreviousPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToPreviousPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::ScrollToNextPrompt => Ok(ProtobufAction {
+                name: ProtobufActionName::ScrollToNextPrompt as i32,
+                optional_payload: None,
+            }),
+            Action::CopyLastCommandOutput => Ok(ProtobufAction {
+                name: ProtobufActionName::CopyLastCommandOutput as i32,
+                optional_payload: None,
+            }),
             Action::PageScrollUp => Ok(ProtobufAction {
                 name: ProtobufActionName::PageScrollUp as i32,
                 optional_payload: None,
@@ -793,6 +1016,18 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ToggleFocusFullscreen as i32,
                 optional_payload: None,
             }),
+            Action::ToggleExpandPane => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleExpandPane as i32,
+                optional_payload: None,
+            }),
+            Action::FocusNextPaneInStack => Ok(ProtobufAction {
+                name: ProtobufActionName::FocusNextPaneInStack as i32,
+                optional_payload: None,
+            }),
+            Action::FocusPreviousPaneInStack => Ok(ProtobufAction {
+                name: ProtobufActionName::FocusPreviousPaneInStack as i32,
+                optional_payload: None,
+            }),
             Action::TogglePaneFrames => Ok(ProtobufAction {
                 name: ProtobufActionName::TogglePaneFrames as i32,
                 optional_payload: None,
@@ -801,6 +1036,34 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::ToggleActiveSyncTab as i32,
                 optional_payload: None,
             }),
+            Action::ToggleActivePaneSync => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleActivePaneSync as i32,
+                optional_payload: None,
+            }),
+            Action::TogglePaneInGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePaneInGroup as i32,
+                optional_payload: None,
+            }),
+            Action::ClearPaneGroup => Ok(ProtobufAction {
+                name: ProtobufActionName::ClearPaneGroup as i32,
+                optional_payload: None,
+            }),
+            Action::CloseGroupedPanes => Ok(ProtobufAction {
+                name: ProtobufActionName::CloseGroupedPanes as i32,
+                optional_payload: None,
+            }),
+            Action::FloatGroupedPanes => Ok(ProtobufAction {
+                name: ProtobufActionName::FloatGroupedPanes as i32,
+                optional_payload: None,
+            }),
+            Action::BreakGroupedPanesToNewTab => Ok(ProtobufAction {
+                name: ProtobufActionName::BreakGroupedPanesToNewTab as i32,
+                optional_payload: None,
+            }),
+            Action::SyncGroupedPanes => Ok(ProtobufAction {
+                name: ProtobufActionName::SyncGroupedPanes as i32,
+                optional_payload: None,
+            }),
             Action::NewPane(direction, new_pane_name) => {
                 let direction = direction.and_then(|direction| {
                     let protobuf_direction: ProtobufResizeDirection = direction.try_into().ok()?;
@@ -869,6 +1132,10 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::TogglePaneEmbedOrFloating as i32,
                 optional_payload: None,
             }),
+            Action::TogglePanePinned => Ok(ProtobufAction {
+                name: ProtobufActionName::TogglePanePinned as i32,
+                optional_payload: None,
+            }),
             Action::ToggleFloatingPanes => Ok(ProtobufAction {
                 name: ProtobufActionName::ToggleFloatingPanes as i32,
                 optional_payload: None,
@@ -904,6 +1171,14 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::CloseTab as i32,
                 optional_payload: None,
             }),
+            Action::ReopenClosedPane => Ok(ProtobufAction {
+                name: ProtobufActionName::ReopenClosedPane as i32,
+                optional_payload: None,
+            }),
+            Action::ReopenClosedTab => Ok(ProtobufAction {
+                name: ProtobufActionName::ReopenClosedTab as i32,
+                optional_payload: None,
+            }),
             Action::GoToTab(tab_index) => Ok(ProtobufAction {
                 name: ProtobufActionName::GoToTab as i32,
                 optional_payload: Some(OptionalPayload::GoToTabPayload(tab_index)),
@@ -1147,11 +1422,130 @@ impl TryFrom<Action> for ProtobufAction {
                 name: ProtobufActionName::BreakPaneLeft as i32,
                 optional_payload: None,
             }),
+            Action::MovePaneToTab(index) => Ok(ProtobufAction {
+                name: ProtobufActionName::MovePaneToTab as i32,
+                optional_payload: Some(OptionalPayload::MovePaneToTabPayload(index)),
+            }),
+            Action::MoveTab(direction) => {
+                let direction: ProtobufResizeDirection = direction.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::MoveTab as i32,
+                    optional_payload: Some(OptionalPayload::MoveTabPayload(direction as i32)),
+                })
+            },
+            Action::CtrlLeftClick(position) => {
+                let position: ProtobufPosition = position.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::CtrlLeftClick as i32,
+                    optional_payload: Some(OptionalPayload::CtrlLeftClickPayload(position)),
+                })
+            },
+            Action::ShiftLeftClick(position) => {
+                let position: ProtobufPosition = position.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::ShiftLeftClick as i32,
+                    optional_payload: Some(OptionalPayload::ShiftLeftClickPayload(position)),
+                })
+            },
+            Action::AltLeftClick(position) => {
+                let position: ProtobufPosition = position.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::AltLeftClick as i32,
+                    optional_payload: Some(OptionalPayload::AltLeftClickPayload(position)),
+                })
+            },
+            Action::MouseHover(position) => {
+                let position: ProtobufPosition = position.try_into()?;
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::MouseHover as i32,
+                    optional_payload: Some(OptionalPayload::MouseHoverPayload(position)),
+                })
+            },
+            Action::RespawnPane => Ok(ProtobufAction {
+                name: ProtobufActionName::RespawnPane as i32,
+                optional_payload: None,
+            }),
+            Action::SwitchWorkspace => Ok(ProtobufAction {
+                name: ProtobufActionName::SwitchWorkspace as i32,
+                optional_payload: None,
+            }),
+            Action::ToggleScratchpad => Ok(ProtobufAction {
+                name: ProtobufActionName::ToggleScratchpad as i32,
+                optional_payload: None,
+            }),
+            Action::SwitchTheme(theme) => Ok(ProtobufAction {
+                name: ProtobufActionName::SwitchTheme as i32,
+                optional_payload: Some(OptionalPayload::SwitchThemePayload(theme)),
+            }),
+            Action::QueryPaneProcesses => Ok(ProtobufAction {
+                name: ProtobufActionName::QueryPaneProcesses as i32,
+                optional_payload: None,
+            }),
+            Action::LoadBuffer(contents) => Ok(ProtobufAction {
+                name: ProtobufActionName::LoadBuffer as i32,
+                optional_payload: Some(OptionalPayload::LoadBufferPayload(contents)),
+            }),
+            Action::SaveBuffer(file_path) => Ok(ProtobufAction {
+                name: ProtobufActionName::SaveBuffer as i32,
+                optional_payload: Some(OptionalPayload::SaveBufferPayload(file_path)),
+            }),
+            Action::PasteBuffer(terminal_pane_id) => Ok(ProtobufAction {
+                name: ProtobufActionName::PasteBuffer as i32,
+                optional_payload: terminal_pane_id.map(OptionalPayload::PasteBufferPayload),
+            }),
+            Action::DumpLayout => Ok(ProtobufAction {
+                name: ProtobufActionName::DumpLayout as i32,
+                optional_payload: None,
+            }),
+            Action::EnterCopyMode => Ok(ProtobufAction {
+                name: ProtobufActionName::EnterCopyMode as i32,
+                optional_payload: None,
+            }),
+            Action::LinkHints => Ok(ProtobufAction {
+                name: ProtobufActionName::LinkHints as i32,
+                optional_payload: None,
+            }),
+            Action::NewInPlacePane(run_command_action, pane_name) => {
+                let command = run_command_action.and_then(|r| {
+                    let mut protobuf_run_command_action: ProtobufRunCommandAction =
+                        r.try_into().ok()?;
+                    protobuf_run_command_action.pane_name = pane_name;
+                    Some(protobuf_run_command_action)
+                });
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::NewInPlacePane as i32,
+                    optional_payload: Some(OptionalPayload::NewInPlacePanePayload(
+                        NewFloatingPanePayload { command },
+                    )),
+                })
+            },
+            Action::NewInPlacePluginPane(run_plugin, pane_name) => {
+                let plugin_url: Url = Url::from(&run_plugin.location);
+                Ok(ProtobufAction {
+                    name: ProtobufActionName::NewInPlacePluginPane as i32,
+                    optional_payload: Some(OptionalPayload::NewInPlacePluginPanePayload(
+                        NewPluginPanePayload {
+                            plugin_url: plugin_url.into(),
+                            pane_name,
+                        },
+                    )),
+                })
+            },
             Action::NoOp
             | Action::Confirm
             | Action::Deny
             | Action::Copy
-            | Action::SkipConfirm(..) => Err("Unsupported action"),
+            | Action::SerializeSessionAndQuit
+            | Action::MoveFocusOrDelegate(..)
+            | Action::ForceScrollUpAt(..)
+            | Action::ForceScrollDownAt(..)
+            | Action::CliPipe { .. }
+            | Action::SkipConfirm(..)
+            | Action::KeySequence(..)
+            | Action::PendingKeys(..)
+            | Action::WriteToPaneId(..)
+            | Action::WriteCharsToPaneId(..)
+            | Action::SwitchToCustomMode(..) => Err("Unsupported action"),
         }
     }
 }
@@ -1163,6 +1557,7 @@ impl TryFrom<ProtobufSearchOption> for SearchOption {
             ProtobufSearchOption::CaseSensitivity => Ok(SearchOption::CaseSensitivity),
             ProtobufSearchOption::WholeWord => Ok(SearchOption::WholeWord),
             ProtobufSearchOption::Wrap => Ok(SearchOption::Wrap),
+            ProtobufSearchOption::Regex => Ok(SearchOption::Regex),
         }
     }
 }
@@ -1174,6 +1569,7 @@ impl TryFrom<SearchOption> for ProtobufSearchOption {
             SearchOption::CaseSensitivity => Ok(ProtobufSearchOption::CaseSensitivity),
             SearchOption::WholeWord => Ok(ProtobufSearchOption::WholeWord),
             SearchOption::Wrap => Ok(ProtobufSearchOption::Wrap),
+            SearchOption::Regex => Ok(ProtobufSearchOption::Regex),
         }
     }
 }
@@ -1219,6 +1615,7 @@ impl TryFrom<ProtobufRunCommandAction> for RunCommandAction {
             direction,
             hold_on_close,
             hold_on_start,
+            wait_for_exit: false,
         })
     }
 }
@@ -1,20 +1,21 @@
 pub use super::generated_api::api::{
     action::{Action as ProtobufAction, Position as ProtobufPosition},
     event::{
-        event::Payload as ProtobufEventPayload, CopyDestination as ProtobufCopyDestination,
-        Event as ProtobufEvent, EventNameList as ProtobufEventNameList,
-        EventType as ProtobufEventType, InputModeKeybinds as ProtobufInputModeKeybinds,
-        KeyBind as ProtobufKeyBind, ModeUpdatePayload as ProtobufModeUpdatePayload,
-        PaneInfo as ProtobufPaneInfo, PaneManifest as ProtobufPaneManifest,
-        SessionManifest as ProtobufSessionManifest, TabInfo as ProtobufTabInfo, *,
+        event::Payload as ProtobufEventPayload, BatteryState as ProtobufBatteryState,
+        CopyDestination as ProtobufCopyDestination, Event as ProtobufEvent,
+        EventNameList as ProtobufEventNameList, EventType as ProtobufEventType,
+        InputModeKeybinds as ProtobufInputModeKeybinds, KeyBind as ProtobufKeyBind,
+        ModeUpdatePayload as ProtobufModeUpdatePayload, PaneInfo as ProtobufPaneInfo,
+        PaneManifest as ProtobufPaneManifest, SessionManifest as ProtobufSessionManifest,
+        TabInfo as ProtobufTabInfo, *,
     },
     input_mode::InputMode as ProtobufInputMode,
     key::Key as ProtobufKey,
     style::Style as ProtobufStyle,
 };
 use crate::data::{
-    CopyDestination, Event, EventType, InputMode, Key, ModeInfo, Mouse, PaneInfo, PaneManifest,
-    PermissionStatus, PluginCapabilities, SessionInfo, Style, TabInfo,
+    BatteryState, CopyDestination, Event, EventType, InputMode, Key, ModeInfo, Mouse, PaneInfo,
+    PaneManifest, PermissionStatus, PluginCapabilities, SessionInfo, Style, SystemState, TabInfo,
 };
 
 use crate::errors::prelude::*;
@@ -23,6 +24,7 @@ use crate::input::actions::Action;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::path::PathBuf;
+use std::time::Duration;
 
 impl TryFrom<ProtobufEvent> for Event {
     type Error = &'static str;
@@ -179,10 +181,98 @@ impl TryFrom<ProtobufEvent> for Event {
                     for protobuf_session_info in protobuf_session_update_payload.session_manifests {
                         session_infos.push(SessionInfo::try_from(protobuf_session_info)?);
                     }
-                    Ok(Event::SessionUpdate(session_infos))
+                    let resurrectable_sessions: Vec<(String, Duration)> =
+                        protobuf_session_update_payload
+                            .resurrectable_sessions
+                            .into_iter()
+                            .map(|r| (r.name, Duration::from_secs(r.creation_time)))
+                            .collect();
+                    Ok(Event::SessionUpdate(session_infos, resurrectable_sessions))
                 },
                 _ => Err("Malformed payload for the SessionUpdate Event"),
             },
+            Some(ProtobufEventType::PaneOutput) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneOutputPayload(pane_output_payload)) => Ok(
+                    Event::PaneOutput(pane_output_payload.pane_id, pane_output_payload.output),
+                ),
+                _ => Err("Malformed payload for the PaneOutput Event"),
+            },
+            Some(ProtobufEventType::PaneOpened) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneOpenedPayload(terminal_pane_id)) => {
+                    Ok(Event::PaneOpened(terminal_pane_id))
+                },
+                _ => Err("Malformed payload for the PaneOpened Event"),
+            },
+            Some(ProtobufEventType::ConfigWasWrittenToDisk) => match protobuf_event.payload {
+                None => Ok(Event::ConfigWasWrittenToDisk),
+                _ => Err("Malformed payload for the ConfigWasWrittenToDisk Event"),
+            },
+            Some(ProtobufEventType::InPlacePaneClosed) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::InPlacePaneClosedPayload(
+                    in_place_pane_closed_payload,
+                )) => Ok(Event::InPlacePaneClosed(
+                    in_place_pane_closed_payload.pane_id,
+                    in_place_pane_closed_payload.return_value,
+                )),
+                _ => Err("Malformed payload for the InPlacePaneClosed Event"),
+            },
+            Some(ProtobufEventType::RunCommandResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::RunCommandResultPayload(run_command_result_payload)) => {
+                    Ok(Event::RunCommandResult(
+                        run_command_result_payload.exit_code,
+                        run_command_result_payload.stdout,
+                        run_command_result_payload.stderr,
+                    ))
+                },
+                _ => Err("Malformed payload for the RunCommandResult Event"),
+            },
+            Some(ProtobufEventType::WebRequestResult) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::WebRequestResultPayload(web_request_result_payload)) => {
+                    let status = match web_request_result_payload.status {
+                        Some(status) => Some(
+                            u16::try_from(status)
+                                .map_err(|_| "Malformed status for the WebRequestResult Event")?,
+                        ),
+                        None => None,
+                    };
+                    let headers = web_request_result_payload
+                        .headers
+                        .into_iter()
+                        .map(|header| (header.name, header.value))
+                        .collect();
+                    Ok(Event::WebRequestResult(
+                        status,
+                        headers,
+                        web_request_result_payload.body,
+                    ))
+                },
+                _ => Err("Malformed payload for the WebRequestResult Event"),
+            },
+            Some(ProtobufEventType::SystemStateUpdate) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::SystemStatePayload(system_state_payload)) => {
+                    Ok(Event::SystemStateUpdate(system_state_payload.try_into()?))
+                },
+                _ => Err("Malformed payload for the SystemStateUpdate Event"),
+            },
+            Some(ProtobufEventType::PendingKeys) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PendingKeysPayload(pending_keys_payload)) => {
+                    let mut pending_keys = vec![];
+                    for key in pending_keys_payload.keys {
+                        pending_keys.push(key.try_into()?);
+                    }
+                    Ok(Event::PendingKeys(pending_keys))
+                },
+                _ => Err("Malformed payload for the PendingKeys Event"),
+            },
+            Some(ProtobufEventType::PaneContents) => match protobuf_event.payload {
+                Some(ProtobufEventPayload::PaneContentsPayload(pane_contents_payload)) => {
+                    Ok(Event::PaneContents(
+                        pane_contents_payload.pane_id,
+                        pane_contents_payload.contents,
+                    ))
+                },
+                _ => Err("Malformed payload for the PaneContents Event"),
+            },
             None => Err("Unknown Protobuf Event"),
         }
     }
@@ -325,19 +415,139 @@ impl TryFrom<Event> for ProtobufEvent {
                     )),
                 })
             },
-            Event::SessionUpdate(session_infos) => {
+            Event::SessionUpdate(session_infos, resurrectable_sessions) => {
                 let mut protobuf_session_manifests = vec![];
                 for session_info in session_infos {
                     protobuf_session_manifests.push(session_info.try_into()?);
                 }
+                let protobuf_resurrectable_sessions = resurrectable_sessions
+                    .into_iter()
+                    .map(|(name, age)| ResurrectableSession {
+                        name,
+                        creation_time: age.as_secs(),
+                    })
+                    .collect();
                 let session_update_payload = SessionUpdatePayload {
                     session_manifests: protobuf_session_manifests,
+                    resurrectable_sessions: protobuf_resurrectable_sessions,
                 };
                 Ok(ProtobufEvent {
                     name: ProtobufEventType::SessionUpdate as i32,
                     payload: Some(event::Payload::SessionUpdatePayload(session_update_payload)),
                 })
             },
+            Event::PaneOutput(pane_id, output) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneOutput as i32,
+                payload: Some(event::Payload::PaneOutputPayload(PaneOutputPayload {
+                    pane_id,
+                    output,
+                })),
+            }),
+            Event::PaneOpened(terminal_pane_id) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneOpened as i32,
+                payload: Some(event::Payload::PaneOpenedPayload(terminal_pane_id)),
+            }),
+            Event::ConfigWasWrittenToDisk => Ok(ProtobufEvent {
+                name: ProtobufEventType::ConfigWasWrittenToDisk as i32,
+                payload: None,
+            }),
+            Event::InPlacePaneClosed(pane_id, return_value) => Ok(ProtobufEvent {
+                name: ProtobufEventType::InPlacePaneClosed as i32,
+                payload: Some(event::Payload::InPlacePaneClosedPayload(
+                    InPlacePaneClosedPayload {
+                        pane_id,
+                        return_value,
+                    },
+                )),
+            }),
+            Event::RunCommandResult(exit_code, stdout, stderr) => Ok(ProtobufEvent {
+                name: ProtobufEventType::RunCommandResult as i32,
+                payload: Some(event::Payload::RunCommandResultPayload(
+                    RunCommandResultPayload {
+                        exit_code,
+                        stdout,
+                        stderr,
+                    },
+                )),
+            }),
+            Event::WebRequestResult(status, headers, body) => Ok(ProtobufEvent {
+                name: ProtobufEventType::WebRequestResult as i32,
+                payload: Some(event::Payload::WebRequestResultPayload(
+                    WebRequestResultPayload {
+                        status: status.map(|status| status as u32),
+                        headers: headers
+                            .into_iter()
+                            .map(|(name, value)| Header { name, value })
+                            .collect(),
+                        body,
+                    },
+                )),
+            }),
+            Event::SystemStateUpdate(system_state) => Ok(ProtobufEvent {
+                name: ProtobufEventType::SystemStateUpdate as i32,
+                payload: Some(event::Payload::SystemStatePayload(system_state.into())),
+            }),
+            Event::PendingKeys(pending_keys) => {
+                let mut keys = vec![];
+                for key in pending_keys {
+                    keys.push(key.try_into()?);
+                }
+                Ok(ProtobufEvent {
+                    name: ProtobufEventType::PendingKeys as i32,
+                    payload: Some(event::Payload::PendingKeysPayload(PendingKeysPayload {
+                        keys,
+                    })),
+                })
+            },
+            Event::PaneContents(pane_id, contents) => Ok(ProtobufEvent {
+                name: ProtobufEventType::PaneContents as i32,
+                payload: Some(event::Payload::PaneContentsPayload(PaneContentsPayload {
+                    pane_id,
+                    contents,
+                })),
+            }),
+        }
+    }
+}
+
+impl TryFrom<SystemStatePayload> for SystemState {
+    type Error = &'static str;
+    fn try_from(system_state_payload: SystemStatePayload) -> Result<Self, &'static str> {
+        let load_average = match system_state_payload.load_average.as_slice() {
+            [] => None,
+            [one, five, fifteen] => Some([*one, *five, *fifteen]),
+            _ => return Err("Malformed load average in the SystemState"),
+        };
+        let battery = match system_state_payload.battery {
+            Some(battery) => Some(BatteryState {
+                percentage: u8::try_from(battery.percentage)
+                    .map_err(|_| "Malformed battery percentage in the SystemState")?,
+                charging: battery.charging,
+            }),
+            None => None,
+        };
+        Ok(SystemState {
+            unix_time: system_state_payload.unix_time,
+            utc_offset: system_state_payload.utc_offset,
+            load_average,
+            battery,
+        })
+    }
+}
+
+impl From<SystemState> for SystemStatePayload {
+    fn from(system_state: SystemState) -> Self {
+        SystemStatePayload {
+            unix_time: system_state.unix_time,
+            utc_offset: system_state.utc_offset,
+            load_average: system_state
+                .load_average
+                .map(|load_average| load_average.to_vec())
+                .unwrap_or_default(),
+            battery: system_state.battery.map(|battery| ProtobufBatteryState {
+                percentage: battery.percentage as u32,
+                charging: battery.charging,
+            }),
         }
     }
 }
@@ -366,6 +576,7 @@ impl TryFrom<SessionInfo> for ProtobufSessionManifest {
                 .collect(),
             connected_clients: session_info.connected_clients as u32,
             is_current_session: session_info.is_current_session,
+            group: session_info.group,
         })
     }
 }
@@ -398,6 +609,7 @@ impl TryFrom<ProtobufSessionManifest> for SessionInfo {
             panes,
             connected_clients: protobuf_session_manifest.connected_clients as usize,
             is_current_session: protobuf_session_manifest.is_current_session,
+            group: protobuf_session_manifest.group,
         })
     }
 }
@@ -468,6 +680,24 @@ impl TryFrom<MouseEventPayload> for Mouse {
                 ),
                 _ => Err("Malformed payload for mouse release"),
             },
+            Some(MouseEventName::MouseMiddleClick) => {
+                match mouse_event_payload.mouse_event_payload {
+                    Some(mouse_event_payload::MouseEventPayload::Position(position)) => Ok(
+                        Mouse::MiddleClick(position.line as isize, position.column as usize),
+                    ),
+                    _ => Err("Malformed payload for mouse middle click"),
+                }
+            },
+            Some(MouseEventName::MouseHover) => match mouse_event_payload.mouse_event_payload {
+                Some(mouse_event_payload::MouseEventPayload::Position(position)) => Ok(
+                    Mouse::Hover(position.line as isize, position.column as usize),
+                ),
+                _ => Err("Malformed payload for mouse hover"),
+            },
+            Some(MouseEventName::MouseHoverEnd) => match mouse_event_payload.mouse_event_payload {
+                None => Ok(Mouse::HoverEnd),
+                _ => Err("Malformed payload for mouse hover end"),
+            },
             None => Err("Malformed payload for MouseEventName"),
         }
     }
@@ -525,6 +755,28 @@ impl TryFrom<Mouse> for MouseEventPayload {
                     },
                 )),
             }),
+            Mouse::MiddleClick(line, column) => Ok(MouseEventPayload {
+                mouse_event_name: MouseEventName::MouseMiddleClick as i32,
+                mouse_event_payload: Some(mouse_event_payload::MouseEventPayload::Position(
+                    ProtobufPosition {
+                        line: line as i64,
+                        column: column as i64,
+                    },
+                )),
+            }),
+            Mouse::Hover(line, column) => Ok(MouseEventPayload {
+                mouse_event_name: MouseEventName::MouseHover as i32,
+                mouse_event_payload: Some(mouse_event_payload::MouseEventPayload::Position(
+                    ProtobufPosition {
+                        line: line as i64,
+                        column: column as i64,
+                    },
+                )),
+            }),
+            Mouse::HoverEnd => Ok(MouseEventPayload {
+                mouse_event_name: MouseEventName::MouseHoverEnd as i32,
+                mouse_event_payload: None,
+            }),
         }
     }
 }
@@ -557,6 +809,12 @@ impl TryFrom<ProtobufPaneInfo> for PaneInfo {
             terminal_command: protobuf_pane_info.terminal_command,
             plugin_url: protobuf_pane_info.plugin_url,
             is_selectable: protobuf_pane_info.is_selectable,
+            child_pids: protobuf_pane_info.child_pids,
+            foreground_process: protobuf_pane_info.foreground_process,
+            cwd: protobuf_pane_info.cwd.map(PathBuf::from),
+            has_bell: protobuf_pane_info.has_bell,
+            has_activity: protobuf_pane_info.has_activity,
+            is_silent: protobuf_pane_info.is_silent,
         })
     }
 }
@@ -592,6 +850,12 @@ impl TryFrom<PaneInfo> for ProtobufPaneInfo {
             terminal_command: pane_info.terminal_command,
             plugin_url: pane_info.plugin_url,
             is_selectable: pane_info.is_selectable,
+            child_pids: pane_info.child_pids,
+            foreground_process: pane_info.foreground_process,
+            cwd: pane_info.cwd.map(|cwd| cwd.display().to_string()),
+            has_bell: pane_info.has_bell,
+            has_activity: pane_info.has_activity,
+            is_silent: pane_info.is_silent,
         })
     }
 }
@@ -614,6 +878,9 @@ impl TryFrom<ProtobufTabInfo> for TabInfo {
                 .collect(),
             active_swap_layout_name: protobuf_tab_info.active_swap_layout_name,
             is_swap_layout_dirty: protobuf_tab_info.is_swap_layout_dirty,
+            pane_count: protobuf_tab_info.pane_count as usize,
+            has_unseen_output: protobuf_tab_info.has_unseen_output,
+            focused_pane_title: protobuf_tab_info.focused_pane_title,
         })
     }
 }
@@ -636,6 +903,9 @@ impl TryFrom<TabInfo> for ProtobufTabInfo {
                 .collect(),
             active_swap_layout_name: tab_info.active_swap_layout_name,
             is_swap_layout_dirty: tab_info.is_swap_layout_dirty,
+            pane_count: tab_info.pane_count as u32,
+            has_unseen_output: tab_info.has_unseen_output,
+            focused_pane_title: tab_info.focused_pane_title,
         })
     }
 }
@@ -677,6 +947,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             .and_then(|m| m.try_into().ok())
             .ok_or("malformed payload for mode_info")?;
         let session_name = protobuf_mode_update_payload.session_name;
+        let custom_mode = protobuf_mode_update_payload.custom_mode;
         let capabilities = PluginCapabilities {
             arrow_fonts: protobuf_mode_update_payload.arrow_fonts_support,
         };
@@ -686,6 +957,7 @@ impl TryFrom<ProtobufModeUpdatePayload> for ModeInfo {
             style,
             capabilities,
             session_name,
+            custom_mode,
         };
         Ok(mode_info)
     }
@@ -698,6 +970,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
         let style: ProtobufStyle = mode_info.style.try_into()?;
         let arrow_fonts_support: bool = mode_info.capabilities.arrow_fonts;
         let session_name = mode_info.session_name;
+        let custom_mode = mode_info.custom_mode;
         let mut protobuf_input_mode_keybinds: Vec<ProtobufInputModeKeybinds> = vec![];
         for (input_mode, input_mode_keybinds) in mode_info.keybinds {
             let mode: ProtobufInputMode = input_mode.try_into()?;
@@ -728,6 +1001,7 @@ impl TryFrom<ModeInfo> for ProtobufModeUpdatePayload {
             keybinds: protobuf_input_mode_keybinds,
             arrow_fonts_support,
             session_name,
+            custom_mode,
         })
     }
 }
@@ -783,6 +1057,15 @@ impl TryFrom<ProtobufEventType> for EventType {
             ProtobufEventType::FileSystemDelete => EventType::FileSystemDelete,
             ProtobufEventType::PermissionRequestResult => EventType::PermissionRequestResult,
             ProtobufEventType::SessionUpdate => EventType::SessionUpdate,
+            ProtobufEventType::PaneOutput => EventType::PaneOutput,
+            ProtobufEventType::PaneOpened => EventType::PaneOpened,
+            ProtobufEventType::ConfigWasWrittenToDisk => EventType::ConfigWasWrittenToDisk,
+            ProtobufEventType::InPlacePaneClosed => EventType::InPlacePaneClosed,
+            ProtobufEventType::RunCommandResult => EventType::RunCommandResult,
+            ProtobufEventType::WebRequestResult => EventType::WebRequestResult,
+            ProtobufEventType::SystemStateUpdate => EventType::SystemStateUpdate,
+            ProtobufEventType::PendingKeys => EventType::PendingKeys,
+            ProtobufEventType::PaneContents => EventType::PaneContents,
         })
     }
 }
@@ -808,6 +1091,15 @@ impl TryFrom<EventType> for ProtobufEventType {
             EventType::FileSystemDelete => ProtobufEventType::FileSystemDelete,
             EventType::PermissionRequestResult => ProtobufEventType::PermissionRequestResult,
             EventType::SessionUpdate => ProtobufEventType::SessionUpdate,
+            EventType::PaneOutput => ProtobufEventType::PaneOutput,
+            EventType::PaneOpened => ProtobufEventType::PaneOpened,
+            EventType::ConfigWasWrittenToDisk => ProtobufEventType::ConfigWasWrittenToDisk,
+            EventType::InPlacePaneClosed => ProtobufEventType::InPlacePaneClosed,
+            EventType::RunCommandResult => ProtobufEventType::RunCommandResult,
+            EventType::WebRequestResult => ProtobufEventType::WebRequestResult,
+            EventType::SystemStateUpdate => ProtobufEventType::SystemStateUpdate,
+            EventType::PendingKeys => ProtobufEventType::PendingKeys,
+            EventType::PaneContents => ProtobufEventType::PaneContents,
         })
     }
 }
@@ -886,9 +1178,17 @@ fn serialize_mode_update_event_with_non_default_values() {
             },
             rounded_corners: true,
             hide_session_name: false,
+            styling: crate::data::Styling {
+                frame_unselected: Some(crate::data::StyleDeclaration {
+                    base: PaletteColor::Rgb((3, 2, 1)),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
         },
         capabilities: PluginCapabilities { arrow_fonts: false },
         session_name: Some("my awesome test session".to_owned()),
+        custom_mode: None,
     });
     let protobuf_event: ProtobufEvent = mode_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
@@ -931,6 +1231,9 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            pane_count: 1,
+            has_unseen_output: true,
+            focused_pane_title: None,
         },
         TabInfo {
             position: 1,
@@ -943,6 +1246,9 @@ fn serialize_tab_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            pane_count: 3,
+            has_unseen_output: false,
+            focused_pane_title: Some("vim".to_owned()),
         },
         TabInfo::default(),
     ]);
@@ -1002,6 +1308,25 @@ fn serialize_mouse_event() {
     );
 }
 
+#[test]
+fn serialize_mouse_hover_events() {
+    use prost::Message;
+    for mouse_event in [
+        Event::Mouse(Mouse::Hover(2, 5)),
+        Event::Mouse(Mouse::HoverEnd),
+    ] {
+        let protobuf_event: ProtobufEvent = mouse_event.clone().try_into().unwrap();
+        let serialized_protobuf_event = protobuf_event.encode_to_vec();
+        let deserialized_protobuf_event: ProtobufEvent =
+            Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+        let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+        assert_eq!(
+            mouse_event, deserialized_event,
+            "Event properly serialized/deserialized without change"
+        );
+    }
+}
+
 #[test]
 fn serialize_mouse_event_without_position() {
     use prost::Message;
@@ -1174,7 +1499,7 @@ fn serialize_file_system_delete_event() {
 #[test]
 fn serialize_session_update_event() {
     use prost::Message;
-    let session_update_event = Event::SessionUpdate(Default::default());
+    let session_update_event = Event::SessionUpdate(Default::default(), Default::default());
     let protobuf_event: ProtobufEvent = session_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
     let deserialized_protobuf_event: ProtobufEvent =
@@ -1201,6 +1526,9 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![2, 3, 4],
             active_swap_layout_name: Some("my cool swap layout".to_owned()),
             is_swap_layout_dirty: false,
+            pane_count: 1,
+            has_unseen_output: true,
+            focused_pane_title: None,
         },
         TabInfo {
             position: 1,
@@ -1213,6 +1541,9 @@ fn serialize_session_update_event_with_non_default_values() {
             other_focused_clients: vec![1, 5, 111],
             active_swap_layout_name: None,
             is_swap_layout_dirty: true,
+            pane_count: 3,
+            has_unseen_output: false,
+            focused_pane_title: Some("vim".to_owned()),
         },
         TabInfo::default(),
     ];
@@ -1241,6 +1572,12 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: Some("foo".to_owned()),
             plugin_url: None,
             is_selectable: true,
+            child_pids: vec![1234, 1240],
+            foreground_process: Some("vim".to_owned()),
+            cwd: Some(PathBuf::from("/tmp")),
+            has_bell: true,
+            has_activity: true,
+            is_silent: false,
         },
         PaneInfo {
             id: 1,
@@ -1265,6 +1602,12 @@ fn serialize_session_update_event_with_non_default_values() {
             terminal_command: None,
             plugin_url: Some("i_am_a_fake_plugin".to_owned()),
             is_selectable: true,
+            child_pids: vec![],
+            foreground_process: None,
+            cwd: None,
+            has_bell: false,
+            has_activity: false,
+            is_silent: false,
         },
     ];
     panes.insert(0, panes_list);
@@ -1274,6 +1617,7 @@ fn serialize_session_update_event_with_non_default_values() {
         panes: PaneManifest { panes },
         connected_clients: 2,
         is_current_session: true,
+        group: Some("work".to_owned()),
     };
     let session_info_2 = SessionInfo {
         name: "session 2".to_owned(),
@@ -1283,10 +1627,13 @@ fn serialize_session_update_event_with_non_default_values() {
         },
         connected_clients: 0,
         is_current_session: false,
+        group: None,
     };
     let session_infos = vec![session_info_1, session_info_2];
 
-    let session_update_event = Event::SessionUpdate(session_infos);
+    let resurrectable_sessions = vec![("dead-session".to_owned(), Duration::from_secs(60))];
+
+    let session_update_event = Event::SessionUpdate(session_infos, resurrectable_sessions);
     let protobuf_event: ProtobufEvent = session_update_event.clone().try_into().unwrap();
     let serialized_protobuf_event = protobuf_event.encode_to_vec();
     let deserialized_protobuf_event: ProtobufEvent =
@@ -1297,3 +1644,156 @@ fn serialize_session_update_event_with_non_default_values() {
         "Event properly serialized/deserialized without change"
     );
 }
+
+#[test]
+fn serialize_pane_output_event() {
+    use prost::Message;
+    let pane_output_event = Event::PaneOutput(1, "test result: FAILED\n".to_owned());
+    let protobuf_event: ProtobufEvent = pane_output_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_output_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_opened_event() {
+    use prost::Message;
+    let pane_opened_event = Event::PaneOpened(3);
+    let protobuf_event: ProtobufEvent = pane_opened_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_opened_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_config_was_written_to_disk_event() {
+    use prost::Message;
+    let config_was_written_to_disk_event = Event::ConfigWasWrittenToDisk;
+    let protobuf_event: ProtobufEvent =
+        config_was_written_to_disk_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        config_was_written_to_disk_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_in_place_pane_closed_event() {
+    use prost::Message;
+    let in_place_pane_closed_event = Event::InPlacePaneClosed(3, "selected/file.rs".to_owned());
+    let protobuf_event: ProtobufEvent = in_place_pane_closed_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        in_place_pane_closed_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_run_command_result_event() {
+    use prost::Message;
+    let run_command_result_event = Event::RunCommandResult(
+        Some(1),
+        b"On branch main\n".to_vec(),
+        b"fatal: not a git repository\n".to_vec(),
+    );
+    let protobuf_event: ProtobufEvent = run_command_result_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        run_command_result_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_web_request_result_event() {
+    use prost::Message;
+    use std::collections::BTreeMap;
+    let mut headers = BTreeMap::new();
+    headers.insert("content-type".to_owned(), "application/json".to_owned());
+    let web_request_result_event =
+        Event::WebRequestResult(Some(200), headers, b"{\"temperature\": 21}".to_vec());
+    let protobuf_event: ProtobufEvent = web_request_result_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        web_request_result_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_system_state_update_event() {
+    use prost::Message;
+    let system_state_update_event = Event::SystemStateUpdate(SystemState {
+        unix_time: 1700000000,
+        utc_offset: 3600,
+        load_average: Some([0.5, 0.25, 0.125]),
+        battery: Some(BatteryState {
+            percentage: 87,
+            charging: true,
+        }),
+    });
+    let protobuf_event: ProtobufEvent = system_state_update_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        system_state_update_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pending_keys_event() {
+    use prost::Message;
+    let pending_keys_event = Event::PendingKeys(vec![Key::Ctrl('a'), Key::Char('c')]);
+    let protobuf_event: ProtobufEvent = pending_keys_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pending_keys_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
+
+#[test]
+fn serialize_pane_contents_event() {
+    use prost::Message;
+    let pane_contents_event =
+        Event::PaneContents(1, "\u{1b}[31mtest result: FAILED\u{1b}[m\n".to_owned());
+    let protobuf_event: ProtobufEvent = pane_contents_event.clone().try_into().unwrap();
+    let serialized_protobuf_event = protobuf_event.encode_to_vec();
+    let deserialized_protobuf_event: ProtobufEvent =
+        Message::decode(serialized_protobuf_event.as_slice()).unwrap();
+    let deserialized_event: Event = deserialized_protobuf_event.try_into().unwrap();
+    assert_eq!(
+        pane_contents_event, deserialized_event,
+        "Event properly serialized/deserialized without change"
+    );
+}
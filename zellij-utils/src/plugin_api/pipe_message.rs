@@ -0,0 +1,54 @@
+pub use super::generated_api::api::pipe_message::{
+    Arg as ProtobufArg, PipeMessage as ProtobufPipeMessage, PipeSource as ProtobufPipeSource,
+};
+use crate::data::{PipeMessage, PipeSource};
+
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+
+impl TryFrom<ProtobufPipeMessage> for PipeMessage {
+    type Error = &'static str;
+    fn try_from(protobuf_pipe_message: ProtobufPipeMessage) -> Result<Self, &'static str> {
+        let source = match (
+            ProtobufPipeSource::from_i32(protobuf_pipe_message.source),
+            protobuf_pipe_message.plugin_source_id,
+        ) {
+            (Some(ProtobufPipeSource::Plugin), Some(plugin_id)) => PipeSource::Plugin(plugin_id),
+            (Some(ProtobufPipeSource::Cli), _) => PipeSource::Cli,
+            _ => return Err("Invalid PipeSource"),
+        };
+        let args: BTreeMap<String, String> = protobuf_pipe_message
+            .args
+            .into_iter()
+            .map(|arg| (arg.key, arg.value))
+            .collect();
+        Ok(PipeMessage {
+            source,
+            name: protobuf_pipe_message.name,
+            payload: protobuf_pipe_message.payload,
+            args,
+        })
+    }
+}
+
+impl TryFrom<PipeMessage> for ProtobufPipeMessage {
+    type Error = &'static str;
+    fn try_from(pipe_message: PipeMessage) -> Result<Self, &'static str> {
+        let (source, plugin_source_id) = match pipe_message.source {
+            PipeSource::Plugin(plugin_id) => (ProtobufPipeSource::Plugin, Some(plugin_id)),
+            PipeSource::Cli => (ProtobufPipeSource::Cli, None),
+        };
+        let args: Vec<ProtobufArg> = pipe_message
+            .args
+            .into_iter()
+            .map(|(key, value)| ProtobufArg { key, value })
+            .collect();
+        Ok(ProtobufPipeMessage {
+            source: source as i32,
+            plugin_source_id,
+            name: pipe_message.name,
+            payload: pipe_message.payload,
+            args,
+        })
+    }
+}
@@ -1,20 +1,25 @@
 pub use super::generated_api::api::{
     action::{PaneIdAndShouldFloat, SwitchToModePayload},
-    event::EventNameList as ProtobufEventNameList,
+    event::{EventNameList as ProtobufEventNameList, Header as ProtobufHeader},
     input_mode::InputMode as ProtobufInputMode,
+    pipe_message::Arg as ProtobufArg,
     plugin_command::{
-        plugin_command::Payload, CommandName, ExecCmdPayload, IdAndNewName, MovePayload,
-        OpenCommandPanePayload, OpenFilePayload, PluginCommand as ProtobufPluginCommand,
-        PluginMessagePayload, RequestPluginPermissionPayload, ResizePayload, SetTimeoutPayload,
+        plugin_command::Payload, CommandName, DumpPaneContentsPayload, EnvVariable, ExecCmdPayload,
+        HttpVerb as ProtobufHttpVerb, IdAndNewName, KillSessionsPayload, MessageToPluginPayload,
+        MovePayload, MoveTabToIndexPayload, OpenCommandPanePayload, OpenFilePayload,
+        PluginCommand as ProtobufPluginCommand, PluginMessagePayload, RebindKeysPayload,
+        RequestPluginPermissionPayload, ResizePayload, RunCommandPayload, SetTimeoutPayload,
         SubscribePayload, SwitchSessionPayload, SwitchTabToPayload, UnsubscribePayload,
+        WebRequestPayload, WriteCharsToPaneIdPayload, WriteToPaneIdPayload,
     },
     plugin_permission::PermissionType as ProtobufPermissionType,
     resize::ResizeAction as ProtobufResizeAction,
 };
 
-use crate::data::{ConnectToSession, PermissionType, PluginCommand};
+use crate::data::{ConnectToSession, HttpVerb, MessageToPlugin, PermissionType, PluginCommand};
 
 use std::convert::TryFrom;
+use std::path::PathBuf;
 
 impl TryFrom<ProtobufPluginCommand> for PluginCommand {
     type Error = &'static str;
@@ -516,10 +521,215 @@ impl TryFrom<ProtobufPluginCommand> for PluginCommand {
                         name: payload.name,
                         tab_position: payload.tab_position.map(|p| p as usize),
                         pane_id,
+                        layout: payload.layout,
                     }))
                 },
                 _ => Err("Mismatched payload for SwitchSession"),
             },
+            Some(CommandName::SubscribeToPaneOutput) => match protobuf_plugin_command.payload {
+                Some(Payload::SubscribeToPaneOutputPayload(terminal_pane_id)) => {
+                    Ok(PluginCommand::SubscribeToPaneOutput(terminal_pane_id))
+                },
+                _ => Err("Mismatched payload for SubscribeToPaneOutput"),
+            },
+            Some(CommandName::UnsubscribeFromPaneOutput) => match protobuf_plugin_command.payload {
+                Some(Payload::UnsubscribeFromPaneOutputPayload(terminal_pane_id)) => {
+                    Ok(PluginCommand::UnsubscribeFromPaneOutput(terminal_pane_id))
+                },
+                _ => Err("Mismatched payload for UnsubscribeFromPaneOutput"),
+            },
+            Some(CommandName::PipeMessageToPlugin) => match protobuf_plugin_command.payload {
+                Some(Payload::MessageToPluginPayload(message_to_plugin_payload)) => {
+                    Ok(PluginCommand::PipeMessageToPlugin(MessageToPlugin {
+                        plugin_url: message_to_plugin_payload.plugin_url,
+                        message_name: message_to_plugin_payload.message_name,
+                        message_payload: message_to_plugin_payload.message_payload,
+                        message_args: message_to_plugin_payload
+                            .message_args
+                            .into_iter()
+                            .map(|arg| (arg.key, arg.value))
+                            .collect(),
+                    }))
+                },
+                _ => Err("Mismatched payload for PipeMessageToPlugin"),
+            },
+            Some(CommandName::KillSessions) => match protobuf_plugin_command.payload {
+                Some(Payload::KillSessionsPayload(kill_sessions_payload)) => Ok(
+                    PluginCommand::KillSessions(kill_sessions_payload.session_names),
+                ),
+                _ => Err("Mismatched payload for KillSessions"),
+            },
+            Some(CommandName::DeleteDeadSession) => match protobuf_plugin_command.payload {
+                Some(Payload::DeleteDeadSessionPayload(dead_session_name)) => {
+                    Ok(PluginCommand::DeleteDeadSession(dead_session_name))
+                },
+                _ => Err("Mismatched payload for DeleteDeadSession"),
+            },
+            Some(CommandName::RenameSession) => match protobuf_plugin_command.payload {
+                Some(Payload::RenameSessionPayload(new_session_name)) => {
+                    Ok(PluginCommand::RenameSession(new_session_name))
+                },
+                _ => Err("Mismatched payload for RenameSession"),
+            },
+            Some(CommandName::MoveTabToIndex) => match protobuf_plugin_command.payload {
+                Some(Payload::MoveTabToIndexPayload(move_tab_to_index_payload)) => {
+                    Ok(PluginCommand::MoveTabToIndex(
+                        move_tab_to_index_payload.tab_index,
+                        move_tab_to_index_payload.new_index,
+                    ))
+                },
+                _ => Err("Mismatched payload for MoveTabToIndex"),
+            },
+            Some(CommandName::OpenFileInPlace) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenFileInPlacePayload(file_to_open_payload)) => {
+                    match file_to_open_payload.file_to_open {
+                        Some(file_to_open) => {
+                            Ok(PluginCommand::OpenFileInPlace(file_to_open.try_into()?))
+                        },
+                        None => Err("Malformed open file in place payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for OpenFileInPlace"),
+            },
+            Some(CommandName::OpenTerminalInPlace) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenTerminalInPlacePayload(file_to_open_payload)) => {
+                    match file_to_open_payload.file_to_open {
+                        Some(file_to_open) => {
+                            Ok(PluginCommand::OpenTerminalInPlace(file_to_open.try_into()?))
+                        },
+                        None => Err("Malformed open terminal in place payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for OpenTerminalInPlace"),
+            },
+            Some(CommandName::OpenCommandPaneInPlace) => match protobuf_plugin_command.payload {
+                Some(Payload::OpenCommandPaneInPlacePayload(command_to_run_payload)) => {
+                    match command_to_run_payload.command_to_run {
+                        Some(command_to_run) => Ok(PluginCommand::OpenCommandPaneInPlace(
+                            command_to_run.try_into()?,
+                        )),
+                        None => Err("Malformed open command pane in place payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for OpenCommandPaneInPlace"),
+            },
+            Some(CommandName::RebindKeys) => match protobuf_plugin_command.payload {
+                Some(Payload::RebindKeysPayload(rebind_keys_payload)) => {
+                    Ok(PluginCommand::RebindKeys(
+                        rebind_keys_payload.keybinds,
+                        rebind_keys_payload.write_config_to_disk,
+                    ))
+                },
+                _ => Err("Mismatched payload for RebindKeys"),
+            },
+            Some(CommandName::BlockCliPipeInput) => match protobuf_plugin_command.payload {
+                Some(Payload::BlockCliPipeInputPayload(pipe_name)) => {
+                    Ok(PluginCommand::BlockCliPipeInput(pipe_name))
+                },
+                _ => Err("Mismatched payload for BlockCliPipeInput"),
+            },
+            Some(CommandName::UnblockCliPipeInput) => match protobuf_plugin_command.payload {
+                Some(Payload::UnblockCliPipeInputPayload(pipe_name)) => {
+                    Ok(PluginCommand::UnblockCliPipeInput(pipe_name))
+                },
+                _ => Err("Mismatched payload for UnblockCliPipeInput"),
+            },
+            Some(CommandName::RunCommand) => match protobuf_plugin_command.payload {
+                Some(Payload::RunCommandPayload(run_command_payload)) => {
+                    match run_command_payload.command_to_run {
+                        Some(command_to_run) => {
+                            let env_variables = run_command_payload
+                                .env_variables
+                                .into_iter()
+                                .map(|env_variable| (env_variable.name, env_variable.value))
+                                .collect();
+                            Ok(PluginCommand::RunCommand(
+                                command_to_run.try_into()?,
+                                env_variables,
+                            ))
+                        },
+                        None => Err("Malformed run command payload"),
+                    }
+                },
+                _ => Err("Mismatched payload for RunCommand"),
+            },
+            Some(CommandName::WebRequest) => match protobuf_plugin_command.payload {
+                Some(Payload::WebRequestPayload(web_request_payload)) => {
+                    let verb = match ProtobufHttpVerb::from_i32(web_request_payload.verb) {
+                        Some(verb) => verb.into(),
+                        None => return Err("Unrecognized http verb"),
+                    };
+                    let headers = web_request_payload
+                        .headers
+                        .into_iter()
+                        .map(|header| (header.name, header.value))
+                        .collect();
+                    Ok(PluginCommand::WebRequest(
+                        web_request_payload.url,
+                        verb,
+                        headers,
+                        web_request_payload.body,
+                    ))
+                },
+                _ => Err("Mismatched payload for WebRequest"),
+            },
+            Some(CommandName::ChangeHostFolder) => match protobuf_plugin_command.payload {
+                Some(Payload::ChangeHostFolderPayload(new_host_folder)) => Ok(
+                    PluginCommand::ChangeHostFolder(PathBuf::from(new_host_folder)),
+                ),
+                _ => Err("Mismatched payload for ChangeHostFolder"),
+            },
+            Some(CommandName::SubscribeToSystemState) => match protobuf_plugin_command.payload {
+                Some(Payload::SubscribeToSystemStatePayload(interval)) => {
+                    Ok(PluginCommand::SubscribeToSystemState(interval))
+                },
+                _ => Err("Mismatched payload for SubscribeToSystemState"),
+            },
+            Some(CommandName::SetInterval) => match protobuf_plugin_command.payload {
+                Some(Payload::SetIntervalPayload(set_interval_payload)) => {
+                    Ok(PluginCommand::SetInterval(set_interval_payload.seconds))
+                },
+                _ => Err("Mismatched payload for SetInterval"),
+            },
+            Some(CommandName::ClearInterval) => {
+                if protobuf_plugin_command.payload.is_some() {
+                    return Err("ClearInterval should not have a payload");
+                }
+                Ok(PluginCommand::ClearInterval)
+            },
+            Some(CommandName::CloseTabWithIndex) => match protobuf_plugin_command.payload {
+                Some(Payload::CloseTabWithIndexPayload(tab_index)) => {
+                    Ok(PluginCommand::CloseTabWithIndex(tab_index))
+                },
+                _ => Err("Mismatched payload for CloseTabWithIndex"),
+            },
+            Some(CommandName::DumpPaneContents) => match protobuf_plugin_command.payload {
+                Some(Payload::DumpPaneContentsPayload(dump_pane_contents_payload)) => {
+                    Ok(PluginCommand::DumpPaneContents(
+                        dump_pane_contents_payload.pane_id,
+                        dump_pane_contents_payload.full,
+                    ))
+                },
+                _ => Err("Mismatched payload for DumpPaneContents"),
+            },
+            Some(CommandName::WriteToPaneId) => match protobuf_plugin_command.payload {
+                Some(Payload::WriteToPaneIdPayload(write_to_pane_id_payload)) => {
+                    Ok(PluginCommand::WriteToPaneId(
+                        write_to_pane_id_payload.bytes_to_write,
+                        write_to_pane_id_payload.pane_id,
+                    ))
+                },
+                _ => Err("Mismatched payload for WriteToPaneId"),
+            },
+            Some(CommandName::WriteCharsToPaneId) => match protobuf_plugin_command.payload {
+                Some(Payload::WriteCharsToPaneIdPayload(write_chars_to_pane_id_payload)) => {
+                    Ok(PluginCommand::WriteCharsToPaneId(
+                        write_chars_to_pane_id_payload.chars_to_write,
+                        write_chars_to_pane_id_payload.pane_id,
+                    ))
+                },
+                _ => Err("Mismatched payload for WriteCharsToPaneId"),
+            },
             None => Err("Unrecognized plugin command"),
         }
     }
@@ -873,8 +1083,181 @@ impl TryFrom<PluginCommand> for ProtobufPluginCommand {
                     tab_position: switch_to_session.tab_position.map(|t| t as u32),
                     pane_id: switch_to_session.pane_id.map(|p| p.0),
                     pane_id_is_plugin: switch_to_session.pane_id.map(|p| p.1),
+                    layout: switch_to_session.layout,
+                })),
+            }),
+            PluginCommand::SubscribeToPaneOutput(terminal_pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::SubscribeToPaneOutput as i32,
+                payload: Some(Payload::SubscribeToPaneOutputPayload(terminal_pane_id)),
+            }),
+            PluginCommand::UnsubscribeFromPaneOutput(terminal_pane_id) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::UnsubscribeFromPaneOutput as i32,
+                    payload: Some(Payload::UnsubscribeFromPaneOutputPayload(terminal_pane_id)),
+                })
+            },
+            PluginCommand::PipeMessageToPlugin(message_to_plugin) => Ok(ProtobufPluginCommand {
+                name: CommandName::PipeMessageToPlugin as i32,
+                payload: Some(Payload::MessageToPluginPayload(MessageToPluginPayload {
+                    plugin_url: message_to_plugin.plugin_url,
+                    message_name: message_to_plugin.message_name,
+                    message_payload: message_to_plugin.message_payload,
+                    message_args: message_to_plugin
+                        .message_args
+                        .into_iter()
+                        .map(|(key, value)| ProtobufArg { key, value })
+                        .collect(),
+                })),
+            }),
+            PluginCommand::KillSessions(session_names) => Ok(ProtobufPluginCommand {
+                name: CommandName::KillSessions as i32,
+                payload: Some(Payload::KillSessionsPayload(KillSessionsPayload {
+                    session_names,
+                })),
+            }),
+            PluginCommand::DeleteDeadSession(dead_session_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::DeleteDeadSession as i32,
+                payload: Some(Payload::DeleteDeadSessionPayload(dead_session_name)),
+            }),
+            PluginCommand::RenameSession(new_session_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::RenameSession as i32,
+                payload: Some(Payload::RenameSessionPayload(new_session_name)),
+            }),
+            PluginCommand::MoveTabToIndex(tab_index, new_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::MoveTabToIndex as i32,
+                payload: Some(Payload::MoveTabToIndexPayload(MoveTabToIndexPayload {
+                    tab_index,
+                    new_index,
+                })),
+            }),
+            PluginCommand::OpenFileInPlace(file_to_open) => Ok(ProtobufPluginCommand {
+                name: CommandName::OpenFileInPlace as i32,
+                payload: Some(Payload::OpenFileInPlacePayload(OpenFilePayload {
+                    file_to_open: Some(file_to_open.try_into()?),
+                })),
+            }),
+            PluginCommand::OpenTerminalInPlace(cwd) => Ok(ProtobufPluginCommand {
+                name: CommandName::OpenTerminalInPlace as i32,
+                payload: Some(Payload::OpenTerminalInPlacePayload(OpenFilePayload {
+                    file_to_open: Some(cwd.try_into()?),
                 })),
             }),
+            PluginCommand::OpenCommandPaneInPlace(command_to_run) => Ok(ProtobufPluginCommand {
+                name: CommandName::OpenCommandPaneInPlace as i32,
+                payload: Some(Payload::OpenCommandPaneInPlacePayload(
+                    OpenCommandPanePayload {
+                        command_to_run: Some(command_to_run.try_into()?),
+                    },
+                )),
+            }),
+            PluginCommand::RebindKeys(keybinds, write_config_to_disk) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::RebindKeys as i32,
+                    payload: Some(Payload::RebindKeysPayload(RebindKeysPayload {
+                        keybinds,
+                        write_config_to_disk,
+                    })),
+                })
+            },
+            PluginCommand::BlockCliPipeInput(pipe_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::BlockCliPipeInput as i32,
+                payload: Some(Payload::BlockCliPipeInputPayload(pipe_name)),
+            }),
+            PluginCommand::UnblockCliPipeInput(pipe_name) => Ok(ProtobufPluginCommand {
+                name: CommandName::UnblockCliPipeInput as i32,
+                payload: Some(Payload::UnblockCliPipeInputPayload(pipe_name)),
+            }),
+            PluginCommand::RunCommand(command_to_run, env_variables) => Ok(ProtobufPluginCommand {
+                name: CommandName::RunCommand as i32,
+                payload: Some(Payload::RunCommandPayload(RunCommandPayload {
+                    command_to_run: Some(command_to_run.try_into()?),
+                    env_variables: env_variables
+                        .into_iter()
+                        .map(|(name, value)| EnvVariable { name, value })
+                        .collect(),
+                })),
+            }),
+            PluginCommand::WebRequest(url, verb, headers, body) => Ok(ProtobufPluginCommand {
+                name: CommandName::WebRequest as i32,
+                payload: Some(Payload::WebRequestPayload(WebRequestPayload {
+                    url,
+                    verb: ProtobufHttpVerb::from(verb) as i32,
+                    headers: headers
+                        .into_iter()
+                        .map(|(name, value)| ProtobufHeader { name, value })
+                        .collect(),
+                    body,
+                })),
+            }),
+            PluginCommand::ChangeHostFolder(new_host_folder) => Ok(ProtobufPluginCommand {
+                name: CommandName::ChangeHostFolder as i32,
+                payload: Some(Payload::ChangeHostFolderPayload(
+                    new_host_folder.display().to_string(),
+                )),
+            }),
+            PluginCommand::SubscribeToSystemState(interval) => Ok(ProtobufPluginCommand {
+                name: CommandName::SubscribeToSystemState as i32,
+                payload: Some(Payload::SubscribeToSystemStatePayload(interval)),
+            }),
+            PluginCommand::SetInterval(seconds) => Ok(ProtobufPluginCommand {
+                name: CommandName::SetInterval as i32,
+                payload: Some(Payload::SetIntervalPayload(SetTimeoutPayload { seconds })),
+            }),
+            PluginCommand::ClearInterval => Ok(ProtobufPluginCommand {
+                name: CommandName::ClearInterval as i32,
+                payload: None,
+            }),
+            PluginCommand::CloseTabWithIndex(tab_index) => Ok(ProtobufPluginCommand {
+                name: CommandName::CloseTabWithIndex as i32,
+                payload: Some(Payload::CloseTabWithIndexPayload(tab_index)),
+            }),
+            PluginCommand::DumpPaneContents(pane_id, full) => Ok(ProtobufPluginCommand {
+                name: CommandName::DumpPaneContents as i32,
+                payload: Some(Payload::DumpPaneContentsPayload(DumpPaneContentsPayload {
+                    pane_id,
+                    full,
+                })),
+            }),
+            PluginCommand::WriteToPaneId(bytes_to_write, pane_id) => Ok(ProtobufPluginCommand {
+                name: CommandName::WriteToPaneId as i32,
+                payload: Some(Payload::WriteToPaneIdPayload(WriteToPaneIdPayload {
+                    bytes_to_write,
+                    pane_id,
+                })),
+            }),
+            PluginCommand::WriteCharsToPaneId(chars_to_write, pane_id) => {
+                Ok(ProtobufPluginCommand {
+                    name: CommandName::WriteCharsToPaneId as i32,
+                    payload: Some(Payload::WriteCharsToPaneIdPayload(
+                        WriteCharsToPaneIdPayload {
+                            chars_to_write,
+                            pane_id,
+                        },
+                    )),
+                })
+            },
+        }
+    }
+}
+
+impl From<ProtobufHttpVerb> for HttpVerb {
+    fn from(protobuf_http_verb: ProtobufHttpVerb) -> Self {
+        match protobuf_http_verb {
+            ProtobufHttpVerb::Get => HttpVerb::Get,
+            ProtobufHttpVerb::Post => HttpVerb::Post,
+            ProtobufHttpVerb::Put => HttpVerb::Put,
+            ProtobufHttpVerb::Delete => HttpVerb::Delete,
+        }
+    }
+}
+
+impl From<HttpVerb> for ProtobufHttpVerb {
+    fn from(http_verb: HttpVerb) -> Self {
+        match http_verb {
+            HttpVerb::Get => ProtobufHttpVerb::Get,
+            HttpVerb::Post => ProtobufHttpVerb::Post,
+            HttpVerb::Put => ProtobufHttpVerb::Put,
+            HttpVerb::Delete => ProtobufHttpVerb::Delete,
         }
     }
 }
@@ -19,6 +19,12 @@ impl TryFrom<ProtobufPermissionType> for PermissionType {
                 Ok(PermissionType::OpenTerminalsOrPlugins)
             },
             ProtobufPermissionType::WriteToStdin => Ok(PermissionType::WriteToStdin),
+            ProtobufPermissionType::ReadPaneContents => Ok(PermissionType::ReadPaneContents),
+            ProtobufPermissionType::MessageOtherPlugins => Ok(PermissionType::MessageOtherPlugins),
+            ProtobufPermissionType::Reconfigure => Ok(PermissionType::Reconfigure),
+            ProtobufPermissionType::WebAccess => Ok(PermissionType::WebAccess),
+            ProtobufPermissionType::FullHdAccess => Ok(PermissionType::FullHdAccess),
+            ProtobufPermissionType::ReadSystemState => Ok(PermissionType::ReadSystemState),
         }
     }
 }
@@ -39,6 +45,12 @@ impl TryFrom<PermissionType> for ProtobufPermissionType {
                 Ok(ProtobufPermissionType::OpenTerminalsOrPlugins)
             },
             PermissionType::WriteToStdin => Ok(ProtobufPermissionType::WriteToStdin),
+            PermissionType::ReadPaneContents => Ok(ProtobufPermissionType::ReadPaneContents),
+            PermissionType::MessageOtherPlugins => Ok(ProtobufPermissionType::MessageOtherPlugins),
+            PermissionType::Reconfigure => Ok(ProtobufPermissionType::Reconfigure),
+            PermissionType::WebAccess => Ok(ProtobufPermissionType::WebAccess),
+            PermissionType::FullHdAccess => Ok(ProtobufPermissionType::FullHdAccess),
+            PermissionType::ReadSystemState => Ok(ProtobufPermissionType::ReadSystemState),
         }
     }
 }
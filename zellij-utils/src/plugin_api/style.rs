@@ -1,9 +1,10 @@
 use super::generated_api::api::style::{
     color::Payload as ProtobufColorPayload, Color as ProtobufColor, ColorType as ProtobufColorType,
     Palette as ProtobufPalette, RgbColorPayload as ProtobufRgbColorPayload, Style as ProtobufStyle,
+    StyleDeclaration as ProtobufStyleDeclaration, Styling as ProtobufStyling,
     ThemeHue as ProtobufThemeHue,
 };
-use crate::data::{Palette, PaletteColor, Style, ThemeHue};
+use crate::data::{Palette, PaletteColor, Style, StyleDeclaration, Styling, ThemeHue};
 use crate::errors::prelude::*;
 
 use std::convert::TryFrom;
@@ -11,13 +12,19 @@ use std::convert::TryFrom;
 impl TryFrom<ProtobufStyle> for Style {
     type Error = &'static str;
     fn try_from(protobuf_style: ProtobufStyle) -> Result<Self, &'static str> {
+        let colors: Palette = protobuf_style
+            .palette
+            .ok_or("malformed style payload")?
+            .try_into()?;
+        let styling = match protobuf_style.styling {
+            Some(styling) => styling.try_into()?,
+            None => Styling::from(colors),
+        };
         Ok(Style {
-            colors: protobuf_style
-                .palette
-                .ok_or("malformed style payload")?
-                .try_into()?,
+            colors,
             rounded_corners: protobuf_style.rounded_corners,
             hide_session_name: protobuf_style.hide_session_name,
+            styling,
         })
     }
 }
@@ -29,6 +36,109 @@ impl TryFrom<Style> for ProtobufStyle {
             palette: Some(style.colors.try_into()?),
             rounded_corners: style.rounded_corners,
             hide_session_name: style.hide_session_name,
+            styling: Some(style.styling.try_into()?),
+        })
+    }
+}
+
+impl TryFrom<ProtobufStyling> for Styling {
+    type Error = &'static str;
+    fn try_from(protobuf_styling: ProtobufStyling) -> Result<Self, &'static str> {
+        Ok(Styling {
+            text_unselected: protobuf_styling
+                .text_unselected
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+            text_selected: protobuf_styling
+                .text_selected
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+            ribbon_unselected: protobuf_styling
+                .ribbon_unselected
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+            ribbon_selected: protobuf_styling
+                .ribbon_selected
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+            frame_unselected: match protobuf_styling.frame_unselected {
+                Some(frame_unselected) => Some(frame_unselected.try_into()?),
+                None => None,
+            },
+            frame_selected: protobuf_styling
+                .frame_selected
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+            frame_highlight: protobuf_styling
+                .frame_highlight
+                .ok_or("malformed styling payload")?
+                .try_into()?,
+        })
+    }
+}
+
+impl TryFrom<Styling> for ProtobufStyling {
+    type Error = &'static str;
+    fn try_from(styling: Styling) -> Result<Self, &'static str> {
+        Ok(ProtobufStyling {
+            text_unselected: Some(styling.text_unselected.try_into()?),
+            text_selected: Some(styling.text_selected.try_into()?),
+            ribbon_unselected: Some(styling.ribbon_unselected.try_into()?),
+            ribbon_selected: Some(styling.ribbon_selected.try_into()?),
+            frame_unselected: match styling.frame_unselected {
+                Some(frame_unselected) => Some(frame_unselected.try_into()?),
+                None => None,
+            },
+            frame_selected: Some(styling.frame_selected.try_into()?),
+            frame_highlight: Some(styling.frame_highlight.try_into()?),
+        })
+    }
+}
+
+impl TryFrom<ProtobufStyleDeclaration> for StyleDeclaration {
+    type Error = &'static str;
+    fn try_from(
+        protobuf_style_declaration: ProtobufStyleDeclaration,
+    ) -> Result<Self, &'static str> {
+        Ok(StyleDeclaration {
+            base: protobuf_style_declaration
+                .base
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+            background: protobuf_style_declaration
+                .background
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+            emphasis_0: protobuf_style_declaration
+                .emphasis_0
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+            emphasis_1: protobuf_style_declaration
+                .emphasis_1
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+            emphasis_2: protobuf_style_declaration
+                .emphasis_2
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+            emphasis_3: protobuf_style_declaration
+                .emphasis_3
+                .ok_or("malformed style declaration payload")?
+                .try_into()?,
+        })
+    }
+}
+
+impl TryFrom<StyleDeclaration> for ProtobufStyleDeclaration {
+    type Error = &'static str;
+    fn try_from(style_declaration: StyleDeclaration) -> Result<Self, &'static str> {
+        Ok(ProtobufStyleDeclaration {
+            base: Some(style_declaration.base.try_into()?),
+            background: Some(style_declaration.background.try_into()?),
+            emphasis_0: Some(style_declaration.emphasis_0.try_into()?),
+            emphasis_1: Some(style_declaration.emphasis_1.try_into()?),
+            emphasis_2: Some(style_declaration.emphasis_2.try_into()?),
+            emphasis_3: Some(style_declaration.emphasis_3.try_into()?),
         })
     }
 }
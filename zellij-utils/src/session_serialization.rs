@@ -0,0 +1,680 @@
+//! Serializes the live state of a session (its tabs, their panes, the commands running in them and
+//! optionally their scrollback) into a KDL layout, so that the session can be resurrected later
+//! (eg. after a reboot) with `zellij attach --resurrect <session-name>`.
+use kdl::{KdlDocument, KdlEntry, KdlNode};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::consts::{ZELLIJ_SESSION_LAYOUT_CACHE_DIR, ZELLIJ_SESSION_LAYOUT_STAGING_DIR};
+use crate::input::layout::{
+    Layout, Run, RunPlugin, RunPluginLocation, SplitDirection, SplitSize, TiledPaneLayout,
+};
+use crate::pane_size::PaneGeom;
+
+pub const SESSION_LAYOUT_FILE_NAME: &str = "session-layout.kdl";
+
+#[derive(Default, Debug, Clone)]
+pub struct GlobalLayoutManifest {
+    pub session_name: String,
+    /// The layout the session was started with, used for the tabs opened after resurrection
+    pub default_layout: Box<Layout>,
+    pub tabs: Vec<(String, TabLayoutManifest)>,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct TabLayoutManifest {
+    pub tiled_panes: Vec<PaneLayoutManifest>,
+    pub floating_panes: Vec<PaneLayoutManifest>,
+    pub is_focused: bool,
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct PaneLayoutManifest {
+    pub geom: PaneGeom,
+    pub run: Option<Run>,
+    pub is_borderless: bool,
+    pub title: Option<String>,
+    pub is_focused: bool,
+    pub pane_contents: Option<String>,
+}
+
+/// The folder in which the layout (and pane contents) of the session with this name are saved
+pub fn session_layout_cache_dir(session_name: &str) -> PathBuf {
+    ZELLIJ_SESSION_LAYOUT_CACHE_DIR.join(session_name)
+}
+
+/// The folder in which the layout of the session with this name is written before it is moved
+/// into its cache dir
+pub fn session_layout_staging_dir(session_name: &str) -> PathBuf {
+    ZELLIJ_SESSION_LAYOUT_STAGING_DIR.join(session_name)
+}
+
+/// Replaces the contents of `layout_dir` with the session layout and its pane contents.
+///
+/// Everything is first written to `staging_dir` and only then renamed into place, so that a
+/// failed write (eg. a full disk) leaves the previously serialized session intact.
+pub fn write_session_layout(
+    layout_dir: &Path,
+    staging_dir: &Path,
+    session_layout: &str,
+    pane_contents: &BTreeMap<String, String>,
+) -> std::io::Result<()> {
+    let new_layout_dir = staging_dir.join("new");
+    let previous_layout_dir = staging_dir.join("previous");
+    // leftovers of a write that was interrupted
+    let _ = std::fs::remove_dir_all(staging_dir);
+    std::fs::create_dir_all(&new_layout_dir)?;
+    std::fs::write(
+        new_layout_dir.join(SESSION_LAYOUT_FILE_NAME),
+        session_layout,
+    )?;
+    for (file_name, contents) in pane_contents {
+        std::fs::write(new_layout_dir.join(file_name), contents)?;
+    }
+    if let Some(cache_dir) = layout_dir.parent() {
+        std::fs::create_dir_all(cache_dir)?;
+    }
+    // a folder can only be renamed over an empty one, so the previous layout is moved aside
+    let had_previous_layout = match std::fs::rename(layout_dir, &previous_layout_dir) {
+        Ok(()) => true,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => false,
+        Err(e) => return Err(e),
+    };
+    if let Err(e) = std::fs::rename(&new_layout_dir, layout_dir) {
+        if had_previous_layout {
+            let _ = std::fs::rename(&previous_layout_dir, layout_dir);
+        }
+        return Err(e);
+    }
+    let _ = std::fs::remove_dir_all(staging_dir);
+    Ok(())
+}
+
+/// Session names are used as file names in the socket and layout cache folders, so a name coming
+/// from outside (eg. a plugin) must be a single path component that cannot point outside of them
+pub fn is_valid_session_name(session_name: &str) -> bool {
+    let mut components = Path::new(session_name).components();
+    !session_name.contains('/')
+        && matches!(components.next(), Some(Component::Normal(_)))
+        && components.next().is_none()
+}
+
+/// The sessions that have a serialized layout but are not in `running_sessions`, along with the
+/// time since their layout was last serialized
+pub fn resurrectable_sessions(running_sessions: &[String]) -> Vec<(String, Duration)> {
+    let mut resurrectable_sessions = vec![];
+    if let Ok(session_dirs) = std::fs::read_dir(&*ZELLIJ_SESSION_LAYOUT_CACHE_DIR) {
+        for session_dir in session_dirs.flatten() {
+            let Ok(session_name) = session_dir.file_name().into_string() else {
+                continue;
+            };
+            if running_sessions.contains(&session_name) {
+                continue;
+            }
+            let layout_file = session_dir.path().join(SESSION_LAYOUT_FILE_NAME);
+            if let Ok(modified) = layout_file.metadata().and_then(|m| m.modified()) {
+                let age = SystemTime::now()
+                    .duration_since(modified)
+                    .unwrap_or_default();
+                resurrectable_sessions.push((session_name, age));
+            }
+        }
+    }
+    resurrectable_sessions.sort_by_key(|(_name, age)| *age);
+    resurrectable_sessions
+}
+
+/// Returns the serialized layout along with the pane contents that should be written next to it
+/// in `pane_contents_dir` (file name -> contents), the layout refers to them by their full path
+pub fn serialize_session_layout(
+    global_layout_manifest: GlobalLayoutManifest,
+    pane_contents_dir: &Path,
+) -> Result<(String, BTreeMap<String, String>), &'static str> {
+    let mut pane_contents = BTreeMap::new();
+    let mut layout_children = KdlDocument::new();
+    if let Some((new_tab_template, _floating_panes)) =
+        &global_layout_manifest.default_layout.template
+    {
+        let mut template_node = KdlNode::new("new_tab_template");
+        if new_tab_template.children_split_direction == SplitDirection::Vertical {
+            template_node.push(KdlEntry::new_prop("split_direction", "vertical"));
+        }
+        let mut template_children = KdlDocument::new();
+        for child in &new_tab_template.children {
+            template_children
+                .nodes_mut()
+                .push(serialize_tiled_pane_layout(child));
+        }
+        template_node.set_children(template_children);
+        layout_children.nodes_mut().push(template_node);
+    }
+    for (tab_name, tab_layout_manifest) in global_layout_manifest.tabs {
+        let mut tab_node = KdlNode::new("tab");
+        tab_node.push(KdlEntry::new_prop("name", tab_name));
+        if tab_layout_manifest.is_focused {
+            tab_node.push(KdlEntry::new_prop("focus", true));
+        }
+        let mut tab_children = KdlDocument::new();
+        let tiled_panes: Vec<&PaneLayoutManifest> =
+            tab_layout_manifest.tiled_panes.iter().collect();
+        if !tiled_panes.is_empty() {
+            let (split_direction, tiled_pane_nodes) =
+                serialize_tiled_panes(&tiled_panes, pane_contents_dir, &mut pane_contents)?;
+            if split_direction == Some(SplitDirection::Vertical) {
+                tab_node.push(KdlEntry::new_prop("split_direction", "vertical"));
+            }
+            tab_children.nodes_mut().extend(tiled_pane_nodes);
+        }
+        if !tab_layout_manifest.floating_panes.is_empty() {
+            let mut floating_panes_node = KdlNode::new("floating_panes");
+            let mut floating_panes_children = KdlDocument::new();
+            for floating_pane in &tab_layout_manifest.floating_panes {
+                let mut pane_node =
+                    serialize_pane_manifest(floating_pane, pane_contents_dir, &mut pane_contents);
+                pane_node.push(KdlEntry::new_prop("x", floating_pane.geom.x as i64));
+                pane_node.push(KdlEntry::new_prop("y", floating_pane.geom.y as i64));
+                pane_node.push(KdlEntry::new_prop(
+                    "width",
+                    floating_pane.geom.cols.as_usize() as i64,
+                ));
+                pane_node.push(KdlEntry::new_prop(
+                    "height",
+                    floating_pane.geom.rows.as_usize() as i64,
+                ));
+                floating_panes_children.nodes_mut().push(pane_node);
+            }
+            floating_panes_node.set_children(floating_panes_children);
+            tab_children.nodes_mut().push(floating_panes_node);
+        }
+        tab_node.set_children(tab_children);
+        layout_children.nodes_mut().push(tab_node);
+    }
+    let mut layout_node = KdlNode::new("layout");
+    layout_node.set_children(layout_children);
+    let mut kdl_document = KdlDocument::new();
+    kdl_document.nodes_mut().push(layout_node);
+    kdl_document.fmt();
+    Ok((kdl_document.to_string(), pane_contents))
+}
+
+// Recursively finds the splits between the given panes (which should tile a rectangle) and
+// returns the direction of the outermost split along with the nodes on either side of it
+fn serialize_tiled_panes(
+    panes: &[&PaneLayoutManifest],
+    pane_contents_dir: &Path,
+    pane_contents: &mut BTreeMap<String, String>,
+) -> Result<(Option<SplitDirection>, Vec<KdlNode>), &'static str> {
+    if panes.len() == 1 {
+        return Ok((
+            None,
+            vec![serialize_pane_manifest(
+                panes[0],
+                pane_contents_dir,
+                pane_contents,
+            )],
+        ));
+    }
+    let (split_direction, groups) = split_panes(panes, SplitDirection::Vertical)
+        .map(|groups| (SplitDirection::Vertical, groups))
+        .or_else(|| {
+            split_panes(panes, SplitDirection::Horizontal)
+                .map(|groups| (SplitDirection::Horizontal, groups))
+        })
+        .ok_or("Failed to find a split between tiled panes")?;
+    let extent_of = |group: &[&PaneLayoutManifest]| {
+        let (start, end) = group_bounds(group, split_direction);
+        end - start
+    };
+    let total_extent = extent_of(panes);
+    let is_fixed = |group: &[&PaneLayoutManifest]| {
+        group.len() == 1
+            && match split_direction {
+                SplitDirection::Vertical => group[0].geom.cols.is_fixed(),
+                SplitDirection::Horizontal => group[0].geom.rows.is_fixed(),
+            }
+    };
+    // this pane will take up whatever space is left after the others have been given their
+    // size, so that rounding errors do not accumulate
+    let flexible_group_index = groups
+        .iter()
+        .rposition(|group| !is_fixed(group))
+        .unwrap_or(groups.len() - 1);
+    let mut nodes = vec![];
+    for (i, group) in groups.iter().enumerate() {
+        let mut node = if group.len() == 1 {
+            serialize_pane_manifest(group[0], pane_contents_dir, pane_contents)
+        } else {
+            let (child_split_direction, children) =
+                serialize_tiled_panes(group, pane_contents_dir, pane_contents)?;
+            let mut node = KdlNode::new("pane");
+            if child_split_direction == Some(SplitDirection::Vertical) {
+                node.push(KdlEntry::new_prop("split_direction", "vertical"));
+            }
+            let mut node_children = KdlDocument::new();
+            node_children.nodes_mut().extend(children);
+            node.set_children(node_children);
+            node
+        };
+        if i != flexible_group_index {
+            let extent = extent_of(group);
+            let size = if is_fixed(group) {
+                KdlEntry::new_prop("size", extent as i64)
+            } else {
+                let percent = ((extent as f64 / total_extent as f64) * 100.0).round() as usize;
+                KdlEntry::new_prop("size", format!("{}%", percent.max(1)))
+            };
+            node.entries_mut().insert(0, size);
+        }
+        nodes.push(node);
+    }
+    Ok((Some(split_direction), nodes))
+}
+
+// Groups the panes by the lines along which they can be split in this direction without cutting
+// through any of them (eg. columns for the vertical direction), returns None if there are none
+fn split_panes<'a>(
+    panes: &[&'a PaneLayoutManifest],
+    split_direction: SplitDirection,
+) -> Option<Vec<Vec<&'a PaneLayoutManifest>>> {
+    let bounds = |pane: &PaneLayoutManifest| match split_direction {
+        SplitDirection::Vertical => (pane.geom.x, pane.geom.x + pane.geom.cols.as_usize()),
+        SplitDirection::Horizontal => (pane.geom.y, pane.geom.y + pane.geom.rows.as_usize()),
+    };
+    let (first_start, _) = group_bounds(panes, split_direction);
+    let mut cuts: Vec<usize> = panes
+        .iter()
+        .map(|pane| bounds(pane).0)
+        .filter(|start| *start != first_start)
+        .filter(|cut| {
+            !panes.iter().any(|pane| {
+                let (start, end) = bounds(pane);
+                start < *cut && *cut < end
+            })
+        })
+        .collect();
+    if cuts.is_empty() {
+        return None;
+    }
+    cuts.sort_unstable();
+    cuts.dedup();
+    let mut groups = vec![vec![]; cuts.len() + 1];
+    for pane in panes {
+        let (start, _) = bounds(pane);
+        let group_index = cuts.iter().filter(|cut| **cut <= start).count();
+        groups[group_index].push(*pane);
+    }
+    Some(groups)
+}
+
+fn group_bounds(panes: &[&PaneLayoutManifest], split_direction: SplitDirection) -> (usize, usize) {
+    let bounds = panes.iter().map(|pane| match split_direction {
+        SplitDirection::Vertical => (pane.geom.x, pane.geom.x + pane.geom.cols.as_usize()),
+        SplitDirection::Horizontal => (pane.geom.y, pane.geom.y + pane.geom.rows.as_usize()),
+    });
+    let start = bounds.clone().map(|(start, _)| start).min().unwrap_or(0);
+    let end = bounds.map(|(_, end)| end).max().unwrap_or(0);
+    (start, end)
+}
+
+fn serialize_pane_manifest(
+    pane: &PaneLayoutManifest,
+    pane_contents_dir: &Path,
+    pane_contents: &mut BTreeMap<String, String>,
+) -> KdlNode {
+    let mut node = KdlNode::new("pane");
+    if let Some(run) = &pane.run {
+        serialize_run(&mut node, run);
+    }
+    if pane.is_borderless {
+        node.push(KdlEntry::new_prop("borderless", true));
+    }
+    if let Some(title) = &pane.title {
+        node.push(KdlEntry::new_prop("name", title.clone()));
+    }
+    if pane.is_focused {
+        node.push(KdlEntry::new_prop("focus", true));
+    }
+    if let Some(contents) = &pane.pane_contents {
+        let file_name = format!("pane_contents_{}.txt", pane_contents.len() + 1);
+        let file_path = pane_contents_dir.join(&file_name);
+        node.push(KdlEntry::new_prop(
+            "contents_file",
+            file_path.display().to_string(),
+        ));
+        pane_contents.insert(file_name, contents.clone());
+    }
+    node
+}
+
+fn serialize_run(node: &mut KdlNode, run: &Run) {
+    match run {
+        Run::Command(run_command) => {
+            node.push(KdlEntry::new_prop(
+                "command",
+                run_command.command.display().to_string(),
+            ));
+            if let Some(cwd) = &run_command.cwd {
+                node.push(KdlEntry::new_prop("cwd", cwd.display().to_string()));
+            }
+            if !run_command.hold_on_close {
+                node.push(KdlEntry::new_prop("close_on_exit", true));
+            }
+            if run_command.hold_on_start {
+                node.push(KdlEntry::new_prop("start_suspended", true));
+            }
+            if !run_command.args.is_empty() {
+                let mut args = KdlNode::new("args");
+                for arg in &run_command.args {
+                    args.push(arg.clone());
+                }
+                node.ensure_children().nodes_mut().push(args);
+            }
+            if !run_command.env.is_empty() {
+                let mut env = KdlNode::new("env");
+                for (env_var_name, env_var_value) in &run_command.env {
+                    let mut env_var = KdlNode::new(env_var_name.as_str());
+                    env_var.push(env_var_value.clone());
+                    env.ensure_children().nodes_mut().push(env_var);
+                }
+                node.ensure_children().nodes_mut().push(env);
+            }
+        },
+        Run::EditFile(file_to_edit, _line_number, cwd) => {
+            node.push(KdlEntry::new_prop(
+                "edit",
+                file_to_edit.display().to_string(),
+            ));
+            if let Some(cwd) = cwd {
+                node.push(KdlEntry::new_prop("cwd", cwd.display().to_string()));
+            }
+        },
+        Run::Cwd(cwd) => {
+            node.push(KdlEntry::new_prop("cwd", cwd.display().to_string()));
+        },
+        Run::Plugin(run_plugin) => {
+            node.ensure_children()
+                .nodes_mut()
+                .push(serialize_run_plugin(run_plugin));
+        },
+    }
+}
+
+fn serialize_run_plugin(run_plugin: &RunPlugin) -> KdlNode {
+    let mut plugin_node = KdlNode::new("plugin");
+    let location = match &run_plugin.location {
+        RunPluginLocation::File(path) => format!("file:{}", path.display()),
+        RunPluginLocation::Zellij(tag) => format!("zellij:{tag}"),
+        RunPluginLocation::Remote(url) => url.clone(),
+    };
+    plugin_node.push(KdlEntry::new_prop("location", location));
+    if !run_plugin.configuration.inner().is_empty() {
+        let mut configuration = KdlDocument::new();
+        for (key, value) in run_plugin.configuration.inner() {
+            let mut configuration_node = KdlNode::new(key.as_str());
+            configuration_node.push(value.clone());
+            configuration.nodes_mut().push(configuration_node);
+        }
+        plugin_node.set_children(configuration);
+    }
+    plugin_node
+}
+
+// the template is a regular layout rather than a snapshot of live panes, so it is serialized as is
+fn serialize_tiled_pane_layout(tiled_pane_layout: &TiledPaneLayout) -> KdlNode {
+    let mut node = KdlNode::new("pane");
+    match tiled_pane_layout.split_size {
+        Some(SplitSize::Fixed(size)) => node.push(KdlEntry::new_prop("size", size as i64)),
+        Some(SplitSize::Percent(percent)) => {
+            node.push(KdlEntry::new_prop("size", format!("{percent}%")))
+        },
+        None => {},
+    }
+    if let Some(run) = &tiled_pane_layout.run {
+        serialize_run(&mut node, run);
+    }
+    if tiled_pane_layout.borderless {
+        node.push(KdlEntry::new_prop("borderless", true));
+    }
+    if let Some(name) = &tiled_pane_layout.name {
+        node.push(KdlEntry::new_prop("name", name.clone()));
+    }
+    if !tiled_pane_layout.children.is_empty() {
+        if tiled_pane_layout.children_split_direction == SplitDirection::Vertical {
+            node.push(KdlEntry::new_prop("split_direction", "vertical"));
+        }
+        if tiled_pane_layout.children_are_stacked {
+            node.push(KdlEntry::new_prop("stacked", true));
+        }
+        let children = node.ensure_children();
+        for child in &tiled_pane_layout.children {
+            children
+                .nodes_mut()
+                .push(serialize_tiled_pane_layout(child));
+        }
+    }
+    node
+}
+
+#[cfg(test)]
+fn pane_geom(x: usize, y: usize, cols: usize, rows: usize, fixed_rows: bool) -> PaneGeom {
+    use crate::pane_size::Dimension;
+    let mut cols_dimension = Dimension::percent(50.0);
+    cols_dimension.set_inner(cols);
+    let rows_dimension = if fixed_rows {
+        Dimension::fixed(rows)
+    } else {
+        let mut rows_dimension = Dimension::percent(50.0);
+        rows_dimension.set_inner(rows);
+        rows_dimension
+    };
+    PaneGeom {
+        x,
+        y,
+        cols: cols_dimension,
+        rows: rows_dimension,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn serialize_session_layout_with_tiled_and_floating_panes() {
+    use crate::data::PluginTag;
+    use crate::input::command::RunCommand;
+    use crate::input::layout::PluginUserConfiguration;
+    let tab_bar = Run::Plugin(RunPlugin {
+        location: RunPluginLocation::Zellij(PluginTag::new("tab-bar")),
+        configuration: PluginUserConfiguration::new(BTreeMap::new()),
+        _allow_exec_host_cmd: false,
+    });
+    let default_layout = Layout {
+        template: Some((
+            TiledPaneLayout {
+                children: vec![
+                    TiledPaneLayout {
+                        split_size: Some(SplitSize::Fixed(1)),
+                        borderless: true,
+                        run: Some(tab_bar.clone()),
+                        ..Default::default()
+                    },
+                    TiledPaneLayout::default(),
+                ],
+                ..Default::default()
+            },
+            vec![],
+        )),
+        ..Default::default()
+    };
+    let tab = TabLayoutManifest {
+        tiled_panes: vec![
+            PaneLayoutManifest {
+                geom: pane_geom(0, 0, 100, 1, true),
+                run: Some(tab_bar),
+                is_borderless: true,
+                ..Default::default()
+            },
+            PaneLayoutManifest {
+                geom: pane_geom(0, 1, 50, 29, false),
+                run: Some(Run::Cwd(PathBuf::from("/tmp"))),
+                ..Default::default()
+            },
+            PaneLayoutManifest {
+                geom: pane_geom(50, 1, 50, 15, false),
+                run: Some(Run::Command(RunCommand {
+                    command: PathBuf::from("htop"),
+                    args: vec!["-d".to_owned(), "10".to_owned()],
+                    cwd: Some(PathBuf::from("/home")),
+                    hold_on_close: true,
+                    hold_on_start: true,
+                    ..Default::default()
+                })),
+                title: Some("my htop".to_owned()),
+                is_focused: true,
+                ..Default::default()
+            },
+            PaneLayoutManifest {
+                geom: pane_geom(50, 16, 50, 14, false),
+                ..Default::default()
+            },
+        ],
+        floating_panes: vec![PaneLayoutManifest {
+            geom: pane_geom(10, 5, 40, 10, false),
+            run: Some(Run::EditFile(PathBuf::from("/tmp/foo.rs"), None, None)),
+            ..Default::default()
+        }],
+        is_focused: true,
+    };
+    let global_layout_manifest = GlobalLayoutManifest {
+        session_name: "my-session".to_owned(),
+        default_layout: Box::new(default_layout),
+        tabs: vec![
+            ("Tab #1".to_owned(), tab),
+            ("Tab #2".to_owned(), TabLayoutManifest::default()),
+        ],
+    };
+    let (serialized_layout, pane_contents) =
+        serialize_session_layout(global_layout_manifest, Path::new("/tmp/my-session")).unwrap();
+    assert!(pane_contents.is_empty());
+    let layout = Layout::from_kdl(&serialized_layout, "layout.kdl".into(), None, None);
+    assert!(layout.is_ok(), "serialized layout can be parsed back");
+    insta::assert_snapshot!(serialized_layout);
+}
+
+#[test]
+fn serialize_session_layout_with_pane_contents() {
+    let tab = TabLayoutManifest {
+        tiled_panes: vec![
+            PaneLayoutManifest {
+                geom: pane_geom(0, 0, 50, 30, false),
+                pane_contents: Some("$ ls\nfoo bar".to_owned()),
+                ..Default::default()
+            },
+            PaneLayoutManifest {
+                geom: pane_geom(50, 0, 50, 30, false),
+                pane_contents: Some("$ pwd\n/tmp".to_owned()),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+    let global_layout_manifest = GlobalLayoutManifest {
+        tabs: vec![("Tab #1".to_owned(), tab)],
+        ..Default::default()
+    };
+    let (serialized_layout, pane_contents) =
+        serialize_session_layout(global_layout_manifest, Path::new("/tmp/my-session")).unwrap();
+    assert_eq!(
+        pane_contents.get("pane_contents_1.txt").map(|c| c.as_str()),
+        Some("$ ls\nfoo bar")
+    );
+    assert_eq!(
+        pane_contents.get("pane_contents_2.txt").map(|c| c.as_str()),
+        Some("$ pwd\n/tmp")
+    );
+    assert!(serialized_layout.contains("contents_file=\"/tmp/my-session/pane_contents_1.txt\""));
+    assert!(serialized_layout.contains("contents_file=\"/tmp/my-session/pane_contents_2.txt\""));
+}
+
+#[test]
+fn serialize_session_layout_with_command_pane_env() {
+    use crate::input::command::RunCommand;
+    let mut env = BTreeMap::new();
+    env.insert("RUST_LOG".to_owned(), "debug".to_owned());
+    let tab = TabLayoutManifest {
+        tiled_panes: vec![PaneLayoutManifest {
+            geom: pane_geom(0, 0, 100, 30, false),
+            run: Some(Run::Command(RunCommand {
+                command: PathBuf::from("cargo"),
+                args: vec!["run".to_owned()],
+                env,
+                hold_on_close: true,
+                ..Default::default()
+            })),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let global_layout_manifest = GlobalLayoutManifest {
+        tabs: vec![("Tab #1".to_owned(), tab)],
+        ..Default::default()
+    };
+    let (serialized_layout, _pane_contents) =
+        serialize_session_layout(global_layout_manifest, Path::new("/tmp/my-session")).unwrap();
+    assert!(serialized_layout.contains("RUST_LOG \"debug\""));
+    let layout = Layout::from_kdl(&serialized_layout, "layout.kdl".into(), None, None);
+    assert!(layout.is_ok(), "serialized layout can be parsed back");
+}
+
+#[test]
+fn only_single_path_components_are_valid_session_names() {
+    assert!(is_valid_session_name("my-session"));
+    assert!(is_valid_session_name("..hidden"));
+    for invalid_session_name in ["", ".", "..", "../../x", "a/b", "a/", "/tmp"] {
+        assert!(
+            !is_valid_session_name(invalid_session_name),
+            "{:?} should be rejected",
+            invalid_session_name
+        );
+    }
+}
+
+#[test]
+fn write_session_layout_replaces_the_previous_layout() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let layout_dir = cache_dir.path().join("session_layouts").join("my-session");
+    let staging_dir = cache_dir.path().join("staging").join("my-session");
+    let first_pane_contents = BTreeMap::from([("pane-1".to_owned(), "foo".to_owned())]);
+    write_session_layout(&layout_dir, &staging_dir, "layout 1", &first_pane_contents).unwrap();
+    let second_pane_contents = BTreeMap::from([("pane-2".to_owned(), "bar".to_owned())]);
+    write_session_layout(&layout_dir, &staging_dir, "layout 2", &second_pane_contents).unwrap();
+    assert_eq!(
+        std::fs::read_to_string(layout_dir.join(SESSION_LAYOUT_FILE_NAME)).unwrap(),
+        "layout 2"
+    );
+    assert_eq!(
+        std::fs::read_to_string(layout_dir.join("pane-2")).unwrap(),
+        "bar"
+    );
+    assert!(
+        !layout_dir.join("pane-1").exists(),
+        "contents of the previous layout were removed"
+    );
+    assert!(!staging_dir.exists(), "staging dir was cleaned up");
+}
+
+#[test]
+fn failed_write_session_layout_keeps_the_previous_layout() {
+    let cache_dir = tempfile::tempdir().unwrap();
+    let layout_dir = cache_dir.path().join("session_layouts").join("my-session");
+    let staging_dir = cache_dir.path().join("staging").join("my-session");
+    write_session_layout(&layout_dir, &staging_dir, "layout 1", &BTreeMap::new()).unwrap();
+    // a file name that cannot be created in the staging dir
+    let bad_pane_contents = BTreeMap::from([("no/such/dir".to_owned(), "foo".to_owned())]);
+    assert!(
+        write_session_layout(&layout_dir, &staging_dir, "layout 2", &bad_pane_contents).is_err()
+    );
+    assert_eq!(
+        std::fs::read_to_string(layout_dir.join(SESSION_LAYOUT_FILE_NAME)).unwrap(),
+        "layout 1"
+    );
+}
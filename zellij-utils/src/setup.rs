@@ -3,6 +3,7 @@ use crate::consts::ASSET_MAP;
 use crate::input::theme::Themes;
 use crate::{
     cli::{CliArgs, Command},
+    config_check,
     consts::{
         FEATURES, SYSTEM_DEFAULT_CONFIG_DIR, SYSTEM_DEFAULT_DATA_DIR_PREFIX, VERSION,
         ZELLIJ_DEFAULT_THEMES, ZELLIJ_PROJ_DIR,
@@ -13,6 +14,7 @@ use crate::{
         layout::Layout,
         options::Options,
     },
+    tmux_converter,
 };
 use clap::{Args, IntoApp};
 use clap_complete::Shell;
@@ -83,6 +85,18 @@ fn get_default_themes() -> Themes {
     Themes::default()
 }
 
+/// The themes of a configuration along with the ones zellij ships with and those in `theme_dir`
+pub fn load_themes(
+    config_themes: Themes,
+    theme_dir: Option<PathBuf>,
+) -> Result<Themes, ConfigError> {
+    let mut themes = config_themes.merge(get_default_themes());
+    if let Some(theme_dir) = theme_dir {
+        themes = themes.merge(Themes::from_dir(theme_dir)?);
+    }
+    Ok(themes)
+}
+
 pub fn xdg_config_dir() -> PathBuf {
     ZELLIJ_PROJ_DIR.config_dir().to_owned()
 }
@@ -200,27 +214,41 @@ pub fn dump_default_config() -> std::io::Result<()> {
     dump_asset(DEFAULT_CONFIG)
 }
 
+/// The names of the layouts bundled with zellij, as accepted by `--layout` and `--dump-layout`
+pub const BUILTIN_LAYOUT_NAMES: &[&str] = &["default", "compact", "strider", "disable-status-bar"];
+
 pub fn dump_specified_layout(layout: &str) -> std::io::Result<()> {
-    match layout {
-        "strider" => dump_asset(STRIDER_LAYOUT),
-        "default" => dump_asset(DEFAULT_LAYOUT),
-        "compact" => dump_asset(COMPACT_BAR_LAYOUT),
-        "disable-status" => dump_asset(NO_STATUS_LAYOUT),
-        not_found => Err(std::io::Error::new(
+    // "disable-status" is the name this layout used to be dumped by
+    let layout = if layout == "disable-status" {
+        "disable-status-bar"
+    } else {
+        layout
+    };
+    match Layout::stringified_from_default_assets(Path::new(layout)) {
+        Ok((_layout_path, stringified_layout, _swap_layout)) => {
+            dump_asset(stringified_layout.as_bytes())
+        },
+        Err(_) => Err(std::io::Error::new(
             std::io::ErrorKind::Other,
-            format!("Layout: {} not found", not_found),
+            format!(
+                "Layout: {} not found, available layouts: {}",
+                layout,
+                BUILTIN_LAYOUT_NAMES.join(", ")
+            ),
         )),
     }
 }
 
 pub fn dump_specified_swap_layout(swap_layout: &str) -> std::io::Result<()> {
-    match swap_layout {
-        "strider" => dump_asset(STRIDER_SWAP_LAYOUT),
-        "default" => dump_asset(DEFAULT_SWAP_LAYOUT),
-        "compact" => dump_asset(COMPACT_BAR_SWAP_LAYOUT),
-        not_found => Err(std::io::Error::new(
+    match Layout::stringified_from_default_assets(Path::new(swap_layout)) {
+        Ok((
+            _layout_path,
+            _stringified_layout,
+            Some((_swap_layout_path, stringified_swap_layout)),
+        )) => dump_asset(stringified_swap_layout.as_bytes()),
+        _ => Err(std::io::Error::new(
             std::io::ErrorKind::Other,
-            format!("Swap Layout not found for: {}", not_found),
+            format!("Swap Layout not found for: {}", swap_layout),
         )),
     }
 }
@@ -276,7 +304,8 @@ pub struct Setup {
     #[clap(long, value_parser)]
     pub check: bool,
 
-    /// Dump the specified layout file to stdout
+    /// Dump the specified builtin layout file to stdout (use `zellij action dump-layout` to dump
+    /// the layout of the current session)
     #[clap(long, value_parser)]
     pub dump_layout: Option<String>,
 
@@ -302,6 +331,31 @@ pub struct Setup {
     /// Generates auto-start script for the specified shell
     #[clap(long, value_name = "SHELL", value_parser)]
     pub generate_auto_start: Option<String>,
+
+    /// Converts a tmux configuration file (eg. ~/.tmux.conf) or a GNU screen one (whose name ends
+    /// with screenrc, eg. ~/.screenrc) to a zellij configuration and dumps it to stdout,
+    /// reporting what could not be converted
+    #[clap(long, value_name = "TMUX_OR_SCREEN_CONFIG", value_parser)]
+    pub convert_config: Option<PathBuf>,
+
+    /// Converts a tmuxinator project file to a zellij layout and dumps it to stdout, reporting
+    /// what could not be converted
+    #[clap(long, value_name = "TMUXINATOR_PROJECT", value_parser)]
+    pub convert_layout: Option<PathBuf>,
+
+    /// Lists the names of the themes zellij ships with and of those in the configuration and the
+    /// theme directory, one per line (switch to one with `zellij action switch-theme`)
+    #[clap(long, value_parser)]
+    pub list_themes: bool,
+
+    /// Lists the names of the running sessions, one per line, for the shell completions
+    #[clap(long, value_parser, hide = true)]
+    pub list_sessions_for_completion: bool,
+
+    /// Lists the names of the builtin layouts and those in the layout directory, one per line,
+    /// for the shell completions
+    #[clap(long, value_parser, hide = true)]
+    pub list_layouts_for_completion: bool,
 }
 
 impl Setup {
@@ -330,19 +384,15 @@ impl Setup {
             None => config.options.clone(),
         };
 
-        config.themes = config.themes.merge(get_default_themes());
-
         let user_theme_dir = config_options.theme_dir.clone().or_else(|| {
             get_theme_dir(cli_args.config_dir.clone().or_else(find_default_config_dir))
                 .filter(|dir| dir.exists())
         });
-        if let Some(user_theme_dir) = user_theme_dir {
-            config.themes = config.themes.merge(Themes::from_dir(user_theme_dir)?);
-        }
+        config.themes = load_themes(config.themes, user_theme_dir)?;
 
         if let Some(Command::Setup(ref setup)) = &cli_args.command {
             setup
-                .from_cli_with_options(cli_args, &config_options)
+                .from_cli_with_options(cli_args, &config, &config_options)
                 .map_or_else(
                     |e| {
                         eprintln!("{:?}", e);
@@ -385,13 +435,66 @@ impl Setup {
             std::process::exit(0);
         }
 
+        if let Some(config_path) = &self.convert_config {
+            let config = std::fs::read_to_string(config_path)
+                .with_context(|| format!("failed to read {}", config_path.display()))?;
+            let is_screen_config = config_path
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().ends_with("screenrc"))
+                .unwrap_or(false);
+            let conversion = if is_screen_config {
+                tmux_converter::convert_screen_config(&config)
+            } else {
+                tmux_converter::convert_tmux_config(&config)
+            };
+            Setup::print_conversion(conversion);
+            std::process::exit(0);
+        }
+
+        if let Some(tmuxinator_project) = &self.convert_layout {
+            let tmuxinator_project = std::fs::read_to_string(tmuxinator_project)
+                .with_context(|| format!("failed to read {}", tmuxinator_project.display()))?;
+            let conversion = tmux_converter::convert_tmuxinator_project(&tmuxinator_project)
+                .map_err(|e| anyhow!(e))?;
+            Setup::print_conversion(conversion);
+            std::process::exit(0);
+        }
+
         Ok(())
     }
 
+    fn print_conversion(conversion: tmux_converter::Conversion) {
+        println!("{}", conversion.kdl);
+        if !conversion.unconverted.is_empty() {
+            eprintln!("The following could not be converted and was left out:");
+            for unconverted in conversion.unconverted {
+                eprintln!("  {}", unconverted);
+            }
+        }
+    }
+
     /// Checks the merged configuration
-    pub fn from_cli_with_options(&self, opts: &CliArgs, config_options: &Options) -> Result<()> {
+    pub fn from_cli_with_options(
+        &self,
+        opts: &CliArgs,
+        config: &Config,
+        config_options: &Options,
+    ) -> Result<()> {
         if self.check {
-            Setup::check_defaults_config(opts, config_options)?;
+            let well_defined = Setup::check_defaults_config(opts, config_options)?;
+            std::process::exit(if well_defined { 0 } else { 1 });
+        }
+
+        if self.list_layouts_for_completion {
+            Setup::list_layouts_for_completion(opts, config_options)?;
+            std::process::exit(0);
+        }
+
+        if self.list_themes {
+            let mut out = std::io::stdout();
+            for theme_name in config.themes.theme_names() {
+                writeln!(out, "{}", theme_name)?;
+            }
             std::process::exit(0);
         }
 
@@ -410,7 +513,46 @@ impl Setup {
         Ok(())
     }
 
-    pub fn check_defaults_config(opts: &CliArgs, config_options: &Options) -> std::io::Result<()> {
+    fn list_layouts_for_completion(
+        opts: &CliArgs,
+        config_options: &Options,
+    ) -> std::io::Result<()> {
+        let config_dir = opts.config_dir.clone().or_else(find_default_config_dir);
+        let layout_dir = config_options
+            .layout_dir
+            .clone()
+            .or_else(|| get_layout_dir(config_dir));
+        let mut layout_names: Vec<String> = BUILTIN_LAYOUT_NAMES
+            .iter()
+            .map(|name| name.to_string())
+            .collect();
+        if let Some(Ok(entries)) = layout_dir.map(std::fs::read_dir) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                // swap layouts are loaded along with the layout they share a name with
+                if let Some(layout_name) = file_name
+                    .strip_suffix(".kdl")
+                    .filter(|name| !name.ends_with(".swap"))
+                {
+                    layout_names.push(layout_name.to_owned());
+                }
+            }
+        }
+        layout_names.sort();
+        layout_names.dedup();
+        let mut out = std::io::stdout();
+        for layout_name in layout_names {
+            writeln!(out, "{}", layout_name)?;
+        }
+        Ok(())
+    }
+
+    /// Prints the setup zellij would run with, the configuration file and layouts in it are
+    /// checked for problems, returns whether none of them has errors
+    pub fn check_defaults_config(
+        opts: &CliArgs,
+        config_options: &Options,
+    ) -> std::io::Result<bool> {
         let data_dir = opts.data_dir.clone().unwrap_or_else(get_default_data_dir);
         let config_dir = opts.config_dir.clone().or_else(find_default_config_dir);
         let plugin_dir = data_dir.join("plugins");
@@ -431,6 +573,7 @@ impl Setup {
         let hyperlink_end = "\u{1b}]8;;\u{1b}\\";
 
         let mut message = String::new();
+        let mut well_defined = true;
 
         writeln!(&mut message, "[Version]: {:?}", VERSION).unwrap();
         if let Some(config_dir) = config_dir {
@@ -456,14 +599,33 @@ impl Setup {
                 config_file
             )
             .unwrap();
-            match Config::from_path(&config_file, None) {
-                Ok(_) => message.push_str("[CONFIG FILE]: Well defined.\n"),
-                Err(e) => writeln!(
-                    &mut message,
-                    "[CONFIG ERROR]: {}. \n By default, zellij loads default configuration",
-                    e
-                )
-                .unwrap(),
+            match std::fs::read_to_string(&config_file) {
+                Ok(raw_config) => {
+                    let diagnostics =
+                        config_check::check_config(&raw_config, layout_dir.as_deref(), &plugin_dir);
+                    if diagnostics.is_empty() {
+                        message.push_str("[CONFIG FILE]: Well defined.\n");
+                    } else {
+                        message.push_str("[CONFIG FILE]: Problems found:\n");
+                        for diagnostic in &diagnostics {
+                            writeln!(&mut message, " {}:{}", config_file.display(), diagnostic)
+                                .unwrap();
+                        }
+                        if diagnostics.iter().any(|d| d.is_error()) {
+                            well_defined = false;
+                            message.push_str(" By default, zellij loads default configuration\n");
+                        }
+                    }
+                },
+                Err(e) => {
+                    well_defined = false;
+                    writeln!(
+                        &mut message,
+                        "[CONFIG ERROR]: {}. \n By default, zellij loads default configuration",
+                        e
+                    )
+                    .unwrap()
+                },
             }
         } else {
             message.push_str("[CONFIG FILE]: Not Found\n");
@@ -490,6 +652,28 @@ impl Setup {
         }
         if let Some(layout_dir) = layout_dir {
             writeln!(&mut message, "[LAYOUT DIR]: {:?}", layout_dir).unwrap();
+            let mut layout_files: Vec<PathBuf> = std::fs::read_dir(&layout_dir)
+                .map(|entries| entries.flatten().map(|entry| entry.path()).collect())
+                .unwrap_or_default();
+            layout_files.sort();
+            for layout_file in layout_files {
+                let file_name = layout_file.to_string_lossy().to_string();
+                // swap layouts are checked along with the layout they share a name with
+                if !file_name.ends_with(".kdl") || file_name.ends_with(".swap.kdl") {
+                    continue;
+                }
+                let diagnostics = match std::fs::read_to_string(&layout_file) {
+                    Ok(raw_layout) => config_check::check_layout(&raw_layout, &layout_file),
+                    Err(e) => {
+                        writeln!(&mut message, " {}: {}", file_name, e).unwrap();
+                        continue;
+                    },
+                };
+                for diagnostic in diagnostics {
+                    well_defined &= !diagnostic.is_error();
+                    writeln!(&mut message, " {}:{}", file_name, diagnostic).unwrap();
+                }
+            }
         } else {
             message.push_str("[LAYOUT DIR]: Not Found\n");
         }
@@ -530,7 +714,7 @@ impl Setup {
 
         std::io::stdout().write_all(message.as_bytes())?;
 
-        Ok(())
+        Ok(well_defined)
     }
     fn generate_completion(shell: &str) {
         let shell: Shell = match shell.to_lowercase().parse() {
@@ -2,6 +2,7 @@
 
 use std::{iter, str::from_utf8};
 
+use crate::consts::TERMINAL_TITLE_TEMPLATE;
 use crate::data::{Palette, PaletteColor, PaletteSource, ThemeHue};
 use crate::envs::get_session_name;
 use colorsys::Rgb;
@@ -51,14 +52,26 @@ pub fn adjust_to_size(s: &str, rows: usize, columns: usize) -> String {
         .join("\n\r")
 }
 
-pub fn make_terminal_title(pane_title: &str) -> String {
-    format!(
-        "\u{1b}]0;Zellij {}- {}\u{07}",
-        get_session_name()
-            .map(|n| format!("({}) ", n))
-            .unwrap_or_default(),
-        pane_title,
-    )
+/// The OSC sequence setting the title of the terminal zellij runs in, made from the
+/// `terminal_title_template` option if it is set
+pub fn make_terminal_title(pane_title: &str, tab_name: &str, command: Option<&str>) -> String {
+    let title = match TERMINAL_TITLE_TEMPLATE.get() {
+        Some(template) => fill_template(template, |placeholder| match placeholder {
+            "session" => Some(get_session_name().unwrap_or_default()),
+            "tab" => Some(tab_name.to_owned()),
+            "pane" => Some(pane_title.to_owned()),
+            "command" => Some(command.unwrap_or_default().to_owned()),
+            _ => None,
+        }),
+        None => format!(
+            "Zellij {}- {}",
+            get_session_name()
+                .map(|n| format!("({}) ", n))
+                .unwrap_or_default(),
+            pane_title,
+        ),
+    };
+    format!("\u{1b}]0;{}\u{07}", title)
 }
 
 // Colors
@@ -149,3 +162,34 @@ pub fn version_number(mut version: &str) -> usize {
 
     version_number
 }
+
+/// Fills in the `{placeholder}`s of a template with the values `value_of` returns for them,
+/// leaving the ones it has no value for as they are. Values are inserted as is, so placeholders
+/// that appear in them are not filled in.
+pub fn fill_template(template: &str, mut value_of: impl FnMut(&str) -> Option<String>) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let after_brace = &rest[start + 1..];
+        let placeholder = after_brace
+            .find(|c| c == '{' || c == '}')
+            .filter(|&end| after_brace[end..].starts_with('}'))
+            .map(|end| &after_brace[..end]);
+        match placeholder {
+            Some(placeholder) => {
+                match value_of(placeholder) {
+                    Some(value) => filled.push_str(&value),
+                    None => filled.push_str(&rest[start..start + placeholder.len() + 2]),
+                }
+                rest = &after_brace[placeholder.len() + 1..];
+            },
+            None => {
+                filled.push('{');
+                rest = after_brace;
+            },
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
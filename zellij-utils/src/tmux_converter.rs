@@ -0,0 +1,996 @@
+//! Converts a tmux configuration file (`tmux.conf`) or a GNU screen one (`screenrc`) into a zellij
+//! configuration and a tmuxinator project into a zellij layout, for `zellij setup --convert-config`
+//! and `--convert-layout`.
+//!
+//! Only what has a zellij equivalent is converted: key bindings of the prefix table (or of
+//! screen's command key) go to the `tmux` mode (which the prefix key switches to), a few options,
+//! and the windows and panes of tmuxinator projects. Everything else is reported back so that it
+//! can be ported by hand.
+use kdl::{KdlDocument, KdlEntry, KdlNode};
+use serde_yaml::Value;
+
+/// The result of a conversion
+#[derive(Debug, Default, Clone)]
+pub struct Conversion {
+    /// The converted KDL document
+    pub kdl: String,
+    /// The directives that could not be converted, with where they were found
+    pub unconverted: Vec<String>,
+}
+
+/// Splits a tmux command line into words, honoring single quotes, double quotes, backslash
+/// escapes and comments
+pub fn split_tmux_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = vec![];
+    let mut current_word: Option<String> = None;
+    let mut quote: Option<char> = None;
+    let mut characters = line.chars();
+    while let Some(character) = characters.next() {
+        match (quote, character) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('\''), c) => current_word.get_or_insert_with(String::new).push(c),
+            (_, '\\') => {
+                let escaped = characters
+                    .next()
+                    .ok_or_else(|| "trailing backslash".to_owned())?;
+                current_word.get_or_insert_with(String::new).push(escaped);
+            },
+            (Some(_), c) => current_word.get_or_insert_with(String::new).push(c),
+            (None, '\'' | '"') => {
+                quote = Some(character);
+                current_word.get_or_insert_with(String::new);
+            },
+            // formats (eg. `#{pane_current_path}`) are not comments
+            (None, '#') if current_word.is_none() && characters.clone().next() != Some('{') => {
+                break
+            },
+            (None, c) if c.is_whitespace() => {
+                if let Some(word) = current_word.take() {
+                    words.push(word);
+                }
+            },
+            (None, c) => current_word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    if quote.is_some() {
+        return Err("unterminated quote".to_owned());
+    }
+    words.extend(current_word);
+    Ok(words)
+}
+
+/// The flags of a tmux command and its other arguments
+pub struct TmuxArgs {
+    pub flags: Vec<(char, Option<String>)>,
+    pub positional: Vec<String>,
+}
+
+impl TmuxArgs {
+    /// `flags_with_values` are the flags that take a value (eg. `-t 1` or `-t1`)
+    pub fn parse(args: &[String], flags_with_values: &str) -> Result<Self, String> {
+        let mut flags = vec![];
+        let mut positional = vec![];
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !positional.is_empty() || arg == "-" || !arg.starts_with('-') {
+                positional.push(arg.clone());
+                continue;
+            }
+            if arg == "--" {
+                positional.extend(args.by_ref().cloned());
+                break;
+            }
+            let mut characters = arg.chars().skip(1);
+            while let Some(flag) = characters.next() {
+                if flags_with_values.contains(flag) {
+                    let rest: String = characters.by_ref().collect();
+                    let value = if rest.is_empty() {
+                        args.next()
+                            .cloned()
+                            .ok_or_else(|| format!("-{} needs a value", flag))?
+                    } else {
+                        rest
+                    };
+                    flags.push((flag, Some(value)));
+                } else {
+                    flags.push((flag, None));
+                }
+            }
+        }
+        Ok(TmuxArgs { flags, positional })
+    }
+    pub fn has(&self, flag: char) -> bool {
+        self.flags.iter().any(|(f, _)| *f == flag)
+    }
+    pub fn value(&self, flag: char) -> Option<&str> {
+        self.flags
+            .iter()
+            .rev()
+            .find(|(f, _)| *f == flag)
+            .and_then(|(_, value)| value.as_deref())
+    }
+    pub fn has_only(&self, allowed_flags: &str) -> bool {
+        self.flags.iter().all(|(f, _)| allowed_flags.contains(*f))
+    }
+}
+
+/// Converts a tmux key name (eg. `C-a`, `M-Left` or `|`) to a zellij one
+fn tmux_key_to_zellij(key: &str) -> Option<String> {
+    if let Some(ctrl_key) = key.strip_prefix("C-").or_else(|| key.strip_prefix('^')) {
+        let mut characters = ctrl_key.chars();
+        return match (characters.next(), characters.next()) {
+            (Some(c), None) if c.is_ascii_alphanumeric() => {
+                Some(format!("Ctrl {}", c.to_ascii_lowercase()))
+            },
+            _ => None,
+        };
+    }
+    if let Some(alt_key) = key.strip_prefix("M-") {
+        return match alt_key {
+            "Left" | "Right" | "Up" | "Down" => Some(format!("Alt {}", alt_key)),
+            _ if alt_key.chars().count() == 1 => Some(format!("Alt {}", alt_key)),
+            _ => None,
+        };
+    }
+    let key = match key {
+        "Enter" | "Tab" | "Space" | "Left" | "Right" | "Up" | "Down" | "Home" | "End" => key,
+        "Escape" => "Esc",
+        "BSpace" => "Backspace",
+        "PPage" | "PageUp" | "PgUp" => "PageUp",
+        "NPage" | "PageDown" | "PgDn" => "PageDown",
+        "DC" => "Delete",
+        "IC" => "Insert",
+        _ if key.chars().count() == 1 => key,
+        _ => {
+            let is_function_key = key
+                .strip_prefix('F')
+                .and_then(|index| index.parse::<u8>().ok())
+                .map(|index| (1..=12).contains(&index))
+                .unwrap_or(false);
+            if is_function_key {
+                key
+            } else {
+                return None;
+            }
+        },
+    };
+    Some(key.to_owned())
+}
+
+fn node_with_arguments(name: &str, arguments: &[&str]) -> KdlNode {
+    let mut node = KdlNode::new(name);
+    for argument in arguments {
+        node.push(*argument);
+    }
+    node
+}
+
+fn bind_node(key: &str, actions: Vec<KdlNode>) -> KdlNode {
+    let mut node = KdlNode::new("bind");
+    node.push(key);
+    node.ensure_children().nodes_mut().extend(actions);
+    node
+}
+
+/// A `bind-key` directive, converted once the whole file is read since options such as
+/// `base-index` change what its command means
+struct TmuxBinding {
+    line: String,
+    line_number: usize,
+    key: String,
+    in_root_table: bool,
+    repeats: bool,
+    command: Vec<String>,
+}
+
+#[derive(Default)]
+struct TmuxConfigConverter {
+    prefixes: Vec<String>,
+    base_index: u32,
+    bindings: Vec<TmuxBinding>,
+    tmux_mode_unbinds: Vec<String>,
+    normal_mode_unbinds: Vec<String>,
+    clear_tmux_mode_defaults: bool,
+    options: Vec<KdlNode>,
+    unconverted: Vec<(usize, String)>,
+    /// The key that sends screen's command key itself (`a` with the default `escape ^Aa`)
+    screen_meta_binding: Option<TmuxBinding>,
+}
+
+impl TmuxConfigConverter {
+    fn report(&mut self, line_number: usize, line: &str) {
+        self.unconverted.push((line_number, line.trim().to_owned()));
+    }
+    fn convert_line(&mut self, line_number: usize, line: &str) {
+        let words = match split_tmux_words(line) {
+            Ok(words) => words,
+            Err(_) => return self.report(line_number, line),
+        };
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (command.as_str(), args),
+            None => return,
+        };
+        let converted = match command {
+            "bind-key" | "bind" => self.convert_bind(line_number, line, args),
+            "unbind-key" | "unbind" => self.convert_unbind(args),
+            "set-option" | "set" | "set-window-option" | "setw" => self.convert_option(args),
+            _ => false,
+        };
+        if !converted {
+            self.report(line_number, line);
+        }
+    }
+    fn convert_bind(&mut self, line_number: usize, line: &str, args: &[String]) -> bool {
+        let args = match TmuxArgs::parse(args, "TN") {
+            Ok(args) => args,
+            Err(_) => return false,
+        };
+        let in_root_table = match (args.has('n'), args.value('T')) {
+            (true, _) | (false, Some("root")) => true,
+            (false, Some("prefix")) | (false, None) => false,
+            (false, Some(_)) => return false,
+        };
+        let (key, command) = match args.positional.split_first() {
+            Some((key, command)) if !command.is_empty() => (key, command),
+            _ => return false,
+        };
+        self.bindings.push(TmuxBinding {
+            line: line.to_owned(),
+            line_number,
+            key: key.clone(),
+            in_root_table,
+            repeats: args.has('r'),
+            command: command.to_vec(),
+        });
+        true
+    }
+    fn convert_unbind(&mut self, args: &[String]) -> bool {
+        let args = match TmuxArgs::parse(args, "T") {
+            Ok(args) => args,
+            Err(_) => return false,
+        };
+        let in_root_table = match (args.has('n'), args.value('T')) {
+            (true, _) | (false, Some("root")) => true,
+            (false, Some("prefix")) | (false, None) => false,
+            (false, Some(_)) => return false,
+        };
+        if args.has('a') && !in_root_table {
+            self.clear_tmux_mode_defaults = true;
+            return true;
+        }
+        let key = match args.positional.first().and_then(|k| tmux_key_to_zellij(k)) {
+            Some(key) => key,
+            None => return false,
+        };
+        if in_root_table {
+            self.normal_mode_unbinds.push(key);
+        } else {
+            self.tmux_mode_unbinds.push(key);
+        }
+        true
+    }
+    fn convert_option(&mut self, args: &[String]) -> bool {
+        let args = match TmuxArgs::parse(args, "t") {
+            Ok(args) => args,
+            Err(_) => return false,
+        };
+        if !args.has_only("gsqwot") {
+            return false;
+        }
+        let (name, value) = match args.positional.as_slice() {
+            [name, value] => (name.as_str(), value.as_str()),
+            _ => return false,
+        };
+        match name {
+            "prefix" | "prefix2" => match tmux_key_to_zellij(value) {
+                Some(key) => {
+                    if name == "prefix" {
+                        self.prefixes.insert(0, key);
+                    } else {
+                        self.prefixes.push(key);
+                    }
+                    true
+                },
+                None => false,
+            },
+            "base-index" => match value.parse() {
+                Ok(base_index) => {
+                    self.base_index = base_index;
+                    true
+                },
+                Err(_) => false,
+            },
+            "mouse" => match value {
+                "on" | "off" => {
+                    self.options.push(option_node("mouse_mode", value == "on"));
+                    true
+                },
+                _ => false,
+            },
+            "history-limit" => match value.parse::<i64>() {
+                Ok(limit) => {
+                    self.options.push(option_node("scroll_buffer_size", limit));
+                    true
+                },
+                Err(_) => false,
+            },
+            "default-shell" => {
+                self.options
+                    .push(option_node("default_shell", value.to_owned()));
+                true
+            },
+            _ => false,
+        }
+    }
+    fn convert_screen_line(&mut self, line_number: usize, line: &str) {
+        let words = match split_tmux_words(line) {
+            Ok(words) => words,
+            Err(_) => return self.report(line_number, line),
+        };
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (command.as_str(), args),
+            None => return,
+        };
+        let converted = match (command, args) {
+            ("escape", [keys]) => self.convert_screen_escape(line_number, line, keys),
+            ("bind", args) => self.convert_screen_bind(line_number, line, args),
+            ("defscrollback", [limit]) => match limit.parse::<i64>() {
+                Ok(limit) => {
+                    self.options.push(option_node("scroll_buffer_size", limit));
+                    true
+                },
+                Err(_) => false,
+            },
+            ("mousetrack", [value]) if value == "on" || value == "off" => {
+                self.options.push(option_node("mouse_mode", value == "on"));
+                true
+            },
+            ("shell", [shell]) => {
+                // a leading dash makes it a login shell
+                let shell = shell.strip_prefix('-').unwrap_or(shell);
+                self.options
+                    .push(option_node("default_shell", shell.to_owned()));
+                true
+            },
+            _ => false,
+        };
+        if !converted {
+            self.report(line_number, line);
+        }
+    }
+    /// `escape ^Aa` makes `Ctrl a` the command key and `a` (pressed after it) send `Ctrl a`
+    fn convert_screen_escape(&mut self, line_number: usize, line: &str, keys: &str) -> bool {
+        let (command_key, meta_key) = match split_screen_keys(keys).as_slice() {
+            [command_key, meta_key] => (command_key.clone(), meta_key.clone()),
+            _ => return false,
+        };
+        let command_key = match tmux_key_to_zellij(&command_key) {
+            Some(command_key) => command_key,
+            None => return false,
+        };
+        self.prefixes = vec![command_key];
+        self.screen_meta_binding = Some(TmuxBinding {
+            line: line.to_owned(),
+            line_number,
+            key: meta_key,
+            in_root_table: false,
+            repeats: false,
+            command: vec!["send-prefix".to_owned()],
+        });
+        true
+    }
+    fn convert_screen_bind(&mut self, line_number: usize, line: &str, args: &[String]) -> bool {
+        // `-c` binds keys in a command class other than the one of the command key
+        let (key, command) = match args.split_first() {
+            Some((key, command)) if key == "-" || !key.starts_with('-') => (key, command),
+            _ => return false,
+        };
+        if command.is_empty() {
+            return match tmux_key_to_zellij(key) {
+                Some(key) => {
+                    self.tmux_mode_unbinds.push(key);
+                    true
+                },
+                None => false,
+            };
+        }
+        match screen_command_to_tmux(command) {
+            Some(command) => {
+                self.bindings.push(TmuxBinding {
+                    line: line.to_owned(),
+                    line_number,
+                    key: key.clone(),
+                    in_root_table: false,
+                    repeats: false,
+                    command,
+                });
+                true
+            },
+            None => false,
+        }
+    }
+    /// The zellij actions a tmux command (or a `;` separated list of them) runs, `None` if any of
+    /// them can't be converted
+    fn convert_command(&self, command: &[String]) -> Option<Vec<KdlNode>> {
+        let mut actions = vec![];
+        for command in command.split(|word| word == ";") {
+            let (name, args) = command.split_first()?;
+            let flags_with_values = match name.as_str() {
+                "new-window" | "neww" => "cnt",
+                "split-window" | "splitw" => "clpt",
+                "resize-pane" | "resizep" => "txy",
+                _ => "t",
+            };
+            let args = TmuxArgs::parse(args, flags_with_values).ok()?;
+            let keeps_cwd = args
+                .value('c')
+                .map(|cwd| cwd == "#{pane_current_path}")
+                .unwrap_or(true);
+            let action = match name.as_str() {
+                "split-window" | "splitw" if args.has_only("hvc") && keeps_cwd => {
+                    if !args.positional.is_empty() {
+                        return None;
+                    }
+                    let direction = if args.has('h') { "Right" } else { "Down" };
+                    node_with_arguments("NewPane", &[direction])
+                },
+                "new-window" | "neww" if args.has_only("cn") && keeps_cwd => {
+                    if !args.positional.is_empty() {
+                        return None;
+                    }
+                    let mut new_tab = node_with_arguments("NewTab", &[]);
+                    if let Some(name) = args.value('n') {
+                        let mut name_node = KdlNode::new("name");
+                        name_node.push(name);
+                        new_tab.ensure_children().nodes_mut().push(name_node);
+                    }
+                    new_tab
+                },
+                "kill-pane" | "killp" if args.flags.is_empty() => {
+                    node_with_arguments("CloseFocus", &[])
+                },
+                "kill-window" | "killw" if args.flags.is_empty() => {
+                    node_with_arguments("CloseTab", &[])
+                },
+                "select-pane" | "selectp" => match direction_flag(&args) {
+                    Some(direction) => node_with_arguments("MoveFocus", &[direction]),
+                    None if args.value('t') == Some(":.+") => {
+                        node_with_arguments("FocusNextPane", &[])
+                    },
+                    None if args.value('t') == Some(":.-") => {
+                        node_with_arguments("FocusPreviousPane", &[])
+                    },
+                    None => return None,
+                },
+                "resize-pane" | "resizep" if args.has('Z') => {
+                    node_with_arguments("ToggleFocusFullscreen", &[])
+                },
+                "resize-pane" | "resizep" => {
+                    let direction = direction_flag(&args)?;
+                    node_with_arguments("Resize", &[format!("Increase {}", direction).as_str()])
+                },
+                "next-window" | "next" => node_with_arguments("GoToNextTab", &[]),
+                "previous-window" | "prev" => node_with_arguments("GoToPreviousTab", &[]),
+                "last-window" | "last" => node_with_arguments("ToggleTab", &[]),
+                "select-window" | "selectw" if args.has('n') => {
+                    node_with_arguments("GoToNextTab", &[])
+                },
+                "select-window" | "selectw" if args.has('p') => {
+                    node_with_arguments("GoToPreviousTab", &[])
+                },
+                "select-window" | "selectw" if args.has('l') => {
+                    node_with_arguments("ToggleTab", &[])
+                },
+                "select-window" | "selectw" => {
+                    let index: u32 = args
+                        .value('t')?
+                        .trim_start_matches(&[':', '='][..])
+                        .parse()
+                        .ok()?;
+                    let tab_index = index.checked_sub(self.base_index)? + 1;
+                    let mut go_to_tab = KdlNode::new("GoToTab");
+                    go_to_tab.push(tab_index as i64);
+                    go_to_tab
+                },
+                "next-layout" | "nextl" => node_with_arguments("NextSwapLayout", &[]),
+                "break-pane" | "breakp" => node_with_arguments("BreakPane", &[]),
+                "detach-client" | "detach" => node_with_arguments("Detach", &[]),
+                "copy-mode" => node_with_arguments("SwitchToMode", &["Scroll"]),
+                "command-prompt" if command.iter().any(|word| word.contains("rename-window")) => {
+                    node_with_arguments("SwitchToMode", &["RenameTab"])
+                },
+                "send-prefix" => {
+                    let prefix = self
+                        .prefixes
+                        .first()
+                        .map(|prefix| prefix.as_str())
+                        .unwrap_or("Ctrl b");
+                    let byte = prefix
+                        .strip_prefix("Ctrl ")
+                        .and_then(|key| key.chars().next())
+                        .filter(|key| key.is_ascii_lowercase())
+                        .map(|key| key as u8 - b'a' + 1)?;
+                    let mut write = KdlNode::new("Write");
+                    write.push(byte as i64);
+                    write
+                },
+                _ => return None,
+            };
+            actions.push(action);
+        }
+        Some(actions)
+    }
+    fn into_conversion(mut self) -> Conversion {
+        let mut normal_mode = KdlDocument::new();
+        let mut tmux_mode = KdlDocument::new();
+        for binding in std::mem::take(&mut self.bindings) {
+            let key = tmux_key_to_zellij(&binding.key);
+            let actions = self.convert_command(&binding.command);
+            let (key, mut actions) = match (key, actions) {
+                (Some(key), Some(actions)) => (key, actions),
+                _ => {
+                    self.report(binding.line_number, &binding.line);
+                    continue;
+                },
+            };
+            if binding.in_root_table {
+                normal_mode.nodes_mut().push(bind_node(&key, actions));
+                continue;
+            }
+            let leaves_tmux_mode = actions
+                .iter()
+                .any(|a| a.name().value() == "SwitchToMode" || a.name().value() == "Detach");
+            // like tmux, go back to normal mode after running the command unless it repeats
+            if !binding.repeats && !leaves_tmux_mode {
+                actions.push(node_with_arguments("SwitchToMode", &["Normal"]));
+            }
+            tmux_mode.nodes_mut().push(bind_node(&key, actions));
+        }
+        for key in &self.normal_mode_unbinds {
+            normal_mode
+                .nodes_mut()
+                .push(node_with_arguments("unbind", &[key.as_str()]));
+        }
+        for key in &self.tmux_mode_unbinds {
+            tmux_mode
+                .nodes_mut()
+                .push(node_with_arguments("unbind", &[key.as_str()]));
+        }
+
+        let mut keybinds = KdlDocument::new();
+        if !self.prefixes.is_empty() {
+            let mut prefixes = KdlNode::new("shared_except");
+            prefixes.push("tmux");
+            prefixes.push("locked");
+            for prefix in &self.prefixes {
+                prefixes.ensure_children().nodes_mut().push(bind_node(
+                    prefix,
+                    vec![node_with_arguments("SwitchToMode", &["Tmux"])],
+                ));
+            }
+            if !self.prefixes.iter().any(|p| p == "Ctrl b") {
+                prefixes
+                    .ensure_children()
+                    .nodes_mut()
+                    .push(node_with_arguments("unbind", &["Ctrl b"]));
+            }
+            keybinds.nodes_mut().push(prefixes);
+        }
+        if !normal_mode.nodes().is_empty() {
+            let mut normal_mode_node = KdlNode::new("normal");
+            normal_mode_node.set_children(normal_mode);
+            keybinds.nodes_mut().push(normal_mode_node);
+        }
+        if !tmux_mode.nodes().is_empty() || self.clear_tmux_mode_defaults {
+            let mut tmux_mode_node = KdlNode::new("tmux");
+            if self.clear_tmux_mode_defaults {
+                tmux_mode_node.push(KdlEntry::new_prop("clear-defaults", true));
+            }
+            tmux_mode_node.set_children(tmux_mode);
+            keybinds.nodes_mut().push(tmux_mode_node);
+        }
+
+        let mut config = KdlDocument::new();
+        if !keybinds.nodes().is_empty() {
+            let mut keybinds_node = KdlNode::new("keybinds");
+            keybinds_node.set_children(keybinds);
+            config.nodes_mut().push(keybinds_node);
+        }
+        config.nodes_mut().extend(self.options);
+        config.fmt();
+        // bindings are reported after the other lines, put them back in order
+        self.unconverted
+            .sort_by_key(|(line_number, _)| *line_number);
+        Conversion {
+            kdl: config.to_string(),
+            unconverted: self
+                .unconverted
+                .into_iter()
+                .map(|(line_number, line)| format!("line {}: {}", line_number, line))
+                .collect(),
+        }
+    }
+}
+
+fn option_node(name: &str, value: impl Into<KdlEntry>) -> KdlNode {
+    let mut node = KdlNode::new(name);
+    node.push(value);
+    node
+}
+
+fn direction_flag(args: &TmuxArgs) -> Option<&'static str> {
+    [('L', "Left"), ('R', "Right"), ('U', "Up"), ('D', "Down")]
+        .iter()
+        .find(|(flag, _)| args.has(*flag))
+        .map(|(_, direction)| *direction)
+}
+
+/// Splits screen's key notation (eg. `^Aa`) into keys in tmux's notation (eg. `^A` and `a`)
+fn split_screen_keys(keys: &str) -> Vec<String> {
+    let mut split_keys = vec![];
+    let mut characters = keys.chars();
+    while let Some(character) = characters.next() {
+        match (character, characters.clone().next()) {
+            ('^', Some(ctrl_key)) => {
+                characters.next();
+                split_keys.push(format!("^{}", ctrl_key));
+            },
+            _ => split_keys.push(character.to_string()),
+        }
+    }
+    split_keys
+}
+
+/// The tmux command doing what a screen command does, so that it can be converted like a tmux
+/// binding. Screen's windows are treated as tmux windows (zellij tabs) and its regions as panes
+fn screen_command_to_tmux(command: &[String]) -> Option<Vec<String>> {
+    let (name, args) = command.split_first()?;
+    let args: Vec<&str> = args.iter().map(|arg| arg.as_str()).collect();
+    let tmux_command: &[&str] = match (name.as_str(), args.as_slice()) {
+        ("screen", []) => &["new-window"],
+        ("split", []) | ("split", ["-h"]) => &["split-window", "-v"],
+        ("split", ["-v"]) => &["split-window", "-h"],
+        ("remove", []) => &["kill-pane"],
+        ("kill", []) => &["kill-window"],
+        ("focus", []) | ("focus", ["next"]) => &["select-pane", "-t", ":.+"],
+        ("focus", ["prev"]) => &["select-pane", "-t", ":.-"],
+        ("focus", ["left"]) => &["select-pane", "-L"],
+        ("focus", ["right"]) => &["select-pane", "-R"],
+        ("focus", ["up"]) => &["select-pane", "-U"],
+        ("focus", ["down"]) => &["select-pane", "-D"],
+        ("next", []) => &["next-window"],
+        ("prev", []) => &["previous-window"],
+        ("other", []) => &["last-window"],
+        ("select", [window]) => {
+            return Some(vec![
+                "select-window".to_owned(),
+                "-t".to_owned(),
+                window.to_string(),
+            ])
+        },
+        ("title", []) => &["command-prompt", "rename-window '%%'"],
+        ("layout", ["next"]) => &["next-layout"],
+        ("copy", []) => &["copy-mode"],
+        ("detach", []) => &["detach-client"],
+        ("meta", []) => &["send-prefix"],
+        _ => return None,
+    };
+    Some(tmux_command.iter().map(|word| word.to_string()).collect())
+}
+
+/// Joins lines ending with a backslash with the next one, along with the number of the first
+fn logical_lines(config: &str) -> Vec<(usize, String)> {
+    let mut logical_lines = vec![];
+    let mut line = String::new();
+    let mut first_line_number = 0;
+    for (index, physical_line) in config.lines().enumerate() {
+        if line.is_empty() {
+            first_line_number = index + 1;
+        }
+        match physical_line.strip_suffix('\\') {
+            Some(continued_line) if !continued_line.ends_with('\\') => {
+                line.push_str(continued_line);
+                line.push(' ');
+            },
+            _ => {
+                line.push_str(physical_line);
+                logical_lines.push((first_line_number, std::mem::take(&mut line)));
+            },
+        }
+    }
+    if !line.is_empty() {
+        logical_lines.push((first_line_number, line));
+    }
+    logical_lines
+}
+
+/// Converts the contents of a `tmux.conf` into a zellij configuration
+pub fn convert_tmux_config(tmux_config: &str) -> Conversion {
+    let mut converter = TmuxConfigConverter::default();
+    for (line_number, line) in logical_lines(tmux_config) {
+        converter.convert_line(line_number, &line);
+    }
+    converter.into_conversion()
+}
+
+/// Converts the contents of a GNU screen `screenrc` into a zellij configuration, screen's command
+/// key (`Ctrl a` unless changed with `escape`) switches to the `tmux` mode like tmux's prefix
+pub fn convert_screen_config(screen_config: &str) -> Conversion {
+    let mut converter = TmuxConfigConverter::default();
+    converter.convert_screen_escape(0, "escape ^Aa", "^Aa");
+    for (line_number, line) in logical_lines(screen_config) {
+        converter.convert_screen_line(line_number, &line);
+    }
+    // bound first, so that bindings of the same key in the screenrc take precedence
+    if let Some(meta_binding) = converter.screen_meta_binding.take() {
+        converter.bindings.insert(0, meta_binding);
+    }
+    converter.into_conversion()
+}
+
+/// Converts a tmuxinator project (YAML) into a zellij layout
+pub fn convert_tmuxinator_project(project: &str) -> Result<Conversion, String> {
+    let project: Value = serde_yaml::from_str(project)
+        .map_err(|e| format!("Failed to parse tmuxinator project: {}", e))?;
+    let project = project
+        .as_mapping()
+        .ok_or_else(|| "A tmuxinator project should be a mapping".to_owned())?;
+    let mut unconverted = vec![];
+    let mut root = None;
+    let mut pre_window = vec![];
+    let mut startup_window = None;
+    let mut windows = None;
+    for (key, value) in project {
+        match key.as_str() {
+            // the name of the project becomes that of the layout file
+            Some("name") | Some("project_name") => {},
+            Some("root") | Some("project_root") => {
+                root = value.as_str().map(|root| root.to_owned())
+            },
+            Some("pre_window") | Some("pre_tab") | Some("rbenv") | Some("rvm") => {
+                pre_window.extend(yaml_commands(value))
+            },
+            Some("startup_window") => startup_window = yaml_scalar_to_string(value),
+            Some("windows") | Some("tabs") => windows = value.as_sequence(),
+            _ => unconverted.push(format!(
+                "{}: {}",
+                yaml_to_string(key),
+                yaml_to_string(value)
+            )),
+        }
+    }
+    let windows = windows.ok_or_else(|| "The tmuxinator project has no windows".to_owned())?;
+
+    let mut layout = KdlDocument::new();
+    if let Some(root) = root {
+        layout
+            .nodes_mut()
+            .push(node_with_arguments("cwd", &[root.as_str()]));
+    }
+    layout.nodes_mut().push(default_tab_template());
+    for (index, window) in windows.iter().enumerate() {
+        let (name, window) = match window.as_mapping().and_then(|w| w.iter().next()) {
+            Some((name, window)) => (yaml_to_string(name), window),
+            None => {
+                unconverted.push(format!("windows: {}", yaml_to_string(window)));
+                continue;
+            },
+        };
+        let mut tab = KdlNode::new("tab");
+        tab.push(KdlEntry::new_prop("name", name.clone()));
+        let is_startup_window = startup_window
+            .as_ref()
+            .map(|w| *w == name || *w == index.to_string())
+            .unwrap_or(false);
+        if is_startup_window {
+            tab.push(KdlEntry::new_prop("focus", true));
+        }
+        let mut window_pre = pre_window.clone();
+        let mut window_layout = None;
+        let mut panes = vec![];
+        match window {
+            Value::Mapping(options) => {
+                for (key, value) in options {
+                    match key.as_str() {
+                        Some("root") => {
+                            if let Some(root) = value.as_str() {
+                                tab.push(KdlEntry::new_prop("cwd", root));
+                            }
+                        },
+                        Some("pre") => window_pre.extend(yaml_commands(value)),
+                        Some("layout") => window_layout = value.as_str(),
+                        Some("panes") => {
+                            for pane in value.as_sequence().into_iter().flatten() {
+                                panes.push(pane_node(pane, &window_pre));
+                            }
+                        },
+                        _ => unconverted.push(format!(
+                            "{}.{}: {}",
+                            name,
+                            yaml_to_string(key),
+                            yaml_to_string(value)
+                        )),
+                    }
+                }
+            },
+            _ => panes.push(pane_node(window, &window_pre)),
+        }
+        if panes.is_empty() {
+            panes.push(pane_node(&Value::Null, &window_pre));
+        }
+        let arrangement = match window_layout {
+            None => PaneArrangement::EvenVertical,
+            Some(window_layout) => {
+                PaneArrangement::from_tmux_layout(window_layout).unwrap_or_else(|| {
+                    unconverted.push(format!("{}.layout: {}", name, window_layout));
+                    PaneArrangement::EvenVertical
+                })
+            },
+        };
+        let (split_direction, panes) = arrangement.arrange(panes);
+        if let Some(split_direction) = split_direction {
+            tab.push(KdlEntry::new_prop("split_direction", split_direction));
+        }
+        tab.ensure_children().nodes_mut().extend(panes);
+        layout.nodes_mut().push(tab);
+    }
+
+    let mut layout_node = KdlNode::new("layout");
+    layout_node.set_children(layout);
+    let mut kdl_document = KdlDocument::new();
+    kdl_document.nodes_mut().push(layout_node);
+    kdl_document.fmt();
+    Ok(Conversion {
+        kdl: kdl_document.to_string(),
+        unconverted,
+    })
+}
+
+/// How tmux lays out the panes of a window
+enum PaneArrangement {
+    EvenHorizontal,
+    EvenVertical,
+    MainHorizontal,
+    MainVertical,
+    Tiled,
+}
+
+impl PaneArrangement {
+    fn from_tmux_layout(layout: &str) -> Option<Self> {
+        match layout {
+            "even-horizontal" => Some(PaneArrangement::EvenHorizontal),
+            "even-vertical" => Some(PaneArrangement::EvenVertical),
+            "main-horizontal" => Some(PaneArrangement::MainHorizontal),
+            "main-vertical" => Some(PaneArrangement::MainVertical),
+            "tiled" => Some(PaneArrangement::Tiled),
+            _ => None,
+        }
+    }
+    /// Returns the split direction of the tab and its children
+    fn arrange(&self, mut panes: Vec<KdlNode>) -> (Option<&'static str>, Vec<KdlNode>) {
+        if panes.len() < 2 {
+            return (None, panes);
+        }
+        match self {
+            PaneArrangement::EvenHorizontal => (Some("vertical"), panes),
+            PaneArrangement::EvenVertical => (None, panes),
+            PaneArrangement::MainHorizontal | PaneArrangement::MainVertical => {
+                let main_pane = panes.remove(0);
+                let mut other_panes = KdlNode::new("pane");
+                if let PaneArrangement::MainHorizontal = self {
+                    other_panes.push(KdlEntry::new_prop("split_direction", "vertical"));
+                }
+                other_panes.ensure_children().nodes_mut().extend(panes);
+                let split_direction = match self {
+                    PaneArrangement::MainVertical => Some("vertical"),
+                    _ => None,
+                };
+                (split_direction, vec![main_pane, other_panes])
+            },
+            PaneArrangement::Tiled => {
+                let columns = (panes.len() as f64).sqrt().ceil() as usize;
+                let mut rows = vec![];
+                while !panes.is_empty() {
+                    let row_panes: Vec<KdlNode> = panes.drain(..columns.min(panes.len())).collect();
+                    let mut row = KdlNode::new("pane");
+                    row.push(KdlEntry::new_prop("split_direction", "vertical"));
+                    row.ensure_children().nodes_mut().extend(row_panes);
+                    rows.push(row);
+                }
+                (None, rows)
+            },
+        }
+    }
+}
+
+/// The tab-bar and status-bar of the default layout around each tab
+fn default_tab_template() -> KdlNode {
+    let plugin_pane = |size: i64, location: &str| {
+        let mut pane = KdlNode::new("pane");
+        pane.push(KdlEntry::new_prop("size", size));
+        pane.push(KdlEntry::new_prop("borderless", true));
+        let mut plugin = KdlNode::new("plugin");
+        plugin.push(KdlEntry::new_prop("location", location));
+        pane.ensure_children().nodes_mut().push(plugin);
+        pane
+    };
+    let mut template = KdlNode::new("default_tab_template");
+    let children = template.ensure_children().nodes_mut();
+    children.push(plugin_pane(1, "zellij:tab-bar"));
+    children.push(KdlNode::new("children"));
+    children.push(plugin_pane(2, "zellij:status-bar"));
+    template
+}
+
+/// A pane is either empty, a command, a list of commands or a mapping of its name to either
+fn pane_node(pane: &Value, pre_commands: &[String]) -> KdlNode {
+    let mut node = KdlNode::new("pane");
+    let commands = match pane.as_mapping().and_then(|p| p.iter().next()) {
+        Some((name, commands)) => {
+            node.push(KdlEntry::new_prop("name", yaml_to_string(name)));
+            yaml_commands(commands)
+        },
+        None => yaml_commands(pane),
+    };
+    if commands.is_empty() {
+        return node;
+    }
+    let commands: Vec<String> = pre_commands
+        .iter()
+        .chain(commands.iter())
+        .cloned()
+        .collect();
+    let is_simple_command = commands.len() == 1
+        && !commands[0]
+            .chars()
+            .any(|c| "|&;<>()$`\\\"'*?~{}".contains(c));
+    let (command, args) = if is_simple_command {
+        let mut words = commands[0].split_whitespace().map(|w| w.to_owned());
+        (words.next().unwrap_or_default(), words.collect())
+    } else {
+        // commands with shell syntax (or several commands) are run by a shell, like tmuxinator
+        // types them into one
+        ("sh".to_owned(), vec!["-c".to_owned(), commands.join("; ")])
+    };
+    node.push(KdlEntry::new_prop("command", command));
+    if !args.is_empty() {
+        let mut args_node = KdlNode::new("args");
+        for arg in args {
+            args_node.push(arg);
+        }
+        node.ensure_children().nodes_mut().push(args_node);
+    }
+    node
+}
+
+fn yaml_commands(value: &Value) -> Vec<String> {
+    let commands: Vec<String> = match value {
+        Value::Sequence(commands) => commands.iter().filter_map(yaml_scalar_to_string).collect(),
+        _ => yaml_scalar_to_string(value).into_iter().collect(),
+    };
+    commands
+        .into_iter()
+        .filter(|command| !command.trim().is_empty())
+        .collect()
+}
+
+fn yaml_scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(string) => Some(string.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        Value::Bool(boolean) => Some(boolean.to_string()),
+        _ => None,
+    }
+}
+
+fn yaml_to_string(value: &Value) -> String {
+    yaml_scalar_to_string(value).unwrap_or_else(|| {
+        serde_yaml::to_string(value)
+            .map(|yaml| yaml.trim_start_matches("---").trim().to_owned())
+            .unwrap_or_default()
+    })
+}
+
+#[cfg(test)]
+#[path = "./unit/tmux_converter_test.rs"]
+mod tmux_converter_test;
@@ -0,0 +1,208 @@
+use super::{convert_screen_config, convert_tmux_config, convert_tmuxinator_project};
+use crate::data::{Direction, InputMode, Key, Resize};
+use crate::input::actions::Action;
+use crate::input::config::Config;
+use crate::input::layout::Layout;
+
+#[test]
+fn converts_tmux_keybindings_and_options() {
+    let tmux_config = r##"
+            # use Ctrl a as the prefix
+            unbind C-b
+            set -g prefix C-a
+            bind C-a send-prefix
+            set -g base-index 1
+            set -g mouse off
+            set-option -g history-limit 50000
+            bind | split-window -h -c "#{pane_current_path}"
+            bind - split-window -v
+            bind -r H resize-pane -L 5
+            bind 1 select-window -t :1
+            bind -n M-h select-pane -L
+            bind r source-file ~/.tmux.conf \; display "Reloaded"
+            set -g status-position top
+            bind-key -T copy-mode-vi v send-keys -X begin-selection
+        "##;
+    let conversion = convert_tmux_config(tmux_config);
+    assert_eq!(
+        conversion.unconverted,
+        vec![
+            "line 14: bind r source-file ~/.tmux.conf \\; display \"Reloaded\"",
+            "line 15: set -g status-position top",
+            "line 16: bind-key -T copy-mode-vi v send-keys -X begin-selection",
+        ]
+    );
+    let config = Config::from_kdl(&conversion.kdl, None).unwrap();
+    let keybinds = &config.keybinds;
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('a')),
+        Some(&vec![Action::SwitchToMode(InputMode::Tmux)]),
+        "prefix switches to tmux mode"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('b')),
+        None,
+        "default prefix is unbound"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Ctrl('a')),
+        Some(&vec![
+            Action::Write(vec![1]),
+            Action::SwitchToMode(InputMode::Normal)
+        ])
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('|')),
+        Some(&vec![
+            Action::NewPane(Some(Direction::Right), None),
+            Action::SwitchToMode(InputMode::Normal)
+        ])
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('H')),
+        Some(&vec![Action::Resize(
+            Resize::Increase,
+            Some(Direction::Left)
+        )]),
+        "repeatable bindings stay in tmux mode"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('1')),
+        Some(&vec![
+            Action::GoToTab(1),
+            Action::SwitchToMode(InputMode::Normal)
+        ]),
+        "window indices start at base-index"
+    );
+    assert_eq!(config.options.mouse_mode, Some(false));
+    assert_eq!(config.options.scroll_buffer_size, Some(50000));
+}
+
+#[test]
+fn converts_tmuxinator_project() {
+    let project = r##"
+name: blog
+root: ~/code/blog
+startup_window: editor
+on_project_start: docker compose up -d
+windows:
+  - editor:
+      layout: main-vertical
+      panes:
+        - vim
+        - guard
+        - tail -f log/development.log | grep ERROR
+  - server: bundle exec rails s
+  - shell:
+  - workers:
+      layout: tiled
+      panes:
+        - queue:
+          - cd workers
+          - ./run queue
+        - ./run mailer
+        -
+        "##;
+    let conversion = convert_tmuxinator_project(project).unwrap();
+    assert_eq!(
+        conversion.unconverted,
+        vec!["on_project_start: docker compose up -d"]
+    );
+    let layout = Layout::from_str(&conversion.kdl, "blog.kdl".into(), None, None).unwrap();
+    let tab_names: Vec<Option<String>> =
+        layout.tabs().into_iter().map(|(name, _, _)| name).collect();
+    assert_eq!(
+        tab_names,
+        vec![
+            Some("editor".to_owned()),
+            Some("server".to_owned()),
+            Some("shell".to_owned()),
+            Some("workers".to_owned()),
+        ]
+    );
+    assert!(conversion.kdl.contains(r#"command="bundle" {"#));
+    assert!(conversion
+        .kdl
+        .contains(r#"args "-c" "cd workers; ./run queue""#));
+}
+
+#[test]
+fn converts_screen_config() {
+    let screen_config = r##"
+        startup_message off
+        escape ^Bb
+        defscrollback 10000
+        shell -/bin/zsh
+        bind | split -v
+        bind - split
+        bind j focus down
+        bind x remove
+        bind c
+        bind 0 select 0
+        bind A title
+        bind -c resize j resize +1
+        hardstatus alwayslastline
+    "##;
+    let conversion = convert_screen_config(screen_config);
+    assert_eq!(
+        conversion.unconverted,
+        vec![
+            "line 2: startup_message off",
+            "line 13: bind -c resize j resize +1",
+            "line 14: hardstatus alwayslastline",
+        ]
+    );
+    let config = Config::from_kdl(&conversion.kdl, None).unwrap();
+    let keybinds = &config.keybinds;
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Normal, &Key::Ctrl('b')),
+        Some(&vec![Action::SwitchToMode(InputMode::Tmux)]),
+        "escape key switches to tmux mode"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('b')),
+        Some(&vec![
+            Action::Write(vec![2]),
+            Action::SwitchToMode(InputMode::Normal)
+        ]),
+        "meta key sends the escape key"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('a')),
+        None,
+        "default meta key is not bound once escape is changed"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('|')),
+        Some(&vec![
+            Action::NewPane(Some(Direction::Right), None),
+            Action::SwitchToMode(InputMode::Normal)
+        ])
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('j')),
+        Some(&vec![
+            Action::MoveFocus(Direction::Down),
+            Action::SwitchToMode(InputMode::Normal)
+        ])
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('0')),
+        Some(&vec![
+            Action::GoToTab(1),
+            Action::SwitchToMode(InputMode::Normal)
+        ]),
+        "screen windows are numbered from 0"
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('A')),
+        Some(&vec![Action::SwitchToMode(InputMode::RenameTab)])
+    );
+    assert_eq!(
+        keybinds.get_actions_for_key_in_mode(&InputMode::Tmux, &Key::Char('c')),
+        None,
+        "bind without a command unbinds the key"
+    );
+    assert_eq!(config.options.scroll_buffer_size, Some(10000));
+    assert_eq!(config.options.default_shell, Some("/bin/zsh".into()));
+}